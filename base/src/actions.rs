@@ -1,33 +1,38 @@
 use crate::cf_types::{CfRule, Cfvo};
 use crate::constants::{LAST_COLUMN, LAST_ROW};
 use crate::cut_paste::cf_sqref_anchor;
+use crate::expressions::lexer::LexerMode;
 use crate::expressions::parser::stringify::{
     to_localized_string, to_string_displaced, DisplaceData,
 };
-use crate::expressions::parser::Parser as ExprParser;
-use crate::expressions::types::CellReferenceRC;
+use crate::expressions::parser::{parse_range, Parser as ExprParser};
+use crate::expressions::types::{Area, CellReferenceRC};
 use crate::expressions::utils;
 use crate::model::{CellStructure, Model};
-use crate::types::{ArrayKind, Cell};
+use crate::types::{ArrayKind, Cell, ShiftDirection};
+
+/// Returns the new index after inserting (`delta > 0`) or deleting (`delta < 0`) at `start`,
+/// or `None` if the index was deleted.
+fn displace_index(index: i32, start: i32, delta: i32) -> Option<i32> {
+    if index >= start {
+        if delta < 0 && index < start - delta {
+            None
+        } else {
+            Some(index + delta)
+        }
+    } else {
+        Some(index)
+    }
+}
 
 /// Returns the new row after displacement, or `None` if the row was deleted.
-fn displace_cf_row(row: i32, data: &DisplaceData, sheet: u32) -> Option<i32> {
+pub(crate) fn displace_cf_row(row: i32, data: &DisplaceData, sheet: u32) -> Option<i32> {
     match data {
         DisplaceData::Row {
             sheet: s,
             row: dr,
             delta,
-        } if *s == sheet => {
-            if row >= *dr {
-                if *delta < 0 && row < *dr - *delta {
-                    None
-                } else {
-                    Some(row + *delta)
-                }
-            } else {
-                Some(row)
-            }
-        }
+        } if *s == sheet => displace_index(row, *dr, *delta),
         DisplaceData::RowMove {
             sheet: s,
             row: mr,
@@ -48,23 +53,13 @@ fn displace_cf_row(row: i32, data: &DisplaceData, sheet: u32) -> Option<i32> {
 }
 
 /// Returns the new column after displacement, or `None` if the column was deleted.
-fn displace_cf_col(col: i32, data: &DisplaceData, sheet: u32) -> Option<i32> {
+pub(crate) fn displace_cf_col(col: i32, data: &DisplaceData, sheet: u32) -> Option<i32> {
     match data {
         DisplaceData::Column {
             sheet: s,
             column: dc,
             delta,
-        } if *s == sheet => {
-            if col >= *dc {
-                if *delta < 0 && col < *dc - *delta {
-                    None
-                } else {
-                    Some(col + *delta)
-                }
-            } else {
-                Some(col)
-            }
-        }
+        } if *s == sheet => displace_index(col, *dc, *delta),
         DisplaceData::ColumnMove {
             sheet: s,
             column: mc,
@@ -84,6 +79,47 @@ fn displace_cf_col(col: i32, data: &DisplaceData, sheet: u32) -> Option<i32> {
     }
 }
 
+/// Returns the new (row, column) of a cell after displacement, or `None` if the cell was deleted.
+pub(crate) fn displace_cf_cell(
+    row: i32,
+    col: i32,
+    data: &DisplaceData,
+    sheet: u32,
+) -> Option<(i32, i32)> {
+    match data {
+        DisplaceData::CellHorizontal {
+            sheet: s,
+            row: dr,
+            height,
+            column: dc,
+            delta,
+        } if *s == sheet => {
+            if (*dr..*dr + *height).contains(&row) {
+                Some((row, displace_index(col, *dc, *delta)?))
+            } else {
+                Some((row, col))
+            }
+        }
+        DisplaceData::CellVertical {
+            sheet: s,
+            row: dr,
+            column: dc,
+            width,
+            delta,
+        } if *s == sheet => {
+            if (*dc..*dc + *width).contains(&col) {
+                Some((displace_index(row, *dr, *delta)?, col))
+            } else {
+                Some((row, col))
+            }
+        }
+        _ => Some((
+            displace_cf_row(row, data, sheet)?,
+            displace_cf_col(col, data, sheet)?,
+        )),
+    }
+}
+
 /// Displaces a single A1-style sqref part (e.g. "A1" or "A1:B5").
 /// Returns the original string unchanged if any corner would become #REF!.
 fn displace_cf_sqref_part(part: &str, data: &DisplaceData, sheet: u32) -> String {
@@ -92,10 +128,7 @@ fn displace_cf_sqref_part(part: &str, data: &DisplaceData, sheet: u32) -> String
     match segs.len() {
         1 => {
             if let Some(r) = utils::parse_reference_a1(segs[0]) {
-                if let (Some(nr), Some(nc)) = (
-                    displace_cf_row(r.row, data, sheet),
-                    displace_cf_col(r.column, data, sheet),
-                ) {
+                if let Some((nr, nc)) = displace_cf_cell(r.row, r.column, data, sheet) {
                     if let Some(c) = utils::number_to_column(nc) {
                         return format!("{c}{nr}");
                     }
@@ -108,11 +141,9 @@ fn displace_cf_sqref_part(part: &str, data: &DisplaceData, sheet: u32) -> String
                 utils::parse_reference_a1(segs[0]),
                 utils::parse_reference_a1(segs[1]),
             ) {
-                if let (Some(nr1), Some(nc1), Some(nr2), Some(nc2)) = (
-                    displace_cf_row(r1.row, data, sheet),
-                    displace_cf_col(r1.column, data, sheet),
-                    displace_cf_row(r2.row, data, sheet),
-                    displace_cf_col(r2.column, data, sheet),
+                if let (Some((nr1, nc1)), Some((nr2, nc2))) = (
+                    displace_cf_cell(r1.row, r1.column, data, sheet),
+                    displace_cf_cell(r2.row, r2.column, data, sheet),
                 ) {
                     if let (Some(c1), Some(c2)) =
                         (utils::number_to_column(nc1), utils::number_to_column(nc2))
@@ -335,6 +366,29 @@ impl<'a> Model<'a> {
         }
     }
 
+    /// Moves the changing cells of the scenarios on `sheet` according to `displace_data`.
+    /// Deleted cells are removed from their scenarios and scenarios left without cells are deleted.
+    fn displace_scenario_cells(&mut self, sheet: u32, displace_data: &DisplaceData) {
+        let Some(worksheet) = self.workbook.worksheets.get_mut(sheet as usize) else {
+            return;
+        };
+        for scenario in &mut worksheet.scenarios {
+            scenario.input_cells.retain_mut(|cell| {
+                match displace_cf_cell(cell.row, cell.column, displace_data, sheet) {
+                    Some((row, column)) => {
+                        cell.row = row;
+                        cell.column = column;
+                        true
+                    }
+                    None => false,
+                }
+            });
+        }
+        worksheet
+            .scenarios
+            .retain(|scenario| !scenario.input_cells.is_empty());
+    }
+
     /// Retrieves the column indices for a specific row in a given sheet, sorted in ascending or descending order.
     ///
     /// # Arguments
@@ -446,7 +500,7 @@ impl<'a> Model<'a> {
 
         if let Some((width, height)) = array {
             // We are moving an array formula, we need to move the whole range
-            self.set_user_array_formula(
+            self.set_a1_user_array_formula(
                 sheet,
                 target_row,
                 target_column,
@@ -455,7 +509,7 @@ impl<'a> Model<'a> {
                 &formula_or_value,
             )?;
         } else {
-            self.set_user_input(sheet, target_row, target_column, formula_or_value)?;
+            self.set_a1_user_input(sheet, target_row, target_column, formula_or_value)?;
         }
 
         let worksheet = self.workbook.worksheet_mut(sheet)?;
@@ -501,7 +555,9 @@ impl<'a> Model<'a> {
         }
         self.reset_dynamic_array_spills(sheet)?;
         let worksheet = self.workbook.worksheet(sheet)?;
-        let all_rows: Vec<i32> = worksheet.sheet_data.keys().copied().collect();
+        // Rows are sorted so formulas are rewritten in the same order every time
+        let mut all_rows: Vec<i32> = worksheet.sheet_data.keys().copied().collect();
+        all_rows.sort_unstable();
         for row in all_rows {
             let sorted_columns = self.get_columns_for_row(sheet, row, true)?;
             for col in sorted_columns {
@@ -522,6 +578,7 @@ impl<'a> Model<'a> {
         };
         self.displace_cells(&disp)?;
         self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
 
         // In the list of columns:
         // * Keep all the columns to the left
@@ -613,6 +670,7 @@ impl<'a> Model<'a> {
         };
         self.displace_cells(&disp)?;
         self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
         let worksheet = &mut self.workbook.worksheet_mut(sheet)?;
 
         // deletes all the column styles
@@ -856,6 +914,7 @@ impl<'a> Model<'a> {
         };
         self.displace_cells(&disp)?;
         self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
 
         Ok(())
     }
@@ -925,9 +984,246 @@ impl<'a> Model<'a> {
         };
         self.displace_cells(&disp)?;
         self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
         Ok(())
     }
 
+    /// Inserts blank cells in `area`. The cells in the same rows move right, or the cells in the same
+    /// columns move down, depending on `direction`.
+    ///
+    /// Formulas, defined names, conditional formatting and merged cells follow the moved cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - The cells to insert.
+    /// * `direction` - Either [ShiftDirection::Right] or [ShiftDirection::Down].
+    pub fn insert_cells(&mut self, area: &Area, direction: ShiftDirection) -> Result<(), String> {
+        let delta = match direction {
+            ShiftDirection::Right => area.width,
+            ShiftDirection::Down => area.height,
+            ShiftDirection::Left | ShiftDirection::Up => {
+                return Err("Cells can only be inserted shifting cells right or down".to_string())
+            }
+        };
+        self.shift_cells(area, direction == ShiftDirection::Right, delta)
+    }
+
+    /// Deletes the cells in `area`. The cells to the right move left, or the cells below move up,
+    /// depending on `direction`.
+    ///
+    /// References to the deleted cells become `#REF!`.
+    ///
+    /// # Arguments
+    ///
+    /// * `area` - The cells to delete.
+    /// * `direction` - Either [ShiftDirection::Left] or [ShiftDirection::Up].
+    pub fn delete_cells(&mut self, area: &Area, direction: ShiftDirection) -> Result<(), String> {
+        let delta = match direction {
+            ShiftDirection::Left => -area.width,
+            ShiftDirection::Up => -area.height,
+            ShiftDirection::Right | ShiftDirection::Down => {
+                return Err("Cells can only be deleted shifting cells left or up".to_string())
+            }
+        };
+        self.shift_cells(area, direction == ShiftDirection::Left, delta)
+    }
+
+    /// Inserts (`delta > 0`) or deletes (`delta < 0`) the cells in `area` moving the cells in the same rows
+    /// (`horizontal`) or in the same columns.
+    fn shift_cells(&mut self, area: &Area, horizontal: bool, delta: i32) -> Result<(), String> {
+        let sheet = area.sheet;
+        let last_row = area.row + area.height - 1;
+        let last_column = area.column + area.width - 1;
+        if area.width <= 0
+            || area.height <= 0
+            || area.row < 1
+            || area.column < 1
+            || last_row > LAST_ROW
+            || last_column > LAST_COLUMN
+        {
+            return Err("Invalid area".to_string());
+        }
+        // Rectangles are (first row, first column, last row, last column)
+        let deleted = (area.row, area.column, last_row, last_column);
+        let moving = match (horizontal, delta > 0) {
+            (true, true) => (area.row, area.column, last_row, LAST_COLUMN),
+            (true, false) => (area.row, last_column + 1, last_row, LAST_COLUMN),
+            (false, true) => (area.row, area.column, LAST_ROW, last_column),
+            (false, false) => (last_row + 1, area.column, LAST_ROW, last_column),
+        };
+        let mut regions = vec![moving];
+        if delta < 0 {
+            regions.push(deleted);
+        }
+        let overlaps = |a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)| {
+            a.0 <= b.2 && b.0 <= a.2 && a.1 <= b.3 && b.1 <= a.3
+        };
+        let contains = |a: (i32, i32, i32, i32), b: (i32, i32, i32, i32)| {
+            a.0 <= b.0 && b.2 <= a.2 && a.1 <= b.1 && b.3 <= a.3
+        };
+        let breaks = |rectangle: (i32, i32, i32, i32)| {
+            regions
+                .iter()
+                .any(|region| overlaps(*region, rectangle) && !contains(*region, rectangle))
+        };
+
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let mut cells = Vec::new();
+        for (row, row_data) in &worksheet.sheet_data {
+            for column in row_data.keys() {
+                cells.push((*row, *column));
+            }
+        }
+        for &(row, column) in &cells {
+            if let CellStructure::ArrayFormula {
+                range: (width, height),
+            } = self.get_cell_structure(sheet, row, column)?
+            {
+                if breaks((row, column, row + height - 1, column + width - 1)) {
+                    return Err(
+                        "Cannot shift cells because that would break an array formula".to_string(),
+                    );
+                }
+            }
+        }
+        let mut merges = Vec::new();
+        for range in &worksheet.merge_cells {
+            let (first_column, first_row, merge_last_column, merge_last_row) = parse_range(range)?;
+            let rectangle = (first_row, first_column, merge_last_row, merge_last_column);
+            if breaks(rectangle) {
+                return Err("Cannot shift cells because that would break a merged cell".to_string());
+            }
+            merges.push((range.clone(), rectangle));
+        }
+        if delta > 0 {
+            let last = cells
+                .iter()
+                .filter(|(row, column)| contains(moving, (*row, *column, *row, *column)))
+                .map(|(row, column)| if horizontal { *column } else { *row })
+                .max();
+            if let Some(last) = last {
+                if horizontal && last + delta > LAST_COLUMN {
+                    return Err(
+                        "Cannot shift cells because that would delete cells at the end of a row"
+                            .to_string(),
+                    );
+                }
+                if !horizontal && last + delta > LAST_ROW {
+                    return Err(
+                        "Cannot shift cells because that would delete cells at the end of a column"
+                            .to_string(),
+                    );
+                }
+            }
+        }
+
+        self.reset_dynamic_array_spills(sheet)?;
+        if delta < 0 {
+            for row in area.row..=last_row {
+                for column in area.column..=last_column {
+                    self.workbook
+                        .worksheet_mut(sheet)?
+                        .remove_cell(row, column)?;
+                }
+            }
+        }
+        // Cells are moved starting with the ones that go to free positions
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let mut moved: Vec<(i32, i32)> = worksheet
+            .sheet_data
+            .iter()
+            .flat_map(|(row, row_data)| row_data.keys().map(move |column| (*row, *column)))
+            .filter(|(row, column)| contains(moving, (*row, *column, *row, *column)))
+            .collect();
+        if horizontal {
+            moved.sort_unstable_by_key(|(row, column)| (*column, *row));
+        } else {
+            moved.sort_unstable();
+        }
+        if delta > 0 {
+            moved.reverse();
+        }
+        for (row, column) in moved {
+            if horizontal {
+                self.move_cell(sheet, row, column, row, column + delta)?;
+            } else {
+                self.move_cell(sheet, row, column, row + delta, column)?;
+            }
+        }
+
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        let mut merge_cells = Vec::new();
+        for (range, (first_row, first_column, merge_last_row, merge_last_column)) in merges {
+            let rectangle = (first_row, first_column, merge_last_row, merge_last_column);
+            if delta < 0 && contains(deleted, rectangle) {
+                continue;
+            }
+            if !contains(moving, rectangle) {
+                merge_cells.push(range);
+                continue;
+            }
+            let (row_delta, column_delta) = if horizontal { (0, delta) } else { (delta, 0) };
+            merge_cells.push(format!(
+                "{}{}:{}{}",
+                utils::number_to_column(first_column + column_delta).ok_or("Invalid column")?,
+                first_row + row_delta,
+                utils::number_to_column(merge_last_column + column_delta)
+                    .ok_or("Invalid column")?,
+                merge_last_row + row_delta
+            ));
+        }
+        worksheet.merge_cells = merge_cells;
+
+        // Update all formulas in the workbook
+        let disp = if horizontal {
+            DisplaceData::CellHorizontal {
+                sheet,
+                row: area.row,
+                height: area.height,
+                column: area.column,
+                delta,
+            }
+        } else {
+            DisplaceData::CellVertical {
+                sheet,
+                row: area.row,
+                column: area.column,
+                width: area.width,
+                delta,
+            }
+        };
+        self.displace_cells(&disp)?;
+        self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
+        self.displace_defined_names(&disp);
+        Ok(())
+    }
+
+    /// Updates the references in the formulas of the defined names according to `displace_data`.
+    fn displace_defined_names(&mut self, displace_data: &DisplaceData) {
+        let context = CellReferenceRC {
+            sheet: match self.workbook.worksheets.first() {
+                Some(worksheet) => worksheet.get_name(),
+                None => return,
+            },
+            row: 1,
+            column: 1,
+        };
+        let mut changed = false;
+        for index in 0..self.workbook.defined_names.len() {
+            let formula = self.workbook.defined_names[index].formula.clone();
+            let node = self.parse_english_formula(&formula, &context, LexerMode::A1);
+            let displaced = to_string_displaced(&node, &context, displace_data);
+            if displaced != to_string_displaced(&node, &context, &DisplaceData::None) {
+                self.workbook.defined_names[index].formula = displaced;
+                changed = true;
+            }
+        }
+        if changed {
+            self.reset_parsed_structures();
+        }
+    }
+
     // Inner column move: no boundary/can check, no spill reset.
     // Caller must have validated and reset spills before calling this.
     fn move_column_unchecked(&mut self, sheet: u32, column: i32, delta: i32) -> Result<(), String> {
@@ -1031,9 +1327,9 @@ impl<'a> Model<'a> {
         }
         for (r, value, style_idx, array) in original_cells {
             if let Some(a) = array {
-                self.set_user_array_formula(sheet, r, target_column, a.0, a.1, &value)?;
+                self.set_a1_user_array_formula(sheet, r, target_column, a.0, a.1, &value)?;
             } else {
-                self.set_user_input(sheet, r, target_column, value)?;
+                self.set_a1_user_input(sheet, r, target_column, value)?;
             }
             self.workbook
                 .worksheet_mut(sheet)?
@@ -1049,6 +1345,7 @@ impl<'a> Model<'a> {
         };
         self.displace_cells(&disp)?;
         self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
         Ok(())
     }
 
@@ -1125,7 +1422,7 @@ impl<'a> Model<'a> {
         }
         for (c, value, style_idx, array) in original_cells {
             if let Some(array_range) = array {
-                self.set_user_array_formula(
+                self.set_a1_user_array_formula(
                     sheet,
                     target_row,
                     c,
@@ -1134,7 +1431,7 @@ impl<'a> Model<'a> {
                     &value,
                 )?;
             } else {
-                self.set_user_input(sheet, target_row, c, value)?;
+                self.set_a1_user_input(sheet, target_row, c, value)?;
             }
             self.workbook
                 .worksheet_mut(sheet)?
@@ -1163,6 +1460,7 @@ impl<'a> Model<'a> {
         let disp = DisplaceData::RowMove { sheet, row, delta };
         self.displace_cells(&disp)?;
         self.displace_cf_ranges(sheet, &disp);
+        self.displace_scenario_cells(sheet, &disp);
         Ok(())
     }
 
@@ -1,7 +1,10 @@
 use crate::types::{Border, BorderItem, BorderStyle, Color, Fill, Font, FontScheme, Style};
 
 fn solid_fill(color: Color) -> Fill {
-    Fill { color }
+    Fill {
+        color,
+        ..Default::default()
+    }
 }
 
 fn thin_box_border(color: Color) -> Border {
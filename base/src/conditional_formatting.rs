@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::expressions::utils::parse_reference_a1;
-use crate::formatter::dates::{date_to_serial_number, from_excel_date};
+use crate::formatter::dates::{date_system_offset, date_to_serial_number, from_excel_date};
 use crate::{
     calc_result::CalcResult,
     cell::CellValue,
@@ -845,17 +845,18 @@ impl<'a> Model<'a> {
         stop_if_true: bool,
         ranges: &[(i32, i32, i32, i32)],
     ) {
+        let date1904 = self.workbook.settings.date1904;
         let today_serial = match crate::tz::excel_serial_for_now(&self.tz) {
-            Some(s) => s.floor() as i64,
+            Some(s) => s.floor() as i64 - date_system_offset(date1904) as i64,
             None => return,
         };
-        let today = match from_excel_date(today_serial) {
+        let today = match from_excel_date(today_serial, date1904) {
             Ok(d) => d,
             Err(_) => return,
         };
 
         let serial_of = |d: NaiveDate| -> f64 {
-            date_to_serial_number(d.day(), d.month(), d.year()).unwrap_or(0) as f64
+            date_to_serial_number(d.day(), d.month(), d.year(), date1904).unwrap_or(0) as f64
         };
 
         let range: (f64, f64) = match period {
@@ -0,0 +1,237 @@
+//! Functions implemented by the host application and callable from formulas.
+//!
+//! Formulas with a function name that is not built in are parsed as named functions and
+//! evaluate to `#NAME?`. Once a function with that name is registered the formula calls it.
+//! Nothing about the function is stored in the workbook, formulas keep the name as typed and
+//! survive a save and load. The host needs to register its functions again after loading.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calc_result::CalcResult,
+    expressions::{
+        parser::{ArrayNode, Node},
+        token::{get_error_by_english_name, Error},
+        types::CellReferenceIndex,
+    },
+    language::get_default_language,
+    model::Model,
+};
+
+/// A value passed to or returned by a custom function.
+///
+/// Ranges are passed as arrays of values. It serializes to plain JSON values: numbers,
+/// strings, booleans, `null` for empty cells, arrays of rows and `{"error": "#N/A"}` for errors.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum FunctionValue {
+    Number(f64),
+    Boolean(bool),
+    Text(String),
+    /// The error in English, e.g. "#DIV/0!"
+    Error {
+        error: String,
+    },
+    Array(Vec<Vec<FunctionValue>>),
+    Empty,
+}
+
+/// The model might be moved to other threads, a host whose functions can only be called from one thread
+/// keeps them there and hands the model a `Send` handle.
+pub type CustomFunctionCallback = Arc<dyn Fn(&[FunctionValue]) -> FunctionValue + Send + Sync>;
+
+/// A function registered by the host
+#[derive(Clone)]
+pub struct CustomFunction {
+    /// Name used in formulas, case insensitive
+    pub name: String,
+    pub min_args: usize,
+    /// `None` if there is no limit
+    pub max_args: Option<usize>,
+    /// The function might return a different value with the same arguments (`RAND`, `NOW`).
+    /// The model recalculates every formula on each evaluation so this is informative.
+    pub volatile: bool,
+    pub callback: CustomFunctionCallback,
+}
+
+fn is_valid_function_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {}
+        _ => return false,
+    }
+    chars.all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+}
+
+fn array_node_to_value(node: ArrayNode) -> FunctionValue {
+    match node {
+        ArrayNode::Boolean(value) => FunctionValue::Boolean(value),
+        ArrayNode::Number(value) => FunctionValue::Number(value),
+        ArrayNode::String(value) => FunctionValue::Text(value),
+        ArrayNode::Error(error) => FunctionValue::Error {
+            error: error.to_string(),
+        },
+        ArrayNode::Empty => FunctionValue::Empty,
+    }
+}
+
+fn value_to_array_node(value: FunctionValue) -> ArrayNode {
+    match value {
+        FunctionValue::Number(value) if !value.is_finite() => ArrayNode::Error(Error::NUM),
+        FunctionValue::Number(value) => ArrayNode::Number(value),
+        FunctionValue::Boolean(value) => ArrayNode::Boolean(value),
+        FunctionValue::Text(value) => ArrayNode::String(value),
+        FunctionValue::Error { error } => {
+            ArrayNode::Error(get_error_by_english_name(&error).unwrap_or(Error::VALUE))
+        }
+        // Arrays cannot be nested
+        FunctionValue::Array(_) => ArrayNode::Error(Error::VALUE),
+        FunctionValue::Empty => ArrayNode::Empty,
+    }
+}
+
+fn value_to_calc_result(value: FunctionValue, cell: CellReferenceIndex) -> CalcResult {
+    match value {
+        FunctionValue::Number(value) if !value.is_finite() => CalcResult::new_error(
+            Error::NUM,
+            cell,
+            "Custom function returned an invalid number".to_string(),
+        ),
+        FunctionValue::Number(value) => CalcResult::Number(value),
+        FunctionValue::Boolean(value) => CalcResult::Boolean(value),
+        FunctionValue::Text(value) => CalcResult::String(value),
+        FunctionValue::Error { error } => match get_error_by_english_name(&error) {
+            Some(error) => {
+                CalcResult::new_error(error, cell, "Error in custom function".to_string())
+            }
+            None => CalcResult::new_error(Error::VALUE, cell, error),
+        },
+        FunctionValue::Array(rows) => {
+            if rows.is_empty() || rows[0].is_empty() {
+                return CalcResult::new_error(
+                    Error::VALUE,
+                    cell,
+                    "Custom function returned an empty array".to_string(),
+                );
+            }
+            let width = rows[0].len();
+            if rows.iter().any(|row| row.len() != width) {
+                return CalcResult::new_error(
+                    Error::VALUE,
+                    cell,
+                    "Custom function returned rows of different lengths".to_string(),
+                );
+            }
+            CalcResult::Array(
+                rows.into_iter()
+                    .map(|row| row.into_iter().map(value_to_array_node).collect())
+                    .collect(),
+            )
+        }
+        FunctionValue::Empty => CalcResult::EmptyCell,
+    }
+}
+
+impl Model<'_> {
+    /// Registers a function implemented by the host. If there was already a function with the
+    /// same name it is replaced.
+    ///
+    /// Names of built-in functions cannot be used. Call [Model::evaluate] to update the formulas
+    /// that use the function.
+    pub fn register_function(&mut self, function: CustomFunction) -> Result<(), String> {
+        let name = &function.name;
+        if !is_valid_function_name(name) {
+            return Err(format!("Invalid function name: '{name}'."));
+        }
+        if self.language.functions.lookup(name).is_some()
+            || get_default_language().functions.lookup(name).is_some()
+        {
+            return Err(format!("'{name}' is a built-in function."));
+        }
+        if function.max_args.is_some_and(|max| max < function.min_args) {
+            return Err(format!(
+                "Invalid number of arguments for '{name}': the maximum is less than the minimum."
+            ));
+        }
+        self.custom_functions.insert(name.to_uppercase(), function);
+        Ok(())
+    }
+
+    /// Removes a function registered with [Model::register_function].
+    /// Formulas using it evaluate to `#NAME?` again.
+    pub fn unregister_function(&mut self, name: &str) -> Result<(), String> {
+        match self.custom_functions.remove(&name.to_uppercase()) {
+            Some(_) => Ok(()),
+            None => Err(format!("Function not found: '{name}'.")),
+        }
+    }
+
+    /// Returns the names of the registered functions, sorted
+    pub fn get_registered_functions(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .custom_functions
+            .values()
+            .map(|function| function.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    pub(crate) fn get_custom_function(&self, name: &str) -> Option<CustomFunction> {
+        self.custom_functions.get(&name.to_uppercase()).cloned()
+    }
+
+    pub(crate) fn call_custom_function(
+        &mut self,
+        function: &CustomFunction,
+        args: &[Node],
+        cell: CellReferenceIndex,
+    ) -> CalcResult {
+        if args.len() < function.min_args || function.max_args.is_some_and(|max| args.len() > max) {
+            return CalcResult::new_args_number_error(cell);
+        }
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            let value = match self.evaluate_node_in_context(arg, cell) {
+                CalcResult::Number(value) => FunctionValue::Number(value),
+                CalcResult::Boolean(value) => FunctionValue::Boolean(value),
+                CalcResult::String(value) => FunctionValue::Text(value),
+                CalcResult::Error { error, .. } => FunctionValue::Error {
+                    error: error.to_string(),
+                },
+                CalcResult::Range { left, right } => {
+                    if left.sheet != right.sheet {
+                        return CalcResult::new_error(
+                            Error::VALUE,
+                            cell,
+                            "Ranges must be in the same sheet".to_string(),
+                        );
+                    }
+                    FunctionValue::Array(
+                        self.evaluate_range(left, right)
+                            .into_iter()
+                            .map(|row| row.into_iter().map(array_node_to_value).collect())
+                            .collect(),
+                    )
+                }
+                CalcResult::Array(rows) => FunctionValue::Array(
+                    rows.into_iter()
+                        .map(|row| row.into_iter().map(array_node_to_value).collect())
+                        .collect(),
+                ),
+                CalcResult::EmptyCell | CalcResult::EmptyArg => FunctionValue::Empty,
+                CalcResult::Lambda(_) => {
+                    return CalcResult::new_error(
+                        Error::VALUE,
+                        cell,
+                        "A LAMBDA cannot be passed to a custom function".to_string(),
+                    )
+                }
+            };
+            values.push(value);
+        }
+        value_to_calc_result((function.callback)(&values), cell)
+    }
+}
@@ -197,7 +197,7 @@ impl<'a> Model<'a> {
             };
             // now collect the user-facing formula strings
             for (row, col) in formula_positions {
-                let formula_str = self.get_localized_cell_content(ws_idx_u32, row, col)?;
+                let formula_str = self.get_localized_a1_cell_content(ws_idx_u32, row, col)?;
                 candidates.push((ws_idx_u32, row, col, formula_str));
             }
         }
@@ -0,0 +1,455 @@
+//! Error checking rules, like the green triangles of Excel.
+//!
+//! Each rule looks at a single cell and may suggest a quick fix the UI can apply.
+//! The rules do not change the model.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    expressions::{
+        parser::{
+            static_analysis::{remove_failing_implicit_intersections, walk_nodes_mut},
+            stringify::{to_localized_r1c1_string, to_localized_string},
+            Node,
+        },
+        types::{CellReferenceIndex, CellReferenceRC},
+    },
+    formatter::{format::parse_formatted_number, lexer::is_likely_date_number_format},
+    model::Model,
+    types::Cell,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCheckRule {
+    /// Text that would be a number if typed in the cell
+    NumberStoredAsText,
+    /// The formula differs from the formulas on both sides, which are equal
+    InconsistentFormula,
+    /// A range in the formula stops right before more numbers
+    FormulaOmitsAdjacentCells,
+    /// The formula refers to an empty cell
+    EmptyCellReference,
+    /// The cell has a formula but it is not locked
+    UnlockedFormula,
+    /// Text with a date with a two digit year, like "3/5/29"
+    TwoDigitYear,
+    /// An `@` over a range that does not share a row or column with the cell
+    ImplicitIntersection,
+}
+
+/// A change that fixes the problem found by a rule
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+pub enum QuickFix {
+    /// Set the content of the cell, as typed by the user
+    SetContent { content: String },
+    /// Lock the cell (`protection.locked`)
+    LockCell,
+    /// There is nothing to change in the cell, the problem is in the given cell
+    GoToCell { sheet: u32, row: i32, column: i32 },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CellDiagnostic {
+    pub sheet: u32,
+    pub row: i32,
+    pub column: i32,
+    pub rule: ErrorCheckRule,
+    pub message: String,
+    pub quick_fix: Option<QuickFix>,
+}
+
+/// Absolute coordinates of a range in the formula of the cell (`row`, `column`)
+struct AbsoluteRange {
+    sheet: u32,
+    row1: i32,
+    column1: i32,
+    row2: i32,
+    column2: i32,
+}
+
+/// If the text is a date with a two digit year, like "3/5/29" or "5-Mar-29",
+/// returns the same date with a four digit year.
+/// Years 00 to 29 are in the 21st century, 30 to 99 in the 20th.
+fn expand_two_digit_year(text: &str) -> Option<String> {
+    let text = text.trim();
+    let separator = text.chars().find(|c| matches!(c, '/' | '-' | '.'))?;
+    let parts: Vec<&str> = text.split(separator).collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let is_day_or_month = |part: &str| {
+        (!part.is_empty() && part.len() <= 2 && part.chars().all(|c| c.is_ascii_digit()))
+            || (part.len() >= 3 && part.chars().all(|c| c.is_alphabetic()))
+    };
+    let year = parts[2];
+    if !is_day_or_month(parts[0])
+        || !is_day_or_month(parts[1])
+        || year.len() != 2
+        || !year.chars().all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let century = if year < "30" { "20" } else { "19" };
+    Some(format!(
+        "{}{separator}{}{separator}{century}{year}",
+        parts[0], parts[1]
+    ))
+}
+
+impl Model<'_> {
+    /// Runs the error checking rules on all the cells of the sheet.
+    ///
+    /// Diagnostics are sorted by row and column. A cell may have several diagnostics.
+    pub fn get_error_checks(&self, sheet: u32) -> Result<Vec<CellDiagnostic>, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let mut diagnostics = Vec::new();
+        let mut rows: Vec<&i32> = worksheet.sheet_data.keys().collect();
+        rows.sort_unstable();
+        for row in rows {
+            let row_data = &worksheet.sheet_data[row];
+            let mut columns: Vec<&i32> = row_data.keys().collect();
+            columns.sort_unstable();
+            for column in columns {
+                let cell = CellReferenceIndex {
+                    sheet,
+                    row: *row,
+                    column: *column,
+                };
+                self.check_cell(&cell, &row_data[column], &mut diagnostics)?;
+            }
+        }
+        Ok(diagnostics)
+    }
+
+    fn check_cell(
+        &self,
+        cell: &CellReferenceIndex,
+        data: &Cell,
+        diagnostics: &mut Vec<CellDiagnostic>,
+    ) -> Result<(), String> {
+        let mut add = |rule, message: &str, quick_fix| {
+            diagnostics.push(CellDiagnostic {
+                sheet: cell.sheet,
+                row: cell.row,
+                column: cell.column,
+                rule,
+                message: message.to_string(),
+                quick_fix,
+            })
+        };
+        if let Cell::SharedString { si, .. } = data {
+            let text = self
+                .workbook
+                .shared_strings
+                .get(*si as usize)
+                .map_or("", |s| s.as_str());
+            if let Some(content) = expand_two_digit_year(text) {
+                add(
+                    ErrorCheckRule::TwoDigitYear,
+                    "This cell contains a date string with only two digits for the year.",
+                    Some(QuickFix::SetContent {
+                        content: format!("'{content}"),
+                    }),
+                );
+            } else if self.is_number_text(text) {
+                add(
+                    ErrorCheckRule::NumberStoredAsText,
+                    "The number in this cell is formatted as text or preceded by an apostrophe.",
+                    Some(QuickFix::SetContent {
+                        content: text.trim().to_string(),
+                    }),
+                );
+            }
+            return Ok(());
+        }
+        let formula_index = match data.get_formula() {
+            Some(index) => index,
+            None => return Ok(()),
+        };
+        let node = &self
+            .parsed_formulas
+            .get(cell.sheet as usize)
+            .and_then(|formulas| formulas.get(formula_index as usize))
+            .ok_or("missing formula")?
+            .0;
+
+        if let Some(neighbour) = self.get_inconsistent_formula(cell, formula_index) {
+            let formula = &self.parsed_formulas[cell.sheet as usize][neighbour as usize].0;
+            add(
+                ErrorCheckRule::InconsistentFormula,
+                "The formula in this cell differs from the formulas in this area of the spreadsheet.",
+                Some(QuickFix::SetContent {
+                    content: self.formula_to_string(formula, cell)?,
+                }),
+            );
+        }
+
+        if let Some(formula) = self.extend_ranges_to_adjacent_cells(node, cell) {
+            add(
+                ErrorCheckRule::FormulaOmitsAdjacentCells,
+                "The formula in this cell refers to a range that has additional numbers adjacent to it.",
+                Some(QuickFix::SetContent {
+                    content: self.formula_to_string(&formula, cell)?,
+                }),
+            );
+        }
+
+        if let Some(empty) = self.find_empty_reference(node, cell) {
+            add(
+                ErrorCheckRule::EmptyCellReference,
+                "The formula in this cell refers to empty cells.",
+                Some(QuickFix::GoToCell {
+                    sheet: empty.sheet,
+                    row: empty.row,
+                    column: empty.column,
+                }),
+            );
+        }
+
+        let style = self.workbook.styles.get_style(data.get_style())?;
+        if !style.is_locked() {
+            add(
+                ErrorCheckRule::UnlockedFormula,
+                "This cell is unlocked and contains a formula.",
+                Some(QuickFix::LockCell),
+            );
+        }
+
+        let mut formula = node.clone();
+        if remove_failing_implicit_intersections(&mut formula, cell) {
+            add(
+                ErrorCheckRule::ImplicitIntersection,
+                "The implicit intersection operator (@) in this formula cannot find a cell in the same row or column.",
+                Some(QuickFix::SetContent {
+                    content: self.formula_to_string(&formula, cell)?,
+                }),
+            );
+        }
+        Ok(())
+    }
+
+    /// The formula as the user would type it in the cell
+    fn formula_to_string(&self, node: &Node, cell: &CellReferenceIndex) -> Result<String, String> {
+        if self.workbook.settings.r1c1 {
+            return Ok(format!(
+                "={}",
+                to_localized_r1c1_string(node, self.locale, self.language)
+            ));
+        }
+        let context = CellReferenceRC {
+            sheet: self.workbook.worksheet(cell.sheet)?.get_name(),
+            row: cell.row,
+            column: cell.column,
+        };
+        Ok(format!(
+            "={}",
+            to_localized_string(node, &context, self.locale, self.language)
+        ))
+    }
+
+    /// Text that would be converted to a number (but not to a date) if typed in the cell
+    fn is_number_text(&self, text: &str) -> bool {
+        if text.trim().is_empty() {
+            return false;
+        }
+        let mut currencies = vec!["$", "€"];
+        let currency = &self.locale.currency.symbol;
+        if !currencies.iter().any(|e| e == currency) {
+            currencies.push(currency);
+        }
+        match parse_formatted_number(
+            text.trim(),
+            &currencies,
+            self.locale,
+            self.workbook.settings.date1904,
+        ) {
+            Ok((_, Some(num_fmt))) => !is_likely_date_number_format(&num_fmt),
+            Ok((_, None)) => true,
+            Err(_) => false,
+        }
+    }
+
+    fn get_formula_index(&self, sheet: u32, row: i32, column: i32) -> Option<i32> {
+        self.workbook
+            .worksheet(sheet)
+            .ok()?
+            .cell(row, column)?
+            .get_formula()
+    }
+
+    /// Number constants, totals computed by formulas are not part of the data
+    fn is_number_cell(&self, sheet: u32, row: i32, column: i32) -> bool {
+        self.workbook
+            .worksheet(sheet)
+            .ok()
+            .and_then(|ws| ws.cell(row, column))
+            .is_some_and(|c| matches!(c, Cell::NumberCell { .. }))
+    }
+
+    /// Returns the formula of the neighbours if the cells on both sides (left and right, or
+    /// above and below) have the same formula and it is not the formula of the cell.
+    fn get_inconsistent_formula(
+        &self,
+        cell: &CellReferenceIndex,
+        formula_index: i32,
+    ) -> Option<i32> {
+        let CellReferenceIndex { sheet, row, column } = *cell;
+        let sides = [
+            ((row, column - 1), (row, column + 1)),
+            ((row - 1, column), (row + 1, column)),
+        ];
+        sides
+            .into_iter()
+            .find_map(|((row1, column1), (row2, column2))| {
+                let first = self.get_formula_index(sheet, row1, column1)?;
+                let second = self.get_formula_index(sheet, row2, column2)?;
+                (first == second && first != formula_index).then_some(first)
+            })
+    }
+
+    /// Extends a range over the numbers right after it, without reaching the cell itself.
+    fn extend_range(&self, range: &AbsoluteRange, cell: &CellReferenceIndex) -> AbsoluteRange {
+        let sheet = range.sheet;
+        let can_extend = |row: i32, column: i32| {
+            let is_cell = sheet == cell.sheet && row == cell.row && column == cell.column;
+            !is_cell && self.is_number_cell(sheet, row, column)
+        };
+        let AbsoluteRange {
+            mut row1,
+            mut column1,
+            mut row2,
+            mut column2,
+            ..
+        } = *range;
+        if column1 == column2 && row1 < row2 {
+            while can_extend(row1 - 1, column1) {
+                row1 -= 1;
+            }
+            while can_extend(row2 + 1, column1) {
+                row2 += 1;
+            }
+        } else if row1 == row2 && column1 < column2 {
+            while can_extend(row1, column1 - 1) {
+                column1 -= 1;
+            }
+            while can_extend(row1, column2 + 1) {
+                column2 += 1;
+            }
+        }
+        AbsoluteRange {
+            sheet,
+            row1,
+            column1,
+            row2,
+            column2,
+        }
+    }
+
+    /// If there are numbers next to a one row or one column range of the formula, returns the
+    /// formula with the ranges extended to include them.
+    fn extend_ranges_to_adjacent_cells(
+        &self,
+        node: &Node,
+        cell: &CellReferenceIndex,
+    ) -> Option<Node> {
+        let mut formula = node.clone();
+        let mut extended = false;
+        walk_nodes_mut(&mut formula, &mut |n| {
+            if let Node::RangeKind {
+                sheet_index,
+                absolute_row1,
+                absolute_column1,
+                row1,
+                column1,
+                absolute_row2,
+                absolute_column2,
+                row2,
+                column2,
+                ..
+            } = n
+            {
+                let range = AbsoluteRange {
+                    sheet: *sheet_index,
+                    row1: if *absolute_row1 {
+                        *row1
+                    } else {
+                        *row1 + cell.row
+                    },
+                    column1: if *absolute_column1 {
+                        *column1
+                    } else {
+                        *column1 + cell.column
+                    },
+                    row2: if *absolute_row2 {
+                        *row2
+                    } else {
+                        *row2 + cell.row
+                    },
+                    column2: if *absolute_column2 {
+                        *column2
+                    } else {
+                        *column2 + cell.column
+                    },
+                };
+                let new_range = self.extend_range(&range, cell);
+                if new_range.row1 != range.row1
+                    || new_range.column1 != range.column1
+                    || new_range.row2 != range.row2
+                    || new_range.column2 != range.column2
+                {
+                    extended = true;
+                    *row1 = new_range.row1 - if *absolute_row1 { 0 } else { cell.row };
+                    *column1 = new_range.column1 - if *absolute_column1 { 0 } else { cell.column };
+                    *row2 = new_range.row2 - if *absolute_row2 { 0 } else { cell.row };
+                    *column2 = new_range.column2 - if *absolute_column2 { 0 } else { cell.column };
+                }
+            }
+        });
+        extended.then_some(formula)
+    }
+
+    /// Returns the first cell referenced by the formula that is empty
+    fn find_empty_reference(
+        &self,
+        node: &Node,
+        cell: &CellReferenceIndex,
+    ) -> Option<CellReferenceIndex> {
+        let mut formula = node.clone();
+        let mut empty = None;
+        walk_nodes_mut(&mut formula, &mut |n| {
+            if empty.is_some() {
+                return;
+            }
+            if let Node::ReferenceKind {
+                sheet_index,
+                absolute_row,
+                absolute_column,
+                row,
+                column,
+                ..
+            } = n
+            {
+                let reference = CellReferenceIndex {
+                    sheet: *sheet_index,
+                    row: if *absolute_row { *row } else { *row + cell.row },
+                    column: if *absolute_column {
+                        *column
+                    } else {
+                        *column + cell.column
+                    },
+                };
+                if let Ok(worksheet) = self.workbook.worksheet(reference.sheet) {
+                    if matches!(
+                        worksheet.cell(reference.row, reference.column),
+                        None | Some(Cell::EmptyCell { .. })
+                    ) {
+                        empty = Some(reference);
+                    }
+                }
+            }
+        });
+        empty
+    }
+}
@@ -0,0 +1,373 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calc_result::CalcResult,
+    expressions::{
+        parser::{
+            stringify::{to_localized_r1c1_string, to_localized_string},
+            ArrayNode, Node,
+        },
+        token::Error,
+        types::{CellReferenceIndex, CellReferenceRC},
+        utils::number_to_column,
+    },
+    functions::{catalogue::CATALOGUE, Function},
+    language::get_default_language,
+    model::Model,
+};
+
+/// The value of a sub-expression of a formula
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+#[serde(tag = "type")]
+pub enum EvaluationValue {
+    Number {
+        value: f64,
+    },
+    Text {
+        value: String,
+    },
+    Boolean {
+        value: bool,
+    },
+    /// `origin` is the cell where the error was produced, e.g. "Sheet3!C4"
+    Error {
+        error: String,
+        origin: String,
+        message: String,
+    },
+    /// A reference to a range, e.g. "Sheet1!A1:B3"
+    Range {
+        reference: String,
+    },
+    Array {
+        values: Vec<Vec<EvaluationValue>>,
+    },
+    Empty,
+    Lambda,
+}
+
+/// One reduction of a formula: a sub-expression together with its value
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct EvaluationStep {
+    /// The sub-expression as displayed to the user, e.g. "SUM(A1:A3)"
+    pub expression: String,
+    pub value: EvaluationValue,
+    /// Nesting level of the sub-expression, 0 is the whole formula
+    pub level: u32,
+    /// If the sub-expression is a reference to a cell with a formula, that cell.
+    /// Its own steps can be obtained with [Model::get_evaluation_steps].
+    pub step_into: Option<CellReferenceIndex>,
+}
+
+/// Returns the sub-expressions of a node
+fn get_children(node: &Node) -> Vec<&Node> {
+    match node {
+        Node::OpRangeKind { left, right }
+        | Node::OpConcatenateKind { left, right }
+        | Node::OpSumKind { left, right, .. }
+        | Node::OpProductKind { left, right, .. }
+        | Node::OpPowerKind { left, right }
+        | Node::CompareKind { left, right, .. } => vec![left, right],
+        Node::UnaryKind { right, .. } => vec![right],
+        Node::ImplicitIntersection { child, .. } | Node::SpillRangeOperator { child } => {
+            vec![child]
+        }
+        Node::FunctionKind { args, .. } | Node::NamedFunctionKind { args, .. } => {
+            args.iter().collect()
+        }
+        Node::LambdaDefKind { body, .. } => vec![body],
+        Node::LambdaCallKind { lambda, args } => {
+            let mut children = vec![lambda.as_ref()];
+            children.extend(args);
+            children
+        }
+        Node::BooleanKind(_)
+        | Node::NumberKind(_)
+        | Node::StringKind(_)
+        | Node::ReferenceKind { .. }
+        | Node::RangeKind { .. }
+        | Node::WrongReferenceKind { .. }
+        | Node::WrongRangeKind { .. }
+        | Node::ExternalReferenceKind { .. }
+        | Node::ExternalRangeKind { .. }
+        | Node::ArrayKind(_)
+        | Node::DefinedNameKind(_)
+        | Node::TableNameKind(_)
+        | Node::NamedVariableKind { .. }
+        | Node::ErrorKind(_)
+        | Node::ParseErrorKind { .. }
+        | Node::EmptyArgKind => vec![],
+    }
+}
+
+/// Sub-expressions using LET or LAMBDA variables cannot be evaluated on their own
+fn uses_variables(node: &Node) -> bool {
+    matches!(node, Node::NamedVariableKind { .. })
+        || get_children(node).into_iter().any(uses_variables)
+}
+
+/// Random and time functions return a different value every time they are evaluated
+fn is_volatile_function(function: &Function) -> bool {
+    let name = function.to_localized_name(get_default_language());
+    CATALOGUE
+        .iter()
+        .any(|entry| entry.volatile && entry.name == name)
+}
+
+fn is_literal(node: &Node) -> bool {
+    matches!(
+        node,
+        Node::BooleanKind(_)
+            | Node::NumberKind(_)
+            | Node::StringKind(_)
+            | Node::ArrayKind(_)
+            | Node::ErrorKind(_)
+            | Node::EmptyArgKind
+    )
+}
+
+impl Model<'_> {
+    /// Returns how the formula in the cell evaluates, one sub-expression at a time,
+    /// like the "Evaluate Formula" dialog of Excel.
+    ///
+    /// Steps are in evaluation order, so the sub-expressions of a node come before the node
+    /// and the last step is the whole formula. Literals are not listed. Only the branch taken
+    /// by `IF`, `IFERROR` and `IFNA` is evaluated. The first step with an error value is where
+    /// the error appears in this formula, its origin is the cell where it was produced.
+    ///
+    /// Sub-expressions are evaluated with the values of the last evaluation and the evaluation
+    /// state of the model is restored afterwards, so the model is left untouched. Those with volatile functions (`RAND`, `NOW`, ...) would not
+    /// give the values the formula used and are not listed, except for the whole formula.
+    ///
+    /// Returns an empty list if the cell has no formula.
+    pub fn get_evaluation_steps(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Vec<EvaluationStep>, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let formula_index = match worksheet.cell(row, column).and_then(|c| c.get_formula()) {
+            Some(index) => index,
+            None => return Ok(Vec::new()),
+        };
+        let context = CellReferenceRC {
+            sheet: worksheet.get_name(),
+            row,
+            column,
+        };
+        let node = self.parsed_formulas[sheet as usize][formula_index as usize]
+            .0
+            .clone();
+        let cell = CellReferenceIndex { sheet, row, column };
+
+        // Evaluating the sub-expressions only touches the state of the evaluation
+        let outer_cells = self.cells.clone();
+        let outer_support = self.support.get(&cell).cloned();
+        let outer_dynamic_support = self.dynamic_support.get(&cell).cloned();
+        let last_variable_id = self.last_variable_id;
+        let last_lambda_id = self.last_lambda_id;
+
+        let mut steps = Vec::new();
+        self.add_evaluation_steps(&node, cell, &context, 0, &mut steps);
+
+        self.cells = outer_cells;
+        match outer_support {
+            Some(support) => self.support.insert(cell, support),
+            None => self.support.remove(&cell),
+        };
+        match outer_dynamic_support {
+            Some(support) => self.dynamic_support.insert(cell, support),
+            None => self.dynamic_support.remove(&cell),
+        };
+        self.last_variable_id = last_variable_id;
+        self.lambdas.retain(|id, _| *id < last_lambda_id);
+        self.last_lambda_id = last_lambda_id;
+        Ok(steps)
+    }
+
+    /// Returns true if the node calls a volatile function, including custom ones
+    fn is_volatile(&self, node: &Node) -> bool {
+        let is_volatile_call = match node {
+            Node::FunctionKind { kind, .. } => is_volatile_function(kind),
+            Node::NamedFunctionKind { name, .. } => self
+                .get_custom_function(name)
+                .is_some_and(|function| function.volatile),
+            _ => false,
+        };
+        is_volatile_call
+            || get_children(node)
+                .into_iter()
+                .any(|child| self.is_volatile(child))
+    }
+
+    fn add_evaluation_steps(
+        &mut self,
+        node: &Node,
+        cell: CellReferenceIndex,
+        context: &CellReferenceRC,
+        level: u32,
+        steps: &mut Vec<EvaluationStep>,
+    ) {
+        if level > 0 && is_literal(node) {
+            return;
+        }
+        let children = get_children(node);
+        match node {
+            Node::FunctionKind {
+                kind: Function::If,
+                args,
+            } if !args.is_empty() => {
+                self.add_evaluation_steps(&args[0], cell, context, level + 1, steps);
+                // With a volatile condition the branch taken in the last evaluation is not known
+                let condition = if self.is_volatile(&args[0]) {
+                    CalcResult::EmptyArg
+                } else {
+                    self.evaluate_node_in_context(&args[0], cell)
+                };
+                let branch = match condition {
+                    CalcResult::Boolean(true) => Some(1),
+                    CalcResult::Number(n) if n != 0.0 => Some(1),
+                    CalcResult::Boolean(false) | CalcResult::Number(_) | CalcResult::EmptyCell => {
+                        Some(2)
+                    }
+                    _ => None,
+                };
+                if let Some(arg) = branch.and_then(|index| args.get(index)) {
+                    self.add_evaluation_steps(arg, cell, context, level + 1, steps);
+                }
+            }
+            Node::FunctionKind {
+                kind: kind @ (Function::Iferror | Function::Ifna),
+                args,
+            } if !args.is_empty() => {
+                self.add_evaluation_steps(&args[0], cell, context, level + 1, steps);
+                let is_caught = !self.is_volatile(&args[0])
+                    && match self.evaluate_node_in_context(&args[0], cell) {
+                        CalcResult::Error { error, .. } => {
+                            *kind == Function::Iferror || error == Error::NA
+                        }
+                        _ => false,
+                    };
+                if is_caught {
+                    if let Some(arg) = args.get(1) {
+                        self.add_evaluation_steps(arg, cell, context, level + 1, steps);
+                    }
+                }
+            }
+            _ => {
+                for child in children {
+                    self.add_evaluation_steps(child, cell, context, level + 1, steps);
+                }
+            }
+        }
+        // Sub-expressions with LET or LAMBDA variables only make sense within the whole formula
+        if level > 0 && uses_variables(node) {
+            return;
+        }
+        let result = if !self.is_volatile(node) {
+            self.evaluate_node_in_context(node, cell)
+        } else if level == 0 {
+            // The value the cell got in the last evaluation
+            self.evaluate_cell(cell)
+        } else {
+            return;
+        };
+        let step_into = match node {
+            Node::ReferenceKind {
+                sheet_index,
+                absolute_row,
+                absolute_column,
+                row,
+                column,
+                ..
+            } => {
+                let reference = CellReferenceIndex {
+                    sheet: *sheet_index,
+                    row: if *absolute_row { *row } else { *row + cell.row },
+                    column: if *absolute_column {
+                        *column
+                    } else {
+                        *column + cell.column
+                    },
+                };
+                let has_formula = self
+                    .workbook
+                    .worksheet(reference.sheet)
+                    .ok()
+                    .and_then(|ws| ws.cell(reference.row, reference.column))
+                    .is_some_and(|c| c.get_formula().is_some());
+                has_formula.then_some(reference)
+            }
+            _ => None,
+        };
+        let expression = if self.workbook.settings.r1c1 {
+            to_localized_r1c1_string(node, self.locale, self.language)
+        } else {
+            to_localized_string(node, context, self.locale, self.language)
+        };
+        steps.push(EvaluationStep {
+            expression,
+            value: self.to_evaluation_value(result),
+            level,
+            step_into,
+        });
+    }
+
+    fn reference_to_string(&self, cell: &CellReferenceIndex) -> String {
+        let sheet_name = self
+            .workbook
+            .worksheet(cell.sheet)
+            .map(|ws| ws.get_name())
+            .unwrap_or_default();
+        let column = number_to_column(cell.column).unwrap_or_default();
+        format!("{sheet_name}!{column}{}", cell.row)
+    }
+
+    fn to_evaluation_value(&self, result: CalcResult) -> EvaluationValue {
+        match result {
+            CalcResult::String(value) => EvaluationValue::Text { value },
+            CalcResult::Number(value) => EvaluationValue::Number { value },
+            CalcResult::Boolean(value) => EvaluationValue::Boolean { value },
+            CalcResult::Error {
+                error,
+                origin,
+                message,
+            } => EvaluationValue::Error {
+                error: error.to_localized_error_string(self.language),
+                origin: self.reference_to_string(&origin),
+                message,
+            },
+            CalcResult::Range { left, right } => {
+                let mut reference = self.reference_to_string(&left);
+                if left != right {
+                    let column = number_to_column(right.column).unwrap_or_default();
+                    reference = format!("{reference}:{column}{}", right.row);
+                }
+                EvaluationValue::Range { reference }
+            }
+            CalcResult::EmptyCell | CalcResult::EmptyArg => EvaluationValue::Empty,
+            CalcResult::Array(rows) => EvaluationValue::Array {
+                values: rows
+                    .into_iter()
+                    .map(|row| {
+                        row.into_iter()
+                            .map(|value| match value {
+                                ArrayNode::Boolean(value) => EvaluationValue::Boolean { value },
+                                ArrayNode::Number(value) => EvaluationValue::Number { value },
+                                ArrayNode::String(value) => EvaluationValue::Text { value },
+                                ArrayNode::Error(error) => EvaluationValue::Error {
+                                    error: error.to_localized_error_string(self.language),
+                                    origin: String::new(),
+                                    message: String::new(),
+                                },
+                                ArrayNode::Empty => EvaluationValue::Empty,
+                            })
+                            .collect()
+                    })
+                    .collect(),
+            },
+            CalcResult::Lambda(_) => EvaluationValue::Lambda,
+        }
+    }
+}
@@ -4,7 +4,7 @@
 //!
 //! You will need to instantiate it with a language and a locale.
 //!
-//! It supports two working modes:
+//! It supports three working modes:
 //!
 //! 1. A1 or display mode
 //!    This is for user formulas. References are like `D4`, `D$4` or `F5:T10`
@@ -13,6 +13,9 @@
 //!    R[2]C[5] refers to a cell two rows below and five columns to the right
 //!    It uses the 'en' locale and language.
 //!    This is used internally at runtime.
+//! 3. Localized R1C1 mode
+//!    For user formulas when the workbook is in R1C1 reference style. References are like `RC[-1]`,
+//!    `R2C3`, `R2` (a whole row) or `C` (the whole column), with the letters of the language.
 //!
 //! Formulas look different in different locales:
 //!
@@ -97,6 +100,9 @@ fn is_valid_r1c1_identifier(name: &str, next_char: Option<char>) -> bool {
 pub enum LexerMode {
     A1,
     R1C1,
+    /// The R1C1 style typed by the user, like `RC[-1]`, `R2` or `C`,
+    /// with the row and column letters of the language in any case
+    LocalizedR1C1,
 }
 
 /// Tokenize an input
@@ -220,7 +226,10 @@ impl<'a> Lexer<'a> {
                     '=' => TokenType::Compare(OpCompare::Equal),
                     '{' => TokenType::LeftBrace,
                     '}' => TokenType::RightBrace,
-                    '[' => TokenType::LeftBracket,
+                    '[' => match self.consume_external_sheet_name() {
+                        Some(sheet_name) => self.consume_range(Some(sheet_name)),
+                        None => TokenType::LeftBracket,
+                    },
                     ']' => TokenType::RightBracket,
                     ':' => TokenType::Colon,
                     ';' => TokenType::Semicolon,
@@ -757,7 +766,7 @@ impl<'a> Lexer<'a> {
     fn consume_absolute_reference(&mut self) -> TokenType {
         // This is an absolute reference.
         // $A$4
-        if self.mode == LexerMode::R1C1 {
+        if self.mode != LexerMode::A1 {
             return TokenType::Illegal(
                 self.set_error("Cannot parse A1 reference in R1C1 mode", self.position),
             );
@@ -781,6 +790,39 @@ impl<'a> Lexer<'a> {
         self.consume_range(Some(sheet_name))
     }
 
+    // Consumes the workbook and sheet of an external reference without quotes:
+    // [Budget.xlsx]Sheet1!A1
+    // Returns the sheet name including the workbook, "[Budget.xlsx]Sheet1", and leaves the position
+    // after the '!'. If it is not an external reference the position is not changed.
+    // Quoted references like '[My Budget.xlsx]Sheet 1'!A1 are read as a quoted sheet reference.
+    fn consume_external_sheet_name(&mut self) -> Option<String> {
+        let start = self.position;
+        let mut position = start;
+        while position < self.len && self.chars[position] != ']' {
+            position += 1;
+        }
+        if position == start || position == self.len {
+            return None;
+        }
+        let workbook: String = self.chars[start..position].iter().collect();
+        position += 1;
+        let sheet_start = position;
+        while position < self.len {
+            let next_char = self.chars[position];
+            if next_char.is_alphanumeric() || next_char == '_' || next_char == '.' {
+                position += 1;
+            } else {
+                break;
+            }
+        }
+        if position == sheet_start || position == self.len || self.chars[position] != '!' {
+            return None;
+        }
+        let sheet: String = self.chars[sheet_start..position].iter().collect();
+        self.position = position + 1;
+        Some(format!("[{workbook}]{sheet}"))
+    }
+
     fn consume_range(&mut self, sheet: Option<String>) -> TokenType {
         let m = if self.mode == LexerMode::A1 {
             self.consume_range_a1()
@@ -1,9 +1,17 @@
 use crate::constants::{LAST_COLUMN, LAST_ROW};
 use crate::expressions::{token::TokenType, utils::column_to_number};
 
-use super::Lexer;
+use super::{Lexer, LexerMode};
 use super::{ParsedRange, ParsedReference, Result};
 
+/// One half of a reference in the R1C1 style typed by the user, `R3` is the absolute row 3
+/// and `R[-1]` or `R` are relative rows.
+#[derive(Clone, Copy)]
+struct Coordinate {
+    index: i32,
+    absolute: bool,
+}
+
 impl<'a> Lexer<'a> {
     /// Consumes a reference in A1 style like:
     /// AS23, $AS23, AS$23, $AS$23, R12
@@ -210,6 +218,9 @@ impl<'a> Lexer<'a> {
     /// Consumes a range of references in R1C1 style like:
     /// R12C3:R23C4, R[2]C[-2]:R[3]C[6], R3C[6]:R[-3]C4, R[-2]C:R[-2]C
     pub(super) fn consume_range_r1c1(&mut self) -> Result<ParsedRange> {
+        if self.mode == LexerMode::LocalizedR1C1 {
+            return self.consume_localized_range_r1c1();
+        }
         // first let's try to parse a cell
         match self.consume_reference_r1c1() {
             Ok(cell) => {
@@ -324,4 +335,139 @@ impl<'a> Lexer<'a> {
             absolute_row,
         })
     }
+
+    /// Consumes the letter of a row or a column in any case followed by `[n]`, `n` or nothing
+    fn consume_localized_coordinate(&mut self, letter: &str) -> Option<Coordinate> {
+        let start = self.position;
+        for expected in letter.chars() {
+            match self.read_next_char() {
+                Some(c) if c.to_uppercase().eq(expected.to_uppercase()) => {}
+                _ => {
+                    self.position = start;
+                    return None;
+                }
+            }
+        }
+        let bracketed = self.peek_char() == Some('[');
+        if bracketed {
+            self.position += 1;
+        }
+        let digits_start = self.position;
+        if bracketed && self.peek_char() == Some('-') {
+            self.position += 1;
+        }
+        while self.peek_char().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let digits: String = self.chars[digits_start..self.position].iter().collect();
+        if bracketed {
+            if self.read_next_char() != Some(']') {
+                self.position = start;
+                return None;
+            }
+            return match digits.parse::<i32>() {
+                Ok(index) => Some(Coordinate {
+                    index,
+                    absolute: false,
+                }),
+                Err(_) => {
+                    self.position = start;
+                    None
+                }
+            };
+        }
+        match digits.parse::<i32>() {
+            Ok(index) => Some(Coordinate {
+                index,
+                absolute: true,
+            }),
+            Err(_) if digits.is_empty() => Some(Coordinate {
+                index: 0,
+                absolute: false,
+            }),
+            Err(_) => {
+                self.position = start;
+                None
+            }
+        }
+    }
+
+    /// Consumes `RC[-1]`, `R2C3`, `R[1]` or `C` with the letters of the language.
+    /// Returns the row and the column, one of them is missing for whole rows and columns.
+    fn consume_localized_reference_r1c1(
+        &mut self,
+    ) -> Result<(Option<Coordinate>, Option<Coordinate>)> {
+        let language = self.language;
+        let row = self.consume_localized_coordinate(&language.references.row);
+        let column = self.consume_localized_coordinate(&language.references.column);
+        if row.is_none() && column.is_none() {
+            return Err(self.set_error("Expected R1C1 reference", self.position));
+        }
+        if row.is_some_and(|r| r.absolute && !(1..=LAST_ROW).contains(&r.index)) {
+            return Err(self.set_error("Row out of range in reference", self.position));
+        }
+        if column.is_some_and(|c| c.absolute && !(1..=LAST_COLUMN).contains(&c.index)) {
+            return Err(self.set_error("Column out of range in reference", self.position));
+        }
+        Ok((row, column))
+    }
+
+    /// Consumes a range in the R1C1 style typed by the user (see [LexerMode::LocalizedR1C1]):
+    /// RC[-1], R2C3:R[1]C[1], R2 or R[-1]:R (whole rows), C3 or C[-1]:C (whole columns)
+    fn consume_localized_range_r1c1(&mut self) -> Result<ParsedRange> {
+        let (row1, column1) = self.consume_localized_reference_r1c1()?;
+        let (mut row2, mut column2) = (row1, column1);
+        let mut is_range = false;
+        if self.peek_char() == Some(':') {
+            let position = self.position;
+            self.position += 1;
+            match self.consume_localized_reference_r1c1() {
+                Ok((row, column))
+                    if row.is_some() == row1.is_some() && column.is_some() == column1.is_some() =>
+                {
+                    (row2, column2) = (row, column);
+                    is_range = true;
+                }
+                // This could be the range operator: R1C1:OFFSET(...)
+                _ => self.position = position,
+            }
+        }
+        let reference = |row: Coordinate, column: Coordinate| ParsedReference {
+            row: row.index,
+            absolute_row: row.absolute,
+            column: column.index,
+            absolute_column: column.absolute,
+        };
+        let first_row = Coordinate {
+            index: 1,
+            absolute: true,
+        };
+        let last_row = Coordinate {
+            index: LAST_ROW,
+            absolute: true,
+        };
+        let first_column = Coordinate {
+            index: 1,
+            absolute: true,
+        };
+        let last_column = Coordinate {
+            index: LAST_COLUMN,
+            absolute: true,
+        };
+        match (row1, column1, row2, column2) {
+            (Some(row1), Some(column1), Some(row2), Some(column2)) => Ok(ParsedRange {
+                left: reference(row1, column1),
+                right: is_range.then(|| reference(row2, column2)),
+            }),
+            (Some(row1), None, Some(row2), None) => Ok(ParsedRange {
+                left: reference(row1, first_column),
+                right: Some(reference(row2, last_column)),
+            }),
+            (None, Some(column1), None, Some(column2)) => Ok(ParsedRange {
+                left: reference(first_row, column1),
+                right: Some(reference(last_row, column2)),
+            }),
+            _ => Err(self.set_error("Expected R1C1 reference", self.position)),
+        }
+    }
 }
@@ -2,6 +2,7 @@ mod test_common;
 mod test_implicit_intersection;
 mod test_language;
 mod test_locale;
+mod test_localized_r1c1;
 mod test_ranges;
 mod test_spill_operator;
 mod test_tables;
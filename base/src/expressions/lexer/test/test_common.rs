@@ -220,6 +220,39 @@ fn test_reference_sheet_unicode_absolute() {
     assert_eq!(lx.next_token(), EOF);
 }
 
+#[test]
+fn test_reference_external_workbook() {
+    let mut lx = new_lexer("[Budget.xlsx]Sheet1!$C$34", true);
+    assert_eq!(
+        lx.next_token(),
+        Reference {
+            sheet: Some("[Budget.xlsx]Sheet1".to_string()),
+            column: 3,
+            row: 34,
+            absolute_column: true,
+            absolute_row: true,
+        }
+    );
+    assert_eq!(lx.next_token(), EOF);
+
+    let mut lx = new_lexer("'[My Budget.xlsx]Sheet 1'!C34", true);
+    assert_eq!(
+        lx.next_token(),
+        Reference {
+            sheet: Some("[My Budget.xlsx]Sheet 1".to_string()),
+            column: 3,
+            row: 34,
+            absolute_column: false,
+            absolute_row: false,
+        }
+    );
+    assert_eq!(lx.next_token(), EOF);
+
+    // Not an external reference
+    let mut lx = new_lexer("[Budget.xlsx]", true);
+    assert_eq!(lx.next_token(), LeftBracket);
+}
+
 #[test]
 fn test_unmatched_quote() {
     let mut lx = new_lexer("'A €!$C$34", true);
@@ -0,0 +1,130 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::constants::{LAST_COLUMN, LAST_ROW};
+use crate::expressions::{
+    lexer::{Lexer, LexerMode},
+    token::{OpProduct, OpSum, TokenType::*},
+    types::ParsedReference,
+};
+use crate::language::get_language;
+use crate::locale::get_default_locale;
+
+fn new_lexer<'a>(formula: &str, language: &str) -> Lexer<'a> {
+    let locale = get_default_locale();
+    let language = get_language(language).unwrap();
+    Lexer::new(formula, LexerMode::LocalizedR1C1, locale, language)
+}
+
+fn reference(row: i32, absolute_row: bool, column: i32, absolute_column: bool) -> ParsedReference {
+    ParsedReference {
+        row,
+        absolute_row,
+        column,
+        absolute_column,
+    }
+}
+
+#[test]
+fn cells() {
+    let mut lx = new_lexer("RC[-1]+r2c3*R[1]C", "en");
+    assert_eq!(
+        lx.next_token(),
+        Reference {
+            sheet: None,
+            row: 0,
+            column: -1,
+            absolute_row: false,
+            absolute_column: false,
+        }
+    );
+    assert_eq!(lx.next_token(), Addition(OpSum::Add));
+    assert_eq!(
+        lx.next_token(),
+        Reference {
+            sheet: None,
+            row: 2,
+            column: 3,
+            absolute_row: true,
+            absolute_column: true,
+        }
+    );
+    assert_eq!(lx.next_token(), Product(OpProduct::Times));
+    assert_eq!(
+        lx.next_token(),
+        Reference {
+            sheet: None,
+            row: 1,
+            column: 0,
+            absolute_row: false,
+            absolute_column: false,
+        }
+    );
+    assert_eq!(lx.next_token(), EOF);
+}
+
+#[test]
+fn whole_rows_and_columns() {
+    let mut lx = new_lexer("R2 C[-1]:C Sheet2!R[-1]:R", "en");
+    assert_eq!(
+        lx.next_token(),
+        Range {
+            sheet: None,
+            left: reference(2, true, 1, true),
+            right: reference(2, true, LAST_COLUMN, true),
+        }
+    );
+    assert_eq!(
+        lx.next_token(),
+        Range {
+            sheet: None,
+            left: reference(1, true, -1, false),
+            right: reference(LAST_ROW, true, 0, false),
+        }
+    );
+    assert_eq!(
+        lx.next_token(),
+        Range {
+            sheet: Some("Sheet2".to_string()),
+            left: reference(-1, false, 1, true),
+            right: reference(0, false, LAST_COLUMN, true),
+        }
+    );
+    assert_eq!(lx.next_token(), EOF);
+}
+
+#[test]
+fn letters_of_the_language() {
+    let mut lx = new_lexer("SUMME(Z[-2]S:z[-1]s)", "de");
+    assert_eq!(lx.next_token(), Ident("SUMME".to_string()));
+    assert_eq!(lx.next_token(), LeftParenthesis);
+    assert_eq!(
+        lx.next_token(),
+        Range {
+            sheet: None,
+            left: reference(-2, false, 0, false),
+            right: reference(-1, false, 0, false),
+        }
+    );
+    assert_eq!(lx.next_token(), RightParenthesis);
+    assert_eq!(lx.next_token(), EOF);
+}
+
+#[test]
+fn identifiers() {
+    let mut lx = new_lexer("Revenue+Rate(1)+C3D", "en");
+    assert_eq!(lx.next_token(), Ident("Revenue".to_string()));
+    assert_eq!(lx.next_token(), Addition(OpSum::Add));
+    assert_eq!(lx.next_token(), Ident("Rate".to_string()));
+    assert_eq!(lx.next_token(), LeftParenthesis);
+    assert_eq!(lx.next_token(), Number(1.0));
+    assert_eq!(lx.next_token(), RightParenthesis);
+    assert_eq!(lx.next_token(), Addition(OpSum::Add));
+    assert_eq!(lx.next_token(), Ident("C3D".to_string()));
+    assert_eq!(lx.next_token(), EOF);
+}
+
+#[test]
+fn a1_references_are_not_references() {
+    let mut lx = new_lexer("$A$1", "en");
+    assert!(matches!(lx.next_token(), Illegal(_)));
+}
@@ -88,6 +88,16 @@ fn get_table_column_by_name(table_column_name: &str, table: &Table) -> Option<i3
     None
 }
 
+/// Splits the sheet name of an external reference, "[Budget.xlsx]Sheet1", into the workbook and
+/// the sheet. Sheet names cannot contain brackets so local sheets never match.
+pub(crate) fn split_external_sheet_name(name: &str) -> Option<(String, String)> {
+    let (workbook, sheet) = name.strip_prefix('[')?.split_once(']')?;
+    if workbook.is_empty() || sheet.is_empty() {
+        return None;
+    }
+    Some((workbook.to_string(), sheet.to_string()))
+}
+
 // DefinedNameS is a tuple with the name of the defined name, the index of the sheet and the formula
 pub type DefinedNameS = (String, Option<u32>, String);
 
@@ -160,6 +170,28 @@ pub enum Node {
         row2: i32,
         column2: i32,
     },
+    /// A reference to a cell in another workbook: `[Budget.xlsx]Sheet1!A1`
+    ExternalReferenceKind {
+        workbook: String,
+        sheet_name: String,
+        absolute_row: bool,
+        absolute_column: bool,
+        row: i32,
+        column: i32,
+    },
+    /// A range in another workbook: `[Budget.xlsx]Sheet1!A1:B5`
+    ExternalRangeKind {
+        workbook: String,
+        sheet_name: String,
+        absolute_row1: bool,
+        absolute_column1: bool,
+        row1: i32,
+        column1: i32,
+        absolute_row2: bool,
+        absolute_column2: bool,
+        row2: i32,
+        column2: i32,
+    },
     OpRangeKind {
         left: Box<Node>,
         right: Box<Node>,
@@ -301,6 +333,10 @@ impl<'a> Parser<'a> {
         self.defined_names = defined_names;
     }
 
+    pub fn set_tables(&mut self, tables: HashMap<String, Table>) {
+        self.tables = tables;
+    }
+
     pub fn parse(&mut self, formula: &str, context: &CellReferenceRC) -> Node {
         self.lexer.set_formula(formula);
         self.context = context.clone();
@@ -698,6 +734,18 @@ impl<'a> Parser<'a> {
                 } else {
                     column - context.column
                 };
+                if let Some((workbook, sheet_name)) =
+                    sheet.as_deref().and_then(split_external_sheet_name)
+                {
+                    return Node::ExternalReferenceKind {
+                        workbook,
+                        sheet_name,
+                        row,
+                        column,
+                        absolute_row,
+                        absolute_column,
+                    };
+                }
                 match sheet_index {
                     Some(index) => Node::ReferenceKind {
                         sheet_name: sheet,
@@ -758,6 +806,22 @@ impl<'a> Parser<'a> {
                     };
                 }
 
+                if let Some((workbook, sheet_name)) =
+                    sheet.as_deref().and_then(split_external_sheet_name)
+                {
+                    return Node::ExternalRangeKind {
+                        workbook,
+                        sheet_name,
+                        row1,
+                        column1,
+                        row2,
+                        column2,
+                        absolute_column1,
+                        absolute_column2,
+                        absolute_row1,
+                        absolute_row2,
+                    };
+                }
                 match sheet_index {
                     Some(index) => Node::RangeKind {
                         sheet_name: sheet,
@@ -370,6 +370,87 @@ fn to_string_moved(
             );
             format!("{s1}:{s2}")
         }
+        ExternalReferenceKind {
+            workbook,
+            sheet_name,
+            absolute_row,
+            absolute_column,
+            row,
+            column,
+        } => {
+            // References to other workbooks are not displaced
+            let context = CellReferenceRC {
+                sheet: move_context.source_sheet_name.to_string(),
+                column: move_context.column,
+                row: move_context.row,
+            };
+            stringify_reference(
+                Some(&context),
+                &DisplaceData::None,
+                &Reference {
+                    sheet_name: &Some(format!("[{workbook}]{sheet_name}")),
+                    sheet_index: 0, // HACK
+                    row: *row,
+                    column: *column,
+                    absolute_row: *absolute_row,
+                    absolute_column: *absolute_column,
+                },
+                false,
+                false,
+            )
+        }
+        ExternalRangeKind {
+            workbook,
+            sheet_name,
+            absolute_row1,
+            absolute_column1,
+            row1,
+            column1,
+            absolute_row2,
+            absolute_column2,
+            row2,
+            column2,
+        } => {
+            let full_row = *absolute_row1 && *absolute_row2 && (*row1 == 1) && (*row2 == LAST_ROW);
+            let full_column = *absolute_column1
+                && *absolute_column2
+                && (*column1 == 1)
+                && (*column2 == LAST_COLUMN);
+            let context = CellReferenceRC {
+                sheet: move_context.source_sheet_name.to_string(),
+                column: move_context.column,
+                row: move_context.row,
+            };
+            let s1 = stringify_reference(
+                Some(&context),
+                &DisplaceData::None,
+                &Reference {
+                    sheet_name: &Some(format!("[{workbook}]{sheet_name}")),
+                    sheet_index: 0, // HACK
+                    row: *row1,
+                    column: *column1,
+                    absolute_row: *absolute_row1,
+                    absolute_column: *absolute_column1,
+                },
+                full_row,
+                full_column,
+            );
+            let s2 = stringify_reference(
+                Some(&context),
+                &DisplaceData::None,
+                &Reference {
+                    sheet_name: &None,
+                    sheet_index: 0, // HACK
+                    row: *row2,
+                    column: *column2,
+                    absolute_row: *absolute_row2,
+                    absolute_column: *absolute_column2,
+                },
+                full_row,
+                full_column,
+            );
+            format!("{s1}:{s2}")
+        }
         OpRangeKind { left, right } => format!(
             "{}:{}",
             to_string_moved(left, move_context, locale, language),
@@ -1,4 +1,7 @@
-use crate::functions::Function;
+use crate::{
+    calc_result::Range, expressions::types::CellReferenceIndex, functions::Function,
+    implicit_intersection::implicit_intersection,
+};
 
 use super::Node;
 
@@ -75,6 +78,8 @@ pub fn add_implicit_intersection(node: &mut Node, add: bool) {
         | Node::NamedFunctionKind { .. }
         | Node::ArrayKind(_)
         | Node::ReferenceKind { .. }
+        | Node::ExternalReferenceKind { .. }
+        | Node::ExternalRangeKind { .. }
         | Node::LambdaDefKind { .. }
         | Node::LambdaCallKind { .. } => {}
         Node::ImplicitIntersection { child, .. } => {
@@ -200,7 +205,131 @@ pub fn add_implicit_intersection(node: &mut Node, add: bool) {
     };
 }
 
+/// Calls `f` on the node and then on all its sub-expressions, depth first.
+pub fn walk_nodes_mut(node: &mut Node, f: &mut impl FnMut(&mut Node)) {
+    f(node);
+    match node {
+        Node::OpRangeKind { left, right }
+        | Node::OpConcatenateKind { left, right }
+        | Node::OpSumKind { left, right, .. }
+        | Node::OpProductKind { left, right, .. }
+        | Node::OpPowerKind { left, right }
+        | Node::CompareKind { left, right, .. } => {
+            walk_nodes_mut(left, f);
+            walk_nodes_mut(right, f);
+        }
+        Node::UnaryKind { right, .. } => walk_nodes_mut(right, f),
+        Node::ImplicitIntersection { child, .. } | Node::SpillRangeOperator { child } => {
+            walk_nodes_mut(child, f)
+        }
+        Node::FunctionKind { args, .. } | Node::NamedFunctionKind { args, .. } => {
+            for arg in args {
+                walk_nodes_mut(arg, f);
+            }
+        }
+        Node::LambdaDefKind { body, .. } => walk_nodes_mut(body, f),
+        Node::LambdaCallKind { lambda, args } => {
+            walk_nodes_mut(lambda, f);
+            for arg in args {
+                walk_nodes_mut(arg, f);
+            }
+        }
+        Node::BooleanKind(_)
+        | Node::NumberKind(_)
+        | Node::StringKind(_)
+        | Node::ReferenceKind { .. }
+        | Node::RangeKind { .. }
+        | Node::WrongReferenceKind { .. }
+        | Node::WrongRangeKind { .. }
+        | Node::ExternalReferenceKind { .. }
+        | Node::ExternalRangeKind { .. }
+        | Node::ArrayKind(_)
+        | Node::DefinedNameKind(_)
+        | Node::TableNameKind(_)
+        | Node::NamedVariableKind { .. }
+        | Node::ErrorKind(_)
+        | Node::ParseErrorKind { .. }
+        | Node::EmptyArgKind => {}
+    }
+}
+
+/// Returns true if the node is an `@` typed by the user over a range that does not share
+/// a row or a column with `cell`. The intersection is empty and the result is `#VALUE!`.
+fn is_failing_implicit_intersection(node: &Node, cell: &CellReferenceIndex) -> bool {
+    let child = match node {
+        Node::ImplicitIntersection {
+            automatic: false,
+            child,
+        } => child,
+        _ => return false,
+    };
+    match child.as_ref() {
+        Node::RangeKind {
+            sheet_index,
+            absolute_row1,
+            absolute_column1,
+            row1,
+            column1,
+            absolute_row2,
+            absolute_column2,
+            row2,
+            column2,
+            ..
+        } => {
+            let left = CellReferenceIndex {
+                sheet: *sheet_index,
+                row: if *absolute_row1 {
+                    *row1
+                } else {
+                    row1 + cell.row
+                },
+                column: if *absolute_column1 {
+                    *column1
+                } else {
+                    column1 + cell.column
+                },
+            };
+            let right = CellReferenceIndex {
+                sheet: *sheet_index,
+                row: if *absolute_row2 {
+                    *row2
+                } else {
+                    row2 + cell.row
+                },
+                column: if *absolute_column2 {
+                    *column2
+                } else {
+                    column2 + cell.column
+                },
+            };
+            implicit_intersection(cell, &Range { left, right }).is_none()
+        }
+        _ => false,
+    }
+}
+
+/// Removes the `@` operators that can never find a cell from `cell`, so the range spills
+/// as a dynamic array instead. Returns true if any operator was removed.
+///
+/// `=@B1:B3` typed in `D7` is `#VALUE!`, while `=B1:B3` returns the three values.
+pub(crate) fn remove_failing_implicit_intersections(
+    node: &mut Node,
+    cell: &CellReferenceIndex,
+) -> bool {
+    let mut removed = false;
+    walk_nodes_mut(node, &mut |n| {
+        if is_failing_implicit_intersection(n, cell) {
+            if let Node::ImplicitIntersection { child, .. } = n {
+                *n = child.as_ref().clone();
+                removed = true;
+            }
+        }
+    });
+    removed
+}
+
 /// The result of the static analysis of a node
+#[derive(Clone)]
 pub enum StaticResult {
     // The result of the evaluation is a single value (number, string, boolean, error)
     Scalar,
@@ -262,8 +391,8 @@ pub(crate) fn run_static_analysis_on_node(node: &Node) -> StaticResult {
             StaticResult::Scalar
         }
         Node::NamedFunctionKind { .. } => {
-            // StaticResult::Unknown is also valid
-            StaticResult::Scalar
+            // LAMBDAs in defined names and functions registered by the host can return arrays
+            StaticResult::Unknown
         }
         Node::ArrayKind(array) => {
             let n = array.len() as i32;
@@ -282,6 +411,11 @@ pub(crate) fn run_static_analysis_on_node(node: &Node) -> StaticResult {
             StaticResult::Unknown
         }
         Node::ReferenceKind { .. } => StaticResult::Scalar,
+        Node::ExternalReferenceKind { .. } => StaticResult::Scalar,
+        Node::ExternalRangeKind { .. } => {
+            // Ranges in other workbooks evaluate to arrays
+            StaticResult::Unknown
+        }
 
         // binary operations
         Node::OpConcatenateKind { left, right }
@@ -951,6 +1085,7 @@ fn get_function_args_signature(kind: &Function, arg_count: usize) -> Vec<Signatu
         Function::Xlookup => args_signature_xlookup(arg_count),
         Function::Xmatch => args_signature_xmatch(arg_count),
         Function::Trimrange => args_signature_trimrange(arg_count),
+        Function::Table => vec![Signature::Vector; arg_count],
         Function::Sort => args_signature_sort(arg_count),
         Function::Sortby => args_signature_sortby(arg_count),
         Function::Unique => args_signature_unique(arg_count),
@@ -1573,6 +1708,7 @@ fn static_analysis_on_function(kind: &Function, args: &[Node]) -> StaticResult {
         Function::Xlookup => not_implemented(args),
         Function::Xmatch => not_implemented(args),
         Function::Trimrange => StaticResult::Unknown,
+        Function::Table => StaticResult::Unknown,
         Function::Sort => StaticResult::Unknown,
         Function::Sortby => StaticResult::Unknown,
         Function::Unique => StaticResult::Unknown,
@@ -1,7 +1,7 @@
 use super::{super::utils::quote_name, Node, Reference};
 use crate::constants::{LAST_COLUMN, LAST_ROW};
 use crate::expressions::parser::move_formula::to_string_array_node;
-use crate::expressions::parser::static_analysis::add_implicit_intersection;
+use crate::expressions::parser::static_analysis::{add_implicit_intersection, walk_nodes_mut};
 use crate::expressions::token::{OpSum, OpUnary};
 use crate::language::{get_language, Language};
 use crate::locale::{get_locale, Locale};
@@ -18,16 +18,20 @@ pub enum DisplaceData {
         row: i32,
         delta: i32,
     },
+    /// Cells in the rows `row..row + height` at or to the right of `column` move `delta` columns
     CellHorizontal {
         sheet: u32,
         row: i32,
+        height: i32,
         column: i32,
         delta: i32,
     },
+    /// Cells in the columns `column..column + width` at or below `row` move `delta` rows
     CellVertical {
         sheet: u32,
         row: i32,
         column: i32,
+        width: i32,
         delta: i32,
     },
     RowMove {
@@ -43,6 +47,17 @@ pub enum DisplaceData {
     None,
 }
 
+/// How references are written when stringifying a formula
+#[derive(Clone, Copy)]
+enum Notation<'a> {
+    /// A1 style, relative to the cell the formula lives in
+    A1(&'a CellReferenceRC),
+    /// The internal R1C1 style (`R[-1]C[0]`), always in English
+    Internal,
+    /// The R1C1 style shown to the user (`R[-1]C`) with the row and column letters of the language
+    R1C1,
+}
+
 /// This is the internal mode in IronCalc
 /// Formulas internally are stored in R1C1 format, the locale and language are always "en"
 pub fn to_rc_format(node: &Node) -> String {
@@ -50,7 +65,14 @@ pub fn to_rc_format(node: &Node) -> String {
     let locale = get_locale("en").expect("");
     #[allow(clippy::expect_used)]
     let language = get_language("en").expect("");
-    stringify(node, None, &DisplaceData::None, false, locale, language)
+    stringify(
+        node,
+        Notation::Internal,
+        &DisplaceData::None,
+        false,
+        locale,
+        language,
+    )
 }
 
 pub fn to_english_string(node: &Node, context: &CellReferenceRC) -> String {
@@ -60,7 +82,7 @@ pub fn to_english_string(node: &Node, context: &CellReferenceRC) -> String {
     let language = get_language("en").expect("");
     stringify(
         node,
-        Some(context),
+        Notation::A1(context),
         &DisplaceData::None,
         false,
         locale,
@@ -77,7 +99,7 @@ pub fn to_localized_string(
 ) -> String {
     stringify(
         node,
-        Some(context),
+        Notation::A1(context),
         &DisplaceData::None,
         false,
         locale,
@@ -94,7 +116,7 @@ pub fn to_excel_string(node: &Node, context: &CellReferenceRC) -> String {
     let language = get_language("en").expect("");
     stringify(
         node,
-        Some(context),
+        Notation::A1(context),
         &DisplaceData::None,
         true,
         locale,
@@ -102,6 +124,18 @@ pub fn to_excel_string(node: &Node, context: &CellReferenceRC) -> String {
     )
 }
 
+/// This is the mode used to display the formula in the UI when the workbook is in R1C1 mode
+pub fn to_localized_r1c1_string(node: &Node, locale: &Locale, language: &Language) -> String {
+    stringify(
+        node,
+        Notation::R1C1,
+        &DisplaceData::None,
+        false,
+        locale,
+        language,
+    )
+}
+
 pub fn to_string_displaced(
     node: &Node,
     context: &CellReferenceRC,
@@ -111,7 +145,14 @@ pub fn to_string_displaced(
     let locale = get_locale("en").expect("");
     #[allow(clippy::expect_used)]
     let language = get_language("en").expect("");
-    stringify(node, Some(context), displace_data, false, locale, language)
+    stringify(
+        node,
+        Notation::A1(context),
+        displace_data,
+        false,
+        locale,
+        language,
+    )
 }
 
 /// Converts a local reference to a string applying some displacement if needed.
@@ -179,10 +220,15 @@ pub(crate) fn stringify_reference(
                 DisplaceData::CellHorizontal {
                     sheet,
                     row: displace_row,
+                    height,
                     column: displace_column,
                     delta,
                 } => {
-                    if sheet_index == *sheet && displace_row == &row {
+                    if sheet_index == *sheet
+                        && !full_row
+                        && !full_column
+                        && (*displace_row..*displace_row + *height).contains(&row)
+                    {
                         if *delta < 0 {
                             if &column >= displace_column {
                                 if column < displace_column - *delta {
@@ -199,9 +245,14 @@ pub(crate) fn stringify_reference(
                     sheet,
                     row: displace_row,
                     column: displace_column,
+                    width,
                     delta,
                 } => {
-                    if sheet_index == *sheet && displace_column == &column {
+                    if sheet_index == *sheet
+                        && !full_row
+                        && !full_column
+                        && (*displace_column..*displace_column + *width).contains(&column)
+                    {
                         if *delta < 0 {
                             if &row >= displace_row {
                                 if row < displace_row - *delta {
@@ -317,10 +368,71 @@ pub(crate) fn stringify_reference(
     }
 }
 
+/// Converts a reference to a string in the given notation.
+/// Displacements are only applied in A1 notation.
+fn format_reference(
+    context: Notation,
+    displace_data: &DisplaceData,
+    reference: &Reference,
+    full_row: bool,
+    full_column: bool,
+    language: &Language,
+) -> String {
+    match context {
+        Notation::A1(context) => stringify_reference(
+            Some(context),
+            displace_data,
+            reference,
+            full_row,
+            full_column,
+        ),
+        Notation::Internal => {
+            stringify_reference(None, displace_data, reference, full_row, full_column)
+        }
+        Notation::R1C1 => stringify_reference_r1c1(reference, full_row, full_column, language),
+    }
+}
+
+/// Converts a reference to the R1C1 style shown to the user, like `R[-1]C`, `R2C[3]` or `Sheet2!R1C1`.
+/// If full_row is true only the column is shown (`C[1]`), if full_column is true only the row (`R3`).
+fn stringify_reference_r1c1(
+    reference: &Reference,
+    full_row: bool,
+    full_column: bool,
+    language: &Language,
+) -> String {
+    let row_letter = &language.references.row;
+    let column_letter = &language.references.column;
+    let row = reference.row;
+    let column = reference.column;
+    let row_str = if full_row {
+        "".to_string()
+    } else if reference.absolute_row {
+        format!("{row_letter}{row}")
+    } else if row == 0 {
+        row_letter.to_string()
+    } else {
+        format!("{row_letter}[{row}]")
+    };
+    let column_str = if full_column {
+        "".to_string()
+    } else if reference.absolute_column {
+        format!("{column_letter}{column}")
+    } else if column == 0 {
+        column_letter.to_string()
+    } else {
+        format!("{column_letter}[{column}]")
+    };
+    match &reference.sheet_name {
+        Some(name) => format!("{}!{}{}", quote_name(name), row_str, column_str),
+        None => format!("{row_str}{column_str}"),
+    }
+}
+
 fn format_function(
     name: &str,
     args: &Vec<Node>,
-    context: Option<&CellReferenceRC>,
+    context: Notation,
     displace_data: &DisplaceData,
     export_to_excel: bool,
     locale: &Locale,
@@ -390,7 +502,7 @@ fn format_function(
 
 fn stringify(
     node: &Node,
-    context: Option<&CellReferenceRC>,
+    context: Notation,
     displace_data: &DisplaceData,
     export_to_excel: bool,
     locale: &Locale,
@@ -420,7 +532,7 @@ fn stringify(
             row,
             absolute_row,
             absolute_column,
-        } => stringify_reference(
+        } => format_reference(
             context,
             &DisplaceData::None,
             &Reference {
@@ -433,6 +545,7 @@ fn stringify(
             },
             false,
             false,
+            language,
         ),
         ReferenceKind {
             sheet_name,
@@ -441,7 +554,7 @@ fn stringify(
             row,
             absolute_row,
             absolute_column,
-        } => stringify_reference(
+        } => format_reference(
             context,
             displace_data,
             &Reference {
@@ -454,6 +567,7 @@ fn stringify(
             },
             false,
             false,
+            language,
         ),
         RangeKind {
             sheet_name,
@@ -475,7 +589,7 @@ fn stringify(
                 && *absolute_column2
                 && (*column1 == 1)
                 && (*column2 == LAST_COLUMN);
-            let s1 = stringify_reference(
+            let s1 = format_reference(
                 context,
                 displace_data,
                 &Reference {
@@ -488,8 +602,9 @@ fn stringify(
                 },
                 full_row,
                 full_column,
+                language,
             );
-            let s2 = stringify_reference(
+            let s2 = format_reference(
                 context,
                 displace_data,
                 &Reference {
@@ -502,6 +617,7 @@ fn stringify(
                 },
                 full_row,
                 full_column,
+                language,
             );
             format!("{s1}:{s2}")
         }
@@ -524,7 +640,7 @@ fn stringify(
                 && *absolute_column2
                 && (*column1 == 1)
                 && (*column2 == LAST_COLUMN);
-            let s1 = stringify_reference(
+            let s1 = format_reference(
                 context,
                 &DisplaceData::None,
                 &Reference {
@@ -537,8 +653,80 @@ fn stringify(
                 },
                 full_row,
                 full_column,
+                language,
+            );
+            let s2 = format_reference(
+                context,
+                &DisplaceData::None,
+                &Reference {
+                    sheet_name: &None,
+                    sheet_index: 0, // HACK
+                    row: *row2,
+                    column: *column2,
+                    absolute_row: *absolute_row2,
+                    absolute_column: *absolute_column2,
+                },
+                full_row,
+                full_column,
+                language,
+            );
+            format!("{s1}:{s2}")
+        }
+        ExternalReferenceKind {
+            workbook,
+            sheet_name,
+            column,
+            row,
+            absolute_row,
+            absolute_column,
+        } => format_reference(
+            context,
+            &DisplaceData::None,
+            &Reference {
+                sheet_name: &Some(format!("[{workbook}]{sheet_name}")),
+                sheet_index: 0, // HACK
+                row: *row,
+                column: *column,
+                absolute_row: *absolute_row,
+                absolute_column: *absolute_column,
+            },
+            false,
+            false,
+            language,
+        ),
+        ExternalRangeKind {
+            workbook,
+            sheet_name,
+            absolute_row1,
+            absolute_column1,
+            row1,
+            column1,
+            absolute_row2,
+            absolute_column2,
+            row2,
+            column2,
+        } => {
+            let full_row = *absolute_row1 && *absolute_row2 && (*row1 == 1) && (*row2 == LAST_ROW);
+            let full_column = *absolute_column1
+                && *absolute_column2
+                && (*column1 == 1)
+                && (*column2 == LAST_COLUMN);
+            let s1 = format_reference(
+                context,
+                &DisplaceData::None,
+                &Reference {
+                    sheet_name: &Some(format!("[{workbook}]{sheet_name}")),
+                    sheet_index: 0, // HACK
+                    row: *row1,
+                    column: *column1,
+                    absolute_row: *absolute_row1,
+                    absolute_column: *absolute_column1,
+                },
+                full_row,
+                full_column,
+                language,
             );
-            let s2 = stringify_reference(
+            let s2 = format_reference(
                 context,
                 &DisplaceData::None,
                 &Reference {
@@ -551,6 +739,7 @@ fn stringify(
                 },
                 full_row,
                 full_column,
+                language,
             );
             format!("{s1}:{s2}")
         }
@@ -718,6 +907,8 @@ fn stringify(
                 | ReferenceKind { .. }
                 | RangeKind { .. }
                 | WrongReferenceKind { .. }
+                | ExternalReferenceKind { .. }
+                | ExternalRangeKind { .. }
                 | DefinedNameKind(_)
                 | TableNameKind(_)
                 | NamedVariableKind { .. }
@@ -763,6 +954,8 @@ fn stringify(
                 | ReferenceKind { .. }
                 | RangeKind { .. }
                 | WrongReferenceKind { .. }
+                | ExternalReferenceKind { .. }
+                | ExternalRangeKind { .. }
                 | DefinedNameKind(_)
                 | TableNameKind(_)
                 | NamedVariableKind { .. }
@@ -872,6 +1065,8 @@ fn stringify(
                     | RangeKind { .. }
                     | WrongReferenceKind { .. }
                     | WrongRangeKind { .. }
+                    | ExternalReferenceKind { .. }
+                    | ExternalRangeKind { .. }
                     | OpRangeKind { .. }
                     | OpConcatenateKind { .. }
                     | OpProductKind { .. }
@@ -1184,6 +1379,8 @@ pub(crate) fn rename_sheet_in_node(node: &mut Node, sheet_index: u32, new_name:
         Node::TableNameKind(_) => {}
         Node::NamedVariableKind { .. } => {}
         Node::EmptyArgKind => {}
+        Node::ExternalReferenceKind { .. } => {}
+        Node::ExternalRangeKind { .. } => {}
         Node::LambdaDefKind {
             parameters: _,
             body,
@@ -1287,6 +1484,8 @@ pub(crate) fn rename_defined_name_in_node(
         Node::RangeKind { .. } => {}
         Node::WrongReferenceKind { .. } => {}
         Node::WrongRangeKind { .. } => {}
+        Node::ExternalReferenceKind { .. } => {}
+        Node::ExternalRangeKind { .. } => {}
         Node::TableNameKind(_) => {}
         Node::NamedVariableKind { .. } => {}
         Node::LambdaDefKind {
@@ -1303,3 +1502,70 @@ pub(crate) fn rename_defined_name_in_node(
         }
     }
 }
+
+/// Makes the references point to the cells of another workbook: `Sheet2!A1` becomes
+/// `[Book.xlsx]Sheet2!A1`. References without a sheet name are to cells of `sheet`.
+pub(crate) fn externalize_references_in_node(node: &mut Node, workbook: &str, sheet: &str) {
+    walk_nodes_mut(node, &mut |node| {
+        let external = match node {
+            Node::ReferenceKind {
+                sheet_name,
+                absolute_row,
+                absolute_column,
+                row,
+                column,
+                ..
+            }
+            | Node::WrongReferenceKind {
+                sheet_name,
+                absolute_row,
+                absolute_column,
+                row,
+                column,
+            } => Node::ExternalReferenceKind {
+                workbook: workbook.to_string(),
+                sheet_name: sheet_name.clone().unwrap_or_else(|| sheet.to_string()),
+                absolute_row: *absolute_row,
+                absolute_column: *absolute_column,
+                row: *row,
+                column: *column,
+            },
+            Node::RangeKind {
+                sheet_name,
+                absolute_row1,
+                absolute_column1,
+                row1,
+                column1,
+                absolute_row2,
+                absolute_column2,
+                row2,
+                column2,
+                ..
+            }
+            | Node::WrongRangeKind {
+                sheet_name,
+                absolute_row1,
+                absolute_column1,
+                row1,
+                column1,
+                absolute_row2,
+                absolute_column2,
+                row2,
+                column2,
+            } => Node::ExternalRangeKind {
+                workbook: workbook.to_string(),
+                sheet_name: sheet_name.clone().unwrap_or_else(|| sheet.to_string()),
+                absolute_row1: *absolute_row1,
+                absolute_column1: *absolute_column1,
+                row1: *row1,
+                column1: *column1,
+                absolute_row2: *absolute_row2,
+                absolute_column2: *absolute_column2,
+                row2: *row2,
+                column2: *column2,
+            },
+            _ => return,
+        };
+        *node = external;
+    });
+}
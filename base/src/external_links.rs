@@ -0,0 +1,287 @@
+//! References to cells in other workbooks.
+//!
+//! A formula like `=[Budget.xlsx]Sheet1!A1` or `=SUM('[My Budget.xlsx]Sheet 1'!A1:A10)` refers to a
+//! workbook that is not loaded. The workbook keeps the last known values of those cells
+//! ([ExternalLink]), which are read from the `externalLinkN.xml` parts of an xlsx file.
+//!
+//! The host can implement a [WorkbookResolver] to load the linked workbooks. When the resolver
+//! returns a model its values are used, otherwise the cached values are used. Cells that are not
+//! cached evaluate as empty cells. Evaluating never changes the cached values, the host calls
+//! [Model::update_external_links] to store the live values (e.g. before saving the workbook).
+
+use std::sync::Arc;
+
+use crate::{
+    calc_result::CalcResult,
+    constants::{LAST_COLUMN, LAST_ROW},
+    expressions::{parser::ArrayNode, token::Error, types::CellReferenceIndex},
+    model::Model,
+    types::{ExternalLink, ExternalSheet, ExternalValue},
+};
+
+/// Loads the workbooks referenced by formulas, from disk, a database or another model.
+pub trait WorkbookResolver: Send + Sync {
+    /// Returns the evaluated model of the workbook with the name used in formulas
+    /// (e.g. "Budget.xlsx"), or `None` if it is not available.
+    ///
+    /// It is called at most once per workbook in each evaluation.
+    fn resolve(&self, workbook: &str) -> Option<Model<'static>>;
+}
+
+fn to_external_value(value: CalcResult) -> Option<ExternalValue> {
+    match value {
+        CalcResult::Number(value) => Some(ExternalValue::Number(value)),
+        CalcResult::Boolean(value) => Some(ExternalValue::Boolean(value)),
+        CalcResult::String(value) => Some(ExternalValue::Text(value)),
+        CalcResult::Error { error, .. } => Some(ExternalValue::Error(error)),
+        CalcResult::EmptyCell | CalcResult::EmptyArg => None,
+        CalcResult::Range { .. } | CalcResult::Array(_) | CalcResult::Lambda(_) => {
+            Some(ExternalValue::Error(Error::VALUE))
+        }
+    }
+}
+
+impl Model<'_> {
+    /// Sets the resolver used to load the workbooks referenced by formulas.
+    /// Call [Model::evaluate] to update the values.
+    pub fn set_workbook_resolver(&mut self, resolver: Arc<dyn WorkbookResolver>) {
+        self.workbook_resolver = Some(resolver);
+    }
+
+    /// Removes the resolver. References to other workbooks use the cached values.
+    pub fn remove_workbook_resolver(&mut self) {
+        self.workbook_resolver = None;
+        self.linked_models.clear();
+        self.linked_values.clear();
+    }
+
+    /// Stores in the workbook the values read from the linked workbooks in the last evaluation,
+    /// so they are used when the workbooks cannot be resolved and are saved with the file.
+    pub fn update_external_links(&mut self) {
+        let mut values: Vec<_> = self
+            .linked_values
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        values.sort_by(|a, b| a.0.cmp(&b.0));
+        for ((workbook, sheet, row, column), value) in values {
+            self.set_cached_value(&workbook, &sheet, row, column, value);
+        }
+    }
+
+    /// Returns the linked workbook, asking the resolver the first time
+    fn get_linked_model(&mut self, workbook: &str) -> Option<&Model<'static>> {
+        let resolver = self.workbook_resolver.clone()?;
+        self.linked_models
+            .entry(workbook.to_uppercase())
+            .or_insert_with(|| resolver.resolve(workbook))
+            .as_ref()
+    }
+
+    fn get_external_link_mut(&mut self, workbook: &str) -> &mut ExternalLink {
+        let links = &mut self.workbook.external_links;
+        let index = match links
+            .iter()
+            .position(|link| link.workbook.to_uppercase() == workbook.to_uppercase())
+        {
+            Some(index) => index,
+            None => {
+                links.push(ExternalLink {
+                    workbook: workbook.to_string(),
+                    target: workbook.to_string(),
+                    sheets: Vec::new(),
+                });
+                links.len() - 1
+            }
+        };
+        &mut links[index]
+    }
+
+    fn get_external_sheet(&self, workbook: &str, sheet: &str) -> Option<&ExternalSheet> {
+        self.workbook
+            .external_links
+            .iter()
+            .find(|link| link.workbook.to_uppercase() == workbook.to_uppercase())?
+            .sheets
+            .iter()
+            .find(|s| s.name.to_uppercase() == sheet.to_uppercase())
+    }
+
+    fn set_cached_value(
+        &mut self,
+        workbook: &str,
+        sheet: &str,
+        row: i32,
+        column: i32,
+        value: Option<ExternalValue>,
+    ) {
+        let link = self.get_external_link_mut(workbook);
+        let index = match link
+            .sheets
+            .iter()
+            .position(|s| s.name.to_uppercase() == sheet.to_uppercase())
+        {
+            Some(index) => index,
+            None => {
+                link.sheets.push(ExternalSheet {
+                    name: sheet.to_string(),
+                    cells: Default::default(),
+                });
+                link.sheets.len() - 1
+            }
+        };
+        let cells = &mut link.sheets[index].cells;
+        match value {
+            Some(value) => {
+                cells.entry(row).or_default().insert(column, value);
+            }
+            None => {
+                if let Some(data_row) = cells.get_mut(&row) {
+                    data_row.remove(&column);
+                    if data_row.is_empty() {
+                        cells.remove(&row);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns the value of a cell in another workbook, `None` if it is empty.
+    /// The live value is used if the workbook can be resolved, the cached value otherwise.
+    fn get_external_value(
+        &mut self,
+        workbook: &str,
+        sheet: &str,
+        row: i32,
+        column: i32,
+    ) -> Result<Option<ExternalValue>, String> {
+        if let Some(model) = self.get_linked_model(workbook) {
+            let sheet_index = model
+                .get_sheet_index_by_name(sheet)
+                .ok_or_else(|| format!("Sheet not found: '{sheet}'"))?;
+            let value = match model
+                .workbook
+                .worksheet(sheet_index)
+                .ok()
+                .and_then(|worksheet| worksheet.cell(row, column))
+            {
+                Some(cell) => to_external_value(model.get_cell_value(
+                    cell,
+                    CellReferenceIndex {
+                        sheet: sheet_index,
+                        row,
+                        column,
+                    },
+                )),
+                None => None,
+            };
+            self.linked_values.insert(
+                (workbook.to_string(), sheet.to_string(), row, column),
+                value.clone(),
+            );
+            return Ok(value);
+        }
+        if !self
+            .workbook
+            .external_links
+            .iter()
+            .any(|link| link.workbook.to_uppercase() == workbook.to_uppercase())
+        {
+            return Err(format!("Workbook not found: '{workbook}'"));
+        }
+        let sheet = self
+            .get_external_sheet(workbook, sheet)
+            .ok_or_else(|| format!("Sheet not found: '{sheet}'"))?;
+        Ok(sheet
+            .cells
+            .get(&row)
+            .and_then(|data_row| data_row.get(&column))
+            .cloned())
+    }
+
+    /// Returns the last row and column with data in a sheet of another workbook
+    fn get_external_dimension(&mut self, workbook: &str, sheet: &str) -> (i32, i32) {
+        if let Some(model) = self.get_linked_model(workbook) {
+            if let Some(worksheet) = model
+                .get_sheet_index_by_name(sheet)
+                .and_then(|index| model.workbook.worksheet(index).ok())
+            {
+                let dimension = worksheet.dimension();
+                return (dimension.max_row, dimension.max_column);
+            }
+            return (1, 1);
+        }
+        match self.get_external_sheet(workbook, sheet) {
+            Some(sheet) => {
+                sheet
+                    .cells
+                    .iter()
+                    .fold((1, 1), |(max_row, max_column), (row, data_row)| {
+                        let last_column = data_row.keys().max().copied().unwrap_or(1);
+                        (max_row.max(*row), max_column.max(last_column))
+                    })
+            }
+            None => (1, 1),
+        }
+    }
+
+    pub(crate) fn evaluate_external_reference(
+        &mut self,
+        workbook: &str,
+        sheet: &str,
+        row: i32,
+        column: i32,
+        cell: CellReferenceIndex,
+    ) -> CalcResult {
+        match self.get_external_value(workbook, sheet, row, column) {
+            Ok(Some(ExternalValue::Number(value))) => CalcResult::Number(value),
+            Ok(Some(ExternalValue::Boolean(value))) => CalcResult::Boolean(value),
+            Ok(Some(ExternalValue::Text(value))) => CalcResult::String(value),
+            Ok(Some(ExternalValue::Error(error))) => {
+                CalcResult::new_error(error, cell, "Error in linked workbook".to_string())
+            }
+            Ok(None) => CalcResult::EmptyCell,
+            Err(message) => CalcResult::new_error(Error::REF, cell, message),
+        }
+    }
+
+    /// Ranges in other workbooks evaluate to arrays. Full rows and columns are
+    /// limited to the cells with data.
+    pub(crate) fn evaluate_external_range(
+        &mut self,
+        workbook: &str,
+        sheet: &str,
+        left: (i32, i32),
+        right: (i32, i32),
+        cell: CellReferenceIndex,
+    ) -> CalcResult {
+        let (row1, column1) = left;
+        let (mut row2, mut column2) = right;
+        if row2 == LAST_ROW || column2 == LAST_COLUMN {
+            let (max_row, max_column) = self.get_external_dimension(workbook, sheet);
+            if row2 == LAST_ROW {
+                row2 = max_row.max(row1);
+            }
+            if column2 == LAST_COLUMN {
+                column2 = max_column.max(column1);
+            }
+        }
+        let mut result = Vec::new();
+        for row in row1..=row2 {
+            let mut data_row = Vec::new();
+            for column in column1..=column2 {
+                let value = match self.get_external_value(workbook, sheet, row, column) {
+                    Ok(Some(ExternalValue::Number(value))) => ArrayNode::Number(value),
+                    Ok(Some(ExternalValue::Boolean(value))) => ArrayNode::Boolean(value),
+                    Ok(Some(ExternalValue::Text(value))) => ArrayNode::String(value),
+                    Ok(Some(ExternalValue::Error(error))) => ArrayNode::Error(error),
+                    Ok(None) => ArrayNode::Empty,
+                    Err(message) => return CalcResult::new_error(Error::REF, cell, message),
+                };
+                data_row.push(value);
+            }
+            result.push(data_row);
+        }
+        CalcResult::Array(result)
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Font;
+
+/// Maps the font names stored in a workbook to fonts the front end can actually render.
+///
+/// Workbooks keep the author's font names (Calibri, Aptos, Tahoma, ...) so that they survive
+/// a round trip to xlsx, but those fonts might not be installed where the sheet is drawn.
+/// The lookup order is:
+/// 1. If the font is in `available` it is used as is.
+/// 2. If there is an explicit substitution for it, the substitution is used.
+/// 3. Otherwise the font is rendered with the fallback of its family (serif or monospaced),
+///    or with `default_font` for any other family.
+///
+/// All font name comparisons are case insensitive.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct FontFallback {
+    /// Fonts that can be rendered directly
+    pub available: Vec<String>,
+    /// Explicit substitutions, e.g. "Calibri" => "Carlito"
+    pub substitutions: HashMap<String, String>,
+    /// Fallback for fonts of the serif family (family 1)
+    pub serif_font: String,
+    /// Fallback for fonts of the monospaced family (family 3)
+    pub monospace_font: String,
+    /// Font used when nothing else matches
+    pub default_font: String,
+}
+
+impl Default for FontFallback {
+    fn default() -> Self {
+        FontFallback {
+            available: vec!["Inter".to_string()],
+            substitutions: HashMap::new(),
+            serif_font: "serif".to_string(),
+            monospace_font: "monospace".to_string(),
+            default_font: "Inter".to_string(),
+        }
+    }
+}
+
+impl FontFallback {
+    /// Returns the name of the font that should be used to render `font`
+    pub fn get_rendering_font_name(&self, font: &Font) -> String {
+        let name = font.name.trim();
+        if let Some(available) = self.available.iter().find(|f| f.eq_ignore_ascii_case(name)) {
+            return available.clone();
+        }
+        if let Some((_, substitution)) = self
+            .substitutions
+            .iter()
+            .find(|(f, _)| f.eq_ignore_ascii_case(name))
+        {
+            return substitution.clone();
+        }
+        match font.family {
+            1 => self.serif_font.clone(),
+            3 => self.monospace_font.clone(),
+            _ => self.default_font.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn font(name: &str, family: i32) -> Font {
+        Font {
+            name: name.to_string(),
+            family,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_rendering_font_name() {
+        let mut fallback = FontFallback::default();
+        assert_eq!(fallback.get_rendering_font_name(&font("Inter", 2)), "Inter");
+        assert_eq!(fallback.get_rendering_font_name(&font("inter", 2)), "Inter");
+        assert_eq!(
+            fallback.get_rendering_font_name(&font("Calibri", 2)),
+            "Inter"
+        );
+        assert_eq!(
+            fallback.get_rendering_font_name(&font("Times New Roman", 1)),
+            "serif"
+        );
+        assert_eq!(
+            fallback.get_rendering_font_name(&font("Courier New", 3)),
+            "monospace"
+        );
+
+        fallback
+            .substitutions
+            .insert("Calibri".to_string(), "Carlito".to_string());
+        assert_eq!(
+            fallback.get_rendering_font_name(&font("CALIBRI", 2)),
+            "Carlito"
+        );
+
+        fallback.available.push("Calibri".to_string());
+        assert_eq!(
+            fallback.get_rendering_font_name(&font("Calibri", 2)),
+            "Calibri"
+        );
+    }
+}
@@ -10,40 +10,76 @@ use crate::constants::MINIMUM_DATE_SERIAL_NUMBER;
 
 pub const DATE_OUT_OF_RANGE_MESSAGE: &str = "Out of range parameters for date";
 
+/// Serial number of 1904-01-01 in the 1900 date system.
+/// In the 1904 date system that day is serial number 0, so the same date has a serial number
+/// `DATE_1904_OFFSET` units smaller than in the 1900 date system.
+pub const DATE_1904_OFFSET: i32 = 1_462;
+
+/// Number of days we need to subtract to a 1900 serial number to get a serial number in the
+/// workbook date system.
+#[inline]
+pub(crate) fn date_system_offset(date1904: bool) -> i32 {
+    if date1904 {
+        DATE_1904_OFFSET
+    } else {
+        0
+    }
+}
+
+/// Minimum and maximum serial numbers of the date system
+fn serial_number_range(date1904: bool) -> (i32, i32) {
+    if date1904 {
+        (0, MAXIMUM_DATE_SERIAL_NUMBER - DATE_1904_OFFSET)
+    } else {
+        (MINIMUM_DATE_SERIAL_NUMBER, MAXIMUM_DATE_SERIAL_NUMBER)
+    }
+}
+
 #[inline]
-fn convert_to_serial_number(date: NaiveDate) -> i32 {
-    date.num_days_from_ce() - EXCEL_DATE_BASE
+fn convert_to_serial_number(date: NaiveDate, date1904: bool) -> i32 {
+    date.num_days_from_ce() - EXCEL_DATE_BASE - date_system_offset(date1904)
 }
 
-fn is_date_within_range(date: NaiveDate) -> bool {
-    convert_to_serial_number(date) >= MINIMUM_DATE_SERIAL_NUMBER
-        && convert_to_serial_number(date) <= MAXIMUM_DATE_SERIAL_NUMBER
+fn is_date_within_range(date: NaiveDate, date1904: bool) -> bool {
+    let (minimum, maximum) = serial_number_range(date1904);
+    let serial_number = convert_to_serial_number(date, date1904);
+    serial_number >= minimum && serial_number <= maximum
 }
 
-pub fn from_excel_date(days: i64) -> Result<NaiveDate, String> {
-    if days < MINIMUM_DATE_SERIAL_NUMBER as i64 {
-        return Err(format!(
-            "Excel date must be greater than {MINIMUM_DATE_SERIAL_NUMBER}"
-        ));
+/// Converts a serial number into a date.
+/// If `date1904` is true the serial number counts days from 1904-01-01, otherwise from 1900-01-01.
+pub fn from_excel_date(days: i64, date1904: bool) -> Result<NaiveDate, String> {
+    let (minimum, maximum) = serial_number_range(date1904);
+    if days < minimum as i64 {
+        return Err(format!("Excel date must be greater than {minimum}"));
     };
-    if days > MAXIMUM_DATE_SERIAL_NUMBER as i64 {
-        return Err(format!(
-            "Excel date must be less than {MAXIMUM_DATE_SERIAL_NUMBER}"
-        ));
+    if days > maximum as i64 {
+        return Err(format!("Excel date must be less than {maximum}"));
     };
     #[allow(clippy::expect_used)]
     let dt = NaiveDate::from_ymd_opt(1900, 1, 1).expect("problem with chrono::NaiveDate");
-    Ok(dt + Duration::days(days - 2))
+    Ok(dt + Duration::days(days + date_system_offset(date1904) as i64 - 2))
 }
 
-pub fn date_to_serial_number(day: u32, month: u32, year: i32) -> Result<i32, String> {
+/// Returns the serial number of the date in the given date system
+pub fn date_to_serial_number(
+    day: u32,
+    month: u32,
+    year: i32,
+    date1904: bool,
+) -> Result<i32, String> {
     match NaiveDate::from_ymd_opt(year, month, day) {
-        Some(native_date) => Ok(convert_to_serial_number(native_date)),
+        Some(native_date) => Ok(convert_to_serial_number(native_date, date1904)),
         None => Err(DATE_OUT_OF_RANGE_MESSAGE.to_string()),
     }
 }
 
-pub fn permissive_date_to_serial_number(day: i32, month: i32, year: i32) -> Result<i32, String> {
+pub fn permissive_date_to_serial_number(
+    day: i32,
+    month: i32,
+    year: i32,
+    date1904: bool,
+) -> Result<i32, String> {
     // Excel parses `DATE` very permissively. It allows not just for valid date values, but it
     // allows for invalid dates as well. If you for example enter `DATE(1900, 1, 32)` it will
     // return the date `1900-02-01`. Despite giving a day that is out of range it will just
@@ -53,7 +89,7 @@ pub fn permissive_date_to_serial_number(day: i32, month: i32, year: i32) -> Resu
     // possible.
 
     // Special case for the minimum date
-    if year == 1899 && month == 12 && day == 31 {
+    if !date1904 && year == 1899 && month == 12 && day == 31 {
         return Ok(MINIMUM_DATE_SERIAL_NUMBER);
     }
     let Some(mut date) = NaiveDate::from_ymd_opt(year, 1, 1) else {
@@ -69,7 +105,7 @@ pub fn permissive_date_to_serial_number(day: i32, month: i32, year: i32) -> Resu
     //
     // As a result, we have to run range checks as we parse the date from the biggest unit to the
     // smallest unit.
-    if !is_date_within_range(date) {
+    if !is_date_within_range(date, date1904) {
         return Err(DATE_OUT_OF_RANGE_MESSAGE.to_string());
     }
 
@@ -81,7 +117,7 @@ pub fn permissive_date_to_serial_number(day: i32, month: i32, year: i32) -> Resu
         } else {
             date = date + Months::new(abs_month);
         }
-        if !is_date_within_range(date) {
+        if !is_date_within_range(date, date1904) {
             return Err(DATE_OUT_OF_RANGE_MESSAGE.to_string());
         }
         date
@@ -95,13 +131,13 @@ pub fn permissive_date_to_serial_number(day: i32, month: i32, year: i32) -> Resu
         } else {
             date = date + Days::new(abs_day);
         }
-        if !is_date_within_range(date) {
+        if !is_date_within_range(date, date1904) {
             return Err(DATE_OUT_OF_RANGE_MESSAGE.to_string());
         }
         date
     };
 
-    Ok(convert_to_serial_number(date))
+    Ok(convert_to_serial_number(date, date1904))
 }
 
 #[cfg(test)]
@@ -111,48 +147,71 @@ mod tests {
     #[test]
     fn test_permissive_date_to_serial_number() {
         assert_eq!(
-            permissive_date_to_serial_number(42, 42, 2002),
-            date_to_serial_number(12, 7, 2005)
+            permissive_date_to_serial_number(42, 42, 2002, false),
+            date_to_serial_number(12, 7, 2005, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(1, 42, 2002),
-            date_to_serial_number(1, 6, 2005)
+            permissive_date_to_serial_number(1, 42, 2002, false),
+            date_to_serial_number(1, 6, 2005, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(1, 15, 2000),
-            date_to_serial_number(1, 3, 2001)
+            permissive_date_to_serial_number(1, 15, 2000, false),
+            date_to_serial_number(1, 3, 2001, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(1, 49, 2000),
-            date_to_serial_number(1, 1, 2004)
+            permissive_date_to_serial_number(1, 49, 2000, false),
+            date_to_serial_number(1, 1, 2004, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(1, 49, 2000),
-            date_to_serial_number(1, 1, 2004)
+            permissive_date_to_serial_number(1, 49, 2000, false),
+            date_to_serial_number(1, 1, 2004, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(31, 49, 2000),
-            date_to_serial_number(31, 1, 2004)
+            permissive_date_to_serial_number(31, 49, 2000, false),
+            date_to_serial_number(31, 1, 2004, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(256, 49, 2000),
-            date_to_serial_number(12, 9, 2004)
+            permissive_date_to_serial_number(256, 49, 2000, false),
+            date_to_serial_number(12, 9, 2004, false)
         );
         assert_eq!(
-            permissive_date_to_serial_number(256, 1, 2004),
-            date_to_serial_number(12, 9, 2004)
+            permissive_date_to_serial_number(256, 1, 2004, false),
+            date_to_serial_number(12, 9, 2004, false)
         );
     }
 
     #[test]
     fn test_max_and_min_dates() {
         assert_eq!(
-            permissive_date_to_serial_number(31, 12, 9999),
+            permissive_date_to_serial_number(31, 12, 9999, false),
             Ok(MAXIMUM_DATE_SERIAL_NUMBER),
         );
         assert_eq!(
-            permissive_date_to_serial_number(31, 12, 1899),
+            permissive_date_to_serial_number(31, 12, 1899, false),
             Ok(MINIMUM_DATE_SERIAL_NUMBER),
         );
     }
+
+    #[test]
+    fn test_date1904() {
+        assert_eq!(date_to_serial_number(1, 1, 1904, true), Ok(0));
+        assert_eq!(
+            date_to_serial_number(15, 6, 2024, true),
+            date_to_serial_number(15, 6, 2024, false).map(|n| n - DATE_1904_OFFSET)
+        );
+        assert_eq!(
+            from_excel_date(0, true).ok(),
+            NaiveDate::from_ymd_opt(1904, 1, 1)
+        );
+        assert!(from_excel_date(-1, true).is_err());
+        assert_eq!(from_excel_date(1_462, false), from_excel_date(0, true));
+        assert_eq!(
+            permissive_date_to_serial_number(31, 12, 1903, true),
+            Err(DATE_OUT_OF_RANGE_MESSAGE.to_string())
+        );
+        assert_eq!(
+            permissive_date_to_serial_number(31, 12, 9999, true),
+            Ok(MAXIMUM_DATE_SERIAL_NUMBER - DATE_1904_OFFSET),
+        );
+    }
 }
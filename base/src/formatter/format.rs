@@ -57,6 +57,17 @@ fn use_group_separator(use_thousands: bool, digit_index: i32, group_sizes: &str)
 }
 
 pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Formatted {
+    format_number_in_date_system(value_original, format, locale, false)
+}
+
+/// Formats a number like [`format_number`].
+/// If `date1904` is true, dates are serial numbers in the 1904 date system.
+pub fn format_number_in_date_system(
+    value_original: f64,
+    format: &str,
+    locale: &Locale,
+    date1904: bool,
+) -> Formatted {
     let mut parser = Parser::new(format);
     parser.parse();
     let parts = parser.parts;
@@ -171,7 +182,7 @@ pub fn format_number(value_original: f64, format: &str, locale: &Locale) -> Form
             let hours = (time_fract * 24.0).floor();
             let minutes = ((time_fract * 24.0 - hours) * 60.0).floor();
             let seconds = ((((time_fract * 24.0 - hours) * 60.0) - minutes) * 60.0).round();
-            let date = from_excel_date(value as i64).ok();
+            let date = from_excel_date(value as i64, date1904).ok();
             for token in tokens {
                 match token {
                     TextToken::Literal(c) => {
@@ -745,7 +756,11 @@ fn parse_year(year_str: &str) -> Result<(i32, String), String> {
 // NOTE 1: The separator has to be the same
 // NOTE 2: In some engines "2/3" is implemented ad "2/March of the present year"
 // NOTE 3: I did not implement the "short date"
-pub(crate) fn parse_date(value: &str, locale: &Locale) -> Result<(i32, String), String> {
+pub(crate) fn parse_date(
+    value: &str,
+    locale: &Locale,
+    date1904: bool,
+) -> Result<(i32, String), String> {
     let separator = if value.contains('/') {
         '/'
     } else if value.contains('-') {
@@ -786,7 +801,7 @@ pub(crate) fn parse_date(value: &str, locale: &Locale) -> Result<(i32, String),
     let (day, day_format) = parse_day(day_str)?;
     let (month, month_format) = parse_month(month_str, locale)?;
     let (year, year_format) = parse_year(year_str)?;
-    let serial_number = match date_to_serial_number(day, month, year) {
+    let serial_number = match date_to_serial_number(day, month, year, date1904) {
         Ok(n) => n,
         Err(_) => return Err("Not a valid date".to_string()),
     };
@@ -817,6 +832,7 @@ pub(crate) fn parse_formatted_number(
     original: &str,
     currencies: &[&str],
     locale: &Locale,
+    date1904: bool,
 ) -> Result<(f64, Option<String>), String> {
     let value = original.trim();
     let scientific_format = "0.00E+00";
@@ -877,7 +893,7 @@ pub(crate) fn parse_formatted_number(
     }
 
     // check if it is a date. NOTE: we don't trim the original here
-    if let Ok((serial_number, format)) = parse_date(original, locale) {
+    if let Ok((serial_number, format)) = parse_date(original, locale, date1904) {
         return Ok((serial_number as f64, Some(format)));
     }
 
@@ -0,0 +1,330 @@
+//! Machine readable description of the functions, for autocomplete and argument tooltips.
+//!
+//! The signatures live in `functions/catalogue.rs`, keyed by the English name of the function.
+//! Parameters are written as in the Excel documentation: `number, [significance], [mode]`.
+//! Optional parameters are in brackets and `...` means the previous parameter (or group of
+//! parameters in brackets) can be repeated. Parameters are numbers unless a type is given
+//! (`lookup_value: any`).
+//!
+//! Function names, parameter names and descriptions are localized with the language data.
+//! Parameter names are translated without their trailing number (`number1` is `zahl1` in
+//! German). Anything missing from the language falls back to English.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    functions::{
+        catalogue::{CatalogueEntry, CATALOGUE},
+        Function,
+    },
+    language::{get_default_language, get_language, Language},
+    locale::{get_locale, Locale},
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FunctionCategory {
+    Logical,
+    MathAndTrigonometry,
+    Information,
+    LookupAndReference,
+    Text,
+    Statistical,
+    Compatibility,
+    DateAndTime,
+    Financial,
+    Engineering,
+    Database,
+}
+
+/// The type of a parameter or of the value returned by a function
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ArgumentType {
+    Any,
+    Number,
+    Text,
+    Logical,
+    Reference,
+    Array,
+    Lambda,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FunctionParameter {
+    /// Name of the parameter in the language of the catalogue (`lookup_value`)
+    pub name: String,
+    pub argument_type: ArgumentType,
+    pub optional: bool,
+    /// The parameter can be given several times (`number1, [number2], ...`)
+    pub repeating: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FunctionMetadata {
+    /// Name of the function in the language of the catalogue
+    pub name: String,
+    pub category: FunctionCategory,
+    pub parameters: Vec<FunctionParameter>,
+    pub return_type: ArgumentType,
+    /// The function is evaluated again on every recalculation (`RAND`, `NOW`, `OFFSET`, ...)
+    pub volatile: bool,
+    /// Short description of the function in the language of the catalogue
+    pub description: String,
+}
+
+/// The function and argument the cursor is in, while typing a formula
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ActiveArgument {
+    /// Name of the function in the language of the formula
+    pub function: String,
+    /// Index of the argument, starting at 0
+    pub index: u32,
+}
+
+fn get_catalogue_entries() -> HashMap<&'static str, &'static CatalogueEntry> {
+    CATALOGUE.iter().map(|entry| (entry.name, entry)).collect()
+}
+
+fn parse_parameter(text: &str, optional: bool) -> FunctionParameter {
+    let (name, argument_type) = match text.split_once(':') {
+        Some((name, kind)) => {
+            let argument_type = match kind.trim() {
+                "any" => ArgumentType::Any,
+                "text" => ArgumentType::Text,
+                "logical" => ArgumentType::Logical,
+                "reference" => ArgumentType::Reference,
+                "array" => ArgumentType::Array,
+                "lambda" => ArgumentType::Lambda,
+                _ => ArgumentType::Number,
+            };
+            (name, argument_type)
+        }
+        None => (text, ArgumentType::Number),
+    };
+    FunctionParameter {
+        name: name.trim().to_string(),
+        argument_type,
+        optional,
+        repeating: false,
+    }
+}
+
+/// Parses a list of parameters like `value1: any, [value2: any], ...`
+fn parse_parameters(text: &str) -> Vec<FunctionParameter> {
+    let mut parameters: Vec<FunctionParameter> = Vec::new();
+    // index of the first parameter of the last group, `...` repeats the whole group
+    let mut group_start = 0;
+    let mut rest = text.trim();
+    while !rest.is_empty() {
+        if let Some(inner) = rest.strip_prefix('[') {
+            let end = inner.find(']').unwrap_or(inner.len());
+            group_start = parameters.len();
+            for item in inner[..end].split(',') {
+                parameters.push(parse_parameter(item, true));
+            }
+            rest = inner.get(end + 1..).unwrap_or("");
+        } else if let Some(tail) = rest.strip_prefix("...") {
+            for parameter in &mut parameters[group_start..] {
+                parameter.repeating = true;
+            }
+            rest = tail;
+        } else {
+            let end = rest.find(',').unwrap_or(rest.len());
+            group_start = parameters.len();
+            parameters.push(parse_parameter(&rest[..end], false));
+            rest = &rest[end..];
+        }
+        rest = rest.trim_start().trim_start_matches(',').trim_start();
+    }
+    parameters
+}
+
+/// Translates a parameter name like `number1`, the whole name is tried first (`a1`)
+fn localize_parameter_name(name: &str, language: &Language) -> String {
+    let parameters = &language.catalogue.parameters;
+    if let Some(localized) = parameters.get(name) {
+        return localized.clone();
+    }
+    let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    match parameters.get(base) {
+        Some(localized) => format!("{localized}{}", &name[base.len()..]),
+        None => name.to_string(),
+    }
+}
+
+/// Returns the metadata of the function in the given language
+fn get_function_metadata(
+    function: &Function,
+    entries: &HashMap<&str, &CatalogueEntry>,
+    language: &Language,
+) -> Option<FunctionMetadata> {
+    let english_name = function.to_localized_name(get_default_language());
+    let entry = entries.get(english_name.as_str())?;
+    let mut parameters = parse_parameters(entry.parameters);
+    for parameter in &mut parameters {
+        parameter.name = localize_parameter_name(&parameter.name, language);
+    }
+    let description = language
+        .catalogue
+        .descriptions
+        .get(entry.name)
+        .map_or(entry.description, String::as_str);
+    Some(FunctionMetadata {
+        name: function.to_localized_name(language),
+        category: entry.category,
+        parameters,
+        return_type: entry.returns,
+        volatile: entry.volatile,
+        description: description.to_string(),
+    })
+}
+
+/// Returns the description of all the implemented functions in the given language, sorted by
+/// name.
+pub fn get_function_catalogue(language: &str) -> Result<Vec<FunctionMetadata>, String> {
+    let language = get_language(language)?;
+    let entries = get_catalogue_entries();
+    let mut catalogue: Vec<FunctionMetadata> = Function::into_iter()
+        .filter_map(|function| get_function_metadata(&function, &entries, language))
+        .collect();
+    catalogue.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(catalogue)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+enum Group {
+    Function { name: String, index: u32 },
+    Parenthesis,
+    Brace,
+}
+
+/// Returns the innermost function the cursor is in, and the argument being typed.
+fn find_active_argument(
+    formula: &str,
+    cursor: usize,
+    locale: &Locale,
+    language: &Language,
+) -> Option<ActiveArgument> {
+    let separator = if locale.numbers.symbols.decimal == "," {
+        ';'
+    } else {
+        ','
+    };
+    let chars: Vec<char> = formula.chars().take(cursor).collect();
+    let mut groups = Vec::new();
+    let mut position = 0;
+    while position < chars.len() {
+        let c = chars[position];
+        match c {
+            '"' | '\'' | '[' => {
+                // strings, quoted sheet names and structured references
+                let end = if c == '[' { ']' } else { c };
+                match chars[position + 1..].iter().position(|&x| x == end) {
+                    Some(p) => position += p + 1,
+                    // the cursor is inside
+                    None => return None,
+                }
+            }
+            '(' => {
+                let start = chars[..position]
+                    .iter()
+                    .rposition(|&x| !is_name_char(x))
+                    .map_or(0, |p| p + 1);
+                let name: String = chars[start..position].iter().collect();
+                if name.is_empty() {
+                    groups.push(Group::Parenthesis);
+                } else {
+                    groups.push(Group::Function { name, index: 0 });
+                }
+            }
+            '{' => groups.push(Group::Brace),
+            ')' | '}' => {
+                groups.pop();
+            }
+            _ if c == separator => {
+                if let Some(Group::Function { index, .. }) = groups.last_mut() {
+                    *index += 1;
+                }
+            }
+            _ => {}
+        }
+        position += 1;
+    }
+    groups.iter().rev().find_map(|group| match group {
+        Group::Function { name, index } => {
+            let name = name.strip_prefix("_xlfn.").unwrap_or(name);
+            language
+                .functions
+                .lookup(name)
+                .map(|function| ActiveArgument {
+                    function: function.to_localized_name(language),
+                    index: *index,
+                })
+        }
+        _ => None,
+    })
+}
+
+/// Returns the function and the index of the argument at the `cursor` (in characters) of a
+/// partially typed formula, or `None` if the cursor is not within the arguments of a function.
+///
+/// Used in the formula bar to show the signature of the function and highlight the argument.
+pub fn get_active_argument(
+    formula: &str,
+    cursor: usize,
+    locale: &str,
+    language: &str,
+) -> Result<Option<ActiveArgument>, String> {
+    let locale = get_locale(locale)?;
+    let language = get_language(language)?;
+    Ok(find_active_argument(formula, cursor, locale, language))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn all_functions_are_described() {
+        let language = get_default_language();
+        let entries = get_catalogue_entries();
+        for function in Function::into_iter() {
+            assert!(
+                get_function_metadata(&function, &entries, language).is_some(),
+                "Missing metadata for {function:?}"
+            );
+        }
+        assert_eq!(entries.len(), CATALOGUE.len());
+        assert_eq!(entries.len(), Function::into_iter().len());
+    }
+
+    #[test]
+    fn parameters() {
+        let parameters = parse_parameters(
+            "sum_range: reference, criteria_range1: reference, criteria1: any, [criteria_range2: reference, criteria2: any], ...",
+        );
+        assert_eq!(parameters.len(), 5);
+        assert_eq!(parameters[0].argument_type, ArgumentType::Reference);
+        assert!(!parameters[2].optional && !parameters[2].repeating);
+        assert!(parameters[3].optional && parameters[3].repeating);
+        assert!(parameters[4].optional && parameters[4].repeating);
+        assert_eq!(parameters[4].name, "criteria2");
+
+        let parameters = parse_parameters("[parameter1: any], ..., calculation: any");
+        assert!(parameters[0].repeating);
+        assert!(!parameters[1].optional && !parameters[1].repeating);
+
+        let parameters = parse_parameters("number, [significance]");
+        assert_eq!(parameters[1].argument_type, ArgumentType::Number);
+        assert!(parameters[1].optional && !parameters[1].repeating);
+
+        assert!(parse_parameters("").is_empty());
+    }
+}
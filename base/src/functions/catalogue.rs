@@ -0,0 +1,3800 @@
+//! Signatures of the functions, keyed by their English name.
+//! See [crate::function_catalogue] for the syntax of the parameters.
+
+use crate::function_catalogue::{ArgumentType, FunctionCategory};
+
+pub(crate) struct CatalogueEntry {
+    pub(crate) name: &'static str,
+    pub(crate) category: FunctionCategory,
+    pub(crate) returns: ArgumentType,
+    pub(crate) parameters: &'static str,
+    pub(crate) volatile: bool,
+    pub(crate) description: &'static str,
+}
+
+pub(crate) const CATALOGUE: &[CatalogueEntry] = &[
+    CatalogueEntry {
+        name: "AND",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Logical,
+        parameters: "logical1: logical, [logical2: logical], ...",
+        volatile: false,
+        description: "Returns TRUE if all of its arguments are TRUE",
+    },
+    CatalogueEntry {
+        name: "FALSE",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Logical,
+        parameters: "",
+        volatile: false,
+        description: "Returns the logical value FALSE",
+    },
+    CatalogueEntry {
+        name: "IF",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "logical_test: logical, value_if_true: any, [value_if_false: any]",
+        volatile: false,
+        description: "Returns one value if a condition is TRUE and another value if it is FALSE",
+    },
+    CatalogueEntry {
+        name: "IFERROR",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "value: any, value_if_error: any",
+        volatile: false,
+        description: "Returns a value if the expression is an error and the value of the expression otherwise",
+    },
+    CatalogueEntry {
+        name: "IFNA",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "value: any, value_if_na: any",
+        volatile: false,
+        description: "Returns a value if the expression is #N/A and the value of the expression otherwise",
+    },
+    CatalogueEntry {
+        name: "IFS",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "logical_test1: logical, value_if_true1: any, [logical_test2: logical, value_if_true2: any], ...",
+        volatile: false,
+        description: "Returns the value that corresponds to the first TRUE condition",
+    },
+    CatalogueEntry {
+        name: "LAMBDA",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Lambda,
+        parameters: "[parameter1: any], ..., calculation: any",
+        volatile: false,
+        description: "Creates a custom function with parameters and a calculation",
+    },
+    CatalogueEntry {
+        name: "LET",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "name1: any, name_value1: any, calculation_or_name2: any, [name_value2: any, calculation_or_name3: any], ...",
+        volatile: false,
+        description: "Assigns names to calculation results and uses them in a calculation",
+    },
+    CatalogueEntry {
+        name: "NOT",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Logical,
+        parameters: "logical: logical",
+        volatile: false,
+        description: "Reverses the logic of its argument",
+    },
+    CatalogueEntry {
+        name: "OR",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Logical,
+        parameters: "logical1: logical, [logical2: logical], ...",
+        volatile: false,
+        description: "Returns TRUE if any argument is TRUE",
+    },
+    CatalogueEntry {
+        name: "SWITCH",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "expression: any, value1: any, result1: any, [default_or_value2: any, result2: any], ...",
+        volatile: false,
+        description: "Compares an expression against a list of values and returns the result of the first match",
+    },
+    CatalogueEntry {
+        name: "TRUE",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Logical,
+        parameters: "",
+        volatile: false,
+        description: "Returns the logical value TRUE",
+    },
+    CatalogueEntry {
+        name: "XOR",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Logical,
+        parameters: "logical1: logical, [logical2: logical], ...",
+        volatile: false,
+        description: "Returns a logical exclusive OR of all arguments",
+    },
+    CatalogueEntry {
+        name: "BYCOL",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Array,
+        parameters: "array: array, lambda: lambda",
+        volatile: false,
+        description: "Applies a LAMBDA to each column and returns an array of the results",
+    },
+    CatalogueEntry {
+        name: "BYROW",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Array,
+        parameters: "array: array, lambda: lambda",
+        volatile: false,
+        description: "Applies a LAMBDA to each row and returns an array of the results",
+    },
+    CatalogueEntry {
+        name: "MAP",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Array,
+        parameters: "array1: array, [array2: array], ..., lambda: lambda",
+        volatile: false,
+        description: "Returns an array formed by applying a LAMBDA to each value in the arrays",
+    },
+    CatalogueEntry {
+        name: "REDUCE",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Any,
+        parameters: "[initial_value: any], array: array, lambda: lambda",
+        volatile: false,
+        description: "Reduces an array to an accumulated value by applying a LAMBDA to each value",
+    },
+    CatalogueEntry {
+        name: "SCAN",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Array,
+        parameters: "[initial_value: any], array: array, lambda: lambda",
+        volatile: false,
+        description: "Scans an array applying a LAMBDA to each value and returns an array with each intermediate value",
+    },
+    CatalogueEntry {
+        name: "MAKEARRAY",
+        category: FunctionCategory::Logical,
+        returns: ArgumentType::Array,
+        parameters: "rows, columns, lambda: lambda",
+        volatile: false,
+        description: "Returns an array of the given size calculated by applying a LAMBDA",
+    },
+    CatalogueEntry {
+        name: "ABS",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the absolute value of a number",
+    },
+    CatalogueEntry {
+        name: "ACOS",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the arccosine of a number",
+    },
+    CatalogueEntry {
+        name: "ACOSH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the inverse hyperbolic cosine of a number",
+    },
+    CatalogueEntry {
+        name: "ASIN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the arcsine of a number",
+    },
+    CatalogueEntry {
+        name: "ASINH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the inverse hyperbolic sine of a number",
+    },
+    CatalogueEntry {
+        name: "ATAN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the arctangent of a number",
+    },
+    CatalogueEntry {
+        name: "ATAN2",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "x_num, y_num",
+        volatile: false,
+        description: "Returns the arctangent from x and y coordinates",
+    },
+    CatalogueEntry {
+        name: "ATANH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the inverse hyperbolic tangent of a number",
+    },
+    CatalogueEntry {
+        name: "CHOOSE",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Any,
+        parameters: "index_num, value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Chooses a value from a list of values",
+    },
+    CatalogueEntry {
+        name: "COLUMN",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "[reference: reference]",
+        volatile: false,
+        description: "Returns the column number of a reference",
+    },
+    CatalogueEntry {
+        name: "COLUMNS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "array: array",
+        volatile: false,
+        description: "Returns the number of columns in a reference or array",
+    },
+    CatalogueEntry {
+        name: "COS",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the cosine of a number",
+    },
+    CatalogueEntry {
+        name: "COSH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic cosine of a number",
+    },
+    CatalogueEntry {
+        name: "LOG",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [base]",
+        volatile: false,
+        description: "Returns the logarithm of a number to the given base",
+    },
+    CatalogueEntry {
+        name: "LOG10",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the base-10 logarithm of a number",
+    },
+    CatalogueEntry {
+        name: "LN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the natural logarithm of a number",
+    },
+    CatalogueEntry {
+        name: "MAX",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the largest value in a list of arguments",
+    },
+    CatalogueEntry {
+        name: "MIN",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the smallest value in a list of arguments",
+    },
+    CatalogueEntry {
+        name: "PI",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "",
+        volatile: false,
+        description: "Returns the value of pi",
+    },
+    CatalogueEntry {
+        name: "POWER",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, power",
+        volatile: false,
+        description: "Returns the result of a number raised to a power",
+    },
+    CatalogueEntry {
+        name: "PRODUCT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Multiplies its arguments",
+    },
+    CatalogueEntry {
+        name: "RAND",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "",
+        volatile: true,
+        description: "Returns a random number between 0 and 1",
+    },
+    CatalogueEntry {
+        name: "RANDBETWEEN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "bottom, top",
+        volatile: true,
+        description: "Returns a random integer between the numbers you specify",
+    },
+    CatalogueEntry {
+        name: "ROUND",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, num_digits",
+        volatile: false,
+        description: "Rounds a number to a specified number of digits",
+    },
+    CatalogueEntry {
+        name: "ROUNDDOWN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, num_digits",
+        volatile: false,
+        description: "Rounds a number down, toward zero",
+    },
+    CatalogueEntry {
+        name: "ROUNDUP",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, num_digits",
+        volatile: false,
+        description: "Rounds a number up, away from zero",
+    },
+    CatalogueEntry {
+        name: "SIN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the sine of an angle",
+    },
+    CatalogueEntry {
+        name: "SINH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic sine of a number",
+    },
+    CatalogueEntry {
+        name: "SQRT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns a positive square root",
+    },
+    CatalogueEntry {
+        name: "SQRTPI",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the square root of a number multiplied by pi",
+    },
+    CatalogueEntry {
+        name: "SUM",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Adds its arguments",
+    },
+    CatalogueEntry {
+        name: "SUMIF",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "range: reference, criteria: any, [sum_range: reference]",
+        volatile: false,
+        description: "Adds the cells specified by a given criteria",
+    },
+    CatalogueEntry {
+        name: "SUMIFS",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "sum_range: reference, criteria_range1: reference, criteria1: any, [criteria_range2: reference, criteria2: any], ...",
+        volatile: false,
+        description: "Adds the cells in a range that meet multiple criteria",
+    },
+    CatalogueEntry {
+        name: "SUMX2MY2",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "array_x: array, array_y: array",
+        volatile: false,
+        description: "Returns the sum of the difference of squares of corresponding values in two arrays",
+    },
+    CatalogueEntry {
+        name: "SUMX2PY2",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "array_x: array, array_y: array",
+        volatile: false,
+        description: "Returns the sum of the sum of squares of corresponding values in two arrays",
+    },
+    CatalogueEntry {
+        name: "SUMXMY2",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "array_x: array, array_y: array",
+        volatile: false,
+        description: "Returns the sum of squares of differences of corresponding values in two arrays",
+    },
+    CatalogueEntry {
+        name: "TAN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the tangent of a number",
+    },
+    CatalogueEntry {
+        name: "TANH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic tangent of a number",
+    },
+    CatalogueEntry {
+        name: "ACOT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the arccotangent of a number",
+    },
+    CatalogueEntry {
+        name: "ACOTH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic arccotangent of a number",
+    },
+    CatalogueEntry {
+        name: "COT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the cotangent of an angle",
+    },
+    CatalogueEntry {
+        name: "COTH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic cotangent of a number",
+    },
+    CatalogueEntry {
+        name: "CSC",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the cosecant of an angle",
+    },
+    CatalogueEntry {
+        name: "CSCH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic cosecant of an angle",
+    },
+    CatalogueEntry {
+        name: "SEC",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the secant of an angle",
+    },
+    CatalogueEntry {
+        name: "SECH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the hyperbolic secant of an angle",
+    },
+    CatalogueEntry {
+        name: "EXP",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns e raised to the power of a given number",
+    },
+    CatalogueEntry {
+        name: "FACT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the factorial of a number",
+    },
+    CatalogueEntry {
+        name: "FACTDOUBLE",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the double factorial of a number",
+    },
+    CatalogueEntry {
+        name: "SIGN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the sign of a number",
+    },
+    CatalogueEntry {
+        name: "RADIANS",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "angle",
+        volatile: false,
+        description: "Converts degrees to radians",
+    },
+    CatalogueEntry {
+        name: "DEGREES",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "angle",
+        volatile: false,
+        description: "Converts radians to degrees",
+    },
+    CatalogueEntry {
+        name: "INT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Rounds a number down to the nearest integer",
+    },
+    CatalogueEntry {
+        name: "EVEN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Rounds a number up to the nearest even integer",
+    },
+    CatalogueEntry {
+        name: "ODD",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Rounds a number up to the nearest odd integer",
+    },
+    CatalogueEntry {
+        name: "CEILING",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, significance",
+        volatile: false,
+        description: "Rounds a number up to the nearest multiple of significance",
+    },
+    CatalogueEntry {
+        name: "CEILING.MATH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [significance], [mode]",
+        volatile: false,
+        description: "Rounds a number up to the nearest integer or to the nearest multiple of significance",
+    },
+    CatalogueEntry {
+        name: "CEILING.PRECISE",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [significance]",
+        volatile: false,
+        description: "Rounds a number up to the nearest integer or to the nearest multiple of significance, regardless of its sign",
+    },
+    CatalogueEntry {
+        name: "FLOOR",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, significance",
+        volatile: false,
+        description: "Rounds a number down, toward zero",
+    },
+    CatalogueEntry {
+        name: "FLOOR.MATH",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [significance], [mode]",
+        volatile: false,
+        description: "Rounds a number down to the nearest integer or to the nearest multiple of significance",
+    },
+    CatalogueEntry {
+        name: "FLOOR.PRECISE",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [significance]",
+        volatile: false,
+        description: "Rounds a number down to the nearest integer or to the nearest multiple of significance, regardless of its sign",
+    },
+    CatalogueEntry {
+        name: "ISO.CEILING",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [significance]",
+        volatile: false,
+        description: "Rounds a number up to the nearest integer or to the nearest multiple of significance",
+    },
+    CatalogueEntry {
+        name: "MOD",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, divisor",
+        volatile: false,
+        description: "Returns the remainder from division",
+    },
+    CatalogueEntry {
+        name: "QUOTIENT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "numerator, denominator",
+        volatile: false,
+        description: "Returns the integer portion of a division",
+    },
+    CatalogueEntry {
+        name: "MROUND",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, multiple",
+        volatile: false,
+        description: "Returns a number rounded to the desired multiple",
+    },
+    CatalogueEntry {
+        name: "TRUNC",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, [num_digits]",
+        volatile: false,
+        description: "Truncates a number to an integer",
+    },
+    CatalogueEntry {
+        name: "GCD",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the greatest common divisor",
+    },
+    CatalogueEntry {
+        name: "LCM",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the least common multiple",
+    },
+    CatalogueEntry {
+        name: "BASE",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Text,
+        parameters: "number, radix, [min_length]",
+        volatile: false,
+        description: "Converts a number into a text representation with the given radix",
+    },
+    CatalogueEntry {
+        name: "DECIMAL",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "text: text, radix",
+        volatile: false,
+        description: "Converts a text representation of a number in a given base into a decimal number",
+    },
+    CatalogueEntry {
+        name: "ROMAN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Text,
+        parameters: "number, [form]",
+        volatile: false,
+        description: "Converts an arabic numeral to roman, as text",
+    },
+    CatalogueEntry {
+        name: "ARABIC",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "text: text",
+        volatile: false,
+        description: "Converts a Roman number to Arabic, as a number",
+    },
+    CatalogueEntry {
+        name: "COMBIN",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, number_chosen",
+        volatile: false,
+        description: "Returns the number of combinations for a given number of objects",
+    },
+    CatalogueEntry {
+        name: "COMBINA",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number, number_chosen",
+        volatile: false,
+        description: "Returns the number of combinations with repetitions for a given number of items",
+    },
+    CatalogueEntry {
+        name: "SUMSQ",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the sum of the squares of the arguments",
+    },
+    CatalogueEntry {
+        name: "MDETERM",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "array: array",
+        volatile: false,
+        description: "Returns the matrix determinant of an array",
+    },
+    CatalogueEntry {
+        name: "MINVERSE",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Array,
+        parameters: "array: array",
+        volatile: false,
+        description: "Returns the matrix inverse of an array",
+    },
+    CatalogueEntry {
+        name: "MUNIT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Array,
+        parameters: "dimension",
+        volatile: false,
+        description: "Returns the unit matrix for the specified dimension",
+    },
+    CatalogueEntry {
+        name: "MULTINOMIAL",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the multinomial of a set of numbers",
+    },
+    CatalogueEntry {
+        name: "SERIESSUM",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "x, n, m, coefficients: array",
+        volatile: false,
+        description: "Returns the sum of a power series",
+    },
+    CatalogueEntry {
+        name: "SUMPRODUCT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, [array2: array], ...",
+        volatile: false,
+        description: "Returns the sum of the products of corresponding array components",
+    },
+    CatalogueEntry {
+        name: "ERROR.TYPE",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Number,
+        parameters: "error_val: any",
+        volatile: false,
+        description: "Returns a number corresponding to an error type",
+    },
+    CatalogueEntry {
+        name: "FORMULATEXT",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Text,
+        parameters: "reference: reference",
+        volatile: false,
+        description: "Returns the formula at the given reference as text",
+    },
+    CatalogueEntry {
+        name: "ISBLANK",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is blank",
+    },
+    CatalogueEntry {
+        name: "ISERR",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is any error value except #N/A",
+    },
+    CatalogueEntry {
+        name: "ISERROR",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is any error value",
+    },
+    CatalogueEntry {
+        name: "ISEVEN",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "number",
+        volatile: false,
+        description: "Returns TRUE if the number is even",
+    },
+    CatalogueEntry {
+        name: "ISFORMULA",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "reference: reference",
+        volatile: false,
+        description: "Returns TRUE if there is a reference to a cell that contains a formula",
+    },
+    CatalogueEntry {
+        name: "ISLOGICAL",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is a logical value",
+    },
+    CatalogueEntry {
+        name: "ISNA",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is the #N/A error value",
+    },
+    CatalogueEntry {
+        name: "ISNONTEXT",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is not text",
+    },
+    CatalogueEntry {
+        name: "ISNUMBER",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is a number",
+    },
+    CatalogueEntry {
+        name: "ISODD",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "number",
+        volatile: false,
+        description: "Returns TRUE if the number is odd",
+    },
+    CatalogueEntry {
+        name: "ISOMITTED",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "argument: any",
+        volatile: false,
+        description: "Returns TRUE if the value in a LAMBDA is missing",
+    },
+    CatalogueEntry {
+        name: "ISREF",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is a reference",
+    },
+    CatalogueEntry {
+        name: "ISTEXT",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Logical,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns TRUE if the value is text",
+    },
+    CatalogueEntry {
+        name: "NA",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Any,
+        parameters: "",
+        volatile: false,
+        description: "Returns the error value #N/A",
+    },
+    CatalogueEntry {
+        name: "SHEET",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Number,
+        parameters: "[value: any]",
+        volatile: false,
+        description: "Returns the sheet number of the referenced sheet",
+    },
+    CatalogueEntry {
+        name: "TYPE",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Number,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns a number indicating the data type of a value",
+    },
+    CatalogueEntry {
+        name: "SHEETS",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Number,
+        parameters: "[reference: reference]",
+        volatile: false,
+        description: "Returns the number of sheets in a reference",
+    },
+    CatalogueEntry {
+        name: "N",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Number,
+        parameters: "value: any",
+        volatile: false,
+        description: "Returns a value converted to a number",
+    },
+    CatalogueEntry {
+        name: "CELL",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Any,
+        parameters: "info_type: text, [reference: reference]",
+        volatile: true,
+        description: "Returns information about the formatting, location, or contents of a cell",
+    },
+    CatalogueEntry {
+        name: "INFO",
+        category: FunctionCategory::Information,
+        returns: ArgumentType::Any,
+        parameters: "type_text: text",
+        volatile: true,
+        description: "Returns information about the current operating environment",
+    },
+    CatalogueEntry {
+        name: "ADDRESS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Text,
+        parameters: "row_num, column_num, [abs_num], [a1: logical], [sheet_text: text]",
+        volatile: false,
+        description: "Returns a reference as text to a single cell in a worksheet",
+    },
+    CatalogueEntry {
+        name: "AREAS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "reference: reference",
+        volatile: false,
+        description: "Returns the number of areas in a reference",
+    },
+    CatalogueEntry {
+        name: "CHOOSECOLS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, col_num1, [col_num2], ...",
+        volatile: false,
+        description: "Returns the specified columns from an array",
+    },
+    CatalogueEntry {
+        name: "CHOOSEROWS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, row_num1, [row_num2], ...",
+        volatile: false,
+        description: "Returns the specified rows from an array",
+    },
+    CatalogueEntry {
+        name: "EXPAND",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, rows, [columns], [pad_with: any]",
+        volatile: false,
+        description: "Expands or pads an array to the specified row and column dimensions",
+    },
+    CatalogueEntry {
+        name: "FILTER",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, include: array, [if_empty: any]",
+        volatile: false,
+        description: "Filters a range of data based on criteria you define",
+    },
+    CatalogueEntry {
+        name: "HLOOKUP",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Any,
+        parameters: "lookup_value: any, table_array: array, row_index_num, [range_lookup: logical]",
+        volatile: false,
+        description: "Looks in the top row of an array and returns the value of the indicated cell",
+    },
+    CatalogueEntry {
+        name: "HSTACK",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array1: array, [array2: array], ...",
+        volatile: false,
+        description: "Appends arrays horizontally and in sequence to return a larger array",
+    },
+    CatalogueEntry {
+        name: "INDEX",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Reference,
+        parameters: "reference: reference, row_num, [column_num], [area_num]",
+        volatile: false,
+        description: "Uses an index to choose a value from a reference or array",
+    },
+    CatalogueEntry {
+        name: "INDIRECT",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Reference,
+        parameters: "ref_text: text, [a1: logical]",
+        volatile: true,
+        description: "Returns a reference indicated by a text value",
+    },
+    CatalogueEntry {
+        name: "LOOKUP",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Any,
+        parameters: "lookup_value: any, lookup_vector: array, [result_vector: array]",
+        volatile: false,
+        description: "Looks up values in a vector or array",
+    },
+    CatalogueEntry {
+        name: "MATCH",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "lookup_value: any, lookup_array: array, [match_type]",
+        volatile: false,
+        description: "Looks up values in a reference or array",
+    },
+    CatalogueEntry {
+        name: "OFFSET",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Reference,
+        parameters: "reference: reference, rows, cols, [height], [width]",
+        volatile: true,
+        description: "Returns a reference offset from a given reference",
+    },
+    CatalogueEntry {
+        name: "ROW",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "[reference: reference]",
+        volatile: false,
+        description: "Returns the row number of a reference",
+    },
+    CatalogueEntry {
+        name: "ROWS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "array: array",
+        volatile: false,
+        description: "Returns the number of rows in a reference or array",
+    },
+    CatalogueEntry {
+        name: "SORT",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, [sort_index], [sort_order], [by_col: logical]",
+        volatile: false,
+        description: "Sorts the contents of a range or array",
+    },
+    CatalogueEntry {
+        name: "SORTBY",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, by_array1: array, [sort_order1], [by_array2: array, sort_order2], ...",
+        volatile: false,
+        description: "Sorts the contents of a range or array based on the values in a corresponding range or array",
+    },
+    CatalogueEntry {
+        name: "UNIQUE",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, [by_col: logical], [exactly_once: logical]",
+        volatile: false,
+        description: "Returns a list of unique values in a list or range",
+    },
+    CatalogueEntry {
+        name: "VLOOKUP",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Any,
+        parameters: "lookup_value: any, table_array: array, col_index_num, [range_lookup: logical]",
+        volatile: false,
+        description: "Looks in the first column of an array and moves across the row to return the value of a cell",
+    },
+    CatalogueEntry {
+        name: "VSTACK",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array1: array, [array2: array], ...",
+        volatile: false,
+        description: "Appends arrays vertically and in sequence to return a larger array",
+    },
+    CatalogueEntry {
+        name: "WRAPCOLS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "vector: array, wrap_count, [pad_with: any]",
+        volatile: false,
+        description: "Wraps the provided row or column of values by columns after a specified number of elements",
+    },
+    CatalogueEntry {
+        name: "WRAPROWS",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "vector: array, wrap_count, [pad_with: any]",
+        volatile: false,
+        description: "Wraps the provided row or column of values by rows after a specified number of elements",
+    },
+    CatalogueEntry {
+        name: "XLOOKUP",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Any,
+        parameters: "lookup_value: any, lookup_array: array, return_array: array, [if_not_found: any], [match_mode], [search_mode]",
+        volatile: false,
+        description: "Searches a range or an array, and returns an item corresponding to the first match it finds",
+    },
+    CatalogueEntry {
+        name: "XMATCH",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Number,
+        parameters: "lookup_value: any, lookup_array: array, [match_mode], [search_mode]",
+        volatile: false,
+        description: "Returns the relative position of an item in an array or range of cells",
+    },
+    CatalogueEntry {
+        name: "TRIMRANGE",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "range: reference, [trim_rows], [trim_cols]",
+        volatile: false,
+        description: "Excludes all empty rows and columns from the outer edges of a range or array",
+    },
+    CatalogueEntry {
+        name: "TABLE",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "[row_input: reference], [column_input: reference]",
+        volatile: false,
+        description: "Returns the results of a data table, substituting values in the input cells",
+    },
+    CatalogueEntry {
+        name: "TAKE",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, rows, [columns]",
+        volatile: false,
+        description: "Returns a specified number of contiguous rows or columns from the start or end of an array",
+    },
+    CatalogueEntry {
+        name: "DROP",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, rows, [columns]",
+        volatile: false,
+        description: "Excludes a specified number of rows or columns from the start or end of an array",
+    },
+    CatalogueEntry {
+        name: "TOCOL",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, [ignore], [scan_by_column: logical]",
+        volatile: false,
+        description: "Returns the array in a single column",
+    },
+    CatalogueEntry {
+        name: "TOROW",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array, [ignore], [scan_by_column: logical]",
+        volatile: false,
+        description: "Returns the array in a single row",
+    },
+    CatalogueEntry {
+        name: "TRANSPOSE",
+        category: FunctionCategory::LookupAndReference,
+        returns: ArgumentType::Array,
+        parameters: "array: array",
+        volatile: false,
+        description: "Returns the transpose of an array",
+    },
+    CatalogueEntry {
+        name: "MMULT",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Array,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns the matrix product of two arrays",
+    },
+    CatalogueEntry {
+        name: "SEQUENCE",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Array,
+        parameters: "rows, [columns], [start], [step]",
+        volatile: false,
+        description: "Generates a list of sequential numbers in an array",
+    },
+    CatalogueEntry {
+        name: "RANDARRAY",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Array,
+        parameters: "[rows], [columns], [min], [max], [whole_number: logical]",
+        volatile: true,
+        description: "Returns an array of random numbers between 0 and 1",
+    },
+    CatalogueEntry {
+        name: "CONCAT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text1: any, [text2: any], ...",
+        volatile: false,
+        description: "Combines the text from multiple ranges and/or strings",
+    },
+    CatalogueEntry {
+        name: "TEXTSPLIT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Array,
+        parameters: "text: text, col_delimiter: text, [row_delimiter: text], [ignore_empty: logical], [match_mode], [pad_with: any]",
+        volatile: false,
+        description: "Splits text strings by using column and row delimiters",
+    },
+    CatalogueEntry {
+        name: "CONCATENATE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text1: any, [text2: any], ...",
+        volatile: false,
+        description: "Joins several text items into one text item",
+    },
+    CatalogueEntry {
+        name: "EXACT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Logical,
+        parameters: "text1: text, text2: text",
+        volatile: false,
+        description: "Checks to see if two text values are identical",
+    },
+    CatalogueEntry {
+        name: "FIND",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "find_text: text, within_text: text, [start_num]",
+        volatile: false,
+        description: "Finds one text value within another (case-sensitive)",
+    },
+    CatalogueEntry {
+        name: "LEFT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, [num_chars]",
+        volatile: false,
+        description: "Returns the leftmost characters from a text value",
+    },
+    CatalogueEntry {
+        name: "LEN",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "text: text",
+        volatile: false,
+        description: "Returns the number of characters in a text string",
+    },
+    CatalogueEntry {
+        name: "LOWER",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text",
+        volatile: false,
+        description: "Converts text to lowercase",
+    },
+    CatalogueEntry {
+        name: "MID",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, start_num, num_chars",
+        volatile: false,
+        description: "Returns a specific number of characters from a text string starting at the position you specify",
+    },
+    CatalogueEntry {
+        name: "REPT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, number_times",
+        volatile: false,
+        description: "Repeats text a given number of times",
+    },
+    CatalogueEntry {
+        name: "RIGHT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, [num_chars]",
+        volatile: false,
+        description: "Returns the rightmost characters from a text value",
+    },
+    CatalogueEntry {
+        name: "SEARCH",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "find_text: text, within_text: text, [start_num]",
+        volatile: false,
+        description: "Finds one text value within another (not case-sensitive)",
+    },
+    CatalogueEntry {
+        name: "SUBSTITUTE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, old_text: text, new_text: text, [instance_num]",
+        volatile: false,
+        description: "Substitutes new text for old text in a text string",
+    },
+    CatalogueEntry {
+        name: "REGEXEXTRACT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Any,
+        parameters: "text: text, pattern: text, [return_mode], [case_sensitivity]",
+        volatile: false,
+        description: "Extracts strings within the provided text that match the pattern",
+    },
+    CatalogueEntry {
+        name: "REGEXREPLACE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, pattern: text, replacement: text, [occurrence], [case_sensitivity]",
+        volatile: false,
+        description: "Replaces strings within the provided text that match the pattern with replacement",
+    },
+    CatalogueEntry {
+        name: "REGEXTEST",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Logical,
+        parameters: "text: text, pattern: text, [case_sensitivity]",
+        volatile: false,
+        description: "Determines whether any part of text matches the pattern",
+    },
+    CatalogueEntry {
+        name: "T",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "value: any",
+        volatile: false,
+        description: "Converts its arguments to text",
+    },
+    CatalogueEntry {
+        name: "TEXT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "value: any, format_text: text",
+        volatile: false,
+        description: "Formats a number and converts it to text",
+    },
+    CatalogueEntry {
+        name: "TEXTAFTER",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, delimiter: text, [instance_num], [match_mode], [match_end], [if_not_found: any]",
+        volatile: false,
+        description: "Returns text that occurs after a given character or string",
+    },
+    CatalogueEntry {
+        name: "TEXTBEFORE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, delimiter: text, [instance_num], [match_mode], [match_end], [if_not_found: any]",
+        volatile: false,
+        description: "Returns text that occurs before a given character or string",
+    },
+    CatalogueEntry {
+        name: "TEXTJOIN",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "delimiter: text, ignore_empty: logical, text1: any, [text2: any], ...",
+        volatile: false,
+        description: "Combines the text from multiple ranges and/or strings with a delimiter",
+    },
+    CatalogueEntry {
+        name: "TRIM",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text",
+        volatile: false,
+        description: "Removes spaces from text",
+    },
+    CatalogueEntry {
+        name: "UNICODE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "text: text",
+        volatile: false,
+        description: "Returns the number (code point) that corresponds to the first character of the text",
+    },
+    CatalogueEntry {
+        name: "UNICHAR",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the Unicode character that is referenced by the given numeric value",
+    },
+    CatalogueEntry {
+        name: "CHAR",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the character specified by the code number",
+    },
+    CatalogueEntry {
+        name: "CLEAN",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text",
+        volatile: false,
+        description: "Removes all nonprintable characters from text",
+    },
+    CatalogueEntry {
+        name: "CODE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "text: text",
+        volatile: false,
+        description: "Returns a numeric code for the first character in a text string",
+    },
+    CatalogueEntry {
+        name: "ASC",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text",
+        volatile: false,
+        description: "Changes full-width characters to half-width characters",
+    },
+    CatalogueEntry {
+        name: "ARRAYTOTEXT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "array: array, [format]",
+        volatile: false,
+        description: "Returns an array of text values from any specified range",
+    },
+    CatalogueEntry {
+        name: "DOLLAR",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "number, [decimals]",
+        volatile: false,
+        description: "Converts a number to text, using the currency format",
+    },
+    CatalogueEntry {
+        name: "FINDB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "find_text: text, within_text: text, [start_num]",
+        volatile: false,
+        description: "Finds one text value within another (case-sensitive), counting bytes",
+    },
+    CatalogueEntry {
+        name: "FIXED",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "number, [decimals], [no_commas: logical]",
+        volatile: false,
+        description: "Formats a number as text with a fixed number of decimals",
+    },
+    CatalogueEntry {
+        name: "LEFTB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, [num_bytes]",
+        volatile: false,
+        description: "Returns the leftmost characters from a text value, counting bytes",
+    },
+    CatalogueEntry {
+        name: "LENB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "text: text",
+        volatile: false,
+        description: "Returns the number of bytes used to represent the characters in a text string",
+    },
+    CatalogueEntry {
+        name: "MIDB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, start_num, num_bytes",
+        volatile: false,
+        description: "Returns a specific number of characters from a text string, counting bytes",
+    },
+    CatalogueEntry {
+        name: "NUMBERVALUE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "text: text, [decimal_separator: text], [group_separator: text]",
+        volatile: false,
+        description: "Converts text to number in a locale-independent manner",
+    },
+    CatalogueEntry {
+        name: "PROPER",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text",
+        volatile: false,
+        description: "Capitalizes the first letter in each word of a text value",
+    },
+    CatalogueEntry {
+        name: "REPLACE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "old_text: text, start_num, num_chars, new_text: text",
+        volatile: false,
+        description: "Replaces characters within text",
+    },
+    CatalogueEntry {
+        name: "REPLACEB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "old_text: text, start_num, num_bytes, new_text: text",
+        volatile: false,
+        description: "Replaces characters within text, counting bytes",
+    },
+    CatalogueEntry {
+        name: "RIGHTB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text, [num_bytes]",
+        volatile: false,
+        description: "Returns the rightmost characters from a text value, counting bytes",
+    },
+    CatalogueEntry {
+        name: "SEARCHB",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "find_text: text, within_text: text, [start_num]",
+        volatile: false,
+        description: "Finds one text value within another (not case-sensitive), counting bytes",
+    },
+    CatalogueEntry {
+        name: "UPPER",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "text: text",
+        volatile: false,
+        description: "Converts text to uppercase",
+    },
+    CatalogueEntry {
+        name: "VALUE",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Number,
+        parameters: "text: text",
+        volatile: false,
+        description: "Converts a text argument to a number",
+    },
+    CatalogueEntry {
+        name: "VALUETOTEXT",
+        category: FunctionCategory::Text,
+        returns: ArgumentType::Text,
+        parameters: "value: any, [format]",
+        volatile: false,
+        description: "Returns text from any specified value",
+    },
+    CatalogueEntry {
+        name: "AVERAGE",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the average of its arguments",
+    },
+    CatalogueEntry {
+        name: "AVERAGEA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Returns the average of its arguments, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "AVERAGEIF",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "range: reference, criteria: any, [average_range: reference]",
+        volatile: false,
+        description: "Returns the average of all the cells in a range that meet a given criteria",
+    },
+    CatalogueEntry {
+        name: "AVERAGEIFS",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "average_range: reference, criteria_range1: reference, criteria1: any, [criteria_range2: reference, criteria2: any], ...",
+        volatile: false,
+        description: "Returns the average of all cells that meet multiple criteria",
+    },
+    CatalogueEntry {
+        name: "COUNT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Counts how many numbers are in the list of arguments",
+    },
+    CatalogueEntry {
+        name: "COUNTA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Counts how many values are in the list of arguments",
+    },
+    CatalogueEntry {
+        name: "COUNTBLANK",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "range: reference",
+        volatile: false,
+        description: "Counts the number of blank cells within a range",
+    },
+    CatalogueEntry {
+        name: "COUNTIF",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "range: reference, criteria: any",
+        volatile: false,
+        description: "Counts the number of cells within a range that meet the given criteria",
+    },
+    CatalogueEntry {
+        name: "COUNTIFS",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "criteria_range1: reference, criteria1: any, [criteria_range2: reference, criteria2: any], ...",
+        volatile: false,
+        description: "Counts the number of cells within a range that meet multiple criteria",
+    },
+    CatalogueEntry {
+        name: "MAXIFS",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "max_range: reference, criteria_range1: reference, criteria1: any, [criteria_range2: reference, criteria2: any], ...",
+        volatile: false,
+        description: "Returns the maximum value among cells specified by a given set of conditions or criteria",
+    },
+    CatalogueEntry {
+        name: "MINIFS",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "min_range: reference, criteria_range1: reference, criteria1: any, [criteria_range2: reference, criteria2: any], ...",
+        volatile: false,
+        description: "Returns the minimum value among cells specified by a given set of conditions or criteria",
+    },
+    CatalogueEntry {
+        name: "GEOMEAN",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the geometric mean",
+    },
+    CatalogueEntry {
+        name: "AVEDEV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the average of the absolute deviations of data points from their mean",
+    },
+    CatalogueEntry {
+        name: "BETA.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, alpha, beta, cumulative: logical, [a], [b]",
+        volatile: false,
+        description: "Returns the beta cumulative distribution function",
+    },
+    CatalogueEntry {
+        name: "BETA.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, alpha, beta, [a], [b]",
+        volatile: false,
+        description: "Returns the inverse of the cumulative distribution function for a specified beta distribution",
+    },
+    CatalogueEntry {
+        name: "BINOM.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number_s, trials, probability_s, cumulative: logical",
+        volatile: false,
+        description: "Returns the individual term binomial distribution probability",
+    },
+    CatalogueEntry {
+        name: "BINOM.DIST.RANGE",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "trials, probability_s, number_s, [number_s2]",
+        volatile: false,
+        description: "Returns the probability of a trial result using a binomial distribution",
+    },
+    CatalogueEntry {
+        name: "BINOM.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "trials, probability_s, alpha",
+        volatile: false,
+        description: "Returns the smallest value for which the cumulative binomial distribution is less than or equal to a criterion value",
+    },
+    CatalogueEntry {
+        name: "CHISQ.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom, cumulative: logical",
+        volatile: false,
+        description: "Returns the chi-squared distribution",
+    },
+    CatalogueEntry {
+        name: "CHISQ.DIST.RT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom",
+        volatile: false,
+        description: "Returns the right-tailed probability of the chi-squared distribution",
+    },
+    CatalogueEntry {
+        name: "CHISQ.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom",
+        volatile: false,
+        description: "Returns the inverse of the left-tailed probability of the chi-squared distribution",
+    },
+    CatalogueEntry {
+        name: "CHISQ.INV.RT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom",
+        volatile: false,
+        description: "Returns the inverse of the right-tailed probability of the chi-squared distribution",
+    },
+    CatalogueEntry {
+        name: "CHISQ.TEST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "actual_range: array, expected_range: array",
+        volatile: false,
+        description: "Returns the test for independence",
+    },
+    CatalogueEntry {
+        name: "CONFIDENCE.NORM",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "alpha, standard_dev, size",
+        volatile: false,
+        description: "Returns the confidence interval for a population mean",
+    },
+    CatalogueEntry {
+        name: "CONFIDENCE.T",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "alpha, standard_dev, size",
+        volatile: false,
+        description: "Returns the confidence interval for a population mean, using a Student's t distribution",
+    },
+    CatalogueEntry {
+        name: "COVARIANCE.P",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns covariance, the average of the products of paired deviations",
+    },
+    CatalogueEntry {
+        name: "COVARIANCE.S",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns the sample covariance, the average of the products deviations for each data point pair in two data sets",
+    },
+    CatalogueEntry {
+        name: "DEVSQ",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the sum of squares of deviations",
+    },
+    CatalogueEntry {
+        name: "EXPON.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, lambda, cumulative: logical",
+        volatile: false,
+        description: "Returns the exponential distribution",
+    },
+    CatalogueEntry {
+        name: "F.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom1, deg_freedom2, cumulative: logical",
+        volatile: false,
+        description: "Returns the F probability distribution",
+    },
+    CatalogueEntry {
+        name: "F.DIST.RT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom1, deg_freedom2",
+        volatile: false,
+        description: "Returns the right-tailed F probability distribution",
+    },
+    CatalogueEntry {
+        name: "F.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom1, deg_freedom2",
+        volatile: false,
+        description: "Returns the inverse of the F probability distribution",
+    },
+    CatalogueEntry {
+        name: "F.INV.RT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom1, deg_freedom2",
+        volatile: false,
+        description: "Returns the inverse of the right-tailed F probability distribution",
+    },
+    CatalogueEntry {
+        name: "F.TEST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns the result of an F-test",
+    },
+    CatalogueEntry {
+        name: "FISHER",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the Fisher transformation",
+    },
+    CatalogueEntry {
+        name: "FISHERINV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "y",
+        volatile: false,
+        description: "Returns the inverse of the Fisher transformation",
+    },
+    CatalogueEntry {
+        name: "GAMMA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number",
+        volatile: false,
+        description: "Returns the Gamma function value",
+    },
+    CatalogueEntry {
+        name: "GAMMA.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, alpha, beta, cumulative: logical",
+        volatile: false,
+        description: "Returns the gamma distribution",
+    },
+    CatalogueEntry {
+        name: "GAMMA.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, alpha, beta",
+        volatile: false,
+        description: "Returns the inverse of the gamma cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "GAMMALN",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the natural logarithm of the gamma function",
+    },
+    CatalogueEntry {
+        name: "GAMMALN.PRECISE",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the natural logarithm of the gamma function",
+    },
+    CatalogueEntry {
+        name: "GAUSS",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "z",
+        volatile: false,
+        description: "Returns 0.5 less than the standard normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "HARMEAN",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the harmonic mean",
+    },
+    CatalogueEntry {
+        name: "HYPGEOM.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "sample_s, number_sample, population_s, number_pop, cumulative: logical",
+        volatile: false,
+        description: "Returns the hypergeometric distribution",
+    },
+    CatalogueEntry {
+        name: "KURT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the kurtosis of a data set",
+    },
+    CatalogueEntry {
+        name: "LARGE",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, k",
+        volatile: false,
+        description: "Returns the k-th largest value in a data set",
+    },
+    CatalogueEntry {
+        name: "LOGNORM.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, standard_dev, cumulative: logical",
+        volatile: false,
+        description: "Returns the cumulative lognormal distribution",
+    },
+    CatalogueEntry {
+        name: "LOGNORM.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, mean, standard_dev",
+        volatile: false,
+        description: "Returns the inverse of the lognormal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "MAXA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Returns the maximum value in a list of arguments, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "MEDIAN",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the median of the given numbers",
+    },
+    CatalogueEntry {
+        name: "MINA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Returns the smallest value in a list of arguments, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "NEGBINOM.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number_f, number_s, probability_s, cumulative: logical",
+        volatile: false,
+        description: "Returns the negative binomial distribution",
+    },
+    CatalogueEntry {
+        name: "NORM.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, standard_dev, cumulative: logical",
+        volatile: false,
+        description: "Returns the normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "NORM.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, mean, standard_dev",
+        volatile: false,
+        description: "Returns the inverse of the normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "NORM.S.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "z, cumulative: logical",
+        volatile: false,
+        description: "Returns the standard normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "NORM.S.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability",
+        volatile: false,
+        description: "Returns the inverse of the standard normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "PEARSON",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns the Pearson product moment correlation coefficient",
+    },
+    CatalogueEntry {
+        name: "PHI",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the value of the density function for a standard normal distribution",
+    },
+    CatalogueEntry {
+        name: "POISSON.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, cumulative: logical",
+        volatile: false,
+        description: "Returns the Poisson distribution",
+    },
+    CatalogueEntry {
+        name: "RANK.AVG",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number, ref: reference, [order]",
+        volatile: false,
+        description: "Returns the rank of a number in a list of numbers, the average rank if more than one value has the same rank",
+    },
+    CatalogueEntry {
+        name: "RANK.EQ",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number, ref: reference, [order]",
+        volatile: false,
+        description: "Returns the rank of a number in a list of numbers, the top rank if more than one value has the same rank",
+    },
+    CatalogueEntry {
+        name: "SKEW",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the skewness of a distribution",
+    },
+    CatalogueEntry {
+        name: "SKEW.P",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the skewness of a distribution based on a population",
+    },
+    CatalogueEntry {
+        name: "SMALL",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, k",
+        volatile: false,
+        description: "Returns the k-th smallest value in a data set",
+    },
+    CatalogueEntry {
+        name: "STANDARDIZE",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, standard_dev",
+        volatile: false,
+        description: "Returns a normalized value",
+    },
+    CatalogueEntry {
+        name: "STDEV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Estimates standard deviation based on a sample",
+    },
+    CatalogueEntry {
+        name: "STDEV.P",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Calculates standard deviation based on the entire population",
+    },
+    CatalogueEntry {
+        name: "STDEV.S",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Estimates standard deviation based on a sample",
+    },
+    CatalogueEntry {
+        name: "STDEVA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Estimates standard deviation based on a sample, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "STDEVPA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Calculates standard deviation based on the entire population, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "T.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom, cumulative: logical",
+        volatile: false,
+        description: "Returns the Student's left-tailed t-distribution",
+    },
+    CatalogueEntry {
+        name: "T.DIST.2T",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom",
+        volatile: false,
+        description: "Returns the two-tailed Student's t-distribution",
+    },
+    CatalogueEntry {
+        name: "T.DIST.RT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom",
+        volatile: false,
+        description: "Returns the right-tailed Student's t-distribution",
+    },
+    CatalogueEntry {
+        name: "T.INV",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom",
+        volatile: false,
+        description: "Returns the left-tailed inverse of the Student's t-distribution",
+    },
+    CatalogueEntry {
+        name: "T.INV.2T",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom",
+        volatile: false,
+        description: "Returns the two-tailed inverse of the Student's t-distribution",
+    },
+    CatalogueEntry {
+        name: "T.TEST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array, tails, type",
+        volatile: false,
+        description: "Returns the probability associated with a Student's t-test",
+    },
+    CatalogueEntry {
+        name: "VAR.P",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Calculates variance based on the entire population",
+    },
+    CatalogueEntry {
+        name: "VAR.S",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Estimates variance based on a sample",
+    },
+    CatalogueEntry {
+        name: "VARPA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Calculates variance based on the entire population, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "VARA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "value1: any, [value2: any], ...",
+        volatile: false,
+        description: "Estimates variance based on a sample, including numbers, text, and logical values",
+    },
+    CatalogueEntry {
+        name: "WEIBULL.DIST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, alpha, beta, cumulative: logical",
+        volatile: false,
+        description: "Returns the Weibull distribution",
+    },
+    CatalogueEntry {
+        name: "Z.TEST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, x, [sigma]",
+        volatile: false,
+        description: "Returns the one-tailed probability-value of a z-test",
+    },
+    CatalogueEntry {
+        name: "BETADIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, alpha, beta, [a], [b]",
+        volatile: false,
+        description: "Returns the beta cumulative distribution function",
+    },
+    CatalogueEntry {
+        name: "HYPGEOMDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "sample_s, number_sample, population_s, number_pop",
+        volatile: false,
+        description: "Returns the hypergeometric distribution",
+    },
+    CatalogueEntry {
+        name: "LOGNORMDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, standard_dev",
+        volatile: false,
+        description: "Returns the cumulative lognormal distribution",
+    },
+    CatalogueEntry {
+        name: "NEGBINOMDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number_f, number_s, probability_s",
+        volatile: false,
+        description: "Returns the negative binomial distribution",
+    },
+    CatalogueEntry {
+        name: "NORMSDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "z",
+        volatile: false,
+        description: "Returns the standard normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "TDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom, tails",
+        volatile: false,
+        description: "Returns the Student's t-distribution",
+    },
+    CatalogueEntry {
+        name: "CHIDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom",
+        volatile: false,
+        description: "Returns the one-tailed probability of the chi-squared distribution",
+    },
+    CatalogueEntry {
+        name: "CHIINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom",
+        volatile: false,
+        description: "Returns the inverse of the one-tailed probability of the chi-squared distribution",
+    },
+    CatalogueEntry {
+        name: "CHITEST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "actual_range: array, expected_range: array",
+        volatile: false,
+        description: "Returns the test for independence",
+    },
+    CatalogueEntry {
+        name: "CONFIDENCE",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "alpha, standard_dev, size",
+        volatile: false,
+        description: "Returns the confidence interval for a population mean",
+    },
+    CatalogueEntry {
+        name: "COVAR",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns covariance, the average of the products of paired deviations",
+    },
+    CatalogueEntry {
+        name: "CRITBINOM",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "trials, probability_s, alpha",
+        volatile: false,
+        description: "Returns the smallest value for which the cumulative binomial distribution is less than or equal to a criterion value",
+    },
+    CatalogueEntry {
+        name: "LOGINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, mean, standard_dev",
+        volatile: false,
+        description: "Returns the inverse of the lognormal cumulative distribution function",
+    },
+    CatalogueEntry {
+        name: "BETAINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, alpha, beta, [a], [b]",
+        volatile: false,
+        description: "Returns the inverse of the cumulative distribution function for a specified beta distribution",
+    },
+    CatalogueEntry {
+        name: "BINOMDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number_s, trials, probability_s, cumulative: logical",
+        volatile: false,
+        description: "Returns the individual term binomial distribution probability",
+    },
+    CatalogueEntry {
+        name: "EXPONDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, lambda, cumulative: logical",
+        volatile: false,
+        description: "Returns the exponential distribution",
+    },
+    CatalogueEntry {
+        name: "FDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, deg_freedom1, deg_freedom2",
+        volatile: false,
+        description: "Returns the F probability distribution",
+    },
+    CatalogueEntry {
+        name: "FINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom1, deg_freedom2",
+        volatile: false,
+        description: "Returns the inverse of the F probability distribution",
+    },
+    CatalogueEntry {
+        name: "FTEST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns the result of an F-test",
+    },
+    CatalogueEntry {
+        name: "GAMMADIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, alpha, beta, cumulative: logical",
+        volatile: false,
+        description: "Returns the gamma distribution",
+    },
+    CatalogueEntry {
+        name: "GAMMAINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, alpha, beta",
+        volatile: false,
+        description: "Returns the inverse of the gamma cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "MODE",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the most common value in a data set",
+    },
+    CatalogueEntry {
+        name: "NORMDIST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, standard_dev, cumulative: logical",
+        volatile: false,
+        description: "Returns the normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "NORMINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, mean, standard_dev",
+        volatile: false,
+        description: "Returns the inverse of the normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "NORMSINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability",
+        volatile: false,
+        description: "Returns the inverse of the standard normal cumulative distribution",
+    },
+    CatalogueEntry {
+        name: "PERCENTILE",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array: array, k",
+        volatile: false,
+        description: "Returns the k-th percentile of values in a range",
+    },
+    CatalogueEntry {
+        name: "PERCENTRANK",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array: array, x, [significance]",
+        volatile: false,
+        description: "Returns the percentage rank of a value in a data set",
+    },
+    CatalogueEntry {
+        name: "POISSON",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, mean, cumulative: logical",
+        volatile: false,
+        description: "Returns the Poisson distribution",
+    },
+    CatalogueEntry {
+        name: "QUARTILE",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array: array, quart",
+        volatile: false,
+        description: "Returns the quartile of a data set",
+    },
+    CatalogueEntry {
+        name: "RANK",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number, ref: reference, [order]",
+        volatile: false,
+        description: "Returns the rank of a number in a list of numbers",
+    },
+    CatalogueEntry {
+        name: "STDEVP",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Calculates standard deviation based on the entire population",
+    },
+    CatalogueEntry {
+        name: "TINV",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "probability, deg_freedom",
+        volatile: false,
+        description: "Returns the inverse of the Student's t-distribution",
+    },
+    CatalogueEntry {
+        name: "TTEST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array, tails, type",
+        volatile: false,
+        description: "Returns the probability associated with a Student's t-test",
+    },
+    CatalogueEntry {
+        name: "VAR",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Estimates variance based on a sample",
+    },
+    CatalogueEntry {
+        name: "VARP",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Calculates variance based on the entire population",
+    },
+    CatalogueEntry {
+        name: "WEIBULL",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, alpha, beta, cumulative: logical",
+        volatile: false,
+        description: "Returns the Weibull distribution",
+    },
+    CatalogueEntry {
+        name: "ZTEST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "array: array, x, [sigma]",
+        volatile: false,
+        description: "Returns the one-tailed probability-value of a z-test",
+    },
+    CatalogueEntry {
+        name: "DATE",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "year, month, day",
+        volatile: false,
+        description: "Returns the serial number of a particular date",
+    },
+    CatalogueEntry {
+        name: "DATEDIF",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, end_date, unit: text",
+        volatile: false,
+        description: "Calculates the number of days, months, or years between two dates",
+    },
+    CatalogueEntry {
+        name: "DATEVALUE",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "date_text: text",
+        volatile: false,
+        description: "Converts a date in the form of text to a serial number",
+    },
+    CatalogueEntry {
+        name: "DAY",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number",
+        volatile: false,
+        description: "Converts a serial number to a day of the month",
+    },
+    CatalogueEntry {
+        name: "EDATE",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, months",
+        volatile: false,
+        description: "Returns the serial number of the date that is the indicated number of months before or after the start date",
+    },
+    CatalogueEntry {
+        name: "EOMONTH",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, months",
+        volatile: false,
+        description: "Returns the serial number of the last day of the month before or after a specified number of months",
+    },
+    CatalogueEntry {
+        name: "MONTH",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number",
+        volatile: false,
+        description: "Converts a serial number to a month",
+    },
+    CatalogueEntry {
+        name: "TIME",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "hour, minute, second",
+        volatile: false,
+        description: "Returns the serial number of a particular time",
+    },
+    CatalogueEntry {
+        name: "TIMEVALUE",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "time_text: text",
+        volatile: false,
+        description: "Converts a time in the form of text to a serial number",
+    },
+    CatalogueEntry {
+        name: "HOUR",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number",
+        volatile: false,
+        description: "Converts a serial number to an hour",
+    },
+    CatalogueEntry {
+        name: "MINUTE",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number",
+        volatile: false,
+        description: "Converts a serial number to a minute",
+    },
+    CatalogueEntry {
+        name: "SECOND",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number",
+        volatile: false,
+        description: "Converts a serial number to a second",
+    },
+    CatalogueEntry {
+        name: "NOW",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "",
+        volatile: true,
+        description: "Returns the serial number of the current date and time",
+    },
+    CatalogueEntry {
+        name: "TODAY",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "",
+        volatile: true,
+        description: "Returns the serial number of today's date",
+    },
+    CatalogueEntry {
+        name: "YEAR",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number",
+        volatile: false,
+        description: "Converts a serial number to a year",
+    },
+    CatalogueEntry {
+        name: "NETWORKDAYS",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, end_date, [holidays: array]",
+        volatile: false,
+        description: "Returns the number of whole workdays between two dates",
+    },
+    CatalogueEntry {
+        name: "NETWORKDAYS.INTL",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, end_date, [weekend: any], [holidays: array]",
+        volatile: false,
+        description: "Returns the number of whole workdays between two dates using parameters to indicate which and how many days are weekend days",
+    },
+    CatalogueEntry {
+        name: "DAYS",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "end_date, start_date",
+        volatile: false,
+        description: "Returns the number of days between two dates",
+    },
+    CatalogueEntry {
+        name: "DAYS360",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, end_date, [method: logical]",
+        volatile: false,
+        description: "Calculates the number of days between two dates based on a 360-day year",
+    },
+    CatalogueEntry {
+        name: "WEEKDAY",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number, [return_type]",
+        volatile: false,
+        description: "Converts a serial number to a day of the week",
+    },
+    CatalogueEntry {
+        name: "WEEKNUM",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "serial_number, [return_type]",
+        volatile: false,
+        description: "Converts a serial number to a number representing where the week falls numerically with a year",
+    },
+    CatalogueEntry {
+        name: "WORKDAY",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, days, [holidays: array]",
+        volatile: false,
+        description: "Returns the serial number of the date before or after a specified number of workdays",
+    },
+    CatalogueEntry {
+        name: "WORKDAY.INTL",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, days, [weekend: any], [holidays: array]",
+        volatile: false,
+        description: "Returns the serial number of the date before or after a specified number of workdays using parameters to indicate which and how many days are weekend days",
+    },
+    CatalogueEntry {
+        name: "YEARFRAC",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "start_date, end_date, [basis]",
+        volatile: false,
+        description: "Returns the year fraction representing the number of whole days between start_date and end_date",
+    },
+    CatalogueEntry {
+        name: "ISOWEEKNUM",
+        category: FunctionCategory::DateAndTime,
+        returns: ArgumentType::Number,
+        parameters: "date",
+        volatile: false,
+        description: "Returns the number of the ISO week number of the year for a given date",
+    },
+    CatalogueEntry {
+        name: "ACCRINT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "issue, first_interest, settlement, rate, par, frequency, [basis], [calc_method: logical]",
+        volatile: false,
+        description: "Returns the accrued interest for a security that pays periodic interest",
+    },
+    CatalogueEntry {
+        name: "ACCRINTM",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "issue, settlement, rate, par, [basis]",
+        volatile: false,
+        description: "Returns the accrued interest for a security that pays interest at maturity",
+    },
+    CatalogueEntry {
+        name: "DISC",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, pr, redemption, [basis]",
+        volatile: false,
+        description: "Returns the discount rate for a security",
+    },
+    CatalogueEntry {
+        name: "FVSCHEDULE",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "principal, schedule: array",
+        volatile: false,
+        description: "Returns the future value of an initial principal after applying a series of compound interest rates",
+    },
+    CatalogueEntry {
+        name: "INTRATE",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, investment, redemption, [basis]",
+        volatile: false,
+        description: "Returns the interest rate for a fully invested security",
+    },
+    CatalogueEntry {
+        name: "PRICEDISC",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, discount, redemption, [basis]",
+        volatile: false,
+        description: "Returns the price per $100 face value of a discounted security",
+    },
+    CatalogueEntry {
+        name: "PRICEMAT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, issue, rate, yld, [basis]",
+        volatile: false,
+        description: "Returns the price per $100 face value of a security that pays interest at maturity",
+    },
+    CatalogueEntry {
+        name: "RECEIVED",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, investment, discount, [basis]",
+        volatile: false,
+        description: "Returns the amount received at maturity for a fully invested security",
+    },
+    CatalogueEntry {
+        name: "YIELDDISC",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, pr, redemption, [basis]",
+        volatile: false,
+        description: "Returns the annual yield for a discounted security",
+    },
+    CatalogueEntry {
+        name: "YIELDMAT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, issue, rate, pr, [basis]",
+        volatile: false,
+        description: "Returns the annual yield of a security that pays interest at maturity",
+    },
+    CatalogueEntry {
+        name: "CUMIPMT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, nper, pv, start_period, end_period, type",
+        volatile: false,
+        description: "Returns the cumulative interest paid between two periods",
+    },
+    CatalogueEntry {
+        name: "CUMPRINC",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, nper, pv, start_period, end_period, type",
+        volatile: false,
+        description: "Returns the cumulative principal paid on a loan between two periods",
+    },
+    CatalogueEntry {
+        name: "DB",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "cost, salvage, life, period, [month]",
+        volatile: false,
+        description: "Returns the depreciation of an asset for a specified period by using the fixed-declining balance method",
+    },
+    CatalogueEntry {
+        name: "DDB",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "cost, salvage, life, period, [factor]",
+        volatile: false,
+        description: "Returns the depreciation of an asset for a specified period by using the double-declining balance method",
+    },
+    CatalogueEntry {
+        name: "DOLLARDE",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "fractional_dollar, fraction",
+        volatile: false,
+        description: "Converts a dollar price, expressed as a fraction, into a dollar price, expressed as a decimal number",
+    },
+    CatalogueEntry {
+        name: "DOLLARFR",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "decimal_dollar, fraction",
+        volatile: false,
+        description: "Converts a dollar price, expressed as a decimal number, into a dollar price, expressed as a fraction",
+    },
+    CatalogueEntry {
+        name: "EFFECT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "nominal_rate, npery",
+        volatile: false,
+        description: "Returns the effective annual interest rate",
+    },
+    CatalogueEntry {
+        name: "FV",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, nper, pmt, [pv], [type]",
+        volatile: false,
+        description: "Returns the future value of an investment",
+    },
+    CatalogueEntry {
+        name: "IPMT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, per, nper, pv, [fv], [type]",
+        volatile: false,
+        description: "Returns the interest payment for an investment for a given period",
+    },
+    CatalogueEntry {
+        name: "IRR",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "values: array, [guess]",
+        volatile: false,
+        description: "Returns the internal rate of return for a series of cash flows",
+    },
+    CatalogueEntry {
+        name: "ISPMT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, per, nper, pv",
+        volatile: false,
+        description: "Calculates the interest paid during a specific period of an investment",
+    },
+    CatalogueEntry {
+        name: "MIRR",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "values: array, finance_rate, reinvest_rate",
+        volatile: false,
+        description: "Returns the internal rate of return where positive and negative cash flows are financed at different rates",
+    },
+    CatalogueEntry {
+        name: "NOMINAL",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "effect_rate, npery",
+        volatile: false,
+        description: "Returns the annual nominal interest rate",
+    },
+    CatalogueEntry {
+        name: "NPER",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, pmt, pv, [fv], [type]",
+        volatile: false,
+        description: "Returns the number of periods for an investment",
+    },
+    CatalogueEntry {
+        name: "NPV",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, value1, [value2], ...",
+        volatile: false,
+        description: "Returns the net present value of an investment based on a series of periodic cash flows and a discount rate",
+    },
+    CatalogueEntry {
+        name: "PDURATION",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, pv, fv",
+        volatile: false,
+        description: "Returns the number of periods required by an investment to reach a specified value",
+    },
+    CatalogueEntry {
+        name: "PMT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, nper, pv, [fv], [type]",
+        volatile: false,
+        description: "Returns the periodic payment for an annuity",
+    },
+    CatalogueEntry {
+        name: "PPMT",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, per, nper, pv, [fv], [type]",
+        volatile: false,
+        description: "Returns the payment on the principal for an investment for a given period",
+    },
+    CatalogueEntry {
+        name: "PV",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, nper, pmt, [fv], [type]",
+        volatile: false,
+        description: "Returns the present value of an investment",
+    },
+    CatalogueEntry {
+        name: "RATE",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "nper, pmt, pv, [fv], [type], [guess]",
+        volatile: false,
+        description: "Returns the interest rate per period of an annuity",
+    },
+    CatalogueEntry {
+        name: "RRI",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "nper, pv, fv",
+        volatile: false,
+        description: "Returns an equivalent interest rate for the growth of an investment",
+    },
+    CatalogueEntry {
+        name: "SLN",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "cost, salvage, life",
+        volatile: false,
+        description: "Returns the straight-line depreciation of an asset for one period",
+    },
+    CatalogueEntry {
+        name: "SYD",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "cost, salvage, life, per",
+        volatile: false,
+        description: "Returns the sum-of-years' digits depreciation of an asset for a specified period",
+    },
+    CatalogueEntry {
+        name: "TBILLEQ",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, discount",
+        volatile: false,
+        description: "Returns the bond-equivalent yield for a Treasury bill",
+    },
+    CatalogueEntry {
+        name: "TBILLPRICE",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, discount",
+        volatile: false,
+        description: "Returns the price per $100 face value for a Treasury bill",
+    },
+    CatalogueEntry {
+        name: "TBILLYIELD",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "settlement, maturity, pr",
+        volatile: false,
+        description: "Returns the yield for a Treasury bill",
+    },
+    CatalogueEntry {
+        name: "XIRR",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "values: array, dates: array, [guess]",
+        volatile: false,
+        description: "Returns the internal rate of return for a schedule of cash flows that is not necessarily periodic",
+    },
+    CatalogueEntry {
+        name: "XNPV",
+        category: FunctionCategory::Financial,
+        returns: ArgumentType::Number,
+        parameters: "rate, values: array, dates: array",
+        volatile: false,
+        description: "Returns the net present value for a schedule of cash flows that is not necessarily periodic",
+    },
+    CatalogueEntry {
+        name: "BESSELI",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x, n",
+        volatile: false,
+        description: "Returns the modified Bessel function In(x)",
+    },
+    CatalogueEntry {
+        name: "BESSELJ",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x, n",
+        volatile: false,
+        description: "Returns the Bessel function Jn(x)",
+    },
+    CatalogueEntry {
+        name: "BESSELK",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x, n",
+        volatile: false,
+        description: "Returns the modified Bessel function Kn(x)",
+    },
+    CatalogueEntry {
+        name: "BESSELY",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x, n",
+        volatile: false,
+        description: "Returns the Bessel function Yn(x)",
+    },
+    CatalogueEntry {
+        name: "ERF",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "lower_limit, [upper_limit]",
+        volatile: false,
+        description: "Returns the error function",
+    },
+    CatalogueEntry {
+        name: "ERFC",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the complementary error function",
+    },
+    CatalogueEntry {
+        name: "ERFC.PRECISE",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the complementary error function integrated between x and infinity",
+    },
+    CatalogueEntry {
+        name: "ERF.PRECISE",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "x",
+        volatile: false,
+        description: "Returns the error function",
+    },
+    CatalogueEntry {
+        name: "BIN2DEC",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number: text",
+        volatile: false,
+        description: "Converts a binary number to decimal",
+    },
+    CatalogueEntry {
+        name: "BIN2HEX",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number: text, [places]",
+        volatile: false,
+        description: "Converts a binary number to hexadecimal",
+    },
+    CatalogueEntry {
+        name: "BIN2OCT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number: text, [places]",
+        volatile: false,
+        description: "Converts a binary number to octal",
+    },
+    CatalogueEntry {
+        name: "DEC2BIN",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number, [places]",
+        volatile: false,
+        description: "Converts a decimal number to binary",
+    },
+    CatalogueEntry {
+        name: "DEC2HEX",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number, [places]",
+        volatile: false,
+        description: "Converts a decimal number to hexadecimal",
+    },
+    CatalogueEntry {
+        name: "DEC2OCT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number, [places]",
+        volatile: false,
+        description: "Converts a decimal number to octal",
+    },
+    CatalogueEntry {
+        name: "HEX2BIN",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number: text, [places]",
+        volatile: false,
+        description: "Converts a hexadecimal number to binary",
+    },
+    CatalogueEntry {
+        name: "HEX2DEC",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number: text",
+        volatile: false,
+        description: "Converts a hexadecimal number to decimal",
+    },
+    CatalogueEntry {
+        name: "HEX2OCT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number: text, [places]",
+        volatile: false,
+        description: "Converts a hexadecimal number to octal",
+    },
+    CatalogueEntry {
+        name: "OCT2BIN",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number: text, [places]",
+        volatile: false,
+        description: "Converts an octal number to binary",
+    },
+    CatalogueEntry {
+        name: "OCT2DEC",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number: text",
+        volatile: false,
+        description: "Converts an octal number to decimal",
+    },
+    CatalogueEntry {
+        name: "OCT2HEX",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "number: text, [places]",
+        volatile: false,
+        description: "Converts an octal number to hexadecimal",
+    },
+    CatalogueEntry {
+        name: "BITAND",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number1, number2",
+        volatile: false,
+        description: "Returns a bitwise 'AND' of two numbers",
+    },
+    CatalogueEntry {
+        name: "BITLSHIFT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number, shift_amount",
+        volatile: false,
+        description: "Returns a value number shifted left by shift_amount bits",
+    },
+    CatalogueEntry {
+        name: "BITOR",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number1, number2",
+        volatile: false,
+        description: "Returns a bitwise OR of two numbers",
+    },
+    CatalogueEntry {
+        name: "BITRSHIFT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number, shift_amount",
+        volatile: false,
+        description: "Returns a value number shifted right by shift_amount bits",
+    },
+    CatalogueEntry {
+        name: "BITXOR",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number1, number2",
+        volatile: false,
+        description: "Returns a bitwise 'Exclusive Or' of two numbers",
+    },
+    CatalogueEntry {
+        name: "COMPLEX",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "real_num, i_num, [suffix: text]",
+        volatile: false,
+        description: "Converts real and imaginary coefficients into a complex number",
+    },
+    CatalogueEntry {
+        name: "IMABS",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the absolute value (modulus) of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMAGINARY",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the imaginary coefficient of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMARGUMENT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the argument theta, an angle expressed in radians",
+    },
+    CatalogueEntry {
+        name: "IMCONJUGATE",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the complex conjugate of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMCOS",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the cosine of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMCOSH",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the hyperbolic cosine of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMCOT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the cotangent of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMCSC",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the cosecant of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMCSCH",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the hyperbolic cosecant of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMDIV",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber1: text, inumber2: text",
+        volatile: false,
+        description: "Returns the quotient of two complex numbers",
+    },
+    CatalogueEntry {
+        name: "IMEXP",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the exponential of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMLN",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the natural logarithm of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMLOG10",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the base-10 logarithm of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMLOG2",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the base-2 logarithm of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMPOWER",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text, number",
+        volatile: false,
+        description: "Returns a complex number raised to an integer power",
+    },
+    CatalogueEntry {
+        name: "IMPRODUCT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber1: text, [inumber2: text], ...",
+        volatile: false,
+        description: "Returns the product of complex numbers",
+    },
+    CatalogueEntry {
+        name: "IMREAL",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the real coefficient of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMSEC",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the secant of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMSECH",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the hyperbolic secant of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMSIN",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the sine of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMSINH",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the hyperbolic sine of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMSQRT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the square root of a complex number",
+    },
+    CatalogueEntry {
+        name: "IMSUB",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber1: text, inumber2: text",
+        volatile: false,
+        description: "Returns the difference between two complex numbers",
+    },
+    CatalogueEntry {
+        name: "IMSUM",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber1: text, [inumber2: text], ...",
+        volatile: false,
+        description: "Returns the sum of complex numbers",
+    },
+    CatalogueEntry {
+        name: "IMTAN",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Text,
+        parameters: "inumber: text",
+        volatile: false,
+        description: "Returns the tangent of a complex number",
+    },
+    CatalogueEntry {
+        name: "CONVERT",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number, from_unit: text, to_unit: text",
+        volatile: false,
+        description: "Converts a number from one measurement system to another",
+    },
+    CatalogueEntry {
+        name: "DELTA",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2]",
+        volatile: false,
+        description: "Tests whether two values are equal",
+    },
+    CatalogueEntry {
+        name: "GESTEP",
+        category: FunctionCategory::Engineering,
+        returns: ArgumentType::Number,
+        parameters: "number, [step]",
+        volatile: false,
+        description: "Tests whether a number is greater than a threshold value",
+    },
+    CatalogueEntry {
+        name: "SUBTOTAL",
+        category: FunctionCategory::MathAndTrigonometry,
+        returns: ArgumentType::Number,
+        parameters: "function_num, ref1: reference, [ref2: reference], ...",
+        volatile: false,
+        description: "Returns a subtotal in a list or database",
+    },
+    CatalogueEntry {
+        name: "DAVERAGE",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Returns the average of selected database entries",
+    },
+    CatalogueEntry {
+        name: "DCOUNT",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Counts the cells that contain numbers in a database",
+    },
+    CatalogueEntry {
+        name: "DGET",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Any,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Extracts from a database a single record that matches the specified criteria",
+    },
+    CatalogueEntry {
+        name: "DMAX",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Returns the maximum value from selected database entries",
+    },
+    CatalogueEntry {
+        name: "DMIN",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Returns the minimum value from selected database entries",
+    },
+    CatalogueEntry {
+        name: "DSUM",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Adds the numbers in the field column of records in the database that match the criteria",
+    },
+    CatalogueEntry {
+        name: "DCOUNTA",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Counts nonblank cells in a database",
+    },
+    CatalogueEntry {
+        name: "DPRODUCT",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Multiplies the values in a particular field of records that match the criteria in a database",
+    },
+    CatalogueEntry {
+        name: "DSTDEV",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Estimates the standard deviation based on a sample of selected database entries",
+    },
+    CatalogueEntry {
+        name: "DVAR",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Estimates variance based on a sample from selected database entries",
+    },
+    CatalogueEntry {
+        name: "DVARP",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Calculates variance based on the entire population of selected database entries",
+    },
+    CatalogueEntry {
+        name: "DSTDEVP",
+        category: FunctionCategory::Database,
+        returns: ArgumentType::Number,
+        parameters: "database: reference, field: any, criteria: reference",
+        volatile: false,
+        description: "Calculates the standard deviation based on the entire population of selected database entries",
+    },
+    CatalogueEntry {
+        name: "CORREL",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array1: array, array2: array",
+        volatile: false,
+        description: "Returns the correlation coefficient between two data sets",
+    },
+    CatalogueEntry {
+        name: "RSQ",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "known_ys: array, known_xs: array",
+        volatile: false,
+        description: "Returns the square of the Pearson product moment correlation coefficient",
+    },
+    CatalogueEntry {
+        name: "INTERCEPT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "known_ys: array, known_xs: array",
+        volatile: false,
+        description: "Returns the intercept of the linear regression line",
+    },
+    CatalogueEntry {
+        name: "SLOPE",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "known_ys: array, known_xs: array",
+        volatile: false,
+        description: "Returns the slope of the linear regression line",
+    },
+    CatalogueEntry {
+        name: "STEYX",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "known_ys: array, known_xs: array",
+        volatile: false,
+        description: "Returns the standard error of the predicted y-value for each x in the regression",
+    },
+    CatalogueEntry {
+        name: "FORECAST",
+        category: FunctionCategory::Compatibility,
+        returns: ArgumentType::Number,
+        parameters: "x, known_ys: array, known_xs: array",
+        volatile: false,
+        description: "Returns a value along a linear trend",
+    },
+    CatalogueEntry {
+        name: "FORECAST.LINEAR",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x, known_ys: array, known_xs: array",
+        volatile: false,
+        description: "Returns a future value based on existing values",
+    },
+    CatalogueEntry {
+        name: "FREQUENCY",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Array,
+        parameters: "data_array: array, bins_array: array",
+        volatile: false,
+        description: "Returns a frequency distribution as a vertical array",
+    },
+    CatalogueEntry {
+        name: "GROWTH",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Array,
+        parameters: "known_ys: array, [known_xs: array], [new_xs: array], [const: logical]",
+        volatile: false,
+        description: "Returns values along an exponential trend",
+    },
+    CatalogueEntry {
+        name: "LINEST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Array,
+        parameters: "known_ys: array, [known_xs: array], [const: logical], [stats: logical]",
+        volatile: false,
+        description: "Returns the parameters of a linear trend",
+    },
+    CatalogueEntry {
+        name: "LOGEST",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Array,
+        parameters: "known_ys: array, [known_xs: array], [const: logical], [stats: logical]",
+        volatile: false,
+        description: "Returns the parameters of an exponential trend",
+    },
+    CatalogueEntry {
+        name: "MODE.MULT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Array,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns a vertical array of the most frequently occurring values in a data set",
+    },
+    CatalogueEntry {
+        name: "MODE.SNGL",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number1, [number2], ...",
+        volatile: false,
+        description: "Returns the most common value in a data set",
+    },
+    CatalogueEntry {
+        name: "PERCENTILE.EXC",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, k",
+        volatile: false,
+        description: "Returns the k-th percentile of values in a range, where k is in the range 0..1, exclusive",
+    },
+    CatalogueEntry {
+        name: "PERCENTILE.INC",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, k",
+        volatile: false,
+        description: "Returns the k-th percentile of values in a range",
+    },
+    CatalogueEntry {
+        name: "PERCENTRANK.EXC",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, x, [significance]",
+        volatile: false,
+        description: "Returns the rank of a value in a data set as a percentage (0..1, exclusive) of the data set",
+    },
+    CatalogueEntry {
+        name: "PERCENTRANK.INC",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, x, [significance]",
+        volatile: false,
+        description: "Returns the percentage rank of a value in a data set",
+    },
+    CatalogueEntry {
+        name: "PERMUT",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number, number_chosen",
+        volatile: false,
+        description: "Returns the number of permutations for a given number of objects",
+    },
+    CatalogueEntry {
+        name: "PERMUTATIONA",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "number, number_chosen",
+        volatile: false,
+        description: "Returns the number of permutations for a given number of objects (with repetitions) that can be selected from the total objects",
+    },
+    CatalogueEntry {
+        name: "PROB",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "x_range: array, prob_range: array, lower_limit, [upper_limit]",
+        volatile: false,
+        description: "Returns the probability that values in a range are between two limits",
+    },
+    CatalogueEntry {
+        name: "QUARTILE.EXC",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, quart",
+        volatile: false,
+        description: "Returns the quartile of the data set, based on percentile values from 0..1, exclusive",
+    },
+    CatalogueEntry {
+        name: "QUARTILE.INC",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, quart",
+        volatile: false,
+        description: "Returns the quartile of a data set",
+    },
+    CatalogueEntry {
+        name: "TREND",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Array,
+        parameters: "known_ys: array, [known_xs: array], [new_xs: array], [const: logical]",
+        volatile: false,
+        description: "Returns values along a linear trend",
+    },
+    CatalogueEntry {
+        name: "TRIMMEAN",
+        category: FunctionCategory::Statistical,
+        returns: ArgumentType::Number,
+        parameters: "array: array, percent",
+        volatile: false,
+        description: "Returns the mean of the interior of a data set",
+    },
+];
@@ -64,14 +64,13 @@ macro_rules! date_part_fn {
             // Convert the serial number to a NaiveDate and return the requested
             // component. Broadcasts element-wise over ranges/arrays. Uses the free
             // function from_excel_date to avoid borrowing self in the closure.
-            self.apply_number_unary(
-                &args[0],
-                cell,
-                |f| match from_excel_date(f.floor() as i64) {
+            let date1904 = self.workbook.settings.date1904;
+            self.apply_number_unary(&args[0], cell, |f| {
+                match from_excel_date(f.floor() as i64, date1904) {
                     Ok(date) => Ok(date.$method() as f64),
                     Err(_) => Err(Error::NUM),
-                },
-            )
+                }
+            })
         }
     };
 }
@@ -103,8 +102,8 @@ macro_rules! time_part_fn {
 
 use crate::arithmetic::bcast_idx;
 use crate::constants::MAXIMUM_DATE_SERIAL_NUMBER;
-use crate::constants::MINIMUM_DATE_SERIAL_NUMBER;
 use crate::expressions::types::CellReferenceIndex;
+use crate::formatter::dates::date_system_offset;
 use crate::formatter::dates::date_to_serial_number;
 use crate::formatter::dates::permissive_date_to_serial_number;
 use crate::formatter::dates::DATE_OUT_OF_RANGE_MESSAGE;
@@ -425,7 +424,7 @@ fn parse_year_simple(year_str: &str) -> Result<i32, String> {
     }
 }
 
-fn parse_datevalue_text(value: &str) -> Result<i32, String> {
+fn parse_datevalue_text(value: &str, date1904: bool) -> Result<i32, String> {
     // Trim whitespace and discard any time component (e.g., "2024-02-29 06:00" -> "2024-02-29")
     let mut date_str = value.trim();
     if let Some(idx) = date_str.find('T') {
@@ -489,11 +488,11 @@ fn parse_datevalue_text(value: &str) -> Result<i32, String> {
     let year = parse_year_simple(year_str)?;
 
     // Excel 1900 leap-year bug: 29-Feb-1900 is treated as serial 60
-    if year == 1900 && month == 2 && day == 29 {
+    if !date1904 && year == 1900 && month == 2 && day == 29 {
         return Ok(60);
     }
 
-    match date_to_serial_number(day, month, year) {
+    match date_to_serial_number(day, month, year, date1904) {
         Ok(n) => Ok(n),
         Err(_) => Err("Not a valid date".to_string()),
     }
@@ -508,14 +507,16 @@ impl<'a> Model<'a> {
         let result = self.evaluate_node_in_context(node, cell);
         match result {
             CalcResult::Number(f) => Ok(f.floor() as i64),
-            CalcResult::String(s) => match parse_datevalue_text(&s) {
-                Ok(n) => Ok(n as i64),
-                Err(_) => Err(CalcResult::Error {
-                    error: Error::VALUE,
-                    origin: cell,
-                    message: "Invalid date".to_string(),
-                }),
-            },
+            CalcResult::String(s) => {
+                match parse_datevalue_text(&s, self.workbook.settings.date1904) {
+                    Ok(n) => Ok(n as i64),
+                    Err(_) => Err(CalcResult::Error {
+                        error: Error::VALUE,
+                        origin: cell,
+                        message: "Invalid date".to_string(),
+                    }),
+                }
+            }
             CalcResult::Boolean(b) => {
                 if b {
                     Ok(1)
@@ -600,7 +601,7 @@ impl<'a> Model<'a> {
             month = 1;
             year += 1;
         }
-        match date_to_serial_number(1, month, year) {
+        match date_to_serial_number(1, month, year, self.workbook.settings.date1904) {
             Ok(serial_number) => CalcResult::Number(serial_number as f64 - 1.0),
             Err(message) => CalcResult::Error {
                 error: Error::NUM,
@@ -630,7 +631,8 @@ impl<'a> Model<'a> {
             Err(e) => return e,
         };
 
-        fn date_node(year_f: f64, month_f: f64, day_f: f64) -> ArrayNode {
+        let date1904 = self.workbook.settings.date1904;
+        let date_node = |year_f: f64, month_f: f64, day_f: f64| -> ArrayNode {
             let year = year_f.floor() as i32;
             if year < 0 {
                 return ArrayNode::Error(Error::NUM);
@@ -639,11 +641,12 @@ impl<'a> Model<'a> {
                 day_f.floor() as i32,
                 month_f.floor() as i32,
                 year,
+                date1904,
             ) {
                 Ok(n) => ArrayNode::Number(n as f64),
                 Err(_) => ArrayNode::Error(Error::NUM),
             }
-        }
+        };
 
         match (&year_na, &month_na, &day_na) {
             (NumberOrArray::Number(yr), NumberOrArray::Number(mo), NumberOrArray::Number(dy)) => {
@@ -748,8 +751,10 @@ impl<'a> Model<'a> {
             date - Months::new(months_abs)
         };
 
-        let serial_number = native_date.num_days_from_ce() - EXCEL_DATE_BASE;
-        if serial_number < MINIMUM_DATE_SERIAL_NUMBER {
+        let date1904 = self.workbook.settings.date1904;
+        let serial_number =
+            native_date.num_days_from_ce() - EXCEL_DATE_BASE - date_system_offset(date1904);
+        if from_excel_date(serial_number as i64, date1904).is_err() {
             return CalcResult::Error {
                 error: Error::NUM,
                 origin: cell,
@@ -912,7 +917,7 @@ impl<'a> Model<'a> {
         serial: i64,
         cell: CellReferenceIndex,
     ) -> Result<chrono::NaiveDate, CalcResult> {
-        match from_excel_date(serial) {
+        match from_excel_date(serial, self.workbook.settings.date1904) {
             Ok(date) => Ok(date),
             Err(_) => Err(CalcResult::Error {
                 error: Error::NUM,
@@ -1082,8 +1087,9 @@ impl<'a> Model<'a> {
             }
             None => &self.tz,
         };
+        let offset = date_system_offset(self.workbook.settings.date1904) as f64;
         match crate::tz::excel_serial_for_now(tz) {
-            Some(serial) => CalcResult::Number(serial.floor()),
+            Some(serial) => CalcResult::Number(serial.floor() - offset),
             None => CalcResult::Error {
                 error: Error::ERROR,
                 origin: cell,
@@ -1122,8 +1128,9 @@ impl<'a> Model<'a> {
             }
             None => &self.tz,
         };
+        let offset = date_system_offset(self.workbook.settings.date1904) as f64;
         match crate::tz::excel_serial_for_now(tz) {
-            Some(serial) => CalcResult::Number(serial),
+            Some(serial) => CalcResult::Number(serial - offset),
             None => CalcResult::Error {
                 error: Error::ERROR,
                 origin: cell,
@@ -1197,14 +1204,16 @@ impl<'a> Model<'a> {
             return CalcResult::new_args_number_error(cell);
         }
         match self.evaluate_node_in_context(&args[0], cell) {
-            CalcResult::String(s) => match parse_datevalue_text(&s) {
-                Ok(n) => CalcResult::Number(n as f64),
-                Err(_) => CalcResult::Error {
-                    error: Error::VALUE,
-                    origin: cell,
-                    message: "Invalid date".to_string(),
-                },
-            },
+            CalcResult::String(s) => {
+                match parse_datevalue_text(&s, self.workbook.settings.date1904) {
+                    Ok(n) => CalcResult::Number(n as f64),
+                    Err(_) => CalcResult::Error {
+                        error: Error::VALUE,
+                        origin: cell,
+                        message: "Invalid date".to_string(),
+                    },
+                }
+            }
             CalcResult::Number(f) => CalcResult::Number(f.floor()),
             CalcResult::Boolean(b) => {
                 if b {
@@ -1460,8 +1469,9 @@ impl<'a> Model<'a> {
             1
         };
         // Broadcasts element-wise over a range/array date argument.
+        let date1904 = self.workbook.settings.date1904;
         self.apply_number_unary(&args[0], cell, move |serial| {
-            let date = from_excel_date(serial.floor() as i64).map_err(|_| Error::NUM)?;
+            let date = from_excel_date(serial.floor() as i64, date1904).map_err(|_| Error::NUM)?;
             weekday_number(date, return_type)
         })
     }
@@ -1526,8 +1536,9 @@ impl<'a> Model<'a> {
             return CalcResult::new_args_number_error(cell);
         }
         // Broadcasts element-wise over a range/array date argument.
+        let date1904 = self.workbook.settings.date1904;
         self.apply_number_unary(&args[0], cell, |serial| {
-            let date = from_excel_date(serial.floor() as i64).map_err(|_| Error::NUM)?;
+            let date = from_excel_date(serial.floor() as i64, date1904).map_err(|_| Error::NUM)?;
             Ok(date.iso_week().week() as f64)
         })
     }
@@ -1578,7 +1589,9 @@ impl<'a> Model<'a> {
                 }
             }
         }
-        let serial = date.num_days_from_ce() - EXCEL_DATE_BASE;
+        let serial = date.num_days_from_ce()
+            - EXCEL_DATE_BASE
+            - date_system_offset(self.workbook.settings.date1904);
         CalcResult::Number(serial as f64)
     }
 
@@ -1590,16 +1603,19 @@ impl<'a> Model<'a> {
         let mut holiday_set = std::collections::HashSet::new();
 
         if let Some(arg) = arg_option {
-            self.collect_serial_numbers(arg, cell, |serial| match from_excel_date(serial) {
-                Ok(date) => {
-                    holiday_set.insert(date);
-                    Ok(())
+            let date1904 = self.workbook.settings.date1904;
+            self.collect_serial_numbers(arg, cell, |serial| {
+                match from_excel_date(serial, date1904) {
+                    Ok(date) => {
+                        holiday_set.insert(date);
+                        Ok(())
+                    }
+                    Err(_) => Err(CalcResult::Error {
+                        error: Error::NUM,
+                        origin: cell,
+                        message: "Invalid holiday date".to_string(),
+                    }),
                 }
-                Err(_) => Err(CalcResult::Error {
-                    error: Error::NUM,
-                    origin: cell,
-                    message: "Invalid holiday date".to_string(),
-                }),
             })?;
         }
 
@@ -1659,7 +1675,9 @@ impl<'a> Model<'a> {
                 }
             }
         }
-        let serial = date.num_days_from_ce() - EXCEL_DATE_BASE;
+        let serial = date.num_days_from_ce()
+            - EXCEL_DATE_BASE
+            - date_system_offset(self.workbook.settings.date1904);
         CalcResult::Number(serial as f64)
     }
 
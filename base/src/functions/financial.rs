@@ -26,8 +26,8 @@ use super::financial_util::{compute_irr, compute_npv, compute_rate, compute_xirr
 // (e.g. adding one month to Jan 31 yields Feb 28/29).
 //
 // `months_to_add` may be negative (walk backward) or positive.
-fn add_months_eom(serial: i64, months_to_add: i32) -> Result<i64, String> {
-    let date = from_excel_date(serial)?;
+fn add_months_eom(serial: i64, months_to_add: i32, date1904: bool) -> Result<i64, String> {
+    let date = from_excel_date(serial, date1904)?;
     let src_year = date.year();
     let src_month = date.month() as i32;
     let src_day = date.day();
@@ -41,7 +41,7 @@ fn add_months_eom(serial: i64, months_to_add: i32) -> Result<i64, String> {
     } else {
         src_day.min(last_day_dst)
     };
-    let serial_i32 = date_to_serial_number(dst_day, dst_month as u32, dst_year)?;
+    let serial_i32 = date_to_serial_number(dst_day, dst_month as u32, dst_year, date1904)?;
     Ok(serial_i32 as i64)
 }
 
@@ -61,9 +61,9 @@ fn last_day_of_month(year: i32, month: u32) -> u32 {
     }
 }
 
-fn is_less_than_one_year(start_date: i64, end_date: i64) -> Result<bool, String> {
-    let end = from_excel_date(end_date)?;
-    let start = from_excel_date(start_date)?;
+fn is_less_than_one_year(start_date: i64, end_date: i64, date1904: bool) -> Result<bool, String> {
+    let end = from_excel_date(end_date, date1904)?;
+    let start = from_excel_date(start_date, date1904)?;
     if end_date - start_date < 365 {
         return Ok(true);
     }
@@ -1446,7 +1446,11 @@ impl<'a> Model<'a> {
             Ok(f) => f,
             Err(s) => return s,
         };
-        let less_than_one_year = match is_less_than_one_year(settlement as i64, maturity as i64) {
+        let less_than_one_year = match is_less_than_one_year(
+            settlement as i64,
+            maturity as i64,
+            self.workbook.settings.date1904,
+        ) {
             Ok(f) => f,
             Err(_) => return CalcResult::new_error(Error::NUM, cell, "Invalid date".to_string()),
         };
@@ -1511,7 +1515,11 @@ impl<'a> Model<'a> {
             Ok(f) => f,
             Err(s) => return s,
         };
-        let less_than_one_year = match is_less_than_one_year(settlement as i64, maturity as i64) {
+        let less_than_one_year = match is_less_than_one_year(
+            settlement as i64,
+            maturity as i64,
+            self.workbook.settings.date1904,
+        ) {
             Ok(f) => f,
             Err(_) => return CalcResult::new_error(Error::NUM, cell, "Invalid date".to_string()),
         };
@@ -1562,7 +1570,11 @@ impl<'a> Model<'a> {
             Ok(f) => f,
             Err(s) => return s,
         };
-        let less_than_one_year = match is_less_than_one_year(settlement as i64, maturity as i64) {
+        let less_than_one_year = match is_less_than_one_year(
+            settlement as i64,
+            maturity as i64,
+            self.workbook.settings.date1904,
+        ) {
             Ok(f) => f,
             Err(_) => return CalcResult::new_error(Error::NUM, cell, "Invalid date".to_string()),
         };
@@ -2026,7 +2038,11 @@ impl<'a> Model<'a> {
         let mut period_ends = vec![first_interest_serial];
         let mut step = 1i32;
         loop {
-            let prev_end = match add_months_eom(first_interest_serial, -months_per_period * step) {
+            let prev_end = match add_months_eom(
+                first_interest_serial,
+                -months_per_period * step,
+                self.workbook.settings.date1904,
+            ) {
                 Ok(s) => s,
                 Err(e) => return CalcResult::new_error(Error::NUM, cell, e),
             };
@@ -284,7 +284,9 @@ impl<'a> Model<'a> {
                             return CalcResult::Number(range.left.sheet as f64 + 1.0)
                         }
                         ParsedDefinedName::InvalidDefinedNameFormula
-                        | ParsedDefinedName::LambdaDefinition(..) => {
+                        | ParsedDefinedName::Formula
+                        | ParsedDefinedName::LambdaDefinition(..)
+                        | ParsedDefinedName::ExternalReference(..) => {
                             return CalcResult::Error {
                                 error: Error::ERROR,
                                 origin: cell,
@@ -0,0 +1,170 @@
+use std::collections::HashSet;
+
+use crate::{
+    calc_result::CalcResult,
+    cast::calc_result_to_array_node,
+    expressions::{parser::ArrayNode, parser::Node, token::Error, types::CellReferenceIndex},
+    model::{CellState, Model},
+    types::{ArrayKind, Cell},
+};
+
+impl Model<'_> {
+    /// `{=TABLE(row_input, column_input)}`
+    ///
+    /// The formula of an Excel data table (What-If Analysis). It is an array formula that fills
+    /// the results of the table, anchored at the top left result cell:
+    ///
+    /// * Two variables: the formula is in the cell above and to the left of the results, the
+    ///   values for `row_input` are in the row above and the values for `column_input` in the
+    ///   column to the left.
+    /// * Only `column_input`: the values are in the column to the left and there is one formula
+    ///   per column in the row above.
+    /// * Only `row_input`: the values are in the row above and there is one formula per row in
+    ///   the column to the left.
+    ///
+    /// Each result is computed evaluating the formula again with the values in place of those of
+    /// the input cells. The input cells are not changed and their dependents are evaluated again
+    /// afterwards.
+    pub(crate) fn fn_table(&mut self, args: &[Node], cell: CellReferenceIndex) -> CalcResult {
+        if args.len() != 2 {
+            return CalcResult::new_args_number_error(cell);
+        }
+        let row_input = match self.get_data_table_input(&args[0], cell) {
+            Ok(input) => input,
+            Err(error) => return error,
+        };
+        let column_input = match self.get_data_table_input(&args[1], cell) {
+            Ok(input) => input,
+            Err(error) => return error,
+        };
+        if row_input.is_none() && column_input.is_none() {
+            return CalcResult::new_error(
+                Error::VALUE,
+                cell,
+                "A data table needs a row or a column input cell".to_string(),
+            );
+        }
+        let (width, height) = match self
+            .workbook
+            .worksheet(cell.sheet)
+            .ok()
+            .and_then(|worksheet| worksheet.cell(cell.row, cell.column))
+        {
+            Some(Cell::ArrayFormula {
+                r,
+                kind: ArrayKind::Cse,
+                ..
+            }) => *r,
+            _ => {
+                return CalcResult::new_error(
+                    Error::VALUE,
+                    cell,
+                    "TABLE can only be used in a data table".to_string(),
+                )
+            }
+        };
+        if cell.row == 1 || cell.column == 1 {
+            return CalcResult::new_error(Error::REF, cell, "Invalid data table".to_string());
+        }
+        let at = |row: i32, column: i32| CellReferenceIndex {
+            sheet: cell.sheet,
+            row,
+            column,
+        };
+        // The values of the row above and of the column to the left, before any substitution
+        let mut row_values = Vec::new();
+        if row_input.is_some() {
+            for j in 0..width {
+                row_values.push(self.evaluate_cell(at(cell.row - 1, cell.column + j)));
+            }
+        }
+        let mut column_values = Vec::new();
+        if column_input.is_some() {
+            for i in 0..height {
+                column_values.push(self.evaluate_cell(at(cell.row + i, cell.column - 1)));
+            }
+        }
+
+        // A data table within the formula of another one changes its own inputs only
+        let outer_overrides = self.cell_overrides.clone();
+        // Cells being evaluated stay so, anything else is evaluated again with each substitution
+        let outer_cells = std::mem::take(&mut self.cells);
+        let evaluating: Vec<(u32, i32, i32)> = outer_cells
+            .iter()
+            .filter(|(_, state)| matches!(state, CellState::Evaluating))
+            .map(|(key, _)| *key)
+            .collect();
+        let reset_cells = |model: &mut Model| {
+            model.cells = evaluating
+                .iter()
+                .map(|key| (*key, CellState::Evaluating))
+                .collect();
+        };
+        let mut touched = HashSet::new();
+        let mut result = Vec::new();
+        for i in 0..height {
+            let mut result_row = Vec::new();
+            for j in 0..width {
+                let formula = match (row_input, column_input) {
+                    (Some(_), Some(_)) => at(cell.row - 1, cell.column - 1),
+                    (None, _) => at(cell.row - 1, cell.column + j),
+                    (Some(_), None) => at(cell.row + i, cell.column - 1),
+                };
+                if let Some(input) = row_input {
+                    self.cell_overrides
+                        .insert(input, row_values[j as usize].clone());
+                }
+                if let Some(input) = column_input {
+                    self.cell_overrides
+                        .insert(input, column_values[i as usize].clone());
+                }
+                reset_cells(self);
+                let value = match self.evaluate_cell(formula) {
+                    CalcResult::EmptyCell => ArrayNode::Number(0.0),
+                    value => calc_result_to_array_node(value),
+                };
+                touched.extend(
+                    self.cells
+                        .iter()
+                        .filter(|(_, state)| matches!(state, CellState::Evaluated))
+                        .map(|(key, _)| *key),
+                );
+                result_row.push(value);
+            }
+            result.push(result_row);
+        }
+
+        self.cell_overrides = outer_overrides;
+        reset_cells(self);
+        for (sheet, row, column) in touched {
+            self.evaluate_cell(CellReferenceIndex { sheet, row, column });
+        }
+        let restored_cells = std::mem::replace(&mut self.cells, outer_cells);
+        for (key, state) in restored_cells {
+            if matches!(state, CellState::Evaluated) {
+                self.cells.entry(key).or_insert(state);
+            }
+        }
+        CalcResult::Array(result)
+    }
+
+    /// The input cell of a data table, `None` if the argument is omitted
+    fn get_data_table_input(
+        &mut self,
+        node: &Node,
+        cell: CellReferenceIndex,
+    ) -> Result<Option<CellReferenceIndex>, CalcResult> {
+        if matches!(node, Node::EmptyArgKind) {
+            return Ok(None);
+        }
+        let range = self.get_reference(node, cell)?;
+        if range.left != range.right {
+            return Err(CalcResult::new_error(
+                Error::VALUE,
+                cell,
+                "The input of a data table must be a single cell".to_string(),
+            ));
+        }
+        Ok(Some(range.left))
+    }
+}
@@ -13,6 +13,7 @@ use super::util::{compare_values, from_wildcard_to_regex, result_matches_regex,
 
 mod address_areas;
 mod choosecols_chooserows;
+mod data_table;
 mod drop_take;
 mod expand;
 mod hstack_vstack;
@@ -8,6 +8,7 @@ use crate::{
 };
 
 pub(crate) mod binary_search;
+pub(crate) mod catalogue;
 mod database;
 mod date_and_time;
 mod engineering;
@@ -183,6 +184,7 @@ pub enum Function {
     Xlookup,
     Xmatch,
     Trimrange,
+    Table,
     Take,
     Drop,
     Tocol,
@@ -710,6 +712,7 @@ impl_function_lookup! {
     xlookup   => Xlookup,
     xmatch    => Xmatch,
     trimrange => Trimrange,
+    table     => Table,
     take      => Take,
     drop      => Drop,
     tocol     => Tocol,
@@ -1215,6 +1218,7 @@ impl Function {
             Function::Xlookup => functions.xlookup.clone(),
             Function::Xmatch => functions.xmatch.clone(),
             Function::Trimrange => functions.trimrange.clone(),
+            Function::Table => functions.table.clone(),
             Function::Take => functions.take.clone(),
             Function::Drop => functions.drop.clone(),
             Function::Tocol => functions.tocol.clone(),
@@ -1543,7 +1547,7 @@ impl Function {
         }
     }
 
-    pub fn into_iter() -> IntoIter<Function, 472> {
+    pub fn into_iter() -> IntoIter<Function, 473> {
         [
             Function::And,
             Function::False,
@@ -1659,6 +1663,7 @@ impl Function {
             Function::Xlookup,
             Function::Xmatch,
             Function::Trimrange,
+            Function::Table,
             Function::Take,
             Function::Drop,
             Function::Tocol,
@@ -2290,6 +2295,7 @@ impl<'a> Model<'a> {
             Function::Xlookup => self.fn_xlookup(args, cell),
             Function::Xmatch => self.fn_xmatch(args, cell),
             Function::Trimrange => self.fn_trimrange(args, cell),
+            Function::Table => self.fn_table(args, cell),
             Function::Take => self.fn_take(args, cell),
             Function::Drop => self.fn_drop(args, cell),
             Function::Tocol => self.fn_tocol(args, cell),
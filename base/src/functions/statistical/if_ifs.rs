@@ -70,7 +70,11 @@ impl<'a> Model<'a> {
             }
         }
         for criterion in criteria.iter() {
-            fn_criteria.push(build_criteria(criterion, self.locale));
+            fn_criteria.push(build_criteria(
+                criterion,
+                self.locale,
+                self.workbook.settings.date1904,
+            ));
         }
 
         let mut total = 0.0;
@@ -221,7 +225,11 @@ impl<'a> Model<'a> {
             }
         }
         for criterion in criteria.iter() {
-            fn_criteria.push(build_criteria(criterion, self.locale));
+            fn_criteria.push(build_criteria(
+                criterion,
+                self.locale,
+                self.workbook.settings.date1904,
+            ));
         }
 
         let left_row = sum_range.left.row;
@@ -8,7 +8,7 @@ use crate::{
         token::Error,
         types::CellReferenceIndex,
     },
-    formatter::format::{format_number, parse_formatted_number},
+    formatter::format::{format_number_in_date_system, parse_formatted_number},
     functions::{
         text::util::{substitute, text_after, text_before, Case},
         util::from_wildcard_to_regex,
@@ -289,13 +289,15 @@ impl<'a> Model<'a> {
                     Err(e) => return e,
                 };
                 let locale = self.locale;
+                let date1904 = self.workbook.settings.date1904;
                 let mut output = Vec::with_capacity(arr.len());
                 for row in arr {
                     let mut data_row = Vec::with_capacity(row.len());
                     for node in row {
                         let out = match node {
                             ArrayNode::Number(f) => {
-                                let d = format_number(f, &format_code, locale);
+                                let d =
+                                    format_number_in_date_system(f, &format_code, locale, date1904);
                                 if d.error.is_some() {
                                     ArrayNode::Error(Error::VALUE)
                                 } else {
@@ -303,7 +305,12 @@ impl<'a> Model<'a> {
                                 }
                             }
                             ArrayNode::Empty => {
-                                let d = format_number(0.0, &format_code, locale);
+                                let d = format_number_in_date_system(
+                                    0.0,
+                                    &format_code,
+                                    locale,
+                                    date1904,
+                                );
                                 if d.error.is_some() {
                                     ArrayNode::Error(Error::VALUE)
                                 } else {
@@ -340,7 +347,12 @@ impl<'a> Model<'a> {
                     Ok(s) => s,
                     Err(s) => return s,
                 };
-                let d = format_number(value, &format_code, self.locale);
+                let d = format_number_in_date_system(
+                    value,
+                    &format_code,
+                    self.locale,
+                    self.workbook.settings.date1904,
+                );
                 if let Some(_e) = d.error {
                     return CalcResult::Error {
                         error: Error::VALUE,
@@ -1170,7 +1182,12 @@ impl<'a> Model<'a> {
         match self.evaluate_node_in_context(&args[0], cell) {
             CalcResult::String(text) => {
                 let currencies = vec!["$", "€"];
-                if let Ok((value, _)) = parse_formatted_number(&text, &currencies, self.locale) {
+                if let Ok((value, _)) = parse_formatted_number(
+                    &text,
+                    &currencies,
+                    self.locale,
+                    self.workbook.settings.date1904,
+                ) {
                     return CalcResult::Number(value);
                 };
                 CalcResult::Error {
@@ -13,12 +13,12 @@ use crate::{
 /// serial number as `f64`. Pure numeric strings are rejected here because the
 /// numeric branch in `build_criteria` handles them already, and date parsing
 /// must not shadow the simpler number path.
-fn parse_date_criterion(s: &str, locale: &Locale) -> Option<f64> {
+fn parse_date_criterion(s: &str, locale: &Locale, date1904: bool) -> Option<f64> {
     let trimmed = s.trim();
     if trimmed.is_empty() || trimmed.parse::<f64>().is_ok() {
         return None;
     }
-    parse_date(trimmed, locale)
+    parse_date(trimmed, locale, date1904)
         .ok()
         .map(|(serial, _)| serial as f64)
 }
@@ -368,9 +368,11 @@ fn result_is_equal_to_empty(calc_result: &CalcResult) -> bool {
 /// it as a date and, on success, fall back to numeric comparison against the
 /// resulting Excel serial. This is what lets COUNTIF/SUMIF/AVERAGEIF (and the
 /// *IFS variants) match date-serial cells with date-string criteria.
+/// `date1904` selects the date system of that serial.
 pub(crate) fn build_criteria<'a>(
     value: &'a CalcResult,
     locale: &'a Locale,
+    date1904: bool,
 ) -> Box<dyn Fn(&CalcResult) -> bool + 'a> {
     match value {
         CalcResult::String(s) => {
@@ -380,7 +382,7 @@ pub(crate) fn build_criteria<'a>(
                     Box::new(move |x| result_is_less_or_equal_than_number(x, f))
                 } else if v.is_empty() {
                     Box::new(move |_x| false)
-                } else if let Some(f) = parse_date_criterion(v, locale) {
+                } else if let Some(f) = parse_date_criterion(v, locale, date1904) {
                     Box::new(move |x| result_is_less_or_equal_than_number(x, f))
                 } else {
                     Box::new(move |x| result_is_less_or_equal_than_string(x, &v.to_lowercase()))
@@ -391,7 +393,7 @@ pub(crate) fn build_criteria<'a>(
                     Box::new(move |x| result_is_greater_or_equal_than_number(x, f))
                 } else if v.is_empty() {
                     Box::new(move |_x| false)
-                } else if let Some(f) = parse_date_criterion(v, locale) {
+                } else if let Some(f) = parse_date_criterion(v, locale, date1904) {
                     Box::new(move |x| result_is_greater_or_equal_than_number(x, f))
                 } else {
                     Box::new(move |x| result_is_greater_or_equal_than_string(x, &v.to_lowercase()))
@@ -411,7 +413,7 @@ pub(crate) fn build_criteria<'a>(
                     }
                 } else if v.is_empty() {
                     Box::new(result_is_not_equal_to_empty)
-                } else if let Some(f) = parse_date_criterion(v, locale) {
+                } else if let Some(f) = parse_date_criterion(v, locale, date1904) {
                     Box::new(move |x| result_is_not_equal_to_number(x, f))
                 } else {
                     Box::new(move |x| result_is_not_equal_to_string(x, &v.to_lowercase()))
@@ -422,7 +424,7 @@ pub(crate) fn build_criteria<'a>(
                     Box::new(move |x| result_is_less_than_number(x, f))
                 } else if v.is_empty() {
                     Box::new(move |_x| false)
-                } else if let Some(f) = parse_date_criterion(v, locale) {
+                } else if let Some(f) = parse_date_criterion(v, locale, date1904) {
                     Box::new(move |x| result_is_less_than_number(x, f))
                 } else {
                     Box::new(move |x| result_is_less_than_string(x, &v.to_lowercase()))
@@ -433,7 +435,7 @@ pub(crate) fn build_criteria<'a>(
                     Box::new(move |x| result_is_greater_than_number(x, f))
                 } else if v.is_empty() {
                     Box::new(move |_x| false)
-                } else if let Some(f) = parse_date_criterion(v, locale) {
+                } else if let Some(f) = parse_date_criterion(v, locale, date1904) {
                     Box::new(move |x| result_is_greater_than_number(x, f))
                 } else {
                     Box::new(move |x| result_is_greater_than_string(x, &v.to_lowercase()))
@@ -456,7 +458,7 @@ pub(crate) fn build_criteria<'a>(
                     } else {
                         Box::new(move |_| false)
                     }
-                } else if let Some(f) = parse_date_criterion(v, locale) {
+                } else if let Some(f) = parse_date_criterion(v, locale, date1904) {
                     Box::new(move |x| result_is_equal_to_number(x, f))
                 } else {
                     Box::new(move |x| result_is_equal_to_string(x, &v.to_lowercase()))
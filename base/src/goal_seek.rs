@@ -0,0 +1,293 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{cell::CellValue, expressions::types::CellReferenceIndex, model::Model, types::Cell};
+
+/// Options of [Model::goal_seek]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GoalSeekOptions {
+    /// Maximum number of times the model is evaluated
+    pub max_iterations: u32,
+    /// The search stops when the target cell is this close to the target value
+    pub tolerance: f64,
+}
+
+impl Default for GoalSeekOptions {
+    fn default() -> Self {
+        GoalSeekOptions {
+            max_iterations: 100,
+            tolerance: 0.001,
+        }
+    }
+}
+
+/// The outcome of [Model::goal_seek]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct GoalSeekResult {
+    /// The target cell reached the target value within the tolerance
+    pub converged: bool,
+    /// Number of times the model was evaluated
+    pub iterations: u32,
+    /// The value found for the changing cell, the best one tried if the search did not converge
+    pub value: f64,
+    /// Distance from the target cell to the target value for `value`
+    pub error: f64,
+}
+
+/// The state of a search: the best point so far and the number of evaluations
+struct GoalSeek {
+    target: CellReferenceIndex,
+    target_value: f64,
+    changing: CellReferenceIndex,
+    options: GoalSeekOptions,
+    iterations: u32,
+    best: Option<(f64, f64)>,
+}
+
+impl GoalSeek {
+    fn is_done(&self) -> bool {
+        self.iterations >= self.options.max_iterations
+            || self
+                .best
+                .is_some_and(|(_, error)| error.abs() <= self.options.tolerance)
+    }
+}
+
+impl Model<'_> {
+    /// Finds the value of the `changing` cell that makes the formula in the `target` cell
+    /// evaluate to `target_value`.
+    ///
+    /// The search starts with the current value of the changing cell and uses the secant method.
+    /// If that does not converge it looks for an interval where the target crosses the value and
+    /// narrows it with Brent's method.
+    ///
+    /// If the search converges the changing cell keeps the value found. Otherwise the original
+    /// value is restored. In both cases the model is evaluated.
+    ///
+    /// Fails if the target cell does not contain a formula or the changing cell contains
+    /// something other than a number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ironcalc_base::Model;
+    /// # use ironcalc_base::expressions::types::CellReferenceIndex;
+    /// # use ironcalc_base::goal_seek::GoalSeekOptions;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut model = Model::new_empty("model", "en", "UTC", "en")?;
+    /// model.set_user_input(0, 1, 1, "1".to_string())?;
+    /// model.set_user_input(0, 2, 1, "=A1*A1-2".to_string())?;
+    /// model.evaluate();
+    /// let target = CellReferenceIndex { sheet: 0, row: 2, column: 1 };
+    /// let changing = CellReferenceIndex { sheet: 0, row: 1, column: 1 };
+    /// let result = model.goal_seek(&target, 0.0, &changing, &GoalSeekOptions::default())?;
+    /// assert!(result.converged);
+    /// assert!((result.value - 2f64.sqrt()).abs() < 0.001);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn goal_seek(
+        &mut self,
+        target: &CellReferenceIndex,
+        target_value: f64,
+        changing: &CellReferenceIndex,
+        options: &GoalSeekOptions,
+    ) -> Result<GoalSeekResult, String> {
+        if self
+            .workbook
+            .worksheet(target.sheet)?
+            .cell(target.row, target.column)
+            .and_then(|cell| cell.get_formula())
+            .is_none()
+        {
+            return Err("The target cell must contain a formula".to_string());
+        }
+        let original = self
+            .workbook
+            .worksheet(changing.sheet)?
+            .cell(changing.row, changing.column)
+            .cloned();
+        let start = match &original {
+            None | Some(Cell::EmptyCell { .. }) => 0.0,
+            Some(Cell::NumberCell { v, .. }) => *v,
+            Some(_) => return Err("The changing cell must contain a number".to_string()),
+        };
+        if !target_value.is_finite() {
+            return Err("Invalid target value".to_string());
+        }
+
+        let mut search = GoalSeek {
+            target: *target,
+            target_value,
+            changing: *changing,
+            options: *options,
+            iterations: 0,
+            best: None,
+        };
+        self.goal_seek_secant(&mut search, start)?;
+        if !search.is_done() {
+            self.goal_seek_bracket(&mut search, start)?;
+        }
+
+        let (value, error) = search.best.unwrap_or((start, f64::NAN));
+        let converged = error.abs() <= options.tolerance;
+        if converged {
+            self.update_cell_with_number(changing.sheet, changing.row, changing.column, value)?;
+        } else {
+            let worksheet = self.workbook.worksheet_mut(changing.sheet)?;
+            match original {
+                Some(cell) => worksheet.update_cell(changing.row, changing.column, cell)?,
+                None => worksheet.remove_cell(changing.row, changing.column)?,
+            };
+        }
+        self.evaluate();
+        Ok(GoalSeekResult {
+            converged,
+            iterations: search.iterations,
+            value,
+            error: error.abs(),
+        })
+    }
+
+    /// Sets the changing cell to `x` and returns how far the target is from the target value.
+    /// Errors in the target cell are NaN.
+    fn goal_seek_eval(&mut self, search: &mut GoalSeek, x: f64) -> Result<f64, String> {
+        let changing = search.changing;
+        self.update_cell_with_number(changing.sheet, changing.row, changing.column, x)?;
+        self.evaluate();
+        search.iterations += 1;
+        let target = search.target;
+        let error = match self.get_cell_value_by_index(target.sheet, target.row, target.column)? {
+            CellValue::Number(value) => value - search.target_value,
+            _ => f64::NAN,
+        };
+        if error.is_finite() && search.best.is_none_or(|(_, best)| error.abs() < best.abs()) {
+            search.best = Some((x, error));
+        }
+        Ok(error)
+    }
+
+    fn goal_seek_secant(&mut self, search: &mut GoalSeek, start: f64) -> Result<(), String> {
+        let mut x0 = start;
+        let mut f0 = self.goal_seek_eval(search, x0)?;
+        if search.is_done() || !f0.is_finite() {
+            return Ok(());
+        }
+        let mut x1 = x0 + first_step(x0);
+        let mut f1 = self.goal_seek_eval(search, x1)?;
+        while !search.is_done() && f1.is_finite() {
+            if f0.signum() != f1.signum() {
+                return self.goal_seek_brent(search, (x0, f0), (x1, f1));
+            }
+            if f1 == f0 {
+                return Ok(());
+            }
+            let x2 = x1 - f1 * (x1 - x0) / (f1 - f0);
+            if !x2.is_finite() {
+                return Ok(());
+            }
+            (x0, f0) = (x1, f1);
+            x1 = x2;
+            f1 = self.goal_seek_eval(search, x1)?;
+        }
+        Ok(())
+    }
+
+    /// Looks for a value on each side of `start`, further away each time, with the opposite sign
+    fn goal_seek_bracket(&mut self, search: &mut GoalSeek, start: f64) -> Result<(), String> {
+        let f_start = self.goal_seek_eval(search, start)?;
+        if !f_start.is_finite() {
+            return Ok(());
+        }
+        let mut step = first_step(start);
+        while !search.is_done() && step.is_finite() {
+            for x in [start + step, start - step] {
+                let f = self.goal_seek_eval(search, x)?;
+                if search.is_done() {
+                    return Ok(());
+                }
+                if f.is_finite() && f.signum() != f_start.signum() {
+                    return self.goal_seek_brent(search, (start, f_start), (x, f));
+                }
+            }
+            step *= 2.0;
+        }
+        Ok(())
+    }
+
+    /// Brent's method in an interval where the target crosses the target value
+    fn goal_seek_brent(
+        &mut self,
+        search: &mut GoalSeek,
+        (mut a, mut fa): (f64, f64),
+        (mut b, mut fb): (f64, f64),
+    ) -> Result<(), String> {
+        let (mut c, mut fc) = (b, fb);
+        let mut d = b - a;
+        let mut e = d;
+        while !search.is_done() {
+            if fb.signum() == fc.signum() {
+                (c, fc) = (a, fa);
+                d = b - a;
+                e = d;
+            }
+            if fc.abs() < fb.abs() {
+                (a, fa) = (b, fb);
+                (b, fb) = (c, fc);
+                (c, fc) = (a, fa);
+            }
+            let tol = 2.0 * f64::EPSILON * b.abs();
+            let xm = 0.5 * (c - b);
+            if xm.abs() <= tol {
+                // The interval can not be made smaller
+                return Ok(());
+            }
+            if e.abs() >= tol && fa.abs() > fb.abs() {
+                // Inverse quadratic interpolation or secant
+                let s = fb / fa;
+                let (mut p, mut q) = if a == c {
+                    (2.0 * xm * s, 1.0 - s)
+                } else {
+                    let q = fa / fc;
+                    let r = fb / fc;
+                    (
+                        s * (2.0 * xm * q * (q - r) - (b - a) * (r - 1.0)),
+                        (q - 1.0) * (r - 1.0) * (s - 1.0),
+                    )
+                };
+                if p > 0.0 {
+                    q = -q;
+                }
+                p = p.abs();
+                let min1 = 3.0 * xm * q - (tol * q).abs();
+                let min2 = (e * q).abs();
+                if 2.0 * p < min1.min(min2) {
+                    e = d;
+                    d = p / q;
+                } else {
+                    d = xm;
+                    e = d;
+                }
+            } else {
+                // Bisection
+                d = xm;
+                e = d;
+            }
+            (a, fa) = (b, fb);
+            b += if d.abs() > tol { d } else { tol.copysign(xm) };
+            fb = self.goal_seek_eval(search, b)?;
+            if !fb.is_finite() {
+                return Ok(());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The first step away from `x`, relative to its size
+fn first_step(x: f64) -> f64 {
+    if x == 0.0 {
+        0.01
+    } else {
+        x.abs() * 0.01
+    }
+}
@@ -498,6 +498,22 @@ pub struct Functions {
     pub multinomial: String,
     pub seriessum: String,
     pub sumproduct: String,
+    pub table: String,
+}
+
+#[derive(Encode, Decode)]
+pub struct References {
+    pub row: String,
+    pub column: String,
+}
+
+/// Translations for the function catalogue, English text is used for anything missing
+#[derive(Encode, Decode)]
+pub struct Catalogue {
+    /// Parameter names keyed by the English name without the trailing number (`number1` -> `number`)
+    pub parameters: HashMap<String, String>,
+    /// Function descriptions keyed by the English name of the function
+    pub descriptions: HashMap<String, String>,
 }
 
 #[derive(Encode, Decode)]
@@ -507,6 +523,8 @@ pub struct Language {
     pub booleans: Booleans,
     pub errors: Errors,
     pub functions: Functions,
+    pub references: References,
+    pub catalogue: Catalogue,
 }
 
 pub fn get_default_language() -> &'static Language {
@@ -32,12 +32,23 @@ pub mod calc_result;
 pub mod cell;
 pub mod cf_types;
 pub mod colors;
+pub mod custom_functions;
+pub mod error_checking;
+pub mod evaluate_formula;
 pub mod expressions;
+pub mod external_links;
+pub mod fonts;
 pub mod formatter;
+pub mod function_catalogue;
+pub mod goal_seek;
 pub mod language;
 pub mod locale;
 pub mod new_empty;
 pub mod number_format;
+pub mod outline;
+pub mod precedents;
+pub mod scenarios;
+pub mod solver;
 pub mod themes;
 pub mod types;
 pub mod worksheet;
@@ -71,7 +82,13 @@ pub use locale::get_supported_locales;
 pub use model::get_milliseconds_since_epoch;
 pub use model::FmtSettings;
 pub use model::Model;
+pub use user_model::collaboration;
 pub use user_model::BorderArea;
 pub use user_model::ClipboardData;
 pub use user_model::UserModel;
+pub use user_model::{FindHit, FindLookIn, FindOptions, FindOrder, FindScope};
+pub use user_model::{
+    PasteMode, PasteOperation, PasteSpecialOptions, PortableCell, PortableClipboard,
+    PortableDefinedName, PortableReferences, PortableValue,
+};
 pub use utils::get_all_timezones;
@@ -1,9 +1,10 @@
 #![deny(missing_docs)]
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::vec::Vec;
 
-use crate::expressions::parser::static_analysis::run_static_analysis_on_node;
+use crate::expressions::parser::static_analysis::{run_static_analysis_on_node, walk_nodes_mut};
 use crate::{
     calc_result::{CalcResult, Range},
     cell::CellValue,
@@ -13,7 +14,10 @@ use crate::{
         parser::{
             move_formula::{move_formula, MoveContext},
             static_analysis::StaticResult,
-            stringify::{rename_defined_name_in_node, to_localized_string, to_rc_format},
+            stringify::{
+                rename_defined_name_in_node, to_localized_r1c1_string, to_localized_string,
+                to_rc_format,
+            },
             ArrayNode, NamedVariable, Node, Parser,
         },
         token::{get_error_by_name, Error, OpProduct, OpSum, OpUnary},
@@ -21,17 +25,22 @@ use crate::{
         utils::{self, is_valid_column_number, is_valid_identifier, is_valid_row},
     },
     formatter::{
-        format::{format_number, parse_formatted_number},
+        dates::DATE_1904_OFFSET,
+        format::{format_number, format_number_in_date_system, parse_formatted_number},
         lexer::is_likely_date_number_format,
     },
+    functions::Function,
     implicit_intersection::implicit_intersection,
     language::{get_default_language, get_language, Language},
-    locale::{get_locale, Locale},
+    locale::{get_default_locale, get_locale, Locale},
     types::*,
     utils as common,
 };
 
-use crate::{cf_types::CfCellResult, tz::Tz};
+use crate::{
+    cf_types::CfCellResult, custom_functions::CustomFunction, external_links::WorkbookResolver,
+    fonts::FontFallback, outline::OutlineGroup, tz::Tz,
+};
 
 #[cfg(test)]
 pub use crate::mock_time::get_milliseconds_since_epoch;
@@ -107,6 +116,10 @@ pub(crate) enum ParsedDefinedName {
     RangeReference(Range),
     /// `=LAMBDA(params..., body)`
     LambdaDefinition(Vec<NamedVariable>, Node),
+    /// A reference to another workbook (`=[Budget.xlsx]Sheet1!A1`)
+    ExternalReference(Node),
+    /// Any other formula (`=SUM(C4:D6)`), parsed where it is used to trace its references
+    Formula,
     /// `=SomethingElse`
     InvalidDefinedNameFormula,
 }
@@ -165,6 +178,7 @@ fn formula_value_to_spill_value(v: &FormulaValue) -> SpillValue {
     }
 }
 
+#[derive(Clone)]
 pub(crate) enum CellOrRange {
     // (sheet, row, column)
     Cell((u32, i32, i32)),
@@ -184,6 +198,7 @@ pub(crate) enum CellOrRange {
 /// * A list of cells with its status (evaluating, evaluated, not evaluated)
 /// * A dictionary with the shared strings and their indices.
 ///   This is an optimization for large files (~1 million rows)
+#[derive(Clone)]
 pub struct Model<'a> {
     /// A Rust internal representation of an Excel workbook
     pub workbook: Workbook,
@@ -217,9 +232,24 @@ pub struct Model<'a> {
     pub(crate) spill_cells: Vec<CellReferenceIndex>,
     /// A dictionary to keep track of which cells or ranges support a given cell.
     pub(crate) support: HashMap<CellReferenceIndex, Vec<CellOrRange>>,
+    /// The ranges computed by `INDIRECT` and `OFFSET` in the last evaluation, keyed by the cell with the formula.
+    pub(crate) dynamic_support: HashMap<CellReferenceIndex, Vec<CellOrRange>>,
     /// Evaluated CF results per cell, keyed by (sheet_index, row, column).
     /// Rebuilt from scratch on every call to evaluate_conditional_formatting().
     pub(crate) cf_cache: HashMap<(u32, i32, i32), Vec<CfCellResult>>,
+    /// Maps workbook fonts to fonts that can be rendered. Not part of the workbook.
+    pub(crate) font_fallback: FontFallback,
+    /// Functions registered by the host, keyed by their name in upper case. Not part of the workbook.
+    pub(crate) custom_functions: HashMap<String, CustomFunction>,
+    /// Loads the workbooks referenced by formulas. Not part of the workbook.
+    pub(crate) workbook_resolver: Option<Arc<dyn WorkbookResolver>>,
+    /// The workbooks loaded by the resolver in the current evaluation, keyed by their name in upper case.
+    pub(crate) linked_models: HashMap<String, Option<Model<'static>>>,
+    /// The values read from the linked workbooks in the current evaluation,
+    /// keyed by (workbook, sheet, row, column). See [Model::update_external_links].
+    pub(crate) linked_values: HashMap<(String, String, i32, i32), Option<ExternalValue>>,
+    /// Values used instead of those of the cells, like the inputs of a data table while it is computed
+    pub(crate) cell_overrides: HashMap<CellReferenceIndex, CalcResult>,
 }
 
 // FIXME: Maybe this should be the same as CellReference
@@ -469,6 +499,56 @@ impl<'a> Model<'a> {
             WrongReferenceKind { .. } => {
                 CalcResult::new_error(Error::REF, cell, "Wrong reference".to_string())
             }
+            ExternalReferenceKind {
+                workbook,
+                sheet_name,
+                absolute_row,
+                absolute_column,
+                row,
+                column,
+            } => {
+                let row = if *absolute_row { *row } else { row + cell.row };
+                let column = if *absolute_column {
+                    *column
+                } else {
+                    column + cell.column
+                };
+                self.evaluate_external_reference(workbook, sheet_name, row, column, cell)
+            }
+            ExternalRangeKind {
+                workbook,
+                sheet_name,
+                absolute_row1,
+                absolute_column1,
+                row1,
+                column1,
+                absolute_row2,
+                absolute_column2,
+                row2,
+                column2,
+            } => {
+                let r1 = if *absolute_row1 {
+                    *row1
+                } else {
+                    row1 + cell.row
+                };
+                let c1 = if *absolute_column1 {
+                    *column1
+                } else {
+                    column1 + cell.column
+                };
+                let r2 = if *absolute_row2 {
+                    *row2
+                } else {
+                    row2 + cell.row
+                };
+                let c2 = if *absolute_column2 {
+                    *column2
+                } else {
+                    column2 + cell.column
+                };
+                self.evaluate_external_range(workbook, sheet_name, (r1, c1), (r2, c2), cell)
+            }
             OpRangeKind { left, right } => self.get_range(left, right, cell),
             WrongRangeKind { .. } => {
                 CalcResult::new_error(Error::REF, cell, "Wrong range".to_string())
@@ -544,7 +624,24 @@ impl<'a> Model<'a> {
             OpPowerKind { left, right } => {
                 self.handle_arithmetic(left, right, cell, &|f1, f2| Ok(f1.powf(f2)))
             }
-            FunctionKind { kind, args } => self.evaluate_function(kind, args, cell),
+            FunctionKind { kind, args } => {
+                let result = self.evaluate_function(kind, args, cell);
+                if let (Function::Indirect | Function::Offset, CalcResult::Range { left, right }) =
+                    (kind, &result)
+                {
+                    self.dynamic_support
+                        .entry(cell)
+                        .or_default()
+                        .push(CellOrRange::Range((
+                            left.sheet,
+                            left.row,
+                            left.column,
+                            right.row,
+                            right.column,
+                        )));
+                }
+                result
+            }
             NamedFunctionKind { name, args, id } => {
                 let lambda_result = if let Some(var_id) = id {
                     // Bound by LET — look up the variable, which should be a Lambda.
@@ -575,11 +672,15 @@ impl<'a> Model<'a> {
                             CalcResult::Lambda(lambda_id)
                         }
                         _ => {
+                            // Functions registered by the host
+                            if let Some(function) = self.get_custom_function(name) {
+                                return self.call_custom_function(&function, args, cell);
+                            }
                             return CalcResult::new_error(
                                 Error::NAME,
                                 cell,
                                 format!("Invalid function: {name}"),
-                            )
+                            );
                         }
                     }
                 };
@@ -601,7 +702,11 @@ impl<'a> Model<'a> {
                             self.lambdas.insert(lambda_id, (param_names, body));
                             CalcResult::Lambda(lambda_id)
                         }
-                        ParsedDefinedName::InvalidDefinedNameFormula => CalcResult::new_error(
+                        ParsedDefinedName::ExternalReference(node) => {
+                            self.evaluate_node_in_context(&node, cell)
+                        }
+                        ParsedDefinedName::Formula
+                        | ParsedDefinedName::InvalidDefinedNameFormula => CalcResult::new_error(
                             Error::NAME,
                             cell,
                             format!("Defined name \"{name}\" is not a reference."),
@@ -1124,6 +1229,24 @@ impl<'a> Model<'a> {
         self.workbook.theme.clone()
     }
 
+    /// Sets the rules used to map the fonts in the workbook to fonts that can be rendered.
+    pub fn set_font_fallback(&mut self, font_fallback: FontFallback) {
+        self.font_fallback = font_fallback;
+    }
+
+    /// Returns the font fallback rules
+    pub fn get_font_fallback(&self) -> FontFallback {
+        self.font_fallback.clone()
+    }
+
+    /// Returns the name of the font that should be used to render `font`.
+    ///
+    /// See also:
+    /// * [FontFallback::get_rendering_font_name]
+    pub fn get_rendering_font_name(&self, font: &Font) -> String {
+        self.font_fallback.get_rendering_font_name(font)
+    }
+
     /// Makes the grid lines in the sheet visible (`true`) or hidden (`false`)
     pub fn set_show_grid_lines(&mut self, sheet: u32, show_grid_lines: bool) -> Result<(), String> {
         let worksheet = self.workbook.worksheet_mut(sheet)?;
@@ -1132,7 +1255,11 @@ impl<'a> Model<'a> {
     }
 
     // Returns the 'single' value of a cell. Not arrays or ranges.
-    fn get_cell_value(&self, cell: &Cell, cell_reference: CellReferenceIndex) -> CalcResult {
+    pub(crate) fn get_cell_value(
+        &self,
+        cell: &Cell,
+        cell_reference: CellReferenceIndex,
+    ) -> CalcResult {
         use Cell::*;
         match cell {
             EmptyCell { .. } => CalcResult::EmptyCell,
@@ -1289,6 +1416,9 @@ impl<'a> Model<'a> {
     // Evaluates a cell and returns the value in the cell
     // FIXME: CalcResult cannot be Array or Range, should we have a different type?
     pub(crate) fn evaluate_cell(&mut self, cell_reference: CellReferenceIndex) -> CalcResult {
+        if let Some(value) = self.cell_overrides.get(&cell_reference) {
+            return value.clone();
+        }
         let original_cell = match self.fetch_cell(cell_reference) {
             Some(c) => c.clone(),
             None => return CalcResult::EmptyCell,
@@ -1600,7 +1730,14 @@ impl<'a> Model<'a> {
             last_lambda_id: 0,
             spill_cells: Vec::new(),
             support: HashMap::new(),
+            dynamic_support: HashMap::new(),
             cf_cache: HashMap::new(),
+            font_fallback: FontFallback::default(),
+            custom_functions: HashMap::new(),
+            workbook_resolver: None,
+            linked_models: HashMap::new(),
+            linked_values: HashMap::new(),
+            cell_overrides: HashMap::new(),
         };
 
         model.parse_formulas();
@@ -2246,12 +2383,98 @@ impl<'a> Model<'a> {
     /// * [Model::update_cell_with_number()]
     /// * [Model::update_cell_with_bool()]
     /// * [Model::update_cell_with_text()]
+    ///
+    /// If the workbook is in R1C1 reference style formulas must be written in R1C1 style.
     pub fn set_user_input(
         &mut self,
         sheet: u32,
         row: i32,
         column: i32,
         value: String,
+    ) -> Result<(), String> {
+        let value = self.user_input_to_a1(sheet, row, column, &value);
+        self.set_a1_user_input(sheet, row, column, value)
+    }
+
+    /// Translates a formula written in the R1C1 reference style in the cell (`row`, `column`) to A1 style.
+    /// Values that are not formulas, or any value if the workbook is in A1 style, are returned unchanged.
+    pub(crate) fn user_input_to_a1(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        value: &str,
+    ) -> String {
+        if self.workbook.settings.r1c1 {
+            if let Some(formula) = value.strip_prefix('=') {
+                return format!("={}", self.r1c1_to_a1(formula, sheet, row, column));
+            }
+        }
+        value.to_string()
+    }
+
+    /// Translates a formula typed by the user in R1C1 style in the cell (`row`, `column`) to A1 style,
+    /// in the locale and language of the model. References that fall outside of the sheet become
+    /// `#REF!`. Formulas that cannot be parsed are returned unchanged.
+    pub(crate) fn r1c1_to_a1(&self, formula: &str, sheet: u32, row: i32, column: i32) -> String {
+        let context = CellReferenceRC {
+            sheet: self
+                .workbook
+                .worksheet(sheet)
+                .map(|worksheet| worksheet.get_name())
+                .unwrap_or_default(),
+            row,
+            column,
+        };
+        let mut parser = self.parser.clone();
+        parser.set_lexer_mode(LexerMode::LocalizedR1C1);
+        let mut node = parser.parse(formula, &context);
+        if matches!(node, Node::ParseErrorKind { .. }) {
+            return formula.to_string();
+        }
+        let is_valid = |r: i32, absolute_row: bool, c: i32, absolute_column: bool| {
+            is_valid_row(if absolute_row { r } else { r + row })
+                && is_valid_column_number(if absolute_column { c } else { c + column })
+        };
+        walk_nodes_mut(&mut node, &mut |node| {
+            let is_outside = match node {
+                Node::ReferenceKind {
+                    row,
+                    column,
+                    absolute_row,
+                    absolute_column,
+                    ..
+                } => !is_valid(*row, *absolute_row, *column, *absolute_column),
+                Node::RangeKind {
+                    row1,
+                    column1,
+                    absolute_row1,
+                    absolute_column1,
+                    row2,
+                    column2,
+                    absolute_row2,
+                    absolute_column2,
+                    ..
+                } => {
+                    !is_valid(*row1, *absolute_row1, *column1, *absolute_column1)
+                        || !is_valid(*row2, *absolute_row2, *column2, *absolute_column2)
+                }
+                _ => false,
+            };
+            if is_outside {
+                *node = Node::ErrorKind(Error::REF);
+            }
+        });
+        to_localized_string(&node, &context, self.locale, self.language)
+    }
+
+    /// Same as [Model::set_user_input] but formulas are always in A1 style.
+    pub(crate) fn set_a1_user_input(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        value: String,
     ) -> Result<(), String> {
         // first we make sure we can write in the cell and clear the spills.
         self.prepare_cell_for_user_input(sheet, row, column)?;
@@ -2294,17 +2517,15 @@ impl<'a> Model<'a> {
                     self.set_cell_style(sheet, row, column, &style)?;
                 }
             } else {
-                // The list of currencies is '$', '€' and the local currency
-                let mut currencies = vec!["$", "€"];
-                let currency = &self.locale.currency.symbol;
-                if !currencies.iter().any(|e| e == currency) {
-                    currencies.push(currency);
-                }
+                let currencies = self.input_currencies();
 
                 //  We try to parse as number
-                if let Ok((v, number_format)) =
-                    parse_formatted_number(&value, &currencies, self.locale)
-                {
+                if let Ok((v, number_format)) = parse_formatted_number(
+                    &value,
+                    &currencies,
+                    self.locale,
+                    self.workbook.settings.date1904,
+                ) {
                     if let Some(num_fmt) = number_format {
                         // Should not apply the format in the following cases:
                         // - we assign a date to already date-formatted cell
@@ -2344,6 +2565,32 @@ impl<'a> Model<'a> {
         Ok(())
     }
 
+    /// The currencies recognized in numbers typed by the user: '$', '€' and the local currency
+    fn input_currencies(&self) -> Vec<&str> {
+        let mut currencies = vec!["$", "€"];
+        let currency = self.locale.currency.symbol.as_str();
+        if !currencies.contains(&currency) {
+            currencies.push(currency);
+        }
+        currencies
+    }
+
+    /// Returns true if [Model::set_user_input] stores the value as that same text.
+    /// That is, it is not a formula, a number, a boolean or an error and it doesn't start with a quote.
+    pub(crate) fn is_text_input(&self, value: &str) -> bool {
+        !value.starts_with('\'')
+            && self.formula_without_prefix(value).is_none()
+            && parse_formatted_number(
+                value,
+                &self.input_currencies(),
+                self.locale,
+                self.workbook.settings.date1904,
+            )
+            .is_err()
+            && value.to_lowercase().parse::<bool>().is_err()
+            && get_error_by_name(&value.to_uppercase(), self.language).is_none()
+    }
+
     /// Sets an array formula in an area (CSE formula)
     pub fn set_user_array_formula(
         &mut self,
@@ -2353,6 +2600,20 @@ impl<'a> Model<'a> {
         width: i32,
         height: i32,
         value: &str,
+    ) -> Result<(), String> {
+        let value = self.user_input_to_a1(sheet, row, column, value);
+        self.set_a1_user_array_formula(sheet, row, column, width, height, &value)
+    }
+
+    /// Same as [Model::set_user_array_formula] but the formula is always in A1 style.
+    pub(crate) fn set_a1_user_array_formula(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        width: i32,
+        height: i32,
+        value: &str,
     ) -> Result<(), String> {
         self.prepare_cell_for_user_input(sheet, row, column)?;
         // If value starts with "'" then we force the style to be quote_prefix
@@ -2417,7 +2678,7 @@ impl<'a> Model<'a> {
         // just use set user input on every cell
         for r in row..row + height {
             for c in column..column + width {
-                self.set_user_input(sheet, r, c, value.to_string())?;
+                self.set_a1_user_input(sheet, r, c, value.to_string())?;
             }
         }
 
@@ -2557,6 +2818,60 @@ impl<'a> Model<'a> {
         Ok(())
     }
 
+    /// Returns the index of `value` in the shared strings, adding it if needed
+    pub(crate) fn get_or_add_shared_string(&mut self, value: &str) -> i32 {
+        if let Some(index) = self.shared_strings.get(value) {
+            return *index as i32;
+        }
+        let index = self.workbook.shared_strings.len();
+        self.workbook.shared_strings.push(value.to_string());
+        self.shared_strings.insert(value.to_string(), index);
+        index as i32
+    }
+
+    /// Parses a formula written in English, in the internal R1C1 style or in A1 style, as if it were in `context`
+    pub(crate) fn parse_english_formula(
+        &mut self,
+        formula: &str,
+        context: &CellReferenceRC,
+        mode: LexerMode,
+    ) -> Node {
+        let locale = self.locale;
+        let language = self.language;
+        self.parser.set_locale(get_default_locale());
+        self.parser.set_language(get_default_language());
+        self.parser.set_lexer_mode(mode);
+        let node = self.parser.parse(formula, context);
+        self.parser.set_lexer_mode(LexerMode::A1);
+        self.parser.set_locale(locale);
+        self.parser.set_language(language);
+        node
+    }
+
+    /// Returns the index of the formula (in the internal R1C1 format) in the shared formulas of the sheet,
+    /// adding it if needed
+    pub(crate) fn get_or_add_shared_formula(
+        &mut self,
+        sheet: u32,
+        formula: &str,
+    ) -> Result<i32, String> {
+        let worksheet = self.workbook.worksheet(sheet)?;
+        if let Some(index) = worksheet.shared_formulas.iter().position(|x| x == formula) {
+            return Ok(index as i32);
+        }
+        let cell_reference = CellReferenceRC {
+            sheet: worksheet.get_name(),
+            row: 1,
+            column: 1,
+        };
+        let parsed_formula = self.parse_english_formula(formula, &cell_reference, LexerMode::R1C1);
+        let static_result = run_static_analysis_on_node(&parsed_formula);
+        let shared_formulas = &mut self.workbook.worksheet_mut(sheet)?.shared_formulas;
+        shared_formulas.push(formula.to_string());
+        self.parsed_formulas[sheet as usize].push((parsed_formula, static_result));
+        Ok(shared_formulas.len() as i32 - 1)
+    }
+
     fn set_cell_with_boolean(
         &mut self,
         sheet: u32,
@@ -2584,7 +2899,7 @@ impl<'a> Model<'a> {
     }
 
     // Helper function that returns a defined name given the name and scope
-    fn get_parsed_defined_name(
+    pub(crate) fn get_parsed_defined_name(
         &self,
         name: &str,
         scope: Option<u32>,
@@ -2684,9 +2999,10 @@ impl<'a> Model<'a> {
         match self.workbook.worksheet(sheet_index)?.cell(row, column) {
             Some(cell) => {
                 let format = self.get_style_for_cell(sheet_index, row, column)?.num_fmt;
+                let date1904 = self.workbook.settings.date1904;
                 let formatted_value =
                     cell.formatted_value(&self.workbook.shared_strings, self.language, |value| {
-                        format_number(value, &format, self.locale).text
+                        format_number_in_date_system(value, &format, self.locale, date1904).text
                     });
                 Ok(formatted_value)
             }
@@ -2708,11 +3024,42 @@ impl<'a> Model<'a> {
     /// Returns an error if there is no worksheet
     /// If the cell has quote prefix style it adds a ' at the beginning of the value
     /// If the cell is date formatted it tries to format it as date
+    /// If the workbook is in R1C1 reference style formulas are returned in R1C1 style
     pub fn get_localized_cell_content(
         &self,
         sheet: u32,
         row: i32,
         column: i32,
+    ) -> Result<String, String> {
+        self.localized_cell_content(sheet, row, column, self.workbook.settings.r1c1)
+    }
+
+    /// Same as [Model::get_localized_cell_content] but formulas are always in A1 style
+    pub(crate) fn get_localized_a1_cell_content(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<String, String> {
+        self.localized_cell_content(sheet, row, column, false)
+    }
+
+    /// Same as [Model::get_localized_cell_content] but formulas are always in R1C1 style
+    pub(crate) fn get_localized_r1c1_cell_content(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<String, String> {
+        self.localized_cell_content(sheet, row, column, true)
+    }
+
+    fn localized_cell_content(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        r1c1: bool,
     ) -> Result<String, String> {
         let worksheet = self.workbook.worksheet(sheet)?;
         let cell = match worksheet.cell(row, column) {
@@ -2722,6 +3069,12 @@ impl<'a> Model<'a> {
         match cell.get_formula() {
             Some(formula_index) => {
                 let formula = &self.parsed_formulas[sheet as usize][formula_index as usize].0;
+                if r1c1 {
+                    return Ok(format!(
+                        "={}",
+                        to_localized_r1c1_string(formula, self.locale, self.language)
+                    ));
+                }
                 let cell_ref = CellReferenceRC {
                     sheet: worksheet.get_name(),
                     row,
@@ -2749,7 +3102,12 @@ impl<'a> Model<'a> {
                     if is_likely_date_number_format(&style.num_fmt) {
                         let value = cell.value(&self.workbook.shared_strings, self.language);
                         if let CellValue::Number(n) = value {
-                            let formatted = format_number(n, &style.num_fmt, self.locale);
+                            let formatted = format_number_in_date_system(
+                                n,
+                                &style.num_fmt,
+                                self.locale,
+                                self.workbook.settings.date1904,
+                            );
                             if formatted.error.is_none() {
                                 return Ok(formatted.text);
                             }
@@ -2891,6 +3249,9 @@ impl<'a> Model<'a> {
     /// Phase 2 evaluates every remaining cell in natural order.  Because all spill areas have
     /// already been written, regular cells always read the correct spill values.
     pub fn evaluate(&mut self) {
+        // Linked workbooks are loaded again so their values are up to date
+        self.linked_models.clear();
+        self.linked_values.clear();
         self.collect_spill_cells();
 
         let n = self.spill_cells.len();
@@ -2903,6 +3264,7 @@ impl<'a> Model<'a> {
             retry = false;
             self.cells.clear();
             self.support.clear();
+            self.dynamic_support.clear();
             self.clear_variable_stack();
             self.clear_lambdas();
 
@@ -3365,6 +3727,84 @@ impl<'a> Model<'a> {
         self.workbook.worksheet(sheet)?.is_row_hidden(row)
     }
 
+    /// Returns the outline level of a row
+    #[inline]
+    pub fn get_row_outline_level(&self, sheet: u32, row: i32) -> Result<i32, String> {
+        self.workbook.worksheet(sheet)?.get_row_outline_level(row)
+    }
+
+    /// Sets the outline level of a row
+    #[inline]
+    pub fn set_row_outline_level(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        outline_level: i32,
+    ) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_row_outline_level(row, outline_level)
+    }
+
+    /// Sets whether a row is the summary row of a collapsed group
+    #[inline]
+    pub fn set_row_collapsed(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        collapsed: bool,
+    ) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_row_collapsed(row, collapsed)
+    }
+
+    /// Returns the outline level of a column
+    #[inline]
+    pub fn get_column_outline_level(&self, sheet: u32, column: i32) -> Result<i32, String> {
+        self.workbook
+            .worksheet(sheet)?
+            .get_column_outline_level(column)
+    }
+
+    /// Sets the outline level of a column
+    #[inline]
+    pub fn set_column_outline_level(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        outline_level: i32,
+    ) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_column_outline_level(column, outline_level)
+    }
+
+    /// Sets whether a column is a summary column of a collapsed group
+    #[inline]
+    pub fn set_column_collapsed(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        collapsed: bool,
+    ) -> Result<(), String> {
+        self.workbook
+            .worksheet_mut(sheet)?
+            .set_column_collapsed(column, collapsed)
+    }
+
+    /// Returns the row outline groups of the sheet
+    #[inline]
+    pub fn get_row_outline(&self, sheet: u32) -> Result<Vec<OutlineGroup>, String> {
+        Ok(self.workbook.worksheet(sheet)?.get_row_outline_groups())
+    }
+
+    /// Returns the column outline groups of the sheet
+    #[inline]
+    pub fn get_column_outline(&self, sheet: u32) -> Result<Vec<OutlineGroup>, String> {
+        Ok(self.workbook.worksheet(sheet)?.get_column_outline_groups())
+    }
+
     /// Returns the height of a row
     #[inline]
     pub fn get_row_height(&self, sheet: u32, row: i32) -> Result<f64, String> {
@@ -3392,6 +3832,7 @@ impl<'a> Model<'a> {
             name: name.to_string(),
             formula: formula.to_string(),
             sheet_id,
+            hidden: false,
         });
         self.reset_parsed_structures();
 
@@ -3424,7 +3865,8 @@ impl<'a> Model<'a> {
             }
         }
 
-        // Make sure the formula is valid — accept cell/range references OR a LAMBDA definition.
+        // Make sure the formula is valid — accept cell/range references (also in other workbooks)
+        // OR a LAMBDA definition.
         let is_reference =
             common::ParsedReference::parse_reference_formula(None, formula, self.locale, |name| {
                 self.get_sheet_index_by_name(name)
@@ -3432,7 +3874,7 @@ impl<'a> Model<'a> {
             .is_ok();
 
         if !is_reference {
-            // Try the full parser to see if it is a LAMBDA definition.
+            // Try the full parser to see if it is a LAMBDA definition or an external reference.
             // Defined-name formulas may carry a leading '='; strip it before parsing.
             use crate::expressions::types::CellReferenceRC;
             let formula_body = formula.strip_prefix('=').unwrap_or(formula);
@@ -3447,7 +3889,12 @@ impl<'a> Model<'a> {
                 column: 1,
             };
             let node = self.parser.parse(formula_body, &dummy_ref);
-            if !matches!(node, Node::LambdaDefKind { .. }) {
+            if !matches!(
+                node,
+                Node::LambdaDefKind { .. }
+                    | Node::ExternalReferenceKind { .. }
+                    | Node::ExternalRangeKind { .. }
+            ) {
                 return Err("Formula: Invalid defined name formula".to_string());
             }
         }
@@ -3656,6 +4103,74 @@ impl<'a> Model<'a> {
         Ok(())
     }
 
+    /// Sets the date system of the workbook (1904 if `date1904` is true, 1900 otherwise).
+    /// Existing serial numbers are not modified so dates already in the workbook will move
+    /// four years. Use [`Model::convert_date_system`] to keep them.
+    pub fn set_date1904(&mut self, date1904: bool) {
+        self.workbook.settings.date1904 = date1904;
+        self.evaluate();
+    }
+
+    /// Changes the date system of the workbook re-basing the numbers of all date formatted cells,
+    /// so they display the same dates in the new date system.
+    /// Pure times and dates that cannot be represented in the new date system are left untouched.
+    /// Formulas are not modified.
+    pub fn convert_date_system(&mut self, date1904: bool) -> Result<(), String> {
+        for (cell, _, new_value) in self.get_date_cells_to_rebase(date1904)? {
+            self.update_cell_with_number(cell.sheet, cell.row, cell.column, new_value)?;
+        }
+        self.set_date1904(date1904);
+        Ok(())
+    }
+
+    /// Returns the number cells that need to be re-based if the workbook switches to the
+    /// date system `date1904` together with their old and new values.
+    pub(crate) fn get_date_cells_to_rebase(
+        &self,
+        date1904: bool,
+    ) -> Result<Vec<(CellReferenceIndex, f64, f64)>, String> {
+        let mut cells = Vec::new();
+        if date1904 == self.workbook.settings.date1904 {
+            return Ok(cells);
+        }
+        let offset = DATE_1904_OFFSET as f64;
+        let mut is_date_style = HashMap::new();
+        for (sheet, worksheet) in self.workbook.worksheets.iter().enumerate() {
+            for (row, data_row) in &worksheet.sheet_data {
+                for (column, cell) in data_row {
+                    let (value, style) = match cell {
+                        Cell::NumberCell { v, s } => (*v, *s),
+                        _ => continue,
+                    };
+                    let is_date = match is_date_style.get(&style) {
+                        Some(is_date) => *is_date,
+                        None => {
+                            let num_fmt = &self.workbook.styles.get_style(style)?.num_fmt;
+                            let is_date = is_likely_date_number_format(num_fmt);
+                            is_date_style.insert(style, is_date);
+                            is_date
+                        }
+                    };
+                    if !is_date {
+                        continue;
+                    }
+                    // Numbers without a date part are times in both date systems
+                    let cell = CellReferenceIndex {
+                        sheet: sheet as u32,
+                        row: *row,
+                        column: *column,
+                    };
+                    if date1904 && value >= offset + 1.0 {
+                        cells.push((cell, value, value - offset));
+                    } else if !date1904 && value >= 1.0 {
+                        cells.push((cell, value, value + offset));
+                    }
+                }
+            }
+        }
+        Ok(cells)
+    }
+
     /// Sets the language
     pub fn set_language(&mut self, language_id: &str) -> Result<(), String> {
         let language = match get_language(language_id) {
@@ -3677,6 +4192,76 @@ impl<'a> Model<'a> {
         self.workbook.settings.tz.clone()
     }
 
+    /// Returns true if the workbook uses the 1904 date system
+    pub fn get_date1904(&self) -> bool {
+        self.workbook.settings.date1904
+    }
+
+    /// Sets the reference style in which formulas are entered and displayed, R1C1 if `r1c1` is true
+    /// and A1 otherwise. Formulas are stored the same way in both styles.
+    pub fn set_r1c1(&mut self, r1c1: bool) {
+        self.workbook.settings.r1c1 = r1c1;
+    }
+
+    /// Returns true if formulas are entered and displayed in R1C1 reference style
+    pub fn get_r1c1(&self) -> bool {
+        self.workbook.settings.r1c1
+    }
+
+    /// Translates the formula of a defined name written by the user to A1 style.
+    /// In R1C1 reference style relative references are relative to the given cell.
+    pub(crate) fn defined_name_formula_to_a1(
+        &self,
+        formula: &str,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> String {
+        if !self.workbook.settings.r1c1 {
+            return formula.to_string();
+        }
+        match formula.strip_prefix('=') {
+            Some(body) => format!("={}", self.r1c1_to_a1(body, sheet, row, column)),
+            None => self.r1c1_to_a1(formula, sheet, row, column),
+        }
+    }
+
+    /// Returns the formula of a defined name in the reference style of the workbook.
+    /// In R1C1 reference style relative references are relative to the given cell.
+    pub(crate) fn defined_name_formula_to_user(
+        &self,
+        formula: &str,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> String {
+        if !self.workbook.settings.r1c1 {
+            return formula.to_string();
+        }
+        let (prefix, body) = match formula.strip_prefix('=') {
+            Some(body) => ("=", body),
+            None => ("", formula),
+        };
+        let context = CellReferenceRC {
+            sheet: self
+                .workbook
+                .worksheet(sheet)
+                .map(|worksheet| worksheet.get_name())
+                .unwrap_or_default(),
+            row,
+            column,
+        };
+        let mut parser = self.parser.clone();
+        let node = parser.parse(body, &context);
+        if matches!(node, Node::ParseErrorKind { .. }) {
+            return formula.to_string();
+        }
+        format!(
+            "{prefix}{}",
+            to_localized_r1c1_string(&node, self.locale, self.language)
+        )
+    }
+
     /// Gets the locale of the model
     pub fn get_locale(&self) -> String {
         self.workbook.settings.locale.clone()
@@ -14,12 +14,13 @@ use crate::{
         },
         types::CellReferenceRC,
     },
+    fonts::FontFallback,
     language::{get_default_language, get_language},
     locale::{get_default_locale, get_locale},
     model::{get_milliseconds_since_epoch, Model, ParsedDefinedName},
     types::{
-        DefinedName, Metadata, SheetState, Workbook, WorkbookSettings, WorkbookView, Worksheet,
-        WorksheetView,
+        DefinedName, Metadata, SheetState, Table, Workbook, WorkbookSettings, WorkbookView,
+        Worksheet, WorksheetView,
     },
     utils::ParsedReference,
 };
@@ -38,6 +39,19 @@ fn is_valid_sheet_name(name: &str) -> bool {
     !name.is_empty() && name.chars().count() <= 31 && !name.contains(&invalid[..])
 }
 
+/// The index of the sheet `index` after moving the sheet `from` to `to`
+pub(crate) fn move_sheet_index(index: u32, from: u32, to: u32) -> u32 {
+    if index == from {
+        to
+    } else if from < to && index > from && index <= to {
+        index - 1
+    } else if to < from && index >= to && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
 impl<'a> Model<'a> {
     /// Creates a new worksheet. Note that it does not check if the name or the sheet_id exists
     fn new_empty_worksheet(name: &str, sheet_id: u32, view_ids: &[&u32]) -> Worksheet {
@@ -71,6 +85,8 @@ impl<'a> Model<'a> {
             show_grid_lines: true,
             views,
             conditional_formatting: vec![],
+            outline_properties: Default::default(),
+            scenarios: vec![],
         }
     }
 
@@ -155,7 +171,12 @@ impl<'a> Model<'a> {
                     Node::LambdaDefKind { parameters, body } => {
                         ParsedDefinedName::LambdaDefinition(parameters, *body)
                     }
-                    _ => ParsedDefinedName::InvalidDefinedNameFormula,
+                    node
+                    @ (Node::ExternalReferenceKind { .. } | Node::ExternalRangeKind { .. }) => {
+                        ParsedDefinedName::ExternalReference(node)
+                    }
+                    Node::ParseErrorKind { .. } => ParsedDefinedName::InvalidDefinedNameFormula,
+                    _ => ParsedDefinedName::Formula,
                 }
             };
 
@@ -191,6 +212,36 @@ impl<'a> Model<'a> {
         self.evaluate();
     }
 
+    /// Replaces the workbook keeping the language and the host settings (fonts, functions, resolver).
+    /// The model is not evaluated.
+    pub(crate) fn replace_workbook(&mut self, workbook: Workbook) -> Result<(), String> {
+        let locale = get_locale(&workbook.settings.locale)
+            .map_err(|_| format!("Invalid locale: {}", workbook.settings.locale))?;
+        let tz = Tz::parse(&workbook.settings.tz)?;
+        self.shared_strings = HashMap::new();
+        for (index, s) in workbook.shared_strings.iter().enumerate() {
+            self.shared_strings.insert(s.to_string(), index);
+        }
+        self.workbook = workbook;
+        self.locale = locale;
+        self.tz = tz;
+        self.parser.set_locale(locale);
+        self.cells = HashMap::new();
+        self.spill_cells = Vec::new();
+        self.support = HashMap::new();
+        self.cf_cache = HashMap::new();
+        self.linked_models = HashMap::new();
+        self.linked_values = HashMap::new();
+        let defined_names = self.workbook.get_defined_names_with_scope();
+        self.parser
+            .set_worksheets_and_names(self.workbook.get_worksheet_names(), defined_names);
+        self.parsed_formulas = vec![];
+        self.parse_formulas();
+        self.parsed_defined_names = HashMap::new();
+        self.parse_defined_names();
+        Ok(())
+    }
+
     /// Gets the base name for new sheets
     fn get_sheet_name(&self) -> String {
         let language = self.language;
@@ -344,6 +395,7 @@ impl<'a> Model<'a> {
                 name: defined_name.name.clone(),
                 formula,
                 sheet_id: defined_name.sheet_id,
+                hidden: defined_name.hidden,
             });
         }
         self.workbook.defined_names = defined_names;
@@ -393,6 +445,127 @@ impl<'a> Model<'a> {
         }
     }
 
+    /// Inserts a copy of the sheet `sheet_index` named `new_name` at `position`.
+    ///
+    /// The copy gets its own sheet id. References to the original sheet in the copied formulas
+    /// (like `=Sheet1!A1` in Sheet1) point to the copy. Defined names local to the original sheet
+    /// and its tables are copied too. Tables get a new unique name.
+    ///
+    /// Fails if:
+    ///   * The sheet does not exists
+    ///   * A worksheet with that name already exists or the name is invalid
+    ///   * The position is too large
+    pub fn duplicate_sheet(
+        &mut self,
+        sheet_index: u32,
+        new_name: &str,
+        position: u32,
+    ) -> Result<(), String> {
+        if !is_valid_sheet_name(new_name) {
+            return Err(format!("Invalid name for a sheet: '{new_name}'"));
+        }
+        if self.get_sheet_index_by_name(new_name).is_some() {
+            return Err("A worksheet already exists with that name".to_string());
+        }
+        if position as usize > self.workbook.worksheets.len() {
+            return Err("Sheet index out of range".to_string());
+        }
+        let source = self.workbook.worksheet(sheet_index)?;
+        let old_name = source.get_name();
+        let old_sheet_id = source.sheet_id;
+        let mut worksheet = source.clone();
+        let sheet_id = self.get_new_sheet_id();
+        worksheet.set_name(new_name);
+        worksheet.sheet_id = sheet_id;
+        worksheet.state = SheetState::Visible;
+
+        // All internal formulas are R1C1
+        self.parser.set_lexer_mode(LexerMode::R1C1);
+        let cell_reference = &CellReferenceRC {
+            sheet: old_name.clone(),
+            row: 1,
+            column: 1,
+        };
+        let mut formulas = Vec::new();
+        for formula in &worksheet.shared_formulas {
+            let mut t = self.parser.parse(formula, cell_reference);
+            rename_sheet_in_node(&mut t, sheet_index, new_name);
+            formulas.push(to_rc_format(&t));
+        }
+        worksheet.shared_formulas = formulas;
+        self.parser.set_lexer_mode(LexerMode::A1);
+
+        let mut defined_names = Vec::new();
+        for defined_name in &self.workbook.defined_names {
+            if defined_name.sheet_id != Some(old_sheet_id) {
+                continue;
+            }
+            let mut t = self.parser.parse(&defined_name.formula, cell_reference);
+            rename_sheet_in_node(&mut t, sheet_index, new_name);
+            defined_names.push(DefinedName {
+                name: defined_name.name.clone(),
+                formula: to_localized_string(&t, cell_reference, self.locale, self.language),
+                sheet_id: Some(sheet_id),
+                hidden: defined_name.hidden,
+            });
+        }
+        self.workbook.defined_names.extend(defined_names);
+
+        let mut tables: Vec<Table> = self
+            .workbook
+            .tables
+            .values()
+            .filter(|table| table.sheet_name == old_name)
+            .cloned()
+            .collect();
+        tables.sort_by(|a, b| a.name.cmp(&b.name));
+        for mut table in tables {
+            let mut suffix = 2;
+            let mut name = format!("{}_{suffix}", table.name);
+            while self
+                .workbook
+                .tables
+                .keys()
+                .any(|key| key.to_uppercase() == name.to_uppercase())
+            {
+                suffix += 1;
+                name = format!("{}_{suffix}", table.name);
+            }
+            table.name = name.clone();
+            table.display_name = name.clone();
+            table.sheet_name = new_name.to_string();
+            self.workbook.tables.insert(name, table);
+        }
+        self.parser.set_tables(self.workbook.tables.clone());
+
+        self.workbook
+            .worksheets
+            .insert(position as usize, worksheet);
+        self.reset_parsed_structures();
+        Ok(())
+    }
+
+    /// Moves the sheet `from` so that it ends up at index `to`. The other sheets keep their order.
+    ///
+    /// Formulas and defined names refer to sheets by name or sheet id, so they are not affected.
+    /// The selected sheet of every view follows the sheets.
+    pub fn move_sheet(&mut self, from: u32, to: u32) -> Result<(), String> {
+        let sheet_count = self.workbook.worksheets.len() as u32;
+        if from >= sheet_count || to >= sheet_count {
+            return Err("Sheet index out of range".to_string());
+        }
+        if from == to {
+            return Ok(());
+        }
+        let worksheet = self.workbook.worksheets.remove(from as usize);
+        self.workbook.worksheets.insert(to as usize, worksheet);
+        for view in self.workbook.views.values_mut() {
+            view.sheet = move_sheet_index(view.sheet, from, to);
+        }
+        self.reset_parsed_structures();
+        Ok(())
+    }
+
     pub(crate) fn get_sheet_index_by_sheet_id(&self, sheet_id: u32) -> Option<u32> {
         let worksheets = &self.workbook.worksheets;
         for (index, worksheet) in worksheets.iter().enumerate() {
@@ -458,6 +631,8 @@ impl<'a> Model<'a> {
             settings: WorkbookSettings {
                 tz: timezone.to_string(),
                 locale: locale_id.to_string(),
+                date1904: false,
+                r1c1: false,
             },
             metadata: Metadata {
                 application: APPLICATION.to_string(),
@@ -470,6 +645,7 @@ impl<'a> Model<'a> {
             tables: HashMap::new(),
             views,
             theme: Default::default(),
+            external_links: Vec::new(),
         };
         let parsed_formulas = Vec::new();
         let worksheets = &workbook.worksheets;
@@ -494,7 +670,14 @@ impl<'a> Model<'a> {
             last_lambda_id: 0,
             spill_cells: Vec::new(),
             support: HashMap::new(),
+            dynamic_support: HashMap::new(),
             cf_cache: HashMap::new(),
+            font_fallback: FontFallback::default(),
+            custom_functions: HashMap::new(),
+            workbook_resolver: None,
+            linked_models: HashMap::new(),
+            linked_values: HashMap::new(),
+            cell_overrides: HashMap::new(),
         };
         model.parse_formulas();
         model.evaluate_conditional_formatting();
@@ -0,0 +1,170 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum outline level of a row or a column.
+/// Together with the ungrouped level 0 that makes the eight levels Excel supports.
+pub const MAX_OUTLINE_LEVEL: i32 = 7;
+
+/// A range of consecutive rows (or columns) grouped at a given outline level
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct OutlineGroup {
+    /// First row (or column) of the group
+    pub start: i32,
+    /// Last row (or column) of the group
+    pub end: i32,
+    /// Outline level of the group, from 1 to [MAX_OUTLINE_LEVEL]
+    pub level: i32,
+    /// Whether the summary row (or column) of the group is flagged as collapsed
+    pub collapsed: bool,
+}
+
+impl OutlineGroup {
+    /// Returns the summary row (or column) of the group if it is within `1..=last`.
+    /// It is the one after the group if `summary_after` and the one before otherwise.
+    pub fn get_summary_index(&self, summary_after: bool, last: i32) -> Option<i32> {
+        let index = if summary_after {
+            self.end + 1
+        } else {
+            self.start - 1
+        };
+        if (1..=last).contains(&index) {
+            Some(index)
+        } else {
+            None
+        }
+    }
+}
+
+/// Computes the groups from sorted, non overlapping `(start, end, level)` segments.
+///
+/// The group of level `n` is a maximal run of consecutive rows (or columns) with a level of at least `n`.
+pub(crate) fn get_outline_groups(
+    segments: &[(i32, i32, i32)],
+    summary_after: bool,
+    last: i32,
+    is_collapsed: impl Fn(i32) -> bool,
+) -> Vec<OutlineGroup> {
+    let max_level = segments.iter().map(|s| s.2).max().unwrap_or(0);
+    let mut groups = Vec::new();
+    for level in 1..=max_level {
+        let mut current: Option<(i32, i32)> = None;
+        for &(start, end, segment_level) in segments {
+            if segment_level < level {
+                continue;
+            }
+            current = match current {
+                Some((group_start, group_end)) if group_end + 1 == start => {
+                    Some((group_start, end))
+                }
+                Some((group_start, group_end)) => {
+                    groups.push((group_start, group_end, level));
+                    Some((start, end))
+                }
+                None => Some((start, end)),
+            };
+        }
+        if let Some((group_start, group_end)) = current {
+            groups.push((group_start, group_end, level));
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(start, end, level)| {
+            let mut group = OutlineGroup {
+                start,
+                end,
+                level,
+                collapsed: false,
+            };
+            group.collapsed = group
+                .get_summary_index(summary_after, last)
+                .is_some_and(&is_collapsed);
+            group
+        })
+        .collect()
+}
+
+/// Splits `start..=end` in runs of consecutive rows (or columns) with the same level, as
+/// `(first, last, level)`. Takes sorted, non overlapping segments, anything outside them is at level 0.
+pub(crate) fn get_outline_runs(
+    segments: &[(i32, i32, i32)],
+    start: i32,
+    end: i32,
+) -> Vec<(i32, i32, i32)> {
+    let mut runs: Vec<(i32, i32, i32)> = Vec::new();
+    let mut push = |first: i32, last: i32, level: i32| match runs.last_mut() {
+        Some(run) if run.1 + 1 == first && run.2 == level => run.1 = last,
+        _ => runs.push((first, last, level)),
+    };
+    let mut next = start;
+    for &(segment_start, segment_end, level) in segments {
+        let first = segment_start.max(start);
+        let last = segment_end.min(end);
+        if first > last {
+            continue;
+        }
+        if next < first {
+            push(next, first - 1, 0);
+        }
+        push(first, last, level);
+        next = last + 1;
+    }
+    if next <= end {
+        push(next, end, 0);
+    }
+    runs
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_outline_groups() {
+        // 2-10 level 1, with 4-5 and 7-8 at level 2
+        let segments = [
+            (2, 3, 1),
+            (4, 5, 2),
+            (6, 6, 1),
+            (7, 8, 2),
+            (9, 10, 1),
+            (12, 12, 1),
+        ];
+        let groups = get_outline_groups(&segments, true, 100, |i| i == 6);
+        let groups = groups
+            .iter()
+            .map(|g| (g.start, g.end, g.level, g.collapsed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (2, 10, 1, false),
+                (12, 12, 1, false),
+                (4, 5, 2, true),
+                (7, 8, 2, false)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_outline_runs() {
+        let segments = [(2, 3, 1), (4, 5, 1), (6, 6, 2), (9, 12, 1)];
+        assert_eq!(
+            get_outline_runs(&segments, 3, 10),
+            vec![(3, 5, 1), (6, 6, 2), (7, 8, 0), (9, 10, 1)]
+        );
+        assert_eq!(get_outline_runs(&[], 1, 4), vec![(1, 4, 0)]);
+    }
+
+    #[test]
+    fn test_summary_index() {
+        let group = OutlineGroup {
+            start: 1,
+            end: 3,
+            level: 1,
+            collapsed: false,
+        };
+        assert_eq!(group.get_summary_index(true, 10), Some(4));
+        assert_eq!(group.get_summary_index(false, 10), None);
+        assert_eq!(group.get_summary_index(true, 3), None);
+    }
+}
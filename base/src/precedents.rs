@@ -0,0 +1,474 @@
+use std::collections::{HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    expressions::{
+        parser::{parse_range, Node},
+        types::{CellReferenceIndex, CellReferenceRC},
+        utils::{is_valid_column_number, is_valid_row},
+    },
+    model::{CellOrRange, Model, ParsedDefinedName},
+};
+
+/// A cell or a range found tracing the precedents or the dependents of a cell
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct TracedReference {
+    pub sheet: u32,
+    pub row: i32,
+    pub column: i32,
+    pub width: i32,
+    pub height: i32,
+    /// 1 for direct precedents (or dependents), 2 for the precedents of those and so on
+    pub depth: u32,
+    /// The defined name or table the reference goes through, if any
+    pub name: Option<String>,
+    /// True if the reference is only known evaluating the formula (`INDIRECT`, `OFFSET`)
+    pub dynamic: bool,
+}
+
+/// A reference in a formula, with absolute coordinates
+#[derive(Clone)]
+struct FormulaReference {
+    sheet: u32,
+    row1: i32,
+    column1: i32,
+    row2: i32,
+    column2: i32,
+    name: Option<String>,
+    dynamic: bool,
+}
+
+impl FormulaReference {
+    fn new(left: CellReferenceIndex, right: CellReferenceIndex) -> FormulaReference {
+        FormulaReference {
+            sheet: left.sheet,
+            row1: left.row.min(right.row),
+            column1: left.column.min(right.column),
+            row2: left.row.max(right.row),
+            column2: left.column.max(right.column),
+            name: None,
+            dynamic: false,
+        }
+    }
+
+    fn contains(&self, cell: &CellReferenceIndex) -> bool {
+        self.sheet == cell.sheet
+            && (self.row1..=self.row2).contains(&cell.row)
+            && (self.column1..=self.column2).contains(&cell.column)
+    }
+
+    fn to_traced_reference(&self, depth: u32) -> TracedReference {
+        TracedReference {
+            sheet: self.sheet,
+            row: self.row1,
+            column: self.column1,
+            width: self.column2 - self.column1 + 1,
+            height: self.row2 - self.row1 + 1,
+            depth,
+            name: self.name.clone(),
+            dynamic: self.dynamic,
+        }
+    }
+}
+
+impl Model<'_> {
+    /// Returns the cells and ranges the formula in the cell reads from, up to `depth` levels.
+    ///
+    /// Level 1 are the references in the formula itself, level 2 the references in the formulas
+    /// of those cells, and so on. Every cell or range is listed once, at the lowest level it is found.
+    /// References through defined names and tables carry the name, and the ranges `INDIRECT`
+    /// or `OFFSET` computed in the last evaluation are flagged as dynamic.
+    pub fn get_precedents(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        depth: u32,
+    ) -> Result<Vec<TracedReference>, String> {
+        self.validate_traced_cell(sheet, row, column)?;
+        let mut precedents = Vec::new();
+        let mut seen_references = HashSet::new();
+        let start = CellReferenceIndex { sheet, row, column };
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 1)]);
+        while let Some((cell, level)) = queue.pop_front() {
+            if level > depth {
+                break;
+            }
+            for reference in self.get_formula_references(cell) {
+                let key = (
+                    reference.sheet,
+                    reference.row1,
+                    reference.column1,
+                    reference.row2,
+                    reference.column2,
+                );
+                if !seen_references.insert(key) {
+                    continue;
+                }
+                precedents.push(reference.to_traced_reference(level));
+                for formula_cell in self.get_formula_cells_in(&reference) {
+                    if visited.insert(formula_cell) {
+                        queue.push_back((formula_cell, level + 1));
+                    }
+                }
+            }
+        }
+        Ok(precedents)
+    }
+
+    /// Returns the cells with formulas that read from the cell, up to `depth` levels.
+    ///
+    /// Level 1 are the formulas that reference the cell directly, level 2 the formulas that reference
+    /// those, and so on. The name and dynamic flag are those of the reference that reaches the cell.
+    pub fn get_dependents(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        depth: u32,
+    ) -> Result<Vec<TracedReference>, String> {
+        self.validate_traced_cell(sheet, row, column)?;
+        let mut formulas = Vec::new();
+        for cell in self.get_all_cells() {
+            let cell = CellReferenceIndex {
+                sheet: cell.index,
+                row: cell.row,
+                column: cell.column,
+            };
+            let references = self.get_formula_references(cell);
+            if !references.is_empty() {
+                formulas.push((cell, references));
+            }
+        }
+        let mut dependents = Vec::new();
+        let start = CellReferenceIndex { sheet, row, column };
+        let mut visited = HashSet::from([start]);
+        let mut queue = VecDeque::from([(start, 1)]);
+        while let Some((target, level)) = queue.pop_front() {
+            if level > depth {
+                break;
+            }
+            for (cell, references) in &formulas {
+                if visited.contains(cell) {
+                    continue;
+                }
+                if let Some(reference) = references.iter().find(|r| r.contains(&target)) {
+                    visited.insert(*cell);
+                    dependents.push(TracedReference {
+                        sheet: cell.sheet,
+                        row: cell.row,
+                        column: cell.column,
+                        width: 1,
+                        height: 1,
+                        depth: level,
+                        name: reference.name.clone(),
+                        dynamic: reference.dynamic,
+                    });
+                    queue.push_back((*cell, level + 1));
+                }
+            }
+        }
+        Ok(dependents)
+    }
+
+    fn validate_traced_cell(&self, sheet: u32, row: i32, column: i32) -> Result<(), String> {
+        self.workbook.worksheet(sheet)?;
+        if !is_valid_row(row) {
+            return Err("Invalid row".to_string());
+        }
+        if !is_valid_column_number(column) {
+            return Err("Invalid column".to_string());
+        }
+        Ok(())
+    }
+
+    /// Returns the references in the formula of the cell, if any
+    fn get_formula_references(&self, cell: CellReferenceIndex) -> Vec<FormulaReference> {
+        let formula_index = match self
+            .workbook
+            .worksheet(cell.sheet)
+            .ok()
+            .and_then(|ws| ws.cell(cell.row, cell.column))
+            .and_then(|c| c.get_formula())
+        {
+            Some(index) => index,
+            None => return Vec::new(),
+        };
+        let node = &self.parsed_formulas[cell.sheet as usize][formula_index as usize].0;
+        let mut references = Vec::new();
+        self.collect_references(node, cell, &mut HashSet::new(), &mut references);
+        if let Some(dynamic_references) = self.dynamic_support.get(&cell) {
+            for dynamic_reference in dynamic_references {
+                let (left, right) = match *dynamic_reference {
+                    CellOrRange::Cell((sheet, row, column)) => {
+                        let reference = CellReferenceIndex { sheet, row, column };
+                        (reference, reference)
+                    }
+                    CellOrRange::Range((sheet, row1, column1, row2, column2)) => (
+                        CellReferenceIndex {
+                            sheet,
+                            row: row1,
+                            column: column1,
+                        },
+                        CellReferenceIndex {
+                            sheet,
+                            row: row2,
+                            column: column2,
+                        },
+                    ),
+                };
+                references.push(FormulaReference {
+                    dynamic: true,
+                    ..FormulaReference::new(left, right)
+                });
+            }
+        }
+        references
+    }
+
+    /// Returns the cells with a formula within the reference, sorted by row and column
+    fn get_formula_cells_in(&self, reference: &FormulaReference) -> Vec<CellReferenceIndex> {
+        let mut cells = Vec::new();
+        if let Ok(worksheet) = self.workbook.worksheet(reference.sheet) {
+            for (row, data_row) in &worksheet.sheet_data {
+                if !(reference.row1..=reference.row2).contains(row) {
+                    continue;
+                }
+                for (column, cell) in data_row {
+                    if (reference.column1..=reference.column2).contains(column)
+                        && cell.get_formula().is_some()
+                    {
+                        cells.push(CellReferenceIndex {
+                            sheet: reference.sheet,
+                            row: *row,
+                            column: *column,
+                        });
+                    }
+                }
+            }
+        }
+        cells.sort_by_key(|c| (c.row, c.column));
+        cells
+    }
+
+    /// Returns the table the range belongs to, if any
+    fn get_table_name_for(&self, reference: &FormulaReference) -> Option<String> {
+        let sheet_name = self.workbook.worksheet(reference.sheet).ok()?.get_name();
+        self.workbook.tables.values().find_map(|table| {
+            if table.sheet_name != sheet_name {
+                return None;
+            }
+            let (column1, row1, column2, row2) = parse_range(&table.reference).ok()?;
+            let inside = reference.row1 >= row1
+                && reference.row2 <= row2
+                && reference.column1 >= column1
+                && reference.column2 <= column2;
+            inside.then(|| table.name.clone())
+        })
+    }
+
+    /// Parses the formula of a defined name in the context of the cell that refers to it, so that
+    /// references without a sheet are in the sheet of the cell.
+    fn parse_defined_name_formula(
+        &self,
+        name: &str,
+        scope: Option<u32>,
+        cell: CellReferenceIndex,
+    ) -> Option<Node> {
+        let formula = self.get_defined_name_formula(name, scope).ok()?;
+        let context = CellReferenceRC {
+            sheet: self.workbook.worksheet(cell.sheet).ok()?.get_name(),
+            row: cell.row,
+            column: cell.column,
+        };
+        let mut parser = self.parser.clone();
+        let body = formula.strip_prefix('=').unwrap_or(&formula);
+        match parser.parse(body, &context) {
+            Node::ParseErrorKind { .. } => None,
+            node => Some(node),
+        }
+    }
+
+    /// Collects the references in the node. `names` are the defined names being expanded,
+    /// so that a name whose formula refers to itself is not followed forever.
+    fn collect_references(
+        &self,
+        node: &Node,
+        cell: CellReferenceIndex,
+        names: &mut HashSet<String>,
+        references: &mut Vec<FormulaReference>,
+    ) {
+        match node {
+            Node::ReferenceKind {
+                sheet_index,
+                absolute_row,
+                absolute_column,
+                row,
+                column,
+                ..
+            } => {
+                let reference = CellReferenceIndex {
+                    sheet: *sheet_index,
+                    row: if *absolute_row { *row } else { *row + cell.row },
+                    column: if *absolute_column {
+                        *column
+                    } else {
+                        *column + cell.column
+                    },
+                };
+                references.push(FormulaReference::new(reference, reference));
+            }
+            Node::RangeKind {
+                sheet_index,
+                absolute_row1,
+                absolute_column1,
+                row1,
+                column1,
+                absolute_row2,
+                absolute_column2,
+                row2,
+                column2,
+                ..
+            } => {
+                let left = CellReferenceIndex {
+                    sheet: *sheet_index,
+                    row: if *absolute_row1 {
+                        *row1
+                    } else {
+                        *row1 + cell.row
+                    },
+                    column: if *absolute_column1 {
+                        *column1
+                    } else {
+                        *column1 + cell.column
+                    },
+                };
+                let right = CellReferenceIndex {
+                    sheet: *sheet_index,
+                    row: if *absolute_row2 {
+                        *row2
+                    } else {
+                        *row2 + cell.row
+                    },
+                    column: if *absolute_column2 {
+                        *column2
+                    } else {
+                        *column2 + cell.column
+                    },
+                };
+                let mut reference = FormulaReference::new(left, right);
+                reference.name = self.get_table_name_for(&reference);
+                references.push(reference);
+            }
+            Node::DefinedNameKind((name, scope, _)) => {
+                let reference = match self.get_parsed_defined_name(name, *scope) {
+                    Ok(Some(ParsedDefinedName::CellReference(reference))) => {
+                        FormulaReference::new(reference, reference)
+                    }
+                    Ok(Some(ParsedDefinedName::RangeReference(range))) => {
+                        FormulaReference::new(range.left, range.right)
+                    }
+                    Ok(Some(ParsedDefinedName::Formula)) => {
+                        let key = name.to_lowercase();
+                        let formula = match self.parse_defined_name_formula(name, *scope, cell) {
+                            Some(formula) => formula,
+                            None => return,
+                        };
+                        if !names.insert(key.clone()) {
+                            return;
+                        }
+                        let mut name_references = Vec::new();
+                        self.collect_references(&formula, cell, names, &mut name_references);
+                        names.remove(&key);
+                        references.extend(name_references.into_iter().map(|reference| {
+                            FormulaReference {
+                                name: reference.name.clone().or_else(|| Some(name.clone())),
+                                ..reference
+                            }
+                        }));
+                        return;
+                    }
+                    _ => return,
+                };
+                references.push(FormulaReference {
+                    name: Some(name.clone()),
+                    ..reference
+                });
+            }
+            Node::TableNameKind(name) => {
+                let Some(table) = self.workbook.tables.get(name) else {
+                    return;
+                };
+                let (Some(sheet), Ok((column1, row1, column2, row2))) = (
+                    self.get_sheet_index_by_name(&table.sheet_name),
+                    parse_range(&table.reference),
+                ) else {
+                    return;
+                };
+                references.push(FormulaReference {
+                    name: Some(table.name.clone()),
+                    ..FormulaReference::new(
+                        CellReferenceIndex {
+                            sheet,
+                            row: row1,
+                            column: column1,
+                        },
+                        CellReferenceIndex {
+                            sheet,
+                            row: row2,
+                            column: column2,
+                        },
+                    )
+                });
+            }
+            Node::FunctionKind { args, .. } => {
+                for arg in args {
+                    self.collect_references(arg, cell, names, references);
+                }
+            }
+            Node::OpRangeKind { left, right }
+            | Node::OpConcatenateKind { left, right }
+            | Node::OpSumKind { left, right, .. }
+            | Node::OpProductKind { left, right, .. }
+            | Node::OpPowerKind { left, right }
+            | Node::CompareKind { left, right, .. } => {
+                self.collect_references(left, cell, names, references);
+                self.collect_references(right, cell, names, references);
+            }
+            Node::UnaryKind { right, .. } => {
+                self.collect_references(right, cell, names, references)
+            }
+            Node::ImplicitIntersection { child, .. } | Node::SpillRangeOperator { child } => {
+                self.collect_references(child, cell, names, references)
+            }
+            Node::LambdaDefKind { body, .. } => {
+                self.collect_references(body, cell, names, references)
+            }
+            Node::LambdaCallKind { lambda, args } => {
+                self.collect_references(lambda, cell, names, references);
+                for arg in args {
+                    self.collect_references(arg, cell, names, references);
+                }
+            }
+            Node::NamedFunctionKind { args, .. } => {
+                for arg in args {
+                    self.collect_references(arg, cell, names, references);
+                }
+            }
+            Node::BooleanKind(_)
+            | Node::NumberKind(_)
+            | Node::StringKind(_)
+            | Node::WrongReferenceKind { .. }
+            | Node::WrongRangeKind { .. }
+            | Node::ExternalReferenceKind { .. }
+            | Node::ExternalRangeKind { .. }
+            | Node::ArrayKind(_)
+            | Node::NamedVariableKind { .. }
+            | Node::ErrorKind(_)
+            | Node::ParseErrorKind { .. }
+            | Node::EmptyArgKind => {}
+        }
+    }
+}
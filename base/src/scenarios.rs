@@ -0,0 +1,378 @@
+//! Scenarios are named sets of values for some cells of a sheet (What-If Analysis).
+//! Showing a scenario types its values in the changing cells. The summary compares some result
+//! cells across all the scenarios of a sheet in a new sheet, like Excel's "Scenario Summary".
+//!
+//! The values of the scenarios are as typed in the default locale and language, with formulas in A1
+//! style, whatever the settings of the workbook. The UI translates them for the user.
+
+use crate::{
+    cell::CellValue,
+    expressions::{
+        types::CellReferenceIndex,
+        utils::{is_valid_column_number, is_valid_row, number_to_column, quote_name},
+    },
+    language::get_default_language,
+    locale::get_default_locale,
+    model::Model,
+    types::{Cell, Scenario},
+};
+
+/// Excel does not allow more changing cells in a scenario
+const MAX_CHANGING_CELLS: usize = 32;
+
+const MAX_NAME_LENGTH: usize = 255;
+
+const SUMMARY_SHEET_NAME: &str = "Scenario Summary";
+
+/// A cell of the summary sheet, (row, column, content)
+type SummaryCell = (i32, i32, String);
+
+impl Model<'_> {
+    /// Returns the scenarios of the sheet
+    pub fn get_scenarios(&self, sheet: u32) -> Result<Vec<Scenario>, String> {
+        Ok(self.workbook.worksheet(sheet)?.scenarios.clone())
+    }
+
+    /// Adds a scenario to the sheet. Names are unique in a sheet, ignoring case.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ironcalc_base::Model;
+    /// # use ironcalc_base::types::{Scenario, ScenarioInputCell};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut model = Model::new_empty("model", "en", "UTC", "en")?;
+    /// model.set_user_input(0, 1, 1, "100".to_string())?;
+    /// model.set_user_input(0, 2, 1, "=A1*2".to_string())?;
+    /// let scenario = Scenario {
+    ///     name: "Best case".to_string(),
+    ///     comment: String::new(),
+    ///     user: String::new(),
+    ///     locked: true,
+    ///     hidden: false,
+    ///     input_cells: vec![ScenarioInputCell { row: 1, column: 1, value: "150".to_string() }],
+    /// };
+    /// model.add_scenario(0, scenario)?;
+    /// model.show_scenario(0, "Best case")?;
+    /// model.evaluate();
+    /// assert_eq!(model.get_formatted_cell_value(0, 2, 1)?, "300");
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_scenario(&mut self, sheet: u32, scenario: Scenario) -> Result<(), String> {
+        self.validate_scenario(sheet, &scenario, None)?;
+        self.workbook.worksheet_mut(sheet)?.scenarios.push(scenario);
+        Ok(())
+    }
+
+    /// Replaces the scenario `name` of the sheet, the new one might have a different name
+    pub fn update_scenario(
+        &mut self,
+        sheet: u32,
+        name: &str,
+        scenario: Scenario,
+    ) -> Result<(), String> {
+        let index = self.get_scenario_index(sheet, name)?;
+        self.validate_scenario(sheet, &scenario, Some(index))?;
+        self.workbook.worksheet_mut(sheet)?.scenarios[index] = scenario;
+        Ok(())
+    }
+
+    /// Deletes the scenario `name` of the sheet
+    pub fn delete_scenario(&mut self, sheet: u32, name: &str) -> Result<(), String> {
+        let index = self.get_scenario_index(sheet, name)?;
+        self.workbook.worksheet_mut(sheet)?.scenarios.remove(index);
+        Ok(())
+    }
+
+    /// Types the values of the scenario `name` in its changing cells.
+    /// The model is not evaluated.
+    pub fn show_scenario(&mut self, sheet: u32, name: &str) -> Result<(), String> {
+        let index = self.get_scenario_index(sheet, name)?;
+        let scenario = self.workbook.worksheet(sheet)?.scenarios[index].clone();
+        self.with_default_locale(|model| {
+            for cell in scenario.input_cells {
+                model.set_a1_user_input(sheet, cell.row, cell.column, cell.value)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Adds a sheet after `sheet` with the values of the changing cells of all the scenarios of
+    /// `sheet` and the values the `result_cells` take with each of them. Returns the index of the
+    /// new sheet.
+    ///
+    /// The changing cells keep their values and the model is evaluated.
+    pub fn create_scenario_summary(
+        &mut self,
+        sheet: u32,
+        result_cells: &[CellReferenceIndex],
+    ) -> Result<u32, String> {
+        let (index, _) = self.write_scenario_summary(sheet, result_cells)?;
+        Ok(index)
+    }
+
+    /// Same as [Model::create_scenario_summary] but also returns the cells of the new sheet and
+    /// their content
+    pub(crate) fn write_scenario_summary(
+        &mut self,
+        sheet: u32,
+        result_cells: &[CellReferenceIndex],
+    ) -> Result<(u32, Vec<SummaryCell>), String> {
+        let scenarios = self.get_scenarios(sheet)?;
+        if scenarios.is_empty() {
+            return Err("There are no scenarios in the sheet".to_string());
+        }
+        for cell in result_cells {
+            self.workbook.worksheet(cell.sheet)?;
+        }
+
+        // All the changing cells, in the order they first appear
+        let mut changing_cells: Vec<(i32, i32)> = Vec::new();
+        for scenario in &scenarios {
+            for cell in &scenario.input_cells {
+                if !changing_cells.contains(&(cell.row, cell.column)) {
+                    changing_cells.push((cell.row, cell.column));
+                }
+            }
+        }
+        let worksheet = self.workbook.worksheet(sheet)?;
+        let originals: Vec<(i32, i32, Option<Cell>)> = changing_cells
+            .iter()
+            .map(|&(row, column)| (row, column, worksheet.cell(row, column).cloned()))
+            .collect();
+
+        let mut current_results = Vec::new();
+        for cell in result_cells {
+            current_results.push(self.get_scenario_summary_value(cell)?);
+        }
+        let mut results = Vec::new();
+        let shown = self.try_scenarios(sheet, &scenarios, result_cells, &mut results, &originals);
+        self.restore_changing_cells(sheet, &originals)?;
+        self.evaluate();
+        shown?;
+
+        let mut cells = Vec::new();
+        let mut put = |row: i32, column: i32, value: String| {
+            if !value.is_empty() {
+                cells.push((row, column, value));
+            }
+        };
+        put(2, 2, SUMMARY_SHEET_NAME.to_string());
+        put(3, 4, "Current Values:".to_string());
+        for (index, scenario) in scenarios.iter().enumerate() {
+            put(
+                3,
+                5 + index as i32,
+                self.get_scenario_summary_text(&scenario.name),
+            );
+        }
+        put(4, 2, "Changing Cells:".to_string());
+        let mut row = 5;
+        for &(cell_row, cell_column) in &changing_cells {
+            let reference = CellReferenceIndex {
+                sheet,
+                row: cell_row,
+                column: cell_column,
+            };
+            put(
+                row,
+                3,
+                self.get_scenario_summary_reference(&reference, sheet)?,
+            );
+            put(row, 4, self.get_scenario_summary_value(&reference)?);
+            for (index, scenario) in scenarios.iter().enumerate() {
+                if let Some(cell) = scenario
+                    .input_cells
+                    .iter()
+                    .find(|cell| (cell.row, cell.column) == (cell_row, cell_column))
+                {
+                    put(row, 5 + index as i32, cell.value.clone());
+                }
+            }
+            row += 1;
+        }
+        if !result_cells.is_empty() {
+            put(row, 2, "Result Cells:".to_string());
+            row += 1;
+            for (result_index, cell) in result_cells.iter().enumerate() {
+                put(row, 3, self.get_scenario_summary_reference(cell, sheet)?);
+                put(row, 4, current_results[result_index].clone());
+                for (index, values) in results.iter().enumerate() {
+                    put(row, 5 + index as i32, values[result_index].clone());
+                }
+                row += 1;
+            }
+        }
+        put(
+            row + 1,
+            2,
+            "Notes: Current Values column represents values of changing cells at time Scenario \
+             Summary Report was created."
+                .to_string(),
+        );
+
+        let mut name = SUMMARY_SHEET_NAME.to_string();
+        let mut suffix = 2;
+        while self.get_sheet_index_by_name(&name).is_some() {
+            name = format!("{SUMMARY_SHEET_NAME} {suffix}");
+            suffix += 1;
+        }
+        let index = sheet + 1;
+        self.insert_sheet(&name, index, None)?;
+        self.with_default_locale(|model| {
+            for (row, column, value) in &cells {
+                model.set_a1_user_input(index, *row, *column, value.clone())?;
+            }
+            Ok::<(), String>(())
+        })?;
+        self.evaluate();
+        Ok((index, cells))
+    }
+
+    /// Shows each scenario, starting from the original values of the changing cells, and
+    /// evaluates the model, adding the values of the result cells to `results`.
+    fn try_scenarios(
+        &mut self,
+        sheet: u32,
+        scenarios: &[Scenario],
+        result_cells: &[CellReferenceIndex],
+        results: &mut Vec<Vec<String>>,
+        originals: &[(i32, i32, Option<Cell>)],
+    ) -> Result<(), String> {
+        for scenario in scenarios {
+            self.restore_changing_cells(sheet, originals)?;
+            self.show_scenario(sheet, &scenario.name)?;
+            self.evaluate();
+            let mut values = Vec::new();
+            for cell in result_cells {
+                values.push(self.get_scenario_summary_value(cell)?);
+            }
+            results.push(values);
+        }
+        Ok(())
+    }
+
+    fn restore_changing_cells(
+        &mut self,
+        sheet: u32,
+        originals: &[(i32, i32, Option<Cell>)],
+    ) -> Result<(), String> {
+        let worksheet = self.workbook.worksheet_mut(sheet)?;
+        for (row, column, original) in originals {
+            match original {
+                Some(cell) => worksheet.update_cell(*row, *column, cell.clone())?,
+                None => worksheet.remove_cell(*row, *column)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// The value of the cell as it would be typed in the default locale
+    fn get_scenario_summary_value(&mut self, cell: &CellReferenceIndex) -> Result<String, String> {
+        let value = match self.get_cell_value_by_index(cell.sheet, cell.row, cell.column)? {
+            CellValue::None => String::new(),
+            CellValue::Number(value) => value.to_string(),
+            CellValue::Boolean(value) => value.to_string().to_uppercase(),
+            CellValue::String(value) => self.get_scenario_summary_text(&value),
+        };
+        Ok(value)
+    }
+
+    /// Text that is typed as that same text in the default locale
+    fn get_scenario_summary_text(&mut self, text: &str) -> String {
+        if self.with_default_locale(|model| model.is_text_input(text)) {
+            text.to_string()
+        } else {
+            format!("'{text}")
+        }
+    }
+
+    /// An absolute reference like `$B$3`, with the name of the sheet if it is not `sheet`
+    fn get_scenario_summary_reference(
+        &self,
+        cell: &CellReferenceIndex,
+        sheet: u32,
+    ) -> Result<String, String> {
+        let column = number_to_column(cell.column).ok_or("Invalid column")?;
+        let reference = format!("${column}${}", cell.row);
+        if cell.sheet == sheet {
+            Ok(reference)
+        } else {
+            let sheet_name = quote_name(&self.workbook.worksheet(cell.sheet)?.get_name());
+            Ok(format!("{sheet_name}!{reference}"))
+        }
+    }
+
+    /// Runs `f` with the default locale and language, to read or type the values of the scenarios.
+    /// The model must not be evaluated in `f`.
+    fn with_default_locale<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+        let locale = self.locale;
+        let language = self.language;
+        self.locale = get_default_locale();
+        self.language = get_default_language();
+        self.parser.set_locale(self.locale);
+        self.parser.set_language(self.language);
+        let result = f(self);
+        self.locale = locale;
+        self.language = language;
+        self.parser.set_locale(locale);
+        self.parser.set_language(language);
+        result
+    }
+
+    /// The position of the scenario `name` in the sheet, ignoring case
+    pub(crate) fn get_scenario_index(&self, sheet: u32, name: &str) -> Result<usize, String> {
+        let lower = name.to_lowercase();
+        self.workbook
+            .worksheet(sheet)?
+            .scenarios
+            .iter()
+            .position(|scenario| scenario.name.to_lowercase() == lower)
+            .ok_or_else(|| format!("Scenario not found: '{name}'"))
+    }
+
+    /// Checks the scenario can be in the sheet, `index` is the scenario it replaces
+    fn validate_scenario(
+        &self,
+        sheet: u32,
+        scenario: &Scenario,
+        index: Option<usize>,
+    ) -> Result<(), String> {
+        let name = &scenario.name;
+        if name.trim().is_empty() {
+            return Err("The scenario name cannot be empty".to_string());
+        }
+        if name.chars().count() > MAX_NAME_LENGTH {
+            return Err(format!(
+                "The scenario name cannot be longer than {MAX_NAME_LENGTH} characters"
+            ));
+        }
+        let scenarios = &self.workbook.worksheet(sheet)?.scenarios;
+        if scenarios.iter().enumerate().any(|(other, existing)| {
+            Some(other) != index && existing.name.to_lowercase() == name.to_lowercase()
+        }) {
+            return Err(format!("A scenario named '{name}' already exists"));
+        }
+        if scenario.input_cells.is_empty() {
+            return Err("A scenario needs at least one changing cell".to_string());
+        }
+        if scenario.input_cells.len() > MAX_CHANGING_CELLS {
+            return Err(format!(
+                "A scenario cannot have more than {MAX_CHANGING_CELLS} changing cells"
+            ));
+        }
+        for (position, cell) in scenario.input_cells.iter().enumerate() {
+            if !is_valid_row(cell.row) || !is_valid_column_number(cell.column) {
+                return Err("Invalid changing cell".to_string());
+            }
+            if scenario.input_cells[..position]
+                .iter()
+                .any(|other| (other.row, other.column) == (cell.row, cell.column))
+            {
+                return Err("A changing cell appears twice in the scenario".to_string());
+            }
+        }
+        Ok(())
+    }
+}
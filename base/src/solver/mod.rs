@@ -0,0 +1,843 @@
+//! Maximizes or minimizes the value of a cell by changing other cells, subject to constraints,
+//! like Excel's Solver add-in.
+//!
+//! Linear models are solved with the simplex method, and branch and bound for the integer
+//! constraints. Other models are solved with an augmented Lagrangian and the Nelder-Mead method,
+//! which only needs the values of the cells, not their derivatives.
+//!
+//! Excel stores the Solver setup of a sheet in hidden `solver_*` defined names, see
+//! [Model::get_solver_problem] and [Model::set_solver_problem].
+
+mod names;
+mod nelder_mead;
+mod simplex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    calc_result::CalcResult,
+    cell::CellValue,
+    expressions::{
+        parser::Node,
+        types::{Area, CellReferenceIndex, CellReferenceRC},
+    },
+    model::Model,
+    types::Cell,
+};
+
+use simplex::{LinearConstraint, LinearProgram, Relation};
+
+/// What the solver does with the objective cell
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SolverGoal {
+    Maximize,
+    Minimize,
+    /// Makes the objective cell equal to `value`
+    Value {
+        value: f64,
+    },
+}
+
+/// How the problem is solved
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverMethod {
+    /// The simplex method if the model is linear, the nonlinear method otherwise
+    Automatic,
+    /// The simplex method, fails if the model is not linear
+    Simplex,
+    /// The nonlinear method, even if the model is linear
+    Nonlinear,
+}
+
+/// The kind of constraint
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConstraintOperator {
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    /// The changing cells must be integers
+    Integer,
+    /// The changing cells must be 0 or 1
+    Binary,
+}
+
+/// A constraint like `A1:A5 <= B1:B5`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SolverConstraint {
+    pub left: Area,
+    pub operator: ConstraintOperator,
+    /// A formula in A1 style, e.g. "Sheet1!$B$1:$B$5" or "100".
+    /// It must be a single value or have the size of `left`.
+    /// Ignored by [ConstraintOperator::Integer] and [ConstraintOperator::Binary].
+    pub right: String,
+}
+
+/// Options of [Model::solve]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct SolverOptions {
+    /// Changing cells without a lower bound in the constraints must be non negative
+    pub non_negative: bool,
+    pub method: SolverMethod,
+    /// Maximum number of subproblems in the branch and bound of linear models,
+    /// or of steps of the nonlinear method
+    pub max_iterations: u32,
+    /// How close to its bound a constraint must be to be satisfied
+    pub precision: f64,
+}
+
+impl Default for SolverOptions {
+    fn default() -> Self {
+        SolverOptions {
+            non_negative: true,
+            method: SolverMethod::Automatic,
+            max_iterations: 1000,
+            precision: 1e-6,
+        }
+    }
+}
+
+/// A Solver setup
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SolverProblem {
+    /// The cell with the formula to maximize or minimize
+    pub objective: CellReferenceIndex,
+    pub goal: SolverGoal,
+    /// The changing cells
+    pub variables: Vec<Area>,
+    pub constraints: Vec<SolverConstraint>,
+    pub options: SolverOptions,
+}
+
+/// How the search ended
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SolverStatus {
+    /// The model is linear and the solution is optimal
+    Optimal,
+    /// The nonlinear method found a local optimum that satisfies the constraints
+    Converged,
+    /// There are no values that satisfy the constraints
+    Infeasible,
+    /// The objective can grow (or decrease) without limit
+    Unbounded,
+    /// The search stopped after `max_iterations`
+    IterationLimit,
+}
+
+/// The outcome of [Model::solve]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SolverResult {
+    pub status: SolverStatus,
+    /// Value of the objective cell after the search
+    pub objective: f64,
+    /// The values of the changing cells, row by row for each area of `variables`
+    pub values: Vec<f64>,
+    /// Number of subproblems or steps of the nonlinear method
+    pub iterations: u32,
+    /// The model was solved with the simplex method
+    pub linear: bool,
+}
+
+/// The right side of a single cell constraint
+enum Right {
+    Constant(f64),
+    /// The element at (`row`, `column`) of the value of a formula, or the value itself if it is
+    /// not a range or an array
+    Formula {
+        index: usize,
+        row: i32,
+        column: i32,
+    },
+}
+
+/// A constraint on a single cell `left relation right`
+struct Row {
+    left: CellReferenceIndex,
+    relation: Relation,
+    right: Right,
+}
+
+/// A [SolverProblem] with the areas expanded into cells and the formulas parsed
+struct Compiled {
+    cells: Vec<CellReferenceIndex>,
+    objective: CellReferenceIndex,
+    goal: SolverGoal,
+    rows: Vec<Row>,
+    formulas: Vec<Node>,
+    integers: Vec<usize>,
+    /// Lower and upper bounds of each changing cell
+    bounds: Vec<(f64, f64)>,
+}
+
+/// Values of the objective and of both sides of each row at a point
+struct Point {
+    objective: f64,
+    rows: Vec<(f64, f64)>,
+}
+
+/// How far a row is from being satisfied
+fn row_violation(relation: Relation, left: f64, right: f64) -> f64 {
+    let violation = match relation {
+        Relation::LessOrEqual => (left - right).max(0.0),
+        Relation::Equal => (left - right).abs(),
+        Relation::GreaterOrEqual => (right - left).max(0.0),
+    };
+    if violation.is_nan() {
+        f64::INFINITY
+    } else {
+        violation
+    }
+}
+
+fn area_cells(area: &Area) -> impl Iterator<Item = CellReferenceIndex> + '_ {
+    (area.row..area.row + area.height).flat_map(move |row| {
+        (area.column..area.column + area.width).map(move |column| CellReferenceIndex {
+            sheet: area.sheet,
+            row,
+            column,
+        })
+    })
+}
+
+/// Values very close to an integer are rounded, the simplex method leaves values like 2.9999999999999996
+fn snap(value: f64) -> f64 {
+    let rounded = value.round();
+    if (value - rounded).abs() <= 1e-9 * value.abs().max(1.0) {
+        rounded
+    } else {
+        value
+    }
+}
+
+impl Model<'_> {
+    /// Maximizes, minimizes or sets to a value the `objective` cell by changing the `variables`
+    /// cells, subject to the `constraints`.
+    ///
+    /// The model is checked for linearity by evaluating it at a few points. Linear models are
+    /// solved with the simplex method unless [SolverMethod::Nonlinear] is requested. In nonlinear
+    /// models the integer constraints are satisfied by rounding the solution and solving again
+    /// for the other cells.
+    ///
+    /// The changing cells keep the solution if the status is [SolverStatus::Optimal] or
+    /// [SolverStatus::Converged], or [SolverStatus::IterationLimit] with the constraints satisfied.
+    /// Otherwise the original values are restored. In all cases the model is evaluated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use ironcalc_base::Model;
+    /// # use ironcalc_base::expressions::types::{Area, CellReferenceIndex};
+    /// # use ironcalc_base::solver::*;
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut model = Model::new_empty("model", "en", "UTC", "en")?;
+    /// // Profit of two products that share 100 hours
+    /// model.set_user_input(0, 1, 3, "=20*A1+30*B1".to_string())?;
+    /// model.set_user_input(0, 2, 3, "=A1+2*B1".to_string())?;
+    /// model.evaluate();
+    /// let problem = SolverProblem {
+    ///     objective: CellReferenceIndex { sheet: 0, row: 1, column: 3 },
+    ///     goal: SolverGoal::Maximize,
+    ///     variables: vec![Area { sheet: 0, row: 1, column: 1, width: 2, height: 1 }],
+    ///     constraints: vec![
+    ///         SolverConstraint {
+    ///             left: Area { sheet: 0, row: 2, column: 3, width: 1, height: 1 },
+    ///             operator: ConstraintOperator::LessOrEqual,
+    ///             right: "100".to_string(),
+    ///         },
+    ///         SolverConstraint {
+    ///             left: Area { sheet: 0, row: 1, column: 1, width: 1, height: 1 },
+    ///             operator: ConstraintOperator::LessOrEqual,
+    ///             right: "40".to_string(),
+    ///         },
+    ///     ],
+    ///     options: SolverOptions::default(),
+    /// };
+    /// let result = model.solve(&problem)?;
+    /// assert_eq!(result.status, SolverStatus::Optimal);
+    /// assert_eq!(result.values, vec![40.0, 30.0]);
+    /// assert_eq!(result.objective, 1700.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn solve(&mut self, problem: &SolverProblem) -> Result<SolverResult, String> {
+        let objective = problem.objective;
+        if self
+            .workbook
+            .worksheet(objective.sheet)?
+            .cell(objective.row, objective.column)
+            .and_then(|cell| cell.get_formula())
+            .is_none()
+        {
+            return Err("The objective cell must contain a formula".to_string());
+        }
+        let mut cells: Vec<CellReferenceIndex> = Vec::new();
+        for area in &problem.variables {
+            if area.width < 1 || area.height < 1 {
+                return Err("Invalid area".to_string());
+            }
+            for cell in area_cells(area) {
+                if !cells.contains(&cell) {
+                    cells.push(cell);
+                }
+            }
+        }
+        if cells.is_empty() {
+            return Err("There are no changing cells".to_string());
+        }
+        let mut originals = Vec::new();
+        let mut start = Vec::new();
+        for cell in &cells {
+            let original = self
+                .workbook
+                .worksheet(cell.sheet)?
+                .cell(cell.row, cell.column)
+                .cloned();
+            start.push(match &original {
+                None | Some(Cell::EmptyCell { .. }) => 0.0,
+                Some(Cell::NumberCell { v, .. }) => *v,
+                Some(_) => return Err("The changing cells must contain numbers".to_string()),
+            });
+            originals.push(original);
+        }
+
+        self.evaluate();
+        let compiled = self.solver_compile(problem, cells)?;
+        let options = &problem.options;
+        let linear = match options.method {
+            SolverMethod::Nonlinear => None,
+            SolverMethod::Automatic | SolverMethod::Simplex => {
+                self.solver_linearize(&compiled, &start)?
+            }
+        };
+        if linear.is_none() && options.method == SolverMethod::Simplex {
+            self.solver_restore(&compiled.cells, originals)?;
+            return Err("The model is not linear".to_string());
+        }
+        let is_linear = linear.is_some();
+        let (status, values, iterations) = match linear {
+            Some(program) => {
+                let solution = program.solve(options.max_iterations);
+                let values = solution.values.into_iter().map(snap).collect();
+                (solution.status, values, solution.subproblems)
+            }
+            None => self.solver_nonlinear(&compiled, &start, options)?,
+        };
+
+        let keep = match status {
+            SolverStatus::Optimal | SolverStatus::Converged => true,
+            SolverStatus::IterationLimit => {
+                !values.is_empty() && {
+                    let point = self.solver_evaluate(&compiled, &values)?;
+                    compiled.is_solution(&point, options.precision)
+                }
+            }
+            SolverStatus::Infeasible | SolverStatus::Unbounded => false,
+        };
+        let values = if keep {
+            for (cell, value) in compiled.cells.iter().zip(&values) {
+                self.update_cell_with_number(cell.sheet, cell.row, cell.column, *value)?;
+            }
+            self.evaluate();
+            values
+        } else {
+            self.solver_restore(&compiled.cells, originals)?;
+            start
+        };
+        Ok(SolverResult {
+            status,
+            objective: self.solver_value(&objective),
+            values,
+            iterations,
+            linear: is_linear,
+        })
+    }
+
+    fn solver_restore(
+        &mut self,
+        cells: &[CellReferenceIndex],
+        originals: Vec<Option<Cell>>,
+    ) -> Result<(), String> {
+        for (cell, original) in cells.iter().zip(originals) {
+            let worksheet = self.workbook.worksheet_mut(cell.sheet)?;
+            match original {
+                Some(value) => worksheet.update_cell(cell.row, cell.column, value)?,
+                None => worksheet.remove_cell(cell.row, cell.column)?,
+            };
+        }
+        self.evaluate();
+        Ok(())
+    }
+
+    /// The value of a cell, empty cells are 0 and anything other than a number is NaN
+    fn solver_value(&self, cell: &CellReferenceIndex) -> f64 {
+        match self.get_cell_value_by_index(cell.sheet, cell.row, cell.column) {
+            Ok(CellValue::Number(value)) => value,
+            Ok(CellValue::None) => 0.0,
+            _ => f64::NAN,
+        }
+    }
+
+    fn solver_compile(
+        &mut self,
+        problem: &SolverProblem,
+        cells: Vec<CellReferenceIndex>,
+    ) -> Result<Compiled, String> {
+        let context = CellReferenceIndex {
+            sheet: problem.objective.sheet,
+            row: 1,
+            column: 1,
+        };
+        let context_rc = CellReferenceRC {
+            sheet: self.workbook.worksheet(context.sheet)?.get_name(),
+            row: 1,
+            column: 1,
+        };
+        let mut rows = Vec::new();
+        let mut formulas = Vec::new();
+        let mut integers = Vec::new();
+        let mut binaries = Vec::new();
+        for (index, constraint) in problem.constraints.iter().enumerate() {
+            let left = &constraint.left;
+            if left.width < 1 || left.height < 1 {
+                return Err(format!("Constraint {}: invalid area", index + 1));
+            }
+            let relation = match constraint.operator {
+                ConstraintOperator::LessOrEqual => Relation::LessOrEqual,
+                ConstraintOperator::Equal => Relation::Equal,
+                ConstraintOperator::GreaterOrEqual => Relation::GreaterOrEqual,
+                ConstraintOperator::Integer | ConstraintOperator::Binary => {
+                    for cell in area_cells(left) {
+                        let Some(j) = cells.iter().position(|c| *c == cell) else {
+                            return Err(format!(
+                                "Constraint {}: integer and binary constraints must refer to changing cells",
+                                index + 1
+                            ));
+                        };
+                        integers.push(j);
+                        if constraint.operator == ConstraintOperator::Binary {
+                            binaries.push(j);
+                        }
+                    }
+                    continue;
+                }
+            };
+            let formula = constraint.right.trim();
+            let formula = formula.strip_prefix('=').unwrap_or(formula);
+            if formula.is_empty() {
+                return Err(format!("Constraint {}: missing right side", index + 1));
+            }
+            let node = self.parser.parse(formula, &context_rc);
+            let (height, width) = match self.evaluate_node_in_context(&node, context) {
+                CalcResult::Range { left, right } => {
+                    (right.row - left.row + 1, right.column - left.column + 1)
+                }
+                CalcResult::Array(values) => (
+                    values.len() as i32,
+                    values.first().map_or(0, |row| row.len()) as i32,
+                ),
+                _ => (1, 1),
+            };
+            let single = height == 1 && width == 1;
+            if !single && (height, width) != (left.height, left.width) {
+                return Err(format!(
+                    "Constraint {}: the right side must be a single value or have the size of the left side",
+                    index + 1
+                ));
+            }
+            let formula_index = formulas.len();
+            formulas.push(node);
+            for cell in area_cells(left) {
+                let (row, column) = if single {
+                    (0, 0)
+                } else {
+                    (cell.row - left.row, cell.column - left.column)
+                };
+                rows.push(Row {
+                    left: cell,
+                    relation,
+                    right: Right::Formula {
+                        index: formula_index,
+                        row,
+                        column,
+                    },
+                });
+            }
+        }
+        integers.sort_unstable();
+        integers.dedup();
+
+        let mut bounds = Vec::new();
+        for (j, cell) in cells.iter().enumerate() {
+            let lower_bounded = rows.iter().any(|row| {
+                row.left == *cell
+                    && matches!(row.relation, Relation::Equal | Relation::GreaterOrEqual)
+            });
+            let lower = if problem.options.non_negative && !lower_bounded {
+                0.0
+            } else {
+                f64::NEG_INFINITY
+            };
+            bounds.push((lower, f64::INFINITY));
+            if binaries.contains(&j) {
+                bounds[j] = (0.0, 1.0);
+            }
+        }
+        // Binary cells are integers between 0 and 1
+        binaries.sort_unstable();
+        binaries.dedup();
+        for &j in &binaries {
+            for (relation, value) in [
+                (Relation::GreaterOrEqual, 0.0),
+                (Relation::LessOrEqual, 1.0),
+            ] {
+                rows.push(Row {
+                    left: cells[j],
+                    relation,
+                    right: Right::Constant(value),
+                });
+            }
+        }
+        Ok(Compiled {
+            cells,
+            objective: problem.objective,
+            goal: problem.goal,
+            rows,
+            formulas,
+            integers,
+            bounds,
+        })
+    }
+
+    /// Sets the changing cells to `x` and evaluates the model
+    fn solver_evaluate(&mut self, compiled: &Compiled, x: &[f64]) -> Result<Point, String> {
+        for (cell, value) in compiled.cells.iter().zip(x) {
+            self.update_cell_with_number(cell.sheet, cell.row, cell.column, *value)?;
+        }
+        self.evaluate();
+        let context = CellReferenceIndex {
+            sheet: compiled.objective.sheet,
+            row: 1,
+            column: 1,
+        };
+        let results: Vec<CalcResult> = compiled
+            .formulas
+            .iter()
+            .map(|node| self.evaluate_node_in_context(node, context))
+            .collect();
+        let rows = compiled
+            .rows
+            .iter()
+            .map(|row| {
+                let right = match row.right {
+                    Right::Constant(value) => value,
+                    Right::Formula { index, row, column } => match &results[index] {
+                        CalcResult::Number(value) => *value,
+                        CalcResult::EmptyCell => 0.0,
+                        CalcResult::Range { left, .. } => self.solver_value(&CellReferenceIndex {
+                            sheet: left.sheet,
+                            row: left.row + row,
+                            column: left.column + column,
+                        }),
+                        CalcResult::Array(values) => {
+                            match values
+                                .get(row as usize)
+                                .and_then(|r| r.get(column as usize))
+                            {
+                                Some(crate::expressions::parser::ArrayNode::Number(value)) => {
+                                    *value
+                                }
+                                _ => f64::NAN,
+                            }
+                        }
+                        _ => f64::NAN,
+                    },
+                };
+                (self.solver_value(&row.left), right)
+            })
+            .collect();
+        Ok(Point {
+            objective: self.solver_value(&compiled.objective),
+            rows,
+        })
+    }
+
+    /// Returns the linear program if the model is linear around `start`
+    fn solver_linearize(
+        &mut self,
+        compiled: &Compiled,
+        start: &[f64],
+    ) -> Result<Option<LinearProgram>, String> {
+        // The objective followed by `left - right` for each row
+        let functions = |point: &Point| -> Vec<f64> {
+            std::iter::once(point.objective)
+                .chain(point.rows.iter().map(|(left, right)| left - right))
+                .collect()
+        };
+        let n = start.len();
+        let base = functions(&self.solver_evaluate(compiled, start)?);
+        let mut coefficients = vec![vec![0.0; n]; base.len()];
+        for j in 0..n {
+            let mut x = start.to_vec();
+            x[j] += 1.0;
+            let values = functions(&self.solver_evaluate(compiled, &x)?);
+            for (k, value) in values.iter().enumerate() {
+                coefficients[k][j] = value - base[k];
+            }
+        }
+        // The model must match the linear approximation at other points
+        let steps = [
+            (0..n)
+                .map(|j| 1.5 + 0.5 * (j % 5) as f64)
+                .collect::<Vec<f64>>(),
+            (0..n).map(|j| -0.75 - 0.25 * (j % 3) as f64).collect(),
+        ];
+        for step in &steps {
+            let x: Vec<f64> = start.iter().zip(step).map(|(x, d)| x + d).collect();
+            let values = functions(&self.solver_evaluate(compiled, &x)?);
+            for (k, value) in values.iter().enumerate() {
+                let change: Vec<f64> = coefficients[k]
+                    .iter()
+                    .zip(step)
+                    .map(|(a, d)| a * d)
+                    .collect();
+                let predicted = base[k] + change.iter().sum::<f64>();
+                let scale =
+                    1.0 + value.abs() + base[k].abs() + change.iter().map(|c| c.abs()).sum::<f64>();
+                let error = (value - predicted).abs();
+                if error.is_nan() || error > 1e-9 * scale {
+                    return Ok(None);
+                }
+            }
+        }
+
+        // f(x) = base + a·(x - start) = a·x + constant
+        let constant = |k: usize| -> f64 {
+            base[k]
+                - coefficients[k]
+                    .iter()
+                    .zip(start)
+                    .map(|(a, x)| a * x)
+                    .sum::<f64>()
+        };
+        let mut constraints: Vec<LinearConstraint> = compiled
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| LinearConstraint {
+                coefficients: coefficients[i + 1].clone(),
+                relation: row.relation,
+                rhs: -constant(i + 1),
+            })
+            .collect();
+        let costs = match compiled.goal {
+            SolverGoal::Minimize => coefficients[0].clone(),
+            SolverGoal::Maximize => coefficients[0].iter().map(|a| -a).collect(),
+            SolverGoal::Value { value } => {
+                constraints.push(LinearConstraint {
+                    coefficients: coefficients[0].clone(),
+                    relation: Relation::Equal,
+                    rhs: value - constant(0),
+                });
+                vec![0.0; n]
+            }
+        };
+        let free = compiled
+            .bounds
+            .iter()
+            .map(|(lower, _)| *lower == f64::NEG_INFINITY)
+            .collect();
+        Ok(Some(LinearProgram {
+            costs,
+            constraints,
+            free,
+            integers: compiled.integers.clone(),
+        }))
+    }
+
+    /// Augmented Lagrangian method. Each subproblem is minimized with Nelder-Mead.
+    fn solver_nonlinear(
+        &mut self,
+        compiled: &Compiled,
+        start: &[f64],
+        options: &SolverOptions,
+    ) -> Result<(SolverStatus, Vec<f64>, u32), String> {
+        let project = |x: &[f64]| -> Vec<f64> {
+            x.iter()
+                .zip(&compiled.bounds)
+                .map(|(value, (lower, upper))| value.clamp(*lower, *upper))
+                .collect()
+        };
+        let mut steps = 0;
+        let (status, x) =
+            self.solver_lagrangian(compiled, &project(start), &[], options, &mut steps)?;
+        if compiled.integers.is_empty() || status == SolverStatus::IterationLimit {
+            return Ok((status, x, steps));
+        }
+        // Rounds the integer cells and solves again for the others
+        let mut x = x;
+        for &j in &compiled.integers {
+            x[j] = x[j]
+                .round()
+                .clamp(compiled.bounds[j].0, compiled.bounds[j].1);
+        }
+        let (status, x) =
+            self.solver_lagrangian(compiled, &x, &compiled.integers, options, &mut steps)?;
+        Ok((status, x, steps))
+    }
+
+    /// Minimizes the augmented Lagrangian over the cells not in `fixed`, starting at `x`
+    fn solver_lagrangian(
+        &mut self,
+        compiled: &Compiled,
+        x: &[f64],
+        fixed: &[usize],
+        options: &SolverOptions,
+        steps: &mut u32,
+    ) -> Result<(SolverStatus, Vec<f64>), String> {
+        let free: Vec<usize> = (0..x.len()).filter(|j| !fixed.contains(j)).collect();
+        let point_at = |x: &[f64], y: &[f64]| -> Vec<f64> {
+            let mut point = x.to_vec();
+            for (&j, value) in free.iter().zip(y) {
+                let (lower, upper) = compiled.bounds[j];
+                point[j] = value.clamp(lower, upper);
+            }
+            point
+        };
+        // The constraints are `c(x) <= 0` or `c(x) = 0`
+        let constraints = |point: &Point| -> Vec<(f64, bool)> {
+            let mut values: Vec<(f64, bool)> = compiled
+                .rows
+                .iter()
+                .zip(&point.rows)
+                .map(|(row, (left, right))| match row.relation {
+                    Relation::LessOrEqual => (left - right, false),
+                    Relation::Equal => (left - right, true),
+                    Relation::GreaterOrEqual => (right - left, false),
+                })
+                .collect();
+            if let SolverGoal::Value { value } = compiled.goal {
+                values.push((point.objective - value, true));
+            }
+            values
+        };
+        let objective = |point: &Point| -> f64 {
+            match compiled.goal {
+                SolverGoal::Maximize => -point.objective,
+                SolverGoal::Minimize => point.objective,
+                SolverGoal::Value { .. } => 0.0,
+            }
+        };
+
+        let count =
+            compiled.rows.len() + usize::from(matches!(compiled.goal, SolverGoal::Value { .. }));
+        let mut multipliers = vec![0.0; count];
+        let mut penalty = 10.0;
+        let mut last_violation = f64::INFINITY;
+        let mut y: Vec<f64> = free.iter().map(|&j| x[j]).collect();
+        let mut sizes: Vec<f64> = y
+            .iter()
+            .map(|value| {
+                if *value == 0.0 {
+                    0.1
+                } else {
+                    0.05 * value.abs()
+                }
+            })
+            .collect();
+        while !y.is_empty() {
+            let budget = options.max_iterations.saturating_sub(*steps);
+            let minimum = nelder_mead::minimize(
+                |y| {
+                    let point = self.solver_evaluate(compiled, &point_at(x, y))?;
+                    let mut value = objective(&point);
+                    for ((c, equality), multiplier) in constraints(&point).iter().zip(&multipliers)
+                    {
+                        value += if *equality {
+                            multiplier * c + 0.5 * penalty * c * c
+                        } else {
+                            let shifted = (multiplier / penalty + c).max(0.0);
+                            0.5 * penalty * shifted * shifted
+                                - multiplier * multiplier / (2.0 * penalty)
+                        };
+                    }
+                    Ok(value)
+                },
+                &y,
+                &sizes,
+                budget,
+                options.precision * options.precision,
+            )?;
+            *steps += minimum.steps;
+            let moved = minimum
+                .x
+                .iter()
+                .zip(&y)
+                .map(|(a, b)| (a - b).abs() / (1.0 + b.abs()))
+                .fold(0.0, f64::max);
+            y = minimum.x;
+            // The next subproblem starts close to this solution
+            sizes.iter_mut().for_each(|size| *size *= 0.5);
+
+            let point = self.solver_evaluate(compiled, &point_at(x, &y))?;
+            if compiled.is_solution(&point, options.precision) && moved <= options.precision.sqrt()
+            {
+                break;
+            }
+            if *steps >= options.max_iterations {
+                return Ok((SolverStatus::IterationLimit, point_at(x, &y)));
+            }
+            let values = constraints(&point);
+            let violation = values
+                .iter()
+                .map(|(c, equality)| if *equality { c.abs() } else { c.max(0.0) })
+                .fold(0.0, f64::max);
+            for ((c, equality), multiplier) in values.iter().zip(multipliers.iter_mut()) {
+                *multiplier = if *equality {
+                    *multiplier + penalty * c
+                } else {
+                    (*multiplier + penalty * c).max(0.0)
+                };
+            }
+            if violation > 0.25 * last_violation {
+                if penalty > 1e12 {
+                    // The constraints can not be satisfied
+                    break;
+                }
+                penalty *= 10.0;
+            }
+            last_violation = violation;
+        }
+        let point = point_at(x, &y);
+        let evaluated = self.solver_evaluate(compiled, &point)?;
+        let status = if compiled.is_solution(&evaluated, options.precision) {
+            SolverStatus::Converged
+        } else {
+            SolverStatus::Infeasible
+        };
+        Ok((status, point))
+    }
+}
+
+impl Compiled {
+    /// All the rows are satisfied within `precision`, relative to the size of the right side,
+    /// and the objective has the target value if there is one
+    fn is_solution(&self, point: &Point, precision: f64) -> bool {
+        let objective = match self.goal {
+            SolverGoal::Value { value } => {
+                (point.objective - value).abs() <= precision * (1.0 + value.abs())
+            }
+            SolverGoal::Maximize | SolverGoal::Minimize => point.objective.is_finite(),
+        };
+        objective
+            && self
+                .rows
+                .iter()
+                .zip(&point.rows)
+                .all(|(row, (left, right))| {
+                    row_violation(row.relation, *left, *right) <= precision * (1.0 + right.abs())
+                })
+    }
+}
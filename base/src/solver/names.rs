@@ -0,0 +1,273 @@
+//! Excel keeps the Solver setup of each sheet in hidden defined names local to the sheet:
+//!
+//! * `solver_opt`: the objective cell, e.g. `Sheet1!$D$10`
+//! * `solver_typ`: 1 maximize, 2 minimize, 3 value of `solver_val`
+//! * `solver_adj`: the changing cells, e.g. `Sheet1!$A$1:$A$5,Sheet1!$C$1`
+//! * `solver_num`: the number of constraints
+//! * `solver_lhs{n}`, `solver_rel{n}` and `solver_rhs{n}`: each constraint. The relation is
+//!   1 `<=`, 2 `=`, 3 `>=`, 4 integer, 5 binary and 6 all different (not supported)
+//! * `solver_neg`: 1 if the changing cells are non negative, 2 otherwise
+//! * `solver_eng`: 1 GRG nonlinear, 2 simplex LP, 3 evolutionary
+//! * `solver_itr` and `solver_pre`: the maximum number of iterations and the precision
+
+use std::collections::HashMap;
+
+use crate::{
+    expressions::{
+        types::{Area, CellReferenceIndex},
+        utils::{number_to_column, quote_name},
+    },
+    model::Model,
+    types::DefinedName,
+    utils::ParsedReference,
+};
+
+use super::{
+    ConstraintOperator, SolverConstraint, SolverGoal, SolverMethod, SolverOptions, SolverProblem,
+};
+
+const PREFIX: &str = "solver_";
+
+/// Splits a list of references like `Sheet1!$A$1:$A$5,'A, B'!$C$1`
+fn split_references(formula: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quoted = false;
+    let mut start = 0;
+    for (index, character) in formula.char_indices() {
+        match character {
+            '\'' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(&formula[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&formula[start..]);
+    parts
+}
+
+impl Model<'_> {
+    /// Reads the Solver setup of a sheet from the `solver_*` defined names of the sheet, as
+    /// saved by Excel. Returns `None` if there is no objective cell.
+    ///
+    /// Excel's GRG nonlinear engine is read as [SolverMethod::Automatic] and the evolutionary
+    /// engine as [SolverMethod::Nonlinear].
+    pub fn get_solver_problem(&self, sheet: u32) -> Result<Option<SolverProblem>, String> {
+        let sheet_id = self.workbook.worksheet(sheet)?.sheet_id;
+        let names: HashMap<String, &str> = self
+            .workbook
+            .defined_names
+            .iter()
+            .filter(|name| name.sheet_id == Some(sheet_id))
+            .filter_map(|name| {
+                let lower = name.name.to_lowercase();
+                let formula = name.formula.trim();
+                let formula = formula.strip_prefix('=').unwrap_or(formula);
+                lower.starts_with(PREFIX).then_some((lower, formula))
+            })
+            .collect();
+        let Some(objective) = names.get("solver_opt") else {
+            return Ok(None);
+        };
+        let number = |key: &str| -> Result<Option<f64>, String> {
+            match names.get(key) {
+                Some(value) => value
+                    .trim_matches('"')
+                    .parse::<f64>()
+                    .map(Some)
+                    .map_err(|_| format!("Invalid value of {key}: '{value}'")),
+                None => Ok(None),
+            }
+        };
+        let area = |key: &str, formula: &str| -> Result<Area, String> {
+            match ParsedReference::parse_reference_formula(
+                Some(sheet),
+                formula.trim(),
+                self.locale,
+                |name| self.get_sheet_index_by_name(name),
+            ) {
+                Ok(ParsedReference::CellReference(cell)) => Ok(Area {
+                    sheet: cell.sheet,
+                    row: cell.row,
+                    column: cell.column,
+                    width: 1,
+                    height: 1,
+                }),
+                Ok(ParsedReference::Range(left, right)) => Ok(Area {
+                    sheet: left.sheet,
+                    row: left.row.min(right.row),
+                    column: left.column.min(right.column),
+                    width: (right.column - left.column).abs() + 1,
+                    height: (right.row - left.row).abs() + 1,
+                }),
+                Err(_) => Err(format!("Invalid reference in {key}: '{formula}'")),
+            }
+        };
+
+        let objective = area("solver_opt", objective)?;
+        if (objective.width, objective.height) != (1, 1) {
+            return Err("The objective must be a single cell".to_string());
+        }
+        let goal = match number("solver_typ")? {
+            Some(2.0) => SolverGoal::Minimize,
+            Some(3.0) => SolverGoal::Value {
+                value: number("solver_val")?.unwrap_or(0.0),
+            },
+            _ => SolverGoal::Maximize,
+        };
+        let mut variables = Vec::new();
+        if let Some(formula) = names.get("solver_adj") {
+            for reference in split_references(formula) {
+                variables.push(area("solver_adj", reference)?);
+            }
+        }
+        let mut constraints = Vec::new();
+        let count = number("solver_num")?.unwrap_or(0.0) as usize;
+        for index in 1..=count {
+            let key = format!("solver_lhs{index}");
+            let Some(left) = names.get(&key) else {
+                return Err(format!("Missing {key}"));
+            };
+            let left = area(&key, left)?;
+            let operator = match number(&format!("solver_rel{index}"))? {
+                Some(1.0) => ConstraintOperator::LessOrEqual,
+                Some(2.0) => ConstraintOperator::Equal,
+                Some(3.0) => ConstraintOperator::GreaterOrEqual,
+                Some(4.0) => ConstraintOperator::Integer,
+                Some(5.0) => ConstraintOperator::Binary,
+                _ => return Err(format!("Constraint {index}: unsupported relation")),
+            };
+            let right = match operator {
+                ConstraintOperator::Integer | ConstraintOperator::Binary => String::new(),
+                _ => names
+                    .get(&format!("solver_rhs{index}"))
+                    .map(|formula| formula.to_string())
+                    .ok_or_else(|| format!("Missing solver_rhs{index}"))?,
+            };
+            constraints.push(SolverConstraint {
+                left,
+                operator,
+                right,
+            });
+        }
+        let defaults = SolverOptions::default();
+        let options = SolverOptions {
+            non_negative: number("solver_neg")? != Some(2.0),
+            method: match number("solver_eng")? {
+                Some(2.0) => SolverMethod::Simplex,
+                Some(3.0) => SolverMethod::Nonlinear,
+                _ => SolverMethod::Automatic,
+            },
+            max_iterations: number("solver_itr")?
+                .map_or(defaults.max_iterations, |value| value as u32),
+            precision: number("solver_pre")?.unwrap_or(defaults.precision),
+        };
+        Ok(Some(SolverProblem {
+            objective: CellReferenceIndex {
+                sheet: objective.sheet,
+                row: objective.row,
+                column: objective.column,
+            },
+            goal,
+            variables,
+            constraints,
+            options,
+        }))
+    }
+
+    /// Saves the Solver setup of a sheet in `solver_*` defined names local to the sheet,
+    /// replacing the existing ones. Excel reads them back, they are saved hidden in xlsx files.
+    pub fn set_solver_problem(
+        &mut self,
+        sheet: u32,
+        problem: &SolverProblem,
+    ) -> Result<(), String> {
+        let sheet_id = self.workbook.worksheet(sheet)?.sheet_id;
+        let reference = |area: &Area| -> Result<String, String> {
+            let sheet_name = quote_name(&self.workbook.worksheet(area.sheet)?.get_name());
+            let cell = |row: i32, column: i32| -> Result<String, String> {
+                let column =
+                    number_to_column(column).ok_or_else(|| "Invalid column".to_string())?;
+                Ok(format!("${column}${row}"))
+            };
+            let first = cell(area.row, area.column)?;
+            if area.width == 1 && area.height == 1 {
+                Ok(format!("{sheet_name}!{first}"))
+            } else {
+                let last = cell(area.row + area.height - 1, area.column + area.width - 1)?;
+                Ok(format!("{sheet_name}!{first}:{last}"))
+            }
+        };
+
+        let objective = &problem.objective;
+        let mut names = vec![(
+            "solver_opt".to_string(),
+            reference(&Area {
+                sheet: objective.sheet,
+                row: objective.row,
+                column: objective.column,
+                width: 1,
+                height: 1,
+            })?,
+        )];
+        let (goal_type, value) = match problem.goal {
+            SolverGoal::Maximize => (1, 0.0),
+            SolverGoal::Minimize => (2, 0.0),
+            SolverGoal::Value { value } => (3, value),
+        };
+        names.push(("solver_typ".to_string(), goal_type.to_string()));
+        names.push(("solver_val".to_string(), value.to_string()));
+        let variables = problem
+            .variables
+            .iter()
+            .map(reference)
+            .collect::<Result<Vec<String>, String>>()?;
+        names.push(("solver_adj".to_string(), variables.join(",")));
+        names.push((
+            "solver_num".to_string(),
+            problem.constraints.len().to_string(),
+        ));
+        for (index, constraint) in problem.constraints.iter().enumerate() {
+            let index = index + 1;
+            let (relation, right) = match constraint.operator {
+                ConstraintOperator::LessOrEqual => (1, constraint.right.clone()),
+                ConstraintOperator::Equal => (2, constraint.right.clone()),
+                ConstraintOperator::GreaterOrEqual => (3, constraint.right.clone()),
+                ConstraintOperator::Integer => (4, "integer".to_string()),
+                ConstraintOperator::Binary => (5, "binary".to_string()),
+            };
+            let right = right.trim();
+            let right = right.strip_prefix('=').unwrap_or(right).to_string();
+            names.push((format!("solver_lhs{index}"), reference(&constraint.left)?));
+            names.push((format!("solver_rel{index}"), relation.to_string()));
+            names.push((format!("solver_rhs{index}"), right));
+        }
+        let options = &problem.options;
+        let non_negative = if options.non_negative { 1 } else { 2 };
+        let engine = match options.method {
+            SolverMethod::Automatic => 1,
+            SolverMethod::Simplex => 2,
+            SolverMethod::Nonlinear => 3,
+        };
+        names.push(("solver_neg".to_string(), non_negative.to_string()));
+        names.push(("solver_eng".to_string(), engine.to_string()));
+        names.push(("solver_itr".to_string(), options.max_iterations.to_string()));
+        names.push(("solver_pre".to_string(), options.precision.to_string()));
+        names.push(("solver_ver".to_string(), "3".to_string()));
+
+        self.workbook.defined_names.retain(|name| {
+            name.sheet_id != Some(sheet_id) || !name.name.to_lowercase().starts_with(PREFIX)
+        });
+        self.workbook
+            .defined_names
+            .extend(names.into_iter().map(|(name, formula)| DefinedName {
+                name,
+                formula,
+                sheet_id: Some(sheet_id),
+                hidden: true,
+            }));
+        self.reset_parsed_structures();
+        Ok(())
+    }
+}
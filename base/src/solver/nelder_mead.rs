@@ -0,0 +1,119 @@
+//! Nelder-Mead downhill simplex. It only needs the values of the function, not its derivatives.
+
+/// Result of [minimize]
+pub(crate) struct Minimum {
+    pub x: Vec<f64>,
+    /// Number of steps (reflections, expansions, contractions or shrinks)
+    pub steps: u32,
+}
+
+/// Minimizes `f` starting at `start`. The initial simplex has sides of length `sizes`.
+///
+/// Stops after `max_steps` or when the values at the vertices differ less than `tolerance`
+/// (relative) and the simplex is smaller than `sqrt(tolerance)` (relative).
+/// Values that are not finite are worse than any other value.
+pub(crate) fn minimize<F>(
+    mut f: F,
+    start: &[f64],
+    sizes: &[f64],
+    max_steps: u32,
+    tolerance: f64,
+) -> Result<Minimum, String>
+where
+    F: FnMut(&[f64]) -> Result<f64, String>,
+{
+    let mut f = |x: &[f64]| -> Result<f64, String> {
+        let value = f(x)?;
+        Ok(if value.is_nan() { f64::INFINITY } else { value })
+    };
+    let n = start.len();
+    let mut vertices = vec![(start.to_vec(), f(start)?)];
+    for (j, size) in sizes.iter().enumerate() {
+        let mut x = start.to_vec();
+        x[j] += size;
+        let value = f(&x)?;
+        vertices.push((x, value));
+    }
+    let x_tolerance = tolerance.sqrt();
+    let mut steps = 0;
+    while steps < max_steps {
+        vertices.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let (best, best_value) = (&vertices[0].0, vertices[0].1);
+        let worst_value = vertices[n].1;
+        let spread = if best_value.is_finite() {
+            (worst_value - best_value).abs()
+        } else {
+            f64::INFINITY
+        };
+        let size = vertices[1..]
+            .iter()
+            .flat_map(|(x, _)| {
+                x.iter()
+                    .zip(best)
+                    .map(|(a, b)| (a - b).abs() / (1.0 + b.abs()))
+            })
+            .fold(0.0, f64::max);
+        if spread <= tolerance * (1.0 + best_value.abs()) && size <= x_tolerance {
+            break;
+        }
+        steps += 1;
+
+        let mut centroid = vec![0.0; n];
+        for (x, _) in &vertices[..n] {
+            for (c, value) in centroid.iter_mut().zip(x) {
+                *c += value / n as f64;
+            }
+        }
+        let worst = vertices[n].0.clone();
+        let towards = |t: f64| -> Vec<f64> {
+            centroid
+                .iter()
+                .zip(&worst)
+                .map(|(c, w)| c + t * (c - w))
+                .collect()
+        };
+
+        let reflected = towards(1.0);
+        let reflected_value = f(&reflected)?;
+        if reflected_value < best_value {
+            let expanded = towards(2.0);
+            let expanded_value = f(&expanded)?;
+            vertices[n] = if expanded_value < reflected_value {
+                (expanded, expanded_value)
+            } else {
+                (reflected, reflected_value)
+            };
+            continue;
+        }
+        if reflected_value < vertices[n - 1].1 {
+            vertices[n] = (reflected, reflected_value);
+            continue;
+        }
+        // Contraction, outside if the reflected point is better than the worst one
+        let (contracted, limit) = if reflected_value < worst_value {
+            (towards(0.5), reflected_value)
+        } else {
+            (towards(-0.5), worst_value)
+        };
+        let contracted_value = f(&contracted)?;
+        if contracted_value < limit {
+            vertices[n] = (contracted, contracted_value);
+            continue;
+        }
+        // Shrink towards the best vertex
+        let best = vertices[0].0.clone();
+        for vertex in vertices.iter_mut().skip(1) {
+            let x: Vec<f64> = vertex
+                .0
+                .iter()
+                .zip(&best)
+                .map(|(v, b)| b + 0.5 * (v - b))
+                .collect();
+            let value = f(&x)?;
+            *vertex = (x, value);
+        }
+    }
+    vertices.sort_by(|a, b| a.1.total_cmp(&b.1));
+    let (x, _) = vertices.swap_remove(0);
+    Ok(Minimum { x, steps })
+}
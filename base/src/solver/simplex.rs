@@ -0,0 +1,369 @@
+//! Two phase simplex method with branch and bound for the integer variables.
+//!
+//! Problems are small (a few hundred variables at most) so a dense tableau and Bland's rule
+//! are good enough.
+
+use super::SolverStatus;
+
+const EPS: f64 = 1e-9;
+
+/// Maximum number of pivots of a single linear program
+const MAX_PIVOTS: usize = 50_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Relation {
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+}
+
+/// `coefficients·x relation rhs`
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LinearConstraint {
+    pub coefficients: Vec<f64>,
+    pub relation: Relation,
+    pub rhs: f64,
+}
+
+/// Minimize `costs·x` subject to the constraints
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LinearProgram {
+    pub costs: Vec<f64>,
+    pub constraints: Vec<LinearConstraint>,
+    /// Variables that can be negative, all others are non negative
+    pub free: Vec<bool>,
+    /// Variables that must take integer values
+    pub integers: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct LinearSolution {
+    pub status: SolverStatus,
+    pub values: Vec<f64>,
+    pub objective: f64,
+    /// Number of linear programs solved
+    pub subproblems: u32,
+}
+
+enum Relaxation {
+    Optimal(Vec<f64>, f64),
+    Infeasible,
+    Unbounded,
+    Stalled,
+}
+
+enum Outcome {
+    Optimal,
+    Unbounded,
+    Stalled,
+}
+
+struct Tableau {
+    /// Each row ends with the right hand side
+    rows: Vec<Vec<f64>>,
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    fn pivot(&mut self, objectives: &mut [Vec<f64>], row: usize, column: usize) {
+        let value = self.rows[row][column];
+        for entry in self.rows[row].iter_mut() {
+            *entry /= value;
+        }
+        let pivot_row = self.rows[row].clone();
+        let eliminate = |other: &mut Vec<f64>| {
+            let factor = other[column];
+            if factor != 0.0 {
+                for (entry, pivot) in other.iter_mut().zip(&pivot_row) {
+                    *entry -= factor * pivot;
+                }
+            }
+        };
+        for (index, other) in self.rows.iter_mut().enumerate() {
+            if index != row {
+                eliminate(other);
+            }
+        }
+        for objective in objectives.iter_mut() {
+            eliminate(objective);
+        }
+        self.basis[row] = column;
+    }
+
+    /// Minimizes `objectives[0]`, the other objectives are kept in canonical form.
+    /// Only the columns in `allowed` can enter the basis.
+    fn minimize(&mut self, objectives: &mut [Vec<f64>], allowed: &[bool]) -> Outcome {
+        let width = allowed.len();
+        for _ in 0..MAX_PIVOTS {
+            // Bland's rule: the first column that improves the objective and the first row in
+            // case of a tie. It never cycles.
+            let Some(column) = (0..width).find(|&c| allowed[c] && objectives[0][c] < -EPS) else {
+                return Outcome::Optimal;
+            };
+            let mut leaving: Option<(usize, f64)> = None;
+            for (index, row) in self.rows.iter().enumerate() {
+                if row[column] <= EPS {
+                    continue;
+                }
+                let ratio = row[width] / row[column];
+                leaving = match leaving {
+                    Some((best, best_ratio))
+                        if ratio > best_ratio + EPS
+                            || (ratio >= best_ratio - EPS
+                                && self.basis[best] < self.basis[index]) =>
+                    {
+                        Some((best, best_ratio))
+                    }
+                    _ => Some((index, ratio)),
+                };
+            }
+            let Some((row, _)) = leaving else {
+                return Outcome::Unbounded;
+            };
+            self.pivot(objectives, row, column);
+        }
+        Outcome::Stalled
+    }
+}
+
+impl LinearProgram {
+    /// Solves the problem, with at most `max_subproblems` linear programs in the branch and bound
+    pub(crate) fn solve(&self, max_subproblems: u32) -> LinearSolution {
+        let mut best: Option<(Vec<f64>, f64)> = None;
+        let mut subproblems = 0;
+        let mut status = SolverStatus::Optimal;
+        // Depth first, each node is the list of bounds added to the problem
+        let mut stack = vec![Vec::<LinearConstraint>::new()];
+        while let Some(bounds) = stack.pop() {
+            if subproblems >= max_subproblems {
+                status = SolverStatus::IterationLimit;
+                break;
+            }
+            subproblems += 1;
+            let (values, objective) = match self.solve_relaxation(&bounds) {
+                Relaxation::Optimal(values, objective) => (values, objective),
+                Relaxation::Infeasible => continue,
+                Relaxation::Unbounded => {
+                    return LinearSolution {
+                        status: SolverStatus::Unbounded,
+                        values: Vec::new(),
+                        objective: f64::NAN,
+                        subproblems,
+                    }
+                }
+                Relaxation::Stalled => {
+                    status = SolverStatus::IterationLimit;
+                    break;
+                }
+            };
+            if let Some((_, best_objective)) = &best {
+                if objective >= best_objective - EPS * (1.0 + best_objective.abs()) {
+                    continue;
+                }
+            }
+            let fractional = self
+                .integers
+                .iter()
+                .copied()
+                .find(|&j| (values[j] - values[j].round()).abs() > 1e-6);
+            match fractional {
+                None => {
+                    let mut values = values;
+                    for &j in &self.integers {
+                        values[j] = values[j].round();
+                    }
+                    best = Some((values, objective));
+                }
+                Some(j) => {
+                    let value = values[j];
+                    let bound = |relation, rhs| {
+                        let mut coefficients = vec![0.0; self.costs.len()];
+                        coefficients[j] = 1.0;
+                        let mut bounds = bounds.clone();
+                        bounds.push(LinearConstraint {
+                            coefficients,
+                            relation,
+                            rhs,
+                        });
+                        bounds
+                    };
+                    let down = bound(Relation::LessOrEqual, value.floor());
+                    let up = bound(Relation::GreaterOrEqual, value.ceil());
+                    // The closest side is explored first
+                    if value - value.floor() < 0.5 {
+                        stack.push(up);
+                        stack.push(down);
+                    } else {
+                        stack.push(down);
+                        stack.push(up);
+                    }
+                }
+            }
+        }
+        match best {
+            Some((values, objective)) => LinearSolution {
+                status,
+                values,
+                objective,
+                subproblems,
+            },
+            None => LinearSolution {
+                status: if status == SolverStatus::Optimal {
+                    SolverStatus::Infeasible
+                } else {
+                    status
+                },
+                values: Vec::new(),
+                objective: f64::NAN,
+                subproblems,
+            },
+        }
+    }
+
+    /// Solves the problem with the extra `bounds` and without the integer conditions
+    fn solve_relaxation(&self, bounds: &[LinearConstraint]) -> Relaxation {
+        // Free variables are split in a positive and a negative part
+        let mut columns = Vec::new();
+        for (variable, &free) in self.free.iter().enumerate() {
+            columns.push((variable, 1.0));
+            if free {
+                columns.push((variable, -1.0));
+            }
+        }
+        let structural = columns.len();
+        let constraints: Vec<&LinearConstraint> = self.constraints.iter().chain(bounds).collect();
+
+        // Rows with a non negative right hand side
+        let mut rows = Vec::new();
+        for constraint in &constraints {
+            let mut row: Vec<f64> = columns
+                .iter()
+                .map(|&(variable, sign)| sign * constraint.coefficients[variable])
+                .collect();
+            let (mut relation, mut rhs) = (constraint.relation, constraint.rhs);
+            if rhs < 0.0 {
+                row.iter_mut().for_each(|entry| *entry = -*entry);
+                rhs = -rhs;
+                relation = match relation {
+                    Relation::LessOrEqual => Relation::GreaterOrEqual,
+                    Relation::Equal => Relation::Equal,
+                    Relation::GreaterOrEqual => Relation::LessOrEqual,
+                };
+            }
+            rows.push((row, relation, rhs));
+        }
+
+        let slacks = rows
+            .iter()
+            .filter(|(_, relation, _)| *relation != Relation::Equal)
+            .count();
+        let artificials = rows
+            .iter()
+            .filter(|(_, relation, _)| *relation != Relation::LessOrEqual)
+            .count();
+        let width = structural + slacks + artificials;
+        let first_artificial = structural + slacks;
+
+        let mut tableau = Tableau {
+            rows: Vec::new(),
+            basis: Vec::new(),
+        };
+        let (mut slack, mut artificial) = (structural, first_artificial);
+        for (row, relation, rhs) in rows {
+            let mut entries = row;
+            entries.resize(width + 1, 0.0);
+            entries[width] = rhs;
+            match relation {
+                Relation::LessOrEqual => {
+                    entries[slack] = 1.0;
+                    tableau.basis.push(slack);
+                    slack += 1;
+                }
+                Relation::GreaterOrEqual => {
+                    entries[slack] = -1.0;
+                    slack += 1;
+                    entries[artificial] = 1.0;
+                    tableau.basis.push(artificial);
+                    artificial += 1;
+                }
+                Relation::Equal => {
+                    entries[artificial] = 1.0;
+                    tableau.basis.push(artificial);
+                    artificial += 1;
+                }
+            }
+            tableau.rows.push(entries);
+        }
+
+        let mut costs = vec![0.0; width + 1];
+        for (column, &(variable, sign)) in columns.iter().enumerate() {
+            costs[column] = sign * self.costs[variable];
+        }
+
+        // Phase 1: minimize the sum of the artificial variables
+        let mut phase1 = vec![0.0; width + 1];
+        for (row, &basic) in tableau.rows.iter().zip(&tableau.basis) {
+            if basic >= first_artificial {
+                for (entry, value) in phase1.iter_mut().zip(row) {
+                    *entry -= value;
+                }
+            }
+        }
+        for entry in &mut phase1[first_artificial..width] {
+            *entry = 0.0;
+        }
+        let mut objectives = vec![phase1, costs];
+        match tableau.minimize(&mut objectives, &vec![true; width]) {
+            Outcome::Optimal => {}
+            // The sum of the artificial variables is bounded below by zero
+            Outcome::Unbounded | Outcome::Stalled => return Relaxation::Stalled,
+        }
+        let scale = 1.0
+            + tableau
+                .rows
+                .iter()
+                .map(|row| row[width].abs())
+                .fold(0.0, f64::max);
+        if -objectives[0][width] > 1e-9 * scale {
+            return Relaxation::Infeasible;
+        }
+        // Artificial variables left in the basis are zero, they are replaced when possible
+        for row in 0..tableau.rows.len() {
+            if tableau.basis[row] >= first_artificial {
+                if let Some(column) =
+                    (0..first_artificial).find(|&c| tableau.rows[row][c].abs() > EPS)
+                {
+                    tableau.pivot(&mut objectives, row, column);
+                }
+            }
+        }
+
+        // Phase 2
+        let mut objectives = vec![objectives.remove(1)];
+        let allowed: Vec<bool> = (0..width).map(|c| c < first_artificial).collect();
+        match tableau.minimize(&mut objectives, &allowed) {
+            Outcome::Optimal => {}
+            Outcome::Unbounded => return Relaxation::Unbounded,
+            Outcome::Stalled => return Relaxation::Stalled,
+        }
+
+        let mut values = vec![0.0; self.costs.len()];
+        for (row, &basic) in tableau.rows.iter().zip(&tableau.basis) {
+            if basic < structural {
+                let (variable, sign) = columns[basic];
+                values[variable] += sign * row[width];
+            }
+        }
+        for value in values.iter_mut() {
+            if value.abs() < EPS {
+                *value = 0.0;
+            }
+        }
+        let objective = values
+            .iter()
+            .zip(&self.costs)
+            .map(|(value, cost)| value * cost)
+            .sum();
+        Relaxation::Optimal(values, objective)
+    }
+}
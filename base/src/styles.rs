@@ -88,6 +88,7 @@ impl Styles {
             apply_fill: false,
             quote_prefix: style.quote_prefix,
             alignment: style.alignment.clone(),
+            protection: style.protection.clone(),
         });
         self.cell_xfs.len() as i32 - 1
     }
@@ -102,6 +103,7 @@ impl Styles {
             if style
                 == &(Style {
                     alignment: cell_xf.alignment.clone(),
+                    protection: cell_xf.protection.clone(),
                     num_fmt: get_num_fmt(num_fmt_id, &self.num_fmts),
                     fill: self.fills[fill_id].clone(),
                     font: self.fonts[font_id].clone(),
@@ -253,9 +255,11 @@ impl Styles {
         let num_fmt_id = cell_xf.num_fmt_id;
         let quote_prefix = cell_xf.quote_prefix;
         let alignment = cell_xf.alignment.clone();
+        let protection = cell_xf.protection.clone();
 
         Ok(Style {
             alignment,
+            protection,
             num_fmt: get_num_fmt(num_fmt_id, &self.num_fmts),
             fill: self.fills[fill_id].clone(),
             font: self.fonts[font_id].clone(),
@@ -276,6 +276,7 @@ fn test_color_scale_vs_dxf_fill_priority() {
     let blue_fill = Dxf {
         fill: Some(Fill {
             color: Color::Rgb("#0000FF".to_string()),
+            ..Default::default()
         }),
         ..Dxf::default()
     };
@@ -11,6 +11,7 @@ fn blue_fill() -> Dxf {
     Dxf {
         fill: Some(Fill {
             color: Color::Rgb("#0000FF".to_string()),
+            ..Default::default()
         }),
         font: None,
         border: None,
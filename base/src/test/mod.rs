@@ -9,6 +9,7 @@ mod test_circular_references;
 mod test_column_width;
 mod test_criteria;
 mod test_database;
+mod test_date1904;
 mod test_date_and_time;
 mod test_datedif_leap_month_end;
 mod test_days360_month_end;
@@ -41,6 +42,7 @@ mod test_fn_rept;
 mod test_fn_sequence;
 mod test_fn_sum;
 mod test_fn_sumifs;
+mod test_fn_table;
 mod test_fn_time;
 mod test_frozen_rows_columns;
 mod test_gcd_lcm;
@@ -87,11 +89,15 @@ mod lookup_and_reference;
 mod test_arrays;
 mod test_cell_info_n_sheets;
 mod test_combin_combina;
+mod test_custom_functions;
 mod test_datetime_format;
+mod test_error_checking;
 mod test_escape_quotes;
+mod test_evaluate_formula;
 mod test_even_odd;
 mod test_exp_sign;
 mod test_extend;
+mod test_external_links;
 mod test_floor;
 mod test_fn_bycol_byrow;
 mod test_fn_datevalue_timevalue;
@@ -108,8 +114,10 @@ mod test_fn_tocol_torow;
 mod test_fn_transpose;
 mod test_fn_type;
 mod test_frozen_rows_and_columns;
+mod test_function_catalogue;
 mod test_geomean;
 mod test_get_cell_content;
+mod test_goal_seek;
 mod test_implicit_intersection;
 mod test_issue_155;
 mod test_issue_483;
@@ -122,9 +130,12 @@ mod test_mod_quotient;
 mod test_networkdays;
 mod test_now;
 mod test_percentage;
+mod test_precedents;
 mod test_range_evaluation;
+mod test_scenarios;
 mod test_set_functions_error_handling;
 mod test_sheet_names;
+mod test_solver;
 mod test_spill_functions;
 mod test_today;
 mod test_trigonometric_reciprocals;
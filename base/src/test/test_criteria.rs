@@ -35,14 +35,14 @@ fn en_locale() -> &'static Locale {
 #[test]
 fn test_build_criteria_is_number() {
     let c = CalcResult::Number(42.0);
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Number(42.0)));
     assert!(fn_criteria(&CalcResult::String("42".to_string())));
     assert!(fn_criteria(&CalcResult::String("42.00".to_string())));
     assert!(!fn_criteria(&CalcResult::Number(2.0)));
 
     let c = CalcResult::String("=42".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Number(42.0)));
     assert!(fn_criteria(&CalcResult::String("42".to_string())));
     assert!(fn_criteria(&CalcResult::String("42.00".to_string())));
@@ -52,13 +52,13 @@ fn test_build_criteria_is_number() {
 #[test]
 fn test_build_criteria_is_bool() {
     let c = CalcResult::Boolean(true);
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Boolean(true)));
     assert!(!fn_criteria(&CalcResult::String("true".to_string())));
     assert!(!fn_criteria(&CalcResult::Number(1.0)));
 
     let c = CalcResult::String("=True".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Boolean(true)));
     assert!(!fn_criteria(&CalcResult::String("true".to_string())));
     assert!(!fn_criteria(&CalcResult::Number(1.0)));
@@ -67,7 +67,7 @@ fn test_build_criteria_is_bool() {
 #[test]
 fn test_build_criteria_is_less_than() {
     let c = CalcResult::String("<100".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(!fn_criteria(&CalcResult::Boolean(true)));
     assert!(!fn_criteria(&CalcResult::String("23".to_string())));
     assert!(fn_criteria(&CalcResult::Number(1.0)));
@@ -77,7 +77,7 @@ fn test_build_criteria_is_less_than() {
 #[test]
 fn test_build_criteria_is_less_wildcard() {
     let c = CalcResult::String("=D* G*".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::String(
         "Diarmuid Glynn".to_string()
     )));
@@ -104,7 +104,7 @@ fn test_build_criteria_is_less_wildcard() {
 #[test]
 fn test_build_criteria_date_less_than() {
     let c = CalcResult::String("<7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Number(45137.0)));
     assert!(!fn_criteria(&CalcResult::Number(45138.0)));
     assert!(!fn_criteria(&CalcResult::Number(45139.0)));
@@ -113,7 +113,7 @@ fn test_build_criteria_date_less_than() {
 #[test]
 fn test_build_criteria_date_less_or_equal() {
     let c = CalcResult::String("<=7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Number(45137.0)));
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
     assert!(!fn_criteria(&CalcResult::Number(45139.0)));
@@ -122,7 +122,7 @@ fn test_build_criteria_date_less_or_equal() {
 #[test]
 fn test_build_criteria_date_greater_than() {
     let c = CalcResult::String(">7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(!fn_criteria(&CalcResult::Number(45137.0)));
     assert!(!fn_criteria(&CalcResult::Number(45138.0)));
     assert!(fn_criteria(&CalcResult::Number(45139.0)));
@@ -131,7 +131,7 @@ fn test_build_criteria_date_greater_than() {
 #[test]
 fn test_build_criteria_date_greater_or_equal() {
     let c = CalcResult::String(">=7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(!fn_criteria(&CalcResult::Number(45137.0)));
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
     assert!(fn_criteria(&CalcResult::Number(45139.0)));
@@ -140,7 +140,7 @@ fn test_build_criteria_date_greater_or_equal() {
 #[test]
 fn test_build_criteria_date_not_equal() {
     let c = CalcResult::String("<>7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(fn_criteria(&CalcResult::Number(45137.0)));
     assert!(!fn_criteria(&CalcResult::Number(45138.0)));
     assert!(fn_criteria(&CalcResult::Number(45139.0)));
@@ -149,14 +149,14 @@ fn test_build_criteria_date_not_equal() {
 #[test]
 fn test_build_criteria_date_equal() {
     let c = CalcResult::String("7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(!fn_criteria(&CalcResult::Number(45137.0)));
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
     assert!(!fn_criteria(&CalcResult::Number(45139.0)));
 
     // Same with a leading "=" prefix.
     let c = CalcResult::String("=7/31/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_locale());
+    let fn_criteria = build_criteria(&c, en_locale(), false);
     assert!(!fn_criteria(&CalcResult::Number(45137.0)));
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
     assert!(!fn_criteria(&CalcResult::Number(45139.0)));
@@ -184,13 +184,13 @@ fn test_build_criteria_date_dmy_locale_en_gb() {
     // (M/D/Y) the would-be month is 31 and the parse must fail.
     let en_gb = get_locale("en-GB").unwrap();
     let c = CalcResult::String("<31/7/2023".to_string());
-    let fn_criteria = build_criteria(&c, en_gb);
+    let fn_criteria = build_criteria(&c, en_gb, false);
     assert!(fn_criteria(&CalcResult::Number(45137.0)));
     assert!(!fn_criteria(&CalcResult::Number(45138.0)));
 
     // Under en, the same string is not a parseable date so it falls through to
     // string comparison; numeric cells must never match.
-    let fn_criteria_en = build_criteria(&c, en_locale());
+    let fn_criteria_en = build_criteria(&c, en_locale(), false);
     assert!(!fn_criteria_en(&CalcResult::Number(45137.0)));
     assert!(!fn_criteria_en(&CalcResult::Number(45138.0)));
 }
@@ -201,12 +201,12 @@ fn test_build_criteria_date_dmy_locale_es() {
     // branches than the en-GB test above.
     let es = get_locale("es").unwrap();
     let c = CalcResult::String("31/7/2023".to_string());
-    let fn_criteria = build_criteria(&c, es);
+    let fn_criteria = build_criteria(&c, es, false);
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
     assert!(!fn_criteria(&CalcResult::Number(45137.0)));
 
     let c = CalcResult::String(">=31/7/2023".to_string());
-    let fn_criteria = build_criteria(&c, es);
+    let fn_criteria = build_criteria(&c, es, false);
     assert!(!fn_criteria(&CalcResult::Number(45137.0)));
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
 }
@@ -217,13 +217,13 @@ fn test_build_criteria_date_dot_separator_locale_de() {
     // through parse_date, separate from `/` and `-`.
     let de = get_locale("de").unwrap();
     let c = CalcResult::String("<=31.7.2023".to_string());
-    let fn_criteria = build_criteria(&c, de);
+    let fn_criteria = build_criteria(&c, de, false);
     assert!(fn_criteria(&CalcResult::Number(45137.0)));
     assert!(fn_criteria(&CalcResult::Number(45138.0)));
     assert!(!fn_criteria(&CalcResult::Number(45139.0)));
 
     let c = CalcResult::String("<>31.7.2023".to_string());
-    let fn_criteria = build_criteria(&c, de);
+    let fn_criteria = build_criteria(&c, de, false);
     assert!(fn_criteria(&CalcResult::Number(45137.0)));
     assert!(!fn_criteria(&CalcResult::Number(45138.0)));
 }
@@ -236,7 +236,7 @@ fn test_build_criteria_date_iso_is_locale_independent() {
     let iso = CalcResult::String("2023-07-31".to_string());
     for id in ["en", "en-GB", "es", "de", "fr", "it"] {
         let loc = get_locale(id).unwrap();
-        let fn_criteria = build_criteria(&iso, loc);
+        let fn_criteria = build_criteria(&iso, loc, false);
         assert!(
             fn_criteria(&CalcResult::Number(45138.0)),
             "ISO date should match 45138 under locale {id}"
@@ -255,12 +255,12 @@ fn test_build_criteria_date_locale_disambiguation() {
     // rather than silently defaulted.
     let c = CalcResult::String("3/7/2023".to_string());
 
-    let fn_en = build_criteria(&c, en_locale());
+    let fn_en = build_criteria(&c, en_locale(), false);
     assert!(fn_en(&CalcResult::Number(44992.0))); // March 7
     assert!(!fn_en(&CalcResult::Number(45110.0))); // July 3
 
     let es = get_locale("es").unwrap();
-    let fn_es = build_criteria(&c, es);
+    let fn_es = build_criteria(&c, es, false);
     assert!(!fn_es(&CalcResult::Number(44992.0)));
     assert!(fn_es(&CalcResult::Number(45110.0)));
 }
@@ -271,12 +271,12 @@ fn test_build_criteria_date_localized_month_name() {
     // months_short list, so this only matches under the matching locale.
     let es = get_locale("es").unwrap();
     let c = CalcResult::String("=15-ene-2023".to_string());
-    let fn_es = build_criteria(&c, es);
+    let fn_es = build_criteria(&c, es, false);
     assert!(fn_es(&CalcResult::Number(44941.0))); // 2023-01-15
     assert!(!fn_es(&CalcResult::Number(44942.0)));
 
     // Under en, "ene" is not a known month — parse fails and the criterion
     // falls back to string equality, so no Number cell should match.
-    let fn_en = build_criteria(&c, en_locale());
+    let fn_en = build_criteria(&c, en_locale(), false);
     assert!(!fn_en(&CalcResult::Number(44941.0)));
 }
@@ -0,0 +1,146 @@
+#![allow(clippy::unwrap_used)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use crate::custom_functions::{CustomFunction, FunctionValue};
+use crate::test::util::new_empty_model;
+
+fn double() -> CustomFunction {
+    CustomFunction {
+        name: "DOUBLE".to_string(),
+        min_args: 1,
+        max_args: Some(1),
+        volatile: false,
+        callback: Arc::new(|args| match &args[0] {
+            FunctionValue::Number(value) => FunctionValue::Number(2.0 * value),
+            FunctionValue::Empty => FunctionValue::Number(0.0),
+            FunctionValue::Text(text) if text == "inf" => FunctionValue::Number(f64::INFINITY),
+            _ => FunctionValue::Error {
+                error: "#VALUE!".to_string(),
+            },
+        }),
+    }
+}
+
+#[test]
+fn unknown_until_registered() {
+    let mut model = new_empty_model();
+    model._set("A1", "21");
+    model._set("A2", "=double(A1)");
+    model.evaluate();
+    assert_eq!(model._get_text("A2"), *"#NAME?");
+    assert_eq!(model._get_formula("A2"), *"=double(A1)");
+
+    model.register_function(double()).unwrap();
+    model.evaluate();
+    assert_eq!(model._get_text("A2"), *"42");
+    assert_eq!(model._get_formula("A2"), *"=double(A1)");
+    assert_eq!(model.get_registered_functions(), vec!["DOUBLE".to_string()]);
+
+    model.unregister_function("Double").unwrap();
+    model.evaluate();
+    assert_eq!(model._get_text("A2"), *"#NAME?");
+    assert!(model.unregister_function("DOUBLE").is_err());
+}
+
+#[test]
+fn arguments_and_errors() {
+    let mut model = new_empty_model();
+    model.register_function(double()).unwrap();
+    model._set("A1", "=DOUBLE(1, 2)");
+    model._set("A2", "=DOUBLE()");
+    model._set("A3", "=DOUBLE(\"a\")");
+    model._set("A4", "=DOUBLE(B1)+1");
+    model._set("A5", "=DOUBLE(\"inf\")");
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), *"#ERROR!");
+    assert_eq!(model._get_text("A2"), *"#ERROR!");
+    assert_eq!(model._get_text("A3"), *"#VALUE!");
+    assert_eq!(model._get_text("A4"), *"1");
+    assert_eq!(model._get_text("A5"), *"#NUM!");
+}
+
+#[test]
+fn ranges_and_arrays() {
+    let mut model = new_empty_model();
+    model
+        .register_function(CustomFunction {
+            name: "ROW_TOTALS".to_string(),
+            min_args: 1,
+            max_args: None,
+            volatile: false,
+            callback: Arc::new(|args| match &args[0] {
+                FunctionValue::Array(rows) => FunctionValue::Array(
+                    rows.iter()
+                        .map(|row| {
+                            let total = row
+                                .iter()
+                                .map(|value| match value {
+                                    FunctionValue::Number(n) => *n,
+                                    _ => 0.0,
+                                })
+                                .sum();
+                            vec![FunctionValue::Number(total)]
+                        })
+                        .collect(),
+                ),
+                _ => FunctionValue::Error {
+                    error: "#N/A".to_string(),
+                },
+            }),
+        })
+        .unwrap();
+    model._set("A1", "1");
+    model._set("B1", "2");
+    model._set("A2", "3");
+    model._set("B2", "text");
+    model._set("D1", "=ROW_TOTALS(A1:B2)");
+    model._set("E1", "=ROW_TOTALS(5)");
+    model.evaluate();
+    assert_eq!(model._get_text("D1"), *"3");
+    assert_eq!(model._get_text("D2"), *"3");
+    assert_eq!(model._get_text("E1"), *"#N/A");
+}
+
+#[test]
+fn registration_errors() {
+    let mut model = new_empty_model();
+    let mut function = double();
+    function.name = "Sum".to_string();
+    assert_eq!(
+        model.register_function(function),
+        Err("'Sum' is a built-in function.".to_string())
+    );
+    let mut function = double();
+    function.name = "1ABC".to_string();
+    assert!(model.register_function(function).is_err());
+    let mut function = double();
+    function.max_args = Some(0);
+    assert!(model.register_function(function).is_err());
+    assert!(model.get_registered_functions().is_empty());
+}
+
+#[test]
+fn called_on_every_evaluation() {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = calls.clone();
+    let mut model = new_empty_model();
+    model
+        .register_function(CustomFunction {
+            name: "TICK".to_string(),
+            min_args: 0,
+            max_args: Some(0),
+            volatile: true,
+            callback: Arc::new(move |_| {
+                FunctionValue::Number(counter.fetch_add(1, Ordering::SeqCst) as f64)
+            }),
+        })
+        .unwrap();
+    model._set("A1", "=TICK()");
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), *"0");
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), *"1");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,128 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::cell::CellValue;
+use crate::mock_time;
+use crate::model::Model;
+use crate::test::util::new_empty_model;
+
+// 14:44 20 Mar 2023 Berlin
+const TIMESTAMP_2023: i64 = 1679319865208;
+
+#[test]
+fn date_functions() {
+    let mut model = new_empty_model();
+    model.set_date1904(true);
+    model._set("A1", "=DATE(1904, 1, 1)");
+    model._set("A2", "=DATE(2024, 6, 15)");
+    model._set("A3", "=YEAR(A2)");
+    model._set("A4", "=MONTH(A2)");
+    model._set("A5", "=DAY(A2)");
+    model._set("A6", "=WEEKDAY(A2)");
+    model._set("A7", "=DATEVALUE(\"2024-06-15\")");
+    model._set("A8", "=EDATE(A2, 1)");
+    model._set("A9", "=EOMONTH(A2, 0)");
+    model._set("A10", "=DATE(1903, 12, 31)");
+    model._set("A11", "=YEAR(0)");
+    model.evaluate();
+
+    assert_eq!(model._get_text("A1"), "1/1/1904");
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A1"),
+        Ok(CellValue::Number(0.0))
+    );
+    // 45458 in the 1900 date system
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A2"),
+        Ok(CellValue::Number(43996.0))
+    );
+    assert_eq!(model._get_text("A3"), "2024");
+    assert_eq!(model._get_text("A4"), "6");
+    assert_eq!(model._get_text("A5"), "15");
+    // Saturday
+    assert_eq!(model._get_text("A6"), "7");
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A7"),
+        Ok(CellValue::Number(43996.0))
+    );
+    assert_eq!(model._get_text("A8"), "44026");
+    assert_eq!(model._get_text("A9"), "44011");
+    assert_eq!(model._get_text("A10"), "#NUM!");
+    assert_eq!(model._get_text("A11"), "1904");
+}
+
+#[test]
+fn formatting_and_user_input() {
+    let mut model = new_empty_model();
+    model._set("A1", "45458");
+    model._set("B1", "=TEXT(A1, \"yyyy-mm-dd\")");
+    model.evaluate();
+    assert_eq!(model._get_text("B1"), "2024-06-15");
+
+    model.set_date1904(true);
+    assert_eq!(model._get_text("B1"), "2028-06-16");
+
+    model
+        .set_user_input(0, 2, 1, "2024-06-15".to_string())
+        .unwrap();
+    model.evaluate();
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A2"),
+        Ok(CellValue::Number(43996.0))
+    );
+    assert_eq!(model._get_text("A2"), "2024-06-15");
+}
+
+#[test]
+fn today() {
+    mock_time::set_mock_time(TIMESTAMP_2023);
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model.set_date1904(true);
+    model._set("A1", "=TODAY()");
+    model.evaluate();
+
+    assert_eq!(model._get_text("A1"), "3/20/2023");
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A1"),
+        Ok(CellValue::Number(45005.0 - 1462.0))
+    );
+}
+
+#[test]
+fn convert_date_system() {
+    let mut model = new_empty_model();
+    model
+        .set_user_input(0, 1, 1, "2024-06-15".to_string())
+        .unwrap();
+    // A plain number, not a date
+    model._set("A2", "45458");
+    // A time
+    model.set_user_input(0, 3, 1, "10:30".to_string()).unwrap();
+    model._set("A4", "=DATE(2024, 6, 15)");
+    model.evaluate();
+
+    model.convert_date_system(true).unwrap();
+    assert!(model.get_date1904());
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A1"),
+        Ok(CellValue::Number(43996.0))
+    );
+    assert_eq!(model._get_text("A1"), "2024-06-15");
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A2"),
+        Ok(CellValue::Number(45458.0))
+    );
+    assert_eq!(model._get_text("A3"), "10:30");
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A4"),
+        Ok(CellValue::Number(43996.0))
+    );
+
+    model.convert_date_system(false).unwrap();
+    assert!(!model.get_date1904());
+    assert_eq!(
+        model.get_cell_value_by_ref("Sheet1!A1"),
+        Ok(CellValue::Number(45458.0))
+    );
+    assert_eq!(model._get_text("A1"), "2024-06-15");
+    assert_eq!(model._get_text("A3"), "10:30");
+}
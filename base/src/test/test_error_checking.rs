@@ -0,0 +1,154 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::error_checking::{CellDiagnostic, ErrorCheckRule, QuickFix};
+use crate::test::util::new_empty_model;
+
+fn rules(diagnostics: &[CellDiagnostic]) -> Vec<(i32, i32, ErrorCheckRule)> {
+    diagnostics
+        .iter()
+        .map(|d| (d.row, d.column, d.rule))
+        .collect()
+}
+
+fn set_content(content: &str) -> Option<QuickFix> {
+    Some(QuickFix::SetContent {
+        content: content.to_string(),
+    })
+}
+
+#[test]
+fn text_cells() {
+    let mut model = new_empty_model();
+    model._set("A1", "'123");
+    model._set("A2", "'3/5/29");
+    model._set("A3", "'5-Mar-87");
+    model._set("A4", "'hello");
+    model._set("A5", "'1/2/2023");
+    model._set("A6", "'$1,200.50");
+    model.evaluate();
+
+    let diagnostics = model.get_error_checks(0).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        vec![
+            (1, 1, ErrorCheckRule::NumberStoredAsText),
+            (2, 1, ErrorCheckRule::TwoDigitYear),
+            (3, 1, ErrorCheckRule::TwoDigitYear),
+            (6, 1, ErrorCheckRule::NumberStoredAsText),
+        ]
+    );
+    assert_eq!(diagnostics[0].quick_fix, set_content("123"));
+    assert_eq!(diagnostics[1].quick_fix, set_content("'3/5/2029"));
+    assert_eq!(diagnostics[2].quick_fix, set_content("'5-Mar-1987"));
+}
+
+#[test]
+fn inconsistent_formula() {
+    let mut model = new_empty_model();
+    for row in 1..=3 {
+        model._set(&format!("A{row}"), &row.to_string());
+        model._set(&format!("B{row}"), &(row * 10).to_string());
+    }
+    model._set("C1", "=A1+B1");
+    model._set("C2", "=A2-B2");
+    model._set("C3", "=A3+B3");
+    model.evaluate();
+
+    let diagnostics = model.get_error_checks(0).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        vec![(2, 3, ErrorCheckRule::InconsistentFormula)]
+    );
+    assert_eq!(diagnostics[0].quick_fix, set_content("=A2+B2"));
+}
+
+#[test]
+fn formula_omits_adjacent_cells() {
+    let mut model = new_empty_model();
+    for row in 1..=4 {
+        model._set(&format!("A{row}"), &row.to_string());
+    }
+    model._set("A5", "=SUM(A1:A3)");
+    model._set("C1", "1");
+    model._set("D1", "2");
+    model._set("E1", "3");
+    model._set("F1", "=SUM($D$1:E1)");
+    model._set("B7", "=SUM(A1:A4)");
+    model.evaluate();
+
+    let diagnostics = model.get_error_checks(0).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        vec![
+            (1, 6, ErrorCheckRule::FormulaOmitsAdjacentCells),
+            (5, 1, ErrorCheckRule::FormulaOmitsAdjacentCells),
+        ]
+    );
+    assert_eq!(diagnostics[0].quick_fix, set_content("=SUM($C$1:E1)"));
+    assert_eq!(diagnostics[1].quick_fix, set_content("=SUM(A1:A4)"));
+}
+
+#[test]
+fn empty_cell_reference() {
+    let mut model = new_empty_model();
+    model._set("A1", "2");
+    model._set("B1", "=A1*C3");
+    model._set("B2", "=SUM(D1:D4)");
+    model.evaluate();
+
+    let diagnostics = model.get_error_checks(0).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        vec![(1, 2, ErrorCheckRule::EmptyCellReference)]
+    );
+    assert_eq!(
+        diagnostics[0].quick_fix,
+        Some(QuickFix::GoToCell {
+            sheet: 0,
+            row: 3,
+            column: 3
+        })
+    );
+}
+
+#[test]
+fn unlocked_formula() {
+    let mut model = new_empty_model();
+    model._set("A1", "=1+1");
+    model._set("A2", "=2+2");
+    let mut style = model.get_style_for_cell(0, 1, 1).unwrap();
+    style.protection = Some(crate::types::Protection {
+        locked: false,
+        hidden: false,
+    });
+    model.set_cell_style(0, 1, 1, &style).unwrap();
+    model.evaluate();
+
+    let diagnostics = model.get_error_checks(0).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        vec![(1, 1, ErrorCheckRule::UnlockedFormula)]
+    );
+    assert_eq!(diagnostics[0].quick_fix, Some(QuickFix::LockCell));
+}
+
+#[test]
+fn implicit_intersection() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model._set("A2", "2");
+    model._set("A3", "3");
+    // A2 is in the same row
+    model._set("B2", "=@A1:A3");
+    // There is no cell of A1:A3 in row 7
+    model._set("B7", "=@A1:A3*2");
+    model.evaluate();
+
+    let diagnostics = model.get_error_checks(0).unwrap();
+    assert_eq!(
+        rules(&diagnostics),
+        vec![(7, 2, ErrorCheckRule::ImplicitIntersection)]
+    );
+    assert_eq!(diagnostics[0].quick_fix, set_content("=A1:A3*2"));
+    assert!(model.get_error_checks(3).is_err());
+}
@@ -0,0 +1,201 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::cell::CellValue;
+use crate::evaluate_formula::{EvaluationStep, EvaluationValue};
+use crate::expressions::types::CellReferenceIndex;
+use crate::test::util::new_empty_model;
+
+fn number(value: f64) -> EvaluationValue {
+    EvaluationValue::Number { value }
+}
+
+fn expressions(steps: &[EvaluationStep]) -> Vec<(&str, u32)> {
+    steps
+        .iter()
+        .map(|step| (step.expression.as_str(), step.level))
+        .collect()
+}
+
+#[test]
+fn arithmetic_steps() {
+    let mut model = new_empty_model();
+    model._set("A1", "2");
+    model._set("A2", "3");
+    model._set("B1", "=(A1+A2)*SUM(A1:A2)");
+    model.evaluate();
+
+    let steps = model.get_evaluation_steps(0, 1, 2).unwrap();
+    assert_eq!(
+        expressions(&steps),
+        vec![
+            ("A1", 2),
+            ("A2", 2),
+            ("A1+A2", 1),
+            ("A1:A2", 2),
+            ("SUM(A1:A2)", 1),
+            ("(A1+A2)*SUM(A1:A2)", 0),
+        ]
+    );
+    assert_eq!(steps[2].value, number(5.0));
+    assert_eq!(
+        steps[3].value,
+        EvaluationValue::Range {
+            reference: "Sheet1!A1:A2".to_string()
+        }
+    );
+    assert_eq!(steps[5].value, number(25.0));
+    assert!(steps.iter().all(|step| step.step_into.is_none()));
+}
+
+#[test]
+fn only_the_taken_branch() {
+    let mut model = new_empty_model();
+    model._set("A1", "5");
+    model._set("B1", "=IF(A1>3,A1*2,1/0)");
+    model._set("B2", "=IFERROR(1/A2,\"none\")");
+    model.evaluate();
+
+    let steps = model.get_evaluation_steps(0, 1, 2).unwrap();
+    assert_eq!(
+        expressions(&steps),
+        vec![
+            ("A1", 2),
+            ("A1>3", 1),
+            ("A1", 2),
+            ("A1*2", 1),
+            ("IF(A1>3,A1*2,1/0)", 0),
+        ]
+    );
+    assert_eq!(steps[4].value, number(10.0));
+
+    let steps = model.get_evaluation_steps(0, 2, 2).unwrap();
+    assert_eq!(
+        expressions(&steps),
+        vec![("A2", 2), ("1/A2", 1), ("IFERROR(1/A2,\"none\")", 0)]
+    );
+    assert!(matches!(steps[1].value, EvaluationValue::Error { .. }));
+    assert_eq!(
+        steps[2].value,
+        EvaluationValue::Text {
+            value: "none".to_string()
+        }
+    );
+}
+
+#[test]
+fn error_origin_and_step_into() {
+    let mut model = new_empty_model();
+    model._set("A1", "=1/0");
+    model._set("A2", "=A1+1");
+    model._set("A3", "=SUM(A2,4)");
+    model.evaluate();
+
+    let steps = model.get_evaluation_steps(0, 3, 1).unwrap();
+    assert_eq!(expressions(&steps), vec![("A2", 1), ("SUM(A2,4)", 0)]);
+    assert_eq!(
+        steps[0].step_into,
+        Some(CellReferenceIndex {
+            sheet: 0,
+            row: 2,
+            column: 1
+        })
+    );
+    let error = EvaluationValue::Error {
+        error: "#DIV/0!".to_string(),
+        origin: "Sheet1!A1".to_string(),
+        message: "Divide by 0".to_string(),
+    };
+    assert_eq!(steps[1].value, error);
+
+    // stepping into A2 and then A1
+    let steps = model.get_evaluation_steps(0, 2, 1).unwrap();
+    assert_eq!(
+        steps[0].step_into,
+        Some(CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 1
+        })
+    );
+    let steps = model.get_evaluation_steps(0, 1, 1).unwrap();
+    assert_eq!(expressions(&steps), vec![("1/0", 0)]);
+    assert_eq!(steps[0].value, error);
+}
+
+#[test]
+fn let_variables() {
+    let mut model = new_empty_model();
+    model._set("A1", "4");
+    model._set("B1", "=LET(x,A1*2,x+1)");
+    model.evaluate();
+
+    let steps = model.get_evaluation_steps(0, 1, 2).unwrap();
+    assert_eq!(
+        expressions(&steps),
+        vec![("A1", 2), ("A1*2", 1), ("LET(x,A1*2,x+1)", 0)]
+    );
+    assert_eq!(steps[2].value, number(9.0));
+}
+
+#[test]
+fn cells_without_formulas() {
+    let mut model = new_empty_model();
+    model._set("A1", "4");
+    model.evaluate();
+    assert_eq!(model.get_evaluation_steps(0, 1, 1), Ok(vec![]));
+    assert_eq!(model.get_evaluation_steps(0, 7, 7), Ok(vec![]));
+    assert!(model.get_evaluation_steps(3, 1, 1).is_err());
+}
+
+#[test]
+fn volatile_functions() {
+    let mut model = new_empty_model();
+    model._set("A1", "=RAND()");
+    model._set("B1", "=A1*10+RAND()");
+    model.evaluate();
+    let a1 = model.get_cell_value_by_index(0, 1, 1).unwrap();
+    let b1 = model.get_cell_value_by_index(0, 1, 2).unwrap();
+    let (CellValue::Number(a1_value), CellValue::Number(b1_value)) = (&a1, b1) else {
+        panic!("Expected numbers");
+    };
+
+    let steps = model.get_evaluation_steps(0, 1, 2).unwrap();
+    assert_eq!(
+        expressions(&steps),
+        vec![("A1", 2), ("A1*10", 1), ("A1*10+RAND()", 0)]
+    );
+    assert_eq!(steps[0].value, number(*a1_value));
+    assert_eq!(steps[2].value, number(b1_value));
+    assert_eq!(model.get_cell_value_by_index(0, 1, 1).unwrap(), a1);
+}
+
+#[test]
+fn the_model_is_left_untouched() {
+    let mut model = new_empty_model();
+    model._set("A1", "3");
+    model._set("B1", "=LET(x,A1*2,MAP(A1:A2,LAMBDA(v,v+x)))");
+    model._set("C1", "=SUM(INDIRECT(\"A1:A2\"))+B1");
+    model.evaluate();
+    let cell_count = model.cells.len();
+    let lambda_count = model.lambdas.len();
+    let last_variable_id = model.last_variable_id;
+    let last_lambda_id = model.last_lambda_id;
+    let c1 = CellReferenceIndex {
+        sheet: 0,
+        row: 1,
+        column: 3,
+    };
+    let support_count = model.support[&c1].len();
+    let dynamic_support_count = model.dynamic_support[&c1].len();
+
+    assert!(!model.get_evaluation_steps(0, 1, 2).unwrap().is_empty());
+    assert!(!model.get_evaluation_steps(0, 1, 3).unwrap().is_empty());
+
+    assert_eq!(model.cells.len(), cell_count);
+    assert_eq!(model.lambdas.len(), lambda_count);
+    assert_eq!(model.last_variable_id, last_variable_id);
+    assert_eq!(model.last_lambda_id, last_lambda_id);
+    assert_eq!(model.support[&c1].len(), support_count);
+    assert_eq!(model.dynamic_support[&c1].len(), dynamic_support_count);
+    assert_eq!(model._get_text("C1"), "12");
+}
@@ -0,0 +1,172 @@
+#![allow(clippy::unwrap_used)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use crate::external_links::WorkbookResolver;
+use crate::test::util::new_empty_model;
+use crate::types::{ExternalLink, ExternalSheet, ExternalValue};
+use crate::Model;
+
+/// Resolves "Budget.xlsx" to a copy of a model that can be changed by the test
+struct BudgetResolver {
+    bytes: Mutex<Vec<u8>>,
+}
+
+impl WorkbookResolver for BudgetResolver {
+    fn resolve(&self, workbook: &str) -> Option<Model<'static>> {
+        if workbook != "Budget.xlsx" {
+            return None;
+        }
+        Model::from_bytes(&self.bytes.lock().unwrap(), "en").ok()
+    }
+}
+
+fn new_budget(a1: &str) -> Vec<u8> {
+    let mut model = new_empty_model();
+    model._set("A1", a1);
+    model._set("A2", "=A1*2");
+    model._set("A3", "Total");
+    model.evaluate();
+    model.to_bytes()
+}
+
+fn cached_budget() -> ExternalLink {
+    let mut cells = HashMap::new();
+    cells.insert(1, HashMap::from([(1, ExternalValue::Number(5.0))]));
+    cells.insert(2, HashMap::from([(1, ExternalValue::Number(10.0))]));
+    cells.insert(
+        3,
+        HashMap::from([(1, ExternalValue::Text("Total".to_string()))]),
+    );
+    ExternalLink {
+        workbook: "Budget.xlsx".to_string(),
+        target: "file:///data/Budget.xlsx".to_string(),
+        sheets: vec![ExternalSheet {
+            name: "Sheet1".to_string(),
+            cells,
+        }],
+    }
+}
+
+#[test]
+fn parse_and_stringify() {
+    let mut model = new_empty_model();
+    model._set("A1", "=[Budget.xlsx]Sheet1!A1");
+    model._set("A2", "='[My Budget.xlsx]Data 1'!$B$2*2");
+    model._set("A3", "=SUM([Budget.xlsx]Sheet1!A1:A3)");
+    model._set("A4", "=[budget.xlsx]Sheet1!A:A");
+    model.evaluate();
+
+    assert_eq!(model._get_formula("A1"), *"=[Budget.xlsx]Sheet1!A1");
+    assert_eq!(
+        model._get_formula("A2"),
+        *"='[My Budget.xlsx]Data 1'!$B$2*2"
+    );
+    assert_eq!(model._get_formula("A3"), *"=SUM([Budget.xlsx]Sheet1!A1:A3)");
+    assert_eq!(model._get_formula("A4"), *"=[budget.xlsx]Sheet1!A:A");
+
+    // Nothing is known about those workbooks
+    assert_eq!(model._get_text("A1"), *"#REF!");
+    assert_eq!(model._get_text("A2"), *"#REF!");
+    assert_eq!(model._get_text("A3"), *"#REF!");
+}
+
+#[test]
+fn cached_values() {
+    let mut model = new_empty_model();
+    model.workbook.external_links.push(cached_budget());
+    model._set("B1", "=[Budget.xlsx]Sheet1!A1");
+    model._set("B2", "=SUM([Budget.xlsx]Sheet1!A1:A3)");
+    model._set("B3", "=[BUDGET.XLSX]sheet1!A3");
+    model._set("B4", "=[Budget.xlsx]Sheet1!Z100");
+    model._set("B5", "=[Budget.xlsx]Sheet2!A1");
+    model._set("B6", "=SUM([Budget.xlsx]Sheet1!A:A)");
+    model._set("B7", "=COUNTA([Budget.xlsx]Sheet1!1:3)");
+    model.evaluate();
+
+    assert_eq!(model._get_text("B1"), *"5");
+    assert_eq!(model._get_text("B2"), *"15");
+    assert_eq!(model._get_text("B3"), *"Total");
+    // Cells that are not cached are empty
+    assert_eq!(model._get_text("B4"), *"0");
+    assert_eq!(model._get_text("B5"), *"#REF!");
+    assert_eq!(model._get_text("B6"), *"15");
+    assert_eq!(model._get_text("B7"), *"3");
+}
+
+#[test]
+fn resolver_live_values() {
+    let resolver = Arc::new(BudgetResolver {
+        bytes: Mutex::new(new_budget("7")),
+    });
+    let mut model = new_empty_model();
+    model.workbook.external_links.push(cached_budget());
+    model._set("B1", "=[Budget.xlsx]Sheet1!A1");
+    model._set("B2", "=[Budget.xlsx]Sheet1!A2+1");
+    model._set("B3", "=[Other.xlsx]Sheet1!A1");
+    model.evaluate();
+    assert_eq!(model._get_text("B1"), *"5");
+    assert_eq!(model._get_text("B2"), *"11");
+
+    model.set_workbook_resolver(resolver.clone());
+    model.evaluate();
+    assert_eq!(model._get_text("B1"), *"7");
+    assert_eq!(model._get_text("B2"), *"15");
+    // Not resolved and not cached
+    assert_eq!(model._get_text("B3"), *"#REF!");
+
+    // The workbook is loaded again on each evaluation
+    *resolver.bytes.lock().unwrap() = new_budget("8");
+    model.evaluate();
+    assert_eq!(model._get_text("B1"), *"8");
+    assert_eq!(model._get_text("B2"), *"17");
+
+    // Evaluating does not change the cached values
+    assert_eq!(model.workbook.external_links, vec![cached_budget()]);
+
+    // Until the host stores the last values
+    model.update_external_links();
+    model.remove_workbook_resolver();
+    model.evaluate();
+    assert_eq!(model._get_text("B1"), *"8");
+    assert_eq!(model._get_text("B2"), *"17");
+    let sheet = &model.workbook.external_links[0].sheets[0];
+    assert_eq!(sheet.cells[&2][&1], ExternalValue::Number(16.0));
+}
+
+#[test]
+fn resolver_adds_link() {
+    let resolver = Arc::new(BudgetResolver {
+        bytes: Mutex::new(new_budget("3")),
+    });
+    let mut model = new_empty_model();
+    model.set_workbook_resolver(resolver);
+    model._set("A1", "=SUM([Budget.xlsx]Sheet1!A1:A2)");
+    model._set("A2", "=[Budget.xlsx]Sheet7!A1");
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), *"9");
+    assert_eq!(model._get_text("A2"), *"#REF!");
+    assert!(model.workbook.external_links.is_empty());
+
+    model.update_external_links();
+    assert_eq!(model.workbook.external_links.len(), 1);
+    let link = &model.workbook.external_links[0];
+    assert_eq!(link.workbook, "Budget.xlsx");
+    assert_eq!(link.sheets.len(), 1);
+    assert_eq!(link.sheets[0].cells[&1][&1], ExternalValue::Number(3.0));
+}
+
+#[test]
+fn references_are_not_displaced() {
+    let mut model = new_empty_model();
+    model.workbook.external_links.push(cached_budget());
+    model._set("B3", "=[Budget.xlsx]Sheet1!$A$2+[Budget.xlsx]Sheet1!A1");
+    model.insert_rows(0, 1, 1).unwrap();
+    model.evaluate();
+    assert_eq!(
+        model._get_formula("B4"),
+        *"=[Budget.xlsx]Sheet1!$A$2+[Budget.xlsx]Sheet1!A1"
+    );
+    assert_eq!(model._get_text("B4"), *"15");
+}
@@ -0,0 +1,127 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::util::new_empty_model;
+
+#[test]
+fn one_variable_column_input() {
+    let mut model = new_empty_model();
+    // Monthly payment of a loan for several interest rates
+    model._set("A1", "0.05");
+    model._set("A2", "=A1*100");
+    model._set("B4", "=A2+1");
+    model._set("C4", "=A2*2");
+    model._set("A5", "0.01");
+    model._set("A6", "0.02");
+    model._set("A7", "0.03");
+    model
+        .set_user_array_formula(0, 5, 2, 2, 3, "=TABLE(,A1)")
+        .unwrap();
+    model.evaluate();
+
+    assert_eq!(model._get_text("B5"), "2");
+    assert_eq!(model._get_text("B6"), "3");
+    assert_eq!(model._get_text("B7"), "4");
+    assert_eq!(model._get_text("C5"), "2");
+    assert_eq!(model._get_text("C6"), "4");
+    assert_eq!(model._get_text("C7"), "6");
+    assert_eq!(model._get_formula("B5"), "=TABLE(,A1)");
+
+    // The input cell and its dependents keep their values
+    assert_eq!(model._get_text("A1"), "0.05");
+    assert_eq!(model._get_text("A2"), "5");
+    assert_eq!(model._get_text("B4"), "6");
+    assert_eq!(model._get_text("C4"), "10");
+}
+
+#[test]
+fn one_variable_row_input() {
+    let mut model = new_empty_model();
+    model._set("A1", "3");
+    model._set("B3", "=A1^2");
+    model._set("C2", "1");
+    model._set("D2", "2");
+    model._set("E2", "5");
+    model
+        .set_user_array_formula(0, 3, 3, 3, 1, "=TABLE(A1,)")
+        .unwrap();
+    model.evaluate();
+
+    assert_eq!(model._get_text("C3"), "1");
+    assert_eq!(model._get_text("D3"), "4");
+    assert_eq!(model._get_text("E3"), "25");
+    assert_eq!(model._get_text("B3"), "9");
+}
+
+#[test]
+fn two_variables() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model._set("A2", "1");
+    model._set("A3", "=A1*A2");
+    // The formula in the corner, row values and column values
+    model._set("C5", "=A3+1");
+    model._set("D5", "2");
+    model._set("E5", "3");
+    model._set("C6", "10");
+    model._set("C7", "20");
+    model
+        .set_user_array_formula(0, 6, 4, 2, 2, "=TABLE(A1,A2)")
+        .unwrap();
+    // A formula that depends on the results
+    model._set("G1", "=SUM(D6:E7)");
+    model.evaluate();
+
+    assert_eq!(model._get_text("D6"), "21");
+    assert_eq!(model._get_text("E6"), "31");
+    assert_eq!(model._get_text("D7"), "41");
+    assert_eq!(model._get_text("E7"), "61");
+    assert_eq!(model._get_text("G1"), "154");
+    assert_eq!(model._get_text("A3"), "1");
+    assert_eq!(model._get_text("C5"), "2");
+
+    // Changing the formula updates the table
+    model._set("A3", "=A1+A2");
+    model.evaluate();
+    assert_eq!(model._get_text("D6"), "13");
+    assert_eq!(model._get_text("E7"), "24");
+    assert_eq!(model._get_text("C5"), "3");
+}
+
+#[test]
+fn errors() {
+    let mut model = new_empty_model();
+    model._set("A1", "=TABLE(B1,C1)");
+    model
+        .set_user_array_formula(0, 3, 3, 1, 1, "=TABLE(,)")
+        .unwrap();
+    model
+        .set_user_array_formula(0, 5, 3, 1, 1, "=TABLE(A1:A2,)")
+        .unwrap();
+    model.evaluate();
+
+    assert_eq!(model._get_text("A1"), "#VALUE!");
+    assert_eq!(model._get_text("C3"), "#VALUE!");
+    assert_eq!(model._get_text("C5"), "#VALUE!");
+}
+
+#[test]
+fn text_inputs_do_not_change_the_workbook() {
+    let mut model = new_empty_model();
+    model._set("A1", "=\"Tom\"");
+    model._set("B3", "=\"Hello \"&A1");
+    // The values are not in the shared strings
+    model._set("A4", "=\"Ali\"&\"ce\"");
+    model._set("A5", "=\"B\"&\"ob\"");
+    model
+        .set_user_array_formula(0, 4, 2, 1, 2, "=TABLE(,A1)")
+        .unwrap();
+    let shared_strings = model.workbook.shared_strings.clone();
+
+    model.evaluate();
+    assert_eq!(model._get_text("B4"), "Hello Alice");
+    assert_eq!(model._get_text("B5"), "Hello Bob");
+    assert_eq!(model._get_text("B3"), "Hello Tom");
+    assert_eq!(model._get_text("A1"), "Tom");
+    assert_eq!(model._get_formula("A1"), "=\"Tom\"");
+    assert_eq!(model.workbook.shared_strings, shared_strings);
+}
@@ -0,0 +1,131 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::function_catalogue::{
+    get_active_argument, get_function_catalogue, ActiveArgument, ArgumentType, FunctionCategory,
+};
+
+fn active(function: &str, index: u32) -> Option<ActiveArgument> {
+    Some(ActiveArgument {
+        function: function.to_string(),
+        index,
+    })
+}
+
+#[test]
+fn catalogue() {
+    let catalogue = get_function_catalogue("en").unwrap();
+    assert!(catalogue.windows(2).all(|w| w[0].name <= w[1].name));
+
+    let vlookup = catalogue.iter().find(|f| f.name == "VLOOKUP").unwrap();
+    assert_eq!(vlookup.category, FunctionCategory::LookupAndReference);
+    assert_eq!(vlookup.return_type, ArgumentType::Any);
+    assert!(!vlookup.volatile);
+    let names: Vec<&str> = vlookup.parameters.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(
+        names,
+        vec![
+            "lookup_value",
+            "table_array",
+            "col_index_num",
+            "range_lookup"
+        ]
+    );
+    assert_eq!(vlookup.parameters[3].argument_type, ArgumentType::Logical);
+    assert!(vlookup.parameters[3].optional);
+
+    let now = catalogue.iter().find(|f| f.name == "NOW").unwrap();
+    assert!(now.volatile);
+    assert!(now.parameters.is_empty());
+}
+
+#[test]
+fn localized_catalogue() {
+    let catalogue = get_function_catalogue("de").unwrap();
+    let sum = catalogue.iter().find(|f| f.name == "SUMME").unwrap();
+    assert_eq!(sum.category, FunctionCategory::MathAndTrigonometry);
+    assert!(sum.parameters[1].repeating);
+    assert!(!catalogue.iter().any(|f| f.name == "SUM"));
+    assert_eq!(sum.parameters[0].name, "zahl1");
+    assert_eq!(sum.parameters[1].name, "zahl2");
+    assert_eq!(sum.description, "Addiert die Argumente");
+
+    let catalogue = get_function_catalogue("es").unwrap();
+    let vlookup = catalogue.iter().find(|f| f.name == "BUSCARV").unwrap();
+    assert_eq!(vlookup.parameters[0].name, "valor_buscado");
+
+    // Every language has a translation for every parameter and description
+    let english = get_function_catalogue("en").unwrap();
+    for language in ["de", "es", "fr", "it"] {
+        let catalogue = get_function_catalogue(language).unwrap();
+        assert_eq!(catalogue.len(), english.len());
+        for function in &catalogue {
+            assert!(
+                !english
+                    .iter()
+                    .any(|f| f.description == function.description),
+                "Missing description of {} in '{language}'",
+                function.name
+            );
+        }
+    }
+
+    assert!(get_function_catalogue("xx").is_err());
+}
+
+#[test]
+fn active_argument() {
+    let formula = "=SUM(A1, ROUND(B1, 2), \"a,b\")";
+    let position = |s: &str| formula.find(s).unwrap();
+    assert_eq!(get_active_argument(formula, 0, "en", "en").unwrap(), None);
+    assert_eq!(
+        get_active_argument(formula, position("A1"), "en", "en").unwrap(),
+        active("SUM", 0)
+    );
+    assert_eq!(
+        get_active_argument(formula, position("B1"), "en", "en").unwrap(),
+        active("ROUND", 0)
+    );
+    assert_eq!(
+        get_active_argument(formula, position(" 2"), "en", "en").unwrap(),
+        active("ROUND", 1)
+    );
+    assert_eq!(
+        get_active_argument(formula, position("a,b") + 2, "en", "en").unwrap(),
+        None
+    );
+    assert_eq!(
+        get_active_argument(formula, position("a,b\"") + 4, "en", "en").unwrap(),
+        active("SUM", 2)
+    );
+    assert_eq!(
+        get_active_argument(formula, formula.len(), "en", "en").unwrap(),
+        None
+    );
+}
+
+#[test]
+fn active_argument_partial_formulas() {
+    // parenthesis and arrays do not change the argument
+    assert_eq!(
+        get_active_argument("=if((A1+1)*2>3, {1,2", 20, "en", "en").unwrap(),
+        active("IF", 1)
+    );
+    assert_eq!(
+        get_active_argument("=if((A1+1)*2>3, {1,2}, ", 23, "en", "en").unwrap(),
+        active("IF", 2)
+    );
+    assert_eq!(
+        get_active_argument("=_xlfn.XLOOKUP(A1, B:B", 22, "en", "en").unwrap(),
+        active("XLOOKUP", 1)
+    );
+    // unknown functions are skipped
+    assert_eq!(
+        get_active_argument("=SUM(A1, myfunc(2, ", 19, "en", "en").unwrap(),
+        active("SUM", 1)
+    );
+    // locales with a decimal comma separate arguments with semicolons
+    assert_eq!(
+        get_active_argument("=RUNDEN(1,5; ", 13, "de", "de").unwrap(),
+        active("RUNDEN", 1)
+    );
+}
@@ -0,0 +1,125 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::CellReferenceIndex;
+use crate::goal_seek::GoalSeekOptions;
+use crate::test::util::new_empty_model;
+
+fn cell(row: i32, column: i32) -> CellReferenceIndex {
+    CellReferenceIndex {
+        sheet: 0,
+        row,
+        column,
+    }
+}
+
+#[test]
+fn linear() {
+    let mut model = new_empty_model();
+    model._set("B3", "10");
+    model._set("B10", "=B3*4-100");
+    model.evaluate();
+
+    let result = model
+        .goal_seek(&cell(10, 2), 0.0, &cell(3, 2), &GoalSeekOptions::default())
+        .unwrap();
+    assert!(result.converged);
+    assert!(result.error <= 0.001);
+    assert!(result.iterations <= 5);
+    assert!((result.value - 25.0).abs() < 1e-6);
+    assert_eq!(model._get_text("B10"), "0");
+    assert_eq!(model._get_text("B3"), "25");
+}
+
+#[test]
+fn loan_payment() {
+    let mut model = new_empty_model();
+    // Interest rate that gives a payment of 500 for 20000 in 48 months
+    model._set("A1", "0.01");
+    model._set("A2", "=-PMT(A1/12, 48, 20000)");
+    model.evaluate();
+
+    let result = model
+        .goal_seek(&cell(2, 1), 500.0, &cell(1, 1), &GoalSeekOptions::default())
+        .unwrap();
+    assert!(result.converged);
+    let payment = model.get_cell_value_by_index(0, 2, 1).unwrap();
+    match payment {
+        crate::cell::CellValue::Number(value) => assert!((value - 500.0).abs() <= 0.001),
+        _ => panic!("Expected a number"),
+    }
+}
+
+#[test]
+fn empty_changing_cell_and_flat_start() {
+    let mut model = new_empty_model();
+    // The secant method has no slope at 0
+    model._set("B1", "=A1*A1*A1-8");
+    model.evaluate();
+
+    let result = model
+        .goal_seek(&cell(1, 2), 0.0, &cell(1, 1), &GoalSeekOptions::default())
+        .unwrap();
+    assert!(result.converged);
+    assert!((result.value - 2.0).abs() < 0.001);
+}
+
+#[test]
+fn no_solution_restores_the_value() {
+    let mut model = new_empty_model();
+    model._set("A1", "3");
+    model._set("B1", "=A1*A1+1");
+    model.evaluate();
+
+    let options = GoalSeekOptions {
+        max_iterations: 50,
+        tolerance: 0.001,
+    };
+    let result = model
+        .goal_seek(&cell(1, 2), 0.0, &cell(1, 1), &options)
+        .unwrap();
+    assert!(!result.converged);
+    assert_eq!(result.iterations, 50);
+    // The best value found is close to the minimum
+    assert!(result.error >= 1.0);
+    assert_eq!(model._get_text("A1"), "3");
+    assert_eq!(model._get_text("B1"), "10");
+}
+
+#[test]
+fn errors_in_the_target() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model._set("B1", "=1/(A1-1)+A1");
+    model.evaluate();
+
+    // Starts on #DIV/0!
+    let result = model
+        .goal_seek(&cell(1, 2), 5.0, &cell(1, 1), &GoalSeekOptions::default())
+        .unwrap();
+    assert!(!result.converged);
+    assert_eq!(model._get_text("A1"), "1");
+}
+
+#[test]
+fn invalid_cells() {
+    let mut model = new_empty_model();
+    model._set("A1", "Text");
+    model._set("A2", "=1+1");
+    model._set("B1", "=A1");
+    model._set("B2", "3");
+    model.evaluate();
+    let options = GoalSeekOptions::default();
+
+    assert_eq!(
+        model.goal_seek(&cell(2, 2), 0.0, &cell(1, 1), &options),
+        Err("The target cell must contain a formula".to_string())
+    );
+    assert_eq!(
+        model.goal_seek(&cell(1, 2), 0.0, &cell(1, 1), &options),
+        Err("The changing cell must contain a number".to_string())
+    );
+    assert_eq!(
+        model.goal_seek(&cell(1, 2), 0.0, &cell(2, 1), &options),
+        Err("The changing cell must contain a number".to_string())
+    );
+}
@@ -0,0 +1,251 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::precedents::TracedReference;
+use crate::test::util::new_empty_model;
+use crate::types::DefinedName;
+
+fn traced(
+    sheet: u32,
+    row: i32,
+    column: i32,
+    width: i32,
+    height: i32,
+    depth: u32,
+) -> TracedReference {
+    TracedReference {
+        sheet,
+        row,
+        column,
+        width,
+        height,
+        depth,
+        name: None,
+        dynamic: false,
+    }
+}
+
+#[test]
+fn direct_and_deep_precedents() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model._set("B1", "2");
+    model._set("C1", "=A1+SUM(B1:B3)");
+    model._set("D1", "=C1*2+C1");
+    model.evaluate();
+
+    assert_eq!(
+        model.get_precedents(0, 1, 3, 1).unwrap(),
+        vec![traced(0, 1, 1, 1, 1, 1), traced(0, 1, 2, 1, 3, 1)]
+    );
+    assert_eq!(
+        model.get_precedents(0, 1, 4, 1).unwrap(),
+        vec![traced(0, 1, 3, 1, 1, 1)]
+    );
+    assert_eq!(
+        model.get_precedents(0, 1, 4, 5).unwrap(),
+        vec![
+            traced(0, 1, 3, 1, 1, 1),
+            traced(0, 1, 1, 1, 1, 2),
+            traced(0, 1, 2, 1, 3, 2)
+        ]
+    );
+    assert_eq!(model.get_precedents(0, 1, 1, 1).unwrap(), vec![]);
+}
+
+#[test]
+fn dependents() {
+    let mut model = new_empty_model();
+    model._set("A1", "1");
+    model._set("C1", "=A1+1");
+    model._set("D1", "=C1*2");
+    model._set("E1", "=SUM(A1:A5)");
+    model.evaluate();
+
+    assert_eq!(
+        model.get_dependents(0, 1, 1, 1).unwrap(),
+        vec![traced(0, 1, 3, 1, 1, 1), traced(0, 1, 5, 1, 1, 1)]
+    );
+    assert_eq!(
+        model.get_dependents(0, 1, 1, 2).unwrap(),
+        vec![
+            traced(0, 1, 3, 1, 1, 1),
+            traced(0, 1, 5, 1, 1, 1),
+            traced(0, 1, 4, 1, 1, 2)
+        ]
+    );
+    assert!(model.get_dependents(3, 1, 1, 1).is_err());
+}
+
+#[test]
+fn circular_references() {
+    let mut model = new_empty_model();
+    model._set("A1", "=B1");
+    model._set("B1", "=A1");
+    model.evaluate();
+
+    assert_eq!(
+        model.get_precedents(0, 1, 1, 10).unwrap(),
+        vec![traced(0, 1, 2, 1, 1, 1), traced(0, 1, 1, 1, 1, 2)]
+    );
+    assert_eq!(
+        model.get_dependents(0, 1, 1, 10).unwrap(),
+        vec![traced(0, 1, 2, 1, 1, 1)]
+    );
+}
+
+#[test]
+fn defined_names_and_other_sheets() {
+    let mut model = new_empty_model();
+    model.add_sheet("Data").unwrap();
+    model.new_defined_name("rate", None, "Data!$B$2").unwrap();
+    model._set("A1", "=rate*Data!A1");
+    model.evaluate();
+
+    let precedents = model.get_precedents(0, 1, 1, 1).unwrap();
+    assert_eq!(
+        precedents,
+        vec![
+            TracedReference {
+                name: Some("rate".to_string()),
+                ..traced(1, 2, 2, 1, 1, 1)
+            },
+            traced(1, 1, 1, 1, 1, 1)
+        ]
+    );
+
+    let dependents = model.get_dependents(1, 2, 2, 1).unwrap();
+    assert_eq!(
+        dependents,
+        vec![TracedReference {
+            name: Some("rate".to_string()),
+            ..traced(0, 1, 1, 1, 1, 1)
+        }]
+    );
+}
+
+#[test]
+fn dynamic_references() {
+    let mut model = new_empty_model();
+    model._set("A1", "C3");
+    model._set("B1", "=INDIRECT(A1)");
+    model._set("B2", "=SUM(OFFSET(D1,1,1,2,1))");
+    model.evaluate();
+
+    assert_eq!(
+        model.get_precedents(0, 1, 2, 1).unwrap(),
+        vec![
+            traced(0, 1, 1, 1, 1, 1),
+            TracedReference {
+                dynamic: true,
+                ..traced(0, 3, 3, 1, 1, 1)
+            }
+        ]
+    );
+    assert_eq!(
+        model.get_precedents(0, 2, 2, 1).unwrap(),
+        vec![
+            traced(0, 1, 4, 1, 1, 1),
+            TracedReference {
+                dynamic: true,
+                ..traced(0, 2, 5, 1, 2, 1)
+            }
+        ]
+    );
+    assert_eq!(
+        model.get_dependents(0, 3, 3, 1).unwrap(),
+        vec![TracedReference {
+            dynamic: true,
+            ..traced(0, 1, 2, 1, 1, 1)
+        }]
+    );
+}
+
+#[test]
+fn tracing_does_not_evaluate() {
+    let mut model = new_empty_model();
+    model._set("A1", "=RAND()");
+    model._set("B1", "=INDIRECT(\"C\"&ROUND(A1*5+1,0))");
+    model.evaluate();
+    let value = model._get_text("A1");
+
+    let precedents = model.get_precedents(0, 1, 2, 1).unwrap();
+    assert_eq!(precedents.len(), 2);
+    assert!(precedents[1].dynamic);
+    model.get_dependents(0, 1, 1, 2).unwrap();
+    assert_eq!(model._get_text("A1"), value);
+}
+
+#[test]
+fn defined_names_with_formulas() {
+    // Names with formulas can only come from imported files
+    let mut model = new_empty_model();
+    for (name, formula) in [
+        ("total", "SUM(Sheet1!$B$1:$B$3)*Sheet1!$C$1"),
+        ("loop", "loop+Sheet1!$D$1"),
+    ] {
+        model.workbook.defined_names.push(DefinedName {
+            name: name.to_string(),
+            formula: formula.to_string(),
+            sheet_id: None,
+            hidden: false,
+        });
+    }
+    model.reset_parsed_structures();
+    model._set("A1", "=total");
+    model._set("A2", "=loop");
+    model.evaluate();
+
+    let named = |reference: TracedReference, name: &str| TracedReference {
+        name: Some(name.to_string()),
+        ..reference
+    };
+    assert_eq!(
+        model.get_precedents(0, 1, 1, 1).unwrap(),
+        vec![
+            named(traced(0, 1, 2, 1, 3, 1), "total"),
+            named(traced(0, 1, 3, 1, 1, 1), "total")
+        ]
+    );
+    assert_eq!(
+        model.get_precedents(0, 2, 1, 1).unwrap(),
+        vec![named(traced(0, 1, 4, 1, 1, 1), "loop")]
+    );
+    assert_eq!(
+        model.get_dependents(0, 2, 2, 1).unwrap(),
+        vec![named(traced(0, 1, 1, 1, 1, 1), "total")]
+    );
+}
+
+#[test]
+fn defined_names_with_formulas_in_context() {
+    // References without a sheet are in the sheet of the cell using the name
+    let mut model = new_empty_model();
+    model.add_sheet("Data").unwrap();
+    model.workbook.defined_names.push(DefinedName {
+        name: "double".to_string(),
+        formula: "2*$B$1".to_string(),
+        sheet_id: None,
+        hidden: false,
+    });
+    model.reset_parsed_structures();
+    model._set("A1", "=double");
+    model._set("Data!C3", "=double");
+    model.evaluate();
+
+    let named = |reference: TracedReference| TracedReference {
+        name: Some("double".to_string()),
+        ..reference
+    };
+    assert_eq!(
+        model.get_precedents(0, 1, 1, 1).unwrap(),
+        vec![named(traced(0, 1, 2, 1, 1, 1))]
+    );
+    assert_eq!(
+        model.get_precedents(1, 3, 3, 1).unwrap(),
+        vec![named(traced(1, 1, 2, 1, 1, 1))]
+    );
+    assert_eq!(
+        model.get_dependents(1, 1, 2, 1).unwrap(),
+        vec![named(traced(1, 3, 3, 1, 1, 1))]
+    );
+}
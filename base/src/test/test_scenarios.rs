@@ -0,0 +1,258 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::{Area, CellReferenceIndex};
+use crate::test::util::new_empty_model;
+use crate::types::{Scenario, ScenarioInputCell, ShiftDirection};
+use crate::Model;
+
+fn scenario(name: &str, input_cells: &[(i32, i32, &str)]) -> Scenario {
+    Scenario {
+        name: name.to_string(),
+        comment: String::new(),
+        user: String::new(),
+        locked: true,
+        hidden: false,
+        input_cells: input_cells
+            .iter()
+            .map(|(row, column, value)| ScenarioInputCell {
+                row: *row,
+                column: *column,
+                value: value.to_string(),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn add_update_delete() {
+    let mut model = new_empty_model();
+    model
+        .add_scenario(0, scenario("Best", &[(1, 1, "10")]))
+        .unwrap();
+    model
+        .add_scenario(0, scenario("Worst", &[(1, 1, "1"), (1, 2, "2")]))
+        .unwrap();
+    assert_eq!(model.get_scenarios(0).unwrap().len(), 2);
+
+    model
+        .update_scenario(0, "best", scenario("Likely", &[(1, 1, "5")]))
+        .unwrap();
+    let scenarios = model.get_scenarios(0).unwrap();
+    assert_eq!(scenarios[0], scenario("Likely", &[(1, 1, "5")]));
+    assert_eq!(scenarios[1].name, "Worst");
+
+    model.delete_scenario(0, "WORST").unwrap();
+    assert_eq!(model.get_scenarios(0).unwrap().len(), 1);
+    assert_eq!(
+        model.delete_scenario(0, "Worst"),
+        Err("Scenario not found: 'Worst'".to_string())
+    );
+    assert!(model.get_scenarios(3).is_err());
+}
+
+#[test]
+fn validation() {
+    let mut model = new_empty_model();
+    model
+        .add_scenario(0, scenario("Best", &[(1, 1, "10")]))
+        .unwrap();
+    assert_eq!(
+        model.add_scenario(0, scenario("BEST", &[(1, 1, "1")])),
+        Err("A scenario named 'BEST' already exists".to_string())
+    );
+    assert_eq!(
+        model.add_scenario(0, scenario(" ", &[(1, 1, "1")])),
+        Err("The scenario name cannot be empty".to_string())
+    );
+    assert_eq!(
+        model.add_scenario(0, scenario(&"x".repeat(256), &[(1, 1, "1")])),
+        Err("The scenario name cannot be longer than 255 characters".to_string())
+    );
+    assert_eq!(
+        model.add_scenario(0, scenario("Empty", &[])),
+        Err("A scenario needs at least one changing cell".to_string())
+    );
+    let many: Vec<(i32, i32, &str)> = (1..=33).map(|row| (row, 1, "1")).collect();
+    assert_eq!(
+        model.add_scenario(0, scenario("Many", &many)),
+        Err("A scenario cannot have more than 32 changing cells".to_string())
+    );
+    assert_eq!(
+        model.add_scenario(0, scenario("Invalid", &[(0, 1, "1")])),
+        Err("Invalid changing cell".to_string())
+    );
+    assert_eq!(
+        model.add_scenario(0, scenario("Twice", &[(1, 1, "1"), (1, 1, "2")])),
+        Err("A changing cell appears twice in the scenario".to_string())
+    );
+    // A scenario can keep its own name
+    model
+        .update_scenario(0, "Best", scenario("Best", &[(2, 1, "3")]))
+        .unwrap();
+    assert_eq!(model.get_scenarios(0).unwrap().len(), 1);
+}
+
+#[test]
+fn show_scenario() {
+    let mut model = new_empty_model();
+    model._set("A1", "100");
+    model._set("A2", "0.1");
+    model._set("A3", "=A1*(1+A2)");
+    model
+        .add_scenario(0, scenario("Growth", &[(1, 1, "200"), (2, 1, "0.5")]))
+        .unwrap();
+    model.evaluate();
+    assert_eq!(model._get_text("A3"), "110");
+
+    model.show_scenario(0, "Growth").unwrap();
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), "200");
+    assert_eq!(model._get_text("A3"), "300");
+    assert!(model.show_scenario(0, "Decline").is_err());
+}
+
+#[test]
+fn show_scenario_in_other_locale() {
+    let mut model = Model::new_empty("model", "de", "UTC", "de").unwrap();
+    model.set_r1c1(true);
+    model
+        .add_scenario(
+            0,
+            scenario("Growth", &[(1, 1, "0.5"), (2, 1, "=SUM(A1,1.5)")]),
+        )
+        .unwrap();
+    model.show_scenario(0, "Growth").unwrap();
+    model.evaluate();
+    assert_eq!(model._get_text("A1"), "0,5");
+    assert_eq!(model._get_text("A2"), "2");
+    assert_eq!(
+        model.get_localized_cell_content(0, 2, 1).unwrap(),
+        "=SUMME(Z[-1]S;1,5)"
+    );
+}
+
+#[test]
+fn changing_cells_are_displaced() {
+    let mut model = new_empty_model();
+    model
+        .add_scenario(0, scenario("First", &[(2, 2, "1"), (5, 5, "2")]))
+        .unwrap();
+    model
+        .add_scenario(0, scenario("Second", &[(5, 5, "3")]))
+        .unwrap();
+    let cells = |model: &Model| -> Vec<Vec<(i32, i32)>> {
+        model
+            .get_scenarios(0)
+            .unwrap()
+            .iter()
+            .map(|scenario| {
+                scenario
+                    .input_cells
+                    .iter()
+                    .map(|cell| (cell.row, cell.column))
+                    .collect()
+            })
+            .collect()
+    };
+
+    model.insert_rows(0, 1, 2).unwrap();
+    assert_eq!(cells(&model), vec![vec![(4, 2), (7, 5)], vec![(7, 5)]]);
+    model.insert_columns(0, 3, 1).unwrap();
+    assert_eq!(cells(&model), vec![vec![(4, 2), (7, 6)], vec![(7, 6)]]);
+    model
+        .insert_cells(
+            &Area {
+                sheet: 0,
+                row: 4,
+                column: 1,
+                width: 1,
+                height: 1,
+            },
+            ShiftDirection::Right,
+        )
+        .unwrap();
+    assert_eq!(cells(&model), vec![vec![(4, 3), (7, 6)], vec![(7, 6)]]);
+
+    // Deleted changing cells are removed, and so are the scenarios left without cells
+    model.delete_rows(0, 7, 1).unwrap();
+    assert_eq!(cells(&model), vec![vec![(4, 3)]]);
+    model.delete_columns(0, 1, 1).unwrap();
+    assert_eq!(cells(&model), vec![vec![(4, 2)]]);
+}
+
+#[test]
+fn scenario_summary() {
+    let mut model = new_empty_model();
+    model._set("A1", "100");
+    model._set("A2", "0.1");
+    model._set("A3", "=A1*(1+A2)");
+    model._set("A4", "=IF(A3>150,\"High\",\"Low\")");
+    model
+        .add_scenario(0, scenario("Growth", &[(1, 1, "200"), (2, 1, "0.5")]))
+        .unwrap();
+    model
+        .add_scenario(0, scenario("Decline", &[(2, 1, "-0.5")]))
+        .unwrap();
+    model.evaluate();
+    let result_cells = [
+        CellReferenceIndex {
+            sheet: 0,
+            row: 3,
+            column: 1,
+        },
+        CellReferenceIndex {
+            sheet: 0,
+            row: 4,
+            column: 1,
+        },
+    ];
+    let index = model.create_scenario_summary(0, &result_cells).unwrap();
+    assert_eq!(index, 1);
+    assert_eq!(model.workbook.get_worksheet_names()[1], "Scenario Summary");
+
+    // The changing cells keep their values
+    assert_eq!(model._get_text("A1"), "100");
+    assert_eq!(model._get_text("A2"), "0.1");
+    assert_eq!(model._get_text("A3"), "110");
+
+    let text = |row: i32, column: i32| model.get_formatted_cell_value(1, row, column).unwrap();
+    assert_eq!(text(2, 2), "Scenario Summary");
+    assert_eq!(text(3, 4), "Current Values:");
+    assert_eq!(text(3, 5), "Growth");
+    assert_eq!(text(3, 6), "Decline");
+    assert_eq!(text(4, 2), "Changing Cells:");
+    assert_eq!(text(5, 3), "$A$1");
+    assert_eq!(text(5, 4), "100");
+    assert_eq!(text(5, 5), "200");
+    assert_eq!(text(5, 6), "");
+    assert_eq!(text(6, 3), "$A$2");
+    assert_eq!(text(6, 4), "0.1");
+    assert_eq!(text(6, 5), "0.5");
+    assert_eq!(text(6, 6), "-0.5");
+    assert_eq!(text(7, 2), "Result Cells:");
+    assert_eq!(text(8, 3), "$A$3");
+    assert_eq!(text(8, 4), "110");
+    assert_eq!(text(8, 5), "300");
+    assert_eq!(text(8, 6), "50");
+    assert_eq!(text(9, 3), "$A$4");
+    assert_eq!(text(9, 4), "Low");
+    assert_eq!(text(9, 5), "High");
+    assert_eq!(text(9, 6), "Low");
+
+    // A second summary gets a new name
+    let index = model.create_scenario_summary(0, &[]).unwrap();
+    assert_eq!(
+        model.workbook.get_worksheet_names()[index as usize],
+        "Scenario Summary 2"
+    );
+}
+
+#[test]
+fn scenario_summary_without_scenarios() {
+    let mut model = new_empty_model();
+    assert_eq!(
+        model.create_scenario_summary(0, &[]),
+        Err("There are no scenarios in the sheet".to_string())
+    );
+    assert_eq!(model.workbook.get_worksheet_names().len(), 1);
+}
@@ -0,0 +1,439 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::{Area, CellReferenceIndex};
+use crate::solver::{
+    ConstraintOperator, SolverConstraint, SolverGoal, SolverMethod, SolverOptions, SolverProblem,
+    SolverStatus,
+};
+use crate::test::util::new_empty_model;
+use crate::types::DefinedName;
+
+fn area(row: i32, column: i32, height: i32, width: i32) -> Area {
+    Area {
+        sheet: 0,
+        row,
+        column,
+        width,
+        height,
+    }
+}
+
+fn constraint(left: Area, operator: ConstraintOperator, right: &str) -> SolverConstraint {
+    SolverConstraint {
+        left,
+        operator,
+        right: right.to_string(),
+    }
+}
+
+fn problem(
+    objective: (i32, i32),
+    goal: SolverGoal,
+    variables: Area,
+    constraints: Vec<SolverConstraint>,
+) -> SolverProblem {
+    SolverProblem {
+        objective: CellReferenceIndex {
+            sheet: 0,
+            row: objective.0,
+            column: objective.1,
+        },
+        goal,
+        variables: vec![variables],
+        constraints,
+        options: SolverOptions::default(),
+    }
+}
+
+fn assert_close(value: f64, expected: f64) {
+    assert!(
+        (value - expected).abs() < 1e-3,
+        "Expected {expected}, got {value}"
+    );
+}
+
+#[test]
+fn linear_production_mix() {
+    let mut model = new_empty_model();
+    // Units of two products in A1:B1, profit per unit in A2:B2
+    // Hours per unit in three plants in A3:B5 and hours available in F3:F5
+    model._set("A2", "3");
+    model._set("B2", "5");
+    model._set("A3", "1");
+    model._set("B3", "0");
+    model._set("A4", "0");
+    model._set("B4", "2");
+    model._set("A5", "3");
+    model._set("B5", "2");
+    model._set("E2", "=SUMPRODUCT(A1:B1, A2:B2)");
+    model._set("E3", "=SUMPRODUCT(A1:B1, A3:B3)");
+    model._set("E4", "=SUMPRODUCT(A1:B1, A4:B4)");
+    model._set("E5", "=SUMPRODUCT(A1:B1, A5:B5)");
+    model._set("F3", "4");
+    model._set("F4", "12");
+    model._set("F5", "18");
+    model.evaluate();
+
+    let problem = problem(
+        (2, 5),
+        SolverGoal::Maximize,
+        area(1, 1, 1, 2),
+        vec![constraint(
+            area(3, 5, 3, 1),
+            ConstraintOperator::LessOrEqual,
+            "F3:F5",
+        )],
+    );
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert!(result.linear);
+    assert_eq!(result.values, vec![2.0, 6.0]);
+    assert_eq!(result.objective, 36.0);
+    assert_eq!(model._get_text("E2"), "36");
+    assert_eq!(model._get_text("B1"), "6");
+}
+
+#[test]
+fn integer_and_binary_constraints() {
+    let mut model = new_empty_model();
+    // Knapsack: values in A2:D2, weights in A3:D3, chosen items in A1:D1
+    model._set("A2", "10");
+    model._set("B2", "13");
+    model._set("C2", "7");
+    model._set("D2", "8");
+    model._set("A3", "5");
+    model._set("B3", "7");
+    model._set("C3", "4");
+    model._set("D3", "3");
+    model._set("F2", "=SUMPRODUCT(A1:D1, A2:D2)");
+    model._set("F3", "=SUMPRODUCT(A1:D1, A3:D3)");
+    model.evaluate();
+
+    let mut knapsack = problem(
+        (2, 6),
+        SolverGoal::Maximize,
+        area(1, 1, 1, 4),
+        vec![
+            constraint(area(3, 6, 1, 1), ConstraintOperator::LessOrEqual, "14"),
+            constraint(area(1, 1, 1, 4), ConstraintOperator::Binary, ""),
+        ],
+    );
+    let result = model.solve(&knapsack).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(result.values, vec![0.0, 1.0, 1.0, 1.0]);
+    assert_eq!(model._get_text("F2"), "28");
+
+    // Any number of each item
+    knapsack.constraints[1].operator = ConstraintOperator::Integer;
+    let result = model.solve(&knapsack).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(result.values, vec![1.0, 0.0, 0.0, 3.0]);
+    assert_eq!(model._get_text("F2"), "34");
+}
+
+#[test]
+fn minimize_with_range_constraints() {
+    let mut model = new_empty_model();
+    // Cost of each source and minimum amounts
+    model._set("A2", "3");
+    model._set("B2", "5");
+    model._set("C2", "4");
+    model._set("A3", "1");
+    model._set("B3", "2");
+    model._set("C3", "0");
+    model._set("E1", "=SUMPRODUCT(A1:C1, A2:C2)");
+    model._set("E2", "=SUM(A1:C1)");
+    model.evaluate();
+
+    let problem = problem(
+        (1, 5),
+        SolverGoal::Minimize,
+        area(1, 1, 1, 3),
+        vec![
+            constraint(
+                area(1, 1, 1, 3),
+                ConstraintOperator::GreaterOrEqual,
+                "=A3:C3",
+            ),
+            constraint(area(2, 5, 1, 1), ConstraintOperator::Equal, "10"),
+        ],
+    );
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(result.values, vec![8.0, 2.0, 0.0]);
+    assert_eq!(result.objective, 34.0);
+}
+
+#[test]
+fn value_goal() {
+    let mut model = new_empty_model();
+    model._set("B1", "=3*A1+2");
+    model.evaluate();
+    let problem = problem(
+        (1, 2),
+        SolverGoal::Value { value: 17.0 },
+        area(1, 1, 1, 1),
+        vec![],
+    );
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(model._get_text("A1"), "5");
+}
+
+#[test]
+fn infeasible_and_unbounded() {
+    let mut model = new_empty_model();
+    model._set("A1", "7");
+    model._set("C1", "=A1+B1");
+    model.evaluate();
+
+    let mut problem = problem(
+        (1, 3),
+        SolverGoal::Maximize,
+        area(1, 1, 1, 2),
+        vec![constraint(
+            area(1, 3, 1, 1),
+            ConstraintOperator::LessOrEqual,
+            "-1",
+        )],
+    );
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Infeasible);
+    // The original values are restored
+    assert_eq!(result.values, vec![7.0, 0.0]);
+    assert_eq!(model._get_text("A1"), "7");
+    assert_eq!(model._get_text("B1"), "");
+    assert_eq!(model._get_text("C1"), "7");
+
+    // Without the non negative condition it is possible
+    problem.options.non_negative = false;
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(model._get_text("C1"), "-1");
+
+    problem.constraints.clear();
+    problem.options.non_negative = true;
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Unbounded);
+    assert_eq!(model._get_text("C1"), "-1");
+}
+
+#[test]
+fn nonlinear() {
+    let mut model = new_empty_model();
+    model._set("C1", "=(A1-3)^2+(B1+1)^2");
+    model.evaluate();
+
+    let mut problem = problem((1, 3), SolverGoal::Minimize, area(1, 1, 1, 2), vec![]);
+    problem.options.non_negative = false;
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Converged);
+    assert!(!result.linear);
+    assert_close(result.values[0], 3.0);
+    assert_close(result.values[1], -1.0);
+
+    // B1 can not be negative
+    problem.options.non_negative = true;
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Converged);
+    assert_close(result.values[0], 3.0);
+    assert_close(result.values[1], 0.0);
+    assert_close(result.objective, 1.0);
+
+    // The simplex method needs a linear model
+    problem.options.method = SolverMethod::Simplex;
+    assert_eq!(
+        model.solve(&problem),
+        Err("The model is not linear".to_string())
+    );
+}
+
+#[test]
+fn nonlinear_with_constraints() {
+    let mut model = new_empty_model();
+    // A box with the largest volume for a given surface
+    model._set("A1", "1");
+    model._set("B1", "1");
+    model._set("C1", "1");
+    model._set("E1", "=A1*B1*C1");
+    model._set("E2", "=2*(A1*B1+B1*C1+A1*C1)");
+    model.evaluate();
+
+    let problem = problem(
+        (1, 5),
+        SolverGoal::Maximize,
+        area(1, 1, 1, 3),
+        vec![constraint(
+            area(2, 5, 1, 1),
+            ConstraintOperator::Equal,
+            "24",
+        )],
+    );
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Converged);
+    // A cube of side 2
+    for value in result.values {
+        assert_close(value, 2.0);
+    }
+    assert_close(result.objective, 8.0);
+}
+
+#[test]
+fn nonlinear_with_integers() {
+    let mut model = new_empty_model();
+    model._set("C1", "=(A1-2.6)^2+(B1-1.3)^2");
+    model.evaluate();
+    let problem = problem(
+        (1, 3),
+        SolverGoal::Minimize,
+        area(1, 1, 1, 2),
+        vec![constraint(
+            area(1, 1, 1, 1),
+            ConstraintOperator::Integer,
+            "",
+        )],
+    );
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Converged);
+    assert_eq!(result.values[0], 3.0);
+    assert_close(result.values[1], 1.3);
+}
+
+#[test]
+fn invalid_problems() {
+    let mut model = new_empty_model();
+    model._set("A1", "Text");
+    model._set("B1", "=A1");
+    model._set("C1", "3");
+    model.evaluate();
+
+    let mut problem = problem((1, 3), SolverGoal::Maximize, area(1, 2, 1, 1), vec![]);
+    assert_eq!(
+        model.solve(&problem),
+        Err("The objective cell must contain a formula".to_string())
+    );
+    problem.objective.column = 2;
+    problem.variables = vec![area(1, 1, 1, 1)];
+    assert_eq!(
+        model.solve(&problem),
+        Err("The changing cells must contain numbers".to_string())
+    );
+    problem.variables = vec![area(1, 4, 1, 1)];
+    problem.constraints = vec![constraint(
+        area(1, 5, 1, 1),
+        ConstraintOperator::Integer,
+        "",
+    )];
+    assert_eq!(
+        model.solve(&problem),
+        Err(
+            "Constraint 1: integer and binary constraints must refer to changing cells".to_string()
+        )
+    );
+    problem.constraints = vec![constraint(
+        area(1, 4, 1, 1),
+        ConstraintOperator::LessOrEqual,
+        "F1:F3",
+    )];
+    assert_eq!(
+        model.solve(&problem),
+        Err(
+            "Constraint 1: the right side must be a single value or have the size of the left side"
+                .to_string()
+        )
+    );
+}
+
+#[test]
+fn solver_defined_names() {
+    let mut model = new_empty_model();
+    model.new_sheet();
+    model._set("D1", "=A1+B1");
+    model.evaluate();
+    assert_eq!(model.get_solver_problem(0), Ok(None));
+
+    let mut problem = problem(
+        (1, 4),
+        SolverGoal::Value { value: 12.5 },
+        area(1, 1, 1, 2),
+        vec![
+            constraint(
+                area(1, 1, 1, 2),
+                ConstraintOperator::LessOrEqual,
+                "Sheet2!$A$1:$B$1",
+            ),
+            constraint(area(1, 2, 1, 1), ConstraintOperator::Integer, ""),
+        ],
+    );
+    problem.variables.push(area(3, 3, 2, 1));
+    problem.options = SolverOptions {
+        non_negative: false,
+        method: SolverMethod::Simplex,
+        max_iterations: 50,
+        precision: 0.0001,
+    };
+    model.set_solver_problem(0, &problem).unwrap();
+    let names: Vec<(String, String)> = model
+        .workbook
+        .defined_names
+        .iter()
+        .map(|name| (name.name.clone(), name.formula.clone()))
+        .collect();
+    assert!(names.contains(&("solver_opt".to_string(), "Sheet1!$D$1".to_string())));
+    assert!(names.contains(&(
+        "solver_adj".to_string(),
+        "Sheet1!$A$1:$B$1,Sheet1!$C$3:$C$4".to_string()
+    )));
+    assert!(names.contains(&("solver_rhs2".to_string(), "integer".to_string())));
+    assert_eq!(model.get_solver_problem(0), Ok(Some(problem.clone())));
+    assert_eq!(model.get_solver_problem(1), Ok(None));
+
+    // Setting it again replaces the names
+    let count = model.workbook.defined_names.len();
+    problem.constraints.pop();
+    model.set_solver_problem(0, &problem).unwrap();
+    assert_eq!(model.workbook.defined_names.len(), count - 3);
+    assert_eq!(model.get_solver_problem(0), Ok(Some(problem)));
+}
+
+#[test]
+fn names_saved_by_excel() {
+    let mut model = new_empty_model();
+    model._set("A2", "2");
+    model._set("B2", "3");
+    model._set("D1", "=SUMPRODUCT(A1:B1, A2:B2)");
+    model._set("D2", "=A1+B1");
+    model.evaluate();
+    let sheet_id = Some(model.workbook.worksheets[0].sheet_id);
+    for (name, formula) in [
+        ("solver_adj", "Sheet1!$A$1:$B$1"),
+        ("solver_cvg", "0.0001"),
+        ("solver_eng", "2"),
+        ("solver_itr", "2147483647"),
+        ("solver_lhs1", "Sheet1!$D$2"),
+        ("solver_neg", "1"),
+        ("solver_num", "1"),
+        ("solver_opt", "Sheet1!$D$1"),
+        ("solver_pre", "0.000001"),
+        ("solver_rel1", "1"),
+        ("solver_rhs1", "10"),
+        ("solver_typ", "1"),
+        ("solver_val", "0"),
+        ("solver_ver", "3"),
+    ] {
+        model.workbook.defined_names.push(DefinedName {
+            name: name.to_string(),
+            formula: formula.to_string(),
+            sheet_id,
+            hidden: true,
+        });
+    }
+    let problem = model.get_solver_problem(0).unwrap().unwrap();
+    assert_eq!(problem.goal, SolverGoal::Maximize);
+    assert_eq!(problem.options.method, SolverMethod::Simplex);
+    assert_eq!(problem.constraints[0].right, "10");
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(model._get_text("D1"), "30");
+    assert_eq!(model._get_text("B1"), "10");
+}
@@ -1,6 +1,6 @@
 #![allow(clippy::unwrap_used)]
 
-use crate::types::{Alignment, HorizontalAlignment, VerticalAlignment};
+use crate::types::{Alignment, HorizontalAlignment, ReadingOrder, VerticalAlignment};
 
 #[test]
 fn alignment_default() {
@@ -10,7 +10,12 @@ fn alignment_default() {
         Alignment {
             horizontal: HorizontalAlignment::General,
             vertical: VerticalAlignment::Bottom,
-            wrap_text: false
+            wrap_text: false,
+            text_rotation: 0,
+            indent: 0,
+            shrink_to_fit: false,
+            justify_last_line: false,
+            reading_order: ReadingOrder::ContextDependent,
         }
     );
 
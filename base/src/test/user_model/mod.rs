@@ -5,16 +5,22 @@ mod test_autofill_rows;
 mod test_batch_row_column_diff;
 mod test_border;
 mod test_clear_cells;
+mod test_collaboration;
 mod test_column_style;
 mod test_conditional_formatting;
 mod test_cut_n_paste;
+mod test_data_table;
+mod test_date_system;
 mod test_defined_names;
 mod test_delete_row_column_formatting;
 mod test_diff_queue;
+mod test_duplicate_move_sheet;
 mod test_dynamic_arrays;
 mod test_evaluation;
+mod test_find_replace;
 mod test_fn_formulatext;
 mod test_general;
+mod test_goal_seek;
 mod test_grid_lines;
 mod test_hidden_columns;
 mod test_keyboard_navigation;
@@ -24,12 +30,20 @@ mod test_named_styles;
 mod test_on_area_selection;
 mod test_on_expand_selected_range;
 mod test_on_paste_styles;
+mod test_outline;
 mod test_paste_csv;
+mod test_paste_html;
+mod test_paste_special;
+mod test_portable_clipboard;
+mod test_r1c1_mode;
 mod test_recursive;
 mod test_rename_sheet;
 mod test_row_column;
+mod test_scenarios;
 mod test_sheet_state;
 mod test_sheets_undo_redo;
+mod test_shift_cells;
+mod test_solver;
 mod test_styles;
 mod test_to_from_bytes;
 mod test_undo_redo;
@@ -0,0 +1,392 @@
+#![allow(clippy::unwrap_used)]
+
+use std::collections::BTreeMap;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::collaboration::Collaboration;
+use crate::expressions::types::Area;
+use crate::test::util::new_empty_model;
+use crate::types::{Col, Row};
+use crate::UserModel;
+
+fn new_site(site: u32) -> (UserModel<'static>, Collaboration) {
+    let mut model = UserModel::from_model(new_empty_model());
+    let collaboration = Collaboration::new(site, &mut model);
+    (model, collaboration)
+}
+
+#[test]
+fn concurrent_insert_and_set_value() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+
+    model1.insert_rows(0, 2, 1).unwrap();
+    model2.set_user_input(0, 5, 1, "=A4+1").unwrap();
+    model2.set_user_input(0, 4, 1, "1").unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site1.receive(&mut model1, &from2).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+
+    for model in [&model1, &model2] {
+        assert_eq!(model.get_cell_content(0, 6, 1).unwrap(), "=A5+1");
+        assert_eq!(model.get_formatted_cell_value(0, 6, 1).unwrap(), "2");
+        assert_eq!(model.get_formatted_cell_value(0, 5, 1).unwrap(), "1");
+    }
+}
+
+#[test]
+fn edits_in_deleted_rows_are_dropped() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+
+    model1.delete_rows(0, 3, 2).unwrap();
+    model2.set_user_input(0, 4, 2, "Lost").unwrap();
+    model2.set_user_input(0, 6, 2, "Kept").unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site2.receive(&mut model2, &from1).unwrap();
+    site1.receive(&mut model1, &from2).unwrap();
+
+    for model in [&model1, &model2] {
+        assert_eq!(model.get_formatted_cell_value(0, 4, 2).unwrap(), "Kept");
+        assert_eq!(model.get_formatted_cell_value(0, 2, 2).unwrap(), "");
+        assert_eq!(model.get_formatted_cell_value(0, 3, 2).unwrap(), "");
+    }
+}
+
+#[test]
+fn same_cell_last_writer_wins() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+
+    model1.set_user_input(0, 1, 1, "One").unwrap();
+    model2.set_user_input(0, 1, 1, "Two").unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site1.receive(&mut model1, &from2).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+
+    // Same Lamport timestamp, the higher site goes last
+    assert_eq!(model1.get_formatted_cell_value(0, 1, 1).unwrap(), "Two");
+    assert_eq!(model2.get_formatted_cell_value(0, 1, 1).unwrap(), "Two");
+}
+
+#[test]
+fn out_of_order_and_duplicated_delivery() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+
+    model1.set_user_input(0, 1, 1, "First").unwrap();
+    let first = site1.flush(&mut model1);
+    model1.insert_rows(0, 1, 1).unwrap();
+    let second = site1.flush(&mut model1);
+
+    // The second operation waits for the first one
+    site2.receive(&mut model2, &second).unwrap();
+    assert_eq!(model2.get_formatted_cell_value(0, 1, 1).unwrap(), "");
+    assert_eq!(site2.clock().get(1), 0);
+
+    site2.receive(&mut model2, &first).unwrap();
+    site2.receive(&mut model2, &first).unwrap();
+    assert_eq!(model2.get_formatted_cell_value(0, 2, 1).unwrap(), "First");
+    assert_eq!(site2.clock().get(1), 2);
+    assert_eq!(site2.clock(), site1.clock());
+}
+
+#[test]
+fn concurrent_sheets() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+    model1.new_sheet().unwrap();
+    let from1 = site1.flush(&mut model1);
+    site2.receive(&mut model2, &from1).unwrap();
+
+    // Site 1 deletes the first sheet while site 2 writes in the second one
+    model1.delete_sheet(0).unwrap();
+    model2.set_user_input(1, 1, 1, "Moved").unwrap();
+    model2.set_user_input(0, 1, 1, "Lost").unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site1.receive(&mut model1, &from2).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+
+    for model in [&model1, &model2] {
+        assert_eq!(model.get_worksheets_properties().len(), 1);
+        assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Moved");
+    }
+}
+
+#[test]
+fn concurrent_sheet_moves() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+    model1.new_sheet().unwrap();
+    model1.new_sheet().unwrap();
+    let from1 = site1.flush(&mut model1);
+    site2.receive(&mut model2, &from1).unwrap();
+
+    // Site 1 moves the last sheet to the front while site 2 writes in it and duplicates the first one
+    model1.move_sheet(2, 0).unwrap();
+    model2.set_user_input(2, 1, 1, "Third").unwrap();
+    model2.duplicate_sheet(0, "Copy", 1).unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site1.receive(&mut model1, &from2).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+
+    for model in [&model1, &model2] {
+        let names: Vec<String> = model
+            .get_worksheets_properties()
+            .into_iter()
+            .map(|properties| properties.name)
+            .collect();
+        assert_eq!(names, vec!["Sheet3", "Sheet1", "Copy", "Sheet2"]);
+        assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "Third");
+    }
+}
+
+#[test]
+fn undo_is_shared() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+    model1.set_user_input(0, 3, 3, "Hello").unwrap();
+    let from1 = site1.flush(&mut model1);
+    site2.receive(&mut model2, &from1).unwrap();
+
+    model1.undo().unwrap();
+    model2.insert_columns(0, 1, 2).unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site1.receive(&mut model1, &from2).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+
+    for model in [&model1, &model2] {
+        assert_eq!(model.get_formatted_cell_value(0, 3, 5).unwrap(), "");
+        assert_eq!(model.get_formatted_cell_value(0, 3, 3).unwrap(), "");
+    }
+}
+
+#[test]
+fn invalid_operations() {
+    let (mut model, mut site) = new_site(1);
+    assert_eq!(
+        site.receive(&mut model, b"invalid"),
+        Err("Error parsing operations".to_string())
+    );
+}
+
+#[test]
+fn sites_join_a_session() {
+    // Site 1 hands out the ids of the sites that join
+    let (mut model1, mut site1) = new_site(1);
+    let (site, state) = site1.add_site(&mut model1);
+    assert_eq!(site, 2);
+    let mut model2 = UserModel::from_bytes(&model1.to_bytes(), "en").unwrap();
+    let mut site2 = Collaboration::from_bytes(&state).unwrap();
+    model1.set_user_input(0, 1, 1, "1").unwrap();
+    let from1 = site1.flush(&mut model1);
+    // An operation of site 2 that is concurrent with the one of site 1
+    model2.insert_rows(0, 1, 1).unwrap();
+    let from2 = site2.flush(&mut model2);
+
+    // Site 3 starts from site 1, before it gets the operation of site 2
+    let (site, state) = site1.add_site(&mut model1);
+    assert_eq!(site, 3);
+    let mut model3 = UserModel::from_bytes(&model1.to_bytes(), "en").unwrap();
+    let mut site3 = Collaboration::from_bytes(&state).unwrap();
+    assert_eq!(site3.site_id(), 3);
+    model3.set_user_input(0, 1, 2, "=A1*2").unwrap();
+    let from3 = site3.flush(&mut model3);
+
+    site1.receive(&mut model1, &from2).unwrap();
+    site1.receive(&mut model1, &from3).unwrap();
+    site2.receive(&mut model2, &from3).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+    site3.receive(&mut model3, &from2).unwrap();
+    site3.receive(&mut model3, &from1).unwrap();
+
+    for model in [&model1, &model2, &model3] {
+        assert_eq!(model.get_formatted_cell_value(0, 2, 1).unwrap(), "1");
+        assert_eq!(model.get_cell_content(0, 2, 2).unwrap(), "=A2*2");
+        assert_eq!(model.get_formatted_cell_value(0, 2, 2).unwrap(), "2");
+    }
+
+    // The state can be stored and loaded again
+    let mut site1 = Collaboration::from_bytes(&site1.to_bytes()).unwrap();
+    model3.set_user_input(0, 5, 1, "5").unwrap();
+    let from3 = site3.flush(&mut model3);
+    site1.receive(&mut model1, &from3).unwrap();
+    assert_eq!(model1.get_formatted_cell_value(0, 5, 1).unwrap(), "5");
+    assert!(Collaboration::from_bytes(b"invalid").is_err());
+}
+
+/// A network of sites that edit the same workbook. Messages are delivered late and in any order.
+struct Network {
+    sites: Vec<(UserModel<'static>, Collaboration)>,
+    /// Messages in transit: (destination, bytes)
+    in_transit: Vec<(usize, Vec<u8>)>,
+    rng: StdRng,
+}
+
+impl Network {
+    fn new(site_count: usize, seed: u64) -> Network {
+        let mut sites: Vec<(UserModel, Collaboration)> =
+            (0..site_count).map(|i| new_site(i as u32 + 1)).collect();
+        for (_, collaboration) in sites.iter_mut() {
+            for peer in 0..site_count {
+                collaboration.add_peer(peer as u32 + 1);
+            }
+        }
+        Network {
+            sites,
+            in_transit: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn random_edit(&mut self, site: usize) {
+        let rng = &mut self.rng;
+        let model = &mut self.sites[site].0;
+        let row = rng.gen_range(1..8);
+        let column = rng.gen_range(1..6);
+        let count = rng.gen_range(1..3);
+        let sheet = rng.gen_range(0..model.get_worksheets_properties().len()) as u32;
+        let result = match rng.gen_range(0..12) {
+            0..=3 => {
+                model.set_user_input(sheet, row, column, &format!("{}", rng.gen_range(0..100)))
+            }
+            4 => model.set_user_input(sheet, row, column, &format!("=A{}+B{}", row + 1, row)),
+            5 => model.insert_rows(sheet, row, count),
+            6 => model.delete_rows(sheet, row, count),
+            7 => model.insert_columns(sheet, column, count),
+            8 => model.delete_columns(sheet, column, count),
+            9 => model.set_columns_width(sheet, column, column, rng.gen_range(20.0..200.0)),
+            10 => model.undo(),
+            _ => model.new_sheet(),
+        };
+        result.unwrap();
+    }
+
+    fn send(&mut self, site: usize) {
+        let (model, collaboration) = &mut self.sites[site];
+        let bytes = collaboration.flush(model);
+        for destination in 0..self.sites.len() {
+            if destination != site {
+                self.in_transit.push((destination, bytes.clone()));
+            }
+        }
+    }
+
+    fn deliver(&mut self, index: usize) {
+        let (destination, bytes) = self.in_transit.swap_remove(index);
+        let (model, collaboration) = &mut self.sites[destination];
+        collaboration.receive(model, &bytes).unwrap();
+    }
+
+    fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            let site = self.rng.gen_range(0..self.sites.len());
+            match self.rng.gen_range(0..4) {
+                0 | 1 => self.random_edit(site),
+                2 => self.send(site),
+                _ => {
+                    if !self.in_transit.is_empty() {
+                        let index = self.rng.gen_range(0..self.in_transit.len());
+                        self.deliver(index);
+                    }
+                }
+            }
+        }
+        for site in 0..self.sites.len() {
+            self.send(site);
+        }
+        while !self.in_transit.is_empty() {
+            let index = self.rng.gen_range(0..self.in_transit.len());
+            self.deliver(index);
+        }
+    }
+}
+
+/// Contents of the cells, column widths and row heights of every sheet.
+/// The order of the internal lists (e.g. shared formulas) depends on the history of each site.
+type SheetState = (
+    String,
+    Vec<Col>,
+    Vec<Row>,
+    BTreeMap<(i32, i32), (String, String)>,
+);
+
+fn shared_state(model: &UserModel) -> Vec<SheetState> {
+    let mut state = Vec::new();
+    for (sheet, worksheet) in model.get_model().workbook.worksheets.iter().enumerate() {
+        let mut cells = BTreeMap::new();
+        for (row, data_row) in &worksheet.sheet_data {
+            for column in data_row.keys() {
+                let sheet = sheet as u32;
+                let content = model.get_cell_content(sheet, *row, *column).unwrap();
+                let value = model
+                    .get_formatted_cell_value(sheet, *row, *column)
+                    .unwrap();
+                cells.insert((*row, *column), (content, value));
+            }
+        }
+        state.push((
+            worksheet.name.clone(),
+            worksheet.cols.clone(),
+            worksheet.rows.clone(),
+            cells,
+        ));
+    }
+    state
+}
+
+#[test]
+fn random_concurrent_edits_converge() {
+    for seed in 0..20 {
+        let mut network = Network::new(3, seed);
+        network.run(150);
+        let expected = shared_state(&network.sites[0].0);
+        for (model, collaboration) in &network.sites[1..] {
+            assert_eq!(
+                collaboration.clock(),
+                network.sites[0].1.clock(),
+                "seed {seed}"
+            );
+            assert_eq!(shared_state(model), expected, "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn merged_cells_move_with_their_rows() {
+    let (mut model1, mut site1) = new_site(1);
+    let (mut model2, mut site2) = new_site(2);
+
+    model1.insert_rows(0, 1, 2).unwrap();
+    let area = Area {
+        sheet: 0,
+        row: 3,
+        column: 2,
+        width: 1,
+        height: 1,
+    };
+    model2.set_selected_cell(3, 2).unwrap();
+    model2
+        .paste_html(&area, "<table><tr><td colspan=2>Merged</td></tr></table>")
+        .unwrap();
+    let from1 = site1.flush(&mut model1);
+    let from2 = site2.flush(&mut model2);
+    site1.receive(&mut model1, &from2).unwrap();
+    site2.receive(&mut model2, &from1).unwrap();
+
+    for model in [&model1, &model2] {
+        assert_eq!(
+            model.model.workbook.worksheets[0].merge_cells,
+            vec!["B5:C5".to_string()]
+        );
+        assert_eq!(model.get_formatted_cell_value(0, 5, 2).unwrap(), "Merged");
+    }
+}
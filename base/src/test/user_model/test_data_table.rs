@@ -0,0 +1,68 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::{Area, CellReferenceIndex};
+use crate::test::user_model::util::new_empty_user_model;
+
+#[test]
+fn create_data_table_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "2").unwrap();
+    model.set_user_input(0, 3, 1, "=A1*10").unwrap();
+    model.set_user_input(0, 4, 1, "1").unwrap();
+    model.set_user_input(0, 5, 1, "3").unwrap();
+    model.set_user_input(0, 3, 2, "=A1+1").unwrap();
+    model.set_user_input(0, 3, 3, "=A1*A1").unwrap();
+    // A3:C5, the values in A4:A5 and the formulas in B3:C3
+    let area = Area {
+        sheet: 0,
+        row: 3,
+        column: 1,
+        width: 3,
+        height: 3,
+    };
+    let input = CellReferenceIndex {
+        sheet: 0,
+        row: 1,
+        column: 1,
+    };
+    model.create_data_table(&area, None, Some(&input)).unwrap();
+
+    assert_eq!(
+        model.get_cell_content(0, 4, 2),
+        Ok("=TABLE(,A1)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2), Ok("2".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 5, 2), Ok("4".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 4, 3), Ok("1".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 5, 3), Ok("9".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 1),
+        Ok("20".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2), Ok("".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 5, 3), Ok("".to_string()));
+
+    model.redo().unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 3), Ok("9".to_string()));
+}
+
+#[test]
+fn create_data_table_errors() {
+    let mut model = new_empty_user_model();
+    let area = Area {
+        sheet: 0,
+        row: 3,
+        column: 1,
+        width: 3,
+        height: 3,
+    };
+    let inside = CellReferenceIndex {
+        sheet: 0,
+        row: 4,
+        column: 2,
+    };
+    assert!(model.create_data_table(&area, None, None).is_err());
+    assert!(model.create_data_table(&area, Some(&inside), None).is_err());
+}
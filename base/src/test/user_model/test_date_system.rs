@@ -0,0 +1,54 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::user_model::util::new_empty_user_model;
+
+#[test]
+fn convert_date_system_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "2024-06-15").unwrap();
+    model.set_user_input(0, 1, 2, "=YEAR(A1)").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "2024-06-15"
+    );
+
+    model.convert_date_system(true).unwrap();
+    assert!(model.get_date1904());
+    assert_eq!(model.get_cell_content(0, 1, 1).unwrap(), "2024-06-15");
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "2024");
+
+    model.undo().unwrap();
+    assert!(!model.get_date1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "2024-06-15"
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "2024");
+
+    model.redo().unwrap();
+    assert!(model.get_date1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "2024-06-15"
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "2024");
+}
+
+#[test]
+fn set_date1904_shifts_dates() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "2024-06-15").unwrap();
+
+    model.set_date1904(true).unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "2028-06-16"
+    );
+
+    model.undo().unwrap();
+    assert!(!model.get_date1904());
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "2024-06-15"
+    );
+}
@@ -0,0 +1,182 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::user_model::util::new_empty_user_model;
+use crate::types::{Table, TableColumn, TableStyleInfo};
+
+fn sheet_names(model: &crate::UserModel) -> Vec<String> {
+    model
+        .get_worksheets_properties()
+        .into_iter()
+        .map(|properties| properties.name)
+        .collect()
+}
+
+#[test]
+fn duplicate_sheet() {
+    let mut model = new_empty_user_model();
+    model.new_sheet().unwrap();
+    model.set_user_input(0, 1, 1, "21").unwrap();
+    model.set_user_input(0, 2, 1, "=A1*2").unwrap();
+    model
+        .set_user_input(0, 3, 1, "=Sheet1!A1+Sheet2!A1")
+        .unwrap();
+    model.set_user_input(1, 1, 1, "=Sheet1!A2").unwrap();
+    model
+        .new_defined_name("Local", Some(0), "Sheet1!$A$1")
+        .unwrap();
+    model
+        .new_defined_name("Global", None, "Sheet1!$A$2")
+        .unwrap();
+
+    model.duplicate_sheet(0, "Copy", 1).unwrap();
+
+    assert_eq!(sheet_names(&model), vec!["Sheet1", "Copy", "Sheet2"]);
+    assert_eq!(model.get_selected_sheet(), 1);
+    assert_eq!(model.get_cell_content(1, 2, 1), Ok("=A1*2".to_string()));
+    assert_eq!(
+        model.get_cell_content(1, 3, 1),
+        Ok("=Copy!A1+Sheet2!A1".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(1, 2, 1),
+        Ok("42".to_string())
+    );
+    // Formulas in other sheets are not affected
+    assert_eq!(
+        model.get_cell_content(2, 1, 1),
+        Ok("=Sheet1!A2".to_string())
+    );
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![
+            ("Local".to_string(), Some(0), "Sheet1!$A$1".to_string()),
+            ("Global".to_string(), None, "Sheet1!$A$2".to_string()),
+            ("Local".to_string(), Some(1), "Copy!$A$1".to_string()),
+        ]
+    );
+
+    // Changes in the copy do not change the original
+    model.set_user_input(1, 1, 1, "1").unwrap();
+    assert_eq!(model.get_formatted_cell_value(1, 2, 1), Ok("2".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("42".to_string())
+    );
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(sheet_names(&model), vec!["Sheet1", "Sheet2"]);
+    assert_eq!(model.get_defined_name_list().len(), 2);
+
+    model.redo().unwrap();
+    assert_eq!(sheet_names(&model), vec!["Sheet1", "Copy", "Sheet2"]);
+    assert_eq!(model.get_defined_name_list().len(), 3);
+}
+
+#[test]
+fn duplicate_sheet_errors() {
+    let mut model = new_empty_user_model();
+    assert!(model.duplicate_sheet(0, "Sheet1", 1).is_err());
+    assert!(model.duplicate_sheet(0, "Bad:Name", 1).is_err());
+    assert!(model.duplicate_sheet(0, "Copy", 2).is_err());
+    assert!(model.duplicate_sheet(1, "Copy", 1).is_err());
+    assert_eq!(sheet_names(&model), vec!["Sheet1"]);
+}
+
+#[test]
+fn duplicate_sheet_with_table() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "Amount").unwrap();
+    model.set_user_input(0, 2, 1, "3").unwrap();
+    model.set_user_input(0, 3, 1, "4").unwrap();
+    let table = Table {
+        name: "Sales".to_string(),
+        display_name: "Sales".to_string(),
+        sheet_name: "Sheet1".to_string(),
+        reference: "A1:A3".to_string(),
+        totals_row_count: 0,
+        header_row_count: 1,
+        header_row_dxf_id: None,
+        data_dxf_id: None,
+        totals_row_dxf_id: None,
+        columns: vec![TableColumn {
+            id: 1,
+            name: "Amount".to_string(),
+            ..Default::default()
+        }],
+        style_info: TableStyleInfo::default(),
+        has_filters: false,
+    };
+    model
+        .model
+        .workbook
+        .tables
+        .insert("Sales".to_string(), table);
+    model
+        .model
+        .parser
+        .set_tables(model.model.workbook.tables.clone());
+    model
+        .set_user_input(0, 1, 2, "=SUM(Sales[Amount])")
+        .unwrap();
+
+    model.duplicate_sheet(0, "Copy", 1).unwrap();
+
+    let table = model.model.workbook.tables.get("Sales_2").unwrap();
+    assert_eq!(table.sheet_name, "Copy");
+    assert_eq!(table.display_name, "Sales_2");
+    // The new table can be used in formulas
+    model
+        .set_user_input(1, 2, 2, "=SUM(Sales_2[Amount])")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(1, 2, 2), Ok("7".to_string()));
+    // The copied formula points to the copied table
+    model.set_user_input(1, 2, 1, "10").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(1, 1, 2),
+        Ok("14".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("7".to_string()));
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(!model.model.workbook.tables.contains_key("Sales_2"));
+}
+
+#[test]
+fn move_sheet() {
+    let mut model = new_empty_user_model();
+    model.new_sheet().unwrap();
+    model.new_sheet().unwrap();
+    model.set_user_input(0, 1, 1, "=Sheet3!A1").unwrap();
+    model.set_user_input(2, 1, 1, "5").unwrap();
+    model
+        .new_defined_name("Local", Some(2), "Sheet3!$A$1")
+        .unwrap();
+    model.set_selected_sheet(2).unwrap();
+
+    model.move_sheet(2, 0).unwrap();
+
+    assert_eq!(sheet_names(&model), vec!["Sheet3", "Sheet1", "Sheet2"]);
+    assert_eq!(model.get_selected_sheet(), 0);
+    assert_eq!(
+        model.get_cell_content(1, 1, 1),
+        Ok("=Sheet3!A1".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(1, 1, 1), Ok("5".to_string()));
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("Local".to_string(), Some(0), "Sheet3!$A$1".to_string())]
+    );
+
+    model.undo().unwrap();
+    assert_eq!(sheet_names(&model), vec!["Sheet1", "Sheet2", "Sheet3"]);
+    assert_eq!(model.get_selected_sheet(), 2);
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("5".to_string()));
+
+    model.redo().unwrap();
+    assert_eq!(sheet_names(&model), vec!["Sheet3", "Sheet1", "Sheet2"]);
+
+    assert!(model.move_sheet(0, 3).is_err());
+}
@@ -1,5 +1,8 @@
 #![allow(clippy::unwrap_used)]
 
+use std::sync::Arc;
+
+use crate::custom_functions::{CustomFunction, FunctionValue};
 use crate::test::user_model::util::new_empty_user_model;
 
 #[test]
@@ -61,3 +64,34 @@ fn randarray() {
     assert!((a1 - c2).abs() < 1e-6);
     assert!(a1 > 0.0 && a1 < 1.0);
 }
+
+#[test]
+fn registering_functions_evaluates() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "=ANSWER()").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("#NAME?".to_string())
+    );
+
+    model
+        .register_function(CustomFunction {
+            name: "ANSWER".to_string(),
+            min_args: 0,
+            max_args: Some(0),
+            volatile: false,
+            callback: Arc::new(|_| FunctionValue::Number(42.0)),
+        })
+        .unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("42".to_string())
+    );
+    assert_eq!(model.get_registered_functions(), vec!["ANSWER"]);
+
+    model.unregister_function("answer").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("#NAME?".to_string())
+    );
+}
@@ -0,0 +1,209 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::Area;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::types::Comment;
+use crate::user_model::{FindLookIn, FindOptions, FindOrder, FindScope};
+use crate::UserModel;
+
+fn options(look_in: FindLookIn, scope: FindScope) -> FindOptions {
+    FindOptions {
+        match_case: false,
+        whole_cell: false,
+        regex: false,
+        look_in,
+        order: FindOrder::ByRows,
+        scope,
+    }
+}
+
+fn cells(model: &UserModel, query: &str, options: &FindOptions) -> Vec<(u32, i32, i32)> {
+    model
+        .find(query, options)
+        .unwrap()
+        .map(|hit| (hit.sheet, hit.row, hit.column))
+        .collect()
+}
+
+fn new_model<'a>() -> UserModel<'a> {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "Apple").unwrap();
+    model.set_user_input(0, 1, 2, "pineapple").unwrap();
+    model.set_user_input(0, 2, 1, "=SUM(A3:A4)").unwrap();
+    model.set_user_input(0, 3, 1, "1000").unwrap();
+    model.set_user_input(0, 4, 1, "5").unwrap();
+    let area = Area {
+        sheet: 0,
+        row: 3,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    model
+        .update_range_style(&area, "num_fmt", "$#,##0")
+        .unwrap();
+    model.new_sheet().unwrap();
+    model.set_user_input(1, 2, 2, "APPLE pie").unwrap();
+    model.set_selected_sheet(0).unwrap();
+    model
+}
+
+#[test]
+fn find_in_values() {
+    let model = new_model();
+    let sheet = options(FindLookIn::Values, FindScope::Sheet);
+    assert_eq!(cells(&model, "apple", &sheet), vec![(0, 1, 1), (0, 1, 2)]);
+    assert_eq!(cells(&model, "$1,000", &sheet), vec![(0, 3, 1)]);
+    // The value of the formula
+    assert_eq!(cells(&model, "1005", &sheet), vec![(0, 2, 1)]);
+    assert!(cells(&model, "SUM", &sheet).is_empty());
+
+    let hit = model.find("1,0", &sheet).unwrap().next().unwrap();
+    assert_eq!(hit.text, "$1,000");
+}
+
+#[test]
+fn find_options() {
+    let model = new_model();
+    let mut workbook = options(FindLookIn::Values, FindScope::Workbook);
+    assert_eq!(
+        cells(&model, "apple", &workbook),
+        vec![(0, 1, 1), (0, 1, 2), (1, 2, 2)]
+    );
+
+    workbook.match_case = true;
+    assert_eq!(cells(&model, "apple", &workbook), vec![(0, 1, 2)]);
+
+    workbook.match_case = false;
+    workbook.whole_cell = true;
+    assert_eq!(cells(&model, "apple", &workbook), vec![(0, 1, 1)]);
+
+    workbook.whole_cell = false;
+    workbook.regex = true;
+    assert_eq!(
+        cells(&model, "^apple", &workbook),
+        vec![(0, 1, 1), (1, 2, 2)]
+    );
+    assert!(model.find("(", &workbook).is_err());
+    assert!(model.find("", &workbook).is_err());
+
+    workbook.regex = false;
+    workbook.order = FindOrder::ByColumns;
+    assert_eq!(
+        cells(&model, "e", &workbook),
+        vec![(0, 1, 1), (0, 1, 2), (1, 2, 2)]
+    );
+    assert_eq!(
+        cells(&model, "0", &options(FindLookIn::Values, FindScope::Sheet)),
+        vec![(0, 2, 1), (0, 3, 1)]
+    );
+}
+
+#[test]
+fn find_in_formulas_and_selection() {
+    let mut model = new_model();
+    let sheet = options(FindLookIn::Formulas, FindScope::Sheet);
+    assert_eq!(cells(&model, "sum(", &sheet), vec![(0, 2, 1)]);
+    assert!(cells(&model, "$1,000", &sheet).is_empty());
+    assert_eq!(cells(&model, "1000", &sheet), vec![(0, 3, 1)]);
+
+    model.set_selected_cell(1, 2).unwrap();
+    model.set_selected_range(1, 2, 4, 2).unwrap();
+    let selection = options(FindLookIn::Formulas, FindScope::Selection);
+    assert_eq!(cells(&model, "apple", &selection), vec![(0, 1, 2)]);
+
+    // A single selected cell searches the whole sheet
+    model.set_selected_cell(1, 1).unwrap();
+    assert_eq!(
+        cells(&model, "apple", &selection),
+        vec![(0, 1, 1), (0, 1, 2)]
+    );
+}
+
+#[test]
+fn find_in_comments() {
+    let mut model = new_model();
+    model.model.workbook.worksheets[0].comments.push(Comment {
+        text: "Check the apples".to_string(),
+        author_name: "Nico".to_string(),
+        author_id: None,
+        cell_ref: "C5".to_string(),
+    });
+    let sheet = options(FindLookIn::Comments, FindScope::Sheet);
+    let hits: Vec<_> = model.find("APPLE", &sheet).unwrap().collect();
+    assert_eq!(hits.len(), 1);
+    assert_eq!((hits[0].row, hits[0].column), (5, 3));
+    assert_eq!(hits[0].text, "Check the apples");
+}
+
+#[test]
+fn replace_all() {
+    let mut model = new_model();
+    model.set_user_input(0, 6, 1, "'007").unwrap();
+    let workbook = options(FindLookIn::Values, FindScope::Workbook);
+
+    let count = model.replace_all("apple", "Pear", &workbook).unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("Pear".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("pinePear".to_string()));
+    assert_eq!(model.get_cell_content(1, 2, 2), Ok("Pear pie".to_string()));
+
+    // Replaces in formulas and keeps text as text
+    let sheet = options(FindLookIn::Formulas, FindScope::Sheet);
+    assert_eq!(model.replace_all("A4", "A6", &sheet).unwrap(), 1);
+    assert_eq!(
+        model.get_cell_content(0, 2, 1),
+        Ok("=SUM(A3:A6)".to_string())
+    );
+    assert_eq!(model.replace_all("007", "008", &sheet).unwrap(), 1);
+    assert_eq!(
+        model.get_formatted_cell_value(0, 6, 1),
+        Ok("008".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("$1,005".to_string())
+    );
+
+    // Regular expressions with groups
+    let mut regex = options(FindLookIn::Formulas, FindScope::Sheet);
+    regex.regex = true;
+    assert_eq!(
+        model
+            .replace_all("^(\\w+)Pear$", "$1-apple", &regex)
+            .unwrap(),
+        1
+    );
+    assert_eq!(
+        model.get_cell_content(0, 1, 2),
+        Ok("pine-apple".to_string())
+    );
+
+    // Each replace is a single step in the history
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("Apple".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("pineapple".to_string()));
+    assert_eq!(model.get_cell_content(1, 2, 2), Ok("APPLE pie".to_string()));
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(1, 2, 2), Ok("Pear pie".to_string()));
+
+    assert_eq!(model.replace_all("missing", "x", &workbook).unwrap(), 0);
+}
+
+#[test]
+fn replace_skips_spilled_cells() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "=SEQUENCE(3)").unwrap();
+    let sheet = options(FindLookIn::Values, FindScope::Sheet);
+    assert_eq!(cells(&model, "2", &sheet), vec![(0, 2, 1)]);
+    let formulas = options(FindLookIn::Formulas, FindScope::Sheet);
+    assert_eq!(model.replace_all("3", "2", &formulas).unwrap(), 1);
+    assert_eq!(
+        model.get_cell_content(0, 1, 1),
+        Ok("=SEQUENCE(2)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 3, 1), Ok("".to_string()));
+}
@@ -0,0 +1,109 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::CellReferenceIndex;
+use crate::goal_seek::GoalSeekOptions;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::UserModel;
+
+#[test]
+fn goal_seek_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 3, 2, "10").unwrap();
+    model.set_user_input(0, 10, 2, "=B3*4-100").unwrap();
+    let target = CellReferenceIndex {
+        sheet: 0,
+        row: 10,
+        column: 2,
+    };
+    let changing = CellReferenceIndex {
+        sheet: 0,
+        row: 3,
+        column: 2,
+    };
+
+    let result = model
+        .goal_seek(&target, 20.0, &changing, &GoalSeekOptions::default())
+        .unwrap();
+    assert!(result.converged);
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 2),
+        Ok("30".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 10, 2),
+        Ok("20".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("10".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 10, 2),
+        Ok("-60".to_string())
+    );
+
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 2),
+        Ok("30".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 10, 2),
+        Ok("20".to_string())
+    );
+}
+
+#[test]
+fn goal_seek_without_solution_is_not_in_history() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "3").unwrap();
+    model.set_user_input(0, 1, 2, "=A1*A1+1").unwrap();
+    let target = CellReferenceIndex {
+        sheet: 0,
+        row: 1,
+        column: 2,
+    };
+    let changing = CellReferenceIndex {
+        sheet: 0,
+        row: 1,
+        column: 1,
+    };
+    let result = model
+        .goal_seek(&target, 0.0, &changing, &GoalSeekOptions::default())
+        .unwrap();
+    assert!(!result.converged);
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("3".to_string()));
+
+    // Undoes the formula in B1
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("".to_string()));
+}
+
+#[test]
+fn goal_seek_redo_in_other_locale() {
+    let mut model = UserModel::new_empty("model", "de", "UTC", "de").unwrap();
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 2, 1, "=A1*4").unwrap();
+    let target = CellReferenceIndex {
+        sheet: 0,
+        row: 2,
+        column: 1,
+    };
+    let changing = CellReferenceIndex {
+        sheet: 0,
+        row: 1,
+        column: 1,
+    };
+    let result = model
+        .goal_seek(&target, 10.0, &changing, &GoalSeekOptions::default())
+        .unwrap();
+    assert!(result.converged);
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("2,5".to_string()));
+
+    model.undo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("2,5".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("10".to_string())
+    );
+}
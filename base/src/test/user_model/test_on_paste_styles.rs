@@ -12,6 +12,7 @@ fn simple_pasting() {
     let mut style = model.get_cell_style(0, 1, 1).unwrap();
     style.fill = Fill {
         color: Color::Rgb("#FF5577".to_string()),
+        ..Default::default()
     };
     let styles = vec![vec![style.clone()]];
 
@@ -0,0 +1,321 @@
+#![allow(clippy::unwrap_used)]
+
+use bitcode::decode;
+
+use crate::outline::OutlineGroup;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::user_model::history::{Diff, QueueDiffs};
+
+fn group(start: i32, end: i32, level: i32, collapsed: bool) -> OutlineGroup {
+    OutlineGroup {
+        start,
+        end,
+        level,
+        collapsed,
+    }
+}
+
+#[test]
+fn group_and_ungroup_rows() {
+    let mut model = new_empty_user_model();
+    model.group_rows(0, 2, 10).unwrap();
+    model.group_rows(0, 4, 6).unwrap();
+    assert_eq!(model.model.get_row_outline_level(0, 3), Ok(1));
+    assert_eq!(model.model.get_row_outline_level(0, 5), Ok(2));
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false), group(4, 6, 2, false)]
+    );
+
+    model.ungroup_rows(0, 4, 4).unwrap();
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false), group(5, 6, 2, false)]
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.model.get_row_outline_level(0, 4), Ok(2));
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(model.get_row_outline(0).unwrap(), vec![]);
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false)]
+    );
+
+    // ungrouping rows that are not grouped does nothing
+    model.ungroup_rows(0, 20, 30).unwrap();
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false)]
+    );
+}
+
+#[test]
+fn maximum_outline_level() {
+    let mut model = new_empty_user_model();
+    for _ in 0..7 {
+        model.group_rows(0, 1, 5).unwrap();
+    }
+    assert_eq!(model.model.get_row_outline_level(0, 3), Ok(7));
+    // The error leaves the model untouched
+    assert_eq!(
+        model.group_rows(0, 5, 6),
+        Err("Row 5 is already at the maximum outline level.".to_string())
+    );
+    assert_eq!(model.model.get_row_outline_level(0, 6), Ok(0));
+    assert_eq!(
+        model.group_columns(0, 3, 2),
+        Err("Invalid column range: 3-2.".to_string())
+    );
+}
+
+#[test]
+fn collapse_and_expand_rows() {
+    let mut model = new_empty_user_model();
+    model.group_rows(0, 2, 10).unwrap();
+    model.group_rows(0, 4, 6).unwrap();
+
+    // collapse the inner group, the summary row is 7
+    model.collapse_row_group(0, 5, 2).unwrap();
+    for row in 4..=6 {
+        assert!(model.model.is_row_hidden(0, row).unwrap());
+    }
+    assert!(!model.model.is_row_hidden(0, 7).unwrap());
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false), group(4, 6, 2, true)]
+    );
+
+    // collapse and expand the outer group, the inner group stays collapsed
+    model.collapse_row_group(0, 2, 1).unwrap();
+    for row in 2..=10 {
+        assert!(model.model.is_row_hidden(0, row).unwrap());
+    }
+    model.expand_row_group(0, 2, 1).unwrap();
+    assert!(!model.model.is_row_hidden(0, 3).unwrap());
+    assert!(model.model.is_row_hidden(0, 5).unwrap());
+    assert!(!model.model.is_row_hidden(0, 7).unwrap());
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false), group(4, 6, 2, true)]
+    );
+
+    model.expand_row_group(0, 4, 2).unwrap();
+    assert!(!model.model.is_row_hidden(0, 5).unwrap());
+
+    model.undo().unwrap();
+    assert!(model.model.is_row_hidden(0, 5).unwrap());
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(2, 10, 1, false), group(4, 6, 2, true)]
+    );
+
+    assert_eq!(
+        model.collapse_row_group(0, 3, 2),
+        Err("Row 3 is not in an outline group of level 2.".to_string())
+    );
+}
+
+#[test]
+fn columns() {
+    let mut model = new_empty_user_model();
+    model.set_columns_width(0, 3, 3, 150.0).unwrap();
+    model.group_columns(0, 2, 4).unwrap();
+    assert_eq!(
+        model.get_column_outline(0).unwrap(),
+        vec![group(2, 4, 1, false)]
+    );
+    // The width is untouched
+    assert_eq!(model.get_column_width(0, 3), Ok(150.0));
+
+    // summary columns to the left
+    model
+        .model
+        .workbook
+        .worksheet_mut(0)
+        .unwrap()
+        .outline_properties
+        .summary_right = false;
+    model.collapse_column_group(0, 3, 1).unwrap();
+    assert!(model.model.is_column_hidden(0, 2).unwrap());
+    assert!(model.model.is_column_hidden(0, 4).unwrap());
+    assert!(!model.model.is_column_hidden(0, 5).unwrap());
+    assert_eq!(
+        model.get_column_outline(0).unwrap(),
+        vec![group(2, 4, 1, true)]
+    );
+    assert!(model
+        .model
+        .workbook
+        .worksheet(0)
+        .unwrap()
+        .is_column_collapsed(1)
+        .unwrap());
+
+    model.expand_column_group(0, 3, 1).unwrap();
+    assert!(!model.model.is_column_hidden(0, 3).unwrap());
+    assert_eq!(model.get_column_width(0, 3), Ok(150.0));
+
+    model.ungroup_columns(0, 2, 4).unwrap();
+    assert_eq!(model.get_column_outline(0).unwrap(), vec![]);
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = new_empty_user_model();
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(model2.get_column_outline(0).unwrap(), vec![]);
+    assert_eq!(model2.get_column_width(0, 3), Ok(150.0));
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_column_outline(0).unwrap(),
+        vec![group(2, 4, 1, false)]
+    );
+}
+
+#[test]
+fn outline_changes_are_range_diffs() {
+    let mut model = new_empty_user_model();
+    model.group_rows(0, 1, 1000).unwrap();
+    model.group_rows(0, 1501, 2000).unwrap();
+    // rows 1001..=1500 are not grouped and are left out
+    model.ungroup_rows(0, 501, 2000).unwrap();
+    model.group_columns(0, 2, 50).unwrap();
+
+    let send_queue = model.flush_send_queue();
+    let queue: Vec<QueueDiffs> = decode(&send_queue).unwrap();
+    let lists: Vec<Vec<Diff>> = queue.iter().map(|q| q.list.clone()).collect();
+    assert_eq!(lists.len(), 4);
+    assert!(matches!(
+        lists[0][..],
+        [Diff::SetRowsOutlineLevel {
+            sheet: 0,
+            row: 1,
+            count: 1000,
+            new_value: 1,
+            old_value: 0
+        }]
+    ));
+    assert!(matches!(
+        lists[2][..],
+        [
+            Diff::SetRowsOutlineLevel {
+                sheet: 0,
+                row: 501,
+                count: 500,
+                new_value: 0,
+                old_value: 1
+            },
+            Diff::SetRowsOutlineLevel {
+                sheet: 0,
+                row: 1501,
+                count: 500,
+                new_value: 0,
+                old_value: 1
+            }
+        ]
+    ));
+    assert!(matches!(
+        lists[3][..],
+        [Diff::SetColumnsOutlineLevel {
+            sheet: 0,
+            column: 2,
+            count: 49,
+            new_value: 1,
+            old_value: 0
+        }]
+    ));
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(1, 500, 1, false)]
+    );
+
+    let mut model2 = new_empty_user_model();
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(
+        model2.get_row_outline(0).unwrap(),
+        vec![group(1, 500, 1, false)]
+    );
+    assert_eq!(
+        model2.get_column_outline(0).unwrap(),
+        vec![group(2, 50, 1, false)]
+    );
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(1, 1000, 1, false), group(1501, 2000, 1, false)]
+    );
+    assert_eq!(model.model.get_row_outline_level(0, 1200), Ok(0));
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(1, 500, 1, false)]
+    );
+}
+
+#[test]
+fn outline_diffs_are_idempotent() {
+    let mut model = new_empty_user_model();
+    model.group_rows(0, 3, 6).unwrap();
+    model.group_columns(0, 3, 6).unwrap();
+    model.flush_send_queue();
+
+    // Runs with different levels get a diff each
+    model.group_rows(0, 1, 8).unwrap();
+    model.group_columns(0, 1, 8).unwrap();
+    let send_queue = model.flush_send_queue();
+    let queue: Vec<QueueDiffs> = decode(&send_queue).unwrap();
+    assert!(matches!(
+        queue[0].list[..],
+        [
+            Diff::SetRowsOutlineLevel {
+                row: 1,
+                count: 2,
+                new_value: 1,
+                old_value: 0,
+                ..
+            },
+            Diff::SetRowsOutlineLevel {
+                row: 3,
+                count: 4,
+                new_value: 2,
+                old_value: 1,
+                ..
+            },
+            Diff::SetRowsOutlineLevel {
+                row: 7,
+                count: 2,
+                new_value: 1,
+                old_value: 0,
+                ..
+            }
+        ]
+    ));
+
+    // Applying the same diffs twice leaves the same levels
+    let mut model2 = new_empty_user_model();
+    model2.group_rows(0, 3, 6).unwrap();
+    model2.group_columns(0, 3, 6).unwrap();
+    model2.apply_external_diffs(&send_queue).unwrap();
+    model2.apply_external_diffs(&send_queue).unwrap();
+    let expected = vec![group(1, 8, 1, false), group(3, 6, 2, false)];
+    assert_eq!(model2.get_row_outline(0).unwrap(), expected);
+    assert_eq!(model2.get_column_outline(0).unwrap(), expected);
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_row_outline(0).unwrap(),
+        vec![group(3, 6, 1, false)]
+    );
+    assert_eq!(
+        model.get_column_outline(0).unwrap(),
+        vec![group(3, 6, 1, false)]
+    );
+    model.redo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(model.get_column_outline(0).unwrap(), expected);
+}
@@ -0,0 +1,296 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::Area;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::types::{BorderItem, BorderStyle, Color, HorizontalAlignment};
+
+fn area(sheet: u32, row: i32, column: i32) -> Area {
+    Area {
+        sheet,
+        row,
+        column,
+        width: 1,
+        height: 1,
+    }
+}
+
+#[test]
+fn copy_and_paste_html() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "21").unwrap();
+    model.set_user_input(0, 1, 2, "=A1*2").unwrap();
+    model.set_user_input(0, 2, 1, "'007").unwrap();
+    model.set_user_input(0, 2, 2, "=1/3").unwrap();
+    model.set_user_input(0, 3, 1, "Merged <&>").unwrap();
+    model
+        .update_range_style(&area(0, 1, 1), "font.b", "true")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 1, 1), "font.color", "#FF0000")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 1, 2), "fill.color", "#FFFF00")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 1, 2), "num_fmt", "#,##0.00")
+        .unwrap();
+    model
+        .update_range_style(&area(0, 3, 1), "alignment.horizontal", "center")
+        .unwrap();
+    model.model.workbook.worksheets[0]
+        .merge_cells
+        .push("A3:B3".to_string());
+    let border = r##"{"item":{"style":"medium","color":"#0000FF"},"type":"All"}"##;
+    model
+        .set_area_with_border(&area(0, 2, 2), &serde_json::from_str(border).unwrap())
+        .unwrap();
+
+    model.set_selected_cell(1, 1).unwrap();
+    model.set_selected_range(1, 1, 3, 2).unwrap();
+    let html = model.copy_to_html().unwrap();
+    assert!(html.contains("<td rowspan") || html.contains(" colspan=\"2\""));
+    assert!(html.contains("Merged &lt;&amp;&gt;"));
+
+    model.new_sheet().unwrap();
+    model.set_selected_sheet(1).unwrap();
+    model.set_selected_cell(5, 3).unwrap();
+    model.paste_html(&area(1, 5, 3), &html).unwrap();
+
+    assert_eq!(model.get_cell_content(1, 5, 3), Ok("21".to_string()));
+    // Formulas are moved like in a copy
+    assert_eq!(model.get_cell_content(1, 5, 4), Ok("=C5*2".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(1, 5, 4),
+        Ok("42.00".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(1, 6, 3),
+        Ok("007".to_string())
+    );
+    assert_eq!(model.get_cell_content(1, 6, 4), Ok("=1/3".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(1, 7, 3),
+        Ok("Merged <&>".to_string())
+    );
+
+    let style = model.get_cell_style(1, 5, 3).unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.font.color, Color::Rgb("#FF0000".to_string()));
+    let style = model.get_cell_style(1, 5, 4).unwrap();
+    assert_eq!(style.fill.color, Color::Rgb("#FFFF00".to_string()));
+    assert_eq!(style.num_fmt, "#,##0.00");
+    let style = model.get_cell_style(1, 6, 4).unwrap();
+    let medium = Some(BorderItem {
+        style: BorderStyle::Medium,
+        color: Color::Rgb("#0000FF".to_string()),
+    });
+    assert_eq!(style.border.top, medium);
+    assert_eq!(style.border.left, medium);
+    let style = model.get_cell_style(1, 7, 3).unwrap();
+    assert_eq!(
+        style.alignment.unwrap().horizontal,
+        HorizontalAlignment::Center
+    );
+    assert_eq!(
+        model.model.workbook.worksheets[1].merge_cells,
+        vec!["C7:D7".to_string()]
+    );
+    assert_eq!([5, 3, 7, 4], model.get_selected_view().range);
+}
+
+#[test]
+fn paste_from_excel() {
+    let html = r##"<html xmlns:o="urn:schemas-microsoft-com:office:office"
+xmlns:x="urn:schemas-microsoft-com:office:excel">
+<head>
+<meta http-equiv=Content-Type content="text/html; charset=utf-8">
+<style>
+<!--table
+	{mso-displayed-decimal-separator:"\.";}
+td
+	{padding-top:1px;
+	color:black;
+	font-size:11.0pt;
+	font-family:Calibri, sans-serif;
+	border:none;
+	white-space:nowrap;}
+.xl65
+	{font-weight:700;
+	mso-number-format:"\0022$\0022\#\,\#\#0\.00";
+	background:#C6EFCE;
+	border-top:.5pt solid windowtext;
+	border-bottom:1.5pt solid windowtext;}
+.xl66
+	{mso-number-format:"0\.0%";
+	text-align:right;
+	white-space:normal;}
+.xl67
+	{mso-number-format:"\@";}
+-->
+</style>
+</head>
+<body link="#0563C1" vlink="#954F72">
+<table border=0 cellpadding=0 cellspacing=0 width=128 style='border-collapse:collapse;width:96pt'>
+<!--StartFragment-->
+ <tr height=20 style='height:15.0pt'>
+  <td height=20 class=xl65 width=64 style='height:15.0pt;width:48pt' x:num="1234.5">$1,234.50</td>
+  <td class=xl66 width=64 style='width:48pt' x:num="0.125">12.5%</td>
+ </tr>
+ <tr height=20 style='height:15.0pt'>
+  <td colspan=2 height=20 class=xl67 style='height:15.0pt'>00123</td>
+ </tr>
+ <tr height=20 style='height:15.0pt'>
+  <td height=20 style='height:15.0pt;color:red'>Line&nbsp;one<br style='mso-data-placement:same-cell'>
+  line two</td>
+  <td><font color="#0000FF"><i>2021-03-04</i></font></td>
+ </tr>
+<!--EndFragment-->
+</table>
+</body>
+</html>"##;
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 2, 2, "Old").unwrap();
+    model.set_selected_cell(1, 1).unwrap();
+    model.paste_html(&area(0, 1, 1), html).unwrap();
+
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("1234.5".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 1),
+        Ok("$1,234.50".to_string())
+    );
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.font.name, "Calibri");
+    assert_eq!(style.font.sz, 11);
+    assert_eq!(style.num_fmt, "\"$\"#,##0.00");
+    assert_eq!(style.fill.color, Color::Rgb("#C6EFCE".to_string()));
+    assert_eq!(style.border.top.unwrap().style, BorderStyle::Thin);
+    assert_eq!(style.border.bottom.unwrap().style, BorderStyle::Thick);
+    // The border of the td rule is not used
+    assert_eq!(style.border.left, None);
+
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 2),
+        Ok("12.5%".to_string())
+    );
+    let alignment = model.get_cell_style(0, 1, 2).unwrap().alignment.unwrap();
+    assert_eq!(alignment.horizontal, HorizontalAlignment::Right);
+    assert!(alignment.wrap_text);
+
+    // Text formatted cells keep the leading zeros
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("00123".to_string())
+    );
+    assert_eq!(model.get_cell_style(0, 2, 1).unwrap().num_fmt, "@");
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("".to_string()));
+    assert_eq!(
+        model.model.workbook.worksheets[0].merge_cells,
+        vec!["A2:B2".to_string()]
+    );
+
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 1),
+        Ok("Line one\nline two".to_string())
+    );
+    let style = model.get_cell_style(0, 3, 1).unwrap();
+    assert_eq!(style.font.color, Color::Rgb("#FF0000".to_string()));
+    let style = model.get_cell_style(0, 3, 2).unwrap();
+    assert!(style.font.i);
+    assert_eq!(style.font.color, Color::Rgb("#0000FF".to_string()));
+
+    // A single step in the history, merges included
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("Old".to_string()));
+    assert!(!model.get_cell_style(0, 1, 1).unwrap().font.b);
+    assert!(model.model.workbook.worksheets[0].merge_cells.is_empty());
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("00123".to_string())
+    );
+    assert_eq!(
+        model.model.workbook.worksheets[0].merge_cells,
+        vec!["A2:B2".to_string()]
+    );
+}
+
+#[test]
+fn paste_from_google_sheets() {
+    let html = concat!(
+        r#"<meta charset="utf-8"><google-sheets-html-origin><style type="text/css"><!--td {border: 1px solid #cccccc;}br {mso-data-placement:same-cell;}--></style>"#,
+        r#"<table xmlns="http://www.w3.org/1999/xhtml" cellspacing="0" cellpadding="0" dir="ltr" border="1" style="table-layout:fixed;font-size:10pt;font-family:Arial;width:0px;border-collapse:collapse;border:none">"#,
+        r#"<colgroup><col width="100"/><col width="100"/></colgroup><tbody><tr style="height:21px;">"#,
+        r#"<td style="overflow:hidden;padding:2px 3px 2px 3px;vertical-align:bottom;text-align:right;" data-sheets-value="{&quot;1&quot;:3,&quot;3&quot;:0.1}" data-sheets-numberformat="{&quot;1&quot;:2,&quot;2&quot;:&quot;0.000&quot;,&quot;3&quot;:1}">0.100</td>"#,
+        r#"<td style="overflow:hidden;padding:2px 3px 2px 3px;vertical-align:bottom;font-weight:bold;border-bottom:2px solid rgb(255, 0, 0);" data-sheets-value="{&quot;1&quot;:3,&quot;3&quot;:0.2}" data-sheets-formula="=R[0]C[-1]*2">0.2</td>"#,
+        r#"</tr><tr style="height:21px;">"#,
+        r#"<td style="overflow:hidden;padding:2px 3px 2px 3px;vertical-align:bottom;" data-sheets-value="{&quot;1&quot;:2,&quot;2&quot;:&quot;1/2&quot;}">1/2</td>"#,
+        r#"<td style="overflow:hidden;padding:2px 3px 2px 3px;vertical-align:bottom;" data-sheets-value="{&quot;1&quot;:4,&quot;4&quot;:1}" data-sheets-formula="=AND(R1C1&gt;0, R[-1]C&gt;0)">TRUE</td>"#,
+        r#"</tr></tbody></table></google-sheets-html-origin>"#
+    );
+    let mut model = new_empty_user_model();
+    model.set_selected_cell(3, 2).unwrap();
+    model.paste_html(&area(0, 3, 2), html).unwrap();
+
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("0.1".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 2),
+        Ok("0.100".to_string())
+    );
+    let style = model.get_cell_style(0, 3, 2).unwrap();
+    assert_eq!(style.font.name, "Arial");
+    assert_eq!(style.font.sz, 10);
+    // The grid lines of the td rule are not borders
+    assert_eq!(style.border.top, None);
+
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("=B3*2".to_string()));
+    // The result takes the number format of B3
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 3),
+        Ok("0.200".to_string())
+    );
+    let style = model.get_cell_style(0, 3, 3).unwrap();
+    assert!(style.font.b);
+    assert_eq!(
+        style.border.bottom,
+        Some(BorderItem {
+            style: BorderStyle::Medium,
+            color: Color::Rgb("#FF0000".to_string()),
+        })
+    );
+
+    // Text is not read as a date
+    assert_eq!(
+        model.get_formatted_cell_value(0, 4, 2),
+        Ok("1/2".to_string())
+    );
+    assert_eq!(
+        model.get_cell_content(0, 4, 3),
+        Ok("=AND($A$1>0,C3>0)".to_string())
+    );
+}
+
+#[test]
+fn paste_single_cell_and_text() {
+    let mut model = new_empty_user_model();
+    let html = r#"<google-sheets-html-origin><span style="font-size:10pt;font-family:Arial;font-style:italic;" data-sheets-root="1" data-sheets-value="{&quot;1&quot;:3,&quot;3&quot;:42}">42</span></google-sheets-html-origin>"#;
+    model.set_selected_cell(1, 1).unwrap();
+    model.paste_html(&area(0, 1, 1), html).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("42".to_string()));
+    assert!(model.get_cell_style(0, 1, 1).unwrap().font.i);
+
+    // Some HTML from a web page
+    let html = "<html><head><title>Page</title></head><body><p><b>Hello</b> world</p><p>again</p></body></html>";
+    model.set_selected_cell(2, 1).unwrap();
+    model.paste_html(&area(0, 2, 1), html).unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("Hello world\nagain".to_string())
+    );
+    assert!(model.get_cell_style(0, 2, 1).unwrap().font.b);
+
+    // Nothing to paste
+    model.paste_html(&area(0, 3, 1), "<html></html>").unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("".to_string()));
+}
@@ -0,0 +1,277 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::Area;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::user_model::{PasteMode, PasteOperation, PasteSpecialOptions};
+use crate::UserModel;
+
+fn options(mode: PasteMode) -> PasteSpecialOptions {
+    PasteSpecialOptions {
+        mode,
+        operation: PasteOperation::None,
+        skip_blanks: false,
+        transpose: false,
+    }
+}
+
+/// Copies the range in the first sheet and pastes it at (row, column) of the selected sheet
+fn copy_and_paste(
+    model: &mut UserModel,
+    source: (i32, i32, i32, i32),
+    row: i32,
+    column: i32,
+    options: &PasteSpecialOptions,
+) -> Result<(), String> {
+    let sheet = model.get_selected_view().sheet;
+    model.set_selected_sheet(0).unwrap();
+    model.set_selected_cell(source.0, source.1).unwrap();
+    model
+        .set_selected_range(source.0, source.1, source.2, source.3)
+        .unwrap();
+    let clipboard = model.copy_to_clipboard().unwrap();
+    model.set_selected_sheet(sheet).unwrap();
+    model.set_selected_cell(row, column).unwrap();
+    model.paste_special(0, source, &clipboard.data, options)
+}
+
+fn area(row: i32, column: i32) -> Area {
+    Area {
+        sheet: 0,
+        row,
+        column,
+        width: 1,
+        height: 1,
+    }
+}
+
+#[test]
+fn paste_values() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "2").unwrap();
+    model.set_user_input(0, 2, 1, "=A1*3").unwrap();
+    model.set_user_input(0, 3, 1, "'123").unwrap();
+    model.set_user_input(0, 4, 1, "=1/3").unwrap();
+    model
+        .update_range_style(&area(1, 1), "font.b", "true")
+        .unwrap();
+
+    copy_and_paste(&mut model, (1, 1, 4, 1), 1, 3, &options(PasteMode::Values)).unwrap();
+
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("6".to_string()));
+    // Text that looks like a number stays text
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 3),
+        Ok("123".to_string())
+    );
+    assert!(model.get_cell_style(0, 3, 3).unwrap().quote_prefix);
+    assert_eq!(
+        model.get_cell_content(0, 4, 3),
+        Ok("0.333333333333333".to_string())
+    );
+    assert!(!model.get_cell_style(0, 1, 3).unwrap().font.b);
+    assert_eq!([1, 3, 4, 3], model.get_selected_view().range);
+
+    // A single step in the history
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 4, 3), Ok("".to_string()));
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("6".to_string()));
+}
+
+#[test]
+fn paste_formulas_and_formats() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "=B1+1").unwrap();
+    model
+        .update_range_style(&area(1, 1), "font.b", "true")
+        .unwrap();
+    model
+        .update_range_style(&area(1, 1), "num_fmt", "0.00")
+        .unwrap();
+    model.set_user_input(0, 5, 4, "Old").unwrap();
+    model
+        .update_range_style(&area(5, 4), "font.i", "true")
+        .unwrap();
+
+    copy_and_paste(
+        &mut model,
+        (1, 1, 1, 1),
+        5,
+        4,
+        &options(PasteMode::Formulas),
+    )
+    .unwrap();
+    assert_eq!(model.get_cell_content(0, 5, 4), Ok("=E5+1".to_string()));
+    let style = model.get_cell_style(0, 5, 4).unwrap();
+    assert!(!style.font.b);
+    assert!(style.font.i);
+
+    copy_and_paste(
+        &mut model,
+        (1, 1, 1, 1),
+        5,
+        4,
+        &options(PasteMode::FormulasAndNumberFormats),
+    )
+    .unwrap();
+    let style = model.get_cell_style(0, 5, 4).unwrap();
+    assert_eq!(style.num_fmt, "0.00");
+    assert!(!style.font.b);
+    assert!(style.font.i);
+
+    model.set_user_input(0, 6, 4, "Kept").unwrap();
+    copy_and_paste(&mut model, (1, 1, 1, 1), 6, 4, &options(PasteMode::Formats)).unwrap();
+    assert_eq!(model.get_cell_content(0, 6, 4), Ok("Kept".to_string()));
+    let style = model.get_cell_style(0, 6, 4).unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.num_fmt, "0.00");
+}
+
+#[test]
+fn paste_column_widths() {
+    let mut model = new_empty_user_model();
+    model.set_columns_width(0, 1, 1, 150.0).unwrap();
+    model.set_columns_width(0, 2, 2, 60.0).unwrap();
+    model.set_user_input(0, 1, 1, "A").unwrap();
+    let width = model.get_column_width(0, 5).unwrap();
+
+    copy_and_paste(
+        &mut model,
+        (1, 1, 1, 2),
+        3,
+        4,
+        &options(PasteMode::ColumnWidths),
+    )
+    .unwrap();
+    assert_eq!(model.get_column_width(0, 4), Ok(150.0));
+    assert_eq!(model.get_column_width(0, 5), Ok(60.0));
+    assert_eq!(model.get_cell_content(0, 3, 4), Ok("".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(model.get_column_width(0, 5), Ok(width));
+}
+
+#[test]
+fn skip_blanks_and_transpose() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 1, 3, "=A1*10").unwrap();
+    for column in 1..=3 {
+        model.set_user_input(0, 5, column, "x").unwrap();
+    }
+
+    let mut skip_blanks = options(PasteMode::All);
+    skip_blanks.skip_blanks = true;
+    copy_and_paste(&mut model, (1, 1, 1, 3), 5, 1, &skip_blanks).unwrap();
+    assert_eq!(model.get_cell_content(0, 5, 1), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 2), Ok("x".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 3), Ok("=A5*10".to_string()));
+
+    // A1:C1 pasted in E1:E3
+    let mut transpose = options(PasteMode::All);
+    transpose.transpose = true;
+    copy_and_paste(&mut model, (1, 1, 1, 3), 1, 5, &transpose).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 5), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 5), Ok("".to_string()));
+    // References are displaced by the move of the cell, C1 went to E3
+    assert_eq!(model.get_cell_content(0, 3, 5), Ok("=C3*10".to_string()));
+    assert_eq!([1, 5, 3, 5], model.get_selected_view().range);
+}
+
+#[test]
+fn paste_link() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "5").unwrap();
+    model.set_user_input(0, 1, 2, "=A1+1").unwrap();
+
+    copy_and_paste(&mut model, (1, 1, 1, 2), 3, 1, &options(PasteMode::Link)).unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("=A1".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 2), Ok("=B1".to_string()));
+
+    model.new_sheet().unwrap();
+    model.set_selected_sheet(1).unwrap();
+    copy_and_paste(&mut model, (1, 1, 1, 2), 2, 2, &options(PasteMode::Link)).unwrap();
+    assert_eq!(
+        model.get_cell_content(1, 2, 2),
+        Ok("=Sheet1!A1".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(1, 2, 3), Ok("6".to_string()));
+}
+
+#[test]
+fn paste_operations() {
+    let mut model = new_empty_user_model();
+    // Source A1:A5
+    for (row, value) in ["2", "2", "0", "2", "text"].iter().enumerate() {
+        model.set_user_input(0, row as i32 + 1, 1, value).unwrap();
+    }
+    // Destination C1:C5, C2 is empty
+    model.set_user_input(0, 1, 3, "10").unwrap();
+    model.set_user_input(0, 3, 3, "10").unwrap();
+    model.set_user_input(0, 4, 3, "=B4*2").unwrap();
+    model.set_user_input(0, 5, 3, "1").unwrap();
+    model.set_user_input(0, 4, 2, "4").unwrap();
+
+    let mut add = options(PasteMode::Values);
+    add.operation = PasteOperation::Add;
+    copy_and_paste(&mut model, (1, 1, 5, 1), 1, 3, &add).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("12".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("10".to_string()));
+    assert_eq!(model.get_cell_content(0, 4, 3), Ok("=B4*2+2".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 4, 3),
+        Ok("10".to_string())
+    );
+    // Text is pasted as it is
+    assert_eq!(model.get_cell_content(0, 5, 3), Ok("text".to_string()));
+
+    let mut divide = options(PasteMode::All);
+    divide.operation = PasteOperation::Divide;
+    copy_and_paste(&mut model, (1, 1, 3, 1), 1, 3, &divide).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("6".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("1".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 3, 3),
+        Ok("#DIV/0!".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("12".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("10".to_string()));
+
+    let mut formats = options(PasteMode::Formats);
+    formats.operation = PasteOperation::Multiply;
+    assert_eq!(
+        copy_and_paste(&mut model, (1, 1, 3, 1), 1, 3, &formats),
+        Err("Operations can only be used to paste all, values or formulas".to_string())
+    );
+}
+
+#[test]
+fn spill_cells() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "=SEQUENCE(3)").unwrap();
+
+    // Values of the spilled cells are pasted
+    copy_and_paste(&mut model, (1, 1, 3, 1), 1, 3, &options(PasteMode::Values)).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("3".to_string()));
+
+    // Only the formula is pasted, it spills again
+    copy_and_paste(
+        &mut model,
+        (1, 1, 3, 1),
+        1,
+        5,
+        &options(PasteMode::Formulas),
+    )
+    .unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 1, 5),
+        Ok("=SEQUENCE(3)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 3, 5), Ok("3".to_string()));
+}
@@ -0,0 +1,204 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::Area;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::types::Color;
+use crate::user_model::{PortableClipboard, PortableReferences};
+use crate::UserModel;
+
+/// Sheet1!A1:A5 of a workbook named "Budget.xlsx"
+fn new_source_model<'a>() -> UserModel<'a> {
+    let mut model = UserModel::new_empty("Budget.xlsx", "en", "UTC", "en").unwrap();
+    model.new_sheet().unwrap();
+    model.set_user_input(1, 2, 2, "10").unwrap();
+    model.new_defined_name("Rate", None, "Sheet1!$A$1").unwrap();
+    model.set_user_input(0, 1, 1, "2").unwrap();
+    model.set_user_input(0, 2, 1, "=A1*2").unwrap();
+    model.set_user_input(0, 3, 1, "=Sheet2!B2+A1").unwrap();
+    model.set_user_input(0, 4, 1, "=Rate*3").unwrap();
+    model.set_user_input(0, 5, 1, "'007").unwrap();
+    let area = Area {
+        sheet: 0,
+        row: 2,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    model.update_range_style(&area, "font.b", "true").unwrap();
+    model
+        .update_range_style(&area, "fill.color", "#FF0000")
+        .unwrap();
+    model.set_selected_sheet(0).unwrap();
+    model.set_selected_cell(1, 1).unwrap();
+    model.set_selected_range(1, 1, 5, 1).unwrap();
+    model
+}
+
+/// Copies Sheet1!A1:A5 of the source and pastes it in C3 of the target, through JSON
+fn copy_and_paste(target: &mut UserModel, references: PortableReferences) {
+    let source = new_source_model();
+    let clipboard = source.copy_to_portable_clipboard().unwrap();
+    let json = serde_json::to_string(&clipboard).unwrap();
+    let clipboard: PortableClipboard = serde_json::from_str(&json).unwrap();
+    target.set_selected_cell(3, 3).unwrap();
+    target
+        .paste_portable_clipboard(&clipboard, references)
+        .unwrap();
+}
+
+#[test]
+fn copy_to_portable_clipboard() {
+    let model = new_source_model();
+    let clipboard = model.copy_to_portable_clipboard().unwrap();
+    assert_eq!(clipboard.workbook, "Budget.xlsx");
+    assert_eq!(clipboard.sheet, "Sheet1");
+    assert_eq!(clipboard.range, (1, 1, 5, 1));
+    assert_eq!(clipboard.cells.len(), 5);
+    assert_eq!(clipboard.cells[1].formula, Some("=R[-1]C[0]*2".to_string()));
+    assert_eq!(clipboard.defined_names.len(), 1);
+    assert_eq!(clipboard.defined_names[0].name, "Rate");
+    assert_eq!(clipboard.defined_names[0].formula, "Sheet1!$A$1");
+    assert!(!clipboard.defined_names[0].local);
+}
+
+#[test]
+fn paste_rewriting_references() {
+    let mut model = new_empty_user_model();
+    copy_and_paste(&mut model, PortableReferences::Rewrite);
+
+    assert_eq!(model.get_cell_content(0, 3, 3), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 4, 3), Ok("=C3*2".to_string()));
+    // There is no Sheet2 in this workbook
+    assert_eq!(model.get_cell_content(0, 5, 3), Ok("12".to_string()));
+    assert_eq!(model.get_cell_content(0, 6, 3), Ok("=Rate*3".to_string()));
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("Rate".to_string(), None, "Sheet1!$A$1".to_string())]
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 7, 3),
+        Ok("007".to_string())
+    );
+    assert!(model.get_cell_style(0, 7, 3).unwrap().quote_prefix);
+
+    let style = model.get_cell_style(0, 4, 3).unwrap();
+    assert!(style.font.b);
+    assert_eq!(style.fill.color, Color::Rgb("#FF0000".to_string()));
+    assert_eq!([3, 3, 7, 3], model.get_selected_view().range);
+
+    // A single step in the history
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 4, 3), Ok("".to_string()));
+    assert!(!model.get_cell_style(0, 4, 3).unwrap().font.b);
+    assert!(model.get_defined_name_list().is_empty());
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 6, 3), Ok("=Rate*3".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 6, 3), Ok("0".to_string()));
+}
+
+#[test]
+fn paste_existing_sheets_and_names() {
+    let mut model = new_empty_user_model();
+    model.new_sheet().unwrap();
+    model.set_user_input(1, 2, 2, "100").unwrap();
+    model.new_defined_name("Rate", None, "Sheet2!$B$2").unwrap();
+    model.set_selected_sheet(0).unwrap();
+    copy_and_paste(&mut model, PortableReferences::Rewrite);
+
+    assert_eq!(
+        model.get_cell_content(0, 5, 3),
+        Ok("=Sheet2!D4+C3".to_string())
+    );
+    // The name of this workbook is kept
+    assert_eq!(
+        model.get_formatted_cell_value(0, 6, 3),
+        Ok("300".to_string())
+    );
+}
+
+#[test]
+fn paste_external_references() {
+    let mut model = new_empty_user_model();
+    copy_and_paste(&mut model, PortableReferences::External);
+
+    // References without a sheet name are to the sheet the cells were copied from
+    assert_eq!(
+        model.get_cell_content(0, 4, 3),
+        Ok("=[Budget.xlsx]Sheet1!C3*2".to_string())
+    );
+    assert_eq!(
+        model.get_cell_content(0, 5, 3),
+        Ok("=[Budget.xlsx]Sheet2!D4+[Budget.xlsx]Sheet1!C3".to_string())
+    );
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![(
+            "Rate".to_string(),
+            None,
+            "[Budget.xlsx]Sheet1!$A$1".to_string()
+        )]
+    );
+    assert_eq!(model.get_cell_content(0, 6, 3), Ok("=Rate*3".to_string()));
+}
+
+#[test]
+fn paste_external_references_in_place() {
+    let mut source = UserModel::new_empty("Src.xlsx", "en", "UTC", "en").unwrap();
+    source.set_user_input(0, 2, 1, "=A1*2").unwrap();
+    source.set_selected_cell(2, 1).unwrap();
+    let clipboard = source.copy_to_portable_clipboard().unwrap();
+
+    let mut model = new_empty_user_model();
+    model.set_selected_cell(2, 1).unwrap();
+    model
+        .paste_portable_clipboard(&clipboard, PortableReferences::External)
+        .unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 2, 1),
+        Ok("=[Src.xlsx]Sheet1!A1*2".to_string())
+    );
+}
+
+#[test]
+fn paste_values() {
+    let mut model = new_empty_user_model();
+    copy_and_paste(&mut model, PortableReferences::Values);
+
+    assert_eq!(model.get_cell_content(0, 4, 3), Ok("4".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 3), Ok("12".to_string()));
+    assert_eq!(model.get_cell_content(0, 6, 3), Ok("6".to_string()));
+    assert!(model.get_defined_name_list().is_empty());
+    assert!(model.get_cell_style(0, 4, 3).unwrap().font.b);
+}
+
+#[test]
+fn theme_colors_and_spills() {
+    let mut source = new_empty_user_model();
+    source.set_user_input(0, 1, 1, "=SEQUENCE(3)").unwrap();
+    let area = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    source
+        .update_range_style(&area, "font.color", "[4, 0]")
+        .unwrap();
+    source.set_selected_cell(1, 1).unwrap();
+    source.set_selected_range(1, 1, 3, 1).unwrap();
+    let clipboard = source.copy_to_portable_clipboard().unwrap();
+    assert!(matches!(clipboard.cells[0].style.font.color, Color::Rgb(_)));
+    assert!(clipboard.cells[1].is_spill);
+
+    let mut model = new_empty_user_model();
+    model.set_selected_cell(2, 2).unwrap();
+    model
+        .paste_portable_clipboard(&clipboard, PortableReferences::Rewrite)
+        .unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 2, 2),
+        Ok("=SEQUENCE(3)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 4, 2), Ok("3".to_string()));
+}
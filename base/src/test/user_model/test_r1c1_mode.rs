@@ -0,0 +1,189 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::test::user_model::util::new_empty_user_model;
+use crate::UserModel;
+
+#[test]
+fn enter_and_display_r1c1() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 2, 3, "5").unwrap();
+    model.set_user_input(0, 3, 1, "7").unwrap();
+    model.set_r1c1(true).unwrap();
+    assert!(model.get_r1c1());
+
+    model.set_user_input(0, 3, 3, "=R[-1]C+RC[-2]").unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=R[-1]C+RC[-2]");
+    assert_eq!(model.get_formatted_cell_value(0, 3, 3).unwrap(), "12");
+
+    model.set_r1c1(false).unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 3).unwrap(), "=C2+A3");
+}
+
+#[test]
+fn a1_formulas_in_r1c1() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 3, 3, "=SUM(A1:B2)").unwrap();
+    model.set_user_input(0, 4, 3, "=$A$1+Sheet1!B$2").unwrap();
+    model.set_user_input(0, 5, 3, "=SUM(A:A)+SUM(2:3)").unwrap();
+    model.set_r1c1(true).unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 3, 3).unwrap(),
+        "=SUM(R[-2]C[-2]:R[-1]C[-1])"
+    );
+    assert_eq!(
+        model.get_cell_content(0, 4, 3).unwrap(),
+        "=R1C1+Sheet1!R2C[-1]"
+    );
+    assert_eq!(
+        model.get_cell_content(0, 5, 3).unwrap(),
+        "=SUM(C[-2]:C[-2])+SUM(R[-3]:R[-2])"
+    );
+
+    // What is displayed can be entered back
+    model
+        .set_user_input(0, 6, 3, "=SUM(C[-2]:C[-2])+SUM(R[-4]:R[-3])")
+        .unwrap();
+    model.set_r1c1(false).unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 6, 3).unwrap(),
+        "=SUM(A:A)+SUM(2:3)"
+    );
+}
+
+#[test]
+fn whole_rows_and_columns() {
+    let mut model = new_empty_user_model();
+    model.set_r1c1(true).unwrap();
+    model.set_user_input(0, 5, 5, "=SUM(R2)+SUM(C)").unwrap();
+    model.set_user_input(0, 5, 6, "=SUM(R1:R3)").unwrap();
+    model.set_r1c1(false).unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 5, 5).unwrap(),
+        "=SUM($2:$2)+SUM(E:E)"
+    );
+    assert_eq!(model.get_cell_content(0, 5, 6).unwrap(), "=SUM($1:$3)");
+}
+
+#[test]
+fn identifiers_and_strings_are_kept() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "2.5").unwrap();
+    model.set_r1c1(true).unwrap();
+    model
+        .set_user_input(0, 1, 2, "=ROUND(rc[-1],0)&\"RC\"")
+        .unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 1, 2).unwrap(),
+        "=ROUND(RC[-1],0)&\"RC\""
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "3RC");
+
+    // references outside of the sheet
+    model.set_user_input(0, 2, 2, "=R[-5]C").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 2, 2).unwrap(), "#REF!");
+}
+
+#[test]
+fn localized_letters() {
+    let mut model = UserModel::new_empty("model", "de", "UTC", "de").unwrap();
+    model.set_user_input(0, 1, 1, "3").unwrap();
+    model.set_user_input(0, 2, 1, "4").unwrap();
+    model.set_r1c1(true).unwrap();
+    model
+        .set_user_input(0, 3, 1, "=SUMME(Z[-2]S:Z[-1]S)")
+        .unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 3, 1).unwrap(),
+        "=SUMME(Z[-2]S:Z[-1]S)"
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 3, 1).unwrap(), "7");
+
+    model.set_r1c1(false).unwrap();
+    assert_eq!(model.get_cell_content(0, 3, 1).unwrap(), "=SUMME(A1:A2)");
+}
+
+#[test]
+fn defined_names() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "42").unwrap();
+    model.set_r1c1(true).unwrap();
+    model
+        .new_defined_name("answer", None, "Sheet1!R1C1")
+        .unwrap();
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("answer".to_string(), None, "Sheet1!R1C1".to_string())]
+    );
+    model.set_user_input(0, 2, 2, "=answer").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 2, 2).unwrap(), "42");
+
+    model.set_r1c1(false).unwrap();
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("answer".to_string(), None, "Sheet1!$A$1".to_string())]
+    );
+}
+
+#[test]
+fn defined_names_relative_to_the_selected_cell() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 3, 1, "42").unwrap();
+    model.set_r1c1(true).unwrap();
+    model.set_selected_cell(3, 2).unwrap();
+    model
+        .new_defined_name("left", None, "Sheet1!RC[-1]")
+        .unwrap();
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("left".to_string(), None, "Sheet1!RC[-1]".to_string())]
+    );
+
+    model.set_selected_cell(1, 1).unwrap();
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("left".to_string(), None, "Sheet1!R[2]C".to_string())]
+    );
+    model.set_user_input(0, 5, 5, "=left").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 5, 5).unwrap(), "42");
+
+    model.set_r1c1(false).unwrap();
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("left".to_string(), None, "Sheet1!A3".to_string())]
+    );
+}
+
+#[test]
+fn undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_r1c1(true).unwrap();
+    model.set_user_input(0, 1, 3, "=RC[-2]").unwrap();
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(!model.get_r1c1());
+    assert_eq!(model.get_cell_content(0, 1, 3).unwrap(), "");
+
+    model.redo().unwrap();
+    assert!(model.get_r1c1());
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3).unwrap(), "=RC[-2]");
+    model.set_r1c1(false).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 3).unwrap(), "=A1");
+}
+
+#[test]
+fn other_sheets_and_ranges_outside_of_the_sheet() {
+    let mut model = new_empty_user_model();
+    model.new_sheet().unwrap();
+    model.set_user_input(1, 2, 2, "6").unwrap();
+    model.set_r1c1(true).unwrap();
+    model
+        .set_user_input(0, 1, 1, "=Sheet2!R[1]C[1]*2+SUM(R[-1]C:RC)")
+        .unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "#REF!");
+    model.set_user_input(0, 1, 1, "=Sheet2!R[1]C[1]*2").unwrap();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "12");
+
+    model.set_r1c1(false).unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1).unwrap(), "=Sheet2!B2*2");
+}
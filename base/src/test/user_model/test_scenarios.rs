@@ -0,0 +1,151 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::CellReferenceIndex;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::types::{Scenario, ScenarioInputCell};
+use crate::UserModel;
+
+fn scenario(name: &str, value: &str) -> Scenario {
+    Scenario {
+        name: name.to_string(),
+        comment: String::new(),
+        user: String::new(),
+        locked: true,
+        hidden: false,
+        input_cells: vec![ScenarioInputCell {
+            row: 1,
+            column: 1,
+            value: value.to_string(),
+        }],
+    }
+}
+
+#[test]
+fn scenarios_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.add_scenario(0, scenario("First", "1")).unwrap();
+    model.add_scenario(0, scenario("Second", "2")).unwrap();
+    model
+        .update_scenario(0, "Second", scenario("Third", "3"))
+        .unwrap();
+    model.delete_scenario(0, "First").unwrap();
+    assert_eq!(
+        model.get_scenarios(0).unwrap(),
+        vec![scenario("Third", "3")]
+    );
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_scenarios(0).unwrap(),
+        vec![scenario("First", "1"), scenario("Third", "3")]
+    );
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_scenarios(0).unwrap(),
+        vec![scenario("First", "1"), scenario("Second", "2")]
+    );
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(model.get_scenarios(0).unwrap().is_empty());
+
+    model.redo().unwrap();
+    model.redo().unwrap();
+    model.redo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_scenarios(0).unwrap(),
+        vec![scenario("Third", "3")]
+    );
+}
+
+#[test]
+fn show_scenario_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "5").unwrap();
+    model.set_user_input(0, 2, 1, "=A1*2").unwrap();
+    model.add_scenario(0, scenario("Big", "50")).unwrap();
+
+    model.show_scenario(0, "big").unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("100".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("10".to_string())
+    );
+
+    model.redo().unwrap();
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("100".to_string())
+    );
+}
+
+#[test]
+fn show_scenario_redo_in_other_locale() {
+    let mut model = UserModel::new_empty("model", "de", "UTC", "de").unwrap();
+    model.add_scenario(0, scenario("Half", "0.5")).unwrap();
+    model.show_scenario(0, "Half").unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("0,5".to_string()));
+
+    model.undo().unwrap();
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("0,5".to_string()));
+}
+
+#[test]
+fn delete_rows_undo_restores_scenarios() {
+    let mut model = new_empty_user_model();
+    model.add_scenario(0, scenario("First", "1")).unwrap();
+    model.delete_rows(0, 1, 1).unwrap();
+    assert!(model.get_scenarios(0).unwrap().is_empty());
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_scenarios(0).unwrap(),
+        vec![scenario("First", "1")]
+    );
+    model.redo().unwrap();
+    assert!(model.get_scenarios(0).unwrap().is_empty());
+}
+
+#[test]
+fn scenario_summary_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "5").unwrap();
+    model.set_user_input(0, 2, 1, "=A1*2").unwrap();
+    model.add_scenario(0, scenario("Big", "50")).unwrap();
+    let result = CellReferenceIndex {
+        sheet: 0,
+        row: 2,
+        column: 1,
+    };
+    let index = model.create_scenario_summary(0, &[result]).unwrap();
+    assert_eq!(index, 1);
+    assert_eq!(model.get_selected_sheet(), 1);
+    assert_eq!(
+        model.get_formatted_cell_value(1, 7, 5),
+        Ok("100".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(0, 2, 1),
+        Ok("10".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_worksheets_properties().len(), 1);
+
+    model.redo().unwrap();
+    assert_eq!(model.get_worksheets_properties().len(), 2);
+    assert_eq!(
+        model.get_formatted_cell_value(1, 2, 2),
+        Ok("Scenario Summary".to_string())
+    );
+    assert_eq!(
+        model.get_formatted_cell_value(1, 7, 5),
+        Ok("100".to_string())
+    );
+}
@@ -0,0 +1,229 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::cf_types::{CfRuleInput, Cfvo, ColorScaleThreshold};
+use crate::expressions::types::Area;
+use crate::test::user_model::util::new_empty_user_model;
+use crate::types::{Color, ShiftDirection};
+
+fn area(row: i32, column: i32, width: i32, height: i32) -> Area {
+    Area {
+        sheet: 0,
+        row,
+        column,
+        width,
+        height,
+    }
+}
+
+#[test]
+fn insert_cells_shift_right() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 2, 2, "1").unwrap();
+    model.set_user_input(0, 2, 3, "2").unwrap();
+    model.set_user_input(0, 5, 2, "3").unwrap();
+    model.set_user_input(0, 1, 1, "=B2+C2+B5").unwrap();
+    model.set_user_input(0, 1, 2, "=SUM(B2:C2)").unwrap();
+
+    model
+        .insert_cells(&area(2, 2, 2, 2), ShiftDirection::Right)
+        .unwrap();
+
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 4), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 5), Ok("2".to_string()));
+    // Outside of the band
+    assert_eq!(model.get_cell_content(0, 5, 2), Ok("3".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("=D2+E2+B5".to_string()));
+    assert_eq!(
+        model.get_cell_content(0, 1, 2),
+        Ok("=SUM(D2:E2)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("6".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 4), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("=B2+C2+B5".to_string()));
+
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 4), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("=D2+E2+B5".to_string()));
+}
+
+#[test]
+fn insert_cells_shift_down() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 3, 2, "1").unwrap();
+    model.set_user_input(0, 3, 4, "2").unwrap();
+    model.set_user_input(0, 1, 1, "=B3+D3").unwrap();
+    // A formula that moves keeps pointing to the same cells
+    model.set_user_input(0, 4, 2, "=B3*10").unwrap();
+
+    model
+        .insert_cells(&area(2, 2, 2, 1), ShiftDirection::Down)
+        .unwrap();
+
+    assert_eq!(model.get_cell_content(0, 4, 2), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 5, 2), Ok("=B4*10".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 4), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("=B4+D3".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 5, 2),
+        Ok("10".to_string())
+    );
+}
+
+#[test]
+fn delete_cells_shift_left() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 2, 2, "1").unwrap();
+    model.set_user_input(0, 2, 3, "2").unwrap();
+    model.set_user_input(0, 2, 4, "3").unwrap();
+    model.set_user_input(0, 1, 1, "=D2*2").unwrap();
+    model.set_user_input(0, 3, 1, "=B2").unwrap();
+
+    model
+        .delete_cells(&area(2, 2, 2, 1), ShiftDirection::Left)
+        .unwrap();
+
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("3".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 4), Ok("".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("=B2*2".to_string()));
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("=#REF!".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 2, 2), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 3), Ok("2".to_string()));
+    assert_eq!(model.get_cell_content(0, 2, 4), Ok("3".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("=D2*2".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1), Ok("6".to_string()));
+}
+
+#[test]
+fn delete_cells_shift_up() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 2, 1, "1").unwrap();
+    model.set_user_input(0, 3, 1, "2").unwrap();
+    model.set_user_input(0, 4, 1, "3").unwrap();
+    model.set_user_input(0, 1, 2, "=SUM(A2:A4)").unwrap();
+
+    model
+        .delete_cells(&area(3, 1, 1, 1), ShiftDirection::Up)
+        .unwrap();
+
+    assert_eq!(model.get_cell_content(0, 3, 1), Ok("3".to_string()));
+    assert_eq!(
+        model.get_cell_content(0, 1, 2),
+        Ok("=SUM(A2:A3)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("4".to_string()));
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 1, 2),
+        Ok("=SUM(A2:A4)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2), Ok("6".to_string()));
+}
+
+#[test]
+fn wrong_direction() {
+    let mut model = new_empty_user_model();
+    assert!(model
+        .insert_cells(&area(1, 1, 1, 1), ShiftDirection::Left)
+        .is_err());
+    assert!(model
+        .delete_cells(&area(1, 1, 1, 1), ShiftDirection::Down)
+        .is_err());
+}
+
+#[test]
+fn defined_names_and_conditional_formatting() {
+    let mut model = new_empty_user_model();
+    model
+        .new_defined_name("Total", None, "Sheet1!$C$2")
+        .unwrap();
+    let rule = CfRuleInput::ColorScale {
+        thresholds: vec![
+            ColorScaleThreshold {
+                cfvo: Cfvo::Min,
+                color: Color::Rgb("#FF0000".to_string()),
+            },
+            ColorScaleThreshold {
+                cfvo: Cfvo::Max,
+                color: Color::Rgb("#00FF00".to_string()),
+            },
+        ],
+    };
+    model.add_conditional_formatting(0, "C2:D2", rule).unwrap();
+
+    model
+        .insert_cells(&area(2, 1, 1, 1), ShiftDirection::Right)
+        .unwrap();
+
+    assert_eq!(
+        model.get_defined_name_list(),
+        vec![("Total".to_string(), None, "Sheet1!$D$2".to_string())]
+    );
+    let list = model.get_conditional_formatting_list(0).unwrap();
+    assert_eq!(list[0].range, "D2:E2");
+}
+
+#[test]
+fn merged_cells() {
+    let mut model = new_empty_user_model();
+    model.model.workbook.worksheets[0]
+        .merge_cells
+        .push("C2:D3".to_string());
+
+    // Would split the merge
+    assert!(model
+        .insert_cells(&area(2, 1, 1, 1), ShiftDirection::Right)
+        .is_err());
+
+    model
+        .insert_cells(&area(2, 1, 1, 2), ShiftDirection::Right)
+        .unwrap();
+    assert_eq!(
+        model.model.workbook.worksheets[0].merge_cells,
+        vec!["D2:E3".to_string()]
+    );
+
+    model
+        .delete_cells(&area(2, 4, 2, 2), ShiftDirection::Left)
+        .unwrap();
+    assert!(model.model.workbook.worksheets[0].merge_cells.is_empty());
+
+    model.undo().unwrap();
+    assert_eq!(
+        model.model.workbook.worksheets[0].merge_cells,
+        vec!["D2:E3".to_string()]
+    );
+}
+
+#[test]
+fn array_formulas() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "=SEQUENCE(3)").unwrap();
+    model
+        .set_user_array_formula(0, 1, 3, 1, 3, "=A1:A3*2")
+        .unwrap();
+
+    // Would split the CSE array in C1:C3
+    assert_eq!(
+        model.insert_cells(&area(2, 3, 1, 1), ShiftDirection::Right),
+        Err("Cannot shift cells because that would break an array formula".to_string())
+    );
+
+    // Dynamic arrays spill again from their new anchor
+    model
+        .insert_cells(&area(1, 1, 1, 3), ShiftDirection::Down)
+        .unwrap();
+    assert_eq!(
+        model.get_cell_content(0, 4, 1),
+        Ok("=SEQUENCE(3)".to_string())
+    );
+    assert_eq!(model.get_formatted_cell_value(0, 6, 1), Ok("3".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 3, 1), Ok("".to_string()));
+}
@@ -0,0 +1,64 @@
+#![allow(clippy::unwrap_used)]
+
+use crate::expressions::types::{Area, CellReferenceIndex};
+use crate::solver::{
+    ConstraintOperator, SolverConstraint, SolverGoal, SolverOptions, SolverProblem, SolverStatus,
+};
+use crate::test::user_model::util::new_empty_user_model;
+
+#[test]
+fn solve_undo_redo() {
+    let mut model = new_empty_user_model();
+    model.set_user_input(0, 1, 1, "1").unwrap();
+    model.set_user_input(0, 1, 3, "=2*A1+3*B1").unwrap();
+    model.set_user_input(0, 2, 3, "=A1+B1").unwrap();
+    let problem = SolverProblem {
+        objective: CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 3,
+        },
+        goal: SolverGoal::Maximize,
+        variables: vec![Area {
+            sheet: 0,
+            row: 1,
+            column: 1,
+            width: 2,
+            height: 1,
+        }],
+        constraints: vec![SolverConstraint {
+            left: Area {
+                sheet: 0,
+                row: 2,
+                column: 3,
+                width: 1,
+                height: 1,
+            },
+            operator: ConstraintOperator::LessOrEqual,
+            right: "4".to_string(),
+        }],
+        options: SolverOptions::default(),
+    };
+
+    let result = model.solve(&problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("0".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("4".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 3),
+        Ok("12".to_string())
+    );
+
+    // Both cells change in a single step
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 1), Ok("1".to_string()));
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("".to_string()));
+    assert_eq!(model.get_formatted_cell_value(0, 1, 3), Ok("2".to_string()));
+
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_content(0, 1, 2), Ok("4".to_string()));
+    assert_eq!(
+        model.get_formatted_cell_value(0, 1, 3),
+        Ok("12".to_string())
+    );
+}
@@ -1,5 +1,7 @@
 #![allow(clippy::unwrap_used)]
-use crate::types::Color;
+use crate::types::{
+    Color, FontScheme, GradientStop, GradientType, PatternType, Protection, ReadingOrder,
+};
 
 use crate::{
     expressions::types::Area,
@@ -241,7 +243,8 @@ fn basic_alignment() {
         Some(Alignment {
             horizontal: HorizontalAlignment::Center,
             vertical: VerticalAlignment::Bottom,
-            wrap_text: false
+            wrap_text: false,
+            ..Default::default()
         })
     );
 
@@ -254,7 +257,8 @@ fn basic_alignment() {
         Some(Alignment {
             horizontal: HorizontalAlignment::CenterContinuous,
             vertical: VerticalAlignment::Bottom,
-            wrap_text: false
+            wrap_text: false,
+            ..Default::default()
         })
     );
 
@@ -275,7 +279,8 @@ fn basic_alignment() {
         Some(Alignment {
             horizontal: HorizontalAlignment::General,
             vertical: VerticalAlignment::Distributed,
-            wrap_text: false
+            wrap_text: false,
+            ..Default::default()
         })
     );
 
@@ -288,7 +293,8 @@ fn basic_alignment() {
         Some(Alignment {
             horizontal: HorizontalAlignment::General,
             vertical: VerticalAlignment::Justify,
-            wrap_text: false
+            wrap_text: false,
+            ..Default::default()
         })
     );
 
@@ -304,7 +310,8 @@ fn basic_alignment() {
         Some(Alignment {
             horizontal: HorizontalAlignment::General,
             vertical: VerticalAlignment::Justify,
-            wrap_text: false
+            wrap_text: false,
+            ..Default::default()
         })
     );
 }
@@ -380,7 +387,8 @@ fn basic_wrap_text() {
         Some(Alignment {
             horizontal: HorizontalAlignment::General,
             vertical: VerticalAlignment::Bottom,
-            wrap_text: true
+            wrap_text: true,
+            ..Default::default()
         })
     );
     model.undo().unwrap();
@@ -395,7 +403,8 @@ fn basic_wrap_text() {
         Some(Alignment {
             horizontal: HorizontalAlignment::General,
             vertical: VerticalAlignment::Bottom,
-            wrap_text: true
+            wrap_text: true,
+            ..Default::default()
         })
     );
 
@@ -531,3 +540,310 @@ fn font_size_errors() {
         Err("Invalid value for font size: '-1'.".to_string())
     );
 }
+
+#[test]
+fn font_name() {
+    let mut model = new_empty_user_model();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    let font = model.get_cell_style(0, 1, 1).unwrap().font;
+    assert_eq!(font.name, "Inter");
+    assert_eq!(font.scheme, FontScheme::Minor);
+
+    model
+        .update_range_style(&range, "font.name", "Georgia")
+        .unwrap();
+    let font = model.get_cell_style(0, 1, 1).unwrap().font;
+    assert_eq!(font.name, "Georgia");
+    // An explicit font is no longer the theme font
+    assert_eq!(font.scheme, FontScheme::None);
+
+    assert_eq!(
+        model.update_range_style(&range, "font.name", " "),
+        Err("Invalid value for font name: ''.".to_string())
+    );
+
+    model.undo().unwrap();
+    assert_eq!(model.get_cell_style(0, 1, 1).unwrap().font.name, "Inter");
+    model.redo().unwrap();
+    assert_eq!(model.get_cell_style(0, 1, 1).unwrap().font.name, "Georgia");
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = new_empty_user_model();
+    model2.apply_external_diffs(&send_queue).unwrap();
+    assert_eq!(model2.get_cell_style(0, 1, 1).unwrap().font.name, "Georgia");
+
+    // Georgia is not available for rendering by default and the font family is sans serif
+    let font = model.get_cell_style(0, 1, 1).unwrap().font;
+    assert_eq!(model.get_rendering_font_name(&font), "Inter");
+    let mut fallback = model.get_font_fallback();
+    fallback.available.push("Georgia".to_string());
+    model.set_font_fallback(fallback);
+    assert_eq!(model.get_rendering_font_name(&font), "Georgia");
+}
+
+#[test]
+fn pattern_fills() {
+    let mut model = new_empty_user_model();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    let fill = model.get_cell_style(0, 1, 1).unwrap().fill;
+    assert_eq!(fill.pattern_type, PatternType::Solid);
+    assert_eq!(fill.color, Color::None);
+
+    // The background colour of a solid fill is its colour
+    model
+        .update_range_style(&range, "fill.bg_color", "#FF0000")
+        .unwrap();
+    let fill = model.get_cell_style(0, 1, 1).unwrap().fill;
+    assert_eq!(fill.color, Color::Rgb("#FF0000".to_string()));
+
+    model
+        .update_range_style(&range, "fill.pattern_type", "darkGrid")
+        .unwrap();
+    model
+        .update_range_style(&range, "fill.fg_color", "#FF0000")
+        .unwrap();
+    model
+        .update_range_style(&range, "fill.bg_color", "[4,0.5]")
+        .unwrap();
+    let fill = model.get_cell_style(0, 1, 1).unwrap().fill;
+    assert_eq!(fill.pattern_type, PatternType::DarkGrid);
+    assert_eq!(fill.color, Color::Rgb("#FF0000".to_string()));
+    assert_eq!(fill.bg_color, Color::Theme(4, 0.5));
+
+    assert_eq!(
+        model.update_range_style(&range, "fill.pattern_type", "dots"),
+        Err("Invalid value for pattern type: 'dots'.".to_string())
+    );
+
+    // A plain colour goes back to a solid fill
+    model
+        .update_range_style(&range, "fill.color", "#00FF00")
+        .unwrap();
+    let fill = model.get_cell_style(0, 1, 1).unwrap().fill;
+    assert_eq!(fill.pattern_type, PatternType::Solid);
+    assert_eq!(fill.bg_color, Color::None);
+
+    model.undo().unwrap();
+    let fill = model.get_cell_style(0, 1, 1).unwrap().fill;
+    assert_eq!(fill.pattern_type, PatternType::DarkGrid);
+    assert_eq!(fill.color, Color::Rgb("#FF0000".to_string()));
+}
+
+#[test]
+fn gradient_fills() {
+    let mut model = new_empty_user_model();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    model
+        .update_range_style(&range, "fill.gradient_stops", "0:#FFFFFF;1:[4,0]")
+        .unwrap();
+    model
+        .update_range_style(&range, "fill.gradient_degree", "90")
+        .unwrap();
+    let gradient = model
+        .get_cell_style(0, 1, 1)
+        .unwrap()
+        .fill
+        .gradient
+        .unwrap();
+    assert_eq!(gradient.gradient_type, GradientType::Linear);
+    assert_eq!(gradient.degree, 90.0);
+    assert_eq!(
+        gradient.stops,
+        vec![
+            GradientStop {
+                position: 0.0,
+                color: Color::Rgb("#FFFFFF".to_string())
+            },
+            GradientStop {
+                position: 1.0,
+                color: Color::Theme(4, 0.0)
+            }
+        ]
+    );
+
+    model
+        .update_range_style(&range, "fill.gradient_type", "path")
+        .unwrap();
+    model
+        .update_range_style(&range, "fill.gradient_rect", "0.5,0.5,0.5,0.5")
+        .unwrap();
+    let gradient = model
+        .get_cell_style(0, 1, 1)
+        .unwrap()
+        .fill
+        .gradient
+        .unwrap();
+    assert_eq!(gradient.gradient_type, GradientType::Path);
+    assert_eq!(gradient.left, 0.5);
+    assert_eq!(gradient.bottom, 0.5);
+
+    assert_eq!(
+        model.update_range_style(&range, "fill.gradient_stops", "0:#FFFFFF"),
+        Err("A gradient needs at least two stops: '0:#FFFFFF'.".to_string())
+    );
+    assert_eq!(
+        model.update_range_style(&range, "fill.gradient_stops", "0:#FFFFFF;2:#000000"),
+        Err("Invalid value for fraction: '2'.".to_string())
+    );
+    assert_eq!(
+        model.update_range_style(&range, "fill.gradient_rect", "0,1"),
+        Err("Invalid value for gradient rectangle: '0,1'.".to_string())
+    );
+    assert_eq!(
+        model.update_range_style(&range, "fill.gradient_type", "radial"),
+        Err("Invalid value for gradient type: 'radial'.".to_string())
+    );
+
+    // Removing the gradient
+    model
+        .update_range_style(&range, "fill.gradient_type", "")
+        .unwrap();
+    assert_eq!(model.get_cell_style(0, 1, 1).unwrap().fill.gradient, None);
+    model.undo().unwrap();
+    assert!(model
+        .get_cell_style(0, 1, 1)
+        .unwrap()
+        .fill
+        .gradient
+        .is_some());
+
+    let send_queue = model.flush_send_queue();
+    let mut model2 = new_empty_user_model();
+    model2.apply_external_diffs(&send_queue).unwrap();
+    let gradient = model2
+        .get_cell_style(0, 1, 1)
+        .unwrap()
+        .fill
+        .gradient
+        .unwrap();
+    assert_eq!(gradient.gradient_type, GradientType::Path);
+}
+
+#[test]
+fn alignment_rotation_and_indent() {
+    let mut model = new_empty_user_model();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    model
+        .update_range_style(&range, "alignment.text_rotation", "45")
+        .unwrap();
+    model
+        .update_range_style(&range, "alignment.indent", "2")
+        .unwrap();
+    model
+        .update_range_style(&range, "alignment.shrink_to_fit", "true")
+        .unwrap();
+    model
+        .update_range_style(&range, "alignment.justify_last_line", "true")
+        .unwrap();
+    model
+        .update_range_style(&range, "alignment.reading_order", "rightToLeft")
+        .unwrap();
+    let alignment = model.get_cell_style(0, 1, 1).unwrap().alignment;
+    assert_eq!(
+        alignment,
+        Some(Alignment {
+            text_rotation: 45,
+            indent: 2,
+            shrink_to_fit: true,
+            justify_last_line: true,
+            reading_order: ReadingOrder::RightToLeft,
+            ..Default::default()
+        })
+    );
+
+    // vertical stacked text
+    model
+        .update_range_style(&range, "alignment.text_rotation", "255")
+        .unwrap();
+    let alignment = model.get_cell_style(0, 1, 1).unwrap().alignment.unwrap();
+    assert_eq!(alignment.text_rotation, 255);
+
+    assert_eq!(
+        model.update_range_style(&range, "alignment.text_rotation", "200"),
+        Err("Invalid value for text rotation: '200'.".to_string())
+    );
+    assert_eq!(
+        model.update_range_style(&range, "alignment.indent", "-1"),
+        Err("Invalid value for indent: '-1'.".to_string())
+    );
+    assert_eq!(
+        model.update_range_style(&range, "alignment.reading_order", "rtl"),
+        Err("Invalid value for reading order: 'rtl'.".to_string())
+    );
+
+    model.undo().unwrap();
+    let alignment = model.get_cell_style(0, 1, 1).unwrap().alignment.unwrap();
+    assert_eq!(alignment.text_rotation, 45);
+
+    // Clearing the alignment removes everything
+    model.update_range_style(&range, "alignment", "").unwrap();
+    assert_eq!(model.get_cell_style(0, 1, 1).unwrap().alignment, None);
+}
+
+#[test]
+fn basic_protection() {
+    let mut model = new_empty_user_model();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    // cells are locked by default
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert_eq!(style.protection, None);
+    assert!(style.is_locked());
+
+    model
+        .update_range_style(&range, "protection.locked", "false")
+        .unwrap();
+    let style = model.get_cell_style(0, 1, 1).unwrap();
+    assert!(!style.is_locked());
+    assert_eq!(
+        style.protection,
+        Some(Protection {
+            locked: false,
+            hidden: false
+        })
+    );
+
+    model
+        .update_range_style(&range, "protection.hidden", "true")
+        .unwrap();
+    let protection = model.get_cell_style(0, 1, 1).unwrap().protection.unwrap();
+    assert!(!protection.locked);
+    assert!(protection.hidden);
+
+    assert!(model
+        .update_range_style(&range, "protection.locked", "maybe")
+        .is_err());
+
+    model.undo().unwrap();
+    model.undo().unwrap();
+    assert!(model.get_cell_style(0, 1, 1).unwrap().is_locked());
+}
@@ -1,6 +1,6 @@
 use bitcode::{Decode, Encode};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, fmt::Display};
+use std::{collections::HashMap, fmt::Display, str::FromStr};
 
 use crate::{cf_types::ConditionalFormatting, expressions::token::Error};
 
@@ -8,10 +8,18 @@ fn default_as_false() -> bool {
     false
 }
 
+fn default_as_true() -> bool {
+    true
+}
+
 fn is_false(b: &bool) -> bool {
     !*b
 }
 
+fn is_zero(i: &i32) -> bool {
+    *i == 0
+}
+
 #[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Clone, Default)]
 #[serde(untagged)]
 pub enum Color {
@@ -106,6 +114,11 @@ pub struct Metadata {
 pub struct WorkbookSettings {
     pub tz: String,
     pub locale: String,
+    /// If true serial numbers count days from 1904-01-01 (older Mac workbooks),
+    /// otherwise from 1900-01-01
+    pub date1904: bool,
+    /// If true formulas are entered and displayed in R1C1 reference style (`=R[-1]C+RC[-2]`)
+    pub r1c1: bool,
 }
 
 /// A Workbook View tracks of the selected sheet for each view
@@ -132,6 +145,7 @@ pub struct Workbook {
     pub tables: HashMap<String, Table>,
     pub views: HashMap<u32, WorkbookView>,
     pub theme: Theme,
+    pub external_links: Vec<ExternalLink>,
 }
 
 /// A defined name. The `sheet_id` is the sheet index in case the name is local
@@ -140,6 +154,34 @@ pub struct DefinedName {
     pub name: String,
     pub formula: String,
     pub sheet_id: Option<u32>,
+    /// Hidden names are not meant for the user, like the Solver setup (`hidden="1"`)
+    pub hidden: bool,
+}
+
+/// A workbook referenced by formulas like `=[Budget.xlsx]Sheet1!A1` (`externalLinkN.xml`).
+/// It keeps the last known values of the cells used by those formulas.
+#[derive(Encode, Decode, Debug, PartialEq, Clone)]
+pub struct ExternalLink {
+    /// The name used in formulas, e.g. "Budget.xlsx"
+    pub workbook: String,
+    /// Location of the linked file as stored in the xlsx file, e.g. "file:///C:/data/Budget.xlsx"
+    pub target: String,
+    pub sheets: Vec<ExternalSheet>,
+}
+
+/// The cached values of a sheet in a linked workbook. It is row first, like [SheetData].
+#[derive(Encode, Decode, Debug, PartialEq, Clone)]
+pub struct ExternalSheet {
+    pub name: String,
+    pub cells: HashMap<i32, HashMap<i32, ExternalValue>>,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq, Clone)]
+pub enum ExternalValue {
+    Number(f64),
+    Boolean(bool),
+    Text(String),
+    Error(Error),
 }
 
 /// * state:
@@ -162,6 +204,28 @@ impl Display for SheetState {
     }
 }
 
+/// Where the cells move when cells are inserted (right or down) or deleted (left or up)
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ShiftDirection {
+    Right,
+    Down,
+    Left,
+    Up,
+}
+
+impl ShiftDirection {
+    /// The direction that undoes a shift in this direction
+    pub fn opposite(self) -> ShiftDirection {
+        match self {
+            ShiftDirection::Right => ShiftDirection::Left,
+            ShiftDirection::Down => ShiftDirection::Up,
+            ShiftDirection::Left => ShiftDirection::Right,
+            ShiftDirection::Up => ShiftDirection::Down,
+        }
+    }
+}
+
 /// Represents the state of the worksheet as seen by the user. This includes
 /// details such as the currently selected cell, the visible range, and the
 /// position of the viewport.
@@ -199,6 +263,53 @@ pub struct Worksheet {
     /// Whether or not to show the grid lines in the worksheet
     pub show_grid_lines: bool,
     pub conditional_formatting: Vec<ConditionalFormatting>,
+    pub outline_properties: OutlineProperties,
+    /// What-If Analysis scenarios of the sheet
+    pub scenarios: Vec<Scenario>,
+}
+
+/// Where the summary rows and columns of outline groups are (`<outlinePr>`).
+/// The collapsed flag of a group is stored in its summary row or column.
+#[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct OutlineProperties {
+    /// Summary rows are below their detail rows
+    pub summary_below: bool,
+    /// Summary columns are to the right of their detail columns
+    pub summary_right: bool,
+}
+
+impl Default for OutlineProperties {
+    fn default() -> Self {
+        OutlineProperties {
+            summary_below: true,
+            summary_right: true,
+        }
+    }
+}
+
+/// A named set of values for some cells of a sheet (What-If Analysis).
+// ECMA-376-1:2016 section 18.3.1.76
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct Scenario {
+    pub name: String,
+    pub comment: String,
+    /// The user who created or last edited the scenario
+    pub user: String,
+    /// Excel does not allow changes to the scenario when the sheet is protected
+    pub locked: bool,
+    /// Excel does not list the scenario when the sheet is protected
+    pub hidden: bool,
+    pub input_cells: Vec<ScenarioInputCell>,
+}
+
+/// A changing cell of a [Scenario] and its value as typed in the default locale and language,
+/// with formulas in A1 style
+// ECMA-376-1:2016 section 18.3.1.42
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct ScenarioInputCell {
+    pub row: i32,
+    pub column: i32,
+    pub value: String,
 }
 
 /// Internal representation of Excel's sheet_data
@@ -215,6 +326,10 @@ pub struct Row {
     pub custom_height: bool,
     pub s: i32,
     pub hidden: bool,
+    /// Outline (grouping) level, 0 if the row is not grouped
+    pub outline_level: i32,
+    /// True if this is the summary row of a collapsed group
+    pub collapsed: bool,
 }
 
 // ECMA-376-1:2016 section 18.3.1.13
@@ -229,6 +344,10 @@ pub struct Col {
     pub custom_width: bool,
     pub hidden: bool,
     pub style: Option<i32>,
+    /// Outline (grouping) level, 0 if the columns are not grouped
+    pub outline_level: i32,
+    /// True if these are summary columns of a collapsed group
+    pub collapsed: bool,
 }
 
 /// Cell type enum matching Excel TYPE() function values.
@@ -462,6 +581,9 @@ impl Default for Styles {
 pub struct Style {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub alignment: Option<Alignment>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub protection: Option<Protection>,
     pub num_fmt: String,
     pub fill: Fill,
     pub font: Font,
@@ -469,10 +591,18 @@ pub struct Style {
     pub quote_prefix: bool,
 }
 
+impl Style {
+    /// Cells are locked unless the style says otherwise
+    pub fn is_locked(&self) -> bool {
+        !matches!(self.protection, Some(Protection { locked: false, .. }))
+    }
+}
+
 impl Default for Style {
     fn default() -> Self {
         Style {
             alignment: None,
+            protection: None,
             num_fmt: "general".to_string(),
             fill: Fill::default(),
             font: Font::default(),
@@ -545,6 +675,10 @@ pub struct Font {
     // 3 -> monospaced
     // ...
     pub family: i32,
+    // The character set of the font (ECMA-376-1:2016 section 18.4.1), e.g. 0 -> ANSI, 128 -> Shift JIS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub charset: Option<i32>,
     pub scheme: FontScheme,
 }
 
@@ -559,16 +693,162 @@ impl Default for Font {
             color: Color::None,
             name: "Inter".to_string(),
             family: 2,
+            charset: None,
             scheme: FontScheme::Minor,
         }
     }
 }
 
+// ST_PatternType simple type (§18.18.55).
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Default)]
+pub enum PatternType {
+    None,
+    #[default]
+    Solid,
+    MediumGray,
+    DarkGray,
+    LightGray,
+    DarkHorizontal,
+    DarkVertical,
+    DarkDown,
+    DarkUp,
+    DarkGrid,
+    DarkTrellis,
+    LightHorizontal,
+    LightVertical,
+    LightDown,
+    LightUp,
+    LightGrid,
+    LightTrellis,
+    Gray125,
+    Gray0625,
+}
+
+impl PatternType {
+    fn is_default(&self) -> bool {
+        self == &PatternType::default()
+    }
+}
+
+impl Display for PatternType {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PatternType::None => write!(formatter, "none"),
+            PatternType::Solid => write!(formatter, "solid"),
+            PatternType::MediumGray => write!(formatter, "mediumGray"),
+            PatternType::DarkGray => write!(formatter, "darkGray"),
+            PatternType::LightGray => write!(formatter, "lightGray"),
+            PatternType::DarkHorizontal => write!(formatter, "darkHorizontal"),
+            PatternType::DarkVertical => write!(formatter, "darkVertical"),
+            PatternType::DarkDown => write!(formatter, "darkDown"),
+            PatternType::DarkUp => write!(formatter, "darkUp"),
+            PatternType::DarkGrid => write!(formatter, "darkGrid"),
+            PatternType::DarkTrellis => write!(formatter, "darkTrellis"),
+            PatternType::LightHorizontal => write!(formatter, "lightHorizontal"),
+            PatternType::LightVertical => write!(formatter, "lightVertical"),
+            PatternType::LightDown => write!(formatter, "lightDown"),
+            PatternType::LightUp => write!(formatter, "lightUp"),
+            PatternType::LightGrid => write!(formatter, "lightGrid"),
+            PatternType::LightTrellis => write!(formatter, "lightTrellis"),
+            PatternType::Gray125 => write!(formatter, "gray125"),
+            PatternType::Gray0625 => write!(formatter, "gray0625"),
+        }
+    }
+}
+
+impl FromStr for PatternType {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "none" => Ok(PatternType::None),
+            "solid" => Ok(PatternType::Solid),
+            "mediumGray" => Ok(PatternType::MediumGray),
+            "darkGray" => Ok(PatternType::DarkGray),
+            "lightGray" => Ok(PatternType::LightGray),
+            "darkHorizontal" => Ok(PatternType::DarkHorizontal),
+            "darkVertical" => Ok(PatternType::DarkVertical),
+            "darkDown" => Ok(PatternType::DarkDown),
+            "darkUp" => Ok(PatternType::DarkUp),
+            "darkGrid" => Ok(PatternType::DarkGrid),
+            "darkTrellis" => Ok(PatternType::DarkTrellis),
+            "lightHorizontal" => Ok(PatternType::LightHorizontal),
+            "lightVertical" => Ok(PatternType::LightVertical),
+            "lightDown" => Ok(PatternType::LightDown),
+            "lightUp" => Ok(PatternType::LightUp),
+            "lightGrid" => Ok(PatternType::LightGrid),
+            "lightTrellis" => Ok(PatternType::LightTrellis),
+            "gray125" => Ok(PatternType::Gray125),
+            "gray0625" => Ok(PatternType::Gray0625),
+            _ => Err(format!("Invalid value for pattern type: '{value}'.")),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "lowercase")]
+#[derive(Default)]
+pub enum GradientType {
+    #[default]
+    Linear,
+    Path,
+}
+
+impl Display for GradientType {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            GradientType::Linear => write!(formatter, "linear"),
+            GradientType::Path => write!(formatter, "path"),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Clone)]
+pub struct GradientStop {
+    /// Position of the stop, between 0 and 1
+    pub position: f64,
+    pub color: Color,
+}
+
+// gradientFill (§18.8.24)
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Clone, Default)]
+pub struct GradientFill {
+    #[serde(default)]
+    pub gradient_type: GradientType,
+    /// Angle of a linear gradient in degrees, clockwise from left to right
+    #[serde(default)]
+    pub degree: f64,
+    // For path gradients, the rectangle the gradient starts from as fractions of the cell
+    #[serde(default)]
+    pub left: f64,
+    #[serde(default)]
+    pub right: f64,
+    #[serde(default)]
+    pub top: f64,
+    #[serde(default)]
+    pub bottom: f64,
+    pub stops: Vec<GradientStop>,
+}
+
+/// The background of a cell.
+/// A solid fill just uses `color`. In pattern fills `color` is the colour of the pattern
+/// and `bg_color` the colour behind it. If there is a gradient it takes precedence.
 #[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Clone, Default)]
 pub struct Fill {
     #[serde(skip_serializing_if = "Color::is_none")]
     #[serde(default)]
     pub color: Color,
+    #[serde(skip_serializing_if = "PatternType::is_default")]
+    #[serde(default)]
+    pub pattern_type: PatternType,
+    #[serde(skip_serializing_if = "Color::is_none")]
+    #[serde(default)]
+    pub bg_color: Color,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub gradient: Option<GradientFill>,
 }
 
 #[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
@@ -640,6 +920,33 @@ impl Display for VerticalAlignment {
     }
 }
 
+// ST_ReadingOrder is not a named simple type, the xlsx values are 0, 1 and 2 (§18.8.1)
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(Default)]
+pub enum ReadingOrder {
+    #[default]
+    ContextDependent,
+    LeftToRight,
+    RightToLeft,
+}
+
+impl ReadingOrder {
+    fn is_default(&self) -> bool {
+        self == &ReadingOrder::default()
+    }
+}
+
+impl Display for ReadingOrder {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ReadingOrder::ContextDependent => write!(formatter, "contextDependent"),
+            ReadingOrder::LeftToRight => write!(formatter, "leftToRight"),
+            ReadingOrder::RightToLeft => write!(formatter, "rightToLeft"),
+        }
+    }
+}
+
 // 1762
 #[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone, Default)]
 pub struct Alignment {
@@ -652,6 +959,45 @@ pub struct Alignment {
     #[serde(default = "default_as_false")]
     #[serde(skip_serializing_if = "is_false")]
     pub wrap_text: bool,
+    /// Rotation in degrees as stored in xlsx: 0 to 90 rotates the text counterclockwise,
+    /// 91 to 180 rotates it clockwise by (value - 90) degrees and 255 stacks the letters vertically
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub text_rotation: i32,
+    /// Indentation level, each level is the width of three characters of the default font
+    #[serde(default)]
+    #[serde(skip_serializing_if = "is_zero")]
+    pub indent: i32,
+    #[serde(default = "default_as_false")]
+    #[serde(skip_serializing_if = "is_false")]
+    pub shrink_to_fit: bool,
+    /// The last line of distributed text is also distributed
+    #[serde(default = "default_as_false")]
+    #[serde(skip_serializing_if = "is_false")]
+    pub justify_last_line: bool,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "ReadingOrder::is_default")]
+    pub reading_order: ReadingOrder,
+}
+
+/// Protection of a cell. It only has an effect when the sheet is protected.
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, PartialEq, Eq, Clone)]
+pub struct Protection {
+    #[serde(default = "default_as_true")]
+    pub locked: bool,
+    /// The formula of the cell is not shown
+    #[serde(default = "default_as_false")]
+    #[serde(skip_serializing_if = "is_false")]
+    pub hidden: bool,
+}
+
+impl Default for Protection {
+    fn default() -> Self {
+        Protection {
+            locked: true,
+            hidden: false,
+        }
+    }
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
@@ -700,6 +1046,7 @@ pub struct CellXfs {
     pub apply_fill: bool,
     pub quote_prefix: bool,
     pub alignment: Option<Alignment>,
+    pub protection: Option<Protection>,
 }
 
 #[derive(Encode, Decode, Debug, PartialEq, Eq, Clone)]
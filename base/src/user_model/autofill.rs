@@ -158,14 +158,16 @@ impl<'a> UserModel<'a> {
             let values = if sign < 0 {
                 (row1..=last_row)
                     .rev()
-                    .map(|row| self.get_cell_content(sheet, row, column))
+                    .map(|row| self.model.get_localized_a1_cell_content(sheet, row, column))
                     .collect::<Result<Vec<_>, _>>()?
             } else {
                 (row1..=last_row)
-                    .map(|row| self.get_cell_content(sheet, row, column))
+                    .map(|row| self.model.get_localized_a1_cell_content(sheet, row, column))
                     .collect::<Result<Vec<_>, _>>()?
             };
-            let case_seed = self.get_cell_content(sheet, row1, column)?;
+            let case_seed = self
+                .model
+                .get_localized_a1_cell_content(sheet, row1, column)?;
             let possible_progression = detect_progression(&values, locale, &case_seed);
             for (range_idx, row_ref) in row_range.iter().enumerate() {
                 let row = *row_ref;
@@ -192,7 +194,7 @@ impl<'a> UserModel<'a> {
                 }
 
                 self.model
-                    .set_user_input(sheet, row, column, target_value.to_string())?;
+                    .set_a1_user_input(sheet, row, column, target_value.to_string())?;
 
                 // Compute the new style and set it
                 let new_style = self.model.get_style_for_cell(sheet, source_row, column)?;
@@ -298,14 +300,16 @@ impl<'a> UserModel<'a> {
             let values = if sign < 0 {
                 (column1..=last_column)
                     .rev()
-                    .map(|column| self.get_cell_content(sheet, row, column))
+                    .map(|column| self.model.get_localized_a1_cell_content(sheet, row, column))
                     .collect::<Result<Vec<_>, _>>()?
             } else {
                 (column1..=last_column)
-                    .map(|column| self.get_cell_content(sheet, row, column))
+                    .map(|column| self.model.get_localized_a1_cell_content(sheet, row, column))
                     .collect::<Result<Vec<_>, _>>()?
             };
-            let case_seed = self.get_cell_content(sheet, row, column1)?;
+            let case_seed = self
+                .model
+                .get_localized_a1_cell_content(sheet, row, column1)?;
             let possible_progression = detect_progression(&values, locale, &case_seed);
             for (range_idx, column_ref) in column_range.iter().enumerate() {
                 let column = *column_ref;
@@ -333,7 +337,7 @@ impl<'a> UserModel<'a> {
                 }
 
                 self.model
-                    .set_user_input(sheet, row, column, target_value.to_string())?;
+                    .set_a1_user_input(sheet, row, column, target_value.to_string())?;
 
                 let new_style = self.model.get_style_for_cell(sheet, row, source_column)?;
                 // Compute the new style and set it
@@ -2,6 +2,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    fmt::Write,
     io::Cursor,
 };
 
@@ -9,14 +10,34 @@ use csv::{ReaderBuilder, WriterBuilder};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    cell::CellValue,
     cf_types::ConditionalFormatting,
-    expressions::types::{Area, CellReferenceIndex},
+    constants::{LAST_COLUMN, LAST_ROW},
+    expressions::{
+        lexer::LexerMode,
+        parser::{
+            parse_range,
+            static_analysis::walk_nodes_mut,
+            stringify::{
+                externalize_references_in_node, to_english_string, to_localized_string,
+                to_rc_format,
+            },
+            Node,
+        },
+        token::{get_error_by_english_name, Error},
+        types::{Area, CellReferenceIndex, CellReferenceRC},
+        utils::{number_to_column, quote_name},
+    },
+    language::get_default_language,
     model::CellStructure,
-    types::{ArrayKind, Cell, Style},
+    types::{ArrayKind, Cell, CellType, Color, Style, Theme},
     UserModel,
 };
 
 use crate::user_model::history::Diff;
+use crate::user_model::html::{
+    escape_html, json_field, json_string, read_html_table, style_to_css, HtmlCell,
+};
 
 /// Data for the clipboard
 pub type ClipboardData = HashMap<i32, HashMap<i32, ClipboardCell>>;
@@ -26,6 +47,10 @@ pub type ClipboardTuple = (i32, i32, i32, i32);
 #[derive(Serialize, Deserialize)]
 pub struct ClipboardCell {
     text: String,
+    /// The value as it would be typed, used to paste values
+    value: String,
+    /// The value if it is a number, used to paste with an operation
+    number: Option<f64>,
     is_spill: bool,
     style: Style,
 }
@@ -38,6 +63,129 @@ pub struct Clipboard {
     pub(crate) range: (i32, i32, i32, i32),
 }
 
+/// What [UserModel::paste_special] pastes
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteMode {
+    /// Contents and formats, like [UserModel::paste_from_clipboard]
+    All,
+    /// The values, formulas are replaced by their results
+    Values,
+    /// Formulas and constants without the formats
+    Formulas,
+    /// Only the formats
+    Formats,
+    /// Formulas and constants with their number formats
+    FormulasAndNumberFormats,
+    /// Only the widths of the copied columns
+    ColumnWidths,
+    /// Formulas that reference the copied cells (e.g. `=Sheet1!A1`)
+    Link,
+}
+
+/// Operation between the copied numbers and the numbers in the destination
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PasteOperation {
+    /// The copied cells replace the destination
+    None,
+    /// Adds the copied numbers to the destination
+    Add,
+    /// Subtracts the copied numbers from the destination
+    Subtract,
+    /// Multiplies the destination by the copied numbers
+    Multiply,
+    /// Divides the destination by the copied numbers
+    Divide,
+}
+
+/// Options of [UserModel::paste_special]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PasteSpecialOptions {
+    /// What is pasted
+    pub mode: PasteMode,
+    /// Operation with the destination, only when pasting all, values or formulas
+    pub operation: PasteOperation,
+    /// Empty copied cells leave the destination unchanged
+    pub skip_blanks: bool,
+    /// The rows of the copied area are pasted as columns
+    pub transpose: bool,
+}
+
+/// A copy of some cells that does not depend on the workbook it was copied from,
+/// see [UserModel::copy_to_portable_clipboard]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PortableClipboard {
+    /// Name of the workbook the cells were copied from
+    pub workbook: String,
+    /// Name of the sheet the cells were copied from
+    pub sheet: String,
+    /// First row, first column, last row and last column of the copied area
+    pub range: ClipboardTuple,
+    /// Every cell of the copied area, row by row
+    pub cells: Vec<PortableCell>,
+    /// The defined names used by the copied formulas
+    pub defined_names: Vec<PortableDefinedName>,
+}
+
+/// A cell of a [PortableClipboard]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PortableCell {
+    /// Row in the source sheet
+    pub row: i32,
+    /// Column in the source sheet
+    pub column: i32,
+    /// The formula in the internal R1C1 style and in English (`=SUM(R[-2]C[0]:R[-1]C[0])`), if any
+    pub formula: Option<String>,
+    /// The value of the cell, the result if it is a formula
+    pub value: PortableValue,
+    /// The cell is part of the result of a dynamic array formula
+    pub is_spill: bool,
+    /// The style, with the theme colors already resolved
+    pub style: Style,
+}
+
+/// Value of a [PortableCell]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum PortableValue {
+    /// An empty cell
+    Empty,
+    /// A number
+    Number(f64),
+    /// A boolean
+    Boolean(bool),
+    /// A text
+    Text(String),
+    /// An error, in English (`#DIV/0!`)
+    Error(String),
+}
+
+/// A defined name used by the formulas of a [PortableClipboard]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PortableDefinedName {
+    /// Name
+    pub name: String,
+    /// The name is local to the sheet the cells were copied from
+    pub local: bool,
+    /// The formula in English and A1 style (`Sheet1!$A$1:$A$5`)
+    pub formula: String,
+}
+
+/// How [UserModel::paste_portable_clipboard] pastes the references of the copied formulas
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PortableReferences {
+    /// References with a sheet name are to the sheets with the same names in this workbook and the
+    /// defined names that do not exist are created. Formulas that reference a missing sheet are pasted as values.
+    Rewrite,
+    /// References are to the cells of the source workbook (`=[Book.xlsx]Sheet1!A1`),
+    /// the defined names that do not exist are created with references to the source workbook.
+    External,
+    /// Formulas are pasted as values
+    Values,
+}
+
 impl<'a> UserModel<'a> {
     /// Returns a copy of the selected area
     pub fn copy_to_clipboard(&self) -> Result<Clipboard, String> {
@@ -55,16 +203,21 @@ impl<'a> UserModel<'a> {
             let mut text_row = Vec::new();
             for column in column_start..=column_end {
                 let text = self.get_formatted_cell_value(sheet, row, column)?;
-                let content = self.get_cell_content(sheet, row, column)?;
+                let content = self
+                    .model
+                    .get_localized_a1_cell_content(sheet, row, column)?;
                 let style = self.model.get_style_for_cell(sheet, row, column)?;
                 let is_spill = matches!(
                     self.model.get_cell_structure(sheet, row, column)?,
                     CellStructure::SpillArray { .. } | CellStructure::SpillDynamic { .. }
                 );
+                let (value, number) = self.get_cell_value_input(sheet, row, column)?;
                 data_row.insert(
                     column,
                     ClipboardCell {
                         text: content,
+                        value,
+                        number,
                         is_spill,
                         style,
                     },
@@ -200,7 +353,7 @@ impl<'a> UserModel<'a> {
         for (target_row, target_column, old_value, old_style, new_value, style) in changes {
             if let Some(ref v) = new_value {
                 self.model
-                    .set_user_input(sheet, target_row, target_column, v.clone())?;
+                    .set_a1_user_input(sheet, target_row, target_column, v.clone())?;
                 diff_list.push(Diff::SetCellValue {
                     sheet,
                     row: target_row,
@@ -311,7 +464,7 @@ impl<'a> UserModel<'a> {
                     .cell(ext_row, ext_col)
                     .cloned();
                 self.model
-                    .set_user_input(ext_sheet, ext_row, ext_col, new_formula.clone())?;
+                    .set_a1_user_input(ext_sheet, ext_row, ext_col, new_formula.clone())?;
                 diff_list.push(Diff::SetCellValue {
                     sheet: ext_sheet,
                     row: ext_row,
@@ -375,50 +528,362 @@ impl<'a> UserModel<'a> {
             }
         } else {
             // Copy-paste: duplicate CF rules from the source area to the target.
-            let cf_copies = self.model.get_cf_rules_to_copy(
+            self.copy_conditional_formatting(
                 source_sheet,
-                source_first_row,
-                source_first_column,
-                source_last_row,
-                source_last_column,
+                source_range,
+                sheet,
                 selected_row,
                 selected_column,
-            );
-            for (new_range, new_rule) in cf_copies {
-                let priority = self
+                &mut diff_list,
+            )?;
+        }
+        self.push_diff_list(diff_list);
+        // select the pasted area
+        self.set_selected_range(selected_row, selected_column, max_row, max_column)?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Pastes part of what was copied, or combines it with the destination, depending on the options.
+    /// The result is a single step in the undo history. Cut cells are pasted with [UserModel::paste_from_clipboard].
+    ///
+    /// With an operation the copied numbers are combined with the destination: numbers and empty cells
+    /// get the result, formulas are wrapped (`=(A1*2)+5`) and text is left unchanged.
+    /// Copied cells that are not numbers are pasted as usual.
+    pub fn paste_special(
+        &mut self,
+        source_sheet: u32,
+        source_range: ClipboardTuple,
+        clipboard: &ClipboardData,
+        options: &PasteSpecialOptions,
+    ) -> Result<(), String> {
+        let PasteSpecialOptions {
+            mode,
+            operation,
+            skip_blanks,
+            transpose,
+        } = *options;
+        if operation != PasteOperation::None
+            && !matches!(
+                mode,
+                PasteMode::All | PasteMode::Values | PasteMode::Formulas
+            )
+        {
+            return Err("Operations can only be used to paste all, values or formulas".to_string());
+        }
+        let view = self.get_selected_view();
+        let sheet = view.sheet;
+        let [selected_row, selected_column, _, _] = view.range;
+        let (source_first_row, source_first_column, source_last_row, source_last_column) =
+            source_range;
+        let mut diff_list = Vec::new();
+
+        if mode == PasteMode::ColumnWidths {
+            for source_column in source_first_column..=source_last_column {
+                let column = selected_column + source_column - source_first_column;
+                let old_value = self.model.get_column_width(sheet, column)?;
+                let new_value = self.model.get_column_width(source_sheet, source_column)?;
+                self.model.set_column_width(sheet, column, new_value)?;
+                diff_list.push(Diff::SetColumnWidth {
+                    sheet,
+                    column,
+                    new_value,
+                    old_value,
+                });
+            }
+            self.push_diff_list(diff_list);
+            return Ok(());
+        }
+
+        let mut height = source_last_row - source_first_row + 1;
+        let mut width = source_last_column - source_first_column + 1;
+        if transpose {
+            (height, width) = (width, height);
+        }
+        let source_sheet_name = &self.model.workbook.worksheet(source_sheet)?.name;
+        let link_prefix = if source_sheet == sheet {
+            String::new()
+        } else {
+            format!("{}!", quote_name(source_sheet_name))
+        };
+
+        // Compute all changes: (row, column, old value, new value, new style)
+        let mut changes = Vec::new();
+        for (source_row, data_row) in clipboard {
+            for (source_column, value) in data_row {
+                let is_blank = value.text.is_empty() && value.value.is_empty();
+                if skip_blanks && is_blank {
+                    continue;
+                }
+                let mut delta_row = source_row - source_first_row;
+                let mut delta_column = source_column - source_first_column;
+                if transpose {
+                    (delta_row, delta_column) = (delta_column, delta_row);
+                }
+                let target_row = selected_row + delta_row;
+                let target_column = selected_column + delta_column;
+
+                let new_value = match mode {
+                    PasteMode::Formats | PasteMode::ColumnWidths => None,
+                    PasteMode::Link => {
+                        let column_name = number_to_column(*source_column)
+                            .ok_or_else(|| format!("Invalid column: {source_column}"))?;
+                        Some(format!("={link_prefix}{column_name}{source_row}"))
+                    }
+                    PasteMode::Values => Some(value.value.clone()),
+                    PasteMode::All | PasteMode::Formulas | PasteMode::FormulasAndNumberFormats => {
+                        if value.is_spill {
+                            // Spill cells carry no formula/value
+                            Some(String::new())
+                        } else {
+                            // References in formulas are displaced
+                            let source = &CellReferenceIndex {
+                                sheet,
+                                column: *source_column,
+                                row: *source_row,
+                            };
+                            let target = &CellReferenceIndex {
+                                sheet,
+                                column: target_column,
+                                row: target_row,
+                            };
+                            Some(
+                                self.model
+                                    .extend_copied_value(&value.text, source, target)?,
+                            )
+                        }
+                    }
+                };
+                let new_value = match (operation, value.number) {
+                    (PasteOperation::None, _) => new_value,
+                    (_, Some(number)) => self.get_operation_input(
+                        sheet,
+                        target_row,
+                        target_column,
+                        operation,
+                        number,
+                    )?,
+                    // Empty cells do not change the destination
+                    (_, None) if is_blank => None,
+                    (_, None) => new_value,
+                };
+                let new_style = match mode {
+                    PasteMode::All | PasteMode::Formats => Some(value.style.clone()),
+                    PasteMode::FormulasAndNumberFormats => {
+                        let mut style =
+                            self.model
+                                .get_style_for_cell(sheet, target_row, target_column)?;
+                        style.num_fmt = value.style.num_fmt.clone();
+                        Some(style)
+                    }
+                    _ => None,
+                };
+                let old_value = self
                     .model
                     .workbook
                     .worksheet(sheet)?
-                    .conditional_formatting
-                    .iter()
-                    .map(|cf| cf.priority)
-                    .max()
-                    .map(|m| m + 1)
-                    .unwrap_or(1);
-                self.model
-                    .workbook
-                    .worksheet_mut(sheet)?
-                    .conditional_formatting
-                    .push(ConditionalFormatting {
-                        range: new_range.clone(),
-                        cf_rule: new_rule.clone(),
-                        priority,
+                    .cell(target_row, target_column)
+                    .cloned();
+                changes.push((target_row, target_column, old_value, new_value, new_style));
+            }
+        }
+        if mode != PasteMode::Formats && operation == PasteOperation::None && !skip_blanks {
+            // clear the whole area (this resets array formulas)
+            self.model.range_clear_contents(&Area {
+                sheet,
+                row: selected_row,
+                column: selected_column,
+                width,
+                height,
+            })?;
+        }
+        // set the new values and styles
+        for (target_row, target_column, old_value, new_value, new_style) in changes {
+            if let Some(new_value) = new_value {
+                if old_value.is_some() || !new_value.is_empty() {
+                    self.model.set_a1_user_input(
+                        sheet,
+                        target_row,
+                        target_column,
+                        new_value.clone(),
+                    )?;
+                    diff_list.push(Diff::SetCellValue {
+                        sheet,
+                        row: target_row,
+                        column: target_column,
+                        new_value,
+                        old_value: Box::new(old_value),
                     });
-                diff_list.push(Diff::AddConditionalFormatting {
+                }
+            }
+            if let Some(style) = new_style {
+                let old_style =
+                    self.model
+                        .get_cell_style_or_none(sheet, target_row, target_column)?;
+                self.model
+                    .set_cell_style(sheet, target_row, target_column, &style)?;
+                diff_list.push(Diff::SetCellStyle {
                     sheet,
-                    range: new_range,
-                    rule: Box::new(new_rule),
-                    priority,
+                    row: target_row,
+                    column: target_column,
+                    old_value: Box::new(old_style),
+                    new_value: Box::new(style),
                 });
             }
         }
+        if matches!(mode, PasteMode::All | PasteMode::Formats) && !transpose {
+            self.copy_conditional_formatting(
+                source_sheet,
+                source_range,
+                sheet,
+                selected_row,
+                selected_column,
+                &mut diff_list,
+            )?;
+        }
         self.push_diff_list(diff_list);
         // select the pasted area
-        self.set_selected_range(selected_row, selected_column, max_row, max_column)?;
+        self.set_selected_range(
+            selected_row,
+            selected_column,
+            selected_row + height - 1,
+            selected_column + width - 1,
+        )?;
         self.evaluate_if_not_paused();
         Ok(())
     }
 
+    /// Returns the value of a cell as it would be typed and the value if it is a number
+    fn get_cell_value_input(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<(String, Option<f64>), String> {
+        let model = &self.model;
+        let cell = model
+            .workbook
+            .worksheet(sheet)?
+            .cell(row, column)
+            .cloned()
+            .unwrap_or_default();
+        let shared_strings = &model.workbook.shared_strings;
+        let text = cell.get_localized_text(shared_strings, model.locale, model.language);
+        let number = match cell.value(shared_strings, model.language) {
+            CellValue::Number(number) => Some(number),
+            _ => None,
+        };
+        // Text that would be read as something else is quoted
+        if cell.get_type() == CellType::Text && !text.is_empty() && !model.is_text_input(&text) {
+            return Ok((format!("'{text}"), number));
+        }
+        Ok((text, number))
+    }
+
+    /// Returns the input of the cell after the operation with a number, or `None` if it does not change
+    fn get_operation_input(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        operation: PasteOperation,
+        number: f64,
+    ) -> Result<Option<String>, String> {
+        let model = &self.model;
+        let symbol = match operation {
+            PasteOperation::None => return Ok(None),
+            PasteOperation::Add => "+",
+            PasteOperation::Subtract => "-",
+            PasteOperation::Multiply => "*",
+            PasteOperation::Divide => "/",
+        };
+        let to_input = |number: f64| self.number_to_input(number);
+        let content = model.get_localized_a1_cell_content(sheet, row, column)?;
+        if let Some(formula) = content.strip_prefix('=') {
+            let number = if number < 0.0 {
+                format!("({})", to_input(number))
+            } else {
+                to_input(number)
+            };
+            return Ok(Some(format!("=({formula}){symbol}{number}")));
+        }
+        let value = match model.get_cell_value_by_index(sheet, row, column)? {
+            CellValue::Number(value) => value,
+            CellValue::None => 0.0,
+            CellValue::String(_) | CellValue::Boolean(_) => return Ok(None),
+        };
+        let result = match operation {
+            PasteOperation::None => return Ok(None),
+            PasteOperation::Add => value + number,
+            PasteOperation::Subtract => value - number,
+            PasteOperation::Multiply => value * number,
+            PasteOperation::Divide => {
+                if number == 0.0 {
+                    return Ok(Some(Error::DIV.to_localized_error_string(model.language)));
+                }
+                value / number
+            }
+        };
+        Ok(Some(to_input(result)))
+    }
+
+    /// Returns a number as it would be typed
+    fn number_to_input(&self, number: f64) -> String {
+        let model = &self.model;
+        Cell::new_number(number, 0).get_localized_text(&[], model.locale, model.language)
+    }
+
+    /// Duplicates the conditional formatting rules of the source area at the target
+    fn copy_conditional_formatting(
+        &mut self,
+        source_sheet: u32,
+        source_range: ClipboardTuple,
+        sheet: u32,
+        target_row: i32,
+        target_column: i32,
+        diff_list: &mut Vec<Diff>,
+    ) -> Result<(), String> {
+        let (source_first_row, source_first_column, source_last_row, source_last_column) =
+            source_range;
+        let cf_copies = self.model.get_cf_rules_to_copy(
+            source_sheet,
+            source_first_row,
+            source_first_column,
+            source_last_row,
+            source_last_column,
+            target_row,
+            target_column,
+        );
+        for (new_range, new_rule) in cf_copies {
+            let priority = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .conditional_formatting
+                .iter()
+                .map(|cf| cf.priority)
+                .max()
+                .map(|m| m + 1)
+                .unwrap_or(1);
+            self.model
+                .workbook
+                .worksheet_mut(sheet)?
+                .conditional_formatting
+                .push(ConditionalFormatting {
+                    range: new_range.clone(),
+                    cf_rule: new_rule.clone(),
+                    priority,
+                });
+            diff_list.push(Diff::AddConditionalFormatting {
+                sheet,
+                range: new_range,
+                rule: Box::new(new_rule),
+                priority,
+            });
+        }
+        Ok(())
+    }
+
     /// Paste a csv-string into the model
     pub fn paste_csv_string(&mut self, area: &Area, csv: &str) -> Result<(), String> {
         let sheet = area.sheet;
@@ -470,8 +935,9 @@ impl<'a> UserModel<'a> {
             let mut column = area.column;
             for value in row_data {
                 let old_value = old_values.remove(&(row, column)).unwrap_or(None);
+                let value = self.model.user_input_to_a1(sheet, row, column, value);
                 self.model
-                    .set_user_input(sheet, row, column, value.to_string())?;
+                    .set_a1_user_input(sheet, row, column, value.to_string())?;
                 diff_list.push(Diff::SetCellValue {
                     sheet,
                     row,
@@ -490,4 +956,632 @@ impl<'a> UserModel<'a> {
         self.evaluate_if_not_paused();
         Ok(())
     }
+
+    /// Returns the selected area as an HTML table with the formatting as CSS, the way Excel and
+    /// Google Sheets put it on the clipboard. Number formats go in `mso-number-format`, numbers keep
+    /// their precision in `x:num` and formulas are kept, in R1C1 style, in `data-sheets-formula`.
+    pub fn copy_to_html(&self) -> Result<String, String> {
+        let view = self.get_selected_view();
+        let sheet = view.sheet;
+        let [row_start, column_start, row_end, column_end] = view.range;
+        let worksheet = self.model.workbook.worksheet(sheet)?;
+        let dimension = worksheet.dimension();
+        let row_end = row_end.min(dimension.max_row).max(row_start);
+        let column_end = column_end.min(dimension.max_column).max(column_start);
+
+        // Merged cells with the top left corner in the area, clipped to the area
+        let mut spans = HashMap::new();
+        let mut covered = HashSet::new();
+        for merged in &worksheet.merge_cells {
+            let Ok((first_column, first_row, last_column, last_row)) = parse_range(merged) else {
+                continue;
+            };
+            if !(row_start..=row_end).contains(&first_row)
+                || !(column_start..=column_end).contains(&first_column)
+            {
+                continue;
+            }
+            let last_row = last_row.min(row_end);
+            let last_column = last_column.min(column_end);
+            spans.insert(
+                (first_row, first_column),
+                (last_row - first_row + 1, last_column - first_column + 1),
+            );
+            for row in first_row..=last_row {
+                for column in first_column..=last_column {
+                    if (row, column) != (first_row, first_column) {
+                        covered.insert((row, column));
+                    }
+                }
+            }
+        }
+
+        let theme = &self.model.workbook.theme;
+        let mut html = String::from(concat!(
+            "<html xmlns:x=\"urn:schemas-microsoft-com:office:excel\">",
+            "<head><meta charset=\"utf-8\"></head><body>",
+            // Google Sheets only reads the data-sheets-* attributes inside this element
+            "<google-sheets-html-origin>",
+            "<table style=\"border-collapse:collapse;table-layout:fixed\"><colgroup>"
+        ));
+        for column in column_start..=column_end {
+            let width = self.model.get_column_width(sheet, column)?;
+            let _ = write!(html, "<col width=\"{}\">", width.round());
+        }
+        html.push_str("</colgroup>");
+        for row in row_start..=row_end {
+            let height = self.model.get_row_height(sheet, row)?;
+            let _ = write!(html, "<tr style=\"height:{}px\">", height.round());
+            for column in column_start..=column_end {
+                if covered.contains(&(row, column)) {
+                    continue;
+                }
+                html.push_str("<td");
+                if let Some(&(height, width)) = spans.get(&(row, column)) {
+                    if height > 1 {
+                        let _ = write!(html, " rowspan=\"{height}\"");
+                    }
+                    if width > 1 {
+                        let _ = write!(html, " colspan=\"{width}\"");
+                    }
+                }
+                let style = self.model.get_style_for_cell(sheet, row, column)?;
+                let _ = write!(
+                    html,
+                    " style=\"{}\"",
+                    escape_html(&style_to_css(&style, theme))
+                );
+                let value = match self.model.get_cell_value_by_index(sheet, row, column)? {
+                    CellValue::Number(number) => {
+                        let _ = write!(html, " x:num=\"{number}\"");
+                        Some(format!("{{\"1\":3,\"3\":{number}}}"))
+                    }
+                    CellValue::Boolean(value) => {
+                        Some(format!("{{\"1\":4,\"4\":{}}}", value as i32))
+                    }
+                    CellValue::String(text) if !text.is_empty() => {
+                        Some(format!("{{\"1\":2,\"2\":{}}}", json_string(&text)))
+                    }
+                    _ => None,
+                };
+                if let Some(value) = value {
+                    let _ = write!(html, " data-sheets-value=\"{}\"", escape_html(&value));
+                }
+                let is_spill = matches!(
+                    self.model.get_cell_structure(sheet, row, column)?,
+                    CellStructure::SpillArray { .. } | CellStructure::SpillDynamic { .. }
+                );
+                let content = self
+                    .model
+                    .get_localized_r1c1_cell_content(sheet, row, column)?;
+                if content.starts_with('=') && !is_spill {
+                    let _ = write!(html, " data-sheets-formula=\"{}\"", escape_html(&content));
+                }
+                let text = self.get_formatted_cell_value(sheet, row, column)?;
+                let _ = write!(html, ">{}</td>", escape_html(&text).replace('\n', "<br>"));
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table></google-sheets-html-origin></body></html>");
+        Ok(html)
+    }
+
+    /// Pastes an HTML table, as copied from Excel, Google Sheets or [UserModel::copy_to_html], at the
+    /// top left corner of the area. Fonts, colours, borders, alignment, number formats and merged cells
+    /// are kept. Formulas are kept when the source has them in R1C1 style (`data-sheets-formula`),
+    /// otherwise the values are pasted. HTML without a table is pasted in a single cell.
+    ///
+    /// Cells that were merged in the pasted area are unmerged.
+    pub fn paste_html(&mut self, area: &Area, html: &str) -> Result<(), String> {
+        let sheet = area.sheet;
+        let cells = read_html_table(html);
+        if cells.is_empty() {
+            return Ok(());
+        }
+        let width = cells.iter().map(|c| c.column + c.width).max().unwrap_or(1);
+        let height = cells.iter().map(|c| c.row + c.height).max().unwrap_or(1);
+        let last_row = area.row + height - 1;
+        let last_column = area.column + width - 1;
+        if last_row > LAST_ROW || last_column > LAST_COLUMN {
+            return Err("The pasted cells do not fit in the sheet".to_string());
+        }
+        let paste_area = Area {
+            sheet,
+            row: area.row,
+            column: area.column,
+            width,
+            height,
+        };
+
+        // Every cell of the area gets a value and a style so that undo restores all of them.
+        // Cells covered by a merged cell get its style, for the borders.
+        let mut contents: HashMap<(i32, i32), (String, &HtmlCell)> = HashMap::new();
+        for cell in &cells {
+            let row = area.row + cell.row;
+            let column = area.column + cell.column;
+            for r in row..row + cell.height {
+                for c in column..column + cell.width {
+                    contents.insert((r, c), (String::new(), cell));
+                }
+            }
+            let input = self.get_html_cell_input(cell, sheet, row, column);
+            contents.insert((row, column), (input, cell));
+        }
+
+        let mut old_cells = HashMap::new();
+        {
+            let worksheet = self.model.workbook.worksheet(sheet)?;
+            for row in area.row..=last_row {
+                for column in area.column..=last_column {
+                    old_cells.insert((row, column), worksheet.cell(row, column).cloned());
+                }
+            }
+        }
+        let mut old_styles = HashMap::new();
+        for row in area.row..=last_row {
+            for column in area.column..=last_column {
+                let style = self.model.get_cell_style_or_none(sheet, row, column)?;
+                old_styles.insert((row, column), style);
+            }
+        }
+
+        let mut diff_list = Vec::new();
+        let overlapping: Vec<String> = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .merge_cells
+            .iter()
+            .filter(|merged| {
+                parse_range(merged).is_ok_and(|(first_column, first_row, last_c, last_r)| {
+                    first_row <= last_row
+                        && last_r >= area.row
+                        && first_column <= last_column
+                        && last_c >= area.column
+                })
+            })
+            .cloned()
+            .collect();
+        for range in overlapping {
+            let worksheet = self.model.workbook.worksheet_mut(sheet)?;
+            worksheet.merge_cells.retain(|merged| merged != &range);
+            diff_list.push(Diff::UnmergeCells { sheet, range });
+        }
+
+        self.model.range_clear_contents(&paste_area)?;
+
+        let default_cell = HtmlCell {
+            row: 0,
+            column: 0,
+            width: 1,
+            height: 1,
+            text: String::new(),
+            attributes: HashMap::new(),
+            style: Style::default(),
+            num_fmt: None,
+        };
+        for row in area.row..=last_row {
+            for column in area.column..=last_column {
+                let (input, cell) = contents
+                    .remove(&(row, column))
+                    .unwrap_or((String::new(), &default_cell));
+                self.model
+                    .set_a1_user_input(sheet, row, column, input.clone())?;
+                diff_list.push(Diff::SetCellValue {
+                    sheet,
+                    row,
+                    column,
+                    new_value: input,
+                    old_value: Box::new(old_cells.remove(&(row, column)).unwrap_or(None)),
+                });
+                // The number format and the quote prefix are those of the input unless the HTML has a format
+                let input_style = self.model.get_style_for_cell(sheet, row, column)?;
+                let mut style = cell.style.clone();
+                style.num_fmt = cell.num_fmt.clone().unwrap_or(input_style.num_fmt);
+                style.quote_prefix = input_style.quote_prefix;
+                self.model.set_cell_style(sheet, row, column, &style)?;
+                diff_list.push(Diff::SetCellStyle {
+                    sheet,
+                    row,
+                    column,
+                    old_value: Box::new(old_styles.remove(&(row, column)).unwrap_or(None)),
+                    new_value: Box::new(style),
+                });
+            }
+        }
+
+        for cell in cells.iter().filter(|c| c.width > 1 || c.height > 1) {
+            let row = area.row + cell.row;
+            let column = area.column + cell.column;
+            let range = format!(
+                "{}{}:{}{}",
+                number_to_column(column).ok_or("Invalid column")?,
+                row,
+                number_to_column(column + cell.width - 1).ok_or("Invalid column")?,
+                row + cell.height - 1
+            );
+            self.model
+                .workbook
+                .worksheet_mut(sheet)?
+                .merge_cells
+                .push(range.clone());
+            diff_list.push(Diff::MergeCells { sheet, range });
+        }
+
+        self.push_diff_list(diff_list);
+        // select the pasted area
+        self.set_selected_range(area.row, area.column, last_row, last_column)?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the input of a cell pasted from HTML at (row, column): the formula, the value or the text
+    fn get_html_cell_input(&self, cell: &HtmlCell, sheet: u32, row: i32, column: i32) -> String {
+        let model = &self.model;
+        // Text that would be read as something else is quoted
+        let text_input = |text: &str| {
+            if text.is_empty() || model.is_text_input(text) {
+                text.to_string()
+            } else {
+                format!("'{text}")
+            }
+        };
+        if let Some(formula) = cell
+            .attributes
+            .get("data-sheets-formula")
+            .and_then(|formula| formula.strip_prefix('='))
+        {
+            return format!("={}", model.r1c1_to_a1(formula, sheet, row, column));
+        }
+        if let Some(value) = cell.attributes.get("data-sheets-value") {
+            match json_field(value, "1").as_deref() {
+                Some("2") => {
+                    if let Some(text) = json_field(value, "2") {
+                        return text_input(&text);
+                    }
+                }
+                Some("3") => {
+                    if let Some(number) = json_field(value, "3").and_then(|n| n.parse().ok()) {
+                        return self.number_to_input(number);
+                    }
+                }
+                Some("4") => {
+                    if let Some(boolean) = json_field(value, "4") {
+                        let boolean = boolean == "1" || boolean == "true";
+                        return Cell::new_boolean(boolean, 0).get_localized_text(
+                            &[],
+                            model.locale,
+                            model.language,
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+        if let Some(number) = cell
+            .attributes
+            .get("x:num")
+            .and_then(|number| number.trim().parse().ok())
+        {
+            return self.number_to_input(number);
+        }
+        if cell.attributes.contains_key("x:str") || cell.num_fmt.as_deref() == Some("@") {
+            return text_input(&cell.text);
+        }
+        cell.text.clone()
+    }
+
+    /// Returns a copy of the selected area that can be pasted in another workbook with
+    /// [UserModel::paste_portable_clipboard]. It can be serialized.
+    pub fn copy_to_portable_clipboard(&self) -> Result<PortableClipboard, String> {
+        let model = &self.model;
+        let view = self.get_selected_view();
+        let sheet = view.sheet;
+        let worksheet = model.workbook.worksheet(sheet)?;
+        let [row_start, column_start, row_end, column_end] = view.range;
+        let dimension = worksheet.dimension();
+        let row_end = row_end.min(dimension.max_row).max(row_start);
+        let column_end = column_end.min(dimension.max_column).max(column_start);
+        let theme = &model.workbook.theme;
+        let mut cells = Vec::new();
+        let mut defined_names: Vec<PortableDefinedName> = Vec::new();
+        for row in row_start..=row_end {
+            for column in column_start..=column_end {
+                let cell = worksheet.cell(row, column);
+                let formula = match cell.and_then(|cell| cell.get_formula()) {
+                    Some(index) => {
+                        let mut node = model.parsed_formulas[sheet as usize][index as usize]
+                            .0
+                            .clone();
+                        walk_nodes_mut(&mut node, &mut |node| {
+                            if let Node::DefinedNameKind((name, scope, formula)) = node {
+                                let local = scope.is_some();
+                                if !defined_names.iter().any(|defined_name| {
+                                    defined_name.local == local
+                                        && defined_name.name.to_uppercase() == name.to_uppercase()
+                                }) {
+                                    defined_names.push(PortableDefinedName {
+                                        name: name.clone(),
+                                        local,
+                                        formula: formula.clone(),
+                                    });
+                                }
+                            }
+                        });
+                        Some(format!("={}", to_rc_format(&node)))
+                    }
+                    None => None,
+                };
+                let value = match cell {
+                    Some(cell) => {
+                        let shared_strings = &model.workbook.shared_strings;
+                        match cell.value(shared_strings, get_default_language()) {
+                            CellValue::String(text) if cell.get_type() == CellType::ErrorValue => {
+                                PortableValue::Error(text)
+                            }
+                            CellValue::String(text) => PortableValue::Text(text),
+                            CellValue::Number(number) => PortableValue::Number(number),
+                            CellValue::Boolean(boolean) => PortableValue::Boolean(boolean),
+                            CellValue::None => PortableValue::Empty,
+                        }
+                    }
+                    None => PortableValue::Empty,
+                };
+                let is_spill = matches!(
+                    model.get_cell_structure(sheet, row, column)?,
+                    CellStructure::SpillArray { .. } | CellStructure::SpillDynamic { .. }
+                );
+                let mut style = model.get_style_for_cell(sheet, row, column)?;
+                resolve_theme_colors(&mut style, theme);
+                cells.push(PortableCell {
+                    row,
+                    column,
+                    formula,
+                    value,
+                    is_spill,
+                    style,
+                });
+            }
+        }
+        Ok(PortableClipboard {
+            workbook: model.workbook.name.clone(),
+            sheet: worksheet.get_name(),
+            range: (row_start, column_start, row_end, column_end),
+            cells,
+            defined_names,
+        })
+    }
+
+    /// Pastes cells copied with [UserModel::copy_to_portable_clipboard], possibly in another workbook,
+    /// at the selected cell. The result is a single step in the undo history.
+    ///
+    /// Styles are recreated and relative references are displaced as usual. The defined names that are
+    /// created keep their formulas, or reference the source workbook with [PortableReferences::External].
+    pub fn paste_portable_clipboard(
+        &mut self,
+        clipboard: &PortableClipboard,
+        references: PortableReferences,
+    ) -> Result<(), String> {
+        let view = self.get_selected_view();
+        let sheet = view.sheet;
+        let [row, column, _, _] = view.range;
+        let (source_row, source_column, source_last_row, source_last_column) = clipboard.range;
+        let last_row = row + source_last_row - source_row;
+        let last_column = column + source_last_column - source_column;
+        if last_row > LAST_ROW || last_column > LAST_COLUMN {
+            return Err("The pasted cells do not fit in the sheet".to_string());
+        }
+        let sheet_name = self.model.workbook.worksheet(sheet)?.get_name();
+        let mut diff_list = Vec::new();
+
+        // Names that cannot be created, formulas that use them are pasted as values
+        let mut missing_names = HashSet::new();
+        if references != PortableReferences::Values {
+            for defined_name in &clipboard.defined_names {
+                let scope = if defined_name.local {
+                    Some(sheet)
+                } else {
+                    None
+                };
+                if self
+                    .model
+                    .get_defined_name_formula(&defined_name.name, scope)
+                    .is_ok()
+                {
+                    continue;
+                }
+                let formula = if references == PortableReferences::External {
+                    let context = CellReferenceRC {
+                        sheet: sheet_name.clone(),
+                        row: 1,
+                        column: 1,
+                    };
+                    let mut node = self.model.parse_english_formula(
+                        &defined_name.formula,
+                        &context,
+                        LexerMode::A1,
+                    );
+                    externalize_references_in_node(
+                        &mut node,
+                        &clipboard.workbook,
+                        &clipboard.sheet,
+                    );
+                    to_english_string(&node, &context)
+                } else {
+                    defined_name.formula.clone()
+                };
+                match self
+                    .model
+                    .new_defined_name(&defined_name.name, scope, &formula)
+                {
+                    Ok(()) => diff_list.push(Diff::CreateDefinedName {
+                        name: defined_name.name.clone(),
+                        scope,
+                        value: formula,
+                    }),
+                    Err(_) => {
+                        missing_names.insert(defined_name.name.to_uppercase());
+                    }
+                }
+            }
+        }
+
+        let mut changes = Vec::new();
+        for cell in &clipboard.cells {
+            let target_row = row + cell.row - source_row;
+            let target_column = column + cell.column - source_column;
+            let formula_input = match &cell.formula {
+                Some(formula) if references != PortableReferences::Values && !cell.is_spill => self
+                    .get_portable_formula_input(
+                        formula,
+                        &CellReferenceRC {
+                            sheet: sheet_name.clone(),
+                            row: target_row,
+                            column: target_column,
+                        },
+                        clipboard,
+                        references,
+                        &missing_names,
+                    ),
+                _ => None,
+            };
+            let input = match formula_input {
+                Some(input) => input,
+                // The cells of a dynamic array are filled by the formula unless it is pasted as a value
+                None if cell.is_spill && references != PortableReferences::Values => String::new(),
+                None => self.portable_value_to_input(&cell.value),
+            };
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .cell(target_row, target_column)
+                .cloned();
+            let old_style = self
+                .model
+                .get_cell_style_or_none(sheet, target_row, target_column)?;
+            changes.push((target_row, target_column, input, old_value, old_style, cell));
+        }
+
+        self.model.range_clear_contents(&Area {
+            sheet,
+            row,
+            column,
+            width: last_column - column + 1,
+            height: last_row - row + 1,
+        })?;
+        for (target_row, target_column, input, old_value, old_style, cell) in changes {
+            self.model
+                .set_a1_user_input(sheet, target_row, target_column, input.clone())?;
+            diff_list.push(Diff::SetCellValue {
+                sheet,
+                row: target_row,
+                column: target_column,
+                new_value: input,
+                old_value: Box::new(old_value),
+            });
+            let mut style = cell.style.clone();
+            style.quote_prefix = self
+                .model
+                .get_style_for_cell(sheet, target_row, target_column)?
+                .quote_prefix;
+            self.model
+                .set_cell_style(sheet, target_row, target_column, &style)?;
+            diff_list.push(Diff::SetCellStyle {
+                sheet,
+                row: target_row,
+                column: target_column,
+                old_value: Box::new(old_style),
+                new_value: Box::new(style),
+            });
+        }
+        self.push_diff_list(diff_list);
+        // select the pasted area
+        self.set_selected_range(row, column, last_row, last_column)?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the input of a copied formula pasted in `context`, or `None` if it has to be pasted as a value
+    fn get_portable_formula_input(
+        &mut self,
+        formula: &str,
+        context: &CellReferenceRC,
+        clipboard: &PortableClipboard,
+        references: PortableReferences,
+        missing_names: &HashSet<String>,
+    ) -> Option<String> {
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        let mut node = self
+            .model
+            .parse_english_formula(formula, context, LexerMode::R1C1);
+        if references == PortableReferences::External {
+            externalize_references_in_node(&mut node, &clipboard.workbook, &clipboard.sheet);
+        }
+        let mut is_missing = false;
+        walk_nodes_mut(&mut node, &mut |node| match node {
+            Node::WrongReferenceKind { .. } | Node::WrongRangeKind { .. } => is_missing = true,
+            Node::NamedVariableKind { name, .. } => {
+                is_missing |= missing_names.contains(&name.to_uppercase())
+            }
+            _ => {}
+        });
+        if is_missing {
+            return None;
+        }
+        let model = &self.model;
+        Some(format!(
+            "={}",
+            to_localized_string(&node, context, model.locale, model.language)
+        ))
+    }
+
+    /// Returns a copied value as it would be typed
+    fn portable_value_to_input(&self, value: &PortableValue) -> String {
+        let model = &self.model;
+        match value {
+            PortableValue::Empty => String::new(),
+            PortableValue::Number(number) => self.number_to_input(*number),
+            PortableValue::Boolean(boolean) => {
+                Cell::new_boolean(*boolean, 0).get_localized_text(&[], model.locale, model.language)
+            }
+            // Text that would be read as something else is quoted
+            PortableValue::Text(text) => {
+                if text.is_empty() || model.is_text_input(text) {
+                    text.clone()
+                } else {
+                    format!("'{text}")
+                }
+            }
+            PortableValue::Error(error) => match get_error_by_english_name(error) {
+                Some(error) => error.to_localized_error_string(model.language),
+                None => error.clone(),
+            },
+        }
+    }
+}
+
+/// Replaces the theme colors of the style by their values
+fn resolve_theme_colors(style: &mut Style, theme: &Theme) {
+    let resolve = |color: &mut Color| {
+        if let Color::Theme(..) = color {
+            *color = Color::Rgb(color.to_rgb(theme));
+        }
+    };
+    resolve(&mut style.font.color);
+    resolve(&mut style.fill.color);
+    resolve(&mut style.fill.bg_color);
+    let border = &mut style.border;
+    for item in [
+        &mut border.left,
+        &mut border.right,
+        &mut border.top,
+        &mut border.bottom,
+        &mut border.diagonal,
+    ]
+    .into_iter()
+    .flatten()
+    {
+        resolve(&mut item.color);
+    }
 }
@@ -0,0 +1,1327 @@
+//! Collaborative editing on top of the diff queue.
+//!
+//! Every site (a user editing the workbook) wraps the diffs of its [UserModel] in operations with an id
+//! (site, counter), a Lamport timestamp and the vector clock of the operations it had seen.
+//! All the sites order the operations in the same way, by (Lamport timestamp, site), and an operation is
+//! transformed against the concurrent operations before it in that order (e.g. a value set in row 5 moves to
+//! row 6 if someone else inserted a row above). When a remote operation belongs before operations that are
+//! already applied, the workbook is restored from a snapshot and the log is replayed.
+//! Because the order and the transformations are the same in every site, all of them converge to the same
+//! workbook once they have received the same operations.
+//!
+//! The undo and redo lists of each site are transformed in the same way, so undoing a change after remote
+//! edits undoes it where it is now.
+//!
+//! Operations are folded into the snapshot once all the known sites have seen them. A site is known after
+//! it sends an operation or when it is declared with [Collaboration::add_peer].
+
+use std::collections::{BTreeMap, HashMap};
+
+use bitcode::{Decode, Encode};
+
+use crate::{
+    actions::{displace_cf_cell, displace_cf_col, displace_cf_row},
+    expressions::{
+        parser::{
+            parse_range,
+            stringify::{to_string_displaced, DisplaceData},
+        },
+        types::CellReferenceRC,
+        utils::number_to_column,
+    },
+    model::Model,
+    new_empty::move_sheet_index,
+    types::{Cell, ShiftDirection, Style, Workbook, WorksheetView},
+    UserModel,
+};
+
+use super::history::{Diff, DiffList, DiffType, QueueDiffs};
+
+/// Identifies a site (a participant) in a collaborative session
+pub type SiteId = u32;
+
+/// Identifies an operation: the site that created it and its sequence number in that site (starting at 1)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode)]
+pub struct OperationId {
+    /// The site that created the operation
+    pub site: SiteId,
+    /// The sequence number of the operation in the site
+    pub counter: u64,
+}
+
+/// Number of operations of each site that have been seen
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode)]
+pub struct VectorClock(BTreeMap<SiteId, u64>);
+
+impl VectorClock {
+    /// Returns the number of operations of `site` that have been seen
+    pub fn get(&self, site: SiteId) -> u64 {
+        self.0.get(&site).copied().unwrap_or(0)
+    }
+
+    /// Returns true if the operation `id` has been seen
+    pub fn contains(&self, id: &OperationId) -> bool {
+        self.get(id.site) >= id.counter
+    }
+
+    /// Returns true if all the operations seen by `other` have been seen
+    pub fn dominates(&self, other: &VectorClock) -> bool {
+        other
+            .0
+            .iter()
+            .all(|(site, counter)| self.get(*site) >= *counter)
+    }
+
+    fn set(&mut self, site: SiteId, counter: u64) {
+        self.0.insert(site, counter);
+    }
+
+    fn merge(&mut self, other: &VectorClock) {
+        for (site, counter) in &other.0 {
+            if self.get(*site) < *counter {
+                self.set(*site, *counter);
+            }
+        }
+    }
+}
+
+#[derive(Clone, Encode, Decode)]
+struct Operation {
+    id: OperationId,
+    lamport: u64,
+    /// Operations seen by the site when this one was created
+    context: VectorClock,
+    diffs: QueueDiffs,
+    /// Formulas, strings and styles of the cells in the undo diffs
+    cells: CellData,
+}
+
+impl Operation {
+    fn key(&self) -> (u64, SiteId) {
+        (self.lamport, self.id.site)
+    }
+}
+
+/// Cells in the diffs refer to formulas, strings and styles by their position in the lists of the workbook.
+/// Those positions are not the same in every site, so they are replaced by positions in these lists.
+#[derive(Clone, Default, Encode, Decode)]
+struct CellData {
+    formulas: Vec<String>,
+    strings: Vec<String>,
+    styles: Vec<Style>,
+}
+
+fn get_position<T: PartialEq + Clone>(list: &mut Vec<T>, value: &T) -> i32 {
+    match list.iter().position(|x| x == value) {
+        Some(index) => index as i32,
+        None => {
+            list.push(value.clone());
+            list.len() as i32 - 1
+        }
+    }
+}
+
+trait CellVisitor {
+    fn cell(&mut self, sheet: u32, cell: &mut Cell);
+    fn style(&mut self, style: &mut i32);
+}
+
+/// Visits the cells (and row and column styles) that the diffs restore when undone
+fn visit_old_cells(list: &mut DiffList, visitor: &mut impl CellVisitor) {
+    for diff in list {
+        match diff {
+            Diff::SetCellValue {
+                sheet, old_value, ..
+            } => {
+                if let Some(cell) = old_value.as_mut() {
+                    visitor.cell(*sheet, cell);
+                }
+            }
+            Diff::SetArrayValue {
+                sheet,
+                old_values: old_value,
+                ..
+            }
+            | Diff::RangeClearContents {
+                sheet, old_value, ..
+            }
+            | Diff::RangeClearAll {
+                sheet, old_value, ..
+            } => {
+                for cell in old_value.iter_mut().flatten().flatten() {
+                    visitor.cell(*sheet, cell);
+                }
+            }
+            Diff::DeleteCells {
+                sheet, old_value, ..
+            } => {
+                for cell in old_value.iter_mut().flatten().flatten() {
+                    visitor.cell(*sheet, cell);
+                }
+            }
+            Diff::DeleteRows {
+                sheet, old_data, ..
+            } => {
+                for row_data in old_data {
+                    if let Some(row) = &mut row_data.row {
+                        visitor.style(&mut row.s);
+                    }
+                    for cell in row_data.data.values_mut() {
+                        visitor.cell(*sheet, cell);
+                    }
+                }
+            }
+            Diff::DeleteColumns {
+                sheet, old_data, ..
+            } => {
+                for column_data in old_data {
+                    if let Some(style) = column_data.column.as_mut().and_then(|c| c.style.as_mut())
+                    {
+                        visitor.style(style);
+                    }
+                    for cell in column_data.data.values_mut() {
+                        visitor.cell(*sheet, cell);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Moves the formulas, strings and styles of the cells from the workbook to a [CellData]
+struct CellExporter<'a, 'b> {
+    model: &'a Model<'b>,
+    data: CellData,
+}
+
+impl CellVisitor for CellExporter<'_, '_> {
+    fn cell(&mut self, sheet: u32, cell: &mut Cell) {
+        let formulas = self
+            .model
+            .workbook
+            .worksheets
+            .get(sheet as usize)
+            .map(|worksheet| &worksheet.shared_formulas);
+        match cell {
+            Cell::CellFormula { f, .. } | Cell::ArrayFormula { f, .. } => {
+                match formulas.and_then(|formulas| formulas.get(*f as usize)) {
+                    Some(formula) => *f = get_position(&mut self.data.formulas, formula),
+                    None => {
+                        *cell = Cell::EmptyCell {
+                            s: cell.get_style(),
+                        }
+                    }
+                }
+            }
+            Cell::SharedString { si, .. } => {
+                let value = self
+                    .model
+                    .workbook
+                    .shared_strings
+                    .get(*si as usize)
+                    .cloned()
+                    .unwrap_or_default();
+                *si = get_position(&mut self.data.strings, &value);
+            }
+            _ => {}
+        }
+        let mut style = cell.get_style();
+        self.style(&mut style);
+        cell.set_style(style);
+    }
+
+    fn style(&mut self, style: &mut i32) {
+        let value = self
+            .model
+            .workbook
+            .styles
+            .get_style(*style)
+            .unwrap_or_default();
+        *style = get_position(&mut self.data.styles, &value);
+    }
+}
+
+/// Moves the formulas, strings and styles of the cells from a [CellData] to the workbook
+struct CellImporter<'a, 'b, 'c> {
+    model: &'a mut Model<'b>,
+    data: &'c CellData,
+}
+
+impl CellVisitor for CellImporter<'_, '_, '_> {
+    fn cell(&mut self, sheet: u32, cell: &mut Cell) {
+        match cell {
+            Cell::CellFormula { f, .. } | Cell::ArrayFormula { f, .. } => {
+                let index =
+                    self.data.formulas.get(*f as usize).and_then(|formula| {
+                        self.model.get_or_add_shared_formula(sheet, formula).ok()
+                    });
+                match index {
+                    Some(index) => *f = index,
+                    None => {
+                        *cell = Cell::EmptyCell {
+                            s: cell.get_style(),
+                        }
+                    }
+                }
+            }
+            Cell::SharedString { si, .. } => {
+                let value = self
+                    .data
+                    .strings
+                    .get(*si as usize)
+                    .cloned()
+                    .unwrap_or_default();
+                *si = self.model.get_or_add_shared_string(&value);
+            }
+            _ => {}
+        }
+        let mut style = cell.get_style();
+        self.style(&mut style);
+        cell.set_style(style);
+    }
+
+    fn style(&mut self, style: &mut i32) {
+        let value = self
+            .data
+            .styles
+            .get(*style as usize)
+            .cloned()
+            .unwrap_or_default();
+        *style = self.model.workbook.styles.get_style_index_or_create(&value);
+    }
+}
+
+fn export_cells(model: &Model, list: &mut DiffList) -> CellData {
+    let mut exporter = CellExporter {
+        model,
+        data: CellData::default(),
+    };
+    visit_old_cells(list, &mut exporter);
+    exporter.data
+}
+
+fn import_cells(model: &mut Model, list: &mut DiffList, data: &CellData) {
+    visit_old_cells(list, &mut CellImporter { model, data });
+}
+
+#[derive(Clone, Encode, Decode)]
+struct LoggedOperation {
+    operation: Operation,
+    /// The diffs as applied, transformed against the concurrent operations before it
+    applied: QueueDiffs,
+}
+
+/// A change in the layout of the workbook that moves the cells, rows, columns or sheets after it
+enum Shift {
+    Cells(DisplaceData),
+    InsertSheet(u32),
+    DeleteSheet(u32),
+    MoveSheet { from: u32, to: u32 },
+}
+
+impl Shift {
+    fn row(&self, sheet: u32, row: i32) -> Option<i32> {
+        match self {
+            Shift::Cells(data) => displace_cf_row(row, data, sheet),
+            _ => Some(row),
+        }
+    }
+
+    fn column(&self, sheet: u32, column: i32) -> Option<i32> {
+        match self {
+            Shift::Cells(data) => displace_cf_col(column, data, sheet),
+            _ => Some(column),
+        }
+    }
+
+    fn cell(&self, sheet: u32, row: i32, column: i32) -> Option<(i32, i32)> {
+        match self {
+            Shift::Cells(data) => displace_cf_cell(row, column, data, sheet),
+            _ => Some((row, column)),
+        }
+    }
+
+    /// Where rows are inserted: they are never removed, but might end up at the beginning of a deleted block
+    fn row_insertion(&self, sheet: u32, row: i32) -> i32 {
+        match self {
+            Shift::Cells(DisplaceData::Row {
+                sheet: s,
+                row: start,
+                delta,
+            }) if *s == sheet => insertion(row, *start, *delta),
+            _ => self.row(sheet, row).unwrap_or(row),
+        }
+    }
+
+    fn column_insertion(&self, sheet: u32, column: i32) -> i32 {
+        match self {
+            Shift::Cells(DisplaceData::Column {
+                sheet: s,
+                column: start,
+                delta,
+            }) if *s == sheet => insertion(column, *start, *delta),
+            _ => self.column(sheet, column).unwrap_or(column),
+        }
+    }
+
+    /// A block of `count` rows starting at `row`. Rows inserted inside the block become part of it.
+    fn rows(&self, sheet: u32, row: i32, count: i32) -> Option<(i32, i32)> {
+        match self {
+            Shift::Cells(DisplaceData::Row {
+                sheet: s,
+                row: start,
+                delta,
+            }) if *s == sheet => span(row, count, *start, *delta),
+            _ => {
+                let first = self.row(sheet, row)?;
+                let last = self.row(sheet, row + count - 1)?;
+                Some((first.min(last), (last - first).abs() + 1))
+            }
+        }
+    }
+
+    fn columns(&self, sheet: u32, column: i32, count: i32) -> Option<(i32, i32)> {
+        match self {
+            Shift::Cells(DisplaceData::Column {
+                sheet: s,
+                column: start,
+                delta,
+            }) if *s == sheet => span(column, count, *start, *delta),
+            _ => {
+                let first = self.column(sheet, column)?;
+                let last = self.column(sheet, column + count - 1)?;
+                Some((first.min(last), (last - first).abs() + 1))
+            }
+        }
+    }
+
+    /// A range like "B2:C4", that moves and changes its size with its rows and columns
+    fn range(&self, sheet: u32, range: &str) -> Option<String> {
+        let (column1, row1, column2, row2) = parse_range(range).ok()?;
+        let (row, height) = self.rows(sheet, row1, row2 - row1 + 1)?;
+        let (column, width) = self.columns(sheet, column1, column2 - column1 + 1)?;
+        Some(format!(
+            "{}{}:{}{}",
+            number_to_column(column)?,
+            row,
+            number_to_column(column + width - 1)?,
+            row + height - 1
+        ))
+    }
+
+    fn sheet(&self, sheet: u32) -> Option<u32> {
+        match self {
+            Shift::InsertSheet(index) if sheet >= *index => Some(sheet + 1),
+            Shift::DeleteSheet(index) if sheet == *index => None,
+            Shift::DeleteSheet(index) if sheet > *index => Some(sheet - 1),
+            Shift::MoveSheet { from, to } => Some(move_sheet_index(sheet, *from, *to)),
+            _ => Some(sheet),
+        }
+    }
+
+    fn scope(&self, scope: Option<u32>) -> Option<Option<u32>> {
+        match scope {
+            Some(sheet) => self.sheet(sheet).map(Some),
+            None => Some(None),
+        }
+    }
+
+    fn sheet_insertion(&self, index: u32) -> u32 {
+        match self {
+            Shift::DeleteSheet(deleted) if index > *deleted => index - 1,
+            Shift::DeleteSheet(_) => index,
+            _ => self.sheet(index).unwrap_or(index),
+        }
+    }
+
+    /// Updates the references of a formula typed in the cell (`sheet`, `row`, `column`)
+    fn formula(&self, model: &mut Model, sheet: u32, row: i32, column: i32, value: &str) -> String {
+        let (data, body) = match (self, value.strip_prefix('=')) {
+            (Shift::Cells(data), Some(body)) => (data, body),
+            _ => return value.to_string(),
+        };
+        let sheet_name = match model.workbook.worksheets.get(sheet as usize) {
+            Some(worksheet) => worksheet.get_name(),
+            None => return value.to_string(),
+        };
+        let context = CellReferenceRC {
+            sheet: sheet_name,
+            row,
+            column,
+        };
+        let node = model.parser.parse(body, &context);
+        let displaced = to_string_displaced(&node, &context, data);
+        // Formulas that do not change are kept as typed (they might be in another language)
+        if displaced == to_string_displaced(&node, &context, &DisplaceData::None) {
+            value.to_string()
+        } else {
+            format!("={displaced}")
+        }
+    }
+}
+
+fn insertion(index: i32, start: i32, delta: i32) -> i32 {
+    if delta > 0 {
+        if index >= start {
+            index + delta
+        } else {
+            index
+        }
+    } else if index >= start - delta {
+        index + delta
+    } else if index > start {
+        start
+    } else {
+        index
+    }
+}
+
+fn span(first: i32, count: i32, start: i32, delta: i32) -> Option<(i32, i32)> {
+    if delta > 0 {
+        if start <= first {
+            Some((first + delta, count))
+        } else if start < first + count {
+            Some((first, count + delta))
+        } else {
+            Some((first, count))
+        }
+    } else {
+        let end = start - delta;
+        let map = |index: i32| {
+            if index < start {
+                index
+            } else if index < end {
+                start
+            } else {
+                index + delta
+            }
+        };
+        let new_first = map(first);
+        let new_count = map(first + count) - new_first;
+        if new_count > 0 {
+            Some((new_first, new_count))
+        } else {
+            None
+        }
+    }
+}
+
+fn push_move_shifts(
+    shifts: &mut Vec<Shift>,
+    sheet: u32,
+    first: i32,
+    count: i32,
+    delta: i32,
+    rows: bool,
+) {
+    // Same order as Model::move_rows_action and Model::move_columns_action
+    let indices: Vec<i32> = if delta > 0 {
+        (first..first + count).rev().collect()
+    } else {
+        (first..first + count).collect()
+    };
+    for index in indices {
+        shifts.push(Shift::Cells(if rows {
+            DisplaceData::RowMove {
+                sheet,
+                row: index,
+                delta,
+            }
+        } else {
+            DisplaceData::ColumnMove {
+                sheet,
+                column: index,
+                delta,
+            }
+        }));
+    }
+}
+
+/// The shifts caused by a diff, reversed when the diff is undone
+fn push_shifts(shifts: &mut Vec<Shift>, diff: &Diff, undo: bool) {
+    let sign = if undo { -1 } else { 1 };
+    match diff {
+        Diff::InsertRows { sheet, row, count } => shifts.push(Shift::Cells(DisplaceData::Row {
+            sheet: *sheet,
+            row: *row,
+            delta: sign * count,
+        })),
+        Diff::DeleteRows {
+            sheet, row, count, ..
+        } => shifts.push(Shift::Cells(DisplaceData::Row {
+            sheet: *sheet,
+            row: *row,
+            delta: -sign * count,
+        })),
+        Diff::InsertColumns {
+            sheet,
+            column,
+            count,
+        } => shifts.push(Shift::Cells(DisplaceData::Column {
+            sheet: *sheet,
+            column: *column,
+            delta: sign * count,
+        })),
+        Diff::DeleteColumns {
+            sheet,
+            column,
+            count,
+            ..
+        } => shifts.push(Shift::Cells(DisplaceData::Column {
+            sheet: *sheet,
+            column: *column,
+            delta: -sign * count,
+        })),
+        Diff::InsertCells {
+            sheet,
+            row,
+            column,
+            width,
+            height,
+            direction,
+        }
+        | Diff::DeleteCells {
+            sheet,
+            row,
+            column,
+            width,
+            height,
+            direction,
+            ..
+        } => {
+            let sign = if matches!(diff, Diff::DeleteCells { .. }) {
+                -sign
+            } else {
+                sign
+            };
+            shifts.push(Shift::Cells(match direction {
+                ShiftDirection::Right | ShiftDirection::Left => DisplaceData::CellHorizontal {
+                    sheet: *sheet,
+                    row: *row,
+                    height: *height,
+                    column: *column,
+                    delta: sign * width,
+                },
+                ShiftDirection::Down | ShiftDirection::Up => DisplaceData::CellVertical {
+                    sheet: *sheet,
+                    row: *row,
+                    column: *column,
+                    width: *width,
+                    delta: sign * height,
+                },
+            }));
+        }
+        Diff::MoveRows {
+            sheet,
+            row,
+            row_count,
+            delta,
+        } => {
+            let first = if undo { row + delta } else { *row };
+            push_move_shifts(shifts, *sheet, first, *row_count, sign * delta, true);
+        }
+        Diff::MoveColumns {
+            sheet,
+            column,
+            column_count,
+            delta,
+        } => {
+            let first = if undo { column + delta } else { *column };
+            push_move_shifts(shifts, *sheet, first, *column_count, sign * delta, false);
+        }
+        Diff::NewSheet { index, .. } | Diff::DuplicateSheet { index, .. } => shifts.push(if undo {
+            Shift::DeleteSheet(*index)
+        } else {
+            Shift::InsertSheet(*index)
+        }),
+        Diff::DeleteSheet { sheet, .. } => shifts.push(if undo {
+            Shift::InsertSheet(*sheet)
+        } else {
+            Shift::DeleteSheet(*sheet)
+        }),
+        Diff::MoveSheet { from, to } => shifts.push(if undo {
+            Shift::MoveSheet {
+                from: *to,
+                to: *from,
+            }
+        } else {
+            Shift::MoveSheet {
+                from: *from,
+                to: *to,
+            }
+        }),
+        _ => {}
+    }
+}
+
+fn get_shifts(diffs: &QueueDiffs) -> Vec<Shift> {
+    let mut shifts = Vec::new();
+    match diffs.r#type {
+        DiffType::Redo => {
+            for diff in &diffs.list {
+                push_shifts(&mut shifts, diff, false);
+            }
+        }
+        DiffType::Undo => {
+            for diff in diffs.list.iter().rev() {
+                push_shifts(&mut shifts, diff, true);
+            }
+        }
+    }
+    shifts
+}
+
+/// Moves a diff created before `shift` to where it belongs after it.
+/// Returns `None` if the diff targets something that no longer exists.
+fn transform_diff(model: &mut Model, diff: &Diff, undo: bool, shift: &Shift) -> Option<Diff> {
+    let mut diff = diff.clone();
+    match &mut diff {
+        Diff::SetCellValue {
+            sheet,
+            row,
+            column,
+            new_value,
+            ..
+        }
+        | Diff::SetArrayValue {
+            sheet,
+            row,
+            column,
+            new_value,
+            ..
+        } => {
+            *new_value = shift.formula(model, *sheet, *row, *column, new_value);
+            (*row, *column) = shift.cell(*sheet, *row, *column)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::CellClearFormatting {
+            sheet, row, column, ..
+        }
+        | Diff::SetCellStyle {
+            sheet, row, column, ..
+        }
+        | Diff::RebaseDateSerial {
+            sheet, row, column, ..
+        } => {
+            (*row, *column) = shift.cell(*sheet, *row, *column)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::RangeClearContents {
+            sheet,
+            row,
+            column,
+            width,
+            height,
+            ..
+        }
+        | Diff::RangeClearAll {
+            sheet,
+            row,
+            column,
+            width,
+            height,
+            ..
+        } => {
+            if undo {
+                // The old values are restored at the same place
+                (*row, *column) = shift.cell(*sheet, *row, *column)?;
+            } else {
+                (*row, *height) = shift.rows(*sheet, *row, *height)?;
+                (*column, *width) = shift.columns(*sheet, *column, *width)?;
+            }
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::SetColumnWidth { sheet, column, .. }
+        | Diff::SetColumnHidden { sheet, column, .. }
+        | Diff::SetColumnCollapsed { sheet, column, .. }
+        | Diff::SetColumnStyle { sheet, column, .. }
+        | Diff::DeleteColumnStyle { sheet, column, .. }
+        | Diff::MoveColumns { sheet, column, .. } => {
+            *column = shift.column(*sheet, *column)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::SetRowHeight { sheet, row, .. }
+        | Diff::SetRowHidden { sheet, row, .. }
+        | Diff::SetRowCollapsed { sheet, row, .. }
+        | Diff::SetRowStyle { sheet, row, .. }
+        | Diff::DeleteRowStyle { sheet, row, .. }
+        | Diff::MoveRows { sheet, row, .. } => {
+            *row = shift.row(*sheet, *row)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::SetRowsOutlineLevel {
+            sheet, row, count, ..
+        } => {
+            (*row, *count) = shift.rows(*sheet, *row, *count)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::SetColumnsOutlineLevel {
+            sheet,
+            column,
+            count,
+            ..
+        } => {
+            (*column, *count) = shift.columns(*sheet, *column, *count)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::InsertRows { sheet, row, count } => {
+            if undo {
+                (*row, *count) = shift.rows(*sheet, *row, *count)?;
+            } else {
+                *row = shift.row_insertion(*sheet, *row);
+            }
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::DeleteRows {
+            sheet,
+            row,
+            count,
+            old_data,
+            ..
+        } => {
+            if undo {
+                *row = shift.row_insertion(*sheet, *row);
+                for (index, row_data) in old_data.iter_mut().enumerate() {
+                    if let Some(row_style) = &mut row_data.row {
+                        row_style.r = *row + index as i32;
+                    }
+                }
+            } else {
+                (*row, *count) = shift.rows(*sheet, *row, *count)?;
+            }
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::InsertColumns {
+            sheet,
+            column,
+            count,
+        } => {
+            if undo {
+                (*column, *count) = shift.columns(*sheet, *column, *count)?;
+            } else {
+                *column = shift.column_insertion(*sheet, *column);
+            }
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::DeleteColumns {
+            sheet,
+            column,
+            count,
+            ..
+        } => {
+            if undo {
+                *column = shift.column_insertion(*sheet, *column);
+            } else {
+                (*column, *count) = shift.columns(*sheet, *column, *count)?;
+            }
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::InsertCells {
+            sheet, row, column, ..
+        }
+        | Diff::DeleteCells {
+            sheet, row, column, ..
+        } => {
+            (*row, *column) = shift.cell(*sheet, *row, *column)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::SetFrozenRowsCount { sheet, .. }
+        | Diff::SetFrozenColumnsCount { sheet, .. }
+        | Diff::SetShowGridLines { sheet, .. }
+        | Diff::AddConditionalFormatting { sheet, .. }
+        | Diff::DeleteConditionalFormatting { sheet, .. }
+        | Diff::UpdateConditionalFormatting { sheet, .. }
+        | Diff::AddScenario { sheet, .. }
+        | Diff::DeleteScenario { sheet, .. }
+        | Diff::UpdateScenario { sheet, .. } => {
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::MergeCells { sheet, range } | Diff::UnmergeCells { sheet, range } => {
+            *range = shift.range(*sheet, range)?;
+            *sheet = shift.sheet(*sheet)?;
+        }
+        Diff::DeleteSheet { sheet, .. } => {
+            *sheet = if undo {
+                shift.sheet_insertion(*sheet)
+            } else {
+                shift.sheet(*sheet)?
+            };
+        }
+        Diff::NewSheet { index, .. } => {
+            *index = if undo {
+                shift.sheet(*index)?
+            } else {
+                shift.sheet_insertion(*index)
+            };
+        }
+        Diff::DuplicateSheet { sheet, index, .. } => {
+            *sheet = shift.sheet(*sheet)?;
+            *index = if undo {
+                shift.sheet(*index)?
+            } else {
+                shift.sheet_insertion(*index)
+            };
+        }
+        Diff::MoveSheet { from, to } => {
+            *from = shift.sheet(*from)?;
+            *to = shift.sheet(*to)?;
+        }
+        Diff::RenameSheet { index, .. }
+        | Diff::SetSheetColor { index, .. }
+        | Diff::SetSheetState { index, .. } => {
+            *index = shift.sheet(*index)?;
+        }
+        Diff::CreateDefinedName { scope, .. } | Diff::DeleteDefinedName { scope, .. } => {
+            *scope = shift.scope(*scope)?;
+        }
+        Diff::UpdateDefinedName {
+            scope, new_scope, ..
+        } => {
+            *scope = shift.scope(*scope)?;
+            *new_scope = shift.scope(*new_scope)?;
+        }
+        Diff::SetTheme { .. }
+        | Diff::SetLocale { .. }
+        | Diff::SetTimezone { .. }
+        | Diff::SetDate1904 { .. }
+        | Diff::SetR1C1 { .. }
+        | Diff::CreateNamedStyle { .. }
+        | Diff::DeleteNamedStyle { .. }
+        | Diff::UpdateNamedStyle { .. } => {}
+    }
+    Some(diff)
+}
+
+fn transform_list(model: &mut Model, list: &DiffList, undo: bool, shift: &Shift) -> DiffList {
+    list.iter()
+        .filter_map(|diff| transform_diff(model, diff, undo, shift))
+        .collect()
+}
+
+fn transform_diffs(model: &mut Model, diffs: &QueueDiffs, shift: &Shift) -> QueueDiffs {
+    let undo = matches!(diffs.r#type, DiffType::Undo);
+    QueueDiffs {
+        r#type: diffs.r#type.clone(),
+        list: transform_list(model, &diffs.list, undo, shift),
+    }
+}
+
+/// Applies the diffs one by one. A diff that fails (e.g. renaming a sheet to a name that a concurrent
+/// operation already used) is skipped in every site.
+fn apply_diffs(model: &mut UserModel, diffs: &QueueDiffs, cells: &CellData) {
+    match diffs.r#type {
+        DiffType::Redo => {
+            for diff in &diffs.list {
+                let mut diff = diff.clone();
+                // Two sites might have added a sheet with the same name
+                if let Diff::NewSheet { name, .. } | Diff::DuplicateSheet { name, .. } = &mut diff {
+                    let base_name = name.clone();
+                    let mut suffix = 2;
+                    while model.model.get_sheet_index_by_name(name).is_some() {
+                        *name = format!("{base_name} ({suffix})");
+                        suffix += 1;
+                    }
+                }
+                let _ = model.apply_diff_list(&vec![diff]);
+            }
+        }
+        DiffType::Undo => {
+            let mut list = diffs.list.clone();
+            import_cells(&mut model.model, &mut list, cells);
+            for diff in list.into_iter().rev() {
+                let _ = model.apply_undo_diff_list(&vec![diff]);
+            }
+        }
+    }
+}
+
+/// Keeps a [UserModel] in sync with the models of other sites.
+///
+/// The host edits the model as usual, sends the bytes returned by [Collaboration::flush] to the other sites
+/// and passes the bytes it receives to [Collaboration::receive].
+/// Operations can be received in any order and more than once.
+///
+/// # Examples
+///
+/// ```rust
+/// # use ironcalc_base::UserModel;
+/// # use ironcalc_base::collaboration::Collaboration;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut model1 = UserModel::new_empty("model", "en", "UTC", "en")?;
+/// let mut model2 = UserModel::new_empty("model", "en", "UTC", "en")?;
+/// let mut site1 = Collaboration::new(1, &mut model1);
+/// let mut site2 = Collaboration::new(2, &mut model2);
+///
+/// // Concurrent edits
+/// model1.insert_rows(0, 1, 1)?;
+/// model2.set_user_input(0, 3, 1, "=A2*2")?;
+/// let from1 = site1.flush(&mut model1);
+/// let from2 = site2.flush(&mut model2);
+/// site1.receive(&mut model1, &from2)?;
+/// site2.receive(&mut model2, &from1)?;
+///
+/// assert_eq!(model1.get_cell_content(0, 4, 1)?, "=A3*2");
+/// assert_eq!(model2.get_cell_content(0, 4, 1)?, "=A3*2");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// A site can also join a session that is already going on: [Collaboration::add_site] returns the state the
+/// new site starts with, and the new site loads it with [Collaboration::from_bytes] next to a copy of the
+/// model of the site that added it.
+#[derive(Clone)]
+pub struct Collaboration {
+    site: SiteId,
+    lamport: u64,
+    /// Operations integrated in the model
+    clock: VectorClock,
+    /// The workbook before the first operation in the log
+    snapshot: Workbook,
+    /// Operations folded into the snapshot
+    snapshot_clock: VectorClock,
+    /// Operations not in the snapshot in the order they are applied
+    log: Vec<LoggedOperation>,
+    /// Local operations not sent yet
+    outbox: Vec<Operation>,
+    /// Received operations waiting for the operations they depend on
+    pending: Vec<Operation>,
+    /// The operations each of the other sites is known to have seen
+    peers: BTreeMap<SiteId, VectorClock>,
+}
+
+/// A [Collaboration] as stored. The workbook and the operations are encoded on their own, a single
+/// encoder for all of them needs too much stack.
+#[derive(Encode, Decode)]
+struct SessionState {
+    site: SiteId,
+    lamport: u64,
+    clock: VectorClock,
+    snapshot: Vec<u8>,
+    snapshot_clock: VectorClock,
+    log: Vec<u8>,
+    outbox: Vec<u8>,
+    pending: Vec<u8>,
+    peers: BTreeMap<SiteId, VectorClock>,
+}
+
+impl Collaboration {
+    /// Starts a session for `site`. All the sites must start from the same workbook.
+    /// Changes in the model that have not been flushed are not part of the session.
+    pub fn new(site: SiteId, model: &mut UserModel) -> Collaboration {
+        model.take_send_queue();
+        Collaboration {
+            site,
+            lamport: 0,
+            clock: VectorClock::default(),
+            snapshot: model.model.workbook.clone(),
+            snapshot_clock: VectorClock::default(),
+            log: Vec::new(),
+            outbox: Vec::new(),
+            pending: Vec::new(),
+            peers: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the state of the session to be stored and loaded again with [Collaboration::from_bytes].
+    /// The model must be stored at the same time.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        bitcode::encode(&SessionState {
+            site: self.site,
+            lamport: self.lamport,
+            clock: self.clock.clone(),
+            snapshot: bitcode::encode(&self.snapshot),
+            snapshot_clock: self.snapshot_clock.clone(),
+            log: bitcode::encode(&self.log),
+            outbox: bitcode::encode(&self.outbox),
+            pending: bitcode::encode(&self.pending),
+            peers: self.peers.clone(),
+        })
+    }
+
+    /// Loads the state of a session returned by [Collaboration::to_bytes] or [Collaboration::add_site]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Collaboration, String> {
+        let error = |_| "Error parsing collaboration state".to_string();
+        let state: SessionState = bitcode::decode(bytes).map_err(error)?;
+        Ok(Collaboration {
+            site: state.site,
+            lamport: state.lamport,
+            clock: state.clock,
+            snapshot: bitcode::decode(&state.snapshot).map_err(error)?,
+            snapshot_clock: state.snapshot_clock,
+            log: bitcode::decode(&state.log).map_err(error)?,
+            outbox: bitcode::decode(&state.outbox).map_err(error)?,
+            pending: bitcode::decode(&state.pending).map_err(error)?,
+            peers: state.peers,
+        })
+    }
+
+    /// Adds a new site to the session. Returns its id and the state it starts with.
+    /// The new site has seen all the operations integrated in this one and its model must be a copy of the
+    /// model of this site.
+    pub fn add_site(&mut self, model: &mut UserModel) -> (SiteId, Vec<u8>) {
+        self.stamp_local_diffs(model);
+        let site = self
+            .peers
+            .keys()
+            .copied()
+            .chain([self.site])
+            .max()
+            .unwrap_or_default()
+            + 1;
+        let mut state = self.clone();
+        state.site = site;
+        state.outbox.clear();
+        state.peers.remove(&site);
+        state.peers.insert(self.site, self.clock.clone());
+        self.peers.insert(site, self.clock.clone());
+        (site, state.to_bytes())
+    }
+
+    /// Returns the id of the site
+    pub fn site_id(&self) -> SiteId {
+        self.site
+    }
+
+    /// Returns the operations integrated in the model, local and remote
+    pub fn clock(&self) -> &VectorClock {
+        &self.clock
+    }
+
+    /// Declares a site of the session. Operations are kept until all the known sites have seen them.
+    pub fn add_peer(&mut self, site: SiteId) {
+        if site != self.site {
+            self.peers.entry(site).or_default();
+        }
+    }
+
+    /// Returns the local operations since the last call, to be sent to the other sites
+    pub fn flush(&mut self, model: &mut UserModel) -> Vec<u8> {
+        self.stamp_local_diffs(model);
+        bitcode::encode(&std::mem::take(&mut self.outbox))
+    }
+
+    /// Integrates operations from other sites in the model
+    pub fn receive(&mut self, model: &mut UserModel, bytes: &[u8]) -> Result<(), String> {
+        let operations = bitcode::decode::<Vec<Operation>>(bytes)
+            .map_err(|_| "Error parsing operations".to_string())?;
+        // Operations already received are ignored
+        let operations: Vec<Operation> = operations
+            .into_iter()
+            .filter(|operation| {
+                let id = operation.id;
+                id.site != self.site
+                    && !self.clock.contains(&id)
+                    && !self.pending.iter().any(|pending| pending.id == id)
+            })
+            .collect();
+        if operations
+            .iter()
+            .any(|operation| !operation.context.dominates(&self.snapshot_clock))
+        {
+            return Err("Operation is older than the shared state".to_string());
+        }
+        self.stamp_local_diffs(model);
+        for operation in operations {
+            let id = operation.id;
+            let peer = self.peers.entry(id.site).or_default();
+            peer.merge(&operation.context);
+            if peer.get(id.site) < id.counter {
+                peer.set(id.site, id.counter);
+            }
+            self.pending.push(operation);
+        }
+
+        // Index of the first operation that needs to be transformed and applied again
+        let mut replay_from: Option<usize> = None;
+        let mut integrated = Vec::new();
+        while let Some(index) = self.pending.iter().position(|o| self.is_ready(o)) {
+            let operation = self.pending.swap_remove(index);
+            integrated.push(operation.id);
+            let key = operation.key();
+            let position = self.log.partition_point(|l| l.operation.key() < key);
+            self.clock.set(operation.id.site, operation.id.counter);
+            self.lamport = self.lamport.max(operation.lamport);
+            let logged = LoggedOperation {
+                applied: operation.diffs.clone(),
+                operation,
+            };
+            self.log.insert(position, logged);
+            if position + 1 < self.log.len() || replay_from.is_some() {
+                replay_from = Some(replay_from.map_or(position, |from| from.min(position)));
+            } else {
+                self.log[position].applied = self.transform(&mut model.model, position);
+                self.apply(model, position);
+            }
+        }
+        if let Some(from) = replay_from {
+            self.replay(model, from)?;
+        }
+        self.transform_history(model, &integrated);
+        self.compact(model);
+        Ok(())
+    }
+
+    /// Moves the changes in the undo and redo lists of the site to where they are after the remote operations
+    fn transform_history(&self, model: &mut UserModel, integrated: &[OperationId]) {
+        let shifts: Vec<Shift> = self
+            .log
+            .iter()
+            .filter(|logged| integrated.contains(&logged.operation.id))
+            .flat_map(|logged| get_shifts(&logged.applied))
+            .collect();
+        if shifts.is_empty() {
+            return;
+        }
+        let mut undo_stack = std::mem::take(&mut model.history_mut().undo_stack);
+        let mut redo_stack = std::mem::take(&mut model.history_mut().redo_stack);
+        for shift in &shifts {
+            for list in &mut undo_stack {
+                *list = transform_list(&mut model.model, list, true, shift);
+            }
+            for list in &mut redo_stack {
+                *list = transform_list(&mut model.model, list, false, shift);
+            }
+        }
+        let history = model.history_mut();
+        history.undo_stack = undo_stack;
+        history.redo_stack = redo_stack;
+    }
+
+    fn is_ready(&self, operation: &Operation) -> bool {
+        let id = operation.id;
+        self.clock.get(id.site) + 1 == id.counter
+            && operation
+                .context
+                .0
+                .iter()
+                .all(|(site, counter)| *site == id.site || self.clock.get(*site) >= *counter)
+    }
+
+    /// Wraps the diffs in the send queue of the model in operations.
+    /// They have already been applied and go to the end of the log.
+    fn stamp_local_diffs(&mut self, model: &mut UserModel) {
+        for mut diffs in model.take_send_queue() {
+            let cells = match diffs.r#type {
+                DiffType::Undo => export_cells(&model.model, &mut diffs.list),
+                DiffType::Redo => CellData::default(),
+            };
+            let counter = self.clock.get(self.site) + 1;
+            self.lamport += 1;
+            let operation = Operation {
+                id: OperationId {
+                    site: self.site,
+                    counter,
+                },
+                lamport: self.lamport,
+                context: self.clock.clone(),
+                diffs,
+                cells,
+            };
+            self.clock.set(self.site, counter);
+            self.outbox.push(operation.clone());
+            self.log.push(LoggedOperation {
+                applied: operation.diffs.clone(),
+                operation,
+            });
+        }
+    }
+
+    /// Transforms the operation at `index` against the concurrent operations before it
+    fn transform(&self, model: &mut Model, index: usize) -> QueueDiffs {
+        let operation = &self.log[index].operation;
+        let mut diffs = operation.diffs.clone();
+        for previous in &self.log[..index] {
+            if operation.context.contains(&previous.operation.id) {
+                continue;
+            }
+            for shift in get_shifts(&previous.applied) {
+                diffs = transform_diffs(model, &diffs, &shift);
+            }
+        }
+        diffs
+    }
+
+    fn apply(&self, model: &mut UserModel, index: usize) {
+        let paused = model.is_evaluation_paused();
+        model.pause_evaluation();
+        let logged = &self.log[index];
+        apply_diffs(model, &logged.applied, &logged.operation.cells);
+        if !paused {
+            model.resume_evaluation();
+        }
+        model.evaluate_if_not_paused();
+    }
+
+    /// Restores the snapshot and applies the log again, transforming the operations from `from` on.
+    /// The views (selected sheets and cells) of the site are kept.
+    fn replay(&mut self, model: &mut UserModel, from: usize) -> Result<(), String> {
+        let workbook_views = model.model.workbook.views.clone();
+        let sheet_views: HashMap<u32, HashMap<u32, WorksheetView>> = model
+            .model
+            .workbook
+            .worksheets
+            .iter()
+            .map(|worksheet| (worksheet.sheet_id, worksheet.views.clone()))
+            .collect();
+
+        // The cells in the undo history refer to the lists of the current workbook
+        let history = model.history_mut();
+        let mut history_lists = std::mem::take(&mut history.undo_stack);
+        let redo_count = history.redo_stack.len();
+        history_lists.append(&mut history.redo_stack);
+        let history_cells: Vec<CellData> = history_lists
+            .iter_mut()
+            .map(|list| export_cells(&model.model, list))
+            .collect();
+
+        model.model.replace_workbook(self.snapshot.clone())?;
+        let paused = model.is_evaluation_paused();
+        model.pause_evaluation();
+        for index in 0..self.log.len() {
+            if index >= from {
+                self.log[index].applied = self.transform(&mut model.model, index);
+            }
+            let logged = &self.log[index];
+            apply_diffs(model, &logged.applied, &logged.operation.cells);
+        }
+        if !paused {
+            model.resume_evaluation();
+        }
+
+        for (list, cells) in history_lists.iter_mut().zip(&history_cells) {
+            import_cells(&mut model.model, list, cells);
+        }
+        let history = model.history_mut();
+        history.redo_stack = history_lists.split_off(history_lists.len() - redo_count);
+        history.undo_stack = history_lists;
+
+        let workbook = &mut model.model.workbook;
+        let sheet_count = workbook.worksheets.len() as u32;
+        for worksheet in &mut workbook.worksheets {
+            if let Some(views) = sheet_views.get(&worksheet.sheet_id) {
+                worksheet.views = views.clone();
+            }
+        }
+        workbook.views = workbook_views;
+        for view in workbook.views.values_mut() {
+            view.sheet = view.sheet.min(sheet_count.saturating_sub(1));
+        }
+        model.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Folds the log into the snapshot when all the known sites have seen all of it
+    fn compact(&mut self, model: &UserModel) {
+        let is_stable = |logged: &LoggedOperation| {
+            self.peers
+                .values()
+                .all(|clock| clock.contains(&logged.operation.id))
+        };
+        if self.pending.is_empty() && !self.log.is_empty() && self.log.iter().all(is_stable) {
+            self.snapshot = model.model.workbook.clone();
+            self.snapshot_clock = self.clock.clone();
+            self.log.clear();
+        }
+    }
+}
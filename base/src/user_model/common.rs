@@ -7,14 +7,24 @@ use serde::{Deserialize, Serialize};
 use crate::{
     cf_types::ExtendedStyle,
     constants::{LAST_COLUMN, LAST_ROW},
+    custom_functions::CustomFunction,
+    error_checking::CellDiagnostic,
+    evaluate_formula::EvaluationStep,
     expressions::{
-        types::Area,
-        utils::{is_valid_column_number, is_valid_row},
+        parser::parse_range,
+        types::{Area, CellReferenceIndex},
+        utils::{is_valid_column_number, is_valid_row, number_to_column},
     },
+    fonts::FontFallback,
+    goal_seek::{GoalSeekOptions, GoalSeekResult},
     model::{FmtSettings, Model},
+    outline::{OutlineGroup, MAX_OUTLINE_LEVEL},
+    precedents::TracedReference,
+    solver::{SolverProblem, SolverResult},
     types::{
-        Alignment, ArrayKind, BorderItem, Cell, CellType, Col, Color, HorizontalAlignment,
-        SheetProperties, SheetState, Style, Theme, VerticalAlignment,
+        Alignment, ArrayKind, BorderItem, Cell, CellType, Col, Color, Fill, Font, FontScheme,
+        GradientFill, GradientStop, GradientType, HorizontalAlignment, PatternType, ReadingOrder,
+        Scenario, SheetProperties, SheetState, ShiftDirection, Style, Theme, VerticalAlignment,
     },
 };
 
@@ -95,6 +105,77 @@ fn vertical(value: &str) -> Result<VerticalAlignment, String> {
     }
 }
 
+fn reading_order(value: &str) -> Result<ReadingOrder, String> {
+    match value {
+        "contextDependent" => Ok(ReadingOrder::ContextDependent),
+        "leftToRight" => Ok(ReadingOrder::LeftToRight),
+        "rightToLeft" => Ok(ReadingOrder::RightToLeft),
+        _ => Err(format!("Invalid value for reading order: '{value}'.")),
+    }
+}
+
+fn text_rotation(value: &str) -> Result<i32, String> {
+    match value.parse::<i32>() {
+        Ok(r) if (0..=180).contains(&r) || r == 255 => Ok(r),
+        _ => Err(format!("Invalid value for text rotation: '{value}'.")),
+    }
+}
+
+fn indent(value: &str) -> Result<i32, String> {
+    match value.parse::<i32>() {
+        Ok(i) if (0..=250).contains(&i) => Ok(i),
+        _ => Err(format!("Invalid value for indent: '{value}'.")),
+    }
+}
+
+fn fraction(value: &str) -> Result<f64, String> {
+    match value.trim().parse::<f64>() {
+        Ok(f) if (0.0..=1.0).contains(&f) => Ok(f),
+        _ => Err(format!("Invalid value for fraction: '{value}'.")),
+    }
+}
+
+// Parses gradient stops like "0:#FFFFFF;1:[4,0.5]"
+fn gradient_stops(value: &str) -> Result<Vec<GradientStop>, String> {
+    let mut stops = Vec::new();
+    for stop in value.split(';') {
+        let (position, color) = match stop.split_once(':') {
+            Some(p) => p,
+            None => return Err(format!("Invalid value for gradient stop: '{stop}'.")),
+        };
+        stops.push(GradientStop {
+            position: fraction(position)?,
+            color: Color::from_param(color.trim())?,
+        });
+    }
+    if stops.len() < 2 {
+        return Err(format!("A gradient needs at least two stops: '{value}'."));
+    }
+    Ok(stops)
+}
+
+// Returns the gradient of the fill, creating a two colour gradient if there is none
+fn gradient(fill: &mut Fill) -> &mut GradientFill {
+    let color = if fill.color.is_some() {
+        fill.color.clone()
+    } else {
+        Color::Theme(4, 0.0)
+    };
+    fill.gradient.get_or_insert_with(|| GradientFill {
+        stops: vec![
+            GradientStop {
+                position: 0.0,
+                color: Color::Rgb("#FFFFFF".to_string()),
+            },
+            GradientStop {
+                position: 1.0,
+                color,
+            },
+        ],
+        ..Default::default()
+    })
+}
+
 fn update_style(old_value: &Style, style_path: &str, value: &str) -> Result<Style, String> {
     let mut style = old_value.clone();
     match style_path {
@@ -113,6 +194,15 @@ fn update_style(old_value: &Style, style_path: &str, value: &str) -> Result<Styl
         "font.color" => {
             style.font.color = Color::from_param(value)?;
         }
+        "font.name" => {
+            let name = value.trim();
+            if name.is_empty() {
+                return Err("Invalid value for font name: ''.".to_string());
+            }
+            style.font.name = name.to_string();
+            // An explicit font name overrides the theme font
+            style.font.scheme = FontScheme::None;
+        }
         "font.size" => {
             let new_size: i32 = value
                 .parse()
@@ -133,9 +223,58 @@ fn update_style(old_value: &Style, style_path: &str, value: &str) -> Result<Styl
             }
             style.font.sz = new_size;
         }
-        "fill.color" | "fill.bg_color" | "fill.fg_color" => {
+        "fill.color" => {
+            // A plain colour replaces patterns and gradients with a solid fill
+            style.fill = Fill {
+                color: Color::from_param(value)?,
+                ..Default::default()
+            };
+        }
+        "fill.fg_color" => {
             style.fill.color = Color::from_param(value)?;
         }
+        "fill.bg_color" => {
+            let color = Color::from_param(value)?;
+            // The background of a solid fill is the colour of the fill, as it has always been
+            if style.fill.pattern_type == PatternType::Solid && style.fill.gradient.is_none() {
+                style.fill.color = color.clone();
+            }
+            style.fill.bg_color = color;
+        }
+        "fill.pattern_type" => {
+            style.fill.pattern_type = value.parse::<PatternType>()?;
+            style.fill.gradient = None;
+        }
+        "fill.gradient_type" => match value {
+            "" => style.fill.gradient = None,
+            "linear" => gradient(&mut style.fill).gradient_type = GradientType::Linear,
+            "path" => gradient(&mut style.fill).gradient_type = GradientType::Path,
+            _ => return Err(format!("Invalid value for gradient type: '{value}'.")),
+        },
+        "fill.gradient_degree" => {
+            let degree = match value.parse::<f64>() {
+                Ok(d) if d.is_finite() => d,
+                _ => return Err(format!("Invalid value for gradient degree: '{value}'.")),
+            };
+            gradient(&mut style.fill).degree = degree;
+        }
+        "fill.gradient_rect" => {
+            let rect = value
+                .split(',')
+                .map(fraction)
+                .collect::<Result<Vec<f64>, String>>()?;
+            if rect.len() != 4 {
+                return Err(format!("Invalid value for gradient rectangle: '{value}'."));
+            }
+            let gradient = gradient(&mut style.fill);
+            gradient.left = rect[0];
+            gradient.right = rect[1];
+            gradient.top = rect[2];
+            gradient.bottom = rect[3];
+        }
+        "fill.gradient_stops" => {
+            gradient(&mut style.fill).stops = gradient_stops(value)?;
+        }
         "num_fmt" => {
             value.clone_into(&mut style.num_fmt);
         }
@@ -175,6 +314,44 @@ fn update_style(old_value: &Style, style_path: &str, value: &str) -> Result<Styl
                 style.alignment = Some(alignment)
             }
         },
+        "alignment.text_rotation" => {
+            let text_rotation = text_rotation(value)?;
+            style
+                .alignment
+                .get_or_insert_with(Default::default)
+                .text_rotation = text_rotation;
+        }
+        "alignment.indent" => {
+            let indent = indent(value)?;
+            style.alignment.get_or_insert_with(Default::default).indent = indent;
+        }
+        "alignment.shrink_to_fit" => {
+            let shrink_to_fit = boolean(value)?;
+            style
+                .alignment
+                .get_or_insert_with(Default::default)
+                .shrink_to_fit = shrink_to_fit;
+        }
+        "alignment.justify_last_line" => {
+            let justify_last_line = boolean(value)?;
+            style
+                .alignment
+                .get_or_insert_with(Default::default)
+                .justify_last_line = justify_last_line;
+        }
+        "alignment.reading_order" => {
+            let reading_order = reading_order(value)?;
+            style
+                .alignment
+                .get_or_insert_with(Default::default)
+                .reading_order = reading_order;
+        }
+        "protection.locked" => {
+            style.protection.get_or_insert_with(Default::default).locked = boolean(value)?;
+        }
+        "protection.hidden" => {
+            style.protection.get_or_insert_with(Default::default).hidden = boolean(value)?;
+        }
         _ => {
             return Err(format!("Invalid style path: '{style_path}'."));
         }
@@ -407,6 +584,8 @@ impl<'a> UserModel<'a> {
         if !is_valid_row(row) {
             return Err("Invalid row".to_string());
         }
+        // The history always keeps formulas in A1 style
+        let value = &self.model.user_input_to_a1(sheet, row, column, value);
         let old_value = self
             .model
             .workbook
@@ -420,7 +599,7 @@ impl<'a> UserModel<'a> {
             old_value
         };
         self.model
-            .set_user_input(sheet, row, column, value.to_string())?;
+            .set_a1_user_input(sheet, row, column, value.to_string())?;
 
         self.evaluate_if_not_paused();
 
@@ -476,6 +655,312 @@ impl<'a> UserModel<'a> {
         self.model.get_formatted_cell_value(sheet, row, column)
     }
 
+    /// Returns how the formula in the cell evaluates, one sub-expression at a time
+    ///
+    /// See also:
+    /// * [Model::get_evaluation_steps]
+    #[inline]
+    pub fn get_evaluation_steps(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<Vec<EvaluationStep>, String> {
+        self.model.get_evaluation_steps(sheet, row, column)
+    }
+
+    /// Returns the problems found by the error checking rules in the sheet
+    ///
+    /// See also:
+    /// * [Model::get_error_checks]
+    #[inline]
+    pub fn get_error_checks(&self, sheet: u32) -> Result<Vec<CellDiagnostic>, String> {
+        self.model.get_error_checks(sheet)
+    }
+
+    /// Returns the cells and ranges the cell depends on, up to `depth` levels
+    ///
+    /// See also:
+    /// * [Model::get_precedents]
+    #[inline]
+    pub fn get_precedents(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        depth: u32,
+    ) -> Result<Vec<TracedReference>, String> {
+        self.model.get_precedents(sheet, row, column, depth)
+    }
+
+    /// Returns the cells whose formulas depend on the cell, up to `depth` levels
+    ///
+    /// See also:
+    /// * [Model::get_dependents]
+    #[inline]
+    pub fn get_dependents(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        depth: u32,
+    ) -> Result<Vec<TracedReference>, String> {
+        self.model.get_dependents(sheet, row, column, depth)
+    }
+
+    /// Changes the number in `changing` until the formula in `target` evaluates to `target_value`.
+    /// If the search converges the change is a single step in the history.
+    ///
+    /// See also:
+    /// * [Model::goal_seek]
+    pub fn goal_seek(
+        &mut self,
+        target: &CellReferenceIndex,
+        target_value: f64,
+        changing: &CellReferenceIndex,
+        options: &GoalSeekOptions,
+    ) -> Result<GoalSeekResult, String> {
+        let old_value = self
+            .model
+            .workbook
+            .worksheet(changing.sheet)?
+            .cell(changing.row, changing.column)
+            .cloned();
+        let result = self
+            .model
+            .goal_seek(target, target_value, changing, options)?;
+        if result.converged {
+            self.push_diff_list(vec![Diff::SetCellValue {
+                sheet: changing.sheet,
+                row: changing.row,
+                column: changing.column,
+                new_value: self.get_cell_content(changing.sheet, changing.row, changing.column)?,
+                old_value: Box::new(old_value),
+            }]);
+        }
+        Ok(result)
+    }
+
+    /// Solves the optimization `problem`. If the changing cells keep the solution the change is
+    /// a single step in the history.
+    ///
+    /// See also:
+    /// * [Model::solve]
+    pub fn solve(&mut self, problem: &SolverProblem) -> Result<SolverResult, String> {
+        let mut old_values = Vec::new();
+        for area in &problem.variables {
+            for row in area.row..area.row + area.height {
+                for column in area.column..area.column + area.width {
+                    if old_values
+                        .iter()
+                        .any(|(s, r, c, _)| (*s, *r, *c) == (area.sheet, row, column))
+                    {
+                        continue;
+                    }
+                    let old_value = self
+                        .model
+                        .workbook
+                        .worksheet(area.sheet)?
+                        .cell(row, column)
+                        .cloned();
+                    old_values.push((area.sheet, row, column, old_value));
+                }
+            }
+        }
+        let result = self.model.solve(problem)?;
+        let mut diff_list = Vec::new();
+        for (sheet, row, column, old_value) in old_values {
+            let new_value = self.model.workbook.worksheet(sheet)?.cell(row, column);
+            if new_value == old_value.as_ref() {
+                continue;
+            }
+            diff_list.push(Diff::SetCellValue {
+                sheet,
+                row,
+                column,
+                new_value: self.get_cell_content(sheet, row, column)?,
+                old_value: Box::new(old_value),
+            });
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(result)
+    }
+
+    /// Creates a data table in `area`, the whole table including the row and the column of the
+    /// input values. The results fill the table without its first row and column:
+    ///
+    /// * With both inputs the formula is in the top left cell, the values for `row_input` in
+    ///   the first row and the values for `column_input` in the first column.
+    /// * With only `column_input` the values are in the first column and the formulas in the
+    ///   first row.
+    /// * With only `row_input` the values are in the first row and the formulas in the first
+    ///   column.
+    ///
+    /// The input cells must be in the same sheet as the table.
+    pub fn create_data_table(
+        &mut self,
+        area: &Area,
+        row_input: Option<&CellReferenceIndex>,
+        column_input: Option<&CellReferenceIndex>,
+    ) -> Result<(), String> {
+        if area.width < 2 || area.height < 2 {
+            return Err("A data table needs at least two rows and two columns".to_string());
+        }
+        let input = |input: Option<&CellReferenceIndex>| -> Result<String, String> {
+            match input {
+                None => Ok(String::new()),
+                Some(cell) => {
+                    if cell.sheet != area.sheet {
+                        return Err("Input cell reference is not valid".to_string());
+                    }
+                    let inside = (area.row..area.row + area.height).contains(&cell.row)
+                        && (area.column..area.column + area.width).contains(&cell.column);
+                    if inside {
+                        return Err("The input cell cannot be in the data table".to_string());
+                    }
+                    let column = number_to_column(cell.column)
+                        .ok_or_else(|| "Input cell reference is not valid".to_string())?;
+                    Ok(format!("{column}{}", cell.row))
+                }
+            }
+        };
+        if row_input.is_none() && column_input.is_none() {
+            return Err("A data table needs a row or a column input cell".to_string());
+        }
+        let formula = format!("=TABLE({},{})", input(row_input)?, input(column_input)?);
+        self.set_a1_array_formula(
+            area.sheet,
+            area.row + 1,
+            area.column + 1,
+            area.width - 1,
+            area.height - 1,
+            &formula,
+        )
+    }
+
+    /// Returns the scenarios of the sheet
+    ///
+    /// See also:
+    /// * [Model::get_scenarios]
+    pub fn get_scenarios(&self, sheet: u32) -> Result<Vec<Scenario>, String> {
+        self.model.get_scenarios(sheet)
+    }
+
+    /// Adds a scenario to the sheet
+    ///
+    /// See also:
+    /// * [Model::add_scenario]
+    pub fn add_scenario(&mut self, sheet: u32, scenario: Scenario) -> Result<(), String> {
+        self.model.add_scenario(sheet, scenario.clone())?;
+        self.push_diff_list(vec![Diff::AddScenario {
+            sheet,
+            scenario: Box::new(scenario),
+        }]);
+        Ok(())
+    }
+
+    /// Replaces the scenario `name` of the sheet
+    ///
+    /// See also:
+    /// * [Model::update_scenario]
+    pub fn update_scenario(
+        &mut self,
+        sheet: u32,
+        name: &str,
+        scenario: Scenario,
+    ) -> Result<(), String> {
+        let index = self.model.get_scenario_index(sheet, name)?;
+        let old_value = self.model.get_scenarios(sheet)?.remove(index);
+        self.model.update_scenario(sheet, name, scenario.clone())?;
+        self.push_diff_list(vec![Diff::UpdateScenario {
+            sheet,
+            index: index as u32,
+            old_value: Box::new(old_value),
+            new_value: Box::new(scenario),
+        }]);
+        Ok(())
+    }
+
+    /// Deletes the scenario `name` of the sheet
+    ///
+    /// See also:
+    /// * [Model::delete_scenario]
+    pub fn delete_scenario(&mut self, sheet: u32, name: &str) -> Result<(), String> {
+        let index = self.model.get_scenario_index(sheet, name)?;
+        let old_value = self.model.get_scenarios(sheet)?.remove(index);
+        self.model.delete_scenario(sheet, name)?;
+        self.push_diff_list(vec![Diff::DeleteScenario {
+            sheet,
+            index: index as u32,
+            old_value: Box::new(old_value),
+        }]);
+        Ok(())
+    }
+
+    /// Types the values of the scenario `name` in its changing cells as a single step in the
+    /// history.
+    ///
+    /// See also:
+    /// * [Model::show_scenario]
+    pub fn show_scenario(&mut self, sheet: u32, name: &str) -> Result<(), String> {
+        let index = self.model.get_scenario_index(sheet, name)?;
+        let scenario = self.model.get_scenarios(sheet)?.remove(index);
+        let mut old_values = Vec::new();
+        for cell in &scenario.input_cells {
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(sheet)?
+                .cell(cell.row, cell.column)
+                .cloned();
+            old_values.push(old_value);
+        }
+        self.model.show_scenario(sheet, name)?;
+        // The values of the scenario are not localized, the history keeps them as the user types them
+        let mut diff_list = Vec::new();
+        for (cell, old_value) in scenario.input_cells.iter().zip(old_values) {
+            diff_list.push(Diff::SetCellValue {
+                sheet,
+                row: cell.row,
+                column: cell.column,
+                new_value: self.get_cell_content(sheet, cell.row, cell.column)?,
+                old_value: Box::new(old_value),
+            });
+        }
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Adds a sheet after `sheet` comparing the values of `result_cells` across the scenarios
+    /// of the sheet and selects it. Returns the index of the new sheet.
+    ///
+    /// See also:
+    /// * [Model::create_scenario_summary]
+    pub fn create_scenario_summary(
+        &mut self,
+        sheet: u32,
+        result_cells: &[CellReferenceIndex],
+    ) -> Result<u32, String> {
+        let (index, cells) = self.model.write_scenario_summary(sheet, result_cells)?;
+        let name = self.model.workbook.worksheet(index)?.get_name();
+        let mut diff_list = vec![Diff::NewSheet { index, name }];
+        for (row, column, _) in cells {
+            diff_list.push(Diff::SetCellValue {
+                sheet: index,
+                row,
+                column,
+                new_value: self.get_cell_content(index, row, column)?,
+                old_value: Box::new(None),
+            });
+        }
+        self.push_diff_list(diff_list);
+        self.set_selected_sheet(index)?;
+        Ok(index)
+    }
+
     /// Returns the type of the cell
     ///
     /// See also
@@ -537,6 +1022,39 @@ impl<'a> UserModel<'a> {
         Ok(())
     }
 
+    /// Inserts a copy of `sheet` named `new_name` at `position` and selects it
+    ///
+    /// See also:
+    /// * [Model::duplicate_sheet]
+    pub fn duplicate_sheet(
+        &mut self,
+        sheet: u32,
+        new_name: &str,
+        position: u32,
+    ) -> Result<(), String> {
+        self.model.duplicate_sheet(sheet, new_name, position)?;
+        self.set_selected_sheet(position)?;
+        self.push_diff_list(vec![Diff::DuplicateSheet {
+            sheet,
+            index: position,
+            name: new_name.to_string(),
+        }]);
+        Ok(())
+    }
+
+    /// Moves the sheet `from` to the index `to`
+    ///
+    /// See also:
+    /// * [Model::move_sheet]
+    pub fn move_sheet(&mut self, from: u32, to: u32) -> Result<(), String> {
+        if from == to {
+            return Ok(());
+        }
+        self.model.move_sheet(from, to)?;
+        self.push_diff_list(vec![Diff::MoveSheet { from, to }]);
+        Ok(())
+    }
+
     /// Hides sheet by index
     ///
     /// See also:
@@ -971,6 +1489,7 @@ impl<'a> UserModel<'a> {
             });
         }
 
+        let old_scenarios = self.model.get_scenarios(sheet)?;
         self.model.delete_rows(sheet, row, row_count)?;
 
         let diff_list = vec![Diff::DeleteRows {
@@ -978,6 +1497,78 @@ impl<'a> UserModel<'a> {
             row,
             count: row_count,
             old_data,
+            old_scenarios,
+        }];
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Inserts blank cells in `area`, moving the cells in the same rows right or the cells in the
+    /// same columns down.
+    ///
+    /// See also [`Model::insert_cells`].
+    pub fn insert_cells(&mut self, area: &Area, direction: ShiftDirection) -> Result<(), String> {
+        self.model.insert_cells(area, direction)?;
+
+        let diff_list = vec![Diff::InsertCells {
+            sheet: area.sheet,
+            row: area.row,
+            column: area.column,
+            width: area.width,
+            height: area.height,
+            direction,
+        }];
+        self.push_diff_list(diff_list);
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Deletes the cells in `area`, moving the cells to the right left or the cells below up.
+    ///
+    /// See also [`Model::delete_cells`].
+    pub fn delete_cells(&mut self, area: &Area, direction: ShiftDirection) -> Result<(), String> {
+        let worksheet = self.model.workbook.worksheet(area.sheet)?;
+        let mut old_value = Vec::new();
+        for row in area.row..area.row + area.height {
+            let mut row_data = Vec::new();
+            for column in area.column..area.column + area.width {
+                // Spill cells are recreated by the evaluation, only their style is kept
+                let cell = match worksheet.cell(row, column) {
+                    Some(Cell::SpillCell { s, .. }) => Some(Cell::EmptyCell { s: *s }),
+                    cell => cell.cloned(),
+                };
+                row_data.push(cell);
+            }
+            old_value.push(row_data);
+        }
+        let old_merges = worksheet
+            .merge_cells
+            .iter()
+            .filter(|range| {
+                parse_range(range).is_ok_and(|(first_column, first_row, last_column, last_row)| {
+                    first_row >= area.row
+                        && last_row < area.row + area.height
+                        && first_column >= area.column
+                        && last_column < area.column + area.width
+                })
+            })
+            .cloned()
+            .collect();
+
+        let old_scenarios = self.model.get_scenarios(area.sheet)?;
+        self.model.delete_cells(area, direction)?;
+
+        let diff_list = vec![Diff::DeleteCells {
+            sheet: area.sheet,
+            row: area.row,
+            column: area.column,
+            width: area.width,
+            height: area.height,
+            direction,
+            old_value,
+            old_merges,
+            old_scenarios,
         }];
         self.push_diff_list(diff_list);
         self.evaluate_if_not_paused();
@@ -1011,6 +1602,8 @@ impl<'a> UserModel<'a> {
                         custom_width: col.custom_width,
                         style: col.style,
                         hidden: col.hidden,
+                        outline_level: col.outline_level,
+                        collapsed: col.collapsed,
                     });
                     break;
                 }
@@ -1036,6 +1629,7 @@ impl<'a> UserModel<'a> {
             });
         }
 
+        let old_scenarios = self.model.get_scenarios(sheet)?;
         self.model.delete_columns(sheet, column, column_count)?;
 
         let diff_list = vec![Diff::DeleteColumns {
@@ -1043,6 +1637,7 @@ impl<'a> UserModel<'a> {
             column,
             count: column_count,
             old_data,
+            old_scenarios,
         }];
         self.push_diff_list(diff_list);
         self.evaluate_if_not_paused();
@@ -1305,6 +1900,380 @@ impl<'a> UserModel<'a> {
         Ok(())
     }
 
+    /// Groups the rows in `row_start..=row_end`, increasing their outline level by one
+    ///
+    /// Fails if any of the rows is already at the maximum outline level ([MAX_OUTLINE_LEVEL]).
+    ///
+    /// See also:
+    /// * [Model::set_row_outline_level]
+    pub fn group_rows(&mut self, sheet: u32, row_start: i32, row_end: i32) -> Result<(), String> {
+        let runs = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .get_rows_outline_runs(row_start, row_end)?;
+        if let Some((row, _, _)) = runs.iter().find(|run| run.2 >= MAX_OUTLINE_LEVEL) {
+            return Err(format!(
+                "Row {row} is already at the maximum outline level."
+            ));
+        }
+        self.model
+            .workbook
+            .worksheet_mut(sheet)?
+            .change_rows_outline_level(row_start, row_end, |level| level + 1)?;
+        // One diff per run of consecutive rows with the same level
+        let diff_list = runs
+            .into_iter()
+            .map(|(first, last, level)| Diff::SetRowsOutlineLevel {
+                sheet,
+                row: first,
+                count: last - first + 1,
+                new_value: level + 1,
+                old_value: level,
+            })
+            .collect();
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Ungroups the rows in `row_start..=row_end`, decreasing the outline level of the grouped ones by one.
+    /// Rows hidden by collapsing a group are not made visible.
+    ///
+    /// See also:
+    /// * [Model::set_row_outline_level]
+    pub fn ungroup_rows(&mut self, sheet: u32, row_start: i32, row_end: i32) -> Result<(), String> {
+        let runs = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .get_rows_outline_runs(row_start, row_end)?;
+        self.model
+            .workbook
+            .worksheet_mut(sheet)?
+            .change_rows_outline_level(row_start, row_end, |level| (level - 1).max(0))?;
+        // One diff per run of consecutive grouped rows with the same level, the ones that are
+        // not grouped are left out
+        let diff_list: Vec<Diff> = runs
+            .into_iter()
+            .filter(|run| run.2 > 0)
+            .map(|(first, last, level)| Diff::SetRowsOutlineLevel {
+                sheet,
+                row: first,
+                count: last - first + 1,
+                new_value: level - 1,
+                old_value: level,
+            })
+            .collect();
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    // Returns the outline group of the given level containing `row`
+    fn get_row_outline_group(
+        &self,
+        sheet: u32,
+        row: i32,
+        level: i32,
+    ) -> Result<OutlineGroup, String> {
+        self.model
+            .get_row_outline(sheet)?
+            .into_iter()
+            .find(|g| g.level == level && g.start <= row && row <= g.end)
+            .ok_or_else(|| format!("Row {row} is not in an outline group of level {level}."))
+    }
+
+    /// Collapses the outline group of the given level that contains `row`.
+    /// All the rows of the group are hidden and its summary row is flagged as collapsed.
+    pub fn collapse_row_group(&mut self, sheet: u32, row: i32, level: i32) -> Result<(), String> {
+        let group = self.get_row_outline_group(sheet, row, level)?;
+        let mut diff_list = Vec::new();
+        for row in group.start..=group.end {
+            let old_value = self.model.is_row_hidden(sheet, row)?;
+            if !old_value {
+                diff_list.push(Diff::SetRowHidden {
+                    sheet,
+                    row,
+                    new_value: true,
+                    old_value,
+                });
+                self.model.set_row_hidden(sheet, row, true)?;
+            }
+        }
+        let worksheet = self.model.workbook.worksheet(sheet)?;
+        let summary_after = worksheet.outline_properties.summary_below;
+        if let Some(summary) = group.get_summary_index(summary_after, LAST_ROW) {
+            let old_value = worksheet.is_row_collapsed(summary)?;
+            diff_list.push(Diff::SetRowCollapsed {
+                sheet,
+                row: summary,
+                new_value: true,
+                old_value,
+            });
+            self.model.set_row_collapsed(sheet, summary, true)?;
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    /// Expands the outline group of the given level that contains `row`.
+    /// Rows in nested groups that are still collapsed stay hidden.
+    pub fn expand_row_group(&mut self, sheet: u32, row: i32, level: i32) -> Result<(), String> {
+        let group = self.get_row_outline_group(sheet, row, level)?;
+        let collapsed_groups = self
+            .model
+            .get_row_outline(sheet)?
+            .into_iter()
+            .filter(|g| {
+                g.level > level && g.collapsed && group.start <= g.start && g.end <= group.end
+            })
+            .collect::<Vec<OutlineGroup>>();
+        let mut diff_list = Vec::new();
+        for row in group.start..=group.end {
+            if collapsed_groups
+                .iter()
+                .any(|g| g.start <= row && row <= g.end)
+            {
+                continue;
+            }
+            let old_value = self.model.is_row_hidden(sheet, row)?;
+            if old_value {
+                diff_list.push(Diff::SetRowHidden {
+                    sheet,
+                    row,
+                    new_value: false,
+                    old_value,
+                });
+                self.model.set_row_hidden(sheet, row, false)?;
+            }
+        }
+        let worksheet = self.model.workbook.worksheet(sheet)?;
+        let summary_after = worksheet.outline_properties.summary_below;
+        if let Some(summary) = group.get_summary_index(summary_after, LAST_ROW) {
+            let old_value = worksheet.is_row_collapsed(summary)?;
+            diff_list.push(Diff::SetRowCollapsed {
+                sheet,
+                row: summary,
+                new_value: false,
+                old_value,
+            });
+            self.model.set_row_collapsed(sheet, summary, false)?;
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    /// Returns the row outline groups of the sheet, sorted by level and start row
+    ///
+    /// See also:
+    /// * [Model::get_row_outline]
+    #[inline]
+    pub fn get_row_outline(&self, sheet: u32) -> Result<Vec<OutlineGroup>, String> {
+        self.model.get_row_outline(sheet)
+    }
+
+    /// Groups the columns in `column_start..=column_end`, increasing their outline level by one
+    ///
+    /// Fails if any of the columns is already at the maximum outline level ([MAX_OUTLINE_LEVEL]).
+    ///
+    /// See also:
+    /// * [Model::set_column_outline_level]
+    pub fn group_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), String> {
+        let runs = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .get_columns_outline_runs(column_start, column_end)?;
+        if let Some((column, _, _)) = runs.iter().find(|run| run.2 >= MAX_OUTLINE_LEVEL) {
+            return Err(format!(
+                "Column {column} is already at the maximum outline level."
+            ));
+        }
+        self.model
+            .workbook
+            .worksheet_mut(sheet)?
+            .change_columns_outline_level(column_start, column_end, |level| level + 1)?;
+        // One diff per run of consecutive columns with the same level
+        let diff_list = runs
+            .into_iter()
+            .map(|(first, last, level)| Diff::SetColumnsOutlineLevel {
+                sheet,
+                column: first,
+                count: last - first + 1,
+                new_value: level + 1,
+                old_value: level,
+            })
+            .collect();
+        self.push_diff_list(diff_list);
+        Ok(())
+    }
+
+    /// Ungroups the columns in `column_start..=column_end`, decreasing the outline level of the grouped ones by one.
+    /// Columns hidden by collapsing a group are not made visible.
+    ///
+    /// See also:
+    /// * [Model::set_column_outline_level]
+    pub fn ungroup_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), String> {
+        let runs = self
+            .model
+            .workbook
+            .worksheet(sheet)?
+            .get_columns_outline_runs(column_start, column_end)?;
+        self.model
+            .workbook
+            .worksheet_mut(sheet)?
+            .change_columns_outline_level(column_start, column_end, |level| (level - 1).max(0))?;
+        // One diff per run of consecutive grouped columns with the same level, the ones that are
+        // not grouped are left out
+        let diff_list: Vec<Diff> = runs
+            .into_iter()
+            .filter(|run| run.2 > 0)
+            .map(|(first, last, level)| Diff::SetColumnsOutlineLevel {
+                sheet,
+                column: first,
+                count: last - first + 1,
+                new_value: level - 1,
+                old_value: level,
+            })
+            .collect();
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    // Returns the outline group of the given level containing `column`
+    fn get_column_outline_group(
+        &self,
+        sheet: u32,
+        column: i32,
+        level: i32,
+    ) -> Result<OutlineGroup, String> {
+        self.model
+            .get_column_outline(sheet)?
+            .into_iter()
+            .find(|g| g.level == level && g.start <= column && column <= g.end)
+            .ok_or_else(|| format!("Column {column} is not in an outline group of level {level}."))
+    }
+
+    /// Collapses the outline group of the given level that contains `column`.
+    /// All the columns of the group are hidden and its summary column is flagged as collapsed.
+    pub fn collapse_column_group(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        level: i32,
+    ) -> Result<(), String> {
+        let group = self.get_column_outline_group(sheet, column, level)?;
+        let mut diff_list = Vec::new();
+        for column in group.start..=group.end {
+            let old_value = self.model.is_column_hidden(sheet, column)?;
+            if !old_value {
+                diff_list.push(Diff::SetColumnHidden {
+                    sheet,
+                    column,
+                    new_value: true,
+                    old_value,
+                });
+                self.model.set_column_hidden(sheet, column, true)?;
+            }
+        }
+        let worksheet = self.model.workbook.worksheet(sheet)?;
+        let summary_after = worksheet.outline_properties.summary_right;
+        if let Some(summary) = group.get_summary_index(summary_after, LAST_COLUMN) {
+            let old_value = worksheet.is_column_collapsed(summary)?;
+            diff_list.push(Diff::SetColumnCollapsed {
+                sheet,
+                column: summary,
+                new_value: true,
+                old_value,
+            });
+            self.model.set_column_collapsed(sheet, summary, true)?;
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    /// Expands the outline group of the given level that contains `column`.
+    /// Columns in nested groups that are still collapsed stay hidden.
+    pub fn expand_column_group(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        level: i32,
+    ) -> Result<(), String> {
+        let group = self.get_column_outline_group(sheet, column, level)?;
+        let collapsed_groups = self
+            .model
+            .get_column_outline(sheet)?
+            .into_iter()
+            .filter(|g| {
+                g.level > level && g.collapsed && group.start <= g.start && g.end <= group.end
+            })
+            .collect::<Vec<OutlineGroup>>();
+        let mut diff_list = Vec::new();
+        for column in group.start..=group.end {
+            if collapsed_groups
+                .iter()
+                .any(|g| g.start <= column && column <= g.end)
+            {
+                continue;
+            }
+            let old_value = self.model.is_column_hidden(sheet, column)?;
+            if old_value {
+                diff_list.push(Diff::SetColumnHidden {
+                    sheet,
+                    column,
+                    new_value: false,
+                    old_value,
+                });
+                self.model.set_column_hidden(sheet, column, false)?;
+            }
+        }
+        let worksheet = self.model.workbook.worksheet(sheet)?;
+        let summary_after = worksheet.outline_properties.summary_right;
+        if let Some(summary) = group.get_summary_index(summary_after, LAST_COLUMN) {
+            let old_value = worksheet.is_column_collapsed(summary)?;
+            diff_list.push(Diff::SetColumnCollapsed {
+                sheet,
+                column: summary,
+                new_value: false,
+                old_value,
+            });
+            self.model.set_column_collapsed(sheet, summary, false)?;
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+        }
+        Ok(())
+    }
+
+    /// Returns the column outline groups of the sheet, sorted by level and start column
+    ///
+    /// See also:
+    /// * [Model::get_column_outline]
+    #[inline]
+    pub fn get_column_outline(&self, sheet: u32) -> Result<Vec<OutlineGroup>, String> {
+        self.model.get_column_outline(sheet)
+    }
+
     /// Gets the height of a row
     ///
     /// See also:
@@ -1765,6 +2734,53 @@ impl<'a> UserModel<'a> {
         color.to_rgb(&self.model.workbook.theme)
     }
 
+    /// Sets the rules used to map workbook fonts to fonts that can be rendered.
+    ///
+    /// See also:
+    /// * [Model::set_font_fallback]
+    pub fn set_font_fallback(&mut self, font_fallback: FontFallback) {
+        self.model.set_font_fallback(font_fallback);
+    }
+
+    /// Returns the font fallback rules
+    pub fn get_font_fallback(&self) -> FontFallback {
+        self.model.get_font_fallback()
+    }
+
+    /// Registers a function implemented by the host and evaluates the model.
+    /// Registered functions are not part of the workbook nor of the undo history.
+    ///
+    /// See also:
+    /// * [Model::register_function]
+    pub fn register_function(&mut self, function: CustomFunction) -> Result<(), String> {
+        self.model.register_function(function)?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Removes a registered function and evaluates the model.
+    ///
+    /// See also:
+    /// * [Model::unregister_function]
+    pub fn unregister_function(&mut self, name: &str) -> Result<(), String> {
+        self.model.unregister_function(name)?;
+        self.evaluate_if_not_paused();
+        Ok(())
+    }
+
+    /// Returns the names of the registered functions
+    pub fn get_registered_functions(&self) -> Vec<String> {
+        self.model.get_registered_functions()
+    }
+
+    /// Returns the name of the font the front end should use to render `font`.
+    ///
+    /// See also:
+    /// * [Model::get_rendering_font_name]
+    pub fn get_rendering_font_name(&self, font: &Font) -> String {
+        self.model.get_rendering_font_name(font)
+    }
+
     /// Set the gid lines in the worksheet to visible (`true`) or hidden (`false`)
     pub fn set_show_grid_lines(&mut self, sheet: u32, show_grid_lines: bool) -> Result<(), String> {
         let old_value = self.model.workbook.worksheet(sheet)?.show_grid_lines;
@@ -1918,6 +2934,20 @@ impl<'a> UserModel<'a> {
         width: i32,
         height: i32,
         formula: &str,
+    ) -> Result<(), String> {
+        let formula = &self.model.user_input_to_a1(sheet, row, column, formula);
+        self.set_a1_array_formula(sheet, row, column, width, height, formula)
+    }
+
+    /// Same as [UserModel::set_user_array_formula] but the formula is always in A1 style.
+    fn set_a1_array_formula(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        width: i32,
+        height: i32,
+        formula: &str,
     ) -> Result<(), String> {
         let ws = self.model.workbook.worksheet(sheet)?;
         let mut old_values = Vec::new();
@@ -1936,7 +2966,7 @@ impl<'a> UserModel<'a> {
             old_values.push(row_vals);
         }
         self.model
-            .set_user_array_formula(sheet, row, column, width, height, formula)?;
+            .set_a1_user_array_formula(sheet, row, column, width, height, formula)?;
         self.push_diff_list(vec![Diff::SetArrayValue {
             sheet,
             row,
@@ -1950,9 +2980,21 @@ impl<'a> UserModel<'a> {
         Ok(())
     }
 
-    /// Returns the list of defined names
+    /// Returns the list of defined names.
+    /// Formulas are in R1C1 style, relative to the selected cell, if the workbook is in R1C1 reference style.
     pub fn get_defined_name_list(&self) -> Vec<(String, Option<u32>, String)> {
-        self.model.workbook.get_defined_names_with_scope()
+        let (sheet, row, column) = self.get_selected_cell();
+        self.model
+            .workbook
+            .get_defined_names_with_scope()
+            .into_iter()
+            .map(|(name, scope, formula)| {
+                let formula = self
+                    .model
+                    .defined_name_formula_to_user(&formula, sheet, row, column);
+                (name, scope, formula)
+            })
+            .collect()
     }
 
     /// Delete an existing defined name
@@ -1976,6 +3018,10 @@ impl<'a> UserModel<'a> {
         scope: Option<u32>,
         formula: &str,
     ) -> Result<(), String> {
+        let (sheet, row, column) = self.get_selected_cell();
+        let formula = &self
+            .model
+            .defined_name_formula_to_a1(formula, sheet, row, column);
         self.model.new_defined_name(name, scope, formula)?;
         let diff_list = vec![Diff::CreateDefinedName {
             name: name.to_string(),
@@ -1996,6 +3042,10 @@ impl<'a> UserModel<'a> {
         new_scope: Option<u32>,
         new_formula: &str,
     ) -> Result<(), String> {
+        let (sheet, row, column) = self.get_selected_cell();
+        let new_formula = &self
+            .model
+            .defined_name_formula_to_a1(new_formula, sheet, row, column);
         let old_formula = self
             .model
             .get_defined_name_formula(name, scope)
@@ -2022,6 +3072,10 @@ impl<'a> UserModel<'a> {
         scope: Option<u32>,
         formula: &str,
     ) -> Result<Option<u32>, String> {
+        let (sheet, row, column) = self.get_selected_cell();
+        let formula = &self
+            .model
+            .defined_name_formula_to_a1(formula, sheet, row, column);
         self.model.is_valid_defined_name(name, scope, formula)
     }
 
@@ -2045,6 +3099,64 @@ impl<'a> UserModel<'a> {
         self.model.set_locale(locale)
     }
 
+    /// Sets the date system of the workbook (1904 if `date1904` is true, 1900 otherwise).
+    /// Serial numbers are not modified, see [`UserModel::convert_date_system`].
+    pub fn set_date1904(&mut self, date1904: bool) -> Result<(), String> {
+        let diff_list = vec![Diff::SetDate1904 {
+            old_value: self.get_date1904(),
+            new_value: date1904,
+        }];
+        self.push_diff_list(diff_list);
+        self.model.set_date1904(date1904);
+        Ok(())
+    }
+
+    /// Changes the date system of the workbook re-basing the numbers of all date formatted cells
+    /// so that they keep displaying the same dates.
+    pub fn convert_date_system(&mut self, date1904: bool) -> Result<(), String> {
+        let mut diff_list = Vec::new();
+        for (cell, old_value, new_value) in self.model.get_date_cells_to_rebase(date1904)? {
+            self.model
+                .update_cell_with_number(cell.sheet, cell.row, cell.column, new_value)?;
+            diff_list.push(Diff::RebaseDateSerial {
+                sheet: cell.sheet,
+                row: cell.row,
+                column: cell.column,
+                old_value,
+                new_value,
+            });
+        }
+        diff_list.push(Diff::SetDate1904 {
+            old_value: self.get_date1904(),
+            new_value: date1904,
+        });
+        self.push_diff_list(diff_list);
+        self.model.set_date1904(date1904);
+        Ok(())
+    }
+
+    /// Returns true if the workbook uses the 1904 date system
+    pub fn get_date1904(&self) -> bool {
+        self.model.get_date1904()
+    }
+
+    /// Sets the reference style of the workbook, R1C1 if `r1c1` is true and A1 otherwise.
+    /// Cell contents and defined names are entered and displayed in that style.
+    pub fn set_r1c1(&mut self, r1c1: bool) -> Result<(), String> {
+        let diff_list = vec![Diff::SetR1C1 {
+            old_value: self.get_r1c1(),
+            new_value: r1c1,
+        }];
+        self.push_diff_list(diff_list);
+        self.model.set_r1c1(r1c1);
+        Ok(())
+    }
+
+    /// Returns true if the workbook is in R1C1 reference style
+    pub fn get_r1c1(&self) -> bool {
+        self.model.get_r1c1()
+    }
+
     /// Gets the timezone of the model
     pub fn get_timezone(&self) -> String {
         self.model.get_timezone()
@@ -2080,6 +3192,18 @@ impl<'a> UserModel<'a> {
         self.history.push(diff_list);
     }
 
+    pub(super) fn take_send_queue(&mut self) -> Vec<QueueDiffs> {
+        std::mem::take(&mut self.send_queue)
+    }
+
+    pub(super) fn history_mut(&mut self) -> &mut History {
+        &mut self.history
+    }
+
+    pub(super) fn is_evaluation_paused(&self) -> bool {
+        self.pause_evaluation
+    }
+
     pub(super) fn evaluate_if_not_paused(&mut self) {
         if !self.pause_evaluation {
             self.model.evaluate();
@@ -0,0 +1,279 @@
+#![deny(missing_docs)]
+
+#[cfg(target_arch = "wasm32")]
+use regex_lite as regex;
+
+use regex::Regex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    expressions::utils::parse_reference_a1, model::CellStructure, types::Cell,
+    user_model::history::Diff, UserModel,
+};
+
+/// What the search looks at
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindLookIn {
+    /// The formatted values, as displayed in the cells (e.g. "$1,000")
+    Values,
+    /// The formulas and the constants as they were typed
+    Formulas,
+    /// The text of the comments
+    Comments,
+}
+
+/// The order in which the cells are visited
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindOrder {
+    /// Row by row, from left to right
+    ByRows,
+    /// Column by column, from top to bottom
+    ByColumns,
+}
+
+/// The cells that are searched
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FindScope {
+    /// The selected range. A single selected cell searches the whole sheet.
+    Selection,
+    /// The selected sheet
+    Sheet,
+    /// All the sheets, in order
+    Workbook,
+}
+
+/// Options of [UserModel::find] and [UserModel::replace_all]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FindOptions {
+    /// Upper and lower case letters are different
+    pub match_case: bool,
+    /// The query must match the whole text of the cell
+    pub whole_cell: bool,
+    /// The query is a regular expression
+    pub regex: bool,
+    /// What is searched
+    pub look_in: FindLookIn,
+    /// The order of the results
+    pub order: FindOrder,
+    /// Where to search
+    pub scope: FindScope,
+}
+
+/// A cell that matches the query
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FindHit {
+    /// Sheet index
+    pub sheet: u32,
+    /// Row of the cell
+    pub row: i32,
+    /// Column of the cell
+    pub column: i32,
+    /// The text that matched: the formatted value, the formula or the comment
+    pub text: String,
+}
+
+fn build_regex(query: &str, options: &FindOptions) -> Result<Regex, String> {
+    if query.is_empty() {
+        return Err("Nothing to find".to_string());
+    }
+    let pattern = if options.regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    let pattern = if options.whole_cell {
+        format!("^(?:{pattern})$")
+    } else {
+        pattern
+    };
+    let pattern = if options.match_case {
+        pattern
+    } else {
+        format!("(?i){pattern}")
+    };
+    Regex::new(&pattern).map_err(|_| format!("Invalid regular expression: '{query}'"))
+}
+
+impl<'a> UserModel<'a> {
+    /// Returns the cells that match `query`, in the order given by `options`.
+    ///
+    /// Empty cells never match. Fails if the query is empty or is not a valid regular expression.
+    pub fn find(
+        &self,
+        query: &str,
+        options: &FindOptions,
+    ) -> Result<impl Iterator<Item = FindHit> + '_, String> {
+        let re = build_regex(query, options)?;
+        let look_in = options.look_in;
+        let cells = self.get_find_cells(options)?;
+        Ok(cells.into_iter().filter_map(move |(sheet, row, column)| {
+            let text = self.get_find_text(sheet, row, column, look_in)?;
+            if re.is_match(&text) {
+                Some(FindHit {
+                    sheet,
+                    row,
+                    column,
+                    text,
+                })
+            } else {
+                None
+            }
+        }))
+    }
+
+    /// Replaces `query` with `replacement` in the formulas and constants of all the matching cells.
+    /// Returns the number of cells that changed.
+    ///
+    /// The search always looks in the formulas, whatever `options.look_in` is.
+    /// The replacement can use the groups of a regular expression (`$1`).
+    /// Cells of array formulas entered with Ctrl+Shift+Enter and spilled cells are skipped.
+    /// All the changes are a single step in the history.
+    pub fn replace_all(
+        &mut self,
+        query: &str,
+        replacement: &str,
+        options: &FindOptions,
+    ) -> Result<usize, String> {
+        let options = FindOptions {
+            look_in: FindLookIn::Formulas,
+            ..*options
+        };
+        let re = build_regex(query, &options)?;
+        let replacement = if options.regex {
+            replacement.to_string()
+        } else {
+            replacement.replace('$', "$$")
+        };
+        let mut changes = Vec::new();
+        for hit in self.find(query, &options)? {
+            if !matches!(
+                self.model
+                    .get_cell_structure(hit.sheet, hit.row, hit.column)?,
+                CellStructure::SingleCell | CellStructure::DynamicFormula { .. }
+            ) {
+                continue;
+            }
+            let new_text = re.replace_all(&hit.text, replacement.as_str()).into_owned();
+            if new_text == hit.text {
+                continue;
+            }
+            changes.push((hit.sheet, hit.row, hit.column, new_text));
+        }
+
+        let mut diff_list = Vec::new();
+        for (sheet, row, column, new_text) in &changes {
+            let value = self.model.user_input_to_a1(*sheet, *row, *column, new_text);
+            let old_value = self
+                .model
+                .workbook
+                .worksheet(*sheet)?
+                .cell(*row, *column)
+                .cloned();
+            self.model
+                .set_a1_user_input(*sheet, *row, *column, value.clone())?;
+            diff_list.push(Diff::SetCellValue {
+                sheet: *sheet,
+                row: *row,
+                column: *column,
+                new_value: value,
+                old_value: Box::new(old_value),
+            });
+        }
+        if !diff_list.is_empty() {
+            self.push_diff_list(diff_list);
+            self.evaluate_if_not_paused();
+        }
+        Ok(changes.len())
+    }
+
+    /// The cells that might match, in order
+    fn get_find_cells(&self, options: &FindOptions) -> Result<Vec<(u32, i32, i32)>, String> {
+        let view = self.get_selected_view();
+        let sheets = match options.scope {
+            FindScope::Selection | FindScope::Sheet => vec![view.sheet],
+            FindScope::Workbook => (0..self.model.workbook.worksheets.len() as u32).collect(),
+        };
+        let [row1, column1, row2, column2] = view.range;
+        let (row1, row2) = (row1.min(row2), row1.max(row2));
+        let (column1, column2) = (column1.min(column2), column1.max(column2));
+        let in_selection =
+            options.scope == FindScope::Selection && (row1, column1) != (row2, column2);
+        let mut cells = Vec::new();
+        for sheet in sheets {
+            let worksheet = self.model.workbook.worksheet(sheet)?;
+            let mut sheet_cells: Vec<(i32, i32)> = if options.look_in == FindLookIn::Comments {
+                worksheet
+                    .comments
+                    .iter()
+                    .filter_map(|comment| parse_reference_a1(&comment.cell_ref))
+                    .map(|reference| (reference.row, reference.column))
+                    .collect()
+            } else {
+                worksheet
+                    .sheet_data
+                    .iter()
+                    .flat_map(|(row, row_data)| row_data.keys().map(move |column| (*row, *column)))
+                    .collect()
+            };
+            if in_selection {
+                sheet_cells.retain(|(row, column)| {
+                    (row1..=row2).contains(row) && (column1..=column2).contains(column)
+                });
+            }
+            match options.order {
+                FindOrder::ByRows => sheet_cells.sort_unstable(),
+                FindOrder::ByColumns => {
+                    sheet_cells.sort_unstable_by_key(|(row, column)| (*column, *row))
+                }
+            }
+            cells.extend(
+                sheet_cells
+                    .into_iter()
+                    .map(|(row, column)| (sheet, row, column)),
+            );
+        }
+        Ok(cells)
+    }
+
+    /// The text that is searched in a cell, `None` if there is nothing to search
+    fn get_find_text(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        look_in: FindLookIn,
+    ) -> Option<String> {
+        let text = match look_in {
+            FindLookIn::Values => self.get_formatted_cell_value(sheet, row, column).ok()?,
+            FindLookIn::Formulas => {
+                let worksheet = self.model.workbook.worksheet(sheet).ok()?;
+                if matches!(worksheet.cell(row, column), Some(Cell::SpillCell { .. })) {
+                    return None;
+                }
+                self.get_cell_content(sheet, row, column).ok()?
+            }
+            FindLookIn::Comments => {
+                let worksheet = self.model.workbook.worksheet(sheet).ok()?;
+                worksheet
+                    .comments
+                    .iter()
+                    .find(|comment| {
+                        parse_reference_a1(&comment.cell_ref).is_some_and(|reference| {
+                            (reference.row, reference.column) == (row, column)
+                        })
+                    })?
+                    .text
+                    .clone()
+            }
+        };
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+}
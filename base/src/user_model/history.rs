@@ -4,7 +4,7 @@ use bitcode::{Decode, Encode};
 
 use crate::{
     cf_types::CfRule,
-    types::{Cell, Col, Color, Row, SheetState, Style, Theme, Worksheet},
+    types::{Cell, Col, Color, Row, Scenario, SheetState, ShiftDirection, Style, Theme, Worksheet},
 };
 
 #[derive(Clone, Encode, Decode)]
@@ -93,6 +93,32 @@ pub(crate) enum Diff {
         new_value: bool,
         old_value: bool,
     },
+    SetColumnsOutlineLevel {
+        sheet: u32,
+        column: i32,
+        count: i32,
+        new_value: i32,
+        old_value: i32,
+    },
+    SetColumnCollapsed {
+        sheet: u32,
+        column: i32,
+        new_value: bool,
+        old_value: bool,
+    },
+    SetRowsOutlineLevel {
+        sheet: u32,
+        row: i32,
+        count: i32,
+        new_value: i32,
+        old_value: i32,
+    },
+    SetRowCollapsed {
+        sheet: u32,
+        row: i32,
+        new_value: bool,
+        old_value: bool,
+    },
     SetColumnStyle {
         sheet: u32,
         column: i32,
@@ -125,6 +151,8 @@ pub(crate) enum Diff {
         row: i32,
         count: i32,
         old_data: Vec<RowData>,
+        // Scenarios of the sheet, deleted changing cells are removed from them
+        old_scenarios: Vec<Scenario>,
     },
     InsertColumns {
         sheet: u32,
@@ -136,6 +164,27 @@ pub(crate) enum Diff {
         column: i32,
         count: i32,
         old_data: Vec<ColumnData>,
+        old_scenarios: Vec<Scenario>,
+    },
+    InsertCells {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        width: i32,
+        height: i32,
+        direction: ShiftDirection,
+    },
+    DeleteCells {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        width: i32,
+        height: i32,
+        direction: ShiftDirection,
+        old_value: Vec<Vec<Option<Cell>>>,
+        // Merged cells inside the deleted area
+        old_merges: Vec<String>,
+        old_scenarios: Vec<Scenario>,
     },
     DeleteSheet {
         sheet: u32,
@@ -160,6 +209,15 @@ pub(crate) enum Diff {
         old_value: String,
         new_value: String,
     },
+    DuplicateSheet {
+        sheet: u32,
+        index: u32,
+        name: String,
+    },
+    MoveSheet {
+        from: u32,
+        to: u32,
+    },
     SetSheetColor {
         index: u32,
         old_value: Color,
@@ -217,6 +275,21 @@ pub(crate) enum Diff {
         old_value: String,
         new_value: String,
     },
+    SetDate1904 {
+        old_value: bool,
+        new_value: bool,
+    },
+    SetR1C1 {
+        old_value: bool,
+        new_value: bool,
+    },
+    RebaseDateSerial {
+        sheet: u32,
+        row: i32,
+        column: i32,
+        old_value: f64,
+        new_value: f64,
+    },
     // Named style diffs
     CreateNamedStyle {
         name: String,
@@ -255,6 +328,31 @@ pub(crate) enum Diff {
         new_range: String,
         new_rule: Box<CfRule>,
     },
+    // Scenario diffs
+    AddScenario {
+        sheet: u32,
+        scenario: Box<Scenario>,
+    },
+    DeleteScenario {
+        sheet: u32,
+        index: u32,
+        old_value: Box<Scenario>,
+    },
+    UpdateScenario {
+        sheet: u32,
+        index: u32,
+        old_value: Box<Scenario>,
+        new_value: Box<Scenario>,
+    },
+    // Merged cells, the range is like "A1:C2"
+    MergeCells {
+        sheet: u32,
+        range: String,
+    },
+    UnmergeCells {
+        sheet: u32,
+        range: String,
+    },
     // FIXME: we are missing SetViewDiffs
 }
 
@@ -0,0 +1,1127 @@
+//! Reading and writing the HTML tables that spreadsheets put on the clipboard.
+//!
+//! Excel and Google Sheets copy a `<table>` and describe the formatting with CSS, inline or in the
+//! rules of a `<style>` element (the `.xl65 {...}` classes of Excel). Excel writes the number format
+//! in `mso-number-format` and Google Sheets adds the values and the formulas as `data-sheets-*`
+//! attributes. This is not a full HTML parser, it only reads what is needed to get the cells of the
+//! first table of the document.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::types::{
+    Alignment, BorderItem, BorderStyle, Color, Fill, FontScheme, HorizontalAlignment, PatternType,
+    Style, Theme, VerticalAlignment,
+};
+
+/// A cell of the table as read from the HTML
+pub(crate) struct HtmlCell {
+    /// Row of the cell, relative to the first row of the table
+    pub(crate) row: i32,
+    /// Column of the cell, relative to the first column of the table
+    pub(crate) column: i32,
+    /// Number of columns of the cell (`colspan`)
+    pub(crate) width: i32,
+    /// Number of rows of the cell (`rowspan`)
+    pub(crate) height: i32,
+    /// The text of the cell, `<br>` are line breaks
+    pub(crate) text: String,
+    /// The attributes of the `<td>`, the names are in lowercase
+    pub(crate) attributes: HashMap<String, String>,
+    /// The style given by the CSS, including what the cell inherits from the table and the row
+    pub(crate) style: Style,
+    /// The number format, if the HTML has one
+    pub(crate) num_fmt: Option<String>,
+}
+
+enum Token {
+    /// An opening tag, `<br/>` and the like are opening tags too
+    Open {
+        name: String,
+        attributes: HashMap<String, String>,
+    },
+    Close(String),
+    Text(String),
+    /// The content of a `<style>` element
+    Css(String),
+}
+
+/// A CSS rule with one of the selectors we understand: `td`, `.xl65` or `td.xl65`
+struct CssRule {
+    element: Option<String>,
+    class: Option<String>,
+    declarations: Vec<(String, String)>,
+}
+
+/// The cell being read
+struct OpenCell {
+    row: i32,
+    column: i32,
+    width: i32,
+    height: i32,
+    text: String,
+    attributes: HashMap<String, String>,
+    declarations: Vec<(String, String)>,
+}
+
+/// Elements that start a new line in the text of a cell
+const BLOCK_ELEMENTS: [&str; 9] = ["p", "div", "li", "h1", "h2", "h3", "h4", "h5", "h6"];
+
+/// Elements whose text is never content
+const IGNORED_ELEMENTS: [&str; 2] = ["head", "title"];
+
+/// Reads the cells of the first table of the HTML. Positions covered by a cell that spans several
+/// rows or columns have no cell of their own.
+/// If there is no table the whole document is read as a single cell.
+pub(crate) fn read_html_table(html: &str) -> Vec<HtmlCell> {
+    let tokens = tokenize(html);
+    let mut rules = Vec::new();
+    for token in &tokens {
+        if let Token::Css(css) = token {
+            parse_stylesheet(css, &mut rules);
+        }
+    }
+    let has_table = tokens
+        .iter()
+        .any(|token| matches!(token, Token::Open { name, .. } if name == "table"));
+    if has_table {
+        read_table(&tokens, &rules)
+    } else {
+        read_single_cell(&tokens, &rules)
+    }
+}
+
+fn read_table(tokens: &[Token], rules: &[CssRule]) -> Vec<HtmlCell> {
+    let mut cells = Vec::new();
+    let mut depth = 0;
+    let mut table_declarations = Vec::new();
+    let mut row_declarations = Vec::new();
+    let mut cell: Option<OpenCell> = None;
+    // Row and column of the next cell
+    let mut row = -1;
+    let mut column = 0;
+    let mut occupied = HashSet::new();
+    for token in tokens {
+        match token {
+            Token::Open { name, attributes } if name == "table" => {
+                depth += 1;
+                if depth == 1 {
+                    table_declarations = inherited(element_declarations(name, attributes, rules));
+                }
+            }
+            Token::Close(name) if name == "table" => {
+                if depth == 1 {
+                    break;
+                }
+                depth -= 1;
+            }
+            // Nested tables are ignored
+            _ if depth != 1 => {}
+            Token::Open { name, attributes } if name == "tr" => {
+                finish_cell(&mut cell, &mut cells);
+                row += 1;
+                column = 0;
+                row_declarations = inherited(element_declarations(name, attributes, rules));
+            }
+            Token::Open { name, attributes } if name == "td" || name == "th" => {
+                finish_cell(&mut cell, &mut cells);
+                row = row.max(0);
+                while occupied.contains(&(row, column)) {
+                    column += 1;
+                }
+                let span = |attribute: &str| {
+                    attributes
+                        .get(attribute)
+                        .and_then(|span| span.trim().parse::<i32>().ok())
+                        .unwrap_or(1)
+                        .clamp(1, 1000)
+                };
+                let width = span("colspan");
+                let height = span("rowspan");
+                for r in row..row + height {
+                    for c in column..column + width {
+                        occupied.insert((r, c));
+                    }
+                }
+                let mut declarations = table_declarations.clone();
+                declarations.extend(row_declarations.iter().cloned());
+                declarations.extend(element_declarations(name, attributes, rules));
+                cell = Some(OpenCell {
+                    row,
+                    column,
+                    width,
+                    height,
+                    text: String::new(),
+                    attributes: attributes.clone(),
+                    declarations,
+                });
+                column += width;
+            }
+            Token::Close(name) if name == "td" || name == "th" || name == "tr" => {
+                finish_cell(&mut cell, &mut cells);
+            }
+            _ => {
+                if let Some(cell) = &mut cell {
+                    read_cell_content(cell, token, rules);
+                }
+            }
+        }
+    }
+    finish_cell(&mut cell, &mut cells);
+    cells
+}
+
+fn read_single_cell(tokens: &[Token], rules: &[CssRule]) -> Vec<HtmlCell> {
+    let mut cell = OpenCell {
+        row: 0,
+        column: 0,
+        width: 1,
+        height: 1,
+        text: String::new(),
+        attributes: HashMap::new(),
+        declarations: Vec::new(),
+    };
+    let mut ignored = 0;
+    for token in tokens {
+        match token {
+            Token::Open { name, .. } if IGNORED_ELEMENTS.contains(&name.as_str()) => ignored += 1,
+            Token::Close(name) if IGNORED_ELEMENTS.contains(&name.as_str()) => ignored -= 1,
+            _ if ignored > 0 => {}
+            Token::Open { name, attributes }
+                if !matches!(name.as_str(), "html" | "body" | "meta") =>
+            {
+                // Google Sheets puts the attributes of a single cell in a `<span>`
+                for (attribute, value) in attributes {
+                    cell.attributes
+                        .entry(attribute.clone())
+                        .or_insert_with(|| value.clone());
+                }
+                read_cell_content(&mut cell, token, rules);
+            }
+            _ => read_cell_content(&mut cell, token, rules),
+        }
+    }
+    if cell.text.trim().is_empty() && cell.attributes.is_empty() {
+        return vec![];
+    }
+    let mut cells = Vec::new();
+    finish_cell(&mut Some(cell), &mut cells);
+    cells
+}
+
+/// Text and formatting elements (`<b>`, `<font>`, `<span style=...>`) inside a cell.
+/// The formatting applies to the whole cell.
+fn read_cell_content(cell: &mut OpenCell, token: &Token, rules: &[CssRule]) {
+    match token {
+        Token::Open { name, attributes } => {
+            if name == "br" {
+                while cell.text.ends_with(' ') {
+                    cell.text.pop();
+                }
+                cell.text.push('\n');
+            } else if BLOCK_ELEMENTS.contains(&name.as_str())
+                && !cell.text.is_empty()
+                && !cell.text.ends_with('\n')
+            {
+                cell.text.push('\n');
+            }
+            cell.declarations
+                .extend(element_declarations(name, attributes, rules));
+        }
+        Token::Text(text) => {
+            // Runs of white space are a single space, as in the browser
+            for c in text.chars() {
+                if c.is_ascii_whitespace() {
+                    if !cell.text.is_empty() && !cell.text.ends_with([' ', '\n']) {
+                        cell.text.push(' ');
+                    }
+                } else {
+                    cell.text.push(c);
+                }
+            }
+        }
+        Token::Close(_) | Token::Css(_) => {}
+    }
+}
+
+fn finish_cell(cell: &mut Option<OpenCell>, cells: &mut Vec<HtmlCell>) {
+    if let Some(cell) = cell.take() {
+        let (style, mut num_fmt) = declarations_to_style(&cell.declarations);
+        if let Some(format) = cell
+            .attributes
+            .get("data-sheets-numberformat")
+            .and_then(|format| json_field(format, "2"))
+        {
+            num_fmt = Some(format);
+        }
+        let text = cell
+            .text
+            .trim_end_matches([' ', '\n'])
+            .replace('\u{a0}', " ");
+        cells.push(HtmlCell {
+            row: cell.row,
+            column: cell.column,
+            width: cell.width,
+            height: cell.height,
+            text,
+            attributes: cell.attributes,
+            style,
+            num_fmt,
+        });
+    }
+}
+
+/// Table and row declarations that the cells inherit. Borders are drawn around the table or the row, not the cells.
+fn inherited(declarations: Vec<(String, String)>) -> Vec<(String, String)> {
+    declarations
+        .into_iter()
+        .filter(|(name, _)| !name.starts_with("border"))
+        .collect()
+}
+
+/// The CSS declarations of an element: those implied by the element itself (`<b>`, `<font color=...>`),
+/// those of the matching rules and the inline style, in order of precedence.
+fn element_declarations(
+    name: &str,
+    attributes: &HashMap<String, String>,
+    rules: &[CssRule],
+) -> Vec<(String, String)> {
+    let mut declarations = Vec::new();
+    let mut add = |property: &str, value: &str| {
+        declarations.push((property.to_string(), value.to_string()));
+    };
+    match name {
+        "b" | "strong" => add("font-weight", "bold"),
+        "i" | "em" => add("font-style", "italic"),
+        "u" => add("text-decoration", "underline"),
+        "s" | "strike" | "del" => add("text-decoration", "line-through"),
+        _ => {}
+    }
+    for (attribute, property) in [
+        ("color", "color"),
+        ("face", "font-family"),
+        ("bgcolor", "background-color"),
+        ("align", "text-align"),
+        ("valign", "vertical-align"),
+    ] {
+        if let Some(value) = attributes.get(attribute) {
+            add(property, value);
+        }
+    }
+    let classes: Vec<String> = attributes
+        .get("class")
+        .map(|class| {
+            class
+                .split_whitespace()
+                .map(|c| c.to_ascii_lowercase())
+                .collect()
+        })
+        .unwrap_or_default();
+    let is_cell = name == "td" || name == "th";
+    // Rules for all the elements of a kind go first, class rules take precedence
+    for rule in rules.iter().filter(|rule| rule.class.is_none()) {
+        if rule.element.as_deref() == Some(name) {
+            // A border on every cell (`td {border: 1px solid #cccccc}`) only draws the grid lines
+            declarations.extend(
+                rule.declarations
+                    .iter()
+                    .filter(|(property, _)| !is_cell || !property.starts_with("border"))
+                    .cloned(),
+            );
+        }
+    }
+    for rule in rules {
+        if let Some(class) = &rule.class {
+            if classes.contains(class) && rule.element.as_deref().is_none_or(|e| e == name) {
+                declarations.extend(rule.declarations.iter().cloned());
+            }
+        }
+    }
+    if let Some(style) = attributes.get("style") {
+        declarations.extend(parse_declarations(style));
+    }
+    declarations
+}
+
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    // Same byte offsets as `html`
+    let lower = html.to_ascii_lowercase();
+    let bytes = html.as_bytes();
+    let length = html.len();
+    let mut position = 0;
+    while position < length {
+        let rest = &html[position..];
+        let starts_tag = rest.starts_with('<')
+            && bytes
+                .get(position + 1)
+                .is_some_and(|&b| b.is_ascii_alphabetic() || b == b'/' || b == b'!' || b == b'?');
+        if rest.starts_with('<') && !starts_tag {
+            tokens.push(Token::Text("<".to_string()));
+            position += 1;
+        } else if !starts_tag {
+            let end = rest.find('<').map_or(length, |p| position + p);
+            tokens.push(Token::Text(decode_entities(&html[position..end])));
+            position = end;
+        } else if rest.starts_with("<!--") {
+            position = rest.find("-->").map_or(length, |p| position + p + 3);
+        } else if rest.starts_with("<!") || rest.starts_with("<?") {
+            position = rest.find('>').map_or(length, |p| position + p + 1);
+        } else if let Some(tag) = rest.strip_prefix("</") {
+            let end = tag.find('>').unwrap_or(tag.len());
+            tokens.push(Token::Close(tag[..end].trim().to_ascii_lowercase()));
+            position = (position + 2 + end + 1).min(length);
+        } else {
+            let (name, attributes, end) = read_tag(html, position + 1);
+            position = end;
+            if name == "style" || name == "script" {
+                let close = format!("</{name}");
+                let content_end = lower[position..]
+                    .find(&close)
+                    .map_or(length, |p| position + p);
+                if name == "style" {
+                    tokens.push(Token::Css(html[position..content_end].to_string()));
+                }
+                position = lower[content_end..]
+                    .find('>')
+                    .map_or(length, |p| content_end + p + 1);
+            } else {
+                tokens.push(Token::Open { name, attributes });
+            }
+        }
+    }
+    tokens
+}
+
+/// Reads the name and the attributes of the tag that starts at `start`, just after the `<`.
+/// Returns them with the position after the tag.
+fn read_tag(html: &str, start: usize) -> (String, HashMap<String, String>, usize) {
+    let bytes = html.as_bytes();
+    let length = bytes.len();
+    let mut position = start;
+    while position < length
+        && (bytes[position].is_ascii_alphanumeric()
+            || matches!(bytes[position], b'-' | b':' | b'_'))
+    {
+        position += 1;
+    }
+    let name = html[start..position].to_ascii_lowercase();
+    let mut attributes = HashMap::new();
+    loop {
+        while position < length
+            && (bytes[position].is_ascii_whitespace() || bytes[position] == b'/')
+        {
+            position += 1;
+        }
+        if position >= length {
+            break;
+        }
+        if bytes[position] == b'>' {
+            position += 1;
+            break;
+        }
+        let name_start = position;
+        while position < length
+            && !bytes[position].is_ascii_whitespace()
+            && !matches!(bytes[position], b'=' | b'>' | b'/')
+        {
+            position += 1;
+        }
+        let attribute = html[name_start..position].to_ascii_lowercase();
+        while position < length && bytes[position].is_ascii_whitespace() {
+            position += 1;
+        }
+        let mut value = String::new();
+        if position < length && bytes[position] == b'=' {
+            position += 1;
+            while position < length && bytes[position].is_ascii_whitespace() {
+                position += 1;
+            }
+            if position < length && matches!(bytes[position], b'"' | b'\'') {
+                let quote = bytes[position];
+                let value_start = position + 1;
+                let value_end = bytes[value_start..]
+                    .iter()
+                    .position(|&b| b == quote)
+                    .map_or(length, |p| value_start + p);
+                value = decode_entities(&html[value_start..value_end]);
+                position = (value_end + 1).min(length);
+            } else {
+                let value_start = position;
+                while position < length
+                    && !bytes[position].is_ascii_whitespace()
+                    && bytes[position] != b'>'
+                {
+                    position += 1;
+                }
+                value = decode_entities(&html[value_start..position]);
+            }
+        }
+        attributes.entry(attribute).or_insert(value);
+    }
+    (name, attributes, position)
+}
+
+fn decode_entities(text: &str) -> String {
+    if !text.contains('&') {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let decoded = rest.find(';').filter(|&end| end <= 10).and_then(|end| {
+            let c = match &rest[1..end] {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some('\u{a0}'),
+                entity => {
+                    if let Some(hex) = entity
+                        .strip_prefix("#x")
+                        .or_else(|| entity.strip_prefix("#X"))
+                    {
+                        u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                    } else if let Some(decimal) = entity.strip_prefix('#') {
+                        decimal.parse().ok().and_then(char::from_u32)
+                    } else {
+                        None
+                    }
+                }
+            };
+            c.map(|c| (c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                result.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                result.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Escapes the text for the content or the attribute value of an element
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => result.push_str("&amp;"),
+            '<' => result.push_str("&lt;"),
+            '>' => result.push_str("&gt;"),
+            '"' => result.push_str("&quot;"),
+            '\'' => result.push_str("&#39;"),
+            _ => result.push(c),
+        }
+    }
+    result
+}
+
+fn parse_stylesheet(css: &str, rules: &mut Vec<CssRule>) {
+    let mut css = css.replace("<!--", "").replace("-->", "");
+    while let Some(start) = css.find("/*") {
+        let end = css[start..].find("*/").map_or(css.len(), |p| start + p + 2);
+        css.replace_range(start..end, "");
+    }
+    let mut rest = css.as_str();
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|p| open + p) else {
+            break;
+        };
+        let declarations = parse_declarations(&rest[open + 1..close]);
+        for selector in rest[..open].split(',') {
+            let selector = selector.trim().to_ascii_lowercase();
+            if selector.is_empty()
+                || selector.contains(|c: char| {
+                    c.is_whitespace() || matches!(c, '>' | '@' | ':' | '[' | '*' | '#')
+                })
+            {
+                continue;
+            }
+            let (element, class) = match selector.split_once('.') {
+                Some((element, class)) => (element, Some(class.to_string())),
+                None => (selector.as_str(), None),
+            };
+            rules.push(CssRule {
+                element: (!element.is_empty()).then(|| element.to_string()),
+                class,
+                declarations: declarations.clone(),
+            });
+        }
+        rest = &rest[close + 1..];
+    }
+}
+
+/// Splits `color:red; mso-number-format:"0\.00\;"` into its declarations.
+/// Property names are in lowercase, values keep their quotes and escapes.
+fn parse_declarations(block: &str) -> Vec<(String, String)> {
+    let mut declarations = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    let mut chars = block.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push(c);
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            '"' | '\'' => {
+                if quote == Some(c) {
+                    quote = None;
+                } else if quote.is_none() {
+                    quote = Some(c);
+                }
+                current.push(c);
+            }
+            ';' if quote.is_none() => {
+                push_declaration(&mut declarations, &current);
+                current.clear();
+            }
+            _ => current.push(c),
+        }
+    }
+    push_declaration(&mut declarations, &current);
+    declarations
+}
+
+fn push_declaration(declarations: &mut Vec<(String, String)>, text: &str) {
+    if let Some((property, value)) = text.split_once(':') {
+        let value = value.trim();
+        let value = value.strip_suffix("!important").unwrap_or(value).trim();
+        declarations.push((property.trim().to_ascii_lowercase(), value.to_string()));
+    }
+}
+
+/// Removes the quotes and the CSS escapes of a value: `"\#\,\#\#0\.00"` is `#,##0.00` and `\0022` is `"`
+fn css_unescape(value: &str) -> String {
+    let value = value.trim();
+    let value = ['"', '\'']
+        .iter()
+        .find_map(|&q| value.strip_prefix(q).and_then(|v| v.strip_suffix(q)))
+        .unwrap_or(value);
+    let chars: Vec<char> = value.chars().collect();
+    let mut result = String::with_capacity(value.len());
+    let mut position = 0;
+    while position < chars.len() {
+        if chars[position] == '\\' && position + 1 < chars.len() {
+            let hex: String = chars[position + 1..]
+                .iter()
+                .take(6)
+                .take_while(|c| c.is_ascii_hexdigit())
+                .collect();
+            if hex.is_empty() {
+                result.push(chars[position + 1]);
+                position += 2;
+            } else {
+                if let Some(c) = u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                    result.push(c);
+                }
+                position += 1 + hex.len();
+                // A space ends the escape
+                if chars.get(position) == Some(&' ') {
+                    position += 1;
+                }
+            }
+        } else {
+            result.push(chars[position]);
+            position += 1;
+        }
+    }
+    result
+}
+
+/// Quotes a value for CSS, the way Excel writes `mso-number-format`
+fn css_quote(value: &str) -> String {
+    let mut result = String::from("\"");
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => {
+                result.push_str("\\0022");
+                if chars
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_hexdigit() || *c == ' ')
+                {
+                    result.push(' ');
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+/// Splits a value like `1px solid rgb(0, 0, 0)` in its parts
+fn css_values(value: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+    for c in value.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if c.is_whitespace() && depth == 0 {
+            if !current.is_empty() {
+                values.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        values.push(current);
+    }
+    values
+}
+
+/// Parses a CSS color. `Some(Color::None)` is a transparent color.
+fn parse_color(value: &str) -> Option<Color> {
+    let value = value.trim().to_ascii_lowercase();
+    let rgb = |r: u8, g: u8, b: u8| Color::Rgb(format!("#{r:02X}{g:02X}{b:02X}"));
+    if let Some(hex) = value.strip_prefix('#') {
+        let digits: Vec<u8> = hex
+            .chars()
+            .filter_map(|c| c.to_digit(16).map(|d| d as u8))
+            .collect();
+        if digits.len() != hex.len() {
+            return None;
+        }
+        return match digits[..] {
+            [r1, r2, g1, g2, b1, b2] => Some(rgb(r1 * 16 + r2, g1 * 16 + g2, b1 * 16 + b2)),
+            [r, g, b] => Some(rgb(r * 17, g * 17, b * 17)),
+            _ => None,
+        };
+    }
+    if let Some(arguments) = value
+        .strip_prefix("rgba(")
+        .or_else(|| value.strip_prefix("rgb("))
+        .and_then(|v| v.strip_suffix(')'))
+    {
+        let parts: Vec<&str> = arguments
+            .split([',', ' ', '/'])
+            .filter(|p| !p.is_empty())
+            .collect();
+        if parts.len() == 4 && parts[3].parse::<f64>().ok() == Some(0.0) {
+            return Some(Color::None);
+        }
+        let channel = |part: &str| {
+            part.parse::<f64>()
+                .ok()
+                .map(|v| v.round().clamp(0.0, 255.0) as u8)
+        };
+        return match parts[..] {
+            [r, g, b, ..] => Some(rgb(channel(r)?, channel(g)?, channel(b)?)),
+            _ => None,
+        };
+    }
+    let hex = match value.as_str() {
+        "transparent" | "none" => return Some(Color::None),
+        "black" | "windowtext" => "#000000",
+        "white" | "window" => "#FFFFFF",
+        "red" => "#FF0000",
+        "lime" => "#00FF00",
+        "green" => "#008000",
+        "blue" => "#0000FF",
+        "yellow" => "#FFFF00",
+        "cyan" | "aqua" => "#00FFFF",
+        "magenta" | "fuchsia" => "#FF00FF",
+        "gray" | "grey" => "#808080",
+        "silver" => "#C0C0C0",
+        "maroon" => "#800000",
+        "olive" => "#808000",
+        "purple" => "#800080",
+        "teal" => "#008080",
+        "navy" => "#000080",
+        "orange" => "#FFA500",
+        _ => return None,
+    };
+    Some(Color::Rgb(hex.to_string()))
+}
+
+/// A length in points, `11pt`, `14px` or just `11`
+fn parse_points(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if let Some(pixels) = value.strip_suffix("px") {
+        return pixels.trim().parse::<f64>().ok().map(|px| px * 0.75);
+    }
+    value
+        .strip_suffix("pt")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Parses a border like `.5pt solid windowtext` (Excel) or `1px solid #000000` (Google Sheets)
+fn parse_border(value: &str) -> Option<BorderItem> {
+    let mut line = None;
+    // 1 thin, 2 medium and 3 thick
+    let mut weight = 1;
+    let mut color = Color::None;
+    for part in css_values(&value.to_ascii_lowercase()) {
+        match part.as_str() {
+            "none" | "hidden" => return None,
+            "solid" | "dashed" | "dotted" | "double" | "groove" | "ridge" | "inset" | "outset" => {
+                line = Some(part)
+            }
+            "thin" => weight = 1,
+            "medium" => weight = 2,
+            "thick" => weight = 3,
+            _ => {
+                if let Some(pixels) = part.strip_suffix("px").and_then(|p| p.parse::<f64>().ok()) {
+                    weight = if pixels <= 1.5 {
+                        1
+                    } else if pixels <= 2.5 {
+                        2
+                    } else {
+                        3
+                    };
+                } else if let Some(points) =
+                    part.strip_suffix("pt").and_then(|p| p.parse::<f64>().ok())
+                {
+                    // Excel writes thin borders as .5pt, medium as 1pt and thick as 1.5pt
+                    weight = if points <= 0.5 {
+                        1
+                    } else if points <= 1.0 {
+                        2
+                    } else {
+                        3
+                    };
+                } else if let Some(c) = parse_color(&part) {
+                    color = c;
+                }
+            }
+        }
+    }
+    let style = match line?.as_str() {
+        "double" => BorderStyle::Double,
+        "dotted" => BorderStyle::Dotted,
+        "dashed" => BorderStyle::MediumDashed,
+        _ => match weight {
+            1 => BorderStyle::Thin,
+            2 => BorderStyle::Medium,
+            _ => BorderStyle::Thick,
+        },
+    };
+    Some(BorderItem { style, color })
+}
+
+/// The number format of `mso-number-format`, Excel uses names for some of them
+fn parse_number_format(value: &str) -> String {
+    let format = css_unescape(value);
+    let named = match format.to_ascii_lowercase().as_str() {
+        "general" | "standard" => "general",
+        "fixed" => "0.00",
+        "percent" => "0.00%",
+        "scientific" => "0.00E+00",
+        "short date" => "m/d/yyyy",
+        "medium date" => "d-mmm-yy",
+        "long date" => "dddd, mmmm d, yyyy",
+        "short time" => "h:mm",
+        "medium time" => "h:mm AM/PM",
+        "long time" => "h:mm:ss",
+        _ => return format,
+    };
+    named.to_string()
+}
+
+/// Computes the style of the declarations, later declarations take precedence.
+/// Returns the number format apart, the style keeps the default one.
+fn declarations_to_style(declarations: &[(String, String)]) -> (Style, Option<String>) {
+    let mut style = Style::default();
+    let mut num_fmt = None;
+    let mut alignment = Alignment::default();
+    for (property, value) in declarations {
+        let lower = value.to_ascii_lowercase();
+        let font = &mut style.font;
+        match property.as_str() {
+            "font-weight" => {
+                font.b = lower == "bold"
+                    || lower == "bolder"
+                    || lower.parse::<i32>().is_ok_and(|weight| weight >= 600);
+            }
+            "font-style" => font.i = lower == "italic" || lower == "oblique",
+            "text-decoration" | "text-decoration-line" => {
+                if lower == "none" {
+                    font.u = false;
+                    font.strike = false;
+                }
+                font.u |= lower.contains("underline");
+                font.strike |= lower.contains("line-through");
+            }
+            "text-underline-style" => font.u = lower != "none",
+            "color" => {
+                if let Some(color) = parse_color(&lower) {
+                    font.color = color;
+                }
+            }
+            "font-size" => {
+                if let Some(points) = parse_points(&lower) {
+                    font.sz = (points.round() as i32).max(1);
+                }
+            }
+            "font-family" => {
+                let family = value.split(',').next().unwrap_or_default();
+                let name = css_unescape(family);
+                if !name.is_empty() {
+                    font.name = name;
+                    font.scheme = FontScheme::None;
+                }
+            }
+            "background" | "background-color" => {
+                if let Some(color) = css_values(&lower).iter().find_map(|v| parse_color(v)) {
+                    style.fill = Fill {
+                        color,
+                        ..Default::default()
+                    };
+                }
+            }
+            "text-align" => {
+                alignment.horizontal = match lower.as_str() {
+                    "left" | "start" => HorizontalAlignment::Left,
+                    "center" => HorizontalAlignment::Center,
+                    "right" | "end" => HorizontalAlignment::Right,
+                    "justify" => HorizontalAlignment::Justify,
+                    "general" => HorizontalAlignment::General,
+                    _ => continue,
+                };
+            }
+            "vertical-align" => {
+                alignment.vertical = match lower.as_str() {
+                    "top" | "text-top" => VerticalAlignment::Top,
+                    "middle" => VerticalAlignment::Center,
+                    "bottom" | "text-bottom" | "baseline" => VerticalAlignment::Bottom,
+                    _ => continue,
+                };
+            }
+            "white-space" => {
+                alignment.wrap_text = matches!(
+                    lower.as_str(),
+                    "normal" | "pre-wrap" | "pre-line" | "break-spaces"
+                );
+            }
+            "border" => {
+                let item = parse_border(&lower);
+                let border = &mut style.border;
+                border.top = item.clone();
+                border.right = item.clone();
+                border.bottom = item.clone();
+                border.left = item;
+            }
+            "border-top" => style.border.top = parse_border(&lower),
+            "border-right" => style.border.right = parse_border(&lower),
+            "border-bottom" => style.border.bottom = parse_border(&lower),
+            "border-left" => style.border.left = parse_border(&lower),
+            "mso-number-format" => num_fmt = Some(parse_number_format(value)),
+            _ => {}
+        }
+    }
+    if alignment != Alignment::default() {
+        style.alignment = Some(alignment);
+    }
+    (style, num_fmt)
+}
+
+/// The CSS of a cell with the style, for the `style` attribute of its `<td>`
+pub(crate) fn style_to_css(style: &Style, theme: &Theme) -> String {
+    let mut css = Vec::new();
+    let font = &style.font;
+    if font.b {
+        css.push("font-weight:bold".to_string());
+    }
+    if font.i {
+        css.push("font-style:italic".to_string());
+    }
+    match (font.u, font.strike) {
+        (true, true) => css.push("text-decoration:underline line-through".to_string()),
+        (true, false) => css.push("text-decoration:underline".to_string()),
+        (false, true) => css.push("text-decoration:line-through".to_string()),
+        (false, false) => {}
+    }
+    let color = font.color.to_rgb(theme);
+    if !color.is_empty() {
+        css.push(format!("color:{color}"));
+    }
+    css.push(format!("font-size:{}pt", font.sz));
+    css.push(format!("font-family:{}", css_quote(&font.name)));
+    if style.fill.pattern_type != PatternType::None {
+        let background = style.fill.color.to_rgb(theme);
+        if !background.is_empty() {
+            css.push(format!("background-color:{background}"));
+        }
+    }
+    let alignment = style.alignment.clone().unwrap_or_default();
+    let horizontal = match alignment.horizontal {
+        HorizontalAlignment::Left => Some("left"),
+        HorizontalAlignment::Center | HorizontalAlignment::CenterContinuous => Some("center"),
+        HorizontalAlignment::Right => Some("right"),
+        HorizontalAlignment::Justify | HorizontalAlignment::Distributed => Some("justify"),
+        HorizontalAlignment::General | HorizontalAlignment::Fill => None,
+    };
+    if let Some(horizontal) = horizontal {
+        css.push(format!("text-align:{horizontal}"));
+    }
+    let vertical = match alignment.vertical {
+        VerticalAlignment::Top => "top",
+        VerticalAlignment::Center => "middle",
+        VerticalAlignment::Bottom | VerticalAlignment::Distributed | VerticalAlignment::Justify => {
+            "bottom"
+        }
+    };
+    css.push(format!("vertical-align:{vertical}"));
+    if alignment.wrap_text {
+        css.push("white-space:normal".to_string());
+    } else {
+        css.push("white-space:nowrap".to_string());
+    }
+    let border = &style.border;
+    for (side, item) in [
+        ("top", &border.top),
+        ("right", &border.right),
+        ("bottom", &border.bottom),
+        ("left", &border.left),
+    ] {
+        if let Some(item) = item {
+            let (width, line) = match item.style {
+                BorderStyle::Thin => ("1px", "solid"),
+                BorderStyle::Medium => ("2px", "solid"),
+                BorderStyle::Thick => ("3px", "solid"),
+                BorderStyle::Double => ("3px", "double"),
+                BorderStyle::Dotted => ("1px", "dotted"),
+                BorderStyle::SlantDashDot
+                | BorderStyle::MediumDashed
+                | BorderStyle::MediumDashDotDot
+                | BorderStyle::MediumDashDot => ("2px", "dashed"),
+            };
+            let color = item.color.to_rgb(theme);
+            let color = if color.is_empty() { "#000000" } else { &color };
+            css.push(format!("border-{side}:{width} {line} {color}"));
+        }
+    }
+    if style.num_fmt != "general" {
+        css.push(format!("mso-number-format:{}", css_quote(&style.num_fmt)));
+    }
+    css.join(";")
+}
+
+/// Returns the value of a field of a JSON object like the `data-sheets-value` of Google Sheets,
+/// `{"1":3,"3":42}`. Strings are unquoted, other values are returned as they are written.
+pub(crate) fn json_field(json: &str, key: &str) -> Option<String> {
+    let chars: Vec<char> = json.trim().chars().collect();
+    if chars.first() != Some(&'{') {
+        return None;
+    }
+    let skip_spaces = |mut position: usize| {
+        while chars.get(position).is_some_and(|c| c.is_whitespace()) {
+            position += 1;
+        }
+        position
+    };
+    let mut position = 1;
+    loop {
+        position = skip_spaces(position);
+        let (name, end) = read_json_string(&chars, position)?;
+        position = skip_spaces(end);
+        if chars.get(position) != Some(&':') {
+            return None;
+        }
+        position = skip_spaces(position + 1);
+        let (value, end) = if chars.get(position) == Some(&'"') {
+            read_json_string(&chars, position)?
+        } else {
+            let start = position;
+            let mut depth = 0;
+            let mut in_string = false;
+            while let Some(&c) = chars.get(position) {
+                match c {
+                    '\\' if in_string => position += 1,
+                    '"' => in_string = !in_string,
+                    '{' | '[' if !in_string => depth += 1,
+                    '}' | ']' if !in_string && depth > 0 => depth -= 1,
+                    ',' | '}' if !in_string && depth == 0 => break,
+                    _ => {}
+                }
+                position += 1;
+            }
+            let value: String = chars[start..position].iter().collect();
+            (value.trim().to_string(), position)
+        };
+        if name == key {
+            return Some(value);
+        }
+        position = skip_spaces(end);
+        if chars.get(position) != Some(&',') {
+            return None;
+        }
+        position += 1;
+    }
+}
+
+/// Reads the JSON string that starts at `position` and returns it with the position after it
+fn read_json_string(chars: &[char], position: usize) -> Option<(String, usize)> {
+    if chars.get(position) != Some(&'"') {
+        return None;
+    }
+    let mut result = String::new();
+    let mut position = position + 1;
+    loop {
+        let c = *chars.get(position)?;
+        position += 1;
+        match c {
+            '"' => return Some((result, position)),
+            '\\' => {
+                let escaped = *chars.get(position)?;
+                position += 1;
+                match escaped {
+                    'n' => result.push('\n'),
+                    't' => result.push('\t'),
+                    'r' => result.push('\r'),
+                    'b' => result.push('\u{8}'),
+                    'f' => result.push('\u{c}'),
+                    'u' => {
+                        let hex: String = chars.get(position..position + 4)?.iter().collect();
+                        result.push(
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)?,
+                        );
+                        position += 4;
+                    }
+                    _ => result.push(escaped),
+                }
+            }
+            _ => result.push(c),
+        }
+    }
+}
+
+/// Writes the text as a JSON string
+pub(crate) fn json_string(text: &str) -> String {
+    let mut result = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+            _ => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
@@ -4,9 +4,12 @@ mod autofill;
 mod border;
 mod border_utils;
 mod clipboard;
+pub mod collaboration;
 mod common;
 mod conditional_formatting;
+mod find;
 pub(crate) mod history;
+mod html;
 mod named_cell_styles;
 mod sequence_detector;
 mod ui;
@@ -17,5 +20,9 @@ pub use common::UserModel;
 #[cfg(test)]
 pub use ui::SelectedView;
 
-pub use clipboard::ClipboardData;
+pub use clipboard::{
+    ClipboardData, PasteMode, PasteOperation, PasteSpecialOptions, PortableCell, PortableClipboard,
+    PortableDefinedName, PortableReferences, PortableValue,
+};
 pub use common::BorderArea;
+pub use find::{FindHit, FindLookIn, FindOptions, FindOrder, FindScope};
@@ -138,6 +138,49 @@ impl<'a> UserModel<'a> {
                 } => {
                     self.model.set_row_hidden(*sheet, *row, *old_value)?;
                 }
+                Diff::SetColumnsOutlineLevel {
+                    sheet,
+                    column,
+                    count,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model
+                        .workbook
+                        .worksheet_mut(*sheet)?
+                        .change_columns_outline_level(*column, *column + *count - 1, |_| {
+                            *old_value
+                        })?;
+                }
+                Diff::SetColumnCollapsed {
+                    sheet,
+                    column,
+                    new_value: _,
+                    old_value,
+                } => {
+                    self.model
+                        .set_column_collapsed(*sheet, *column, *old_value)?;
+                }
+                Diff::SetRowsOutlineLevel {
+                    sheet,
+                    row,
+                    count,
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model
+                        .workbook
+                        .worksheet_mut(*sheet)?
+                        .change_rows_outline_level(*row, *row + *count - 1, |_| *old_value)?;
+                }
+                Diff::SetRowCollapsed {
+                    sheet,
+                    row,
+                    new_value: _,
+                    old_value,
+                } => {
+                    self.model.set_row_collapsed(*sheet, *row, *old_value)?;
+                }
                 Diff::SetRowHeight {
                     sheet,
                     row,
@@ -240,6 +283,7 @@ impl<'a> UserModel<'a> {
                     row,
                     count: _,
                     old_data,
+                    old_scenarios,
                 } => {
                     needs_evaluation = true;
                     self.model
@@ -252,6 +296,7 @@ impl<'a> UserModel<'a> {
                         }
                         worksheet.sheet_data.insert(r, row_data.data.clone());
                     }
+                    worksheet.scenarios = old_scenarios.clone();
                 }
                 Diff::InsertColumns {
                     sheet,
@@ -266,6 +311,7 @@ impl<'a> UserModel<'a> {
                     column,
                     count: _,
                     old_data,
+                    old_scenarios,
                 } => {
                     needs_evaluation = true;
                     self.model
@@ -281,8 +327,60 @@ impl<'a> UserModel<'a> {
                             let style = col.style;
                             let hidden = col.hidden;
                             worksheet.set_column_width_and_style(c, width, hidden, style)?;
+                            worksheet.set_column_outline_level(c, col.outline_level)?;
+                            worksheet.set_column_collapsed(c, col.collapsed)?;
                         }
                     }
+                    worksheet.scenarios = old_scenarios.clone();
+                }
+                Diff::InsertCells {
+                    sheet,
+                    row,
+                    column,
+                    width,
+                    height,
+                    direction,
+                } => {
+                    let area = Area {
+                        sheet: *sheet,
+                        row: *row,
+                        column: *column,
+                        width: *width,
+                        height: *height,
+                    };
+                    self.model.delete_cells(&area, direction.opposite())?;
+                    needs_evaluation = true;
+                }
+                Diff::DeleteCells {
+                    sheet,
+                    row,
+                    column,
+                    width,
+                    height,
+                    direction,
+                    old_value,
+                    old_merges,
+                    old_scenarios,
+                } => {
+                    needs_evaluation = true;
+                    let area = Area {
+                        sheet: *sheet,
+                        row: *row,
+                        column: *column,
+                        width: *width,
+                        height: *height,
+                    };
+                    self.model.insert_cells(&area, direction.opposite())?;
+                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
+                    for (r, row_data) in (*row..).zip(old_value) {
+                        for (c, cell) in (*column..).zip(row_data) {
+                            if let Some(cell) = cell {
+                                worksheet.update_cell(r, c, cell.clone())?;
+                            }
+                        }
+                    }
+                    worksheet.merge_cells.extend(old_merges.iter().cloned());
+                    worksheet.scenarios = old_scenarios.clone();
                 }
                 Diff::SetFrozenRowsCount {
                     sheet,
@@ -307,6 +405,34 @@ impl<'a> UserModel<'a> {
                 } => {
                     self.model.rename_sheet_by_index(*index, old_value)?;
                 }
+                Diff::DuplicateSheet {
+                    sheet: _,
+                    index,
+                    name: _,
+                } => {
+                    // Removes the copies of the local defined names and tables as well
+                    let worksheet = self.model.workbook.worksheet(*index)?;
+                    let sheet_id = worksheet.sheet_id;
+                    let sheet_name = worksheet.get_name();
+                    self.model
+                        .workbook
+                        .defined_names
+                        .retain(|defined_name| defined_name.sheet_id != Some(sheet_id));
+                    self.model
+                        .workbook
+                        .tables
+                        .retain(|_, table| table.sheet_name != sheet_name);
+                    self.model
+                        .parser
+                        .set_tables(self.model.workbook.tables.clone());
+                    self.model.delete_sheet(*index)?;
+                    if *index > 0 {
+                        self.set_selected_sheet(*index - 1)?;
+                    }
+                }
+                Diff::MoveSheet { from, to } => {
+                    self.model.move_sheet(*to, *from)?;
+                }
                 Diff::SetSheetColor {
                     index,
                     old_value,
@@ -403,6 +529,7 @@ impl<'a> UserModel<'a> {
                     worksheet.color = old_data.color.clone();
                     worksheet.merge_cells = old_data.merge_cells.clone();
                     worksheet.shared_formulas = old_data.shared_formulas.clone();
+                    worksheet.scenarios = old_data.scenarios.clone();
                     self.model.reset_parsed_structures();
 
                     self.set_selected_sheet(sheet_index)?;
@@ -488,6 +615,29 @@ impl<'a> UserModel<'a> {
                 } => {
                     self.model.set_timezone(old_value)?;
                 }
+                Diff::SetDate1904 {
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_date1904(*old_value);
+                }
+                Diff::SetR1C1 {
+                    old_value,
+                    new_value: _,
+                } => {
+                    self.model.set_r1c1(*old_value);
+                }
+                Diff::RebaseDateSerial {
+                    sheet,
+                    row,
+                    column,
+                    old_value,
+                    new_value: _,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .update_cell_with_number(*sheet, *row, *column, *old_value)?;
+                }
                 Diff::CreateNamedStyle { name, xf_id: _ } => {
                     self.model.workbook.styles.delete_named_style_entry(name)?;
                 }
@@ -579,6 +729,35 @@ impl<'a> UserModel<'a> {
                     }
                     needs_evaluation = true;
                 }
+                Diff::AddScenario { sheet, scenario } => {
+                    self.model.delete_scenario(*sheet, &scenario.name)?;
+                }
+                Diff::DeleteScenario {
+                    sheet,
+                    index,
+                    old_value,
+                } => {
+                    let scenarios = &mut self.model.workbook.worksheet_mut(*sheet)?.scenarios;
+                    let index = (*index as usize).min(scenarios.len());
+                    scenarios.insert(index, *old_value.clone());
+                }
+                Diff::UpdateScenario {
+                    sheet,
+                    old_value,
+                    new_value,
+                    ..
+                } => {
+                    self.model
+                        .update_scenario(*sheet, &new_value.name, *old_value.clone())?;
+                }
+                Diff::MergeCells { sheet, range } => {
+                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
+                    worksheet.merge_cells.retain(|merged| merged != range);
+                }
+                Diff::UnmergeCells { sheet, range } => {
+                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
+                    worksheet.merge_cells.push(range.clone());
+                }
             }
         }
         if needs_evaluation {
@@ -601,7 +780,7 @@ impl<'a> UserModel<'a> {
                 } => {
                     needs_evaluation = true;
                     self.model
-                        .set_user_input(*sheet, *row, *column, new_value.to_string())?;
+                        .set_a1_user_input(*sheet, *row, *column, new_value.to_string())?;
                 }
                 Diff::SetArrayValue {
                     sheet,
@@ -613,7 +792,7 @@ impl<'a> UserModel<'a> {
                     old_values: _,
                 } => {
                     needs_evaluation = true;
-                    self.model.set_user_array_formula(
+                    self.model.set_a1_user_array_formula(
                         *sheet, *row, *column, *width, *height, new_value,
                     )?;
                 }
@@ -641,6 +820,49 @@ impl<'a> UserModel<'a> {
                 } => {
                     self.model.set_row_hidden(*sheet, *row, *new_value)?;
                 }
+                Diff::SetColumnsOutlineLevel {
+                    sheet,
+                    column,
+                    count,
+                    new_value,
+                    old_value: _,
+                } => {
+                    self.model
+                        .workbook
+                        .worksheet_mut(*sheet)?
+                        .change_columns_outline_level(*column, *column + *count - 1, |_| {
+                            *new_value
+                        })?;
+                }
+                Diff::SetColumnCollapsed {
+                    sheet,
+                    column,
+                    new_value,
+                    old_value: _,
+                } => {
+                    self.model
+                        .set_column_collapsed(*sheet, *column, *new_value)?;
+                }
+                Diff::SetRowsOutlineLevel {
+                    sheet,
+                    row,
+                    count,
+                    new_value,
+                    old_value: _,
+                } => {
+                    self.model
+                        .workbook
+                        .worksheet_mut(*sheet)?
+                        .change_rows_outline_level(*row, *row + *count - 1, |_| *new_value)?;
+                }
+                Diff::SetRowCollapsed {
+                    sheet,
+                    row,
+                    new_value,
+                    old_value: _,
+                } => {
+                    self.model.set_row_collapsed(*sheet, *row, *new_value)?;
+                }
                 Diff::SetRowHeight {
                     sheet,
                     row,
@@ -700,10 +922,7 @@ impl<'a> UserModel<'a> {
                     needs_evaluation = true;
                 }
                 Diff::DeleteRows {
-                    sheet,
-                    row,
-                    count,
-                    old_data: _,
+                    sheet, row, count, ..
                 } => {
                     self.model.delete_rows(*sheet, *row, *count)?;
                     needs_evaluation = true;
@@ -720,11 +939,48 @@ impl<'a> UserModel<'a> {
                     sheet,
                     column,
                     count,
-                    old_data: _,
+                    ..
                 } => {
                     self.model.delete_columns(*sheet, *column, *count)?;
                     needs_evaluation = true;
                 }
+                Diff::InsertCells {
+                    sheet,
+                    row,
+                    column,
+                    width,
+                    height,
+                    direction,
+                } => {
+                    let area = Area {
+                        sheet: *sheet,
+                        row: *row,
+                        column: *column,
+                        width: *width,
+                        height: *height,
+                    };
+                    self.model.insert_cells(&area, *direction)?;
+                    needs_evaluation = true;
+                }
+                Diff::DeleteCells {
+                    sheet,
+                    row,
+                    column,
+                    width,
+                    height,
+                    direction,
+                    ..
+                } => {
+                    let area = Area {
+                        sheet: *sheet,
+                        row: *row,
+                        column: *column,
+                        width: *width,
+                        height: *height,
+                    };
+                    self.model.delete_cells(&area, *direction)?;
+                    needs_evaluation = true;
+                }
                 Diff::SetFrozenRowsCount {
                     sheet,
                     new_value,
@@ -752,6 +1008,13 @@ impl<'a> UserModel<'a> {
                 } => {
                     self.model.rename_sheet_by_index(*index, new_value)?;
                 }
+                Diff::DuplicateSheet { sheet, index, name } => {
+                    self.model.duplicate_sheet(*sheet, name, *index)?;
+                    self.set_selected_sheet(*index)?;
+                }
+                Diff::MoveSheet { from, to } => {
+                    self.model.move_sheet(*from, *to)?;
+                }
                 Diff::SetSheetColor {
                     index,
                     old_value: _,
@@ -872,6 +1135,29 @@ impl<'a> UserModel<'a> {
                 } => {
                     self.model.set_timezone(new_value)?;
                 }
+                Diff::SetDate1904 {
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_date1904(*new_value);
+                }
+                Diff::SetR1C1 {
+                    old_value: _,
+                    new_value,
+                } => {
+                    self.model.set_r1c1(*new_value);
+                }
+                Diff::RebaseDateSerial {
+                    sheet,
+                    row,
+                    column,
+                    old_value: _,
+                    new_value,
+                } => {
+                    needs_evaluation = true;
+                    self.model
+                        .update_cell_with_number(*sheet, *row, *column, *new_value)?;
+                }
                 Diff::CreateNamedStyle { name, xf_id } => {
                     self.model
                         .workbook
@@ -956,6 +1242,31 @@ impl<'a> UserModel<'a> {
                     }
                     needs_evaluation = true;
                 }
+                Diff::AddScenario { sheet, scenario } => {
+                    self.model.add_scenario(*sheet, *scenario.clone())?;
+                }
+                Diff::DeleteScenario {
+                    sheet, old_value, ..
+                } => {
+                    self.model.delete_scenario(*sheet, &old_value.name)?;
+                }
+                Diff::UpdateScenario {
+                    sheet,
+                    old_value,
+                    new_value,
+                    ..
+                } => {
+                    self.model
+                        .update_scenario(*sheet, &old_value.name, *new_value.clone())?;
+                }
+                Diff::MergeCells { sheet, range } => {
+                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
+                    worksheet.merge_cells.push(range.clone());
+                }
+                Diff::UnmergeCells { sheet, range } => {
+                    let worksheet = self.model.workbook.worksheet_mut(*sheet)?;
+                    worksheet.merge_cells.retain(|merged| merged != range);
+                }
             }
         }
 
@@ -2,9 +2,10 @@ use crate::constants::{self, LAST_COLUMN, LAST_ROW};
 use crate::expressions::types::CellReferenceIndex;
 use crate::expressions::utils::{is_valid_column_number, is_valid_row};
 use crate::model::CellStructure;
+use crate::outline::{get_outline_groups, get_outline_runs, OutlineGroup, MAX_OUTLINE_LEVEL};
 use crate::{expressions::token::Error, types::*};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug, PartialEq, Eq)]
 pub struct WorksheetDimension {
@@ -112,6 +113,8 @@ impl Worksheet {
             custom_width: false,
             style: Some(style_index),
             hidden: false,
+            outline_level: 0,
+            collapsed: false,
         }];
         Ok(())
     }
@@ -141,6 +144,8 @@ impl Worksheet {
             custom_height: false,
             s: style_index,
             hidden: false,
+            outline_level: 0,
+            collapsed: false,
         });
         Ok(())
     }
@@ -196,6 +201,8 @@ impl Worksheet {
                 custom_width,
                 style: cols[index].style,
                 hidden: cols[index].hidden,
+                outline_level: cols[index].outline_level,
+                collapsed: cols[index].collapsed,
             };
             let col = Col {
                 min: column,
@@ -204,6 +211,8 @@ impl Worksheet {
                 custom_width,
                 style: None,
                 hidden: false,
+                outline_level: cols[index].outline_level,
+                collapsed: cols[index].collapsed,
             };
             let post = Col {
                 min: column + 1,
@@ -212,12 +221,14 @@ impl Worksheet {
                 custom_width,
                 style: cols[index].style,
                 hidden: cols[index].hidden,
+                outline_level: cols[index].outline_level,
+                collapsed: cols[index].collapsed,
             };
             cols.remove(index);
             if column != max {
                 cols.insert(index, post);
             }
-            if custom_width {
+            if custom_width || col.outline_level != 0 || col.collapsed {
                 cols.insert(index, col);
             }
             if column != min {
@@ -397,6 +408,8 @@ impl Worksheet {
             custom_height: false,
             s: 0,
             hidden,
+            outline_level: 0,
+            collapsed: false,
         });
         Ok(())
     }
@@ -429,6 +442,8 @@ impl Worksheet {
             custom_height: true,
             s: 0,
             hidden,
+            outline_level: 0,
+            collapsed: false,
         });
         Ok(())
     }
@@ -473,6 +488,8 @@ impl Worksheet {
             custom_width: width != constants::DEFAULT_COLUMN_WIDTH,
             style,
             hidden,
+            outline_level: 0,
+            collapsed: false,
         };
         let mut index = 0;
         let mut split = false;
@@ -506,6 +523,8 @@ impl Worksheet {
                 custom_width: cols[index].custom_width,
                 style: cols[index].style,
                 hidden: cols[index].hidden,
+                outline_level: cols[index].outline_level,
+                collapsed: cols[index].collapsed,
             };
             let post = Col {
                 min: column + 1,
@@ -514,8 +533,12 @@ impl Worksheet {
                 custom_width: cols[index].custom_width,
                 style: cols[index].style,
                 hidden: cols[index].hidden,
+                outline_level: cols[index].outline_level,
+                collapsed: cols[index].collapsed,
             };
             col.style = cols[index].style;
+            col.outline_level = cols[index].outline_level;
+            col.collapsed = cols[index].collapsed;
             cols.remove(index);
             if column != max {
                 cols.insert(index, post);
@@ -604,6 +627,336 @@ impl Worksheet {
         Ok(false)
     }
 
+    /// Returns the outline level of a row, 0 if it is not grouped
+    pub fn get_row_outline_level(&self, row: i32) -> Result<i32, String> {
+        if !is_valid_row(row) {
+            return Err(format!("Row number '{row}' is not valid."));
+        }
+        Ok(self
+            .rows
+            .iter()
+            .find(|r| r.r == row)
+            .map_or(0, |r| r.outline_level))
+    }
+
+    /// Returns true if the row is the summary row of a collapsed group
+    pub fn is_row_collapsed(&self, row: i32) -> Result<bool, String> {
+        if !is_valid_row(row) {
+            return Err(format!("Row number '{row}' is not valid."));
+        }
+        Ok(self.rows.iter().any(|r| r.r == row && r.collapsed))
+    }
+
+    /// Changes the outline level of a row.
+    ///
+    /// Fails if the row index is outside allowed range or the level is not in 0..=MAX_OUTLINE_LEVEL.
+    pub fn set_row_outline_level(&mut self, row: i32, outline_level: i32) -> Result<(), String> {
+        if !is_valid_row(row) {
+            return Err(format!("Row number '{row}' is not valid."));
+        }
+        if !(0..=MAX_OUTLINE_LEVEL).contains(&outline_level) {
+            return Err(format!("Invalid outline level: '{outline_level}'."));
+        }
+        if let Some(r) = self.rows.iter_mut().find(|r| r.r == row) {
+            r.outline_level = outline_level;
+            return Ok(());
+        }
+        if outline_level != 0 {
+            self.rows.push(Row {
+                height: constants::DEFAULT_ROW_HEIGHT / constants::ROW_HEIGHT_FACTOR,
+                r: row,
+                custom_format: false,
+                custom_height: false,
+                s: 0,
+                hidden: false,
+                outline_level,
+                collapsed: false,
+            });
+        }
+        Ok(())
+    }
+
+    /// Changes the collapsed flag of a row
+    pub fn set_row_collapsed(&mut self, row: i32, collapsed: bool) -> Result<(), String> {
+        if !is_valid_row(row) {
+            return Err(format!("Row number '{row}' is not valid."));
+        }
+        if let Some(r) = self.rows.iter_mut().find(|r| r.r == row) {
+            r.collapsed = collapsed;
+            return Ok(());
+        }
+        if collapsed {
+            self.rows.push(Row {
+                height: constants::DEFAULT_ROW_HEIGHT / constants::ROW_HEIGHT_FACTOR,
+                r: row,
+                custom_format: false,
+                custom_height: false,
+                s: 0,
+                hidden: false,
+                outline_level: 0,
+                collapsed,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the outline level of a column, 0 if it is not grouped
+    pub fn get_column_outline_level(&self, column: i32) -> Result<i32, String> {
+        if !is_valid_column_number(column) {
+            return Err(format!("Column number '{column}' is not valid."));
+        }
+        Ok(self
+            .cols
+            .iter()
+            .find(|c| c.min <= column && column <= c.max)
+            .map_or(0, |c| c.outline_level))
+    }
+
+    /// Returns true if the column is a summary column of a collapsed group
+    pub fn is_column_collapsed(&self, column: i32) -> Result<bool, String> {
+        if !is_valid_column_number(column) {
+            return Err(format!("Column number '{column}' is not valid."));
+        }
+        Ok(self
+            .cols
+            .iter()
+            .any(|c| c.min <= column && column <= c.max && c.collapsed))
+    }
+
+    // Makes sure there is a column record spanning only `column` and returns its index
+    fn get_single_column_index(&mut self, column: i32) -> Result<usize, String> {
+        let width = self.get_actual_column_width(column)?;
+        let hidden = self.is_column_hidden(column)?;
+        let style = self.get_column_style(column)?;
+        self.set_column_width_and_style(column, width, hidden, style)?;
+        self.cols
+            .iter()
+            .position(|c| c.min == column && c.max == column)
+            .ok_or_else(|| format!("Column number '{column}' is not valid."))
+    }
+
+    /// Changes the outline level of a column.
+    ///
+    /// Fails if the column index is outside allowed range or the level is not in 0..=MAX_OUTLINE_LEVEL.
+    pub fn set_column_outline_level(
+        &mut self,
+        column: i32,
+        outline_level: i32,
+    ) -> Result<(), String> {
+        if !(0..=MAX_OUTLINE_LEVEL).contains(&outline_level) {
+            return Err(format!("Invalid outline level: '{outline_level}'."));
+        }
+        if self.get_column_outline_level(column)? == outline_level {
+            return Ok(());
+        }
+        let index = self.get_single_column_index(column)?;
+        self.cols[index].outline_level = outline_level;
+        Ok(())
+    }
+
+    /// Changes the collapsed flag of a column
+    pub fn set_column_collapsed(&mut self, column: i32, collapsed: bool) -> Result<(), String> {
+        if self.is_column_collapsed(column)? == collapsed {
+            return Ok(());
+        }
+        let index = self.get_single_column_index(column)?;
+        self.cols[index].collapsed = collapsed;
+        Ok(())
+    }
+
+    /// Returns the outline levels of the rows in `row_start..=row_end` as runs of consecutive rows
+    /// with the same level, `(first row, last row, level)`
+    pub fn get_rows_outline_runs(
+        &self,
+        row_start: i32,
+        row_end: i32,
+    ) -> Result<Vec<(i32, i32, i32)>, String> {
+        if !is_valid_row(row_start) || !is_valid_row(row_end) || row_start > row_end {
+            return Err(format!("Invalid row range: {row_start}-{row_end}."));
+        }
+        let mut segments = self
+            .rows
+            .iter()
+            .filter(|r| r.outline_level > 0 && row_start <= r.r && r.r <= row_end)
+            .map(|r| (r.r, r.r, r.outline_level))
+            .collect::<Vec<_>>();
+        segments.sort_unstable();
+        Ok(get_outline_runs(&segments, row_start, row_end))
+    }
+
+    /// Changes the outline level of every row in `row_start..=row_end` in a single pass over the
+    /// rows. `change` takes the current level of a row and returns the new one, which the caller
+    /// makes sure is in 0..=MAX_OUTLINE_LEVEL.
+    pub(crate) fn change_rows_outline_level(
+        &mut self,
+        row_start: i32,
+        row_end: i32,
+        change: impl Fn(i32) -> i32,
+    ) -> Result<(), String> {
+        if !is_valid_row(row_start) || !is_valid_row(row_end) || row_start > row_end {
+            return Err(format!("Invalid row range: {row_start}-{row_end}."));
+        }
+        let mut existing = HashSet::new();
+        for r in self
+            .rows
+            .iter_mut()
+            .filter(|r| row_start <= r.r && r.r <= row_end)
+        {
+            r.outline_level = change(r.outline_level);
+            existing.insert(r.r);
+        }
+        // Rows without a record are at level 0
+        let outline_level = change(0);
+        if outline_level != 0 {
+            for row in (row_start..=row_end).filter(|row| !existing.contains(row)) {
+                self.rows.push(Row {
+                    height: constants::DEFAULT_ROW_HEIGHT / constants::ROW_HEIGHT_FACTOR,
+                    r: row,
+                    custom_format: false,
+                    custom_height: false,
+                    s: 0,
+                    hidden: false,
+                    outline_level,
+                    collapsed: false,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the outline levels of the columns in `column_start..=column_end` as runs of
+    /// consecutive columns with the same level, `(first column, last column, level)`
+    pub fn get_columns_outline_runs(
+        &self,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<Vec<(i32, i32, i32)>, String> {
+        if !is_valid_column_number(column_start)
+            || !is_valid_column_number(column_end)
+            || column_start > column_end
+        {
+            return Err(format!(
+                "Invalid column range: {column_start}-{column_end}."
+            ));
+        }
+        let segments = self
+            .cols
+            .iter()
+            .filter(|c| c.outline_level > 0)
+            .map(|c| (c.min, c.max, c.outline_level))
+            .collect::<Vec<_>>();
+        Ok(get_outline_runs(&segments, column_start, column_end))
+    }
+
+    /// Changes the outline level of every column in `column_start..=column_end` in a single pass
+    /// over the column records, splitting the ones that are partly in the range.
+    /// `change` takes the current level of a column and returns the new one, which the caller
+    /// makes sure is in 0..=MAX_OUTLINE_LEVEL.
+    pub(crate) fn change_columns_outline_level(
+        &mut self,
+        column_start: i32,
+        column_end: i32,
+        change: impl Fn(i32) -> i32,
+    ) -> Result<(), String> {
+        if !is_valid_column_number(column_start)
+            || !is_valid_column_number(column_end)
+            || column_start > column_end
+        {
+            return Err(format!(
+                "Invalid column range: {column_start}-{column_end}."
+            ));
+        }
+        // Columns without a record are at level 0
+        let outline_level = change(0);
+        let gap = |min: i32, max: i32| Col {
+            min,
+            max,
+            width: constants::DEFAULT_COLUMN_WIDTH / constants::COLUMN_WIDTH_FACTOR,
+            custom_width: false,
+            style: None,
+            hidden: false,
+            outline_level,
+            collapsed: false,
+        };
+        let mut cols = Vec::with_capacity(self.cols.len() + 2);
+        // first column of the range that is not written yet
+        let mut next = column_start;
+        for col in std::mem::take(&mut self.cols) {
+            if col.max < column_start || col.min > column_end {
+                if col.min > column_end && next <= column_end {
+                    if outline_level != 0 {
+                        cols.push(gap(next, column_end));
+                    }
+                    next = column_end + 1;
+                }
+                cols.push(col);
+                continue;
+            }
+            if col.min < column_start {
+                cols.push(Col {
+                    max: column_start - 1,
+                    ..col.clone()
+                });
+            }
+            if next < col.min && outline_level != 0 {
+                cols.push(gap(next, col.min - 1));
+            }
+            let max = col.max.min(column_end);
+            cols.push(Col {
+                min: col.min.max(column_start),
+                max,
+                outline_level: change(col.outline_level),
+                ..col.clone()
+            });
+            if col.max > column_end {
+                cols.push(Col {
+                    min: column_end + 1,
+                    ..col
+                });
+            }
+            next = max + 1;
+        }
+        if next <= column_end && outline_level != 0 {
+            cols.push(gap(next, column_end));
+        }
+        self.cols = cols;
+        Ok(())
+    }
+
+    /// Returns the row outline groups of the sheet, sorted by level and start row
+    pub fn get_row_outline_groups(&self) -> Vec<OutlineGroup> {
+        let mut segments = self
+            .rows
+            .iter()
+            .filter(|r| r.outline_level > 0)
+            .map(|r| (r.r, r.r, r.outline_level))
+            .collect::<Vec<_>>();
+        segments.sort_unstable();
+        get_outline_groups(
+            &segments,
+            self.outline_properties.summary_below,
+            LAST_ROW,
+            |row| self.is_row_collapsed(row).unwrap_or(false),
+        )
+    }
+
+    /// Returns the column outline groups of the sheet, sorted by level and start column
+    pub fn get_column_outline_groups(&self) -> Vec<OutlineGroup> {
+        let mut segments = self
+            .cols
+            .iter()
+            .filter(|c| c.outline_level > 0)
+            .map(|c| (c.min, c.max, c.outline_level))
+            .collect::<Vec<_>>();
+        segments.sort_unstable();
+        get_outline_groups(
+            &segments,
+            self.outline_properties.summary_right,
+            LAST_COLUMN,
+            |column| self.is_column_collapsed(column).unwrap_or(false),
+        )
+    }
+
     /// Returns the column style index if present
     pub fn get_column_style(&self, column: i32) -> Result<Option<i32>, String> {
         if !is_valid_column_number(column) {
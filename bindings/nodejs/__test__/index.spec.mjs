@@ -18,3 +18,12 @@ test('Raw API smoke test', (t) => {
   t.is(model.getFormattedCellValue(0, 1, 1), '2');
 });
 
+
+test('Custom functions', (t) => {
+  const model = new Model("Workbook1", "en", "UTC", "en");
+
+  model.registerFunction("DOUBLE", 1, 1, false, (args) => args[0] * 2);
+  model.setUserInput(0, 1, 1, "=DOUBLE(21)");
+  model.evaluate();
+  t.is(model.getFormattedCellValue(0, 1, 1), '42');
+});
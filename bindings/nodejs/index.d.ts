@@ -7,6 +7,11 @@ export declare class Model {
   saveToXlsx(file: string): void
   saveToIcalc(file: string): void
   evaluate(): void
+  setR1C1(r1c1: boolean): void
+  getR1C1(): boolean
+  registerFunction(name: string, minArgs: number, maxArgs: number | undefined | null, volatile: boolean, callback: (arg: any) => any): void
+  unregisterFunction(name: string): void
+  getRegisteredFunctions(): Array<string>
   setUserInput(sheet: number, row: number, column: number, value: string): void
   clearCellContents(sheet: number, row: number, column: number): void
   getCellContent(sheet: number, row: number, column: number): string
@@ -55,6 +60,8 @@ export declare class UserModel {
   hideSheet(sheet: number): void
   unhideSheet(sheet: number): void
   renameSheet(sheet: number, name: string): void
+  duplicateSheet(sheet: number, newName: string, position: number): void
+  moveSheet(from: number, to: number): void
   setSheetColor(sheet: number, color: string): void
   rangeClearAll(sheet: number, startRow: number, startColumn: number, endRow: number, endColumn: number): void
   rangeClearContents(sheet: number, startRow: number, startColumn: number, endRow: number, endColumn: number): void
@@ -63,6 +70,8 @@ export declare class UserModel {
   insertColumns(sheet: number, column: number, columnCount: number): void
   deleteRows(sheet: number, row: number, rowCount: number): void
   deleteColumns(sheet: number, column: number, columnCount: number): void
+  insertCells(area: unknown, direction: unknown): void
+  deleteCells(area: unknown, direction: unknown): void
   setRowsHeight(sheet: number, rowStart: number, rowEnd: number, height: number): void
   setColumnsWidth(sheet: number, columnStart: number, columnEnd: number, width: number): void
   getRowHeight(sheet: number, row: number): number
@@ -105,9 +114,29 @@ export declare class UserModel {
   toBytes(): Array<number>
   getName(): string
   setName(name: string): void
+  setR1C1(r1c1: boolean): void
+  getR1C1(): boolean
+  registerFunction(name: string, minArgs: number, maxArgs: number | undefined | null, volatile: boolean, callback: (arg: any) => any): void
+  unregisterFunction(name: string): void
+  getRegisteredFunctions(): Array<string>
   copyToClipboard(): unknown
   pasteFromClipboard(sourceSheet: number, sourceRange: unknown, clipboard: unknown, isCut: boolean): void
+  pasteSpecial(sourceSheet: number, sourceRange: unknown, clipboard: unknown, options: unknown): void
   pasteCsvText(area: unknown, csv: string): void
+  copyToHtml(): string
+  pasteHtml(area: unknown, html: string): void
+  find(query: string, options: unknown): unknown
+  replaceAll(query: string, replacement: string, options: unknown): number
+  goalSeek(target: unknown, targetValue: number, changing: unknown, options: unknown): unknown
+  createDataTable(area: unknown, rowInput: unknown, columnInput: unknown): void
+  getScenarios(sheet: number): unknown
+  addScenario(sheet: number, scenario: unknown): void
+  updateScenario(sheet: number, name: string, scenario: unknown): void
+  deleteScenario(sheet: number, name: string): void
+  showScenario(sheet: number, name: string): void
+  createScenarioSummary(sheet: number, resultCells: unknown): number
+  copyToPortableClipboard(): unknown
+  pastePortableClipboard(clipboard: unknown, references: unknown): void
   getDefinedNameList(): unknown
   newDefinedName(name: string, scope: number | undefined | null, formula: string): void
   updateDefinedName(name: string, scope: number | undefined | null, newName: string, newScope: number | undefined | null, newFormula: string): void
@@ -0,0 +1,91 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use napi::{bindgen_prelude::*, sys};
+use serde_json::Value;
+
+use ironcalc::base::custom_functions::{CustomFunction, FunctionValue};
+
+/// A JavaScript function called from formulas.
+struct JsCallback {
+  env: sys::napi_env,
+  function: FunctionRef<Value, Value>,
+}
+
+impl JsCallback {
+  fn call(&self, args: &[FunctionValue]) -> FunctionValue {
+    let env = Env::from_raw(self.env);
+    let result = serde_json::to_value(args)
+      .map_err(|e| Error::from_reason(e.to_string()))
+      .and_then(|args| self.function.borrow_back(&env)?.call(args))
+      .and_then(|value| {
+        serde_json::from_value(value).map_err(|e| Error::from_reason(e.to_string()))
+      });
+    // Exceptions and unexpected values are #VALUE! errors
+    result.unwrap_or_else(|_| FunctionValue::Error {
+      error: "#VALUE!".to_string(),
+    })
+  }
+}
+
+thread_local! {
+  /// The JavaScript functions registered in this thread, by id
+  static CALLBACKS: RefCell<HashMap<usize, Rc<JsCallback>>> = RefCell::new(HashMap::new());
+}
+
+/// N-API values can only be used from the JavaScript thread. The function stays in the thread
+/// that registered it and the model holds this handle. Called from any other thread it is a
+/// `#VALUE!` error.
+struct CallbackHandle(usize);
+
+impl CallbackHandle {
+  fn new(callback: JsCallback) -> CallbackHandle {
+    static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    CALLBACKS.with(|callbacks| callbacks.borrow_mut().insert(id, Rc::new(callback)));
+    CallbackHandle(id)
+  }
+
+  fn call(&self, args: &[FunctionValue]) -> FunctionValue {
+    // The function might register or unregister functions, the registry is not borrowed during the call
+    match CALLBACKS.with(|callbacks| callbacks.borrow().get(&self.0).cloned()) {
+      Some(callback) => callback.call(args),
+      None => FunctionValue::Error {
+        error: "#VALUE!".to_string(),
+      },
+    }
+  }
+}
+
+impl Drop for CallbackHandle {
+  fn drop(&mut self) {
+    // Nothing to remove in other threads or once the registry of this thread is gone
+    let _ = CALLBACKS.try_with(|callbacks| callbacks.borrow_mut().remove(&self.0));
+  }
+}
+
+/// The callback receives the list of arguments. Ranges are arrays of rows, empty cells are
+/// `null` and errors are objects like `{ error: "#N/A" }`.
+pub(crate) fn new_custom_function(
+  env: &Env,
+  name: String,
+  min_args: u32,
+  max_args: Option<u32>,
+  volatile: bool,
+  callback: FunctionRef<Value, Value>,
+) -> CustomFunction {
+  let callback = CallbackHandle::new(JsCallback {
+    env: env.raw(),
+    function: callback,
+  });
+  CustomFunction {
+    name,
+    min_args: min_args as usize,
+    max_args: max_args.map(|max| max as usize),
+    volatile,
+    callback: Arc::new(move |args| callback.call(args)),
+  }
+}
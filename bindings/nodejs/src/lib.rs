@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate napi_derive;
 
+mod custom_functions;
 mod model;
 mod user_model;
 
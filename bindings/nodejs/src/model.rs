@@ -2,6 +2,9 @@
 
 use napi::{self, bindgen_prelude::*, Result, Unknown};
 use serde::Serialize;
+use serde_json::Value;
+
+use crate::custom_functions::new_custom_function;
 
 use ironcalc::{
   base::{
@@ -86,6 +89,42 @@ impl Model {
     self.model.evaluate();
   }
 
+  #[napi(js_name = "setR1C1")]
+  pub fn set_r1c1(&mut self, r1c1: bool) {
+    self.model.set_r1c1(r1c1);
+  }
+
+  #[napi(js_name = "getR1C1")]
+  pub fn get_r1c1(&self) -> bool {
+    self.model.get_r1c1()
+  }
+
+  /// Registers a function implemented in JavaScript. The callback receives the list of
+  /// arguments and returns the value of the function.
+  #[napi(js_name = "registerFunction")]
+  pub fn register_function(
+    &mut self,
+    env: Env,
+    name: String,
+    min_args: u32,
+    max_args: Option<u32>,
+    volatile: bool,
+    callback: FunctionRef<Value, Value>,
+  ) -> Result<()> {
+    let function = new_custom_function(&env, name, min_args, max_args, volatile, callback);
+    self.model.register_function(function).map_err(to_js_error)
+  }
+
+  #[napi(js_name = "unregisterFunction")]
+  pub fn unregister_function(&mut self, name: String) -> Result<()> {
+    self.model.unregister_function(&name).map_err(to_js_error)
+  }
+
+  #[napi(js_name = "getRegisteredFunctions")]
+  pub fn get_registered_functions(&self) -> Vec<String> {
+    self.model.get_registered_functions()
+  }
+
   #[napi]
   pub fn set_user_input(&mut self, sheet: u32, row: i32, column: i32, value: String) -> Result<()> {
     self
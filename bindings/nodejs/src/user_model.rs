@@ -1,15 +1,20 @@
 #![deny(clippy::all)]
 
 use serde::Serialize;
+use serde_json::Value;
 
 use napi::{self, bindgen_prelude::*, Result, Unknown};
 
 use ironcalc::base::{
-  expressions::types::Area,
-  types::{CellType, Color, Style},
-  BorderArea, ClipboardData, UserModel as BaseModel,
+  expressions::types::{Area, CellReferenceIndex},
+  goal_seek::GoalSeekOptions,
+  types::{CellType, Color, Scenario, ShiftDirection, Style},
+  BorderArea, ClipboardData, FindHit, FindOptions, PasteSpecialOptions, PortableClipboard,
+  PortableReferences, UserModel as BaseModel,
 };
 
+use crate::custom_functions::new_custom_function;
+
 #[derive(Serialize)]
 struct DefinedName {
   name: String,
@@ -124,6 +129,19 @@ impl UserModel {
     self.model.rename_sheet(sheet, &name).map_err(to_js_error)
   }
 
+  #[napi(js_name = "duplicateSheet")]
+  pub fn duplicate_sheet(&mut self, sheet: u32, new_name: String, position: u32) -> Result<()> {
+    self
+      .model
+      .duplicate_sheet(sheet, &new_name, position)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "moveSheet")]
+  pub fn move_sheet(&mut self, from: u32, to: u32) -> Result<()> {
+    self.model.move_sheet(from, to).map_err(to_js_error)
+  }
+
   // FIXME: This should alos allow for themed colors
   #[napi(js_name = "setSheetColor")]
   pub fn set_sheet_color(&mut self, sheet: u32, color: String) -> Result<()> {
@@ -226,6 +244,34 @@ impl UserModel {
       .map_err(to_js_error)
   }
 
+  #[napi(js_name = "insertCells")]
+  pub fn insert_cells(&mut self, env: Env, area: Unknown, direction: Unknown) -> Result<()> {
+    let area: Area = env
+      .from_js_value(area)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let direction: ShiftDirection = env
+      .from_js_value(direction)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .insert_cells(&area, direction)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "deleteCells")]
+  pub fn delete_cells(&mut self, env: Env, area: Unknown, direction: Unknown) -> Result<()> {
+    let area: Area = env
+      .from_js_value(area)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let direction: ShiftDirection = env
+      .from_js_value(direction)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .delete_cells(&area, direction)
+      .map_err(to_js_error)
+  }
+
   #[napi(js_name = "setRowsHeight")]
   pub fn set_rows_height(
     &mut self,
@@ -578,6 +624,45 @@ impl UserModel {
     self.model.set_name(&name);
   }
 
+  #[napi(js_name = "setR1C1")]
+  pub fn set_r1c1(&mut self, r1c1: bool) -> Result<()> {
+    self
+      .model
+      .set_r1c1(r1c1)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "getR1C1")]
+  pub fn get_r1c1(&self) -> bool {
+    self.model.get_r1c1()
+  }
+
+  /// Registers a function implemented in JavaScript. The callback receives the list of
+  /// arguments and returns the value of the function.
+  #[napi(js_name = "registerFunction")]
+  pub fn register_function(
+    &mut self,
+    env: Env,
+    name: String,
+    min_args: u32,
+    max_args: Option<u32>,
+    volatile: bool,
+    callback: FunctionRef<Value, Value>,
+  ) -> Result<()> {
+    let function = new_custom_function(&env, name, min_args, max_args, volatile, callback);
+    self.model.register_function(function).map_err(to_js_error)
+  }
+
+  #[napi(js_name = "unregisterFunction")]
+  pub fn unregister_function(&mut self, name: String) -> Result<()> {
+    self.model.unregister_function(&name).map_err(to_js_error)
+  }
+
+  #[napi(js_name = "getRegisteredFunctions")]
+  pub fn get_registered_functions(&self) -> Vec<String> {
+    self.model.get_registered_functions()
+  }
+
   #[napi(js_name = "copyToClipboard")]
   pub fn copy_to_clipboard(&'_ self, env: Env) -> Result<Unknown<'_>> {
     let data = self
@@ -611,6 +696,30 @@ impl UserModel {
       .map_err(|e| to_js_error(e.to_string()))
   }
 
+  #[napi(js_name = "pasteSpecial")]
+  pub fn paste_special(
+    &mut self,
+    env: Env,
+    source_sheet: u32,
+    source_range: Unknown,
+    clipboard: Unknown,
+    options: Unknown,
+  ) -> Result<()> {
+    let source_range: (i32, i32, i32, i32) = env
+      .from_js_value(source_range)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let clipboard: ClipboardData = env
+      .from_js_value(clipboard)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let options: PasteSpecialOptions = env
+      .from_js_value(options)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .paste_special(source_sheet, source_range, &clipboard, &options)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
   #[napi(js_name = "pasteCsvText")]
   pub fn paste_csv_string(&mut self, env: Env, area: Unknown, csv: String) -> Result<()> {
     let range: Area = env
@@ -622,6 +731,204 @@ impl UserModel {
       .map_err(|e| to_js_error(e.to_string()))
   }
 
+  #[napi(js_name = "copyToHtml")]
+  pub fn copy_to_html(&self) -> Result<String> {
+    self
+      .model
+      .copy_to_html()
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "pasteHtml")]
+  pub fn paste_html(&mut self, env: Env, area: Unknown, html: String) -> Result<()> {
+    let range: Area = env
+      .from_js_value(area)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .paste_html(&range, &html)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "find")]
+  pub fn find(&'_ self, env: Env, query: String, options: Unknown) -> Result<Unknown<'_>> {
+    let options: FindOptions = env
+      .from_js_value(options)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let hits: Vec<FindHit> = self
+      .model
+      .find(&query, &options)
+      .map_err(to_js_error)?
+      .collect();
+    env
+      .to_js_value(&hits)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "replaceAll")]
+  pub fn replace_all(
+    &mut self,
+    env: Env,
+    query: String,
+    replacement: String,
+    options: Unknown,
+  ) -> Result<u32> {
+    let options: FindOptions = env
+      .from_js_value(options)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .replace_all(&query, &replacement, &options)
+      .map(|count| count as u32)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "goalSeek")]
+  pub fn goal_seek(
+    &'_ mut self,
+    env: Env,
+    target: Unknown,
+    target_value: f64,
+    changing: Unknown,
+    options: Unknown,
+  ) -> Result<Unknown<'_>> {
+    let target: CellReferenceIndex = env
+      .from_js_value(target)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let changing: CellReferenceIndex = env
+      .from_js_value(changing)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let options: GoalSeekOptions = env
+      .from_js_value(options)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let result = self
+      .model
+      .goal_seek(&target, target_value, &changing, &options)
+      .map_err(to_js_error)?;
+    env
+      .to_js_value(&result)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "createDataTable")]
+  pub fn create_data_table(
+    &mut self,
+    env: Env,
+    area: Unknown,
+    row_input: Unknown,
+    column_input: Unknown,
+  ) -> Result<()> {
+    let area: Area = env
+      .from_js_value(area)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let row_input: Option<CellReferenceIndex> = env
+      .from_js_value(row_input)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let column_input: Option<CellReferenceIndex> = env
+      .from_js_value(column_input)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .create_data_table(&area, row_input.as_ref(), column_input.as_ref())
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "getScenarios")]
+  pub fn get_scenarios(&'_ self, env: Env, sheet: u32) -> Result<Unknown<'_>> {
+    let scenarios = self.model.get_scenarios(sheet).map_err(to_js_error)?;
+    env
+      .to_js_value(&scenarios)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "addScenario")]
+  pub fn add_scenario(&mut self, env: Env, sheet: u32, scenario: Unknown) -> Result<()> {
+    let scenario: Scenario = env
+      .from_js_value(scenario)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .add_scenario(sheet, scenario)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "updateScenario")]
+  pub fn update_scenario(
+    &mut self,
+    env: Env,
+    sheet: u32,
+    name: String,
+    scenario: Unknown,
+  ) -> Result<()> {
+    let scenario: Scenario = env
+      .from_js_value(scenario)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .update_scenario(sheet, &name, scenario)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "deleteScenario")]
+  pub fn delete_scenario(&mut self, sheet: u32, name: String) -> Result<()> {
+    self
+      .model
+      .delete_scenario(sheet, &name)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "showScenario")]
+  pub fn show_scenario(&mut self, sheet: u32, name: String) -> Result<()> {
+    self.model.show_scenario(sheet, &name).map_err(to_js_error)
+  }
+
+  #[napi(js_name = "createScenarioSummary")]
+  pub fn create_scenario_summary(
+    &mut self,
+    env: Env,
+    sheet: u32,
+    result_cells: Unknown,
+  ) -> Result<u32> {
+    let result_cells: Vec<CellReferenceIndex> = env
+      .from_js_value(result_cells)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .create_scenario_summary(sheet, &result_cells)
+      .map_err(to_js_error)
+  }
+
+  #[napi(js_name = "copyToPortableClipboard")]
+  pub fn copy_to_portable_clipboard(&'_ self, env: Env) -> Result<Unknown<'_>> {
+    let data = self
+      .model
+      .copy_to_portable_clipboard()
+      .map_err(|e| to_js_error(e.to_string()))?;
+
+    env
+      .to_js_value(&data)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
+  #[napi(js_name = "pastePortableClipboard")]
+  pub fn paste_portable_clipboard(
+    &mut self,
+    env: Env,
+    clipboard: Unknown,
+    references: Unknown,
+  ) -> Result<()> {
+    let clipboard: PortableClipboard = env
+      .from_js_value(clipboard)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    let references: PortableReferences = env
+      .from_js_value(references)
+      .map_err(|e| to_js_error(e.to_string()))?;
+    self
+      .model
+      .paste_portable_clipboard(&clipboard, references)
+      .map_err(|e| to_js_error(e.to_string()))
+  }
+
   #[napi(js_name = "getDefinedNameList")]
   pub fn get_defined_name_list(&'_ self, env: Env) -> Result<Unknown<'_>> {
     let data: Vec<DefinedName> = self
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyList};
+
+use xlsx::base::custom_functions::{CustomFunction, FunctionValue};
+
+fn value_error() -> FunctionValue {
+    FunctionValue::Error {
+        error: "#VALUE!".to_string(),
+    }
+}
+
+fn to_python(py: Python<'_>, value: &FunctionValue) -> PyResult<Py<PyAny>> {
+    Ok(match value {
+        FunctionValue::Number(value) => value.into_pyobject(py)?.into_any().unbind(),
+        FunctionValue::Boolean(value) => PyBool::new(py, *value).to_owned().into_any().unbind(),
+        FunctionValue::Text(value) => value.into_pyobject(py)?.into_any().unbind(),
+        FunctionValue::Error { error } => {
+            let dict = PyDict::new(py);
+            dict.set_item("error", error)?;
+            dict.into_any().unbind()
+        }
+        FunctionValue::Array(rows) => {
+            let list = PyList::empty(py);
+            for row in rows {
+                let values = row
+                    .iter()
+                    .map(|value| to_python(py, value))
+                    .collect::<PyResult<Vec<_>>>()?;
+                list.append(PyList::new(py, values)?)?;
+            }
+            list.into_any().unbind()
+        }
+        FunctionValue::Empty => py.None(),
+    })
+}
+
+fn from_python(value: &Bound<'_, PyAny>) -> FunctionValue {
+    if value.is_none() {
+        return FunctionValue::Empty;
+    }
+    // bool is a subclass of int in Python, it needs to go first
+    if let Ok(value) = value.cast::<PyBool>() {
+        return FunctionValue::Boolean(value.is_true());
+    }
+    if let Ok(value) = value.extract::<f64>() {
+        return FunctionValue::Number(value);
+    }
+    if let Ok(value) = value.extract::<String>() {
+        return FunctionValue::Text(value);
+    }
+    if let Ok(dict) = value.cast::<PyDict>() {
+        return match dict.get_item("error").ok().flatten() {
+            Some(error) => match error.extract::<String>() {
+                Ok(error) => FunctionValue::Error { error },
+                Err(_) => value_error(),
+            },
+            None => value_error(),
+        };
+    }
+    if let Ok(rows) = value.extract::<Vec<Bound<'_, PyAny>>>() {
+        let mut array = Vec::with_capacity(rows.len());
+        for row in rows {
+            match row.extract::<Vec<Bound<'_, PyAny>>>() {
+                Ok(row) => array.push(row.iter().map(from_python).collect()),
+                Err(_) => return value_error(),
+            }
+        }
+        return FunctionValue::Array(array);
+    }
+    value_error()
+}
+
+/// The callable receives the list of arguments. Ranges are lists of rows, empty cells are
+/// `None` and errors are dictionaries like `{"error": "#N/A"}`.
+/// Exceptions raised by the callable are `#VALUE!` errors.
+pub(crate) fn new_custom_function(
+    name: String,
+    min_args: usize,
+    max_args: Option<usize>,
+    volatile: bool,
+    callable: Py<PyAny>,
+) -> CustomFunction {
+    CustomFunction {
+        name,
+        min_args,
+        max_args,
+        volatile,
+        callback: Arc::new(move |args| {
+            Python::attach(|py| {
+                let args = args
+                    .iter()
+                    .map(|value| to_python(py, value))
+                    .collect::<PyResult<Vec<_>>>()
+                    .and_then(|args| PyList::new(py, args));
+                match args.and_then(|args| callable.call1(py, (args,))) {
+                    Ok(result) => from_python(result.bind(py)),
+                    Err(_) => value_error(),
+                }
+            })
+        }),
+    }
+}
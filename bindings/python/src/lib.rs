@@ -1,14 +1,17 @@
 use pyo3::exceptions::PyException;
 use pyo3::{create_exception, prelude::*, wrap_pyfunction};
 
-use types::{PyCellType, PySheetProperty, PyStyle};
-use xlsx::base::expressions::types::Area;
+use custom_functions::new_custom_function;
+use types::{PyCellType, PyGoalSeekResult, PySheetProperty, PyStyle};
+use xlsx::base::expressions::types::{Area, CellReferenceIndex};
+use xlsx::base::goal_seek::GoalSeekOptions;
 use xlsx::base::types::{Color, Style, Workbook};
 use xlsx::base::{Model, UserModel};
 
 use xlsx::export::{save_to_icalc, save_to_xlsx};
 use xlsx::import;
 
+mod custom_functions;
 mod types;
 
 create_exception!(_ironcalc, WorkbookError, PyException);
@@ -59,6 +62,44 @@ impl PyUserModel {
             .map_err(|e| WorkbookError::new_err(e.to_string()))
     }
 
+    /// Sets the reference style, R1C1 if `r1c1` is true and A1 otherwise
+    pub fn set_r1c1(&mut self, r1c1: bool) -> PyResult<()> {
+        self.model
+            .set_r1c1(r1c1)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn get_r1c1(&self) -> bool {
+        self.model.get_r1c1()
+    }
+
+    /// Registers a function implemented in Python, callable from formulas.
+    /// The callable receives the list of arguments and returns the value of the function.
+    #[pyo3(signature = (name, min_args, max_args, volatile, callable))]
+    pub fn register_function(
+        &mut self,
+        name: String,
+        min_args: usize,
+        max_args: Option<usize>,
+        volatile: bool,
+        callable: Py<PyAny>,
+    ) -> PyResult<()> {
+        let function = new_custom_function(name, min_args, max_args, volatile, callable);
+        self.model
+            .register_function(function)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn unregister_function(&mut self, name: &str) -> PyResult<()> {
+        self.model
+            .unregister_function(name)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn get_registered_functions(&self) -> Vec<String> {
+        self.model.get_registered_functions()
+    }
+
     pub fn get_formatted_cell_value(&self, sheet: u32, row: i32, column: i32) -> PyResult<String> {
         self.model
             .get_formatted_cell_value(sheet, row, column)
@@ -118,6 +159,42 @@ impl PyModel {
         self.model.evaluate()
     }
 
+    /// Sets the reference style, R1C1 if `r1c1` is true and A1 otherwise
+    pub fn set_r1c1(&mut self, r1c1: bool) {
+        self.model.set_r1c1(r1c1)
+    }
+
+    pub fn get_r1c1(&self) -> bool {
+        self.model.get_r1c1()
+    }
+
+    /// Registers a function implemented in Python, callable from formulas.
+    /// The callable receives the list of arguments and returns the value of the function.
+    #[pyo3(signature = (name, min_args, max_args, volatile, callable))]
+    pub fn register_function(
+        &mut self,
+        name: String,
+        min_args: usize,
+        max_args: Option<usize>,
+        volatile: bool,
+        callable: Py<PyAny>,
+    ) -> PyResult<()> {
+        let function = new_custom_function(name, min_args, max_args, volatile, callable);
+        self.model
+            .register_function(function)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn unregister_function(&mut self, name: &str) -> PyResult<()> {
+        self.model
+            .unregister_function(name)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))
+    }
+
+    pub fn get_registered_functions(&self) -> Vec<String> {
+        self.model.get_registered_functions()
+    }
+
     // Set values
 
     /// Set an input
@@ -336,6 +413,37 @@ impl PyModel {
         ))
     }
 
+    /// Changes the number in `changing` (sheet, row, column) until the formula in `target`
+    /// evaluates to `target_value`. The original value is restored if there is no solution.
+    #[pyo3(signature = (target, target_value, changing, max_iterations=100, tolerance=0.001))]
+    pub fn goal_seek(
+        &mut self,
+        target: (u32, i32, i32),
+        target_value: f64,
+        changing: (u32, i32, i32),
+        max_iterations: u32,
+        tolerance: f64,
+    ) -> PyResult<PyGoalSeekResult> {
+        let (sheet, row, column) = target;
+        let target = CellReferenceIndex { sheet, row, column };
+        let (sheet, row, column) = changing;
+        let changing = CellReferenceIndex { sheet, row, column };
+        let options = GoalSeekOptions {
+            max_iterations,
+            tolerance,
+        };
+        let result = self
+            .model
+            .goal_seek(&target, target_value, &changing, &options)
+            .map_err(|e| WorkbookError::new_err(e.to_string()))?;
+        Ok(PyGoalSeekResult {
+            converged: result.converged,
+            iterations: result.iterations,
+            value: result.value,
+            error: result.error,
+        })
+    }
+
     #[allow(clippy::panic)]
     pub fn test_panic(&self) -> PyResult<()> {
         panic!("This function panics for testing panic handling");
@@ -1,7 +1,7 @@
 use pyo3::prelude::*;
 use xlsx::base::types::{
     Alignment, Border, BorderItem, BorderStyle, CellType, Color, Fill, Font, FontScheme,
-    HorizontalAlignment, Style, VerticalAlignment,
+    GradientFill, HorizontalAlignment, Protection, ReadingOrder, Style, VerticalAlignment,
 };
 
 fn color_to_string(c: Color) -> Option<String> {
@@ -28,6 +28,19 @@ pub struct PySheetProperty {
     pub color: Option<String>,
 }
 
+#[derive(Clone)]
+#[pyclass]
+pub struct PyGoalSeekResult {
+    #[pyo3(get)]
+    pub converged: bool,
+    #[pyo3(get)]
+    pub iterations: u32,
+    #[pyo3(get)]
+    pub value: f64,
+    #[pyo3(get)]
+    pub error: f64,
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct Cell {
@@ -60,6 +73,14 @@ pub enum PyVerticalAlignment {
     Top,
 }
 
+#[pyclass(eq, eq_int)]
+#[derive(PartialEq, Clone)]
+pub enum PyReadingOrder {
+    ContextDependent,
+    LeftToRight,
+    RightToLeft,
+}
+
 #[pyclass]
 #[derive(Clone)]
 pub struct PyAlignment {
@@ -69,6 +90,25 @@ pub struct PyAlignment {
     pub vertical: PyVerticalAlignment,
     #[pyo3(get)]
     pub wrap_text: bool,
+    #[pyo3(get)]
+    pub text_rotation: i32,
+    #[pyo3(get)]
+    pub indent: i32,
+    #[pyo3(get)]
+    pub shrink_to_fit: bool,
+    #[pyo3(get)]
+    pub justify_last_line: bool,
+    #[pyo3(get)]
+    pub reading_order: PyReadingOrder,
+}
+
+#[pyclass]
+#[derive(Clone)]
+pub struct PyProtection {
+    #[pyo3(get)]
+    pub locked: bool,
+    #[pyo3(get)]
+    pub hidden: bool,
 }
 
 #[pyclass]
@@ -77,6 +117,8 @@ pub struct PyStyle {
     #[pyo3(get)]
     pub alignment: Option<PyAlignment>,
     #[pyo3(get)]
+    pub protection: Option<PyProtection>,
+    #[pyo3(get)]
     pub num_fmt: String,
     #[pyo3(get)]
     pub fill: PyFill,
@@ -158,6 +200,8 @@ pub struct PyFont {
     #[pyo3(get)]
     pub family: i32,
     #[pyo3(get)]
+    pub charset: Option<i32>,
+    #[pyo3(get)]
     pub scheme: PyFontScheme,
 }
 
@@ -166,6 +210,12 @@ pub struct PyFont {
 pub struct PyFill {
     #[pyo3(get)]
     pub color: Option<String>,
+    #[pyo3(get)]
+    pub pattern_type: String,
+    #[pyo3(get)]
+    pub bg_color: Option<String>,
+    // Not exposed to Python, kept so gradients survive a get/set round trip
+    pub gradient: Option<GradientFill>,
 }
 
 #[pyclass(eq, eq_int)]
@@ -210,6 +260,16 @@ impl From<PyVerticalAlignment> for VerticalAlignment {
     }
 }
 
+impl From<PyReadingOrder> for ReadingOrder {
+    fn from(py_order: PyReadingOrder) -> Self {
+        match py_order {
+            PyReadingOrder::ContextDependent => ReadingOrder::ContextDependent,
+            PyReadingOrder::LeftToRight => ReadingOrder::LeftToRight,
+            PyReadingOrder::RightToLeft => ReadingOrder::RightToLeft,
+        }
+    }
+}
+
 impl From<PyFontScheme> for FontScheme {
     fn from(py_scheme: PyFontScheme) -> Self {
         match py_scheme {
@@ -242,6 +302,12 @@ impl From<&PyFill> for Fill {
     fn from(py_fill: &PyFill) -> Self {
         Fill {
             color: py_fill.color.clone().map_or(Color::None, string_to_color),
+            pattern_type: py_fill.pattern_type.parse().unwrap_or_default(),
+            bg_color: py_fill
+                .bg_color
+                .clone()
+                .map_or(Color::None, string_to_color),
+            gradient: py_fill.gradient.clone(),
         }
     }
 }
@@ -257,6 +323,7 @@ impl From<&PyFont> for Font {
             color: py_font.color.clone().map_or(Color::None, string_to_color),
             name: py_font.name.clone(),
             family: py_font.family,
+            charset: py_font.charset,
             scheme: py_font.scheme.clone().into(),
         }
     }
@@ -291,6 +358,20 @@ impl From<&PyAlignment> for Alignment {
             horizontal: py_align.horizontal.clone().into(),
             vertical: py_align.vertical.clone().into(),
             wrap_text: py_align.wrap_text,
+            text_rotation: py_align.text_rotation,
+            indent: py_align.indent,
+            shrink_to_fit: py_align.shrink_to_fit,
+            justify_last_line: py_align.justify_last_line,
+            reading_order: py_align.reading_order.clone().into(),
+        }
+    }
+}
+
+impl From<&PyProtection> for Protection {
+    fn from(py_protection: &PyProtection) -> Self {
+        Protection {
+            locked: py_protection.locked,
+            hidden: py_protection.hidden,
         }
     }
 }
@@ -299,6 +380,7 @@ impl From<&PyStyle> for Style {
     fn from(py_style: &PyStyle) -> Self {
         Style {
             alignment: py_style.alignment.as_ref().map(|a| a.into()),
+            protection: py_style.protection.as_ref().map(|p| p.into()),
             num_fmt: py_style.num_fmt.clone(),
             fill: (&py_style.fill).into(),
             font: (&py_style.font).into(),
@@ -313,6 +395,9 @@ impl From<Fill> for PyFill {
     fn from(fill: Fill) -> Self {
         PyFill {
             color: color_to_string(fill.color),
+            pattern_type: fill.pattern_type.to_string(),
+            bg_color: color_to_string(fill.bg_color),
+            gradient: fill.gradient,
         }
     }
 }
@@ -346,6 +431,17 @@ impl From<VerticalAlignment> for PyVerticalAlignment {
     }
 }
 
+// From non-Py to Py
+impl From<ReadingOrder> for PyReadingOrder {
+    fn from(order: ReadingOrder) -> Self {
+        match order {
+            ReadingOrder::ContextDependent => PyReadingOrder::ContextDependent,
+            ReadingOrder::LeftToRight => PyReadingOrder::LeftToRight,
+            ReadingOrder::RightToLeft => PyReadingOrder::RightToLeft,
+        }
+    }
+}
+
 // From non-Py to Py
 impl From<FontScheme> for PyFontScheme {
     fn from(scheme: FontScheme) -> Self {
@@ -386,6 +482,7 @@ impl From<Font> for PyFont {
             color: color_to_string(font.color),
             name: font.name,
             family: font.family,
+            charset: font.charset,
             scheme: font.scheme.into(),
         }
     }
@@ -423,6 +520,21 @@ impl From<Alignment> for PyAlignment {
             horizontal: align.horizontal.into(),
             vertical: align.vertical.into(),
             wrap_text: align.wrap_text,
+            text_rotation: align.text_rotation,
+            indent: align.indent,
+            shrink_to_fit: align.shrink_to_fit,
+            justify_last_line: align.justify_last_line,
+            reading_order: align.reading_order.into(),
+        }
+    }
+}
+
+// From non-Py to Py
+impl From<Protection> for PyProtection {
+    fn from(protection: Protection) -> Self {
+        PyProtection {
+            locked: protection.locked,
+            hidden: protection.hidden,
         }
     }
 }
@@ -432,6 +544,7 @@ impl From<Style> for PyStyle {
     fn from(style: Style) -> Self {
         PyStyle {
             alignment: style.alignment.map(|a| a.into()),
+            protection: style.protection.map(|p| p.into()),
             num_fmt: style.num_fmt,
             fill: style.fill.into(),
             font: style.font.into(),
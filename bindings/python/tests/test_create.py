@@ -55,3 +55,20 @@ def test_sheet_dimensions_user_model():
     # Check dimensions
     min_row, max_row, min_col, max_col = model.get_sheet_dimensions(0)
     assert (min_row, max_row, min_col, max_col) == (2, 2, 3, 3)
+
+
+def test_custom_functions():
+    model = ic.create("model", "en", "UTC", "en")
+    model.register_function("DOUBLE", 1, 1, False, lambda args: args[0] * 2)
+    model.register_function("COUNT_EMPTY", 1, None, False, lambda args: sum(
+        1 for row in args[0] for value in row if value is None))
+    model.set_user_input(0, 1, 1, "=DOUBLE(21)")
+    model.set_user_input(0, 2, 1, "=COUNT_EMPTY(C1:D3)")
+    # None * 2 raises an exception
+    model.set_user_input(0, 3, 1, "=DOUBLE(F1)")
+    model.evaluate()
+
+    assert model.get_formatted_cell_value(0, 1, 1) == "42"
+    assert model.get_formatted_cell_value(0, 2, 1) == "6"
+    assert model.get_formatted_cell_value(0, 3, 1) == "#VALUE!"
+    assert model.get_registered_functions() == ["COUNT_EMPTY", "DOUBLE"]
@@ -1,3 +1,13 @@
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
 use serde::Serialize;
 use wasm_bindgen::{
     prelude::{wasm_bindgen, JsError},
@@ -7,20 +17,76 @@ use wasm_bindgen::{
 use ironcalc_base::{
     cf_types::CfRuleInput,
     colors,
+    custom_functions::{CustomFunction, FunctionValue},
     expressions::{
         lexer::util::get_tokens as tokenizer,
-        types::Area,
+        types::{Area, CellReferenceIndex},
         utils::{number_to_column, quote_name as quote_name_ic},
     },
-    types::{CellType, Color, Style},
+    function_catalogue,
+    goal_seek::GoalSeekOptions,
+    types::{CellType, Color, Scenario, ShiftDirection, Style},
     worksheet::NavigationDirection,
-    BorderArea, ClipboardData, UserModel as BaseModel,
+    BorderArea, ClipboardData, FindHit, FindOptions, PasteSpecialOptions, PortableClipboard,
+    PortableReferences, UserModel as BaseModel,
 };
 
 fn to_js_error(error: String) -> JsError {
     JsError::new(&error.to_string())
 }
 
+/// A JavaScript function called from formulas.
+struct JsCallback(js_sys::Function);
+
+thread_local! {
+    /// The JavaScript functions registered in this thread, by id
+    static CALLBACKS: RefCell<HashMap<usize, Rc<JsCallback>>> = RefCell::new(HashMap::new());
+}
+
+/// JavaScript values cannot be shared between threads. The function stays in the thread that
+/// registered it and the model holds this handle. Called from any other thread it is a `#VALUE!` error.
+struct CallbackHandle(usize);
+
+impl CallbackHandle {
+    fn new(callback: JsCallback) -> CallbackHandle {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        CALLBACKS.with(|callbacks| callbacks.borrow_mut().insert(id, Rc::new(callback)));
+        CallbackHandle(id)
+    }
+
+    fn call(&self, args: &[FunctionValue]) -> FunctionValue {
+        // The function might register or unregister functions, the registry is not borrowed during the call
+        match CALLBACKS.with(|callbacks| callbacks.borrow().get(&self.0).cloned()) {
+            Some(callback) => callback.call(args),
+            None => FunctionValue::Error {
+                error: "#VALUE!".to_string(),
+            },
+        }
+    }
+}
+
+impl Drop for CallbackHandle {
+    fn drop(&mut self) {
+        // Nothing to remove in other threads or once the registry of this thread is gone
+        let _ = CALLBACKS.try_with(|callbacks| callbacks.borrow_mut().remove(&self.0));
+    }
+}
+
+impl JsCallback {
+    fn call(&self, args: &[FunctionValue]) -> FunctionValue {
+        let serializer = serde_wasm_bindgen::Serializer::new().serialize_missing_as_null(true);
+        args.serialize(&serializer)
+            .ok()
+            .and_then(|args| self.0.call1(&JsValue::NULL, &args).ok())
+            .and_then(|value| serde_wasm_bindgen::from_value(value).ok())
+            // Exceptions and unexpected values are #VALUE! errors
+            .unwrap_or_else(|| FunctionValue::Error {
+                error: "#VALUE!".to_string(),
+            })
+    }
+}
+
 /// Return an array with a list of all the tokens from a formula
 /// This is used by the UI to color them according to a theme.
 #[wasm_bindgen(js_name = "getTokens", unchecked_return_type = "MarkedToken[]")]
@@ -29,6 +95,32 @@ pub fn get_tokens(formula: &str) -> Result<JsValue, JsError> {
     serde_wasm_bindgen::to_value(&tokens).map_err(JsError::from)
 }
 
+/// Returns the description of all the functions in the given language.
+#[wasm_bindgen(
+    js_name = "getFunctionCatalogue",
+    unchecked_return_type = "FunctionMetadata[]"
+)]
+pub fn get_function_catalogue(language: &str) -> Result<JsValue, JsError> {
+    let catalogue = function_catalogue::get_function_catalogue(language).map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&catalogue).map_err(JsError::from)
+}
+
+/// Returns the function and argument at the cursor of a partially typed formula
+#[wasm_bindgen(
+    js_name = "getActiveArgument",
+    unchecked_return_type = "ActiveArgument | undefined"
+)]
+pub fn get_active_argument(
+    formula: &str,
+    cursor: usize,
+    locale: &str,
+    language: &str,
+) -> Result<JsValue, JsError> {
+    let argument = function_catalogue::get_active_argument(formula, cursor, locale, language)
+        .map_err(to_js_error)?;
+    serde_wasm_bindgen::to_value(&argument).map_err(JsError::from)
+}
+
 #[wasm_bindgen(js_name = "columnNameFromNumber")]
 pub fn column_name_from_number(column: i32) -> Result<String, JsError> {
     match number_to_column(column) {
@@ -208,6 +300,23 @@ impl Model {
         self.model.rename_sheet(sheet, name).map_err(to_js_error)
     }
 
+    #[wasm_bindgen(js_name = "duplicateSheet")]
+    pub fn duplicate_sheet(
+        &mut self,
+        sheet: u32,
+        new_name: &str,
+        position: u32,
+    ) -> Result<(), JsError> {
+        self.model
+            .duplicate_sheet(sheet, new_name, position)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "moveSheet")]
+    pub fn move_sheet(&mut self, from: u32, to: u32) -> Result<(), JsError> {
+        self.model.move_sheet(from, to).map_err(to_js_error)
+    }
+
     #[wasm_bindgen(js_name = "setSheetColor")]
     pub fn set_sheet_color(
         &mut self,
@@ -318,6 +427,36 @@ impl Model {
             .map_err(to_js_error)
     }
 
+    #[wasm_bindgen(js_name = "insertCells")]
+    pub fn insert_cells(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Area")] area: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "ShiftDirection")] direction: JsValue,
+    ) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        let direction: ShiftDirection =
+            serde_wasm_bindgen::from_value(direction).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .insert_cells(&area, direction)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteCells")]
+    pub fn delete_cells(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Area")] area: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "ShiftDirection")] direction: JsValue,
+    ) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        let direction: ShiftDirection =
+            serde_wasm_bindgen::from_value(direction).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .delete_cells(&area, direction)
+            .map_err(to_js_error)
+    }
+
     #[wasm_bindgen(js_name = "moveColumns")]
     pub fn move_columns_action(
         &mut self,
@@ -396,6 +535,152 @@ impl Model {
             .map_err(to_js_error)
     }
 
+    #[wasm_bindgen(js_name = "groupRows")]
+    pub fn group_rows(&mut self, sheet: u32, row_start: i32, row_end: i32) -> Result<(), JsError> {
+        self.model
+            .group_rows(sheet, row_start, row_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "ungroupRows")]
+    pub fn ungroup_rows(
+        &mut self,
+        sheet: u32,
+        row_start: i32,
+        row_end: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .ungroup_rows(sheet, row_start, row_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "collapseRowGroup")]
+    pub fn collapse_row_group(&mut self, sheet: u32, row: i32, level: i32) -> Result<(), JsError> {
+        self.model
+            .collapse_row_group(sheet, row, level)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "expandRowGroup")]
+    pub fn expand_row_group(&mut self, sheet: u32, row: i32, level: i32) -> Result<(), JsError> {
+        self.model
+            .expand_row_group(sheet, row, level)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(
+        js_name = "getEvaluationSteps",
+        unchecked_return_type = "EvaluationStep[]"
+    )]
+    pub fn get_evaluation_steps(
+        &mut self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+    ) -> Result<JsValue, JsError> {
+        let steps = self
+            .model
+            .get_evaluation_steps(sheet, row, column)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&steps).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getErrorChecks", unchecked_return_type = "CellDiagnostic[]")]
+    pub fn get_error_checks(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let diagnostics = self.model.get_error_checks(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&diagnostics).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getPrecedents", unchecked_return_type = "TracedReference[]")]
+    pub fn get_precedents(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        depth: u32,
+    ) -> Result<JsValue, JsError> {
+        let precedents = self
+            .model
+            .get_precedents(sheet, row, column, depth)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&precedents).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getDependents", unchecked_return_type = "TracedReference[]")]
+    pub fn get_dependents(
+        &self,
+        sheet: u32,
+        row: i32,
+        column: i32,
+        depth: u32,
+    ) -> Result<JsValue, JsError> {
+        let dependents = self
+            .model
+            .get_dependents(sheet, row, column, depth)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&dependents).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "getRowOutline", unchecked_return_type = "OutlineGroup[]")]
+    pub fn get_row_outline(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let outline = self.model.get_row_outline(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&outline).map_err(JsError::from)
+    }
+
+    #[wasm_bindgen(js_name = "groupColumns")]
+    pub fn group_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .group_columns(sheet, column_start, column_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "ungroupColumns")]
+    pub fn ungroup_columns(
+        &mut self,
+        sheet: u32,
+        column_start: i32,
+        column_end: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .ungroup_columns(sheet, column_start, column_end)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "collapseColumnGroup")]
+    pub fn collapse_column_group(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        level: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .collapse_column_group(sheet, column, level)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "expandColumnGroup")]
+    pub fn expand_column_group(
+        &mut self,
+        sheet: u32,
+        column: i32,
+        level: i32,
+    ) -> Result<(), JsError> {
+        self.model
+            .expand_column_group(sheet, column, level)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getColumnOutline", unchecked_return_type = "OutlineGroup[]")]
+    pub fn get_column_outline(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let outline = self.model.get_column_outline(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&outline).map_err(JsError::from)
+    }
+
     #[wasm_bindgen(js_name = "getRowHeight")]
     pub fn get_row_height(&mut self, sheet: u32, row: i32) -> Result<f64, JsError> {
         self.model.get_row_height(sheet, row).map_err(to_js_error)
@@ -675,6 +960,65 @@ impl Model {
         self.model.resolve_color(&color)
     }
 
+    /// Registers a function implemented in JavaScript. The callback receives the list of
+    /// arguments: ranges are arrays of rows, empty cells are `null` and errors are objects
+    /// like `{ error: "#N/A" }`. It returns a value in the same format.
+    #[wasm_bindgen(js_name = "registerFunction")]
+    pub fn register_function(
+        &mut self,
+        name: String,
+        min_args: usize,
+        max_args: Option<usize>,
+        volatile: bool,
+        #[wasm_bindgen(unchecked_param_type = "(args: FunctionValue[]) => FunctionValue")]
+        callback: js_sys::Function,
+    ) -> Result<(), JsError> {
+        let callback = CallbackHandle::new(JsCallback(callback));
+        self.model
+            .register_function(CustomFunction {
+                name,
+                min_args,
+                max_args,
+                volatile,
+                callback: Arc::new(move |args| callback.call(args)),
+            })
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "unregisterFunction")]
+    pub fn unregister_function(&mut self, name: &str) -> Result<(), JsError> {
+        self.model.unregister_function(name).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getRegisteredFunctions")]
+    pub fn get_registered_functions(&self) -> Vec<String> {
+        self.model.get_registered_functions()
+    }
+
+    /// Sets the rules used to map workbook fonts to fonts the browser can render.
+    #[wasm_bindgen(js_name = "setFontFallback")]
+    pub fn set_font_fallback(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "FontFallback")] font_fallback: JsValue,
+    ) -> Result<(), JsError> {
+        let font_fallback: ironcalc_base::fonts::FontFallback =
+            serde_wasm_bindgen::from_value(font_fallback)
+                .map_err(|e| to_js_error(e.to_string()))?;
+        self.model.set_font_fallback(font_fallback);
+        Ok(())
+    }
+
+    /// Returns the name of the font that should be used to render `font`.
+    #[wasm_bindgen(js_name = "getRenderingFontName")]
+    pub fn get_rendering_font_name(
+        &self,
+        #[wasm_bindgen(unchecked_param_type = "CellStyleFont")] font: JsValue,
+    ) -> Result<String, JsError> {
+        let font: ironcalc_base::types::Font =
+            serde_wasm_bindgen::from_value(font).map_err(|e| to_js_error(e.to_string()))?;
+        Ok(self.model.get_rendering_font_name(&font))
+    }
+
     #[wasm_bindgen(js_name = "autoFillRows")]
     pub fn auto_fill_rows(
         &mut self,
@@ -842,6 +1186,26 @@ impl Model {
             .map_err(|e| to_js_error(e.to_string()))
     }
 
+    #[wasm_bindgen(js_name = "pasteSpecial")]
+    pub fn paste_special(
+        &mut self,
+        source_sheet: u32,
+        #[wasm_bindgen(unchecked_param_type = "[number, number, number, number]")]
+        source_range: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "ClipboardData")] clipboard: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "PasteSpecialOptions")] options: JsValue,
+    ) -> Result<(), JsError> {
+        let source_range: (i32, i32, i32, i32) =
+            serde_wasm_bindgen::from_value(source_range).map_err(|e| to_js_error(e.to_string()))?;
+        let clipboard: ClipboardData =
+            serde_wasm_bindgen::from_value(clipboard).map_err(|e| to_js_error(e.to_string()))?;
+        let options: PasteSpecialOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .paste_special(source_sheet, source_range, &clipboard, &options)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
     #[wasm_bindgen(js_name = "pasteCsvText")]
     pub fn paste_csv_string(
         &mut self,
@@ -855,6 +1219,179 @@ impl Model {
             .map_err(|e| to_js_error(e.to_string()))
     }
 
+    #[wasm_bindgen(js_name = "copyToHtml")]
+    pub fn copy_to_html(&self) -> Result<String, JsError> {
+        self.model
+            .copy_to_html()
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "pasteHtml")]
+    pub fn paste_html(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Area")] area: JsValue,
+        html: &str,
+    ) -> Result<(), JsError> {
+        let range: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .paste_html(&range, html)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "find", unchecked_return_type = "FindHit[]")]
+    pub fn find(
+        &self,
+        query: &str,
+        #[wasm_bindgen(unchecked_param_type = "FindOptions")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        let hits: Vec<FindHit> = self
+            .model
+            .find(query, &options)
+            .map_err(to_js_error)?
+            .collect();
+        serde_wasm_bindgen::to_value(&hits).map_err(|e| to_js_error(e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "replaceAll")]
+    pub fn replace_all(
+        &mut self,
+        query: &str,
+        replacement: &str,
+        #[wasm_bindgen(unchecked_param_type = "FindOptions")] options: JsValue,
+    ) -> Result<usize, JsError> {
+        let options: FindOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .replace_all(query, replacement, &options)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "goalSeek", unchecked_return_type = "GoalSeekResult")]
+    pub fn goal_seek(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "CellReferenceIndex")] target: JsValue,
+        target_value: f64,
+        #[wasm_bindgen(unchecked_param_type = "CellReferenceIndex")] changing: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "GoalSeekOptions")] options: JsValue,
+    ) -> Result<JsValue, JsError> {
+        let target: CellReferenceIndex =
+            serde_wasm_bindgen::from_value(target).map_err(|e| to_js_error(e.to_string()))?;
+        let changing: CellReferenceIndex =
+            serde_wasm_bindgen::from_value(changing).map_err(|e| to_js_error(e.to_string()))?;
+        let options: GoalSeekOptions =
+            serde_wasm_bindgen::from_value(options).map_err(|e| to_js_error(e.to_string()))?;
+        let result = self
+            .model
+            .goal_seek(&target, target_value, &changing, &options)
+            .map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&result).map_err(|e| to_js_error(e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "createDataTable")]
+    pub fn create_data_table(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "Area")] area: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "CellReferenceIndex | null")] row_input: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "CellReferenceIndex | null")] column_input: JsValue,
+    ) -> Result<(), JsError> {
+        let area: Area =
+            serde_wasm_bindgen::from_value(area).map_err(|e| to_js_error(e.to_string()))?;
+        let row_input: Option<CellReferenceIndex> =
+            serde_wasm_bindgen::from_value(row_input).map_err(|e| to_js_error(e.to_string()))?;
+        let column_input: Option<CellReferenceIndex> =
+            serde_wasm_bindgen::from_value(column_input).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .create_data_table(&area, row_input.as_ref(), column_input.as_ref())
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "getScenarios", unchecked_return_type = "Scenario[]")]
+    pub fn get_scenarios(&self, sheet: u32) -> Result<JsValue, JsError> {
+        let scenarios = self.model.get_scenarios(sheet).map_err(to_js_error)?;
+        serde_wasm_bindgen::to_value(&scenarios).map_err(|e| to_js_error(e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "addScenario")]
+    pub fn add_scenario(
+        &mut self,
+        sheet: u32,
+        #[wasm_bindgen(unchecked_param_type = "Scenario")] scenario: JsValue,
+    ) -> Result<(), JsError> {
+        let scenario: Scenario =
+            serde_wasm_bindgen::from_value(scenario).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .add_scenario(sheet, scenario)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "updateScenario")]
+    pub fn update_scenario(
+        &mut self,
+        sheet: u32,
+        name: &str,
+        #[wasm_bindgen(unchecked_param_type = "Scenario")] scenario: JsValue,
+    ) -> Result<(), JsError> {
+        let scenario: Scenario =
+            serde_wasm_bindgen::from_value(scenario).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .update_scenario(sheet, name, scenario)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "deleteScenario")]
+    pub fn delete_scenario(&mut self, sheet: u32, name: &str) -> Result<(), JsError> {
+        self.model.delete_scenario(sheet, name).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "showScenario")]
+    pub fn show_scenario(&mut self, sheet: u32, name: &str) -> Result<(), JsError> {
+        self.model.show_scenario(sheet, name).map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(js_name = "createScenarioSummary")]
+    pub fn create_scenario_summary(
+        &mut self,
+        sheet: u32,
+        #[wasm_bindgen(unchecked_param_type = "CellReferenceIndex[]")] result_cells: JsValue,
+    ) -> Result<u32, JsError> {
+        let result_cells: Vec<CellReferenceIndex> =
+            serde_wasm_bindgen::from_value(result_cells).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .create_scenario_summary(sheet, &result_cells)
+            .map_err(to_js_error)
+    }
+
+    #[wasm_bindgen(
+        js_name = "copyToPortableClipboard",
+        unchecked_return_type = "PortableClipboard"
+    )]
+    pub fn copy_to_portable_clipboard(&self) -> Result<JsValue, JsError> {
+        let data = self
+            .model
+            .copy_to_portable_clipboard()
+            .map_err(|e| to_js_error(e.to_string()))?;
+
+        serde_wasm_bindgen::to_value(&data).map_err(|e| to_js_error(e.to_string()))
+    }
+
+    #[wasm_bindgen(js_name = "pastePortableClipboard")]
+    pub fn paste_portable_clipboard(
+        &mut self,
+        #[wasm_bindgen(unchecked_param_type = "PortableClipboard")] clipboard: JsValue,
+        #[wasm_bindgen(unchecked_param_type = "PortableReferences")] references: JsValue,
+    ) -> Result<(), JsError> {
+        let clipboard: PortableClipboard =
+            serde_wasm_bindgen::from_value(clipboard).map_err(|e| to_js_error(e.to_string()))?;
+        let references: PortableReferences =
+            serde_wasm_bindgen::from_value(references).map_err(|e| to_js_error(e.to_string()))?;
+        self.model
+            .paste_portable_clipboard(&clipboard, references)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
     #[wasm_bindgen(
         js_name = "getDefinedNameList",
         unchecked_return_type = "DefinedName[]"
@@ -957,6 +1494,41 @@ impl Model {
             .map_err(|e| to_js_error(e.to_string()))
     }
 
+    #[wasm_bindgen(js_name = "setDate1904")]
+    pub fn set_date1904(&mut self, date1904: bool) -> Result<(), JsError> {
+        self.model
+            .set_date1904(date1904)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
+    /// Changes the date system keeping the dates displayed in date formatted cells
+    #[wasm_bindgen(js_name = "convertDateSystem")]
+    pub fn convert_date_system(&mut self, date1904: bool) -> Result<(), JsError> {
+        self.model
+            .convert_date_system(date1904)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
+    /// Returns true if the workbook uses the 1904 date system
+    #[wasm_bindgen(js_name = "getDate1904")]
+    pub fn get_date1904(&self) -> bool {
+        self.model.get_date1904()
+    }
+
+    /// Sets the reference style, R1C1 if `r1c1` is true and A1 otherwise
+    #[wasm_bindgen(js_name = "setR1C1")]
+    pub fn set_r1c1(&mut self, r1c1: bool) -> Result<(), JsError> {
+        self.model
+            .set_r1c1(r1c1)
+            .map_err(|e| to_js_error(e.to_string()))
+    }
+
+    /// Returns true if formulas are entered and displayed in R1C1 reference style
+    #[wasm_bindgen(js_name = "getR1C1")]
+    pub fn get_r1c1(&self) -> bool {
+        self.model.get_r1c1()
+    }
+
     /// Gets the timezone of the model
     #[wasm_bindgen(js_name = "getTimezone")]
     pub fn get_timezone(&self) -> String {
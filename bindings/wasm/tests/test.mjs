@@ -197,3 +197,13 @@ test("move columns", () => {
     assert.strictEqual(model.getCellContent(0, 6, 5), "=SUM(H3:H7)");
     assert.strictEqual(model.getCellContent(0, 7, 5), "=SUM(G3:G7)");
 });
+
+test("custom functions", () => {
+    const model = new Model('Workbook1', 'en', 'UTC', 'en');
+    model.registerFunction("DOUBLE", 1, 1, false, (args) => args[0] * 2);
+    model.setUserInput(0, 1, 1, "=DOUBLE(21)");
+    model.setUserInput(0, 1, 2, "=DOUBLE(\"a\")");
+    assert.strictEqual(model.getFormattedCellValue(0, 1, 1), "42");
+    // "a" * 2 is NaN
+    assert.strictEqual(model.getFormattedCellValue(0, 1, 2), "#NUM!");
+});
@@ -6,6 +6,8 @@ export interface Area {
   height: number;
 }
 
+export type ShiftDirection = "right" | "down" | "left" | "up";
+
 export enum BorderType {
   All = "All",
   Inner = "Inner",
@@ -116,6 +118,115 @@ export type CellArrayStructure =
   | { ArrayAnchor: [number, number] }
   | { ArrayChild: [number, number, number, number] };
 
+export interface OutlineGroup {
+  start: number;
+  end: number;
+  level: number;
+  collapsed: boolean;
+}
+
+export type FunctionCategory =
+  | "logical"
+  | "math_and_trigonometry"
+  | "information"
+  | "lookup_and_reference"
+  | "text"
+  | "statistical"
+  | "compatibility"
+  | "date_and_time"
+  | "financial"
+  | "engineering"
+  | "database";
+
+export type ArgumentType =
+  | "any"
+  | "number"
+  | "text"
+  | "logical"
+  | "reference"
+  | "array"
+  | "lambda";
+
+export interface FunctionParameter {
+  name: string;
+  argument_type: ArgumentType;
+  optional: boolean;
+  repeating: boolean;
+}
+
+export interface FunctionMetadata {
+  name: string;
+  category: FunctionCategory;
+  parameters: FunctionParameter[];
+  return_type: ArgumentType;
+  volatile: boolean;
+  description: string;
+}
+
+export interface ActiveArgument {
+  function: string;
+  index: number;
+}
+
+export type EvaluationValue =
+  | { type: "Number"; value: number }
+  | { type: "Text"; value: string }
+  | { type: "Boolean"; value: boolean }
+  | { type: "Error"; error: string; origin: string; message: string }
+  | { type: "Range"; reference: string }
+  | { type: "Array"; values: EvaluationValue[][] }
+  | { type: "Empty" }
+  | { type: "Lambda" };
+
+export interface EvaluationStep {
+  expression: string;
+  value: EvaluationValue;
+  level: number;
+  step_into: { sheet: number; row: number; column: number } | null;
+}
+
+export type FunctionValue =
+  | number
+  | string
+  | boolean
+  | null
+  | { error: string }
+  | FunctionValue[][];
+
+export type ErrorCheckRule =
+  | "number_stored_as_text"
+  | "inconsistent_formula"
+  | "formula_omits_adjacent_cells"
+  | "empty_cell_reference"
+  | "unlocked_formula"
+  | "two_digit_year"
+  | "implicit_intersection";
+
+export type QuickFix =
+  | { type: "SetContent"; content: string }
+  | { type: "LockCell" }
+  | { type: "GoToCell"; sheet: number; row: number; column: number };
+
+export interface CellDiagnostic {
+  sheet: number;
+  row: number;
+  column: number;
+  rule: ErrorCheckRule;
+  message: string;
+  quick_fix: QuickFix | null;
+}
+
+export interface TracedReference {
+  sheet: number;
+  row: number;
+  column: number;
+  width: number;
+  height: number;
+  depth: number;
+  name: string | null;
+  dynamic: boolean;
+}
+
 export interface WorksheetProperties {
   name: string;
   /** Tab color. Absent when Color::None. */
@@ -134,11 +245,52 @@ export interface WorksheetProperties {
  */
 export type Color = string | [number, number] | undefined;
 
+export type PatternType =
+  | "none"
+  | "solid"
+  | "mediumGray"
+  | "darkGray"
+  | "lightGray"
+  | "darkHorizontal"
+  | "darkVertical"
+  | "darkDown"
+  | "darkUp"
+  | "darkGrid"
+  | "darkTrellis"
+  | "lightHorizontal"
+  | "lightVertical"
+  | "lightDown"
+  | "lightUp"
+  | "lightGrid"
+  | "lightTrellis"
+  | "gray125"
+  | "gray0625";
+
+export interface GradientStop {
+  position: number;
+  color: Color;
+}
+
+export interface GradientFill {
+  gradient_type: "linear" | "path";
+  degree: number;
+  left: number;
+  right: number;
+  top: number;
+  bottom: number;
+  stops: GradientStop[];
+}
+
 interface CellStyleFill {
+  /** Solid colour, or the colour of the pattern. */
   color?: Color;
+  /** Absent for solid fills. */
+  pattern_type?: PatternType;
+  bg_color?: Color;
+  gradient?: GradientFill;
 }
 
-interface CellStyleFont {
+export interface CellStyleFont {
   u: boolean;
   b: boolean;
   i: boolean;
@@ -147,9 +299,27 @@ interface CellStyleFont {
   color?: Color;
   name: string;
   family: number;
+  charset?: number;
   scheme: string;
 }
 
+/**
+ * Maps the fonts stored in the workbook to fonts that the browser can render.
+ * Font names are compared case insensitively.
+ */
+export interface FontFallback {
+  /** Fonts that can be rendered as they are */
+  available: string[];
+  /** Explicit substitutions, e.g. { Calibri: "Carlito" } */
+  substitutions: Record<string, string>;
+  /** Fallback for serif fonts (family 1) */
+  serif_font: string;
+  /** Fallback for monospaced fonts (family 3) */
+  monospace_font: string;
+  /** Font used when nothing else matches */
+  default_font: string;
+}
+
 export interface BorderOptions {
   color?: Color;
   style: BorderStyle;
@@ -200,10 +370,22 @@ export type HorizontalAlignment =
   | "fill"
   | "justify";
 
+export type ReadingOrder = "contextDependent" | "leftToRight" | "rightToLeft";
+
 interface Alignment {
   horizontal: HorizontalAlignment;
   vertical: VerticalAlignment;
   wrap_text: boolean;
+  text_rotation?: number;
+  indent?: number;
+  shrink_to_fit?: boolean;
+  justify_last_line?: boolean;
+  reading_order?: ReadingOrder;
+}
+
+interface Protection {
+  locked: boolean;
+  hidden?: boolean;
 }
 
 export interface CellStyle {
@@ -214,6 +396,7 @@ export interface CellStyle {
   border: CellStyleBorder;
   num_fmt: string;
   alignment?: Alignment;
+  protection?: Protection;
 }
 
 export type ValueOperator =
@@ -352,6 +535,9 @@ export interface DxfFont {
 
 export interface DxfFill {
   color?: Color;
+  pattern_type?: PatternType;
+  bg_color?: Color;
+  gradient?: GradientFill;
 }
 
 export interface DxfBorderItem {
@@ -378,6 +564,11 @@ export interface DxfAlignment {
   horizontal?: HorizontalAlignment;
   vertical?: VerticalAlignment;
   wrap_text?: boolean;
+  text_rotation?: number;
+  indent?: number;
+  shrink_to_fit?: boolean;
+  justify_last_line?: boolean;
+  reading_order?: ReadingOrder;
 }
 
 export interface Dxf {
@@ -461,6 +652,9 @@ type ClipboardData = Map<number, Map <number, ClipboardCell>>;
 
 export interface ClipboardCell {
   text: string;
+  value: string;
+  number: number | null;
+  is_spill: boolean;
   style: CellStyle;
 }
 
@@ -470,6 +664,112 @@ export interface Clipboard {
   range: [number, number, number, number];
 }
 
+export type PasteMode =
+  | "all"
+  | "values"
+  | "formulas"
+  | "formats"
+  | "formulas_and_number_formats"
+  | "column_widths"
+  | "link";
+
+export type PasteOperation = "none" | "add" | "subtract" | "multiply" | "divide";
+
+export interface PasteSpecialOptions {
+  mode: PasteMode;
+  operation: PasteOperation;
+  skip_blanks: boolean;
+  transpose: boolean;
+}
+
+export type PortableValue =
+  | { type: "empty" }
+  | { type: "number"; value: number }
+  | { type: "boolean"; value: boolean }
+  | { type: "text"; value: string }
+  | { type: "error"; value: string };
+
+export interface PortableCell {
+  row: number;
+  column: number;
+  formula: string | null;
+  value: PortableValue;
+  is_spill: boolean;
+  style: CellStyle;
+}
+
+export interface PortableDefinedName {
+  name: string;
+  local: boolean;
+  formula: string;
+}
+
+export interface PortableClipboard {
+  workbook: string;
+  sheet: string;
+  range: [number, number, number, number];
+  cells: PortableCell[];
+  defined_names: PortableDefinedName[];
+}
+
+export type PortableReferences = "rewrite" | "external" | "values";
+
+export type FindLookIn = "values" | "formulas" | "comments";
+
+export type FindOrder = "by_rows" | "by_columns";
+
+export type FindScope = "selection" | "sheet" | "workbook";
+
+export interface FindOptions {
+  match_case: boolean;
+  whole_cell: boolean;
+  regex: boolean;
+  look_in: FindLookIn;
+  order: FindOrder;
+  scope: FindScope;
+}
+
+export interface FindHit {
+  sheet: number;
+  row: number;
+  column: number;
+  text: string;
+}
+
+export interface CellReferenceIndex {
+  sheet: number;
+  row: number;
+  column: number;
+}
+
+export interface GoalSeekOptions {
+  max_iterations: number;
+  tolerance: number;
+}
+
+export interface GoalSeekResult {
+  converged: boolean;
+  iterations: number;
+  value: number;
+  error: number;
+}
+
+export interface ScenarioInputCell {
+  row: number;
+  column: number;
+  /** As typed in English with formulas in A1 style, whatever the locale of the workbook. */
+  value: string;
+}
+
+export interface Scenario {
+  name: string;
+  comment: string;
+  user: string;
+  locked: boolean;
+  hidden: boolean;
+  input_cells: ScenarioInputCell[];
+}
+
 export interface DefinedName {
   name: string;
   scope?: number;
@@ -499,6 +499,19 @@ pub struct Functions {
     pub multinomial: String,
     pub seriessum: String,
     pub sumproduct: String,
+    pub table: String,
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Clone)]
+pub struct References {
+    pub row: String,
+    pub column: String,
+}
+
+#[derive(Encode, Decode, Serialize, Deserialize, Clone)]
+pub struct Catalogue {
+    pub parameters: BTreeMap<String, String>,
+    pub descriptions: BTreeMap<String, String>,
 }
 
 #[derive(Encode, Decode, Serialize, Deserialize, Clone)]
@@ -508,6 +521,8 @@ pub struct Language {
     pub booleans: Booleans,
     pub errors: Errors,
     pub functions: Functions,
+    pub references: References,
+    pub catalogue: Catalogue,
 }
 
 fn main() {
@@ -530,15 +530,22 @@ const Workbook = (props: { model: Model; workbookState: WorkbookState }) => {
         }
         const mimeTypes = [
           "application/json",
+          "text/html",
           "text/plain",
           "text/csv",
-          "text/html",
         ];
         let mimeType = null;
         let value = null;
         for (let index = 0; index < mimeTypes.length; index += 1) {
           mimeType = mimeTypes[index];
           value = event.clipboardData.getData(mimeType);
+          // Only tables are pasted as HTML, other HTML is pasted as text
+          if (
+            mimeType === "text/html" &&
+            !/<table|google-sheets-html-origin/i.test(value)
+          ) {
+            value = null;
+          }
           if (value) {
             break;
           }
@@ -574,7 +581,7 @@ const Workbook = (props: { model: Model; workbookState: WorkbookState }) => {
           } catch (e) {
             setAlertDialogMessage(`${e}`);
           }
-        } else if (mimeType === "text/plain") {
+        } else if (mimeType === "text/html" || mimeType === "text/plain") {
           const {
             sheet,
             range: [rowStart, columnStart, rowEnd, columnEnd],
@@ -589,7 +596,11 @@ const Workbook = (props: { model: Model; workbookState: WorkbookState }) => {
             height: Math.abs(rowEnd - rowStart) + 1,
           };
           try {
-            model.pasteCsvText(range, value);
+            if (mimeType === "text/html") {
+              model.pasteHtml(range, value);
+            } else {
+              model.pasteCsvText(range, value);
+            }
             setRedrawId((id) => id + 1);
           } catch (e) {
             setAlertDialogMessage(`${e}`);
@@ -635,6 +646,7 @@ const Workbook = (props: { model: Model; workbookState: WorkbookState }) => {
         });
         lastClipboardJson.current = clipboardJsonStr;
         event.clipboardData.setData("text/plain", data.csv.trim());
+        event.clipboardData.setData("text/html", model.copyToHtml());
         event.clipboardData.setData("application/json", clipboardJsonStr);
         event.preventDefault();
         event.stopPropagation();
@@ -674,6 +686,7 @@ const Workbook = (props: { model: Model; workbookState: WorkbookState }) => {
         });
         lastClipboardJson.current = clipboardJsonStr;
         event.clipboardData.setData("text/plain", data.csv);
+        event.clipboardData.setData("text/html", model.copyToHtml());
         event.clipboardData.setData("application/json", clipboardJsonStr);
         workbookState.setCutRange({
           sheet: model.getSelectedSheet(),
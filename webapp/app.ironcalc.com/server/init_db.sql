@@ -1 +1,2 @@
 CREATE TABLE IF NOT EXISTS models (hash TEXT, bytes BLOB);
+CREATE TABLE IF NOT EXISTS revisions (hash TEXT, id INTEGER, bytes BLOB, timestamp INTEGER, author TEXT);
@@ -2,12 +2,45 @@ use std::io;
 
 use rocket_db_pools::Connection;
 
+use ironcalc::base::get_milliseconds_since_epoch;
+use rocket::serde::Serialize;
+use rocket_db_pools::sqlx::{SqliteConnection, SqlitePool};
 use rocket_db_pools::{sqlx, Database};
 
+use crate::room::{StoredRoom, Update};
+
 #[derive(Database)]
 #[database("ironcalc")]
 pub struct IronCalcDB(sqlx::SqlitePool);
 
+/// Changes to the tables of `init_db.sql`, in order. The number of changes already applied to a database is
+/// its `user_version`, so databases created by earlier versions of the server are brought up to date.
+const MIGRATIONS: &[&str] = &[
+    // Real-time collaboration rooms
+    "ALTER TABLE models ADD COLUMN revision INTEGER NOT NULL DEFAULT 0;
+     ALTER TABLE models ADD COLUMN session BLOB;
+     CREATE TABLE IF NOT EXISTS diffs (hash TEXT, revision INTEGER, bytes BLOB);",
+];
+
+/// Creates the tables if they do not exist and applies the migrations the database is missing
+pub async fn migrate(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    sqlx::raw_sql(include_str!("../init_db.sql"))
+        .execute(pool)
+        .await?;
+    let (version,): (i64,) = sqlx::query_as("PRAGMA user_version")
+        .fetch_one(pool)
+        .await?;
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        // A failed migration stops the server, the database is left as it was
+        let sql = format!(
+            "BEGIN; {migration} PRAGMA user_version = {}; COMMIT;",
+            index + 1
+        );
+        sqlx::raw_sql(&sql).execute(pool).await?;
+    }
+    Ok(())
+}
+
 pub async fn get_model_list_from_db(mut db: Connection<IronCalcDB>) -> Result<Vec<String>, io::Error> {
     let row: Vec<(String, )> = sqlx::query_as("SELECT hash FROM models")
         .fetch_all(&mut **db)
@@ -19,21 +52,35 @@ pub async fn get_model_list_from_db(mut db: Connection<IronCalcDB>) -> Result<Ve
     Ok(row.into_iter().map(|s| s.0).collect())
 }
 
+/// Adds a new model. Its first revision is created with it.
 pub async fn add_model(
     mut db: Connection<IronCalcDB>,
     hash: &str,
     bytes: &[u8],
+    author: Option<&str>,
 ) -> Result<(), io::Error> {
-    sqlx::query("INSERT INTO models (hash, bytes) VALUES (?, ?)")
-        .bind(hash)
-        .bind(bytes)
-        .execute(&mut **db)
+    insert_model(&mut db, hash, bytes, author)
         .await
         .map_err(|e| {
             eprintln!("Failed to save to the database: {}", e);
             io::Error::other("Internal database error")
-        })?;
-    Ok(())
+        })
+}
+
+async fn insert_model(
+    db: &mut SqliteConnection,
+    hash: &str,
+    bytes: &[u8],
+    author: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let mut transaction = sqlx::Connection::begin(db).await?;
+    sqlx::query("INSERT INTO models (hash, bytes) VALUES (?, ?)")
+        .bind(hash)
+        .bind(bytes)
+        .execute(&mut *transaction)
+        .await?;
+    insert_revision(&mut transaction, hash, bytes, author).await?;
+    transaction.commit().await
 }
 
 pub async fn select_model(
@@ -50,3 +97,244 @@ pub async fn select_model(
         })?;
     Ok(row.map(|r| r.0))
 }
+
+/// Loads the snapshot of a model and the diffs applied after it
+pub async fn select_room(pool: &SqlitePool, hash: &str) -> Result<Option<StoredRoom>, io::Error> {
+    let row: Option<(Vec<u8>, Option<Vec<u8>>, i64)> =
+        sqlx::query_as("SELECT bytes, session, revision FROM models WHERE hash = ?")
+            .bind(hash)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| {
+                eprintln!("Failed to fetch from the database: {}", e);
+                io::Error::other("Internal database error")
+            })?;
+    let Some((snapshot, session, revision)) = row else {
+        return Ok(None);
+    };
+    let log: Vec<(i64, Vec<u8>)> = sqlx::query_as(
+        "SELECT revision, bytes FROM diffs WHERE hash = ? AND revision > ? ORDER BY revision",
+    )
+    .bind(hash)
+    .bind(revision)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| {
+        eprintln!("Failed to fetch from the database: {}", e);
+        io::Error::other("Internal database error")
+    })?;
+    Ok(Some(StoredRoom {
+        snapshot,
+        session,
+        revision: revision as u64,
+        log: log.into_iter().map(|(r, d)| (r as u64, d)).collect(),
+    }))
+}
+
+/// Saves the diffs applied to a model or, if there is one, the new snapshot as a revision.
+/// Diffs older than the snapshot are removed.
+pub async fn save_update(pool: &SqlitePool, hash: &str, update: &Update) -> Result<(), io::Error> {
+    execute_update(pool, hash, update).await.map_err(|e| {
+        eprintln!("Failed to save to the database: {}", e);
+        io::Error::other("Internal database error")
+    })
+}
+
+async fn execute_update(pool: &SqlitePool, hash: &str, update: &Update) -> Result<(), sqlx::Error> {
+    let revision = update.revision as i64;
+    match &update.snapshot {
+        Some(snapshot) => {
+            let mut transaction = pool.begin().await?;
+            sqlx::query("UPDATE models SET bytes = ?, session = ?, revision = ? WHERE hash = ?")
+                .bind(&snapshot.model)
+                .bind(&snapshot.session)
+                .bind(revision)
+                .bind(hash)
+                .execute(&mut *transaction)
+                .await?;
+            sqlx::query("DELETE FROM diffs WHERE hash = ? AND revision < ?")
+                .bind(hash)
+                .bind(revision)
+                .execute(&mut *transaction)
+                .await?;
+            // The snapshots of the room are saves of the model too
+            insert_revision(&mut transaction, hash, &snapshot.model, None).await?;
+            transaction.commit().await
+        }
+        None => {
+            sqlx::query("INSERT INTO diffs (hash, revision, bytes) VALUES (?, ?, ?)")
+                .bind(hash)
+                .bind(revision)
+                .bind(&update.diffs)
+                .execute(pool)
+                .await?;
+            Ok(())
+        }
+    }
+}
+
+/// A saved version of a model
+#[derive(Serialize)]
+#[serde(crate = "rocket::serde")]
+pub struct RevisionInfo {
+    pub id: i64,
+    /// Milliseconds since January 1, 1970
+    pub timestamp: i64,
+    pub author: Option<String>,
+}
+
+/// Lists the revisions of a model, oldest first
+pub async fn select_revisions(
+    mut db: Connection<IronCalcDB>,
+    hash: &str,
+) -> Result<Vec<RevisionInfo>, io::Error> {
+    let rows: Vec<(i64, i64, Option<String>)> =
+        sqlx::query_as("SELECT id, timestamp, author FROM revisions WHERE hash = ? ORDER BY id")
+            .bind(hash)
+            .fetch_all(&mut **db)
+            .await
+            .map_err(|e| {
+                eprintln!("Failed to fetch from the database: {}", e);
+                io::Error::other("Internal database error")
+            })?;
+    Ok(rows
+        .into_iter()
+        .map(|(id, timestamp, author)| RevisionInfo {
+            id,
+            timestamp,
+            author,
+        })
+        .collect())
+}
+
+pub async fn select_revision(
+    mut db: Connection<IronCalcDB>,
+    hash: &str,
+    id: i64,
+) -> Result<Option<Vec<u8>>, io::Error> {
+    select_revision_bytes(&mut db, hash, id).await
+}
+
+/// Loads two revisions of a model
+pub async fn select_revision_pair(
+    mut db: Connection<IronCalcDB>,
+    hash: &str,
+    from: i64,
+    to: i64,
+) -> Result<Option<(Vec<u8>, Vec<u8>)>, io::Error> {
+    let from = select_revision_bytes(&mut db, hash, from).await?;
+    let to = select_revision_bytes(&mut db, hash, to).await?;
+    Ok(from.zip(to))
+}
+
+async fn select_revision_bytes(
+    db: &mut SqliteConnection,
+    hash: &str,
+    id: i64,
+) -> Result<Option<Vec<u8>>, io::Error> {
+    fetch_revision(db, hash, id).await.map_err(|e| {
+        eprintln!("Failed to fetch from the database: {}", e);
+        io::Error::other("Internal database error")
+    })
+}
+
+async fn fetch_revision(
+    db: &mut SqliteConnection,
+    hash: &str,
+    id: i64,
+) -> Result<Option<Vec<u8>>, sqlx::Error> {
+    let row: Option<(Vec<u8>,)> =
+        sqlx::query_as("SELECT bytes FROM revisions WHERE hash = ? AND id = ?")
+            .bind(hash)
+            .bind(id)
+            .fetch_optional(db)
+            .await?;
+    Ok(row.map(|r| r.0))
+}
+
+/// Saves a new version of an existing model. Returns the new revision or `None` if there is no such model.
+pub async fn save_revision(
+    mut db: Connection<IronCalcDB>,
+    hash: &str,
+    bytes: &[u8],
+    author: Option<&str>,
+) -> Result<Option<i64>, io::Error> {
+    replace_model(&mut db, hash, bytes, author)
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to save to the database: {}", e);
+            io::Error::other("Internal database error")
+        })
+}
+
+/// Saves an earlier revision of a model as a new one.
+/// Returns the new revision or `None` if there is no such revision.
+pub async fn restore_revision(
+    mut db: Connection<IronCalcDB>,
+    hash: &str,
+    id: i64,
+    author: Option<&str>,
+) -> Result<Option<i64>, io::Error> {
+    let Some(bytes) = select_revision_bytes(&mut db, hash, id).await? else {
+        return Ok(None);
+    };
+    replace_model(&mut db, hash, &bytes, author)
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to save to the database: {}", e);
+            io::Error::other("Internal database error")
+        })
+}
+
+/// Replaces the model with a new snapshot and records it as a revision.
+/// The diffs and the session of the collaboration room are dropped and its revision is increased, so
+/// clients that reconnect load the whole model.
+async fn replace_model(
+    db: &mut SqliteConnection,
+    hash: &str,
+    bytes: &[u8],
+    author: Option<&str>,
+) -> Result<Option<i64>, sqlx::Error> {
+    let mut transaction = sqlx::Connection::begin(db).await?;
+    // The new revision of the room must be after the revisions of all the diffs it gave out
+    let updated = sqlx::query(
+        "UPDATE models SET bytes = ?, session = NULL, revision = MAX(revision, \
+         COALESCE((SELECT MAX(revision) FROM diffs WHERE hash = ?), 0)) + 1 WHERE hash = ?",
+    )
+    .bind(bytes)
+    .bind(hash)
+    .bind(hash)
+    .execute(&mut *transaction)
+    .await?;
+    if updated.rows_affected() == 0 {
+        return Ok(None);
+    }
+    sqlx::query("DELETE FROM diffs WHERE hash = ?")
+        .bind(hash)
+        .execute(&mut *transaction)
+        .await?;
+    let id = insert_revision(&mut transaction, hash, bytes, author).await?;
+    transaction.commit().await?;
+    Ok(Some(id))
+}
+
+async fn insert_revision(
+    db: &mut SqliteConnection,
+    hash: &str,
+    bytes: &[u8],
+    author: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    let (id,): (i64,) = sqlx::query_as(
+        "INSERT INTO revisions (hash, id, bytes, timestamp, author) \
+         SELECT ?, COALESCE(MAX(id), 0) + 1, ?, ?, ? FROM revisions WHERE hash = ? \
+         RETURNING id",
+    )
+    .bind(hash)
+    .bind(bytes)
+    .bind(get_milliseconds_since_epoch())
+    .bind(author)
+    .bind(hash)
+    .fetch_one(db)
+    .await?;
+    Ok(id)
+}
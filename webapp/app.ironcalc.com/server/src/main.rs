@@ -3,16 +3,37 @@ extern crate rocket;
 
 mod database;
 mod id;
+mod room;
+#[cfg(test)]
+mod test_history;
+#[cfg(test)]
+mod test_room;
 
 use std::io::{self, BufWriter, Cursor, Write};
 
-use database::{add_model, get_model_list_from_db, select_model, IronCalcDB};
+use database::{
+    add_model, get_model_list_from_db, migrate, restore_revision, save_revision, save_update,
+    select_model, select_revision, select_revision_pair, select_revisions, select_room, IronCalcDB,
+    RevisionInfo,
+};
 use ironcalc::base::Model as IModel;
+use ironcalc::compare::{diff_models, CellChange};
 use ironcalc::export::save_xlsx_to_writer;
 use ironcalc::import::load_from_xlsx_bytes;
 use rocket::data::{Data, ToByteUnit};
+use rocket::fairing::AdHoc;
+use rocket::futures::{SinkExt, StreamExt};
 use rocket::http::{ContentType, Header};
 use rocket::response::Responder;
+use rocket::serde::json::Json;
+use rocket::tokio::select;
+use rocket::figment::Figment;
+use rocket::{Build, Rocket, State};
+use rocket_db_pools::sqlx::SqlitePool;
+use rocket_ws::result::Error as WsError;
+use rocket_ws::stream::DuplexStream;
+use rocket_ws::{Channel, Message, WebSocket};
+use room::{Member, Room, Rooms};
 
 const MAX_SIZE_MB: u8 = 20;
 
@@ -74,8 +95,12 @@ async fn download(data: Data<'_>) -> io::Result<FileResponder> {
 }
 
 /// Saves the model on a file called
-#[post("/api/share", data = "<data>")]
-async fn share(db: Connection<IronCalcDB>, data: Data<'_>) -> io::Result<String> {
+#[post("/api/share?<author>", data = "<data>")]
+async fn share(
+    db: Connection<IronCalcDB>,
+    data: Data<'_>,
+    author: Option<&str>,
+) -> io::Result<String> {
     println!("start share");
     let hash = id::new_id();
     let bytes = data.open(MAX_SIZE_MB.megabytes()).into_bytes().await?;
@@ -84,7 +109,7 @@ async fn share(db: Connection<IronCalcDB>, data: Data<'_>) -> io::Result<String>
             "file was not fully uploaded",
         ));
     }
-    add_model(db, &hash, &bytes).await?;
+    add_model(db, &hash, &bytes, author).await?;
     println!("done share: '{}'", hash);
     Ok(hash)
 }
@@ -96,6 +121,86 @@ async fn get_model(db: Connection<IronCalcDB>, hash: &str) -> io::Result<Option<
     Ok(bytes)
 }
 
+/// Saves a new version of a shared model and returns its revision
+#[post("/api/model/<hash>?<author>", data = "<data>")]
+async fn save_model(
+    db: Connection<IronCalcDB>,
+    rooms: &State<Rooms>,
+    hash: &str,
+    author: Option<&str>,
+    data: Data<'_>,
+) -> io::Result<Option<String>> {
+    if rooms.is_open(hash).await {
+        return Err(io::Error::other("The model is being edited"));
+    }
+    let bytes = data.open(MAX_SIZE_MB.megabytes()).into_bytes().await?;
+    if !bytes.is_complete() {
+        return Err(io::Error::other("file was not fully uploaded"));
+    }
+    IModel::from_bytes(&bytes, "en")
+        .map_err(|e| io::Error::other(format!("Error creating model, '{e}'")))?;
+    let revision = save_revision(db, hash, &bytes, author).await?;
+    println!("Save model: '{}', revision: {:?}", hash, revision);
+    Ok(revision.map(|id| id.to_string()))
+}
+
+#[get("/api/model/<hash>/revisions")]
+async fn get_revisions(
+    db: Connection<IronCalcDB>,
+    hash: &str,
+) -> io::Result<Json<Vec<RevisionInfo>>> {
+    let revisions = select_revisions(db, hash).await?;
+    Ok(Json(revisions))
+}
+
+#[get("/api/model/<hash>/revisions/<id>")]
+async fn get_revision(
+    db: Connection<IronCalcDB>,
+    hash: &str,
+    id: i64,
+) -> io::Result<Option<Vec<u8>>> {
+    let bytes = select_revision(db, hash, id).await?;
+    println!("Select revision: '{}', {}", hash, id);
+    Ok(bytes)
+}
+
+/// Makes an earlier revision the current version of the model and returns the new revision
+#[post("/api/model/<hash>/revisions/<id>/restore?<author>")]
+async fn restore(
+    db: Connection<IronCalcDB>,
+    rooms: &State<Rooms>,
+    hash: &str,
+    id: i64,
+    author: Option<&str>,
+) -> io::Result<Option<String>> {
+    if rooms.is_open(hash).await {
+        return Err(io::Error::other("The model is being edited"));
+    }
+    let revision = restore_revision(db, hash, id, author).await?;
+    println!("Restore revision: '{}', {} as {:?}", hash, id, revision);
+    Ok(revision.map(|id| id.to_string()))
+}
+
+/// The cells that changed between two revisions of a model
+#[get("/api/model/<hash>/diff/<from>/<to>")]
+async fn get_diff(
+    db: Connection<IronCalcDB>,
+    hash: &str,
+    from: i64,
+    to: i64,
+) -> io::Result<Option<Json<Vec<CellChange>>>> {
+    let Some((bytes1, bytes2)) = select_revision_pair(db, hash, from, to).await? else {
+        return Ok(None);
+    };
+    let model1 = IModel::from_bytes(&bytes1, "en")
+        .map_err(|e| io::Error::other(format!("Error creating model, '{e}'")))?;
+    let model2 = IModel::from_bytes(&bytes2, "en")
+        .map_err(|e| io::Error::other(format!("Error creating model, '{e}'")))?;
+    let changes = diff_models(&model1, &model2)
+        .map_err(|e| io::Error::other(format!("Error comparing models, '{e}'")))?;
+    Ok(Some(Json(changes)))
+}
+
 #[get("/api/list")]
 async fn get_model_list(db: Connection<IronCalcDB>) -> io::Result<String> {
     let model_list = get_model_list_from_db(db).await?;
@@ -121,11 +226,152 @@ async fn upload(data: Data<'_>, name: &str) -> io::Result<Vec<u8>> {
     Ok(model.to_bytes())
 }
 
+/// Joins the real-time collaboration room of a shared model.
+/// A client that reconnects sends the last revision it saw and gets the diffs it missed.
+#[get("/api/room/<hash>?<revision>")]
+async fn join_room(
+    ws: WebSocket,
+    db: &State<IronCalcDB>,
+    rooms: &State<Rooms>,
+    hash: &str,
+    revision: Option<u64>,
+) -> io::Result<Option<Channel<'static>>> {
+    let pool = SqlitePool::clone(db);
+    // The room is loaded before the upgrade so a missing or broken model is an HTTP error.
+    // The client only joins once the connection is upgraded.
+    let room = if rooms.is_open(hash).await {
+        None
+    } else {
+        match load_room(&pool, hash).await? {
+            Some(room) => Some(room),
+            None => return Ok(None),
+        }
+    };
+    let rooms = Rooms::clone(rooms);
+    let hash = hash.to_string();
+    Ok(Some(ws.channel(move |stream| {
+        Box::pin(async move {
+            let member = match rooms.join(&hash, revision).await {
+                Some(member) => member,
+                None => {
+                    // The room might have been closed in the meantime
+                    let room = match room {
+                        Some(room) => room,
+                        None => match load_room(&pool, &hash).await? {
+                            Some(room) => room,
+                            None => return Ok(()),
+                        },
+                    };
+                    rooms.open(&hash, room, revision).await
+                }
+            };
+            println!("Join room: '{}'", hash);
+            run_member(stream, pool, rooms, hash, member).await
+        })
+    })))
+}
+
+async fn load_room(pool: &SqlitePool, hash: &str) -> io::Result<Option<Room>> {
+    let Some(stored) = select_room(pool, hash).await? else {
+        return Ok(None);
+    };
+    let room =
+        Room::load(stored).map_err(|e| io::Error::other(format!("Error loading room: '{e}'")))?;
+    Ok(Some(room))
+}
+
+/// Relays the frames of the room to the client and applies the diffs it sends until it disconnects
+async fn run_member(
+    stream: DuplexStream,
+    pool: SqlitePool,
+    rooms: Rooms,
+    hash: String,
+    member: Member,
+) -> rocket_ws::result::Result<()> {
+    let Member {
+        room,
+        client,
+        mut frames,
+    } = member;
+    let (mut sink, mut source) = stream.split();
+    let result = loop {
+        select! {
+            frame = frames.recv() => {
+                let Some(frame) = frame else {
+                    break Ok(());
+                };
+                if let Err(e) = sink.send(Message::Binary(frame.to_bytes())).await {
+                    break Err(e);
+                }
+            }
+            message = source.next() => match message {
+                Some(Ok(Message::Binary(diffs))) => {
+                    // The room is locked until the diffs are saved so they are stored in order
+                    let mut room = room.lock().await;
+                    match room.apply(client, &diffs) {
+                        Ok(update) => {
+                            if let Err(e) = save_update(&pool, &hash, &update).await {
+                                break Err(WsError::Io(e));
+                            }
+                        }
+                        Err(e) => {
+                            eprintln!("Invalid diffs in room '{}': {}", hash, e);
+                            room.resync(client);
+                        }
+                    }
+                }
+                Some(Ok(Message::Close(_))) | None => break Ok(()),
+                Some(Ok(_)) => {}
+                Some(Err(e)) => break Err(e),
+            }
+        }
+    };
+    rooms.leave(&hash, &room, client).await;
+    println!("Leave room: '{}'", hash);
+    result
+}
+
+/// The API of the server with the configuration in `figment`
+fn api(figment: Figment) -> Rocket<Build> {
+    rocket::custom(figment)
+        .attach(IronCalcDB::init())
+        .attach(AdHoc::try_on_ignite(
+            "Database migrations",
+            |rocket| async {
+                let Some(db) = IronCalcDB::fetch(&rocket) else {
+                    return Err(rocket);
+                };
+                match migrate(db).await {
+                    Ok(()) => Ok(rocket),
+                    Err(e) => {
+                        eprintln!("Failed to migrate the database: {}", e);
+                        Err(rocket)
+                    }
+                }
+            },
+        ))
+        .manage(Rooms::default())
+        .mount(
+            "/",
+            routes![
+                upload,
+                download,
+                share,
+                get_model,
+                get_model_list,
+                join_room,
+                save_model,
+                get_revisions,
+                get_revision,
+                restore,
+                get_diff
+            ],
+        )
+}
+
 #[launch]
 fn rocket() -> _ {
-    let mut rocket = rocket::build()
-        .attach(IronCalcDB::init())
-        .mount("/", routes![upload, download, share, get_model, get_model_list]);
+    let mut rocket = api(rocket::Config::figment());
 
     if let Ok(frontend_path) = std::env::var("IRONCALC_WEBAPP_DIR") {
         if !frontend_path.is_empty() {
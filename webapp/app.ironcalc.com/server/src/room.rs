@@ -0,0 +1,282 @@
+//! Real-time collaboration rooms.
+//!
+//! There is one room per shared model hash. Every client is a site of a [Collaboration] session and the
+//! room is one more site, with a server side `UserModel`. Clients send the payloads of
+//! [Collaboration::flush]. The room integrates them in its model, gives each payload the next revision
+//! number and relays it to the other clients. The sender only gets an acknowledgement. Because every site
+//! transforms concurrent operations in the same way, all of them end up with the same model, whatever the
+//! order the payloads arrive in.
+//!
+//! A client that joins gets the whole model, its site id and the state of the session to start its own
+//! [Collaboration] with.
+//!
+//! The payloads are kept in a log until there are [SNAPSHOT_INTERVAL] of them. Then the model and the
+//! session are saved as the new snapshot and the log starts again. A client that reconnects with the last
+//! revision it saw gets the payloads it missed or, if those are no longer in the log or the room was closed in
+//! the meantime, the whole model.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use ironcalc::base::collaboration::{Collaboration, SiteId};
+use ironcalc::base::UserModel;
+use rocket::tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use rocket::tokio::sync::Mutex;
+
+/// Number of payloads in the log before a new snapshot is taken
+pub const SNAPSHOT_INTERVAL: usize = 100;
+
+pub type ClientId = u64;
+
+/// A message from the room to a client
+#[derive(Debug, Clone, PartialEq)]
+pub enum Frame {
+    /// The whole model at the given revision, the site id of the client and the state of the session
+    Model {
+        revision: u64,
+        site: SiteId,
+        session: Vec<u8>,
+        bytes: Vec<u8>,
+    },
+    /// Diffs sent by another client
+    Diffs { revision: u64, diffs: Vec<u8> },
+    /// The diffs sent by this client were applied with the given revision
+    Ack { revision: u64 },
+}
+
+impl Frame {
+    /// Binary encoding used over the WebSocket: a tag byte, the revision as big endian `u64` and the payload.
+    /// The payload of a model is the site id as big endian `u32`, the length of the session as big endian
+    /// `u64`, the session and the model.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let (tag, revision) = match self {
+            Frame::Model { revision, .. } => (0, revision),
+            Frame::Diffs { revision, .. } => (1, revision),
+            Frame::Ack { revision } => (2, revision),
+        };
+        let mut bytes = vec![tag];
+        bytes.extend_from_slice(&revision.to_be_bytes());
+        match self {
+            Frame::Model {
+                site,
+                session,
+                bytes: model,
+                ..
+            } => {
+                bytes.extend_from_slice(&site.to_be_bytes());
+                bytes.extend_from_slice(&(session.len() as u64).to_be_bytes());
+                bytes.extend_from_slice(session);
+                bytes.extend_from_slice(model);
+            }
+            Frame::Diffs { diffs, .. } => bytes.extend_from_slice(diffs),
+            Frame::Ack { .. } => {}
+        }
+        bytes
+    }
+}
+
+/// The state of a room as stored in the database
+pub struct StoredRoom {
+    pub snapshot: Vec<u8>,
+    /// The collaboration session at the snapshot, `None` to start a new one
+    pub session: Option<Vec<u8>>,
+    pub revision: u64,
+    /// Diffs applied after the snapshot: (revision, diffs)
+    pub log: Vec<(u64, Vec<u8>)>,
+}
+
+/// What needs to be saved after applying a payload
+pub struct Update {
+    pub revision: u64,
+    pub diffs: Vec<u8>,
+    /// The model and the session after the diffs, if a new snapshot was taken
+    pub snapshot: Option<Snapshot>,
+}
+
+pub struct Snapshot {
+    pub model: Vec<u8>,
+    pub session: Vec<u8>,
+}
+
+pub struct Room {
+    model: UserModel<'static>,
+    collaboration: Collaboration,
+    /// Revision of the last diffs applied to the model
+    revision: u64,
+    /// Revision of the last snapshot
+    snapshot_revision: u64,
+    /// Diffs applied after the last snapshot: (revision, diffs)
+    log: Vec<(u64, Vec<u8>)>,
+    clients: HashMap<ClientId, UnboundedSender<Frame>>,
+    next_client: ClientId,
+}
+
+impl Room {
+    /// Loads the snapshot and applies the diffs in the log.
+    /// Clients that were connected before load the whole model again: the site ids given after the snapshot
+    /// are not stored and might be given again.
+    pub fn load(stored: StoredRoom) -> Result<Room, String> {
+        let mut model = UserModel::from_bytes(&stored.snapshot, "en")?;
+        let mut collaboration = match &stored.session {
+            Some(session) => Collaboration::from_bytes(session)?,
+            None => Collaboration::new(0, &mut model),
+        };
+        let mut revision = stored.revision;
+        for (diff_revision, diffs) in &stored.log {
+            if *diff_revision != revision + 1 {
+                return Err(format!("Missing diffs for revision {}", revision + 1));
+            }
+            collaboration.receive(&mut model, diffs)?;
+            revision = *diff_revision;
+        }
+        Ok(Room {
+            model,
+            collaboration,
+            revision,
+            snapshot_revision: revision,
+            log: Vec::new(),
+            clients: HashMap::new(),
+            next_client: 0,
+        })
+    }
+
+    /// Adds a client to the room. `revision` is the last one the client saw, if it is reconnecting.
+    /// The frames that bring the client up to date are already queued in the receiver.
+    pub fn join(&mut self, revision: Option<u64>) -> (ClientId, UnboundedReceiver<Frame>) {
+        let (sender, receiver) = unbounded_channel();
+        match revision {
+            Some(revision) if revision >= self.snapshot_revision && revision <= self.revision => {
+                for (diff_revision, diffs) in &self.log {
+                    if *diff_revision > revision {
+                        let _ = sender.send(Frame::Diffs {
+                            revision: *diff_revision,
+                            diffs: diffs.clone(),
+                        });
+                    }
+                }
+            }
+            _ => {
+                let _ = sender.send(self.model_frame());
+            }
+        }
+        let client = self.next_client;
+        self.next_client += 1;
+        self.clients.insert(client, sender);
+        (client, receiver)
+    }
+
+    /// The whole model for a client that starts again, with a new site id
+    fn model_frame(&mut self) -> Frame {
+        let (site, session) = self.collaboration.add_site(&mut self.model);
+        Frame::Model {
+            revision: self.revision,
+            site,
+            session,
+            bytes: self.model.to_bytes(),
+        }
+    }
+
+    /// Sends the whole model to a client whose diffs could not be applied. Its local changes are lost but it
+    /// does not drift apart from the room.
+    pub fn resync(&mut self, client: ClientId) {
+        let frame = self.model_frame();
+        if let Some(sender) = self.clients.get(&client) {
+            let _ = sender.send(frame);
+        }
+    }
+
+    /// Removes a client from the room and returns true if the room is empty
+    pub fn leave(&mut self, client: ClientId) -> bool {
+        self.clients.remove(&client);
+        self.clients.is_empty()
+    }
+
+    /// Integrates the operations sent by a client and relays them to the rest of the room
+    pub fn apply(&mut self, client: ClientId, diffs: &[u8]) -> Result<Update, String> {
+        self.collaboration.receive(&mut self.model, diffs)?;
+        self.revision += 1;
+        let revision = self.revision;
+        for (id, sender) in &self.clients {
+            let frame = if *id == client {
+                Frame::Ack { revision }
+            } else {
+                Frame::Diffs {
+                    revision,
+                    diffs: diffs.to_vec(),
+                }
+            };
+            // The client might have disconnected, it will be removed when it leaves
+            let _ = sender.send(frame);
+        }
+        self.log.push((revision, diffs.to_vec()));
+        let snapshot = if self.log.len() >= SNAPSHOT_INTERVAL {
+            self.log.clear();
+            self.snapshot_revision = revision;
+            Some(Snapshot {
+                model: self.model.to_bytes(),
+                session: self.collaboration.to_bytes(),
+            })
+        } else {
+            None
+        };
+        Ok(Update {
+            revision,
+            diffs: diffs.to_vec(),
+            snapshot,
+        })
+    }
+}
+
+/// The open rooms, keyed by model hash. A room is closed when the last client leaves.
+#[derive(Default, Clone)]
+pub struct Rooms {
+    rooms: Arc<Mutex<HashMap<String, Arc<Mutex<Room>>>>>,
+}
+
+/// A client connected to a room
+pub struct Member {
+    pub room: Arc<Mutex<Room>>,
+    pub client: ClientId,
+    pub frames: UnboundedReceiver<Frame>,
+}
+
+impl Rooms {
+    pub async fn is_open(&self, hash: &str) -> bool {
+        self.rooms.lock().await.contains_key(hash)
+    }
+
+    /// Joins an open room. Returns `None` if the room is not open.
+    pub async fn join(&self, hash: &str, revision: Option<u64>) -> Option<Member> {
+        let rooms = self.rooms.lock().await;
+        let room = rooms.get(hash)?.clone();
+        let (client, frames) = room.lock().await.join(revision);
+        Some(Member {
+            room,
+            client,
+            frames,
+        })
+    }
+
+    /// Opens a room loaded from the database and joins it. If it was opened in the meantime that one is used.
+    pub async fn open(&self, hash: &str, room: Room, revision: Option<u64>) -> Member {
+        let mut rooms = self.rooms.lock().await;
+        let room = rooms
+            .entry(hash.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(room)))
+            .clone();
+        let (client, frames) = room.lock().await.join(revision);
+        Member {
+            room,
+            client,
+            frames,
+        }
+    }
+
+    /// Removes a client from the room and closes the room if it is empty
+    pub async fn leave(&self, hash: &str, room: &Arc<Mutex<Room>>, client: ClientId) {
+        let mut rooms = self.rooms.lock().await;
+        if room.lock().await.leave(client) {
+            rooms.remove(hash);
+        }
+    }
+}
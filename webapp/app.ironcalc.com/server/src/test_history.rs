@@ -0,0 +1,244 @@
+#![allow(clippy::unwrap_used)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ironcalc::base::UserModel;
+use rocket::http::{Header, Status};
+use rocket::local::asynchronous::Client;
+use rocket::serde::json::Value;
+use rocket_db_pools::sqlx::{self, SqlitePool};
+
+use crate::database::{migrate, save_update, select_room};
+use crate::room::{Rooms, Snapshot, Update};
+
+static DATABASE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// The server with a new database in a temporary file, and a pool to look into the database
+struct Server {
+    client: Client,
+    pool: SqlitePool,
+    path: std::path::PathBuf,
+}
+
+impl Server {
+    async fn start() -> Server {
+        Server::start_with(include_str!("../init_db.sql")).await
+    }
+
+    /// Starts the server on a database created with `sql`
+    async fn start_with(sql: &str) -> Server {
+        let name = format!(
+            "ironcalc_test_{}_{}.sqlite",
+            std::process::id(),
+            DATABASE_COUNT.fetch_add(1, Ordering::SeqCst)
+        );
+        let path = std::env::temp_dir().join(name);
+        let url = format!("sqlite://{}?mode=rwc", path.display());
+        let pool = SqlitePool::connect(&url).await.unwrap();
+        sqlx::raw_sql(sql).execute(&pool).await.unwrap();
+        let figment = rocket::Config::figment()
+            .merge(("databases.ironcalc.url", path.display().to_string()))
+            .merge(("log_level", "off"));
+        let client = Client::tracked(crate::api(figment)).await.unwrap();
+        Server { client, pool, path }
+    }
+
+    async fn post(&self, uri: String, body: Vec<u8>) -> String {
+        let response = self.client.post(uri).body(body).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        response.into_string().await.unwrap()
+    }
+
+    async fn get_bytes(&self, uri: String) -> Vec<u8> {
+        let response = self.client.get(uri).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        response.into_bytes().await.unwrap()
+    }
+
+    async fn get_json(&self, uri: String) -> Value {
+        let bytes = self.get_bytes(uri).await;
+        rocket::serde::json::serde_json::from_slice(&bytes).unwrap()
+    }
+
+    async fn room_revision(&self, hash: &str) -> i64 {
+        let (revision,): (i64,) = sqlx::query_as("SELECT revision FROM models WHERE hash = ?")
+            .bind(hash)
+            .fetch_one(&self.pool)
+            .await
+            .unwrap();
+        revision
+    }
+
+    async fn stop(self) {
+        self.pool.close().await;
+        drop(self.client);
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+fn model_bytes(cells: &[(i32, i32, &str)]) -> Vec<u8> {
+    let mut model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+    for (row, column, value) in cells {
+        model.set_user_input(0, *row, *column, value).unwrap();
+    }
+    model.to_bytes()
+}
+
+#[rocket::async_test]
+async fn save_list_diff_and_restore() {
+    let server = Server::start().await;
+    let first = model_bytes(&[(1, 1, "1"), (1, 2, "=A1*2")]);
+    let hash = server
+        .post("/api/share?author=Ann".to_string(), first.clone())
+        .await;
+
+    let second = model_bytes(&[(1, 1, "5"), (1, 2, "=A1*2")]);
+    let revision = server
+        .post(format!("/api/model/{hash}?author=Bob"), second.clone())
+        .await;
+    assert_eq!(revision, "2");
+
+    let revisions = server
+        .get_json(format!("/api/model/{hash}/revisions"))
+        .await;
+    let revisions = revisions.as_array().unwrap();
+    assert_eq!(revisions.len(), 2);
+    assert_eq!(revisions[0]["id"], 1);
+    assert_eq!(revisions[0]["author"], "Ann");
+    assert_eq!(revisions[1]["author"], "Bob");
+
+    let bytes = server
+        .get_bytes(format!("/api/model/{hash}/revisions/1"))
+        .await;
+    assert_eq!(bytes, first);
+
+    let changes = server.get_json(format!("/api/model/{hash}/diff/1/2")).await;
+    let changes = changes.as_array().unwrap();
+    assert_eq!(changes.len(), 2);
+    assert_eq!(changes[0]["reference"], "A1");
+    assert_eq!(changes[0]["old_content"], "1");
+    assert_eq!(changes[0]["new_content"], "5");
+    assert_eq!(changes[1]["reference"], "B1");
+    assert_eq!(changes[1]["old_value"], "2");
+    assert_eq!(changes[1]["new_value"], "10");
+
+    let revision = server
+        .post(
+            format!("/api/model/{hash}/revisions/1/restore?author=Cy"),
+            vec![],
+        )
+        .await;
+    assert_eq!(revision, "3");
+    assert_eq!(server.get_bytes(format!("/api/model/{hash}")).await, first);
+    let revisions = server
+        .get_json(format!("/api/model/{hash}/revisions"))
+        .await;
+    assert_eq!(revisions[2]["author"], "Cy");
+
+    let status = server
+        .client
+        .get(format!("/api/model/{hash}/revisions/7"))
+        .dispatch()
+        .await
+        .status();
+    assert_eq!(status, Status::NotFound);
+    server.stop().await;
+}
+
+#[rocket::async_test]
+async fn restore_after_room_diffs() {
+    let server = Server::start().await;
+    let hash = server
+        .post("/api/share".to_string(), model_bytes(&[(1, 1, "1")]))
+        .await;
+
+    // A room gave out revisions 1 to 3 and took a snapshot at 2
+    for revision in 1..=3 {
+        let update = Update {
+            revision,
+            diffs: vec![],
+            snapshot: (revision == 2).then(|| Snapshot {
+                model: model_bytes(&[(1, 1, "2")]),
+                session: vec![],
+            }),
+        };
+        save_update(&server.pool, &hash, &update).await.unwrap();
+    }
+    assert_eq!(server.room_revision(&hash).await, 2);
+    // The snapshot is a revision
+    let revisions = server
+        .get_json(format!("/api/model/{hash}/revisions"))
+        .await;
+    assert_eq!(revisions.as_array().unwrap().len(), 2);
+
+    server
+        .post(format!("/api/model/{hash}/revisions/1/restore"), vec![])
+        .await;
+    // Clients that saw revision 3 load the restored model
+    assert_eq!(server.room_revision(&hash).await, 4);
+    server.stop().await;
+}
+
+#[rocket::async_test]
+async fn databases_created_before_are_migrated() {
+    // The tables before real-time collaboration
+    let server = Server::start_with(
+        "CREATE TABLE models (hash TEXT, bytes BLOB);
+         INSERT INTO models (hash, bytes) VALUES ('old', X'00');",
+    )
+    .await;
+    let stored = select_room(&server.pool, "old").await.unwrap().unwrap();
+    assert_eq!(stored.revision, 0);
+    assert_eq!(stored.session, None);
+
+    let update = Update {
+        revision: 1,
+        diffs: vec![],
+        snapshot: Some(Snapshot {
+            model: model_bytes(&[(1, 1, "2")]),
+            session: vec![],
+        }),
+    };
+    save_update(&server.pool, "old", &update).await.unwrap();
+    assert_eq!(server.room_revision("old").await, 1);
+
+    // Migrating again changes nothing
+    migrate(&server.pool).await.unwrap();
+    assert_eq!(server.room_revision("old").await, 1);
+    server.stop().await;
+}
+
+#[rocket::async_test]
+async fn join_room_waits_for_the_upgrade() {
+    let server = Server::start().await;
+    let hash = server
+        .post("/api/share".to_string(), model_bytes(&[(1, 1, "1")]))
+        .await;
+    {
+        // The local client never upgrades the connection
+        let response = server
+            .client
+            .get(format!("/api/room/{hash}"))
+            .header(Header::new("Connection", "Upgrade"))
+            .header(Header::new("Upgrade", "websocket"))
+            .header(Header::new("Sec-WebSocket-Version", "13"))
+            .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let rooms = server.client.rocket().state::<Rooms>().unwrap();
+        assert!(!rooms.is_open(&hash).await);
+
+        let response = server
+            .client
+            .get("/api/room/missing")
+            .header(Header::new("Connection", "Upgrade"))
+            .header(Header::new("Upgrade", "websocket"))
+            .header(Header::new("Sec-WebSocket-Version", "13"))
+            .header(Header::new("Sec-WebSocket-Key", "dGhlIHNhbXBsZSBub25jZQ=="))
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::NotFound);
+    }
+    server.stop().await;
+}
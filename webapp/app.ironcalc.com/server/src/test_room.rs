@@ -0,0 +1,293 @@
+#![allow(clippy::unwrap_used)]
+
+use ironcalc::base::collaboration::Collaboration;
+use ironcalc::base::UserModel;
+
+use crate::room::{Frame, Member, Room, Rooms, StoredRoom, Update, SNAPSHOT_INTERVAL};
+
+const HASH: &str = "shared";
+
+/// Stands in for the database
+#[derive(Clone)]
+struct Store {
+    snapshot: Vec<u8>,
+    session: Option<Vec<u8>>,
+    revision: u64,
+    log: Vec<(u64, Vec<u8>)>,
+}
+
+impl Store {
+    fn new() -> Store {
+        let model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+        Store {
+            snapshot: model.to_bytes(),
+            session: None,
+            revision: 0,
+            log: vec![],
+        }
+    }
+
+    fn save(&mut self, update: Update) {
+        match update.snapshot {
+            Some(snapshot) => {
+                self.snapshot = snapshot.model;
+                self.session = Some(snapshot.session);
+                self.revision = update.revision;
+                self.log.clear();
+            }
+            None => self.log.push((update.revision, update.diffs)),
+        }
+    }
+
+    fn load(&self) -> StoredRoom {
+        StoredRoom {
+            snapshot: self.snapshot.clone(),
+            session: self.session.clone(),
+            revision: self.revision,
+            log: self.log.clone(),
+        }
+    }
+}
+
+/// A client that talks to the room in process, the way the web app does over the WebSocket
+struct Client {
+    model: UserModel<'static>,
+    collaboration: Option<Collaboration>,
+    member: Member,
+    revision: u64,
+    /// Number of full models received
+    reloads: usize,
+}
+
+/// What a client keeps when it leaves: the model, the session and the last revision it saw
+type Left = (UserModel<'static>, Option<Collaboration>, u64);
+
+impl Client {
+    async fn connect(rooms: &Rooms, store: &Store) -> Client {
+        let model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+        Client::join(rooms, store, (model, None, 0), None).await
+    }
+
+    /// Connects again with what the client had before leaving
+    async fn reconnect(rooms: &Rooms, store: &Store, left: Left) -> Client {
+        let revision = left.2;
+        Client::join(rooms, store, left, Some(revision)).await
+    }
+
+    async fn join(rooms: &Rooms, store: &Store, left: Left, revision: Option<u64>) -> Client {
+        let member = match rooms.join(HASH, revision).await {
+            Some(member) => member,
+            None => {
+                let room = Room::load(store.load()).unwrap();
+                rooms.open(HASH, room, revision).await
+            }
+        };
+        let (model, collaboration, revision) = left;
+        let mut client = Client {
+            model,
+            collaboration,
+            member,
+            revision,
+            reloads: 0,
+        };
+        client.receive();
+        client
+    }
+
+    fn receive(&mut self) {
+        while let Ok(frame) = self.member.frames.try_recv() {
+            match frame {
+                Frame::Model {
+                    revision,
+                    site,
+                    session,
+                    bytes,
+                } => {
+                    self.model = UserModel::from_bytes(&bytes, "en").unwrap();
+                    let collaboration = Collaboration::from_bytes(&session).unwrap();
+                    assert_eq!(collaboration.site_id(), site);
+                    self.collaboration = Some(collaboration);
+                    self.revision = revision;
+                    self.reloads += 1;
+                }
+                Frame::Diffs { revision, diffs } => {
+                    assert_eq!(revision, self.revision + 1);
+                    let collaboration = self.collaboration.as_mut().unwrap();
+                    collaboration.receive(&mut self.model, &diffs).unwrap();
+                    self.revision = revision;
+                }
+                Frame::Ack { revision } => self.revision = revision,
+            }
+        }
+    }
+
+    fn site(&self) -> u32 {
+        self.collaboration.as_ref().unwrap().site_id()
+    }
+
+    /// Sends the local changes without waiting for the acknowledgement
+    async fn send_only(&mut self, store: &mut Store) {
+        let collaboration = self.collaboration.as_mut().unwrap();
+        let diffs = collaboration.flush(&mut self.model);
+        let update = self
+            .member
+            .room
+            .lock()
+            .await
+            .apply(self.member.client, &diffs)
+            .unwrap();
+        store.save(update);
+    }
+
+    async fn send(&mut self, store: &mut Store) {
+        self.send_only(store).await;
+        self.receive();
+    }
+
+    async fn leave(self, rooms: &Rooms) -> Left {
+        rooms
+            .leave(HASH, &self.member.room, self.member.client)
+            .await;
+        (self.model, self.collaboration, self.revision)
+    }
+
+    fn value(&self, row: i32, column: i32) -> String {
+        self.model.get_formatted_cell_value(0, row, column).unwrap()
+    }
+}
+
+#[rocket::async_test]
+async fn diffs_are_relayed() {
+    let rooms = Rooms::default();
+    let mut store = Store::new();
+    let mut client1 = Client::connect(&rooms, &store).await;
+    let mut client2 = Client::connect(&rooms, &store).await;
+    assert_eq!(client1.reloads, 1);
+
+    client1.model.set_user_input(0, 1, 1, "21").unwrap();
+    client1.model.set_user_input(0, 1, 2, "=A1*2").unwrap();
+    client1.send(&mut store).await;
+    client2.receive();
+    assert_eq!(client1.revision, 1);
+    assert_eq!(client2.revision, 1);
+    assert_eq!(client2.value(1, 2), "42");
+
+    client2.model.insert_rows(0, 1, 1).unwrap();
+    client2.send(&mut store).await;
+    client1.receive();
+    assert_eq!(client1.revision, 2);
+    assert_eq!(client1.value(2, 2), "42");
+    assert_eq!(client1.model.get_cell_content(0, 2, 2).unwrap(), "=A2*2");
+}
+
+#[rocket::async_test]
+async fn concurrent_edits_converge() {
+    let rooms = Rooms::default();
+    let mut store = Store::new();
+    let mut client1 = Client::connect(&rooms, &store).await;
+    let mut client2 = Client::connect(&rooms, &store).await;
+    assert_ne!(client1.site(), client2.site());
+
+    // Both clients edit before getting the changes of the other one
+    client1.model.insert_rows(0, 1, 1).unwrap();
+    client2.model.set_user_input(0, 2, 1, "7").unwrap();
+    client2.model.set_user_input(0, 2, 2, "=A2*6").unwrap();
+    client1.send_only(&mut store).await;
+    client2.send_only(&mut store).await;
+    client1.receive();
+    client2.receive();
+    assert_eq!(client1.revision, 2);
+    assert_eq!(client2.revision, 2);
+
+    // A client that joins later gets the same model
+    let client3 = Client::connect(&rooms, &store).await;
+    for client in [&client1, &client2, &client3] {
+        assert_eq!(client.value(3, 1), "7");
+        assert_eq!(client.value(3, 2), "42");
+        assert_eq!(client.model.get_cell_content(0, 3, 2).unwrap(), "=A3*6");
+        assert_eq!(client.value(2, 1), "");
+    }
+}
+
+#[rocket::async_test]
+async fn invalid_diffs_are_rejected() {
+    let rooms = Rooms::default();
+    let mut store = Store::new();
+    let mut client = Client::connect(&rooms, &store).await;
+    let result = client
+        .member
+        .room
+        .lock()
+        .await
+        .apply(client.member.client, b"invalid");
+    assert_eq!(result.err(), Some("Error parsing operations".to_string()));
+
+    // The client loads the model again instead of drifting apart from the room
+    let site = client.site();
+    client.member.room.lock().await.resync(client.member.client);
+    client.receive();
+    assert_eq!(client.reloads, 2);
+    assert_ne!(client.site(), site);
+
+    // The invalid diffs did not take a revision
+    client.model.set_user_input(0, 1, 1, "1").unwrap();
+    client.send(&mut store).await;
+    assert_eq!(client.revision, 1);
+}
+
+#[rocket::async_test]
+async fn reconnect_replays_missed_diffs() {
+    let rooms = Rooms::default();
+    let mut store = Store::new();
+    let mut client1 = Client::connect(&rooms, &store).await;
+    let mut client2 = Client::connect(&rooms, &store).await;
+    client1.model.set_user_input(0, 1, 1, "1").unwrap();
+    client1.send(&mut store).await;
+    client2.receive();
+
+    let left = client2.leave(&rooms).await;
+    for row in 2..5 {
+        client1
+            .model
+            .set_user_input(0, row, 1, &row.to_string())
+            .unwrap();
+        client1.send(&mut store).await;
+    }
+
+    let client2 = Client::reconnect(&rooms, &store, left).await;
+    assert_eq!(client2.reloads, 0);
+    assert_eq!(client2.revision, 4);
+    assert_eq!(client2.value(1, 1), "1");
+    assert_eq!(client2.value(4, 1), "4");
+}
+
+#[rocket::async_test]
+async fn snapshots_and_reload() {
+    let rooms = Rooms::default();
+    let mut store = Store::new();
+    let mut client1 = Client::connect(&rooms, &store).await;
+    let client2 = Client::connect(&rooms, &store).await;
+    let left = client2.leave(&rooms).await;
+
+    for row in 1..=SNAPSHOT_INTERVAL as i32 + 2 {
+        client1.model.set_user_input(0, row, 1, "=ROW()").unwrap();
+        client1.send(&mut store).await;
+    }
+    assert_eq!(store.revision, SNAPSHOT_INTERVAL as u64);
+    assert_eq!(store.log.len(), 2);
+
+    // The diffs client2 missed are not in the log any more, it gets the whole model
+    let client2 = Client::reconnect(&rooms, &store, left).await;
+    assert_eq!(client2.reloads, 1);
+    assert_eq!(client2.revision, SNAPSHOT_INTERVAL as u64 + 2);
+    assert_eq!(client2.value(102, 1), "102");
+
+    // The room is closed and loaded again from the store
+    client1.leave(&rooms).await;
+    client2.leave(&rooms).await;
+    assert!(rooms.join(HASH, None).await.is_none());
+    let client3 = Client::connect(&rooms, &store).await;
+    assert_eq!(client3.revision, SNAPSHOT_INTERVAL as u64 + 2);
+    assert_eq!(client3.value(101, 1), "101");
+    assert_eq!(client3.value(102, 1), "102");
+}
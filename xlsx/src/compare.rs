@@ -1,11 +1,14 @@
 #![allow(clippy::unwrap_used, clippy::panic)]
 
+use std::collections::BTreeSet;
 use std::path::Path;
 
 use ironcalc_base::cell::CellValue;
 use ironcalc_base::types::*;
 use ironcalc_base::{expressions::utils::number_to_column, Model};
 
+use serde::Serialize;
+
 use crate::export::save_to_xlsx;
 use crate::import::load_from_xlsx;
 
@@ -59,146 +62,202 @@ pub fn compare(model1: &Model, model2: &Model) -> CompareResult<Vec<Diff>> {
         let sheet = cell.index;
         let row = cell.row;
         let column = cell.column;
-        let cell1 = &model1
-            .workbook
-            .worksheet(sheet)
-            .unwrap()
-            .cell(row, column)
-            .cloned()
-            .unwrap_or_default();
-        let cell2 = &model2
-            .workbook
-            .worksheet(sheet)
-            .unwrap()
-            .cell(row, column)
-            .cloned()
-            .unwrap_or_default();
-        match (cell1, cell2) {
-            (Cell::EmptyCell { .. }, Cell::EmptyCell { .. }) => {}
-
-            (Cell::NumberCell { v: value1, .. }, Cell::NumberCell { v: value2, .. }) => {
-                if !numbers_are_close(*value1, *value2, eps) {
-                    diffs.push(Diff {
-                        sheet_name: ws1[cell.index as usize].clone(),
-                        row,
-                        column,
-                        value1: cell1.clone(),
-                        value2: cell2.clone(),
-                        reason: "Numbers are different".to_string(),
-                    });
-                }
-            }
+        let cell1 = get_cell(model1, Some(sheet), row, column);
+        let cell2 = get_cell(model2, Some(sheet), row, column);
+        if let Some(reason) = compare_cells(model1, &cell1, model2, &cell2, eps) {
+            diffs.push(Diff {
+                sheet_name: ws1[cell.index as usize].clone(),
+                row,
+                column,
+                value1: cell1,
+                value2: cell2,
+                reason: reason.to_string(),
+            });
+        }
+    }
+    Ok(diffs)
+}
 
-            (Cell::BooleanCell { v: value1, .. }, Cell::BooleanCell { v: value2, .. }) => {
-                if value1 != value2 {
-                    diffs.push(Diff {
-                        sheet_name: ws1[cell.index as usize].clone(),
-                        row,
-                        column,
-                        value1: cell1.clone(),
-                        value2: cell2.clone(),
-                        reason: "Booleans are different".to_string(),
-                    });
-                }
-            }
+/// The cell of a sheet, empty if there is no such cell or sheet
+fn get_cell(model: &Model, sheet: Option<u32>, row: i32, column: i32) -> Cell {
+    sheet
+        .and_then(|sheet| model.workbook.worksheet(sheet).ok())
+        .and_then(|worksheet| worksheet.cell(row, column))
+        .cloned()
+        .unwrap_or_default()
+}
 
-            (Cell::ErrorCell { ei: value1, .. }, Cell::ErrorCell { ei: value2, .. }) => {
-                if value1 != value2 {
-                    diffs.push(Diff {
-                        sheet_name: ws1[cell.index as usize].clone(),
-                        row,
-                        column,
-                        value1: cell1.clone(),
-                        value2: cell2.clone(),
-                        reason: "Errors are different".to_string(),
-                    });
-                }
-            }
+/// Returns the reason why the values of two cells are different, if they are
+fn compare_cells(
+    model1: &Model,
+    cell1: &Cell,
+    model2: &Model,
+    cell2: &Cell,
+    eps: f64,
+) -> Option<&'static str> {
+    match (cell1, cell2) {
+        (Cell::EmptyCell { .. }, Cell::EmptyCell { .. }) => None,
 
-            (Cell::SharedString { si: value1, .. }, Cell::SharedString { si: value2, .. }) => {
-                // FIXME: compare resolved shared-string contents, not indices,
-                // if the two workbooks can have different shared-string tables.
-                if value1 != value2 {
-                    diffs.push(Diff {
-                        sheet_name: ws1[cell.index as usize].clone(),
-                        row,
-                        column,
-                        value1: cell1.clone(),
-                        value2: cell2.clone(),
-                        reason: "Strings are different".to_string(),
-                    });
+        (Cell::NumberCell { v: value1, .. }, Cell::NumberCell { v: value2, .. }) => {
+            (!numbers_are_close(*value1, *value2, eps)).then_some("Numbers are different")
+        }
+
+        (Cell::BooleanCell { v: value1, .. }, Cell::BooleanCell { v: value2, .. }) => {
+            (value1 != value2).then_some("Booleans are different")
+        }
+
+        (Cell::ErrorCell { ei: value1, .. }, Cell::ErrorCell { ei: value2, .. }) => {
+            (value1 != value2).then_some("Errors are different")
+        }
+
+        (Cell::SharedString { si: value1, .. }, Cell::SharedString { si: value2, .. }) => {
+            // The two workbooks might have different shared-string tables
+            let text1 = model1.workbook.shared_strings.get(*value1 as usize);
+            let text2 = model2.workbook.shared_strings.get(*value2 as usize);
+            (text1 != text2).then_some("Strings are different")
+        }
+
+        (
+            Cell::CellFormula { v: v1, .. } | Cell::ArrayFormula { v: v1, .. },
+            Cell::CellFormula { v: v2, .. } | Cell::ArrayFormula { v: v2, .. },
+        ) => {
+            let mismatch = match (v1, v2) {
+                (FormulaValue::Unevaluated, FormulaValue::Unevaluated) => false,
+                (FormulaValue::Boolean(a), FormulaValue::Boolean(b)) => a != b,
+                (FormulaValue::Number(a), FormulaValue::Number(b)) => {
+                    !numbers_are_close(*a, *b, eps)
                 }
-            }
+                (FormulaValue::Text(a), FormulaValue::Text(b)) => a != b,
+                (FormulaValue::Error { ei: e1, .. }, FormulaValue::Error { ei: e2, .. }) => {
+                    e1 != e2
+                }
+                // Some xlsx files store formula errors as t="str" with the error code as
+                // text instead of the correct t="e". Treat them as equivalent.
+                (FormulaValue::Text(s), FormulaValue::Error { ei, .. })
+                | (FormulaValue::Error { ei, .. }, FormulaValue::Text(s)) => s != &format!("{ei}"),
+                _ => true,
+            };
+            mismatch.then_some("Formula values are different")
+        }
 
-            (
-                Cell::CellFormula { v: v1, .. } | Cell::ArrayFormula { v: v1, .. },
-                Cell::CellFormula { v: v2, .. } | Cell::ArrayFormula { v: v2, .. },
-            ) => {
-                let mismatch = match (v1, v2) {
-                    (FormulaValue::Unevaluated, FormulaValue::Unevaluated) => false,
-                    (FormulaValue::Boolean(a), FormulaValue::Boolean(b)) => a != b,
-                    (FormulaValue::Number(a), FormulaValue::Number(b)) => {
-                        !numbers_are_close(*a, *b, eps)
-                    }
-                    (FormulaValue::Text(a), FormulaValue::Text(b)) => a != b,
-                    (FormulaValue::Error { ei: e1, .. }, FormulaValue::Error { ei: e2, .. }) => {
-                        e1 != e2
-                    }
-                    // Some xlsx files store formula errors as t="str" with the error code as
-                    // text instead of the correct t="e". Treat them as equivalent.
-                    (FormulaValue::Text(s), FormulaValue::Error { ei, .. })
-                    | (FormulaValue::Error { ei, .. }, FormulaValue::Text(s)) => {
-                        s != &format!("{ei}")
+        (Cell::SpillCell { v: v1, .. }, Cell::SpillCell { v: v2, .. }) => {
+            let mismatch = match (v1, v2) {
+                (SpillValue::Boolean(a), SpillValue::Boolean(b)) => a != b,
+                (SpillValue::Number(a), SpillValue::Number(b)) => !numbers_are_close(*a, *b, eps),
+                (SpillValue::Text(a), SpillValue::Text(b)) => a != b,
+                (SpillValue::Error(a), SpillValue::Error(b)) => a != b,
+                _ => true,
+            };
+            mismatch.then_some("Spill values are different")
+        }
+
+        (_, _) => Some("Types are different"),
+    }
+}
+
+/// A cell whose content or value changed between two versions of a workbook
+#[derive(Serialize, Debug, PartialEq)]
+pub struct CellChange {
+    pub sheet_name: String,
+    /// Reference of the cell, e.g. "B3"
+    pub reference: String,
+    pub row: i32,
+    pub column: i32,
+    /// Content of the cell as the user would type it, empty if there is no cell
+    pub old_content: String,
+    pub new_content: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// Lists the cells whose formula or value differ between two versions of a workbook, see [compare].
+/// Sheets are matched by name, a sheet that exists in only one of the versions has no cells in the other.
+pub fn diff_models(model1: &Model, model2: &Model) -> Result<Vec<CellChange>, String> {
+    let mut sheet_names = model1.workbook.get_worksheet_names();
+    for name in model2.workbook.get_worksheet_names() {
+        if !sheet_names.contains(&name) {
+            sheet_names.push(name);
+        }
+    }
+    let sheet_index = |model: &Model, name: &str| {
+        model
+            .workbook
+            .worksheets
+            .iter()
+            .position(|ws| ws.name == name)
+            .map(|index| index as u32)
+    };
+    let mut diffs = Vec::new();
+    for sheet_name in &sheet_names {
+        let sheet1 = sheet_index(model1, sheet_name);
+        let sheet2 = sheet_index(model2, sheet_name);
+        let mut cells = BTreeSet::new();
+        for (model, sheet) in [(model1, sheet1), (model2, sheet2)] {
+            if let Some(sheet) = sheet {
+                let worksheet = model.workbook.worksheet(sheet)?;
+                for (row, data_row) in &worksheet.sheet_data {
+                    for column in data_row.keys() {
+                        cells.insert((*row, *column));
                     }
-                    _ => true,
-                };
-                if mismatch {
-                    diffs.push(Diff {
-                        sheet_name: ws1[cell.index as usize].clone(),
-                        row,
-                        column,
-                        value1: cell1.clone(),
-                        value2: cell2.clone(),
-                        reason: "Formula values are different".to_string(),
-                    });
                 }
             }
-
-            (Cell::SpillCell { v: v1, .. }, Cell::SpillCell { v: v2, .. }) => {
-                let mismatch = match (v1, v2) {
-                    (SpillValue::Boolean(a), SpillValue::Boolean(b)) => a != b,
-                    (SpillValue::Number(a), SpillValue::Number(b)) => {
-                        !numbers_are_close(*a, *b, eps)
-                    }
-                    (SpillValue::Text(a), SpillValue::Text(b)) => a != b,
-                    (SpillValue::Error(a), SpillValue::Error(b)) => a != b,
-                    _ => true,
-                };
-                if mismatch {
-                    diffs.push(Diff {
-                        sheet_name: ws1[cell.index as usize].clone(),
+        }
+        for (row, column) in cells {
+            let cell1 = get_cell(model1, sheet1, row, column);
+            let cell2 = get_cell(model2, sheet2, row, column);
+            // Values are compared exactly, a formula might also change without changing its value
+            let reason = compare_cells(model1, &cell1, model2, &cell2, 0.0).or_else(|| {
+                let formula1 = sheet1.and_then(|s| model1.get_cell_formula(s, row, column).ok());
+                let formula2 = sheet2.and_then(|s| model2.get_cell_formula(s, row, column).ok());
+                (formula1.flatten() != formula2.flatten()).then_some("Formulas are different")
+            });
+            if let Some(reason) = reason {
+                diffs.push((
+                    sheet1,
+                    sheet2,
+                    Diff {
+                        sheet_name: sheet_name.clone(),
                         row,
                         column,
-                        value1: cell1.clone(),
-                        value2: cell2.clone(),
-                        reason: "Spill values are different".to_string(),
-                    });
-                }
-            }
-
-            (_, _) => {
-                diffs.push(Diff {
-                    sheet_name: ws1[cell.index as usize].clone(),
-                    row,
-                    column,
-                    value1: cell1.clone(),
-                    value2: cell2.clone(),
-                    reason: "Types are different".to_string(),
-                });
+                        value1: cell1,
+                        value2: cell2,
+                        reason: reason.to_string(),
+                    },
+                ));
             }
         }
     }
-    Ok(diffs)
+    let cell_state = |model: &Model, sheet: Option<u32>, row: i32, column: i32| match sheet {
+        Some(sheet) => Ok((
+            model.get_localized_cell_content(sheet, row, column)?,
+            model.get_formatted_cell_value(sheet, row, column)?,
+        )),
+        None => Ok::<_, String>((String::new(), String::new())),
+    };
+    let mut changes = Vec::new();
+    for (sheet1, sheet2, diff) in diffs {
+        let Diff {
+            sheet_name,
+            row,
+            column,
+            ..
+        } = diff;
+        let (old_content, old_value) = cell_state(model1, sheet1, row, column)?;
+        let (new_content, new_value) = cell_state(model2, sheet2, row, column)?;
+        let column_name =
+            number_to_column(column).ok_or_else(|| format!("Invalid column: {column}"))?;
+        changes.push(CellChange {
+            sheet_name,
+            reference: format!("{column_name}{row}"),
+            row,
+            column,
+            old_content,
+            new_content,
+            old_value,
+            new_value,
+        });
+    }
+    Ok(changes)
 }
 
 fn cell_display(cell: &Cell) -> String {
@@ -371,7 +430,7 @@ pub fn test_load_and_saving(file_path: &str, temp_dir_name: &Path) -> Result<(),
 
 #[cfg(test)]
 mod tests {
-    use crate::compare::compare;
+    use crate::compare::{compare, diff_models};
     use ironcalc_base::Model;
 
     #[test]
@@ -382,4 +441,49 @@ mod tests {
 
         assert!(compare(&model1, &model2).is_err());
     }
+
+    #[test]
+    fn diff_between_versions() {
+        let mut model1 = Model::new_empty("model", "en", "UTC", "en").unwrap();
+        model1.set_user_input(0, 1, 1, "1".to_string()).unwrap();
+        model1.set_user_input(0, 2, 1, "=A1*2".to_string()).unwrap();
+        model1.set_user_input(0, 3, 1, "Same".to_string()).unwrap();
+        model1.set_user_input(0, 1, 3, "=1+1".to_string()).unwrap();
+        model1.evaluate();
+        let mut model2 = Model::from_bytes(&model1.to_bytes(), "en").unwrap();
+        model2.set_user_input(0, 1, 1, "5".to_string()).unwrap();
+        model2.set_user_input(0, 3, 2, "New".to_string()).unwrap();
+        // Same value, different formula
+        model2.set_user_input(0, 1, 3, "=2*1".to_string()).unwrap();
+        model2.new_sheet();
+        model2.set_user_input(1, 1, 1, "Other".to_string()).unwrap();
+        model2.evaluate();
+
+        let changes = diff_models(&model1, &model2).unwrap();
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                (
+                    format!("{}!{}", c.sheet_name, c.reference),
+                    c.old_content.as_str(),
+                    c.new_content.as_str(),
+                    c.new_value.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("Sheet1!A1".to_string(), "1", "5", "5"),
+                ("Sheet1!C1".to_string(), "=1+1", "=2*1", "2"),
+                ("Sheet1!A2".to_string(), "=A1*2", "=A1*2", "10"),
+                ("Sheet1!B3".to_string(), "", "New", "New"),
+                ("Sheet2!A1".to_string(), "", "Other", "Other"),
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&changes[3]).unwrap(),
+            r#"{"sheet_name":"Sheet1","reference":"B3","row":3,"column":2,"old_content":"","new_content":"New","old_value":"","new_value":"New"}"#
+        );
+    }
 }
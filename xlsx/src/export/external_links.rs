@@ -0,0 +1,139 @@
+//! Links to other workbooks (`xl/externalLinks/externalLinkN.xml`) with the cached values of their cells.
+//! Formulas refer to them by position: `[1]Sheet1!A1`.
+
+use ironcalc_base::{
+    expressions::{
+        parser::{
+            new_parser_english, static_analysis::walk_nodes_mut, stringify::to_excel_string, Node,
+        },
+        types::CellReferenceRC,
+        utils::number_to_column,
+    },
+    types::{ExternalLink, ExternalValue, Workbook},
+};
+
+use super::{escape::escape_xml, xml_constants::XML_DECLARATION};
+
+fn get_link_position(links: &[ExternalLink], workbook: &str) -> Option<usize> {
+    links
+        .iter()
+        .position(|link| link.workbook.to_uppercase() == workbook.to_uppercase())
+}
+
+/// Replaces the names of the linked workbooks by their position: `[Budget.xlsx]Sheet1!A1` => `[1]Sheet1!A1`.
+/// Workbooks that are not in `links` are added without cached values.
+pub(crate) fn index_external_workbooks(node: &mut Node, links: &mut Vec<ExternalLink>) {
+    walk_nodes_mut(node, &mut |node| {
+        if let Node::ExternalReferenceKind { workbook, .. }
+        | Node::ExternalRangeKind { workbook, .. } = node
+        {
+            let position = match get_link_position(links, workbook) {
+                Some(position) => position,
+                None => {
+                    links.push(ExternalLink {
+                        workbook: workbook.clone(),
+                        target: workbook.clone(),
+                        sheets: Vec::new(),
+                    });
+                    links.len() - 1
+                }
+            };
+            *workbook = format!("{}", position + 1);
+        }
+    });
+}
+
+/// Same as [index_external_workbooks] for the formula of a defined name
+pub(crate) fn index_external_workbooks_in_formula(
+    formula: &str,
+    workbook: &Workbook,
+    links: &[ExternalLink],
+) -> String {
+    if !formula.contains('[') || workbook.worksheets.is_empty() {
+        return formula.to_string();
+    }
+    let worksheets = workbook.worksheets.iter().map(|s| s.name.clone()).collect();
+    let mut parser = new_parser_english(worksheets, Vec::new(), workbook.tables.clone());
+    let context = CellReferenceRC {
+        sheet: workbook.worksheets[0].name.clone(),
+        row: 1,
+        column: 1,
+    };
+    let mut node = parser.parse(formula, &context);
+    let mut changed = false;
+    walk_nodes_mut(&mut node, &mut |node| {
+        if let Node::ExternalReferenceKind { workbook, .. }
+        | Node::ExternalRangeKind { workbook, .. } = node
+        {
+            if let Some(position) = get_link_position(links, workbook) {
+                *workbook = format!("{}", position + 1);
+                changed = true;
+            }
+        }
+    });
+    if changed {
+        to_excel_string(&node, &context)
+    } else {
+        formula.to_string()
+    }
+}
+
+pub(crate) fn get_external_link_xml(link: &ExternalLink) -> String {
+    let mut sheet_names = String::new();
+    let mut sheet_data_set = String::new();
+    for (sheet_id, sheet) in link.sheets.iter().enumerate() {
+        sheet_names.push_str(&format!("<sheetName val=\"{}\"/>", escape_xml(&sheet.name)));
+        let mut rows: Vec<&i32> = sheet.cells.keys().collect();
+        rows.sort();
+        let mut rows_str = String::new();
+        for row in rows {
+            let data_row = &sheet.cells[row];
+            let mut columns: Vec<&i32> = data_row.keys().collect();
+            columns.sort();
+            let mut cells_str = String::new();
+            for column in columns {
+                let cell_name = match number_to_column(*column) {
+                    Some(column_name) => format!("{column_name}{row}"),
+                    None => continue,
+                };
+                let (t, v) = match &data_row[column] {
+                    ExternalValue::Number(value) => ("n", value.to_string()),
+                    ExternalValue::Boolean(value) => ("b", i32::from(*value).to_string()),
+                    ExternalValue::Text(value) => ("str", escape_xml(value).to_string()),
+                    ExternalValue::Error(error) => ("e", error.to_string()),
+                };
+                cells_str.push_str(&format!(
+                    "<cell r=\"{cell_name}\" t=\"{t}\"><v>{v}</v></cell>"
+                ));
+            }
+            rows_str.push_str(&format!("<row r=\"{row}\">{cells_str}</row>"));
+        }
+        sheet_data_set.push_str(&format!(
+            "<sheetData sheetId=\"{sheet_id}\">{rows_str}</sheetData>"
+        ));
+    }
+    // Both elements need at least one child
+    let sheets = if link.sheets.is_empty() {
+        "".to_string()
+    } else {
+        format!(
+            "<sheetNames>{sheet_names}</sheetNames><sheetDataSet>{sheet_data_set}</sheetDataSet>"
+        )
+    };
+    format!(
+        "{XML_DECLARATION}\n\
+        <externalLink xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+        <externalBook r:id=\"rId1\">{sheets}</externalBook>\
+        </externalLink>"
+    )
+}
+
+pub(crate) fn get_external_link_rels_xml(link: &ExternalLink) -> String {
+    format!(
+        "{XML_DECLARATION}\n\
+        <Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">\
+        <Relationship Id=\"rId1\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/externalLinkPath\" Target=\"{}\" TargetMode=\"External\"/>\
+        </Relationships>",
+        escape_xml(&link.target)
+    )
+}
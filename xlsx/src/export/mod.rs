@@ -5,6 +5,7 @@ mod conditional_formatting;
 mod doc_props;
 mod dxfs_styles;
 mod escape;
+mod external_links;
 mod shared_strings;
 mod styles;
 mod styles_util;
@@ -21,7 +22,7 @@ use std::{
 };
 
 use ironcalc_base::expressions::utils::number_to_column;
-use ironcalc_base::types::Workbook;
+use ironcalc_base::types::{ExternalLink, Workbook};
 use ironcalc_base::{get_milliseconds_since_epoch, Model};
 
 use self::xml_constants::XML_DECLARATION;
@@ -31,7 +32,7 @@ use crate::error::XlsxError;
 #[cfg(test)]
 mod test;
 
-fn get_content_types_xml(workbook: &Workbook) -> String {
+fn get_content_types_xml(workbook: &Workbook, external_links: &[ExternalLink]) -> String {
     // A list of all files in the zip
     let mut content = vec![
         r#"<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">"#.to_string(),
@@ -46,6 +47,12 @@ fn get_content_types_xml(workbook: &Workbook) -> String {
         );
         content.push(sheet);
     }
+    for link in 0..external_links.len() {
+        content.push(format!(
+            r#"<Override PartName="/xl/externalLinks/externalLink{}.xml" ContentType="application/vnd.openxmlformats-officedocument.spreadsheetml.externalLink+xml"/>"#,
+            link + 1
+        ));
+    }
     content.push(
         r#"<Override PartName="/xl/theme/theme1.xml" ContentType="application/vnd.openxmlformats-officedocument.theme+xml"/>"#.to_string(),
     );
@@ -81,11 +88,18 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
     };
     let mut zip = zip::ZipWriter::new(writer);
 
+    // In xlsx files formulas refer to linked workbooks by their position
+    let mut external_links = workbook.external_links.clone();
+    let mut parsed_formulas = model.parsed_formulas.clone();
+    for (node, _) in parsed_formulas.iter_mut().flatten() {
+        external_links::index_external_workbooks(node, &mut external_links);
+    }
+
     let options = zip::write::FileOptions::default();
 
     // root folder
     zip.start_file("[Content_Types].xml", options)?;
-    zip.write_all(get_content_types_xml(workbook).as_bytes())?;
+    zip.write_all(get_content_types_xml(workbook, &external_links).as_bytes())?;
 
     zip.add_directory("docProps", options)?;
     zip.start_file("docProps/app.xml", options)?;
@@ -105,7 +119,9 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
     zip.start_file("xl/styles.xml", options)?;
     zip.write_all(styles::get_styles_xml(workbook).as_bytes())?;
     zip.start_file("xl/workbook.xml", options)?;
-    zip.write_all(workbook::get_workbook_xml(workbook, selected_sheet).as_bytes())?;
+    zip.write_all(
+        workbook::get_workbook_xml(workbook, selected_sheet, &external_links).as_bytes(),
+    )?;
     zip.start_file("xl/metadata.xml", options)?;
     let metadata_xml = include_str!("metadata.xml");
     zip.write_all(metadata_xml.as_bytes())?;
@@ -116,7 +132,24 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
 
     zip.add_directory("xl/_rels", options)?;
     zip.start_file("xl/_rels/workbook.xml.rels", options)?;
-    zip.write_all(workbook_xml_rels::get_workbook_xml_rels(workbook).as_bytes())?;
+    zip.write_all(
+        workbook_xml_rels::get_workbook_xml_rels(workbook, external_links.len()).as_bytes(),
+    )?;
+
+    if !external_links.is_empty() {
+        zip.add_directory("xl/externalLinks", options)?;
+        zip.add_directory("xl/externalLinks/_rels", options)?;
+        for (index, link) in external_links.iter().enumerate() {
+            let id = index + 1;
+            zip.start_file(format!("xl/externalLinks/externalLink{id}.xml"), options)?;
+            zip.write_all(external_links::get_external_link_xml(link).as_bytes())?;
+            zip.start_file(
+                format!("xl/externalLinks/_rels/externalLink{id}.xml.rels"),
+                options,
+            )?;
+            zip.write_all(external_links::get_external_link_rels_xml(link).as_bytes())?;
+        }
+    }
 
     zip.add_directory("xl/worksheets", options)?;
     for (sheet_index, worksheet) in workbook.worksheets.iter().enumerate() {
@@ -136,7 +169,7 @@ pub fn save_xlsx_to_writer<W: Write + Seek>(model: &Model, writer: W) -> Result<
         zip.write_all(
             worksheets::get_worksheet_xml(
                 worksheet,
-                &model.parsed_formulas[sheet_index],
+                &parsed_formulas[sheet_index],
                 sheet_dimension_str,
                 is_sheet_selected,
             )
@@ -1,8 +1,8 @@
-use ironcalc_base::types::{Color, Styles, Workbook};
+use ironcalc_base::types::{Color, FontScheme, Styles, Workbook};
 
 use crate::export::{
     dxfs_styles::get_dxfs_xml,
-    styles_util::{get_alignment, get_border_xml, get_fill_xml},
+    styles_util::{get_alignment, get_border_xml, get_fill_xml, get_protection},
 };
 
 use super::{escape::escape_xml, xml_constants::XML_DECLARATION};
@@ -29,7 +29,14 @@ fn get_fonts_xml(styles: &Styles) -> String {
         let underline = if font.u { "<u/>" } else { "" };
         let strike = if font.strike { "<strike/>" } else { "" };
         let family = format!("<family val=\"{}\"/>", font.family);
-        let scheme = format!("<scheme val=\"{}\"/>", font.scheme);
+        let charset = match font.charset {
+            Some(charset) => format!("<charset val=\"{charset}\"/>"),
+            None => "".to_string(),
+        };
+        let scheme = match font.scheme {
+            FontScheme::None => "".to_string(),
+            _ => format!("<scheme val=\"{}\"/>", font.scheme),
+        };
         fonts_str.push(format!(
             "<font>\
                 {size}\
@@ -40,6 +47,7 @@ fn get_fonts_xml(styles: &Styles) -> String {
                 {underline}\
                 {strike}\
                 {family}\
+                {charset}\
                 {scheme}\
              </font>"
         ));
@@ -204,6 +212,11 @@ fn get_cell_xfs_xml(styles: &Styles) -> String {
         } else {
             ""
         };
+        let apply_protection_str = if cell_xf.protection.is_some() {
+            r#" applyProtection="1""#
+        } else {
+            ""
+        };
         let properties = format!(
             "xfId=\"{xf_id}\" \
                 borderId=\"{border_id}\" \
@@ -215,13 +228,20 @@ fn get_cell_xfs_xml(styles: &Styles) -> String {
                 {apply_font_str}\
                 {apply_fill_str}\
                 {apply_number_format_str}\
-                {apply_border_str}"
+                {apply_border_str}\
+                {apply_protection_str}"
         );
+        let mut children = String::new();
         if let Some(alignment) = &cell_xf.alignment {
-            let alignment = get_alignment(alignment);
-            cell_xfs_str.push(format!("<xf {properties}>{alignment}</xf>"));
-        } else {
+            children.push_str(&get_alignment(alignment));
+        }
+        if let Some(protection) = &cell_xf.protection {
+            children.push_str(&get_protection(protection));
+        }
+        if children.is_empty() {
             cell_xfs_str.push(format!("<xf {properties}/>"));
+        } else {
+            cell_xfs_str.push(format!("<xf {properties}>{children}</xf>"));
         }
     }
     let style_count = cell_xfs.len();
@@ -1,5 +1,6 @@
 use ironcalc_base::types::{
-    Alignment, Border, BorderItem, Color, Fill, HorizontalAlignment, VerticalAlignment,
+    Alignment, Border, BorderItem, Color, Fill, GradientFill, GradientType, HorizontalAlignment,
+    PatternType, Protection, ReadingOrder, VerticalAlignment,
 };
 
 pub(crate) fn get_color_xml(color: &Color, name: &str) -> String {
@@ -35,7 +36,39 @@ pub(crate) fn get_alignment(alignment: &Alignment) -> String {
     } else {
         "".to_string()
     };
-    format!("<alignment{wrap_text}{horizontal}{vertical}/>")
+    let mut extra = String::new();
+    if alignment.text_rotation != 0 {
+        extra.push_str(&format!(" textRotation=\"{}\"", alignment.text_rotation));
+    }
+    if alignment.indent != 0 {
+        extra.push_str(&format!(" indent=\"{}\"", alignment.indent));
+    }
+    if alignment.justify_last_line {
+        extra.push_str(" justifyLastLine=\"1\"");
+    }
+    if alignment.shrink_to_fit {
+        extra.push_str(" shrinkToFit=\"1\"");
+    }
+    match alignment.reading_order {
+        ReadingOrder::ContextDependent => {}
+        ReadingOrder::LeftToRight => extra.push_str(" readingOrder=\"1\""),
+        ReadingOrder::RightToLeft => extra.push_str(" readingOrder=\"2\""),
+    }
+    format!("<alignment{wrap_text}{horizontal}{vertical}{extra}/>")
+}
+
+pub(crate) fn get_protection(protection: &Protection) -> String {
+    let locked = if protection.locked {
+        ""
+    } else {
+        " locked=\"0\""
+    };
+    let hidden = if protection.hidden {
+        " hidden=\"1\""
+    } else {
+        ""
+    };
+    format!("<protection{locked}{hidden}/>")
 }
 
 fn get_border_xml_inner(border: &Option<BorderItem>, name: &str) -> String {
@@ -55,8 +88,55 @@ pub(crate) fn get_border_xml(border: &Border) -> String {
     format!("<border>{left}{right}{top}{bottom}{diagonal}</border>")
 }
 
+fn get_gradient_fill_xml(gradient: &GradientFill) -> String {
+    let mut attributes = String::new();
+    if gradient.gradient_type == GradientType::Path {
+        attributes.push_str(" type=\"path\"");
+    }
+    for (name, value) in [
+        ("degree", gradient.degree),
+        ("left", gradient.left),
+        ("right", gradient.right),
+        ("top", gradient.top),
+        ("bottom", gradient.bottom),
+    ] {
+        if value != 0.0 {
+            attributes.push_str(&format!(" {name}=\"{value}\""));
+        }
+    }
+    let stops = gradient
+        .stops
+        .iter()
+        .map(|stop| {
+            format!(
+                "<stop position=\"{}\">{}</stop>",
+                stop.position,
+                get_color_xml(&stop.color, "color")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("");
+    format!("<gradientFill{attributes}>{stops}</gradientFill>")
+}
+
+// Pattern fills other than solid or none
+fn get_pattern_fill_xml(fill: &Fill) -> String {
+    let fg_color = get_color_xml(&fill.color, "fgColor");
+    let bg_color = get_color_xml(&fill.bg_color, "bgColor");
+    format!(
+        "<patternFill patternType=\"{}\">{fg_color}{bg_color}</patternFill>",
+        fill.pattern_type
+    )
+}
+
 pub(crate) fn get_fill_xml(fill: &Fill) -> String {
-    if fill.color.is_some() {
+    if let Some(gradient) = &fill.gradient {
+        return format!("<fill>{}</fill>", get_gradient_fill_xml(gradient));
+    }
+    if !matches!(fill.pattern_type, PatternType::Solid | PatternType::None) {
+        return format!("<fill>{}</fill>", get_pattern_fill_xml(fill));
+    }
+    if fill.pattern_type == PatternType::Solid && fill.color.is_some() {
         let fg_color = get_color_xml(&fill.color, "fgColor");
         let bg_color = "<bgColor indexed=\"64\"/>".to_string();
         format!(
@@ -68,6 +148,13 @@ pub(crate) fn get_fill_xml(fill: &Fill) -> String {
 }
 
 pub(crate) fn get_dxf_fill_xml(fill: &Fill) -> String {
+    if let Some(gradient) = &fill.gradient {
+        return format!("<fill>{}</fill>", get_gradient_fill_xml(gradient));
+    }
+    if !matches!(fill.pattern_type, PatternType::Solid | PatternType::None) {
+        return format!("<fill>{}</fill>", get_pattern_fill_xml(fill));
+    }
+    // In differential formats the colour of a solid fill is the background colour
     let bg_color = get_color_xml(&fill.color, "bgColor");
     format!("<fill><patternFill>{bg_color}</patternFill></fill>")
 }
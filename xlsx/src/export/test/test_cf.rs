@@ -103,6 +103,7 @@ fn fill_dxf(color: &str) -> Dxf {
     Dxf {
         fill: Some(Fill {
             color: Color::Rgb(color.to_string()),
+            ..Default::default()
         }),
         ..Default::default()
     }
@@ -30,12 +30,17 @@
 
 use std::collections::HashMap;
 
-use ironcalc_base::types::{SheetState, Workbook};
+use ironcalc_base::types::{ExternalLink, SheetState, Workbook};
 
 use super::escape::escape_xml;
+use super::external_links::index_external_workbooks_in_formula;
 use super::xml_constants::XML_DECLARATION;
 
-pub(crate) fn get_workbook_xml(workbook: &Workbook, selected_sheet: u32) -> String {
+pub(crate) fn get_workbook_xml(
+    workbook: &Workbook,
+    selected_sheet: u32,
+    external_links: &[ExternalLink],
+) -> String {
     // sheets
     // <sheet name="Sheet1" sheetId="1" r:id="rId1"/>
     let mut sheets_str: Vec<String> = vec![];
@@ -71,25 +76,59 @@ pub(crate) fn get_workbook_xml(workbook: &Workbook, selected_sheet: u32) -> Stri
         } else {
             "".to_string()
         };
-        let formula = escape_xml(&defined_name.formula);
+        let formula =
+            index_external_workbooks_in_formula(&defined_name.formula, workbook, external_links);
+        let formula = escape_xml(&formula);
+        let hidden = if defined_name.hidden {
+            " hidden=\"1\""
+        } else {
+            ""
+        };
         defined_names_str.push(format!(
-            "<definedName name=\"{name}\"{local_sheet_id}>{formula}</definedName>"
+            "<definedName name=\"{name}\"{local_sheet_id}{hidden}>{formula}</definedName>"
         ))
     }
 
+    // <externalReference r:id="rId7"/>
+    // The relationships come after the worksheets, styles, shared strings, metadata and theme
+    let external_references = if external_links.is_empty() {
+        "".to_string()
+    } else {
+        let first_id = workbook.worksheets.len() + 5;
+        let references: Vec<String> = (0..external_links.len())
+            .map(|index| format!("<externalReference r:id=\"rId{}\"/>", first_id + index))
+            .collect();
+        format!(
+            "<externalReferences>{}</externalReferences>",
+            references.join("")
+        )
+    };
+
     let sheets = sheets_str.join("");
     let defined_names = defined_names_str.join("");
+    let workbook_pr = if workbook.settings.date1904 {
+        "<workbookPr date1904=\"1\"/>"
+    } else {
+        ""
+    };
+    let calc_pr = if workbook.settings.r1c1 {
+        "<calcPr refMode=\"R1C1\"/>"
+    } else {
+        "<calcPr/>"
+    };
     format!("{XML_DECLARATION}\n\
     <workbook xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+    {workbook_pr}\
     <bookViews>
     <workbookView activeTab=\"{selected_sheet}\"/>\
     </bookViews>
       <sheets>\
         {sheets}\
       </sheets>\
+      {external_references}\
       <definedNames>\
         {defined_names}\
       </definedNames>\
-      <calcPr/>\
+      {calc_pr}\
     </workbook>")
 }
@@ -2,7 +2,7 @@ use ironcalc_base::types::Workbook;
 
 use super::xml_constants::{XML_DECLARATION, XML_WORKSHEET};
 
-pub(crate) fn get_workbook_xml_rels(workbook: &Workbook) -> String {
+pub(crate) fn get_workbook_xml_rels(workbook: &Workbook, external_link_count: usize) -> String {
     let mut relationships_str: Vec<String> = vec![];
     let worksheet_count = workbook.worksheets.len() + 1;
     for id in 1..worksheet_count {
@@ -26,6 +26,13 @@ pub(crate) fn get_workbook_xml_rels(workbook: &Workbook) -> String {
     relationships_str.push(
         format!("<Relationship Id=\"rId{id}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/theme\" Target=\"theme/theme1.xml\"/>")
     );
+    // The ids of the external links follow, see workbook.rs
+    for link in 1..=external_link_count {
+        id += 1;
+        relationships_str.push(
+            format!("<Relationship Id=\"rId{id}\" Type=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships/externalLink\" Target=\"externalLinks/externalLink{link}.xml\"/>")
+        );
+    }
     format!(
         "{XML_DECLARATION}\n<Relationships xmlns=\"http://schemas.openxmlformats.org/package/2006/relationships\">{}</Relationships>",
         relationships_str.join("")
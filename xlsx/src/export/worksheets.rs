@@ -26,7 +26,7 @@ use ironcalc_base::{
         types::CellReferenceRC,
         utils::number_to_column,
     },
-    types::{ArrayKind, Cell, FormulaValue, SpillValue, Worksheet},
+    types::{ArrayKind, Cell, FormulaValue, OutlineProperties, Scenario, SpillValue, Worksheet},
 };
 
 use crate::export::conditional_formatting::get_conditional_formatting_xml;
@@ -63,6 +63,116 @@ fn get_formula_attribute(
     escape_xml(formula).to_string()
 }
 
+/// The `<f>` element of the anchor cell of an array formula.
+/// A data table is the array formula `TABLE(row_input, column_input)` and is saved with its input cells:
+/// <f t="dataTable" ref="D6:E7" dt2D="1" dtr="1" r1="A1" r2="A2"/>
+fn get_array_formula_element(
+    sheet_name: String,
+    row: i32,
+    column: i32,
+    parsed_formula: &Node,
+    range: &str,
+) -> String {
+    if let Node::FunctionKind { kind, args } = parsed_formula {
+        if kind.to_xlsx_string() == "TABLE" && args.len() == 2 {
+            let cell_ref = CellReferenceRC {
+                sheet: sheet_name.clone(),
+                row,
+                column,
+            };
+            // A deleted input cell is `del1="1"` (or `del2="1"`) instead of a reference
+            let input = |node: &Node, index: u8| match node {
+                Node::ErrorKind(_) | Node::WrongReferenceKind { .. } => {
+                    format!(r#"del{index}="1""#)
+                }
+                node => format!(
+                    r#"r{index}="{}""#,
+                    escape_xml(&to_excel_string(node, &cell_ref))
+                ),
+            };
+            let attributes = match (&args[0], &args[1]) {
+                (Node::EmptyArgKind, Node::EmptyArgKind) => None,
+                (row_input, Node::EmptyArgKind) => {
+                    Some(format!(r#"dt2D="0" dtr="1" {}"#, input(row_input, 1)))
+                }
+                (Node::EmptyArgKind, column_input) => {
+                    Some(format!(r#"dt2D="0" dtr="0" {}"#, input(column_input, 1)))
+                }
+                (row_input, column_input) => Some(format!(
+                    r#"dt2D="1" dtr="1" {} {}"#,
+                    input(row_input, 1),
+                    input(column_input, 2)
+                )),
+            };
+            if let Some(attributes) = attributes {
+                return format!(r#"<f t="dataTable" ref="{range}" {attributes}/>"#);
+            }
+        }
+    }
+    let formula = get_formula_attribute(sheet_name, row, column, parsed_formula);
+    format!(r#"<f t="array" ref="{range}">{formula}</f>"#)
+}
+
+// Height in points of the rows without a <row> element, the default row height in IronCalc
+const DEFAULT_ROW_HEIGHT: f64 = 16.0;
+
+fn get_outline_attributes(outline_level: i32, collapsed: bool) -> String {
+    let mut attributes = String::new();
+    if outline_level > 0 {
+        attributes.push_str(&format!(" outlineLevel=\"{outline_level}\""));
+    }
+    if collapsed {
+        attributes.push_str(" collapsed=\"1\"");
+    }
+    attributes
+}
+
+// 18.3.1.76 Scenarios
+fn get_scenarios_section(scenarios: &[Scenario]) -> String {
+    if scenarios.is_empty() {
+        return "".to_string();
+    }
+    let mut sqref: Vec<String> = Vec::new();
+    let mut scenarios_str = Vec::new();
+    for scenario in scenarios {
+        let mut input_cells_str = Vec::new();
+        for input_cell in &scenario.input_cells {
+            let column_name = number_to_column(input_cell.column).unwrap_or("A".to_string());
+            let cell_ref = format!("{column_name}{}", input_cell.row);
+            input_cells_str.push(format!(
+                "<inputCells r=\"{cell_ref}\" val=\"{}\"/>",
+                escape_xml(&input_cell.value)
+            ));
+            if !sqref.contains(&cell_ref) {
+                sqref.push(cell_ref);
+            }
+        }
+        let mut attributes = format!(" name=\"{}\"", escape_xml(&scenario.name));
+        if scenario.locked {
+            attributes.push_str(" locked=\"1\"");
+        }
+        if scenario.hidden {
+            attributes.push_str(" hidden=\"1\"");
+        }
+        attributes.push_str(&format!(" count=\"{}\"", scenario.input_cells.len()));
+        if !scenario.user.is_empty() {
+            attributes.push_str(&format!(" user=\"{}\"", escape_xml(&scenario.user)));
+        }
+        if !scenario.comment.is_empty() {
+            attributes.push_str(&format!(" comment=\"{}\"", escape_xml(&scenario.comment)));
+        }
+        scenarios_str.push(format!(
+            "<scenario{attributes}>{}</scenario>",
+            input_cells_str.join("")
+        ));
+    }
+    format!(
+        "<scenarios sqref=\"{}\">{}</scenarios>",
+        sqref.join(" "),
+        scenarios_str.join("")
+    )
+}
+
 pub(crate) fn get_worksheet_xml(
     worksheet: &Worksheet,
     parsed_formulas: &[(Node, StaticResult)],
@@ -84,8 +194,9 @@ pub(crate) fn get_worksheet_xml(
             None => "".to_string(),
         };
         let hidden = if col.hidden { " hidden=\"1\"" } else { "" };
+        let outline = get_outline_attributes(col.outline_level, col.collapsed);
         cols_str.push(format!(
-            "<col min=\"{min}\" max=\"{max}\" width=\"{width}\" customWidth=\"{custom_width}\"{column_style}{hidden}/>"
+            "<col min=\"{min}\" max=\"{max}\" width=\"{width}\" customWidth=\"{custom_width}\"{column_style}{hidden}{outline}/>"
         ));
     }
 
@@ -103,7 +214,18 @@ pub(crate) fn get_worksheet_xml(
         row_style_dict.insert(row.r, row.clone());
     }
 
-    for (row_index, row_data) in worksheet.sheet_data.iter().sorted_by_key(|x| x.0) {
+    // Rows with properties (hidden, grouped, ...) but no cells are exported as well
+    let empty_row = HashMap::new();
+    let row_indices = worksheet
+        .sheet_data
+        .keys()
+        .chain(row_style_dict.keys())
+        .copied()
+        .unique()
+        .sorted()
+        .collect::<Vec<i32>>();
+    for row_index in &row_indices {
+        let row_data = worksheet.sheet_data.get(row_index).unwrap_or(&empty_row);
         let mut row_data_str: Vec<String> = vec![];
         for (column_index, cell) in row_data.iter().sorted_by_key(|x| x.0) {
             let column_name = number_to_column(*column_index).unwrap();
@@ -294,17 +416,18 @@ pub(crate) fn get_worksheet_xml(
                         Some(node) => node,
                         None => continue,
                     };
-                    let formula = get_formula_attribute(
-                        worksheet.get_name(),
-                        *row_index,
-                        *column_index,
-                        &node.0,
-                    );
                     let style = get_cell_style_attribute(*s);
                     let range = match get_range_str(*row_index, *column_index, r.0, r.1) {
                         Some(range) => range,
                         None => continue,
                     };
+                    let formula = get_array_formula_element(
+                        worksheet.get_name(),
+                        *row_index,
+                        *column_index,
+                        &node.0,
+                        &range,
+                    );
                     let b = i32::from(*v);
                     let cm = if matches!(kind, ArrayKind::Dynamic) {
                         r#" cm="1""#
@@ -312,7 +435,7 @@ pub(crate) fn get_worksheet_xml(
                         ""
                     };
                     row_data_str.push(format!(
-                        r#"<c r="{cell_name}"{style} t="b"{cm}><f t="array" ref="{range}">{formula}</f><v>{b}</v></c>"#
+                        r#"<c r="{cell_name}"{style} t="b"{cm}>{formula}<v>{b}</v></c>"#
                     ));
                 }
                 Cell::ArrayFormula {
@@ -334,24 +457,25 @@ pub(crate) fn get_worksheet_xml(
                         Some(node) => node,
                         None => continue,
                     };
-                    let formula = get_formula_attribute(
-                        worksheet.get_name(),
-                        *row_index,
-                        *column_index,
-                        &node.0,
-                    );
                     let style = get_cell_style_attribute(*s);
                     let range = match get_range_str(*row_index, *column_index, r.0, r.1) {
                         Some(range) => range,
                         None => continue,
                     };
+                    let formula = get_array_formula_element(
+                        worksheet.get_name(),
+                        *row_index,
+                        *column_index,
+                        &node.0,
+                        &range,
+                    );
                     let cm = if matches!(kind, ArrayKind::Dynamic) {
                         r#" cm="1""#
                     } else {
                         ""
                     };
                     row_data_str.push(format!(
-                        r#"<c r="{cell_name}"{style}{cm}><f t="array" ref="{range}">{formula}</f><v>{v}</v></c>"#
+                        r#"<c r="{cell_name}"{style}{cm}>{formula}<v>{v}</v></c>"#
                     ));
                 }
                 Cell::ArrayFormula {
@@ -373,17 +497,18 @@ pub(crate) fn get_worksheet_xml(
                         Some(node) => node,
                         None => continue,
                     };
-                    let formula = get_formula_attribute(
-                        worksheet.get_name(),
-                        *row_index,
-                        *column_index,
-                        &node.0,
-                    );
                     let style = get_cell_style_attribute(*s);
                     let range = match get_range_str(*row_index, *column_index, r.0, r.1) {
                         Some(range) => range,
                         None => continue,
                     };
+                    let formula = get_array_formula_element(
+                        worksheet.get_name(),
+                        *row_index,
+                        *column_index,
+                        &node.0,
+                        &range,
+                    );
                     let escaped_v = escape_xml(v);
                     let cm = if matches!(kind, ArrayKind::Dynamic) {
                         r#" cm="1""#
@@ -391,7 +516,7 @@ pub(crate) fn get_worksheet_xml(
                         ""
                     };
                     row_data_str.push(format!(
-                        r#"<c r="{cell_name}"{style} t="str"{cm}><f t="array" ref="{range}">{formula}</f><v>{escaped_v}</v></c>"#
+                        r#"<c r="{cell_name}"{style} t="str"{cm}>{formula}<v>{escaped_v}</v></c>"#
                     ));
                 }
                 Cell::ArrayFormula {
@@ -413,24 +538,25 @@ pub(crate) fn get_worksheet_xml(
                         Some(node) => node,
                         None => continue,
                     };
-                    let formula = get_formula_attribute(
-                        worksheet.get_name(),
-                        *row_index,
-                        *column_index,
-                        &node.0,
-                    );
                     let style = get_cell_style_attribute(*s);
                     let range = match get_range_str(*row_index, *column_index, r.0, r.1) {
                         Some(range) => range,
                         None => continue,
                     };
+                    let formula = get_array_formula_element(
+                        worksheet.get_name(),
+                        *row_index,
+                        *column_index,
+                        &node.0,
+                        &range,
+                    );
                     let cm = if matches!(kind, ArrayKind::Dynamic) {
                         r#" cm="1""#
                     } else {
                         ""
                     };
                     row_data_str.push(format!(
-                        r#"<c r="{cell_name}"{style} t="e"{cm}><f t="array" ref="{range}">{formula}</f><v>{ei}</v></c>"#
+                        r#"<c r="{cell_name}"{style} t="e"{cm}>{formula}<v>{ei}</v></c>"#
                     ));
                 }
             }
@@ -443,12 +569,13 @@ pub(crate) fn get_worksheet_xml(
                     ""
                 };
                 format!(
-                    r#" s="{}" ht="{}" customHeight="{}" customFormat="{}"{}"#,
+                    r#" s="{}" ht="{}" customHeight="{}" customFormat="{}"{}{}"#,
                     row_style.s,
                     row_style.height,
                     i32::from(row_style.custom_height),
                     i32::from(row_style.custom_format),
                     hidden_str,
+                    get_outline_attributes(row_style.outline_level, row_style.collapsed),
                 )
             }
             None => "".to_string(),
@@ -554,22 +681,63 @@ pub(crate) fn get_worksheet_xml(
         format!(r#"<selection activeCell="{active_cell}" sqref="{sqref}"/>"#)
     };
 
+    let outline_properties = &worksheet.outline_properties;
+    let sheet_pr = if *outline_properties != OutlineProperties::default() {
+        format!(
+            "<sheetPr><outlinePr summaryBelow=\"{}\" summaryRight=\"{}\"/></sheetPr>",
+            i32::from(outline_properties.summary_below),
+            i32::from(outline_properties.summary_right)
+        )
+    } else {
+        "".to_string()
+    };
+
+    let outline_level_row = worksheet
+        .rows
+        .iter()
+        .map(|r| r.outline_level)
+        .max()
+        .unwrap_or(0);
+    let outline_level_col = worksheet
+        .cols
+        .iter()
+        .map(|c| c.outline_level)
+        .max()
+        .unwrap_or(0);
+    let sheet_format_pr = if outline_level_row > 0 || outline_level_col > 0 {
+        let mut attributes = String::new();
+        if outline_level_row > 0 {
+            attributes.push_str(&format!(" outlineLevelRow=\"{outline_level_row}\""));
+        }
+        if outline_level_col > 0 {
+            attributes.push_str(&format!(" outlineLevelCol=\"{outline_level_col}\""));
+        }
+        format!("<sheetFormatPr defaultRowHeight=\"{DEFAULT_ROW_HEIGHT}\"{attributes}/>")
+    } else {
+        "".to_string()
+    };
+
+    let scenarios_section = get_scenarios_section(&worksheet.scenarios);
+
     let (cf_sections, cf_ext_lst) =
         get_conditional_formatting_xml(&worksheet.conditional_formatting);
 
     format!(
         "{XML_DECLARATION}\
 <worksheet xmlns=\"http://schemas.openxmlformats.org/spreadsheetml/2006/main\" xmlns:r=\"http://schemas.openxmlformats.org/officeDocument/2006/relationships\">\
+  {sheet_pr}\
   <dimension ref=\"{dimension}\"/>\
   <sheetViews>\
     <sheetView workbookViewId=\"0\"{show_grid_lines}{tab_selected}>\
         {pane}\
     </sheetView>\
   </sheetViews>\
+  {sheet_format_pr}\
   {cols}\
   <sheetData>\
   {sheet_data}\
   </sheetData>\
+  {scenarios_section}\
   {merge_cells_section}\
   {cf_sections}\
   {cf_ext_lst}\
@@ -0,0 +1,181 @@
+//! Links to other workbooks (`xl/externalLinks/externalLinkN.xml`)
+//!
+//! <externalLink>
+//!   <externalBook r:id="rId1">
+//!     <sheetNames><sheetName val="Sheet1"/></sheetNames>
+//!     <sheetDataSet>
+//!       <sheetData sheetId="0">
+//!         <row r="1"><cell r="A1"><v>10</v></cell></row>
+//!       </sheetData>
+//!     </sheetDataSet>
+//!   </externalBook>
+//! </externalLink>
+//!
+//! Formulas refer to the linked workbooks by their position in `<externalReferences>`: `[1]Sheet1!A1`.
+
+use std::{collections::HashMap, io::Read};
+
+use ironcalc_base::{
+    expressions::{
+        parser::{static_analysis::walk_nodes_mut, Node},
+        token::{get_error_by_english_name, Error},
+        utils::parse_reference_a1,
+    },
+    types::{ExternalLink, ExternalSheet, ExternalValue},
+};
+use roxmltree::Node as XmlNode;
+
+use crate::error::XlsxError;
+
+use super::{util::get_attribute, worksheets::Relationship};
+
+/// Returns the name used in formulas from the location of the file:
+/// "file:///C:/data/My%20Budget.xlsx" => "My Budget.xlsx"
+fn get_workbook_name(target: &str) -> String {
+    let name = target.rsplit(['/', '\\']).next().unwrap_or(target);
+    name.replace("%20", " ")
+}
+
+fn load_external_link<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    path: &str,
+) -> Result<Option<ExternalLink>, XlsxError> {
+    let mut text = String::new();
+    archive.by_name(path)?.read_to_string(&mut text)?;
+    let doc = roxmltree::Document::parse(&text)?;
+    // Links to DDE or OLE sources are not supported
+    let book = match doc.descendants().find(|n| n.has_tag_name("externalBook")) {
+        Some(book) => book,
+        None => return Ok(None),
+    };
+    // Without the location of the workbook the link is skipped, the formulas keep the position
+    let id = match book.attribute((
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+        "id",
+    )) {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+
+    // xl/externalLinks/externalLink1.xml => xl/externalLinks/_rels/externalLink1.xml.rels
+    let (folder, file_name) = path.rsplit_once('/').unwrap_or(("", path));
+    let mut rels_text = String::new();
+    match archive.by_name(&format!("{folder}/_rels/{file_name}.rels")) {
+        Ok(mut file) => file.read_to_string(&mut rels_text)?,
+        Err(_e) => return Ok(None),
+    };
+    let rels_doc = roxmltree::Document::parse(&rels_text)?;
+    let target = match rels_doc
+        .descendants()
+        .find(|n| n.has_tag_name("Relationship") && n.attribute("Id") == Some(id))
+    {
+        Some(rel) => get_attribute(&rel, "Target")?.to_string(),
+        None => return Ok(None),
+    };
+
+    let mut sheets: Vec<ExternalSheet> = book
+        .descendants()
+        .filter(|n| n.has_tag_name("sheetName"))
+        .map(|n| ExternalSheet {
+            name: n.attribute("val").unwrap_or("").to_string(),
+            cells: HashMap::new(),
+        })
+        .collect();
+    let sheet_data_nodes: Vec<XmlNode> = book
+        .descendants()
+        .filter(|n| n.has_tag_name("sheetData"))
+        .collect();
+    for sheet_data in sheet_data_nodes {
+        let sheet_id = get_attribute(&sheet_data, "sheetId")?.parse::<usize>()?;
+        let sheet = match sheets.get_mut(sheet_id) {
+            Some(sheet) => sheet,
+            None => continue,
+        };
+        for cell in sheet_data.descendants().filter(|n| n.has_tag_name("cell")) {
+            let reference = match parse_reference_a1(get_attribute(&cell, "r")?) {
+                Some(reference) => reference,
+                None => continue,
+            };
+            let v = match cell.children().find(|n| n.has_tag_name("v")) {
+                Some(v) => v.text().unwrap_or("").to_string(),
+                None => continue,
+            };
+            let value = match cell.attribute("t").unwrap_or("n") {
+                "b" => ExternalValue::Boolean(v == "1"),
+                "e" => ExternalValue::Error(get_error_by_english_name(&v).unwrap_or(Error::NA)),
+                "str" | "s" | "inlineStr" => ExternalValue::Text(v),
+                _ => match v.parse::<f64>() {
+                    Ok(number) => ExternalValue::Number(number),
+                    Err(_) => ExternalValue::Text(v),
+                },
+            };
+            sheet
+                .cells
+                .entry(reference.row)
+                .or_default()
+                .insert(reference.column, value);
+        }
+    }
+    Ok(Some(ExternalLink {
+        workbook: get_workbook_name(&target),
+        target,
+        sheets,
+    }))
+}
+
+/// Loads the links in the order of `<externalReferences>`.
+/// There is a `None` for the links that are not to other workbooks.
+pub(super) fn load_external_links<R: Read + std::io::Seek>(
+    archive: &mut zip::read::ZipArchive<R>,
+    rels: &HashMap<String, Relationship>,
+    ids: &[String],
+) -> Result<Vec<Option<ExternalLink>>, XlsxError> {
+    let mut links = Vec::new();
+    for id in ids {
+        let link = match rels.get(id) {
+            Some(rel) => {
+                let path = match rel.target.strip_prefix('/') {
+                    Some(path) => path.to_string(),
+                    None => format!("xl/{}", rel.target),
+                };
+                load_external_link(archive, &path)?
+            }
+            None => None,
+        };
+        links.push(link);
+    }
+    Ok(links)
+}
+
+/// Replaces the position of the linked workbooks by their names: `[1]Sheet1!A1` => `[Budget.xlsx]Sheet1!A1`
+pub(super) fn name_external_workbooks(node: &mut Node, workbooks: &[Option<String>]) {
+    walk_nodes_mut(node, &mut |node| {
+        if let Node::ExternalReferenceKind { workbook, .. }
+        | Node::ExternalRangeKind { workbook, .. } = node
+        {
+            let name = workbook
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| workbooks.get(index.wrapping_sub(1)))
+                .and_then(|name| name.as_ref());
+            if let Some(name) = name {
+                *workbook = name.clone();
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_workbook_name;
+
+    #[test]
+    fn workbook_names() {
+        assert_eq!(get_workbook_name("Budget.xlsx"), "Budget.xlsx");
+        assert_eq!(
+            get_workbook_name("file:///C:/data/My%20Budget.xlsx"),
+            "My Budget.xlsx"
+        );
+        assert_eq!(get_workbook_name("..\\reports\\Q1.xlsx"), "Q1.xlsx");
+    }
+}
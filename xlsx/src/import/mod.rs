@@ -1,4 +1,5 @@
 mod conditional_formatting;
+mod external_links;
 mod metadata;
 pub(crate) mod shared_strings;
 mod styles;
@@ -29,6 +30,7 @@ use crate::error::XlsxError;
 
 use shared_strings::read_shared_strings;
 
+use external_links::{load_external_links, name_external_workbooks};
 use metadata::load_metadata;
 use styles::load_styles;
 use util::get_attribute;
@@ -74,7 +76,11 @@ fn resolve_theme_path(rels: &HashMap<String, Relationship>) -> Option<String> {
 
 // FIXME: This is a bit of a HACK. We basically re-parse all the defined names assuming the context is `A1`
 // and there is no tables or defined names.
-fn reparse_formula_hack(formula: &str, worksheets: &[String]) -> Result<String, XlsxError> {
+fn reparse_formula_hack(
+    formula: &str,
+    worksheets: &[String],
+    external_workbooks: &[Option<String>],
+) -> Result<String, XlsxError> {
     let defined_names = Vec::new();
     let tables = HashMap::new();
     let mut parser = new_parser_english(worksheets.to_owned(), defined_names, tables);
@@ -83,7 +89,8 @@ fn reparse_formula_hack(formula: &str, worksheets: &[String]) -> Result<String,
         column: 1,
         row: 1,
     };
-    let t = parser.parse(formula, &cell_reference);
+    let mut t = parser.parse(formula, &cell_reference);
+    name_external_workbooks(&mut t, external_workbooks);
 
     Ok(to_english_string(&t, &cell_reference))
 }
@@ -101,6 +108,11 @@ fn load_xlsx_from_reader<R: Read + std::io::Seek>(
     let rels = load_relationships(&mut archive)?;
     let theme_path = resolve_theme_path(&rels);
     let theme = theme::load(&mut archive, theme_path.as_deref());
+    let external_links = load_external_links(&mut archive, &rels, &workbook.external_references)?;
+    workbook.external_workbooks = external_links
+        .iter()
+        .map(|link| link.as_ref().map(|link| link.workbook.clone()))
+        .collect();
     let mut tables = HashMap::new();
     let (worksheets, selected_sheet) = load_sheets(
         &mut archive,
@@ -117,7 +129,8 @@ fn load_xlsx_from_reader<R: Read + std::io::Seek>(
         .map(|s| s.name.clone())
         .collect::<Vec<_>>();
     for dn in &mut workbook.defined_names {
-        dn.formula = reparse_formula_hack(&dn.formula, &worksheet_names)?;
+        dn.formula =
+            reparse_formula_hack(&dn.formula, &worksheet_names, &workbook.external_workbooks)?;
     }
     let metadata = match load_metadata(&mut archive) {
         Ok(metadata) => metadata,
@@ -151,11 +164,14 @@ fn load_xlsx_from_reader<R: Read + std::io::Seek>(
         settings: WorkbookSettings {
             tz: tz.to_string(),
             locale: locale.to_string(),
+            date1904: workbook.date1904,
+            r1c1: workbook.r1c1,
         },
         metadata,
         tables,
         views,
         theme,
+        external_links: external_links.into_iter().flatten().collect(),
     })
 }
 
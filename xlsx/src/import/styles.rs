@@ -2,7 +2,8 @@ use std::{collections::HashMap, io::Read};
 
 use ironcalc_base::types::{
     Alignment, Border, BorderItem, BorderStyle, CellStyleXfs, CellStyles, CellXfs, Color, Dxf,
-    DxfFont, Fill, Font, FontScheme, HorizontalAlignment, NumFmt, Styles, Theme, VerticalAlignment,
+    DxfFont, Fill, Font, FontScheme, GradientFill, GradientStop, GradientType, HorizontalAlignment,
+    NumFmt, PatternType, Protection, ReadingOrder, Styles, Theme, VerticalAlignment,
 };
 use roxmltree::Node;
 
@@ -10,6 +11,97 @@ use crate::error::XlsxError;
 
 use super::util::{get_attribute, get_bool, get_bool_false, get_color, get_number};
 
+fn get_gradient_fill(node: Node, theme: &Theme) -> Result<Fill, XlsxError> {
+    let gradient_type = match node.attribute("type") {
+        Some("path") => GradientType::Path,
+        _ => GradientType::Linear,
+    };
+    let get_f64 = |name: &str| {
+        node.attribute(name)
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(0.0)
+    };
+    let mut stops = Vec::new();
+    for stop in node.children().filter(|n| n.has_tag_name("stop")) {
+        let position = stop
+            .attribute("position")
+            .and_then(|v| v.parse::<f64>().ok())
+            .unwrap_or(0.0);
+        let color = match stop.children().find(|n| n.has_tag_name("color")) {
+            Some(color) => get_color(color, theme)?,
+            None => Color::None,
+        };
+        stops.push(GradientStop { position, color });
+    }
+    // The colour of the first stop is used by clients that cannot draw gradients
+    let color = stops.first().map(|s| s.color.clone()).unwrap_or_default();
+    Ok(Fill {
+        color,
+        gradient: Some(GradientFill {
+            gradient_type,
+            degree: get_f64("degree"),
+            left: get_f64("left"),
+            right: get_f64("right"),
+            top: get_f64("top"),
+            bottom: get_f64("bottom"),
+            stops,
+        }),
+        ..Default::default()
+    })
+}
+
+fn get_reading_order(node: Node) -> ReadingOrder {
+    match node.attribute("readingOrder") {
+        Some("1") => ReadingOrder::LeftToRight,
+        Some("2") => ReadingOrder::RightToLeft,
+        _ => ReadingOrder::ContextDependent,
+    }
+}
+
+// Reads a <fill> node, both for cell styles and for differential formats
+fn get_fill(node: Node, theme: &Theme) -> Result<Fill, XlsxError> {
+    if let Some(gradient_fill) = node.children().find(|n| n.has_tag_name("gradientFill")) {
+        return get_gradient_fill(gradient_fill, theme);
+    }
+    let pattern_fill = match node.children().find(|n| n.has_tag_name("patternFill")) {
+        Some(p) => p,
+        None => return Ok(Fill::default()),
+    };
+    let mut fg_color = Color::None;
+    let mut bg_color = Color::None;
+    for feature in pattern_fill.children() {
+        match feature.tag_name().name() {
+            "fgColor" => fg_color = get_color(feature, theme)?,
+            "bgColor" => bg_color = get_color(feature, theme)?,
+            _ => {}
+        }
+    }
+    let pattern_type = match pattern_fill.attribute("patternType").map(str::parse) {
+        Some(Ok(pattern_type)) => pattern_type,
+        // Differential formats omit the pattern type of solid fills
+        _ if fg_color.is_some() || bg_color.is_some() => PatternType::Solid,
+        _ => PatternType::None,
+    };
+    match pattern_type {
+        PatternType::None => Ok(Fill::default()),
+        // Prefer fgColor (solid fill convention); fall back to bgColor
+        PatternType::Solid => Ok(Fill {
+            color: if fg_color.is_some() {
+                fg_color
+            } else {
+                bg_color
+            },
+            ..Default::default()
+        }),
+        pattern_type => Ok(Fill {
+            color: fg_color,
+            pattern_type,
+            bg_color,
+            gradient: None,
+        }),
+    }
+}
+
 fn get_border(node: Node, name: &str, theme: &Theme) -> Result<Option<BorderItem>, XlsxError> {
     let style;
     let color;
@@ -86,7 +178,8 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
         .collect::<Vec<Node>>()[0];
     for font in font_nodes.children() {
         let mut sz = 11;
-        let mut name = "Inter".to_string();
+        // Calibri is Excel's default font when the name is missing
+        let mut name = "Calibri".to_string();
         // NOTE: In Excel you can have simple underline or double underline
         // In IronCalc convert double underline to simple
         // This in excel is u with a value of "double"
@@ -100,7 +193,9 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
         // explicit <color rgb="FF000000"/>.
         let mut color: Color = Color::None;
         let mut family = 2;
-        let mut scheme = FontScheme::default();
+        let mut charset = None;
+        // A font without a <scheme> child is not a theme font
+        let mut scheme = FontScheme::None;
         for feature in font.children() {
             match feature.tag_name().name() {
                 "sz" => {
@@ -125,7 +220,11 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
                 "strike" => {
                     strike = true;
                 }
-                "name" => name = "Inter".to_string(), // TODO: feature.attribute("val").unwrap_or("Calibri").to_string(),
+                "name" => {
+                    if let Some(val) = feature.attribute("val") {
+                        name = val.to_string();
+                    }
+                }
                 // If there is a theme the font scheme and family overrides other properties like the name
                 "family" => {
                     family = feature
@@ -144,7 +243,11 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
                         Some(_) => FontScheme::default(),
                     }
                 }
-                "charset" => {}
+                "charset" => {
+                    charset = feature
+                        .attribute("val")
+                        .and_then(|val| val.parse::<i32>().ok());
+                }
                 _ => {
                     println!("Unexpected feature {feature:?}");
                 }
@@ -159,6 +262,7 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
             color,
             name,
             family,
+            charset,
             scheme,
         });
     }
@@ -168,43 +272,8 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
         .children()
         .filter(|n| n.has_tag_name("fills"))
         .collect::<Vec<Node>>()[0];
-    for fill in fill_nodes.children() {
-        let pattern_fill = fill
-            .children()
-            .filter(|n| n.has_tag_name("patternFill"))
-            .collect::<Vec<Node>>();
-        if pattern_fill.len() != 1 {
-            // safety belt
-            // Some fills do not have a patternFill, but they have gradientFill
-            fills.push(Fill::default());
-            continue;
-        }
-        let pattern_fill = pattern_fill[0];
-
-        let mut fg_color = Color::None;
-        let mut bg_color = Color::None;
-        for feature in pattern_fill.children() {
-            match feature.tag_name().name() {
-                "fgColor" => {
-                    fg_color = get_color(feature, theme)?;
-                }
-                "bgColor" => {
-                    bg_color = get_color(feature, theme)?;
-                }
-                _ => {
-                    println!("Unexpected pattern");
-                    dbg!(feature);
-                }
-            }
-        }
-        // Prefer fgColor (solid fill convention); fall back to bgColor
-        fills.push(Fill {
-            color: if fg_color.is_some() {
-                fg_color
-            } else {
-                bg_color
-            },
-        })
+    for fill in fill_nodes.children().filter(|n| n.has_tag_name("fill")) {
+        fills.push(get_fill(fill, theme)?);
     }
 
     let mut borders = Vec::new();
@@ -345,11 +414,24 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
                 horizontal,
                 vertical,
                 wrap_text,
+                text_rotation: get_number(alignment_node, "textRotation"),
+                indent: get_number(alignment_node, "indent"),
+                shrink_to_fit: get_bool_false(alignment_node, "shrinkToFit"),
+                justify_last_line: get_bool_false(alignment_node, "justifyLastLine"),
+                reading_order: get_reading_order(alignment_node),
             })
         } else {
             None
         };
 
+        let protection = xfs
+            .children()
+            .find(|n| n.has_tag_name("protection"))
+            .map(|node| Protection {
+                locked: get_bool(node, "locked"),
+                hidden: get_bool_false(node, "hidden"),
+            });
+
         cell_xfs.push(CellXfs {
             xf_id,
             num_fmt_id,
@@ -364,6 +446,7 @@ pub(super) fn load_styles<R: Read + std::io::Seek>(
             apply_fill,
             quote_prefix,
             alignment,
+            protection,
         });
     }
 
@@ -434,31 +517,12 @@ fn load_dxfs(style_sheet: Node, theme: &Theme) -> Result<Vec<Dxf>, XlsxError> {
                     }
                     font = Some(f);
                 }
-                "fill" => {
-                    let pattern_fill_nodes = child
-                        .children()
-                        .filter(|n| n.has_tag_name("patternFill"))
-                        .collect::<Vec<Node>>();
-                    if pattern_fill_nodes.len() == 1 {
-                        let pf = pattern_fill_nodes[0];
-                        let mut fg_color = Color::None;
-                        let mut bg_color = Color::None;
-                        for feat in pf.children() {
-                            match feat.tag_name().name() {
-                                "fgColor" => fg_color = get_color(feat, theme)?,
-                                "bgColor" => bg_color = get_color(feat, theme)?,
-                                _ => {}
-                            }
-                        }
-                        // Prefer fgColor (solid fill convention); fall back to bgColor
-                        fill = Some(Fill {
-                            color: if fg_color.is_some() {
-                                fg_color
-                            } else {
-                                bg_color
-                            },
-                        });
-                    }
+                "fill"
+                    if child.children().any(|n| {
+                        n.has_tag_name("patternFill") || n.has_tag_name("gradientFill")
+                    }) =>
+                {
+                    fill = Some(get_fill(child, theme)?);
                 }
                 "border" => {
                     let left = get_border(child, "left", theme)?;
@@ -503,6 +567,11 @@ fn load_dxfs(style_sheet: Node, theme: &Theme) -> Result<Vec<Dxf>, XlsxError> {
                         horizontal,
                         vertical,
                         wrap_text,
+                        text_rotation: get_number(child, "textRotation"),
+                        indent: get_number(child, "indent"),
+                        shrink_to_fit: get_bool_false(child, "shrinkToFit"),
+                        justify_last_line: get_bool_false(child, "justifyLastLine"),
+                        reading_order: get_reading_order(child),
                     });
                 }
                 _ => {}
@@ -65,15 +65,49 @@ pub(super) fn load_workbook<R: Read + std::io::Seek>(
             }
             None => None,
         };
+        let hidden = matches!(node.attribute("hidden"), Some("1" | "true"));
         defined_names.push(DefinedName {
             name,
             formula,
             sheet_id,
+            hidden,
         })
     }
+    // <workbookPr date1904="1"/> is used by workbooks created with older versions of Excel for Mac
+    let date1904 = doc
+        .descendants()
+        .find(|n| n.has_tag_name("workbookPr"))
+        .and_then(|n| n.attribute("date1904"))
+        .is_some_and(|v| v == "1" || v == "true");
+    // <calcPr refMode="R1C1"/> if the workbook uses the R1C1 reference style
+    let r1c1 = doc
+        .descendants()
+        .find(|n| n.has_tag_name("calcPr"))
+        .and_then(|n| n.attribute("refMode"))
+        .is_some_and(|v| v == "R1C1");
+    // <externalReferences><externalReference r:id="rId5"/></externalReferences>
+    // Formulas refer to them by position: [1]Sheet1!A1
+    let mut external_references = Vec::new();
+    for node in doc
+        .descendants()
+        .filter(|n| n.has_tag_name("externalReference"))
+    {
+        let id = get_attribute(
+            &node,
+            (
+                "http://schemas.openxmlformats.org/officeDocument/2006/relationships",
+                "id",
+            ),
+        )?;
+        external_references.push(id.to_string());
+    }
     // read the relationships file
     Ok(WorkbookXML {
         worksheets: sheets,
         defined_names,
+        date1904,
+        r1c1,
+        external_references,
+        external_workbooks: Vec::new(),
     })
 }
@@ -13,8 +13,9 @@ use ironcalc_base::{
         utils::{column_to_number, parse_reference_a1},
     },
     types::{
-        ArrayKind, Cell, Col, Color, Comment, DefinedName, FormulaValue, Row, SheetData,
-        SheetState, SpillValue, Table, Theme, Worksheet, WorksheetView,
+        ArrayKind, Cell, Col, Color, Comment, DefinedName, FormulaValue, OutlineProperties, Row,
+        Scenario, ScenarioInputCell, SheetData, SheetState, SpillValue, Table, Theme, Worksheet,
+        WorksheetView,
     },
 };
 use roxmltree::Node;
@@ -24,6 +25,7 @@ use crate::error::XlsxError;
 
 use super::{
     conditional_formatting::load_conditional_formatting,
+    external_links::name_external_workbooks,
     shared_strings::decode_xlsx_escapes,
     tables::load_table,
     util::{get_attribute, get_color, get_number},
@@ -39,6 +41,12 @@ pub(crate) struct Sheet {
 pub(crate) struct WorkbookXML {
     pub(crate) worksheets: Vec<Sheet>,
     pub(crate) defined_names: Vec<DefinedName>,
+    pub(crate) date1904: bool,
+    pub(crate) r1c1: bool,
+    /// Relationship ids of the links to other workbooks
+    pub(crate) external_references: Vec<String>,
+    /// Names of the linked workbooks, in the order of `external_references`
+    pub(crate) external_workbooks: Vec<Option<String>>,
 }
 
 pub(crate) struct Relationship {
@@ -163,6 +171,8 @@ fn load_columns(ws: Node) -> Result<Vec<Col>, XlsxError> {
             let style = col
                 .attribute("style")
                 .map(|s| s.parse::<i32>().unwrap_or(0));
+            let outline_level = get_number(col, "outlineLevel");
+            let collapsed = matches!(col.attribute("collapsed"), Some("1"));
             cols.push(Col {
                 min,
                 max,
@@ -170,6 +180,8 @@ fn load_columns(ws: Node) -> Result<Vec<Col>, XlsxError> {
                 custom_width,
                 style,
                 hidden,
+                outline_level,
+                collapsed,
             })
         }
     }
@@ -216,6 +228,64 @@ fn load_sheet_color(ws: Node, theme: &Theme) -> Result<Color, XlsxError> {
     Ok(color)
 }
 
+fn load_outline_properties(ws: Node) -> OutlineProperties {
+    // <sheetPr>
+    //     <outlinePr summaryBelow="0" summaryRight="0"/>
+    // </sheetPr>
+    let outline_pr = ws
+        .children()
+        .filter(|n| n.has_tag_name("sheetPr"))
+        .flat_map(|n| n.children())
+        .find(|n| n.has_tag_name("outlinePr"));
+    match outline_pr {
+        Some(node) => OutlineProperties {
+            summary_below: node.attribute("summaryBelow") != Some("0"),
+            summary_right: node.attribute("summaryRight") != Some("0"),
+        },
+        None => OutlineProperties::default(),
+    }
+}
+
+fn load_scenarios(ws: Node) -> Result<Vec<Scenario>, XlsxError> {
+    // 18.3.1.76 Scenarios
+    // <scenarios current="0" show="0" sqref="B1:B2">
+    //     <scenario name="Best case" locked="1" count="2" user="Jane" comment="">
+    //         <inputCells r="B1" val="10"/>
+    //         <inputCells r="B2" val="0.05"/>
+    //     </scenario>
+    // </scenarios>
+    let mut scenarios = Vec::new();
+    let scenario_nodes = ws
+        .children()
+        .filter(|n| n.has_tag_name("scenarios"))
+        .flat_map(|n| n.children())
+        .filter(|n| n.has_tag_name("scenario"));
+    for scenario in scenario_nodes {
+        let mut input_cells = Vec::new();
+        for input_cell in scenario.children().filter(|n| n.has_tag_name("inputCells")) {
+            let (row, column) =
+                parse_cell_reference(get_attribute(&input_cell, "r")?).map_err(XlsxError::Xml)?;
+            input_cells.push(ScenarioInputCell {
+                row,
+                column,
+                value: input_cell.attribute("val").unwrap_or_default().to_string(),
+            });
+        }
+        scenarios.push(Scenario {
+            name: get_attribute(&scenario, "name")?.to_string(),
+            comment: scenario
+                .attribute("comment")
+                .unwrap_or_default()
+                .to_string(),
+            user: scenario.attribute("user").unwrap_or_default().to_string(),
+            locked: matches!(scenario.attribute("locked"), Some("1" | "true")),
+            hidden: matches!(scenario.attribute("hidden"), Some("1" | "true")),
+            input_cells,
+        });
+    }
+    Ok(scenarios)
+}
+
 fn load_comments<R: Read + std::io::Seek>(
     archive: &mut zip::read::ZipArchive<R>,
     path: &str,
@@ -308,12 +378,14 @@ fn from_a1_to_rc(
     context: String,
     tables: HashMap<String, Table>,
     defined_names: Vec<DefinedNameS>,
+    external_workbooks: &[Option<String>],
     is_array_formula: bool,
 ) -> Result<String, XlsxError> {
     let mut parser = new_parser_english(worksheets.to_owned(), defined_names, tables);
     let cell_reference =
         parse_reference(&context).map_err(|error| XlsxError::Xml(error.to_string()))?;
     let mut t = parser.parse(&formula, &cell_reference);
+    name_external_workbooks(&mut t, external_workbooks);
     if !is_array_formula {
         add_implicit_intersection(&mut t, true);
     }
@@ -330,6 +402,32 @@ fn get_formula_index(formula: &str, shared_formulas: &[String]) -> Option<i32> {
     None
 }
 
+/// A data table is imported as the array formula `TABLE(row_input, column_input)`:
+///
+/// * `<f t="dataTable" ref="D6:E7" dt2D="1" dtr="1" r1="A1" r2="A2"/>` is `TABLE(A1,A2)`
+/// * `<f t="dataTable" ref="B5:C7" dt2D="0" dtr="0" r1="A1"/>` is `TABLE(,A1)`
+/// * `<f t="dataTable" ref="C3:E3" dt2D="0" dtr="1" r1="A1"/>` is `TABLE(A1,)`
+///
+/// An input cell that was deleted (`del1` or `del2`) is `#REF!`.
+fn get_data_table_formula(node: &Node) -> Result<String, XlsxError> {
+    let input = |reference: &str, deleted: &str| -> Result<String, XlsxError> {
+        if node.attribute(deleted) == Some("1") {
+            return Ok("#REF!".to_string());
+        }
+        Ok(get_attribute(node, reference)?.to_string())
+    };
+    let is_2d = node.attribute("dt2D") == Some("1");
+    let is_row = node.attribute("dtr") == Some("1");
+    let formula = if is_2d {
+        format!("TABLE({},{})", input("r1", "del1")?, input("r2", "del2")?)
+    } else if is_row {
+        format!("TABLE({},)", input("r1", "del1")?)
+    } else {
+        format!("TABLE(,{})", input("r1", "del1")?)
+    };
+    Ok(formula)
+}
+
 enum CellArrayKind {
     None,
     DynamicArray(i32, i32),
@@ -755,6 +853,7 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
     tables: &HashMap<String, Table>,
     shared_strings: &mut Vec<String>,
     defined_names: Vec<DefinedNameS>,
+    external_workbooks: &[Option<String>],
     theme: &Theme,
 ) -> Result<(Worksheet, bool), XlsxError> {
     let sheet_name = &settings.name;
@@ -832,8 +931,17 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
         };
         let custom_format = matches!(row.attribute("customFormat"), Some("1"));
         let hidden = matches!(row.attribute("hidden"), Some("1"));
-
-        if custom_height || custom_format || row_style != 0 || has_height_attribute || hidden {
+        let outline_level = get_number(row, "outlineLevel");
+        let collapsed = matches!(row.attribute("collapsed"), Some("1"));
+
+        if custom_height
+            || custom_format
+            || row_style != 0
+            || has_height_attribute
+            || hidden
+            || outline_level != 0
+            || collapsed
+        {
             rows.push(Row {
                 r: row_index,
                 height,
@@ -841,11 +949,13 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                 custom_height,
                 custom_format,
                 hidden,
+                outline_level,
+                collapsed,
             });
         }
 
         // Unused attributes:
-        // * thickBot, thickTop, ph, collapsed, outlineLevel
+        // * thickBot, thickTop, ph
 
         let mut data_row = HashMap::new();
 
@@ -995,6 +1105,7 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                                     context,
                                     tables.clone(),
                                     defined_names.clone(),
+                                    external_workbooks,
                                     false,
                                 )?;
                                 match index_map.get(&si) {
@@ -1040,10 +1151,7 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                             }
                         }
                     }
-                    "dataTable" => {
-                        return Err(XlsxError::NotImplemented("data table formulas".to_string()));
-                    }
-                    "array" => {
+                    "array" | "dataTable" => {
                         let range = match formula_ref {
                             Some(r) => r,
                             None => {
@@ -1070,14 +1178,18 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                                 array_cell.insert((r, c), (row_index, column_index));
                             }
                         }
-                        if is_dynamic_array {
+                        if is_dynamic_array && formula_type == "array" {
                             array_kind =
                                 CellArrayKind::DynamicArray(column2 - column1 + 1, row2 - row1 + 1);
                         } else {
                             array_kind =
                                 CellArrayKind::ArrayFormula(column2 - column1 + 1, row2 - row1 + 1);
                         }
-                        let formula = formula_node.text().unwrap_or("").to_string();
+                        let formula = if formula_type == "dataTable" {
+                            get_data_table_formula(&formula_node)?
+                        } else {
+                            formula_node.text().unwrap_or("").to_string()
+                        };
                         let context = format!("{sheet_name}!{cell_ref}");
                         let formula = from_a1_to_rc(
                             formula,
@@ -1085,6 +1197,7 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                             context,
                             tables.clone(),
                             defined_names.clone(),
+                            external_workbooks,
                             true,
                         )?;
 
@@ -1106,6 +1219,7 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
                             context,
                             tables.clone(),
                             defined_names.clone(),
+                            external_workbooks,
                             false,
                         )?;
 
@@ -1180,6 +1294,8 @@ pub(super) fn load_sheet<R: Read + std::io::Seek>(
             show_grid_lines: sheet_view.show_grid_lines,
             views,
             conditional_formatting,
+            outline_properties: load_outline_properties(ws),
+            scenarios: load_scenarios(ws)?,
         },
         sheet_view.is_selected,
     ))
@@ -1248,6 +1364,7 @@ pub(super) fn load_sheets<R: Read + std::io::Seek>(
                 tables,
                 shared_strings,
                 defined_names.clone(),
+                &workbook.external_workbooks,
                 theme,
             )?;
             if is_selected {
@@ -0,0 +1,50 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::expressions::types::Area;
+use ironcalc_base::types::{Alignment, HorizontalAlignment, ReadingOrder};
+use ironcalc_base::UserModel;
+use std::fs;
+
+#[test]
+fn test_alignment_round_trip() {
+    let mut model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    for (path, value) in [
+        ("alignment.horizontal", "left"),
+        ("alignment.text_rotation", "135"),
+        ("alignment.indent", "3"),
+        ("alignment.shrink_to_fit", "true"),
+        ("alignment.justify_last_line", "true"),
+        ("alignment.reading_order", "leftToRight"),
+    ] {
+        model.update_range_style(&range, path, value).unwrap();
+    }
+
+    let temp_file_name = "temp_file_test_alignment.xlsx";
+    save_to_xlsx(model.get_model(), temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let alignment = reloaded.get_style_for_cell(0, 1, 1).unwrap().alignment;
+    assert_eq!(
+        alignment,
+        Some(Alignment {
+            horizontal: HorizontalAlignment::Left,
+            text_rotation: 135,
+            indent: 3,
+            shrink_to_fit: true,
+            justify_last_line: true,
+            reading_order: ReadingOrder::LeftToRight,
+            ..Default::default()
+        })
+    );
+}
@@ -0,0 +1,62 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use std::fs;
+use std::sync::Arc;
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::custom_functions::{CustomFunction, FunctionValue};
+use ironcalc_base::Model;
+
+fn price_curve() -> CustomFunction {
+    CustomFunction {
+        name: "PRICE_CURVE".to_string(),
+        min_args: 1,
+        max_args: Some(2),
+        volatile: false,
+        callback: Arc::new(|args| match args {
+            [FunctionValue::Number(x)] => FunctionValue::Number(10.0 * x),
+            [FunctionValue::Number(x), FunctionValue::Number(y)] => {
+                FunctionValue::Number(10.0 * x + y)
+            }
+            _ => FunctionValue::Error {
+                error: "#N/A".to_string(),
+            },
+        }),
+    }
+}
+
+#[test]
+fn custom_functions_survive_save_and_load() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model.register_function(price_curve()).unwrap();
+    model.set_user_input(0, 1, 1, "3".to_string()).unwrap();
+    model
+        .set_user_input(0, 1, 2, "=PRICE_CURVE(A1, 2)".to_string())
+        .unwrap();
+    model.evaluate();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 2).unwrap(), "32");
+
+    let temp_file_name = "temp_file_test_custom_functions.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let mut reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    // Names of functions that are not built in are written in lower case
+    assert_eq!(
+        reloaded.get_cell_formula(0, 1, 2).unwrap(),
+        Some("=price_curve(A1,2)".to_string())
+    );
+    // The cached value is kept until the formula is evaluated without the function
+    assert_eq!(reloaded.get_formatted_cell_value(0, 1, 2).unwrap(), "32");
+    reloaded.evaluate();
+    assert_eq!(
+        reloaded.get_formatted_cell_value(0, 1, 2).unwrap(),
+        "#NAME?"
+    );
+
+    reloaded.register_function(price_curve()).unwrap();
+    reloaded.evaluate();
+    assert_eq!(reloaded.get_formatted_cell_value(0, 1, 2).unwrap(), "32");
+}
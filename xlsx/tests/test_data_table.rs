@@ -0,0 +1,129 @@
+#![allow(clippy::unwrap_used)]
+
+use std::io::{Cursor, Read};
+
+use ironcalc::export::save_xlsx_to_writer;
+use ironcalc::import::load_from_xlsx_bytes;
+use ironcalc_base::Model;
+
+fn read_part(bytes: &[u8], name: &str) -> String {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut text = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut text)
+        .unwrap();
+    text
+}
+
+#[test]
+fn data_table_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    let mut set = |row: i32, column: i32, value: &str| {
+        model
+            .set_user_input(0, row, column, value.to_string())
+            .unwrap();
+    };
+    set(1, 1, "1");
+    set(2, 1, "1");
+    set(3, 1, "=A1*A2");
+    // Two variables in C5:E7
+    set(5, 3, "=A3");
+    set(5, 4, "2");
+    set(5, 5, "3");
+    set(6, 3, "10");
+    set(7, 3, "20");
+    // One variable, values in a column in G5:H7
+    set(5, 8, "=A3+1");
+    set(6, 7, "4");
+    set(7, 7, "5");
+    // One variable, values in a row in J5:L6
+    set(5, 11, "6");
+    set(5, 12, "7");
+    set(6, 10, "=A3*2");
+    model
+        .set_user_array_formula(0, 6, 4, 2, 2, "=TABLE(A1,A2)")
+        .unwrap();
+    model
+        .set_user_array_formula(0, 6, 8, 1, 2, "=TABLE(,A1)")
+        .unwrap();
+    model
+        .set_user_array_formula(0, 6, 11, 2, 1, "=TABLE(A2,)")
+        .unwrap();
+    model.evaluate();
+    assert_eq!(model.get_formatted_cell_value(0, 7, 5).unwrap(), "60");
+
+    let bytes = save_xlsx_to_writer(&model, Cursor::new(Vec::new()))
+        .unwrap()
+        .into_inner();
+    let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<f t="dataTable" ref="D6:E7" dt2D="1" dtr="1" r1="A1" r2="A2"/>"#));
+    assert!(sheet.contains(r#"<f t="dataTable" ref="H6:H7" dt2D="0" dtr="0" r1="A1"/>"#));
+    assert!(sheet.contains(r#"<f t="dataTable" ref="K6:L6" dt2D="0" dtr="1" r1="A2"/>"#));
+
+    let workbook = load_from_xlsx_bytes(&bytes, "model", "en", "UTC").unwrap();
+    let mut reloaded = Model::from_workbook(workbook, "en").unwrap();
+    reloaded.evaluate();
+    assert_eq!(
+        reloaded.get_cell_formula(0, 6, 4).unwrap(),
+        Some("=TABLE(A1,A2)".to_string())
+    );
+    assert_eq!(
+        reloaded.get_cell_formula(0, 6, 8).unwrap(),
+        Some("=TABLE(,A1)".to_string())
+    );
+    assert_eq!(
+        reloaded.get_cell_formula(0, 6, 11).unwrap(),
+        Some("=TABLE(A2,)".to_string())
+    );
+    let value = |row: i32, column: i32| reloaded.get_formatted_cell_value(0, row, column).unwrap();
+    assert_eq!(value(6, 4), "20");
+    assert_eq!(value(7, 5), "60");
+    assert_eq!(value(6, 8), "5");
+    assert_eq!(value(7, 8), "6");
+    assert_eq!(value(6, 11), "12");
+    assert_eq!(value(6, 12), "14");
+    assert_eq!(value(3, 1), "1");
+
+    // Saving again gives the same data tables
+    let bytes = save_xlsx_to_writer(&reloaded, Cursor::new(Vec::new()))
+        .unwrap()
+        .into_inner();
+    let reloaded_sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert_eq!(reloaded_sheet, sheet);
+}
+
+#[test]
+fn deleted_data_table_inputs() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model.set_user_input(0, 1, 1, "1".to_string()).unwrap();
+    model.set_user_input(0, 2, 1, "=A1*2".to_string()).unwrap();
+    model.set_user_input(0, 2, 2, "5".to_string()).unwrap();
+    model
+        .set_user_array_formula(0, 3, 2, 1, 1, "=TABLE(#REF!,A1)")
+        .unwrap();
+    model
+        .set_user_array_formula(0, 3, 4, 1, 1, "=TABLE(,#REF!)")
+        .unwrap();
+    model.evaluate();
+
+    let bytes = save_xlsx_to_writer(&model, Cursor::new(Vec::new()))
+        .unwrap()
+        .into_inner();
+    let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(r#"<f t="dataTable" ref="B3:B3" dt2D="1" dtr="1" del1="1" r2="A1"/>"#));
+    assert!(sheet.contains(r#"<f t="dataTable" ref="D3:D3" dt2D="0" dtr="0" del1="1"/>"#));
+    assert!(!sheet.contains(r##"="#REF!""##));
+
+    let workbook = load_from_xlsx_bytes(&bytes, "model", "en", "UTC").unwrap();
+    let reloaded = Model::from_workbook(workbook, "en").unwrap();
+    assert_eq!(
+        reloaded.get_cell_formula(0, 3, 2).unwrap(),
+        Some("=TABLE(#REF!,A1)".to_string())
+    );
+    assert_eq!(
+        reloaded.get_cell_formula(0, 3, 4).unwrap(),
+        Some("=TABLE(,#REF!)".to_string())
+    );
+}
@@ -0,0 +1,34 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::Model;
+use std::fs;
+
+#[test]
+fn test_date1904_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model
+        .set_user_input(0, 1, 1, "2024-06-15".to_string())
+        .unwrap();
+    model.convert_date_system(true).unwrap();
+    model.evaluate();
+
+    let temp_file_name = "temp_file_test_date1904.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    assert!(reloaded.get_date1904());
+    assert_eq!(
+        reloaded.get_formatted_cell_value(0, 1, 1).unwrap(),
+        "2024-06-15"
+    );
+}
+
+#[test]
+fn test_date1900_is_default() {
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC", "en").unwrap();
+    assert!(!model.get_date1904());
+}
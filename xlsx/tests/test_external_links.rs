@@ -0,0 +1,150 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+use ironcalc::export::save_xlsx_to_writer;
+use ironcalc::import::load_from_xlsx_bytes;
+use ironcalc_base::types::{ExternalLink, ExternalSheet, ExternalValue};
+use ironcalc_base::Model;
+
+fn read_part(bytes: &[u8], name: &str) -> String {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut text = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut text)
+        .unwrap();
+    text
+}
+
+#[test]
+fn external_links_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    let mut cells = HashMap::new();
+    cells.insert(
+        1,
+        HashMap::from([
+            (1, ExternalValue::Number(5.0)),
+            (2, ExternalValue::Text("Q1 & Q2".to_string())),
+        ]),
+    );
+    cells.insert(2, HashMap::from([(1, ExternalValue::Boolean(true))]));
+    model.workbook.external_links.push(ExternalLink {
+        workbook: "Budget.xlsx".to_string(),
+        target: "file:///data/Budget.xlsx".to_string(),
+        sheets: vec![ExternalSheet {
+            name: "Sheet 1".to_string(),
+            cells,
+        }],
+    });
+    model
+        .set_user_input(0, 1, 1, "='[Budget.xlsx]Sheet 1'!A1*2".to_string())
+        .unwrap();
+    model
+        .set_user_input(0, 2, 1, "='[Budget.xlsx]Sheet 1'!B1".to_string())
+        .unwrap();
+    // Not linked yet, it is added without values
+    model
+        .set_user_input(0, 3, 1, "=[Sales.xlsx]Sheet1!A1".to_string())
+        .unwrap();
+    model
+        .new_defined_name("rate", None, "'[Budget.xlsx]Sheet 1'!$A$1")
+        .unwrap();
+    model.evaluate();
+    assert_eq!(model.get_formatted_cell_value(0, 1, 1).unwrap(), "10");
+
+    let bytes = save_xlsx_to_writer(&model, Cursor::new(Vec::new()))
+        .unwrap()
+        .into_inner();
+
+    // In the file workbooks are referred to by position
+    let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains("<f>&apos;[1]Sheet 1&apos;!A1*2</f>"));
+    assert!(sheet.contains("<f>[2]Sheet1!A1</f>"));
+    let workbook = read_part(&bytes, "xl/workbook.xml");
+    assert!(workbook.contains(
+        "<externalReferences><externalReference r:id=\"rId6\"/><externalReference r:id=\"rId7\"/></externalReferences>"
+    ));
+    assert!(workbook.contains(">&apos;[1]Sheet 1&apos;!$A$1</definedName>"));
+    let rels = read_part(&bytes, "xl/externalLinks/_rels/externalLink1.xml.rels");
+    assert!(rels.contains("Target=\"file:///data/Budget.xlsx\" TargetMode=\"External\""));
+
+    let workbook = load_from_xlsx_bytes(&bytes, "model", "en", "UTC").unwrap();
+    let mut reloaded = Model::from_workbook(workbook, "en").unwrap();
+    assert_eq!(
+        reloaded.get_cell_formula(0, 1, 1).unwrap(),
+        Some("='[Budget.xlsx]Sheet 1'!A1*2".to_string())
+    );
+    assert_eq!(
+        reloaded.get_cell_formula(0, 3, 1).unwrap(),
+        Some("=[Sales.xlsx]Sheet1!A1".to_string())
+    );
+    assert_eq!(
+        reloaded.workbook.defined_names[0].formula,
+        "'[Budget.xlsx]Sheet 1'!$A$1"
+    );
+    assert_eq!(reloaded.workbook.external_links.len(), 2);
+    assert_eq!(
+        reloaded.workbook.external_links[0],
+        model.workbook.external_links[0]
+    );
+
+    reloaded
+        .set_user_input(
+            0,
+            4,
+            1,
+            "=IF('[Budget.xlsx]Sheet 1'!A2, rate, 0)".to_string(),
+        )
+        .unwrap();
+    reloaded.evaluate();
+    assert_eq!(reloaded.get_formatted_cell_value(0, 1, 1).unwrap(), "10");
+    assert_eq!(
+        reloaded.get_formatted_cell_value(0, 2, 1).unwrap(),
+        "Q1 & Q2"
+    );
+    assert_eq!(reloaded.get_formatted_cell_value(0, 3, 1).unwrap(), "#REF!");
+    assert_eq!(reloaded.get_formatted_cell_value(0, 4, 1).unwrap(), "5");
+}
+
+/// Returns a copy of the xlsx file without the part `name`
+fn remove_part(bytes: &[u8], name: &str) -> Vec<u8> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for index in 0..archive.len() {
+        let file = archive.by_index_raw(index).unwrap();
+        if file.name() != name {
+            writer.raw_copy_file(file).unwrap();
+        }
+    }
+    writer.finish().unwrap().into_inner()
+}
+
+#[test]
+fn external_link_without_relationships() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model
+        .set_user_input(0, 1, 1, "=[Budget.xlsx]Sheet1!A1".to_string())
+        .unwrap();
+    model.set_user_input(0, 2, 1, "=1+1".to_string()).unwrap();
+    model.evaluate();
+    let bytes = save_xlsx_to_writer(&model, Cursor::new(Vec::new()))
+        .unwrap()
+        .into_inner();
+    let bytes = remove_part(&bytes, "xl/externalLinks/_rels/externalLink1.xml.rels");
+
+    // The link is skipped, the rest of the workbook is imported
+    let workbook = load_from_xlsx_bytes(&bytes, "model", "en", "UTC").unwrap();
+    assert!(workbook.external_links.is_empty());
+    let mut reloaded = Model::from_workbook(workbook, "en").unwrap();
+    reloaded.evaluate();
+    assert_eq!(
+        reloaded.get_cell_formula(0, 1, 1).unwrap(),
+        Some("=[1]Sheet1!A1".to_string())
+    );
+    assert_eq!(reloaded.get_formatted_cell_value(0, 1, 1).unwrap(), "#REF!");
+    assert_eq!(reloaded.get_formatted_cell_value(0, 2, 1).unwrap(), "2");
+}
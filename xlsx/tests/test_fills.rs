@@ -0,0 +1,83 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::expressions::types::Area;
+use ironcalc_base::types::{Color, GradientType, PatternType};
+use ironcalc_base::UserModel;
+use std::fs;
+
+fn cell(row: i32, column: i32) -> Area {
+    Area {
+        sheet: 0,
+        row,
+        column,
+        width: 1,
+        height: 1,
+    }
+}
+
+#[test]
+fn test_fills_round_trip() {
+    let mut model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+    // A2: pattern fill
+    model
+        .update_range_style(&cell(2, 1), "fill.pattern_type", "darkGrid")
+        .unwrap();
+    model
+        .update_range_style(&cell(2, 1), "fill.fg_color", "#FF0000")
+        .unwrap();
+    model
+        .update_range_style(&cell(2, 1), "fill.bg_color", "#0000FF")
+        .unwrap();
+    // A3: linear gradient
+    model
+        .update_range_style(&cell(3, 1), "fill.gradient_stops", "0:#FFFFFF;1:#4472C4")
+        .unwrap();
+    model
+        .update_range_style(&cell(3, 1), "fill.gradient_degree", "90")
+        .unwrap();
+    // A4: path gradient
+    model
+        .update_range_style(&cell(4, 1), "fill.gradient_type", "path")
+        .unwrap();
+    model
+        .update_range_style(&cell(4, 1), "fill.gradient_rect", "0.5,0.5,0.5,0.5")
+        .unwrap();
+    // A5: plain solid fill
+    model
+        .update_range_style(&cell(5, 1), "fill.color", "#00FF00")
+        .unwrap();
+
+    let temp_file_name = "temp_file_test_fills.xlsx";
+    save_to_xlsx(model.get_model(), temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let fill = reloaded.get_style_for_cell(0, 2, 1).unwrap().fill;
+    assert_eq!(fill.pattern_type, PatternType::DarkGrid);
+    assert_eq!(fill.color, Color::Rgb("#FF0000".to_string()));
+    assert_eq!(fill.bg_color, Color::Rgb("#0000FF".to_string()));
+    assert_eq!(fill.gradient, None);
+
+    let fill = reloaded.get_style_for_cell(0, 3, 1).unwrap().fill;
+    let gradient = fill.gradient.unwrap();
+    assert_eq!(gradient.gradient_type, GradientType::Linear);
+    assert_eq!(gradient.degree, 90.0);
+    assert_eq!(gradient.stops.len(), 2);
+    assert_eq!(gradient.stops[1].position, 1.0);
+    assert_eq!(gradient.stops[1].color, Color::Rgb("#4472C4".to_string()));
+
+    let fill = reloaded.get_style_for_cell(0, 4, 1).unwrap().fill;
+    let gradient = fill.gradient.unwrap();
+    assert_eq!(gradient.gradient_type, GradientType::Path);
+    assert_eq!(gradient.left, 0.5);
+    assert_eq!(gradient.right, 0.5);
+    assert_eq!(gradient.top, 0.5);
+    assert_eq!(gradient.bottom, 0.5);
+
+    let fill = reloaded.get_style_for_cell(0, 5, 1).unwrap().fill;
+    assert_eq!(fill.pattern_type, PatternType::Solid);
+    assert_eq!(fill.color, Color::Rgb("#00FF00".to_string()));
+}
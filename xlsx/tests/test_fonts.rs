@@ -0,0 +1,54 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::types::{FontScheme, Style};
+use ironcalc_base::Model;
+use std::fs;
+
+#[test]
+// Font names, families and schemes must be read from the xlsx instead of being replaced by
+// the default font. example.xlsx has Calibri theme fonts and Tahoma fonts (used by comments)
+// without a <scheme> child.
+fn test_font_names_are_preserved() {
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC", "en").unwrap();
+    let fonts = &model.workbook.styles.fonts;
+
+    let calibri = &fonts[0];
+    assert_eq!(calibri.name, "Calibri");
+    assert_eq!(calibri.family, 2);
+    assert_eq!(calibri.scheme, FontScheme::Minor);
+
+    let tahoma = fonts.iter().find(|f| f.name == "Tahoma").unwrap();
+    assert_eq!(tahoma.scheme, FontScheme::None);
+
+    let temp_file_name = "temp_file_test_font_names.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+    assert_eq!(&reloaded.workbook.styles.fonts, fonts);
+}
+
+#[test]
+fn test_font_charset_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    let mut style = Style::default();
+    style.font.name = "MS Gothic".to_string();
+    style.font.family = 3;
+    style.font.charset = Some(128);
+    style.font.scheme = FontScheme::None;
+    model.set_cell_style(0, 1, 1, &style).unwrap();
+    model.set_user_input(0, 1, 1, "Hello".to_string()).unwrap();
+
+    let temp_file_name = "temp_file_test_font_charset.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let font = reloaded.get_style_for_cell(0, 1, 1).unwrap().font;
+    assert_eq!(font.name, "MS Gothic");
+    assert_eq!(font.family, 3);
+    assert_eq!(font.charset, Some(128));
+    assert_eq!(font.scheme, FontScheme::None);
+}
@@ -0,0 +1,82 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::outline::OutlineGroup;
+use ironcalc_base::{Model, UserModel};
+use std::fs;
+
+#[test]
+fn test_outline_round_trip() {
+    let mut model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+    model.set_user_input(0, 1, 1, "Revenue").unwrap();
+    model.set_user_input(0, 11, 1, "Total").unwrap();
+    // Rows 2 to 10 are empty but grouped
+    model.group_rows(0, 2, 10).unwrap();
+    model.group_rows(0, 4, 6).unwrap();
+    model.collapse_row_group(0, 4, 2).unwrap();
+    model.group_columns(0, 2, 3).unwrap();
+
+    let temp_file_name = "temp_file_test_outline.xlsx";
+    save_to_xlsx(model.get_model(), temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    assert_eq!(
+        reloaded.get_row_outline(0).unwrap(),
+        vec![
+            OutlineGroup {
+                start: 2,
+                end: 10,
+                level: 1,
+                collapsed: false
+            },
+            OutlineGroup {
+                start: 4,
+                end: 6,
+                level: 2,
+                collapsed: true
+            }
+        ]
+    );
+    assert!(reloaded.is_row_hidden(0, 5).unwrap());
+    assert!(!reloaded.is_row_hidden(0, 7).unwrap());
+    assert_eq!(
+        reloaded.get_column_outline(0).unwrap(),
+        vec![OutlineGroup {
+            start: 2,
+            end: 3,
+            level: 1,
+            collapsed: false
+        }]
+    );
+    assert_eq!(
+        reloaded.get_formatted_cell_value(0, 11, 1),
+        Ok("Total".to_string())
+    );
+}
+
+#[test]
+fn test_outline_summary_position() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    for row in 2..=4 {
+        model.set_row_outline_level(0, row, 1).unwrap();
+    }
+    model
+        .workbook
+        .worksheet_mut(0)
+        .unwrap()
+        .outline_properties
+        .summary_below = false;
+
+    let temp_file_name = "temp_file_test_outline_summary.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let outline_properties = &reloaded.workbook.worksheet(0).unwrap().outline_properties;
+    assert!(!outline_properties.summary_below);
+    assert!(outline_properties.summary_right);
+    assert_eq!(reloaded.get_row_outline_level(0, 3), Ok(1));
+}
@@ -0,0 +1,34 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::import::load_from_xlsx;
+
+#[test]
+fn test_table_precedents() {
+    let mut model = load_from_xlsx("tests/example.xlsx", "en", "UTC", "en").unwrap();
+    let sheet = model
+        .workbook
+        .get_worksheet_names()
+        .iter()
+        .position(|name| name == "Table")
+        .unwrap() as u32;
+    model
+        .set_user_input(sheet, 10, 10, "=COUNTA(Table1[Make])".to_string())
+        .unwrap();
+    model.evaluate();
+
+    let precedents = model.get_precedents(sheet, 10, 10, 1).unwrap();
+    assert_eq!(precedents.len(), 1);
+    let reference = &precedents[0];
+    assert_eq!(reference.name, Some("Table1".to_string()));
+    assert_eq!(
+        (
+            reference.row,
+            reference.column,
+            reference.width,
+            reference.height
+        ),
+        (2, 3, 1, 3)
+    );
+    assert!(!reference.dynamic);
+}
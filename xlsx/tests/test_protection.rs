@@ -0,0 +1,43 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::expressions::types::Area;
+use ironcalc_base::types::Protection;
+use ironcalc_base::UserModel;
+use std::fs;
+
+#[test]
+fn test_protection_round_trip() {
+    let mut model = UserModel::new_empty("model", "en", "UTC", "en").unwrap();
+    let range = Area {
+        sheet: 0,
+        row: 1,
+        column: 1,
+        width: 1,
+        height: 1,
+    };
+    model
+        .update_range_style(&range, "protection.locked", "false")
+        .unwrap();
+    model
+        .update_range_style(&range, "protection.hidden", "true")
+        .unwrap();
+    model.set_user_input(0, 2, 1, "=A1+1").unwrap();
+
+    let temp_file_name = "temp_file_test_protection.xlsx";
+    save_to_xlsx(model.get_model(), temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let style = reloaded.get_style_for_cell(0, 1, 1).unwrap();
+    assert_eq!(
+        style.protection,
+        Some(Protection {
+            locked: false,
+            hidden: true
+        })
+    );
+    assert!(reloaded.get_style_for_cell(0, 2, 1).unwrap().is_locked());
+}
@@ -0,0 +1,38 @@
+#![allow(clippy::unwrap_used)]
+#![allow(clippy::panic)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::Model;
+use std::fs;
+
+#[test]
+fn test_r1c1_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model.set_r1c1(true);
+    model
+        .set_user_input(0, 2, 2, "=R[-1]C[-1]*2".to_string())
+        .unwrap();
+    model.evaluate();
+
+    let temp_file_name = "temp_file_test_r1c1.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    assert!(reloaded.get_r1c1());
+    assert_eq!(
+        reloaded.get_localized_cell_content(0, 2, 2).unwrap(),
+        "=R[-1]C[-1]*2"
+    );
+    assert_eq!(
+        reloaded.get_cell_formula(0, 2, 2).unwrap().unwrap(),
+        "=A1*2"
+    );
+}
+
+#[test]
+fn test_a1_is_default() {
+    let model = load_from_xlsx("tests/example.xlsx", "en", "UTC", "en").unwrap();
+    assert!(!model.get_r1c1());
+}
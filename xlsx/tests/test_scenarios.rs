@@ -0,0 +1,82 @@
+#![allow(clippy::unwrap_used)]
+
+use std::io::{Cursor, Read};
+
+use ironcalc::export::save_xlsx_to_writer;
+use ironcalc::import::load_from_xlsx_bytes;
+use ironcalc_base::types::{Scenario, ScenarioInputCell};
+use ironcalc_base::Model;
+
+fn read_part(bytes: &[u8], name: &str) -> String {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+    let mut text = String::new();
+    archive
+        .by_name(name)
+        .unwrap()
+        .read_to_string(&mut text)
+        .unwrap();
+    text
+}
+
+#[test]
+fn scenarios_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model.set_user_input(0, 1, 2, "100".to_string()).unwrap();
+    model.set_user_input(0, 2, 2, "0.1".to_string()).unwrap();
+    model.set_user_input(0, 3, 2, "=B1*B2".to_string()).unwrap();
+    let best = Scenario {
+        name: "Best & brightest".to_string(),
+        comment: "Created by Jane".to_string(),
+        user: "Jane".to_string(),
+        locked: true,
+        hidden: false,
+        input_cells: vec![
+            ScenarioInputCell {
+                row: 1,
+                column: 2,
+                value: "200".to_string(),
+            },
+            ScenarioInputCell {
+                row: 2,
+                column: 2,
+                value: "0.2".to_string(),
+            },
+        ],
+    };
+    let worst = Scenario {
+        name: "Worst".to_string(),
+        comment: String::new(),
+        user: String::new(),
+        locked: false,
+        hidden: true,
+        input_cells: vec![ScenarioInputCell {
+            row: 2,
+            column: 2,
+            value: "<0>".to_string(),
+        }],
+    };
+    model.add_scenario(0, best.clone()).unwrap();
+    model.add_scenario(0, worst.clone()).unwrap();
+    model.evaluate();
+
+    let bytes = save_xlsx_to_writer(&model, Cursor::new(Vec::new()))
+        .unwrap()
+        .into_inner();
+    let sheet = read_part(&bytes, "xl/worksheets/sheet1.xml");
+    assert!(sheet.contains(
+        "<scenarios sqref=\"B1 B2\">\
+         <scenario name=\"Best &amp; brightest\" locked=\"1\" count=\"2\" user=\"Jane\" comment=\"Created by Jane\">\
+         <inputCells r=\"B1\" val=\"200\"/><inputCells r=\"B2\" val=\"0.2\"/></scenario>\
+         <scenario name=\"Worst\" hidden=\"1\" count=\"1\"><inputCells r=\"B2\" val=\"&lt;0&gt;\"/></scenario>\
+         </scenarios>"
+    ));
+    assert!(sheet.find("</sheetData>").unwrap() < sheet.find("<scenarios").unwrap());
+
+    let workbook = load_from_xlsx_bytes(&bytes, "model", "en", "UTC").unwrap();
+    let mut reloaded = Model::from_workbook(workbook, "en").unwrap();
+    assert_eq!(reloaded.get_scenarios(0).unwrap(), vec![best, worst]);
+
+    reloaded.show_scenario(0, "Best & brightest").unwrap();
+    reloaded.evaluate();
+    assert_eq!(reloaded.get_formatted_cell_value(0, 3, 2).unwrap(), "40");
+}
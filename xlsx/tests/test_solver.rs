@@ -0,0 +1,116 @@
+#![allow(clippy::unwrap_used)]
+
+use ironcalc::export::save_to_xlsx;
+use ironcalc::import::load_from_xlsx;
+use ironcalc_base::expressions::types::{Area, CellReferenceIndex};
+use ironcalc_base::solver::{
+    ConstraintOperator, SolverConstraint, SolverGoal, SolverOptions, SolverProblem, SolverStatus,
+};
+use ironcalc_base::Model;
+use std::fs;
+
+#[test]
+fn test_solver_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model
+        .set_user_input(0, 1, 3, "=4*A1+3*B1".to_string())
+        .unwrap();
+    model.set_user_input(0, 2, 3, "=A1+B1".to_string()).unwrap();
+    model.set_user_input(0, 2, 4, "7.5".to_string()).unwrap();
+    model.evaluate();
+    let problem = SolverProblem {
+        objective: CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 3,
+        },
+        goal: SolverGoal::Maximize,
+        variables: vec![Area {
+            sheet: 0,
+            row: 1,
+            column: 1,
+            width: 2,
+            height: 1,
+        }],
+        constraints: vec![
+            SolverConstraint {
+                left: Area {
+                    sheet: 0,
+                    row: 2,
+                    column: 3,
+                    width: 1,
+                    height: 1,
+                },
+                operator: ConstraintOperator::LessOrEqual,
+                right: "Sheet1!$D$2".to_string(),
+            },
+            SolverConstraint {
+                left: Area {
+                    sheet: 0,
+                    row: 1,
+                    column: 1,
+                    width: 2,
+                    height: 1,
+                },
+                operator: ConstraintOperator::Integer,
+                right: "".to_string(),
+            },
+        ],
+        options: SolverOptions::default(),
+    };
+    model.set_solver_problem(0, &problem).unwrap();
+
+    let temp_file_name = "temp_file_test_solver.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let mut reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let reloaded_problem = reloaded.get_solver_problem(0).unwrap().unwrap();
+    assert_eq!(reloaded_problem, problem);
+    let result = reloaded.solve(&reloaded_problem).unwrap();
+    assert_eq!(result.status, SolverStatus::Optimal);
+    assert_eq!(result.values, vec![7.0, 0.0]);
+}
+
+#[test]
+fn test_hidden_names_round_trip() {
+    let mut model = Model::new_empty("model", "en", "UTC", "en").unwrap();
+    model.set_user_input(0, 1, 2, "=A1*2".to_string()).unwrap();
+    model
+        .new_defined_name("solver_note", None, "Sheet1!$A$1")
+        .unwrap();
+    model.evaluate();
+    let problem = SolverProblem {
+        objective: CellReferenceIndex {
+            sheet: 0,
+            row: 1,
+            column: 2,
+        },
+        goal: SolverGoal::Minimize,
+        variables: vec![Area {
+            sheet: 0,
+            row: 1,
+            column: 1,
+            width: 1,
+            height: 1,
+        }],
+        constraints: vec![],
+        options: SolverOptions::default(),
+    };
+    model.set_solver_problem(0, &problem).unwrap();
+
+    let temp_file_name = "temp_file_test_hidden_names.xlsx";
+    save_to_xlsx(&model, temp_file_name).unwrap();
+    let reloaded = load_from_xlsx(temp_file_name, "en", "UTC", "en").unwrap();
+    fs::remove_file(temp_file_name).unwrap();
+
+    let defined_names = &reloaded.workbook.defined_names;
+    assert!(defined_names.len() > 1);
+    for defined_name in defined_names {
+        // Only the names set up by the Solver are hidden
+        assert_eq!(defined_name.hidden, defined_name.sheet_id.is_some());
+    }
+    assert!(defined_names
+        .iter()
+        .any(|defined_name| defined_name.name == "solver_note" && !defined_name.hidden));
+}